@@ -1,20 +1,22 @@
 //! PKIBody type
 
 use der::asn1::Null;
-use der::Choice;
+use der::{
+    Choice, DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, SliceReader, Tag, Writer,
+};
 
-use crmf::request::CertReqMessages;
+use crmf::request::{CertReqMessages, CertReqMsg};
 use x509_cert::request::CertReq;
 
 use crate::ann::{CaKeyUpdAnnContent, CertAnnContent, CrlAnnContent, RevAnnContent};
 use crate::certified_key_pair::KeyRecRepContent;
 use crate::gen::{GenMsgContent, GenRepContent};
-use crate::message::PkiMessages;
-use crate::poll::PollRepContent;
+use crate::message::{PkiMessage, PkiMessages};
+use crate::poll::{PollRep, PollRepContent, PollReq, PollReqContent, PollReqContentId};
 use crate::pop::{PopoDecKeyChallContent, PopoDecKeyRespContent};
 use crate::response::CertRepMessage;
-use crate::rev::{RevRepContent, RevReqContent};
-use crate::status::{CertConfirmContent, ErrorMsgContent};
+use crate::rev::{RevDetails, RevRepContent, RevReqContent};
+use crate::status::{CertConfirmContent, CertStatus, ErrorMsgContent};
 
 /// The `PKIBody` type is defined in [RFC 4210 Section 5.1.2]
 ///
@@ -94,10 +96,8 @@ pub enum PkiBody<'a> {
     CrlAnn(CrlAnnContent),
     #[asn1(context_specific = "19", tag_mode = "EXPLICIT", constructed = "true")]
     PkiConf(PkiConfirmContent),
-
-    // TODO address recursion error
-    // #[asn1(context_specific = "20", tag_mode = "EXPLICIT", constructed = "true")]
-    // Nested(NestedMessageContent<'a>),
+    #[asn1(context_specific = "20", tag_mode = "EXPLICIT", constructed = "true")]
+    Nested(NestedMessageContent<'a>),
     #[asn1(context_specific = "21", tag_mode = "EXPLICIT", constructed = "true")]
     GenM(GenMsgContent),
     #[asn1(context_specific = "22", tag_mode = "EXPLICIT", constructed = "true")]
@@ -107,11 +107,65 @@ pub enum PkiBody<'a> {
     #[asn1(context_specific = "24", tag_mode = "EXPLICIT", constructed = "true")]
     CertConf(CertConfirmContent<'a>),
     #[asn1(context_specific = "25", tag_mode = "EXPLICIT", constructed = "true")]
-    PollReq(PollRepContent<'a>),
+    PollReq(PollReqContent),
     #[asn1(context_specific = "26", tag_mode = "EXPLICIT", constructed = "true")]
     PollRep(PollRepContent<'a>),
 }
 
+impl<'a> PkiBody<'a> {
+    /// Returns an initialization request (`ir`) body for `requests`
+    pub fn ir(requests: impl IntoIterator<Item = CertReqMsg>) -> Self {
+        Self::Ir(requests.into_iter().collect())
+    }
+
+    /// Returns a certification request (`cr`) body for `requests`
+    pub fn cr(requests: impl IntoIterator<Item = CertReqMsg>) -> Self {
+        Self::Cr(requests.into_iter().collect())
+    }
+
+    /// Returns a key update request (`kur`) body for `requests`
+    pub fn kur(requests: impl IntoIterator<Item = CertReqMsg>) -> Self {
+        Self::KUr(requests.into_iter().collect())
+    }
+
+    /// Returns a revocation request (`rr`) body for `details`
+    pub fn rr(details: impl IntoIterator<Item = RevDetails>) -> Self {
+        Self::Rr(details.into_iter().collect())
+    }
+
+    /// Returns a certificate confirmation (`certConf`) body for `statuses`
+    pub fn cert_conf(statuses: impl IntoIterator<Item = CertStatus<'a>>) -> Self {
+        Self::CertConf(statuses.into_iter().collect())
+    }
+
+    /// Returns a polling request (`pollReq`) body for `cert_req_ids`
+    pub fn poll_req(cert_req_ids: impl IntoIterator<Item = PollReqContentId>) -> Self {
+        Self::PollReq(cert_req_ids.into_iter().map(PollReq::new).collect())
+    }
+
+    /// Returns a polling response (`pollRep`) body for `responses`
+    pub fn poll_rep(responses: impl IntoIterator<Item = PollRep<'a>>) -> Self {
+        Self::PollRep(responses.into_iter().collect())
+    }
+
+    /// Returns a nested message body wrapping `messages`, e.g. for an RA forwarding requests
+    pub fn nested(messages: impl IntoIterator<Item = PkiMessage<'a>>) -> Self {
+        Self::Nested(NestedMessageContent(messages.into_iter().collect()))
+    }
+
+    /// Returns the certificate request messages of an `ir`, `cr`, `kur`, `krr` or `ccr` body
+    pub fn cert_req_messages(&self) -> Option<&CertReqMessages> {
+        match self {
+            Self::Ir(requests)
+            | Self::Cr(requests)
+            | Self::KUr(requests)
+            | Self::Krr(requests)
+            | Self::Ccr(requests) => Some(requests),
+            _ => None,
+        }
+    }
+}
+
 /// The `PKIConfirmContent` type is defined in [RFC 4210 Section 5.3.17]
 ///
 /// ```text
@@ -121,11 +175,48 @@ pub enum PkiBody<'a> {
 /// [RFC 4210 Section 5.3.17]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.4
 pub type PkiConfirmContent = Null;
 
-/// The `PKIConfirmContent` type is defined in [RFC 4210 Section 5.3.17]
+/// The `NestedMessageContent` type is defined in [RFC 4210 Section 5.1.3.4]
 ///
 /// ```text
 ///  NestedMessageContent ::= PKIMessages
 /// ```
 ///
+/// The contained messages are decoded using a separate reader, as decoding the recursive
+/// `PKIMessage` type with the outer one would instantiate an unbounded chain of nested readers.
+/// That reader inherits the nesting depth of the outer one, so deeply nested messages are
+/// rejected with [`der::ErrorKind::DepthExceeded`].
+///
 /// [RFC 4210 Section 5.1.3.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.4
-pub type NestedMessageContent<'a> = PkiMessages<'a>;
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NestedMessageContent<'a>(pub PkiMessages<'a>);
+
+impl<'a> From<PkiMessages<'a>> for NestedMessageContent<'a> {
+    fn from(messages: PkiMessages<'a>) -> Self {
+        Self(messages)
+    }
+}
+
+impl<'a> DecodeValue<'a> for NestedMessageContent<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let bytes = reader.read_slice(header.length)?;
+        let mut nested = SliceReader::new_nested(bytes, reader)?;
+        let messages = PkiMessages::decode_value(&mut nested, header)?;
+        nested.finish(Self(messages))
+    }
+}
+
+impl EncodeValue for NestedMessageContent<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.0.encode_value(writer)
+    }
+}
+
+impl FixedTag for NestedMessageContent<'_> {
+    const TAG: Tag = Tag::Sequence;
+}
@@ -0,0 +1,224 @@
+//! PKIMessage builder
+
+use alloc::vec::Vec;
+use der::asn1::{GeneralizedTime, OctetString, Utf8StringRef};
+use rand_core::CryptoRngCore;
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::ext::pkix::name::GeneralName;
+
+use crate::body::PkiBody;
+use crate::gen::InfoTypeAndValue;
+use crate::header::{CmpCertificate, PkiFreeText, PkiHeader, Pvno};
+use crate::message::PkiMessage;
+
+pub use crate::header::NONCE_LENGTH;
+
+/// PKIMessage builder
+///
+/// The builder assembles the [`PkiHeader`] of a [`PkiMessage`] as described in
+/// [RFC 4210 Section 5.1.1]. The protocol version defaults to [`Pvno::Cmp2000`].
+///
+/// ```
+/// use cmpv2::body::PkiBody;
+/// use cmpv2::builder::PkiMessageBuilder;
+/// use der::asn1::Null;
+/// use x509_cert::ext::pkix::name::GeneralName;
+///
+/// let sender = GeneralName::DirectoryName("CN=MyName".parse().unwrap());
+/// let recipient = GeneralName::DirectoryName("CN=CMPserver".parse().unwrap());
+///
+/// let mut rng = rand::thread_rng();
+/// let msg = PkiMessageBuilder::new(sender, recipient)
+///     .with_implicit_confirm()
+///     .build_with_rng(PkiBody::PkiConf(Null), &mut rng)
+///     .unwrap();
+/// assert_eq!(msg.header.trans_id.unwrap().as_bytes().len(), 16);
+/// assert_eq!(msg.header.sender_nonce.unwrap().as_bytes().len(), 16);
+/// ```
+///
+/// [RFC 4210 Section 5.1.1]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1
+#[derive(Clone, Debug)]
+pub struct PkiMessageBuilder<'a> {
+    header: PkiHeader<'a>,
+    extra_certs: Option<Vec<CmpCertificate>>,
+}
+
+impl<'a> PkiMessageBuilder<'a> {
+    /// Returns a `PkiMessageBuilder` for a message from `sender` to `recipient`
+    pub fn new(sender: GeneralName, recipient: GeneralName) -> Self {
+        Self {
+            header: PkiHeader {
+                pvno: Pvno::Cmp2000,
+                sender,
+                recipient,
+                message_time: None,
+                protection_alg: None,
+                sender_kid: None,
+                recip_kid: None,
+                trans_id: None,
+                sender_nonce: None,
+                recip_nonce: None,
+                free_text: None,
+                general_info: None,
+            },
+            extra_certs: None,
+        }
+    }
+
+    /// Returns a `PkiMessageBuilder` for a message answering `request`.
+    ///
+    /// Sender and recipient are swapped, the transactionID is retained and the senderNonce of
+    /// `request` becomes the recipNonce of the response, as required by
+    /// [RFC 4210 Section 5.1.1].
+    ///
+    /// [RFC 4210 Section 5.1.1]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1
+    pub fn response_to(request: &PkiHeader<'_>) -> Self {
+        let mut builder =
+            Self::new(request.recipient.clone(), request.sender.clone()).with_pvno(request.pvno);
+        builder.header.trans_id = request.trans_id.clone();
+        builder.header.recip_nonce = request.sender_nonce.clone();
+        builder
+    }
+
+    /// Sets the protocol version
+    pub fn with_pvno(mut self, pvno: Pvno) -> Self {
+        self.header.pvno = pvno;
+        self
+    }
+
+    /// Sets the messageTime
+    pub fn with_message_time(mut self, message_time: GeneralizedTime) -> Self {
+        self.header.message_time = Some(message_time);
+        self
+    }
+
+    /// Sets the messageTime to the current time. Errors when the current time can't be
+    /// represented as a `GeneralizedTime`.
+    #[cfg(feature = "std")]
+    pub fn with_current_time(self) -> der::Result<Self> {
+        let now = GeneralizedTime::from_system_time(std::time::SystemTime::now())?;
+        Ok(self.with_message_time(now))
+    }
+
+    /// Sets the protectionAlg
+    pub fn with_protection_alg(mut self, protection_alg: AlgorithmIdentifierOwned) -> Self {
+        self.header.protection_alg = Some(protection_alg);
+        self
+    }
+
+    /// Sets the senderKID
+    pub fn with_sender_kid(mut self, sender_kid: OctetString) -> Self {
+        self.header.sender_kid = Some(sender_kid);
+        self
+    }
+
+    /// Sets the recipKID
+    pub fn with_recip_kid(mut self, recip_kid: OctetString) -> Self {
+        self.header.recip_kid = Some(recip_kid);
+        self
+    }
+
+    /// Sets the transactionID
+    pub fn with_transaction_id(mut self, trans_id: OctetString) -> Self {
+        self.header.trans_id = Some(trans_id);
+        self
+    }
+
+    /// Sets the senderNonce
+    pub fn with_sender_nonce(mut self, sender_nonce: OctetString) -> Self {
+        self.header.sender_nonce = Some(sender_nonce);
+        self
+    }
+
+    /// Sets the recipNonce
+    pub fn with_recip_nonce(mut self, recip_nonce: OctetString) -> Self {
+        self.header.recip_nonce = Some(recip_nonce);
+        self
+    }
+
+    /// Sets the freeText
+    pub fn with_free_text(mut self, free_text: PkiFreeText<'a>) -> Self {
+        self.header.free_text = Some(free_text);
+        self
+    }
+
+    /// Adds an entry to the generalInfo
+    pub fn with_general_info(mut self, info: InfoTypeAndValue) -> Self {
+        self.header
+            .general_info
+            .get_or_insert_with(Vec::new)
+            .push(info);
+        self
+    }
+
+    /// Adds the implicitConfirm entry to the generalInfo, requesting the CA to omit the
+    /// certificate confirmation as described in [RFC 4210 Section 5.1.1.1].
+    ///
+    /// [RFC 4210 Section 5.1.1.1]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1.1
+    pub fn with_implicit_confirm(self) -> Self {
+        self.with_general_info(InfoTypeAndValue::implicit_confirm())
+    }
+
+    /// Adds the confirmWaitTime entry to the generalInfo, stating until when the CA waits for
+    /// the certificate confirmation as described in [RFC 4210 Section 5.1.1.2].
+    ///
+    /// [RFC 4210 Section 5.1.1.2]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1.2
+    pub fn with_confirm_wait_time(self, time: GeneralizedTime) -> der::Result<Self> {
+        Ok(self.with_general_info(InfoTypeAndValue::confirm_wait_time(time)?))
+    }
+
+    /// Adds the certProfile entry to the generalInfo, naming the requested certificate
+    /// profiles as described in [RFC 9480].
+    ///
+    /// [RFC 9480]: https://datatracker.ietf.org/doc/html/rfc9480
+    pub fn with_cert_profile<'p>(
+        self,
+        profiles: impl IntoIterator<Item = Utf8StringRef<'p>>,
+    ) -> der::Result<Self> {
+        Ok(self.with_general_info(InfoTypeAndValue::cert_profile(profiles)?))
+    }
+
+    /// Adds a certificate to the extraCerts of the message
+    pub fn with_extra_cert(mut self, cert: CmpCertificate) -> Self {
+        self.extra_certs.get_or_insert_with(Vec::new).push(cert);
+        self
+    }
+
+    /// Consumes the builder and returns an unprotected [`PkiMessage`] with the given `body`.
+    ///
+    /// The header is used as is, see [`PkiMessageBuilder::build_with_rng`] to generate the
+    /// transactionID and senderNonce.
+    pub fn build(self, body: PkiBody<'a>) -> PkiMessage<'a> {
+        PkiMessage {
+            header: self.header,
+            body,
+            protection: None,
+            extra_certs: self.extra_certs,
+        }
+    }
+
+    /// Consumes the builder and returns an unprotected [`PkiMessage`] with the given `body`.
+    ///
+    /// A fresh senderNonce of [`NONCE_LENGTH`] bytes is generated with `rng`, unless one was
+    /// set explicitly. Likewise, a transactionID is generated unless one was set, which starts
+    /// a new transaction.
+    pub fn build_with_rng(
+        mut self,
+        body: PkiBody<'a>,
+        rng: &mut impl CryptoRngCore,
+    ) -> der::Result<PkiMessage<'a>> {
+        if self.header.trans_id.is_none() {
+            self.header.trans_id = Some(random_octets(rng)?);
+        }
+        if self.header.sender_nonce.is_none() {
+            self.header.sender_nonce = Some(random_octets(rng)?);
+        }
+        Ok(self.build(body))
+    }
+}
+
+fn random_octets(rng: &mut impl CryptoRngCore) -> der::Result<OctetString> {
+    let mut bytes = alloc::vec![0; NONCE_LENGTH];
+    rng.fill_bytes(&mut bytes);
+    OctetString::new(bytes)
+}
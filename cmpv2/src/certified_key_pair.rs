@@ -3,21 +3,27 @@
 use alloc::{boxed::Box, vec::Vec};
 use der::{Choice, Sequence};
 
-use crmf::controls::{EncryptedValue, PkiPublicationInfo};
+use crmf::controls::{EncryptedKey, PkiPublicationInfo};
 
 use crate::header::CmpCertificate;
 use crate::status::PkiStatusInfo;
 
-/// The `CertifiedKeyPair` type is defined in [RFC 4210 Section 5.3.4]
+/// The `CertifiedKeyPair` type is defined in [RFC 4210 Section 5.3.4] and updated in [RFC 9480].
 ///
 /// ```text
 ///  CertifiedKeyPair ::= SEQUENCE {
 ///      certOrEncCert       CertOrEncCert,
-///      privateKey      [0] EncryptedValue      OPTIONAL,
+///      privateKey      [0] EncryptedKey        OPTIONAL,
+///      -- See [RFC4211] for comments on encoding.
 ///      publicationInfo [1] PKIPublicationInfo  OPTIONAL }
 /// ```
 ///
+/// The EncryptedKey CHOICE decodes both the deprecated EncryptedValue of [RFC 4210] peers and
+/// the EnvelopedData of [RFC 9480] peers.
+///
+/// [RFC 4210]: https://www.rfc-editor.org/rfc/rfc4210
 /// [RFC 4210 Section 5.3.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.4
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct CertifiedKeyPair {
@@ -28,7 +34,7 @@ pub struct CertifiedKeyPair {
         constructed = "true",
         optional = "true"
     )]
-    pub priv_key: Option<EncryptedValue>,
+    pub priv_key: Option<EncryptedKey>,
     #[asn1(
         context_specific = "1",
         tag_mode = "EXPLICIT",
@@ -38,15 +44,16 @@ pub struct CertifiedKeyPair {
     pub publication_info: Option<PkiPublicationInfo>,
 }
 
-/// The `CertOrEncCert` type is defined in [RFC 4210 Section 5.3.4]
+/// The `CertOrEncCert` type is defined in [RFC 4210 Section 5.3.4] and updated in [RFC 9480].
 ///
 /// ```text
 ///  CertOrEncCert ::= CHOICE {
 ///      certificate     [0] CMPCertificate,
-///      encryptedCert   [1] EncryptedValue }
+///      encryptedCert   [1] EncryptedKey }
 /// ```
 ///
 /// [RFC 4210 Section 5.3.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.4
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum CertOrEncCert {
@@ -54,7 +61,7 @@ pub enum CertOrEncCert {
     Certificate(Box<CmpCertificate>),
 
     #[asn1(context_specific = "1", tag_mode = "EXPLICIT", constructed = "true")]
-    EncryptedCert(Box<EncryptedValue>),
+    EncryptedCert(EncryptedKey),
 }
 
 /// The `KeyRecRepContent` type is defined in [RFC 4210 Section 5.3.8]
@@ -1,7 +1,48 @@
 //! General purpose message-related types
 
-use der::{Sequence, ValueOrd};
+use alloc::vec::Vec;
+use der::asn1::{Any, GeneralizedTime, Null, ObjectIdentifier, Utf8StringRef};
+// The OID database is always enabled by x509-cert
+use der::oid::db::rfc5912::{ID_IT_CONFIRM_WAIT_TIME, ID_IT_IMPLICIT_CONFIRM};
+use der::{Choice, DecodeValue, EncodeValue, Sequence, Tag, Tagged, ValueOrd};
 use x509_cert::attr::{AttributeType, AttributeValue};
+use x509_cert::crl::CertificateList;
+use x509_cert::ext::pkix::name::{DistributionPointName, GeneralNames};
+use x509_cert::time::Time;
+
+use crate::header::CmpCertificate;
+
+/// The `id-it-caCerts` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_CA_CERTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.17");
+
+/// The `id-it-rootCaCert` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_ROOT_CA_CERT: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.18");
+
+/// The `id-it-rootCaKeyUpdate` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_ROOT_CA_KEY_UPDATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.19");
+
+/// The `id-it-certProfile` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_CERT_PROFILE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.21");
+
+/// The `id-it-crlStatusList` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_CRL_STATUS_LIST: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.22");
+
+/// The `id-it-crls` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_IT_CRLS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.23");
 
 /// The `InfoTypeAndValue` type is defined in [RFC 4210 Section 5.3.19]
 ///
@@ -21,6 +62,201 @@ pub struct InfoTypeAndValue {
     pub value: Option<AttributeValue>,
 }
 
+impl InfoTypeAndValue {
+    /// Returns an `InfoTypeAndValue` of type `oid` carrying `value`
+    pub fn new<T>(oid: ObjectIdentifier, value: &T) -> der::Result<Self>
+    where
+        T: Tagged + EncodeValue,
+    {
+        Ok(Self {
+            oid,
+            value: Some(Any::encode_from(value)?),
+        })
+    }
+
+    /// Returns the `implicitConfirm` entry, which requests the CA to omit the certificate
+    /// confirmation
+    pub fn implicit_confirm() -> Self {
+        Self {
+            oid: ID_IT_IMPLICIT_CONFIRM,
+            value: Some(Any::from(Null)),
+        }
+    }
+
+    /// Returns the `confirmWaitTime` entry, stating until when the CA waits for the certificate
+    /// confirmation
+    pub fn confirm_wait_time(time: GeneralizedTime) -> der::Result<Self> {
+        Self::new(ID_IT_CONFIRM_WAIT_TIME, &time)
+    }
+
+    /// Returns the `certProfile` entry, naming the certificate profiles requested for the
+    /// certificate requests of the message in the same order. Errors if `profiles` is empty.
+    pub fn cert_profile<'a>(
+        profiles: impl IntoIterator<Item = Utf8StringRef<'a>>,
+    ) -> der::Result<Self> {
+        let profiles: CertProfileValue<'a> = profiles.into_iter().collect();
+        if profiles.is_empty() {
+            return Err(Tag::Sequence.value_error());
+        }
+        Self::new(ID_IT_CERT_PROFILE, &profiles)
+    }
+
+    /// Returns the `caCerts` entry of a `genm`, which requests the current CA certificates
+    pub fn ca_certs_request() -> Self {
+        Self {
+            oid: ID_IT_CA_CERTS,
+            value: None,
+        }
+    }
+
+    /// Returns the `caCerts` entry of a `genp`, which carries the current CA certificates
+    pub fn ca_certs(certs: &CaCertsValue) -> der::Result<Self> {
+        Self::new(ID_IT_CA_CERTS, certs)
+    }
+
+    /// Returns the `rootCaCert` entry of a `genm`, which requests an update of the root CA
+    /// certificate `old_root`, if known
+    pub fn root_ca_cert(old_root: Option<&CmpCertificate>) -> der::Result<Self> {
+        match old_root {
+            Some(cert) => Self::new(ID_IT_ROOT_CA_CERT, cert),
+            None => Ok(Self {
+                oid: ID_IT_ROOT_CA_CERT,
+                value: None,
+            }),
+        }
+    }
+
+    /// Returns the `rootCaKeyUpdate` entry of a `genp`, which carries the root CA key `update`,
+    /// if any
+    pub fn root_ca_key_update(update: Option<&RootCaKeyUpdateContent>) -> der::Result<Self> {
+        match update {
+            Some(update) => Self::new(ID_IT_ROOT_CA_KEY_UPDATE, update),
+            None => Ok(Self {
+                oid: ID_IT_ROOT_CA_KEY_UPDATE,
+                value: None,
+            }),
+        }
+    }
+
+    /// Returns the `crlStatusList` entry of a `genm`, which requests the CRLs of the sources in
+    /// `statuses` that are newer than the given thisUpdate. Errors if `statuses` is empty.
+    pub fn crl_status_list(statuses: &CrlStatusListValue) -> der::Result<Self> {
+        if statuses.is_empty() {
+            return Err(Tag::Sequence.value_error());
+        }
+        Self::new(ID_IT_CRL_STATUS_LIST, statuses)
+    }
+
+    /// Returns the `crls` entry of a `genp`, which carries the requested `crls`, if any are
+    /// newer
+    pub fn crls(crls: Option<&CrlsValue>) -> der::Result<Self> {
+        match crls {
+            Some(crls) => Self::new(ID_IT_CRLS, crls),
+            None => Ok(Self {
+                oid: ID_IT_CRLS,
+                value: None,
+            }),
+        }
+    }
+
+    /// Returns whether this is the `implicitConfirm` entry
+    pub fn is_implicit_confirm(&self) -> bool {
+        self.oid == ID_IT_IMPLICIT_CONFIRM
+    }
+
+    /// Returns the value of a `confirmWaitTime` entry, or `None` for other types
+    pub fn to_confirm_wait_time(&self) -> der::Result<Option<GeneralizedTime>> {
+        self.value_if(ID_IT_CONFIRM_WAIT_TIME)
+    }
+
+    /// Returns the value of a `certProfile` entry, or `None` for other types. Errors if the
+    /// list is empty.
+    pub fn to_cert_profile(&self) -> der::Result<Option<CertProfileValue<'_>>> {
+        let profiles: Option<CertProfileValue<'_>> = self.value_if(ID_IT_CERT_PROFILE)?;
+        match profiles {
+            Some(profiles) if profiles.is_empty() => Err(Tag::Sequence.value_error()),
+            profiles => Ok(profiles),
+        }
+    }
+
+    /// Returns the value of a `caCerts` entry, or `None` for other types. Errors if the value
+    /// is absent, as in a request.
+    pub fn to_ca_certs(&self) -> der::Result<Option<CaCertsValue>> {
+        self.value_if(ID_IT_CA_CERTS)
+    }
+
+    /// Returns the value of a `rootCaCert` entry, or `None` for other types or if the value
+    /// is absent
+    pub fn to_root_ca_cert(&self) -> der::Result<Option<CmpCertificate>> {
+        self.optional_value_if(ID_IT_ROOT_CA_CERT)
+    }
+
+    /// Returns the value of a `rootCaKeyUpdate` entry, or `None` for other types or if the
+    /// value is absent
+    pub fn to_root_ca_key_update(&self) -> der::Result<Option<RootCaKeyUpdateContent>> {
+        self.optional_value_if(ID_IT_ROOT_CA_KEY_UPDATE)
+    }
+
+    /// Returns the value of a `crlStatusList` entry, or `None` for other types. Errors if the
+    /// list is empty.
+    pub fn to_crl_status_list(&self) -> der::Result<Option<CrlStatusListValue>> {
+        let statuses: Option<CrlStatusListValue> = self.value_if(ID_IT_CRL_STATUS_LIST)?;
+        match statuses {
+            Some(statuses) if statuses.is_empty() => Err(Tag::Sequence.value_error()),
+            statuses => Ok(statuses),
+        }
+    }
+
+    /// Returns the value of a `crls` entry, or `None` for other types or if the value is
+    /// absent
+    pub fn to_crls(&self) -> der::Result<Option<CrlsValue>> {
+        self.optional_value_if(ID_IT_CRLS)
+    }
+
+    /// Decodes the value as `T` if this entry is of type `oid`, whose value is mandatory
+    fn value_if<'a, T>(&'a self, oid: ObjectIdentifier) -> der::Result<Option<T>>
+    where
+        T: Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        if self.oid != oid {
+            return Ok(None);
+        }
+        match &self.value {
+            Some(value) => value.decode_as().map(Some),
+            None => Err(Tag::Sequence.value_error()),
+        }
+    }
+
+    /// Decodes the value as `T` if this entry is of type `oid`, whose value is optional
+    fn optional_value_if<'a, T>(&'a self, oid: ObjectIdentifier) -> der::Result<Option<T>>
+    where
+        T: Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        match &self.value {
+            Some(value) if self.oid == oid => value.decode_as().map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The `CertProfileValue` type is defined in [RFC 9480].
+///
+/// ```text
+///  CertProfileValue ::= SEQUENCE SIZE (1..MAX) OF UTF8String
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type CertProfileValue<'a> = Vec<Utf8StringRef<'a>>;
+
+/// The `CaCertsValue` type is defined in [RFC 9480].
+///
+/// ```text
+///  CaCertsValue ::= SEQUENCE SIZE (1..MAX) OF CMPCertificate
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type CaCertsValue = Vec<CmpCertificate>;
+
 /// The `GenMsgContent` type is defined in [RFC 4210 Section 5.3.19]
 ///
 /// ```text
@@ -28,7 +264,7 @@ pub struct InfoTypeAndValue {
 /// ```
 ///
 /// [RFC 4210 Section 5.3.19]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19
-pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenMsgContent = Vec<InfoTypeAndValue>;
 
 /// The `GenRepContent` type is defined in [RFC 4210 Section 5.3.20]
 ///
@@ -37,4 +273,87 @@ pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
 /// ```
 ///
 /// [RFC 4210 Section 5.3.20]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.20
-pub type GenRepContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenRepContent = Vec<InfoTypeAndValue>;
+
+/// The `RootCaKeyUpdateContent` type is defined in [RFC 9480].
+///
+/// ```text
+///  RootCaKeyUpdateContent ::= SEQUENCE {
+///      newWithNew              CMPCertificate,
+///      newWithOld          [0] CMPCertificate OPTIONAL,
+///      oldWithNew          [1] CMPCertificate OPTIONAL
+///  }
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct RootCaKeyUpdateContent {
+    pub new_with_new: CmpCertificate,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub new_with_old: Option<CmpCertificate>,
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub old_with_new: Option<CmpCertificate>,
+}
+
+/// The `CRLSource` type is defined in [RFC 9480].
+///
+/// ```text
+///  CRLSource ::= CHOICE {
+///      dpn          [0] DistributionPointName,
+///      issuer       [1] GeneralNames }
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[allow(missing_docs)]
+pub enum CrlSource {
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", constructed = "true")]
+    Dpn(DistributionPointName),
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", constructed = "true")]
+    Issuer(GeneralNames),
+}
+
+/// The `CRLStatus` type is defined in [RFC 9480].
+///
+/// ```text
+///  CRLStatus ::= SEQUENCE {
+///      source       CRLSource,
+///      thisUpdate   Time OPTIONAL }
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CrlStatus {
+    pub source: CrlSource,
+    pub this_update: Option<Time>,
+}
+
+/// The `CRLStatusListValue` type is defined in [RFC 9480].
+///
+/// ```text
+///  CRLStatusListValue ::= SEQUENCE SIZE (1..MAX) OF CRLStatus
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type CrlStatusListValue = Vec<CrlStatus>;
+
+/// The `CRLsValue` type is defined in [RFC 9480].
+///
+/// ```text
+///  CRLsValue ::= SEQUENCE SIZE (1..MAX) OF CertificateList
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type CrlsValue = Vec<CertificateList>;
@@ -8,7 +8,15 @@ use spki::AlgorithmIdentifierOwned;
 use x509_cert::ext::pkix::name::GeneralName;
 use x509_cert::Certificate;
 
-use crate::gen::InfoTypeAndValue;
+use crate::gen::{CertProfileValue, InfoTypeAndValue};
+
+/// Length in bytes of the transactionID and senderNonce values, as generated by the builder
+/// and required by the Lightweight CMP Profile.
+///
+/// [RFC 4210 Section 5.1.1] requires 128 bits of (pseudo-) random data for both.
+///
+/// [RFC 4210 Section 5.1.1]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1
+pub const NONCE_LENGTH: usize = 16;
 
 /// The `PKIHeader` type is defined in [RFC 4210 Section 5.1.1].
 ///
@@ -121,14 +129,41 @@ pub struct PkiHeader<'a> {
     pub general_info: Option<Vec<InfoTypeAndValue>>,
 }
 
+impl PkiHeader<'_> {
+    /// Returns whether the generalInfo contains the `implicitConfirm` entry
+    pub fn implicit_confirm(&self) -> bool {
+        self.general_info_entries()
+            .any(InfoTypeAndValue::is_implicit_confirm)
+    }
+
+    /// Returns the value of the `confirmWaitTime` entry of the generalInfo, if any
+    pub fn confirm_wait_time(&self) -> der::Result<Option<GeneralizedTime>> {
+        self.general_info_entries()
+            .find_map(|info| info.to_confirm_wait_time().transpose())
+            .transpose()
+    }
+
+    /// Returns the value of the `certProfile` entry of the generalInfo, if any
+    pub fn cert_profile(&self) -> der::Result<Option<CertProfileValue<'_>>> {
+        self.general_info_entries()
+            .find_map(|info| info.to_cert_profile().transpose())
+            .transpose()
+    }
+
+    fn general_info_entries(&self) -> impl Iterator<Item = &InfoTypeAndValue> {
+        self.general_info.iter().flatten()
+    }
+}
+
 /// The `PKIHeader` type defined in [RFC 4210 Section 5.1.1] features an inline INTEGER definition
-/// that is implemented as the Pvno enum.
+/// that is implemented as the Pvno enum. The `cmp2021` version is added by [RFC 9480].
 ///
 /// ```text
-///     pvno                INTEGER     { cmp1999(1), cmp2000(2) },
+///     pvno                INTEGER     { cmp1999(1), cmp2000(2), cmp2021(3) },
 /// ```
 ///
 /// [RFC 4210 Section 5.1.1]: https://datatracker.ietf.org/doc/html/rfc4210#section-5.1.1
+/// [RFC 9480]: https://datatracker.ietf.org/doc/html/rfc9480
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Enumerated, Ord, PartialOrd)]
 #[asn1(type = "INTEGER")]
 #[repr(u8)]
@@ -136,6 +171,7 @@ pub struct PkiHeader<'a> {
 pub enum Pvno {
     Cmp1999 = 1,
     Cmp2000 = 2,
+    Cmp2021 = 3,
 }
 
 /// The `PKIFreeText` type is defined in [RFC 4210 Section 5.1.1]
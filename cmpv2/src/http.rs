@@ -0,0 +1,241 @@
+//! HTTP transfer of PKIMessages as described in [RFC 6712].
+//!
+//! A PKIMessage is sent as the DER-encoded body of an HTTP POST request with the content type
+//! `application/pkixcmp`, and the answering PKIMessage is returned as the body of the HTTP
+//! response. The HTTP exchange itself is left to an implementation of [`HttpClient`] or
+//! [`AsyncHttpClient`], which [`HttpTransport`] uses to send requests and validate responses.
+//!
+//! [RFC 6712]: https://www.rfc-editor.org/rfc/rfc6712
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::future::Future;
+use der::{Decode, Encode};
+
+use crate::body::PkiBody;
+use crate::message::PkiMessage;
+use crate::status::{PkiFailureInfo, PkiStatus};
+
+/// Media type of DER-encoded PKIMessages
+pub const CONTENT_TYPE: &str = "application/pkixcmp";
+
+/// HTTP status code of successful responses
+pub const STATUS_OK: u16 = 200;
+
+/// Result type
+pub type Result<T, E> = core::result::Result<T, Error<E>>;
+
+/// Error type, where `E` is the error type of the [`HttpClient`] or [`AsyncHttpClient`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error<E> {
+    /// ASN.1 DER-related errors
+    Asn1(der::Error),
+
+    /// Errors of the HTTP client
+    Http(E),
+
+    /// The HTTP response has a status code other than 200 (OK)
+    Status(u16),
+
+    /// The HTTP response lacks the `application/pkixcmp` content type
+    ContentType(Option<String>),
+
+    /// The transactionID of the response doesn't match the one of the request
+    TransactionId,
+
+    /// The recipNonce of the response doesn't match the senderNonce of the request
+    RecipNonce,
+
+    /// The response is an error message (`errorMsg`)
+    ErrorMessage {
+        /// Status of the error message
+        status: PkiStatus,
+        /// Failure information of the error message
+        fail_info: Option<PkiFailureInfo>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for Error<E> {}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::Http(err) => write!(f, "HTTP error: {}", err),
+            Error::Status(status) => write!(f, "unexpected HTTP status: {}", status),
+            Error::ContentType(Some(content_type)) => {
+                write!(f, "unexpected content type: {}", content_type)
+            }
+            Error::ContentType(None) => f.write_str("missing content type"),
+            Error::TransactionId => f.write_str("transactionID mismatch"),
+            Error::RecipNonce => f.write_str("recipNonce mismatch"),
+            Error::ErrorMessage { status, .. } => {
+                write!(f, "error message received with status {:?}", status)
+            }
+        }
+    }
+}
+
+impl<E> From<der::Error> for Error<E> {
+    fn from(other: der::Error) -> Self {
+        Self::Asn1(other)
+    }
+}
+
+/// HTTP response returned by an [`HttpClient`] or [`AsyncHttpClient`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    /// Status code
+    pub status: u16,
+    /// Value of the `Content-Type` header, if any
+    pub content_type: Option<String>,
+    /// Body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns a successful response carrying the DER encoding of `message`, for use by
+    /// servers.
+    pub fn from_message(message: &PkiMessage<'_>) -> der::Result<Self> {
+        Ok(Self {
+            status: STATUS_OK,
+            content_type: Some(CONTENT_TYPE.into()),
+            body: message.to_der()?,
+        })
+    }
+
+    /// Decodes the PKIMessage carried in the body of this response
+    pub fn message(&self) -> der::Result<PkiMessage<'_>> {
+        PkiMessage::from_der(&self.body)
+    }
+}
+
+/// Blocking HTTP client
+pub trait HttpClient {
+    /// Error type of the client
+    type Error;
+
+    /// Sends an HTTP POST request with the `Content-Type` header `content_type` and `body` to
+    /// `uri`, returning the response.
+    fn post(
+        &mut self,
+        uri: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> core::result::Result<HttpResponse, Self::Error>;
+}
+
+/// Asynchronous HTTP client
+pub trait AsyncHttpClient {
+    /// Error type of the client
+    type Error;
+
+    /// Sends an HTTP POST request with the `Content-Type` header `content_type` and `body` to
+    /// `uri`, returning the response.
+    fn post(
+        &mut self,
+        uri: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> impl Future<Output = core::result::Result<HttpResponse, Self::Error>>;
+}
+
+/// Sends PKIMessages to a CMP server using an HTTP client `C`
+///
+/// Responses are checked to be successful, to have the `application/pkixcmp` content type, and
+/// to answer the request: the transactionID must match and the recipNonce must equal the
+/// senderNonce of the request. A response with an `errorMsg` body is then returned as
+/// [`Error::ErrorMessage`].
+#[derive(Clone, Debug)]
+pub struct HttpTransport<C> {
+    client: C,
+    uri: String,
+}
+
+impl<C> HttpTransport<C> {
+    /// Returns a transport sending requests with `client` to the CMP server at `uri`
+    pub fn new(client: C, uri: impl Into<String>) -> Self {
+        Self {
+            client,
+            uri: uri.into(),
+        }
+    }
+
+    /// Returns the URI of the CMP server
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the HTTP client
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+}
+
+impl<C: HttpClient> HttpTransport<C> {
+    /// Sends `request` and returns the validated response, see [`HttpTransport`]
+    pub fn send(&mut self, request: &PkiMessage<'_>) -> Result<HttpResponse, C::Error> {
+        let body = request.to_der()?;
+        let response = self
+            .client
+            .post(&self.uri, CONTENT_TYPE, &body)
+            .map_err(Error::Http)?;
+        check_response(request, &response)?;
+        Ok(response)
+    }
+}
+
+impl<C: AsyncHttpClient> HttpTransport<C> {
+    /// Sends `request` and returns the validated response, see [`HttpTransport`]
+    pub async fn send_async(&mut self, request: &PkiMessage<'_>) -> Result<HttpResponse, C::Error> {
+        let body = request.to_der()?;
+        let response = self
+            .client
+            .post(&self.uri, CONTENT_TYPE, &body)
+            .await
+            .map_err(Error::Http)?;
+        check_response(request, &response)?;
+        Ok(response)
+    }
+}
+
+/// Returns whether `content_type` is `application/pkixcmp`, ignoring case and parameters
+fn is_pkixcmp(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default();
+    media_type.trim().eq_ignore_ascii_case(CONTENT_TYPE)
+}
+
+fn check_response<E>(request: &PkiMessage<'_>, response: &HttpResponse) -> Result<(), E> {
+    if response.status != STATUS_OK {
+        return Err(Error::Status(response.status));
+    }
+    match &response.content_type {
+        Some(content_type) if is_pkixcmp(content_type) => {}
+        content_type => return Err(Error::ContentType(content_type.clone())),
+    }
+
+    let message = response.message()?;
+    if let Some(trans_id) = &request.header.trans_id {
+        if message.header.trans_id.as_ref() != Some(trans_id) {
+            return Err(Error::TransactionId);
+        }
+    }
+    if let Some(sender_nonce) = &request.header.sender_nonce {
+        if message.header.recip_nonce.as_ref() != Some(sender_nonce) {
+            return Err(Error::RecipNonce);
+        }
+    }
+
+    // Only report error messages answering this request
+    if let PkiBody::Error(content) = &message.body {
+        return Err(Error::ErrorMessage {
+            status: content.pki_status_info.status,
+            fail_info: content.pki_status_info.fail_info,
+        });
+    }
+
+    Ok(())
+}
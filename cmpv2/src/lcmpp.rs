@@ -0,0 +1,242 @@
+//! Lightweight CMP Profile (LCMPP) as described in [RFC 9483].
+//!
+//! The profile narrows down the options of [RFC 4210] to simplify implementations: messages
+//! carry a fixed set of header fields, only a subset of the message bodies is used, and all
+//! messages are protected. [`validate`] reports the violations of these requirements by a
+//! received message. Messages being constructed can be checked with [`validate_unprotected`]
+//! before protecting them.
+//!
+//! [RFC 4210]: https://www.rfc-editor.org/rfc/rfc4210
+//! [RFC 9483]: https://www.rfc-editor.org/rfc/rfc9483
+
+use alloc::vec::Vec;
+use const_oid::db::rfc5912::ID_PASSWORD_BASED_MAC;
+use core::fmt;
+use der::{Tag, Tagged};
+use x509_cert::ext::pkix::name::GeneralName;
+
+use crate::body::PkiBody;
+use crate::header::{Pvno, NONCE_LENGTH};
+use crate::message::PkiMessage;
+use crate::parameter::ID_PBMAC1;
+
+/// Violation of the Lightweight CMP Profile
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// The protocol version is neither `cmp2000` nor `cmp2021`
+    Pvno(Pvno),
+
+    /// The sender isn't a directoryName
+    Sender,
+
+    /// The recipient isn't a directoryName
+    Recipient,
+
+    /// The protectionAlg is missing
+    MissingProtectionAlg,
+
+    /// The protection is missing
+    MissingProtection,
+
+    /// The senderKID is missing from a message with MAC-based protection
+    MissingSenderKid,
+
+    /// The extraCerts, which start with the protection certificate, are missing from the first
+    /// message of a transaction with signature-based protection
+    MissingExtraCerts,
+
+    /// The transactionID is missing
+    MissingTransactionId,
+
+    /// The transactionID isn't [`NONCE_LENGTH`] bytes long
+    TransactionIdLength(usize),
+
+    /// The senderNonce is missing
+    MissingSenderNonce,
+
+    /// The senderNonce isn't [`NONCE_LENGTH`] bytes long
+    SenderNonceLength(usize),
+
+    /// The recipNonce is missing from a message answering a previous message
+    MissingRecipNonce,
+
+    /// The body type, identified by its tag, isn't used by the profile
+    UnsupportedBody(Tag),
+
+    /// The body doesn't contain exactly one request, response, or revocation entry
+    EntryCount(usize),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Pvno(pvno) => write!(f, "unsupported pvno: {:?}", pvno),
+            Violation::Sender => f.write_str("sender is not a directoryName"),
+            Violation::Recipient => f.write_str("recipient is not a directoryName"),
+            Violation::MissingProtectionAlg => f.write_str("protectionAlg is missing"),
+            Violation::MissingProtection => f.write_str("protection is missing"),
+            Violation::MissingSenderKid => f.write_str("senderKID is missing"),
+            Violation::MissingExtraCerts => f.write_str("extraCerts are missing"),
+            Violation::MissingTransactionId => f.write_str("transactionID is missing"),
+            Violation::TransactionIdLength(len) => {
+                write!(f, "transactionID has invalid length: {}", len)
+            }
+            Violation::MissingSenderNonce => f.write_str("senderNonce is missing"),
+            Violation::SenderNonceLength(len) => {
+                write!(f, "senderNonce has invalid length: {}", len)
+            }
+            Violation::MissingRecipNonce => f.write_str("recipNonce is missing"),
+            Violation::UnsupportedBody(tag) => write!(f, "unsupported body: {}", tag),
+            Violation::EntryCount(count) => {
+                write!(f, "body contains {} entries instead of one", count)
+            }
+        }
+    }
+}
+
+/// Checks `message` against the requirements of the Lightweight CMP Profile, returning all
+/// violations found.
+///
+/// Note that [RFC 9483] permits unprotected error messages in some cases, which are reported as
+/// [`Violation::MissingProtectionAlg`] and [`Violation::MissingProtection`].
+///
+/// [RFC 9483]: https://www.rfc-editor.org/rfc/rfc9483
+pub fn validate(message: &PkiMessage<'_>) -> Result<(), Vec<Violation>> {
+    let mut violations = Vec::new();
+    check_header(message, &mut violations);
+    check_protection(message, &mut violations);
+    check_body(&message.body, &mut violations);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Checks `message` like [`validate`], except for the protection and the extraCerts, which
+/// are only added when protecting the message.
+///
+/// The protectionAlg is checked, as it is covered by the protection.
+pub fn validate_unprotected(message: &PkiMessage<'_>) -> Result<(), Vec<Violation>> {
+    let mut violations = Vec::new();
+    check_header(message, &mut violations);
+    if message.header.protection_alg.is_none() {
+        violations.push(Violation::MissingProtectionAlg);
+    }
+    check_body(&message.body, &mut violations);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn check_header(message: &PkiMessage<'_>, violations: &mut Vec<Violation>) {
+    let header = &message.header;
+    if !matches!(header.pvno, Pvno::Cmp2000 | Pvno::Cmp2021) {
+        violations.push(Violation::Pvno(header.pvno));
+    }
+    if !matches!(header.sender, GeneralName::DirectoryName(_)) {
+        violations.push(Violation::Sender);
+    }
+    if !matches!(header.recipient, GeneralName::DirectoryName(_)) {
+        violations.push(Violation::Recipient);
+    }
+
+    match &header.trans_id {
+        None => violations.push(Violation::MissingTransactionId),
+        Some(id) if id.as_bytes().len() != NONCE_LENGTH => {
+            violations.push(Violation::TransactionIdLength(id.as_bytes().len()))
+        }
+        Some(_) => {}
+    }
+    match &header.sender_nonce {
+        None => violations.push(Violation::MissingSenderNonce),
+        Some(nonce) if nonce.as_bytes().len() != NONCE_LENGTH => {
+            violations.push(Violation::SenderNonceLength(nonce.as_bytes().len()))
+        }
+        Some(_) => {}
+    }
+    if header.recip_nonce.is_none() && answers_previous_message(&message.body) {
+        violations.push(Violation::MissingRecipNonce);
+    }
+
+    let is_mac = matches!(
+        &header.protection_alg,
+        Some(alg) if alg.oid == ID_PASSWORD_BASED_MAC || alg.oid == ID_PBMAC1
+    );
+    if is_mac && header.sender_kid.is_none() {
+        violations.push(Violation::MissingSenderKid);
+    }
+}
+
+fn check_protection(message: &PkiMessage<'_>, violations: &mut Vec<Violation>) {
+    match &message.header.protection_alg {
+        None => violations.push(Violation::MissingProtectionAlg),
+        Some(alg) if alg.oid == ID_PASSWORD_BASED_MAC || alg.oid == ID_PBMAC1 => {}
+        Some(_) if starts_transaction(&message.body) => {
+            if message
+                .extra_certs
+                .as_ref()
+                .map_or(true, |certs| certs.is_empty())
+            {
+                violations.push(Violation::MissingExtraCerts);
+            }
+        }
+        Some(_) => {}
+    }
+    if message.protection.is_none() {
+        violations.push(Violation::MissingProtection);
+    }
+}
+
+fn check_body(body: &PkiBody<'_>, violations: &mut Vec<Violation>) {
+    let count = match body {
+        PkiBody::Ir(requests) | PkiBody::Cr(requests) | PkiBody::KUr(requests) => requests.len(),
+        PkiBody::Ip(rep) | PkiBody::Cp(rep) | PkiBody::Kup(rep) => rep.response.len(),
+        PkiBody::Rr(details) => details.len(),
+        PkiBody::P10cr(_)
+        | PkiBody::Rp(_)
+        | PkiBody::PkiConf(_)
+        | PkiBody::Nested(_)
+        | PkiBody::GenM(_)
+        | PkiBody::GenP(_)
+        | PkiBody::Error(_)
+        | PkiBody::CertConf(_)
+        | PkiBody::PollReq(_)
+        | PkiBody::PollRep(_) => return,
+        _ => {
+            violations.push(Violation::UnsupportedBody(body.tag()));
+            return;
+        }
+    };
+
+    if count != 1 {
+        violations.push(Violation::EntryCount(count));
+    }
+}
+
+/// Returns whether a message with `body` is sent in response to a previous message of the
+/// transaction, and hence references its senderNonce.
+fn answers_previous_message(body: &PkiBody<'_>) -> bool {
+    matches!(
+        body,
+        PkiBody::Ip(_)
+            | PkiBody::Cp(_)
+            | PkiBody::Kup(_)
+            | PkiBody::Rp(_)
+            | PkiBody::PkiConf(_)
+            | PkiBody::GenP(_)
+            | PkiBody::CertConf(_)
+            | PkiBody::PollReq(_)
+            | PkiBody::PollRep(_)
+    )
+}
+
+/// Returns whether a message with `body` is the first message of a transaction
+fn starts_transaction(body: &PkiBody<'_>) -> bool {
+    !answers_previous_message(body) && !matches!(body, PkiBody::Error(_))
+}
@@ -20,14 +20,21 @@ extern crate std;
 
 pub mod ann;
 pub mod body;
+#[cfg(feature = "builder")]
+pub mod builder;
 pub mod certified_key_pair;
 pub mod gen;
 pub mod header;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "lcmpp")]
+pub mod lcmpp;
 pub mod message;
 pub mod oob;
 pub mod parameter;
 pub mod poll;
 pub mod pop;
+pub mod protection;
 pub mod response;
 pub mod rev;
 pub mod status;
@@ -2,7 +2,7 @@
 
 use alloc::vec::Vec;
 use der::asn1::BitString;
-use der::Sequence;
+use der::{Sequence, Spanned};
 
 use crate::body::PkiBody;
 use crate::header::{CmpCertificate, PkiHeader};
@@ -41,6 +41,68 @@ pub struct PkiMessage<'a> {
     pub extra_certs: Option<Vec<CmpCertificate>>,
 }
 
+impl<'a> PkiMessage<'a> {
+    /// Returns the messages wrapped by this message if its body is `nested`, as described in
+    /// [RFC 4210 Section 5.1.3.4].
+    ///
+    /// [RFC 4210 Section 5.1.3.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.4
+    pub fn nested_messages(&self) -> Option<&[PkiMessage<'a>]> {
+        match &self.body {
+            PkiBody::Nested(nested) => Some(&nested.0),
+            _ => None,
+        }
+    }
+
+    /// Unwraps the messages of this message if its body is `nested`, or returns `None` for any
+    /// other body.
+    ///
+    /// Use [`PkiMessage::nested_messages`] to inspect the body without consuming the message.
+    pub fn into_nested_messages(self) -> Option<PkiMessages<'a>> {
+        match self.body {
+            PkiBody::Nested(nested) => Some(nested.0),
+            _ => None,
+        }
+    }
+}
+
+/// `PKIMessage` which retains the original encodings of its header and body.
+///
+/// Decoding a [`PkiMessage`] and re-encoding its `ProtectedPart` doesn't necessarily reproduce
+/// the bytes which were protected, e.g. if the sender didn't sort a `SET OF` value. This type
+/// records the exact encodings of the header and body as they were decoded, so that the
+/// protection can be verified over those bytes.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SpannedPkiMessage<'a> {
+    pub header: Spanned<'a, PkiHeader<'a>>,
+    pub body: Spanned<'a, PkiBody<'a>>,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "false",
+        optional = "true"
+    )]
+    pub protection: Option<PkiProtection>,
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub extra_certs: Option<Vec<CmpCertificate>>,
+}
+
+impl<'a> From<SpannedPkiMessage<'a>> for PkiMessage<'a> {
+    fn from(message: SpannedPkiMessage<'a>) -> Self {
+        Self {
+            header: message.header.into_inner(),
+            body: message.body.into_inner(),
+            protection: message.protection,
+            extra_certs: message.extra_certs,
+        }
+    }
+}
+
 /// The `PkiMessages` type is defined in [RFC 4210 Section 5.1].
 ///
 /// ```text
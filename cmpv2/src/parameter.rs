@@ -1,6 +1,6 @@
 //! Parameter types
 
-use der::asn1::OctetString;
+use der::asn1::{ObjectIdentifier, OctetString};
 use der::Sequence;
 
 use spki::AlgorithmIdentifierOwned;
@@ -42,3 +42,27 @@ pub struct DhbmParameter {
     pub owf: AlgorithmIdentifierOwned,
     pub mac: AlgorithmIdentifierOwned,
 }
+
+/// The `id-PBMAC1` object identifier is defined in [RFC 8018 Appendix A.5] and used for CMP
+/// message protection as described in [RFC 9481 Section 6.1.2].
+///
+/// [RFC 8018 Appendix A.5]: https://www.rfc-editor.org/rfc/rfc8018#appendix-A.5
+/// [RFC 9481 Section 6.1.2]: https://www.rfc-editor.org/rfc/rfc9481#section-6.1.2
+pub const ID_PBMAC1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.14");
+
+/// The `PBMAC1-params` type is defined in [RFC 8018 Appendix A.5].
+///
+/// ```text
+/// PBMAC1-params ::= SEQUENCE {
+///     keyDerivationFunc   AlgorithmIdentifier {{PBMAC1-KDFs}},
+///     messageAuthScheme   AlgorithmIdentifier {{PBMAC1-MACs}}
+/// }
+/// ```
+///
+/// [RFC 8018 Appendix A.5]: https://www.rfc-editor.org/rfc/rfc8018#appendix-A.5
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct Pbmac1Params {
+    pub key_derivation_func: AlgorithmIdentifierOwned,
+    pub message_auth_scheme: AlgorithmIdentifierOwned,
+}
@@ -1,11 +1,15 @@
 //! Polling-related types
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::time::Duration;
 use der::asn1::Int;
 
 use der::Sequence;
 
+use crate::body::PkiBody;
 use crate::header::PkiFreeText;
+use crate::status::PkiStatus;
 
 /// The `PollReqContent` type is defined in [RFC 4210 Section 5.3.22].
 ///
@@ -15,10 +19,26 @@ use crate::header::PkiFreeText;
 /// ```
 ///
 /// [RFC 4210 Section 5.3.22]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.22
+pub type PollReqContent = Vec<PollReq>;
+
+/// PollReq corresponds to the type that is inlined in the PollReqContent definition, as shown
+/// below:
+///
+/// ```text
+///  SEQUENCE {
+///      certReqId              INTEGER }
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
-pub struct PollReqContent {
-    pub cert_req_ids: Vec<PollReqContentId>,
+pub struct PollReq {
+    pub cert_req_id: PollReqContentId,
+}
+
+impl PollReq {
+    /// Returns a polling request for the certificate requested with `cert_req_id`
+    pub fn new(cert_req_id: PollReqContentId) -> Self {
+        Self { cert_req_id }
+    }
 }
 
 /// The `PollReqContent` and `PollRepContent` types defined in [RFC 4210 Section 5.3.22] use an
@@ -38,10 +58,126 @@ pub type PollReqContentId = Int;
 /// ```
 ///
 /// [RFC 4210 Section 5.3.22]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.22
+pub type PollRepContent<'a> = Vec<PollRep<'a>>;
+
+/// PollRep corresponds to the type that is inlined in the PollRepContent definition, as shown
+/// below:
+///
+/// ```text
+///  SEQUENCE {
+///      certReqId              INTEGER,
+///      checkAfter             INTEGER,  -- time in seconds
+///      reason                 PKIFreeText OPTIONAL }
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
-pub struct PollRepContent<'a> {
+pub struct PollRep<'a> {
     pub cert_req_id: PollReqContentId,
     pub check_after: u64,
     pub reason: Option<PkiFreeText<'a>>,
 }
+
+impl<'a> PollRep<'a> {
+    /// Returns a polling response asking to poll for `cert_req_id` again after `check_after`
+    /// seconds
+    pub fn new(cert_req_id: PollReqContentId, check_after: u64) -> Self {
+        Self {
+            cert_req_id,
+            check_after,
+            reason: None,
+        }
+    }
+
+    /// Sets the reason why the request is still pending
+    pub fn with_reason(mut self, reason: PkiFreeText<'a>) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Returns the checkAfter interval as [`Duration`]
+    pub fn check_after_duration(&self) -> Duration {
+        Duration::from_secs(self.check_after)
+    }
+}
+
+/// Client-side state of the certificate requests that are pending, i.e. answered with the
+/// `waiting` status, as described in [RFC 4210 Section 5.3.22].
+///
+/// The state tracks the checkAfter interval of the last `pollRep` for each certReqId. Timing
+/// is left to the caller, which sends [`PollState::poll_req`] once
+/// [`PollState::check_after`] has passed and hands the response to [`PollState::update`].
+///
+/// [RFC 4210 Section 5.3.22]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.22
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PollState {
+    pending: BTreeMap<PollReqContentId, Duration>,
+}
+
+impl PollState {
+    /// Returns an empty polling state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether no requests are pending
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns whether the request identified by `cert_req_id` is pending
+    pub fn is_pending(&self, cert_req_id: &PollReqContentId) -> bool {
+        self.pending.contains_key(cert_req_id)
+    }
+
+    /// Returns the certReqIds of the pending requests
+    pub fn pending(&self) -> impl Iterator<Item = &PollReqContentId> {
+        self.pending.keys()
+    }
+
+    /// Returns the checkAfter interval of the request identified by `cert_req_id`, which is
+    /// zero until a `pollRep` was received for it
+    pub fn check_after_for(&self, cert_req_id: &PollReqContentId) -> Option<Duration> {
+        self.pending.get(cert_req_id).copied()
+    }
+
+    /// Returns the shortest checkAfter interval of the pending requests, i.e. the time to wait
+    /// before sending the next `pollReq`
+    pub fn check_after(&self) -> Option<Duration> {
+        self.pending.values().min().copied()
+    }
+
+    /// Returns a `pollReq` body for all pending requests
+    pub fn poll_req(&self) -> PkiBody<'static> {
+        PkiBody::poll_req(self.pending.keys().cloned())
+    }
+
+    /// Updates the state with a response `body`.
+    ///
+    /// The requests of a `pollRep` are marked as pending with the given checkAfter intervals.
+    /// The responses of an `ip`, `cp`, `kup` or `ccp` are marked as pending when their status
+    /// is `waiting`, and are no longer pending otherwise. Other bodies are ignored.
+    pub fn update(&mut self, body: &PkiBody<'_>) {
+        match body {
+            PkiBody::PollRep(responses) => {
+                for response in responses {
+                    self.pending.insert(
+                        response.cert_req_id.clone(),
+                        response.check_after_duration(),
+                    );
+                }
+            }
+            PkiBody::Ip(rep) | PkiBody::Cp(rep) | PkiBody::Kup(rep) | PkiBody::Ccp(rep) => {
+                for response in &rep.response {
+                    if response.status.status == PkiStatus::Waiting {
+                        self.pending
+                            .entry(response.cert_req_id.clone())
+                            .or_insert(Duration::ZERO);
+                    } else {
+                        self.pending.remove(&response.cert_req_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! PKIMessage protection
+//!
+//! The protection of a [`PkiMessage`] is computed over the DER encoding of its
+//! [`ProtectedPart`] as described in [RFC 4210 Section 5.1.3].
+//!
+//! [`ProtectedPart`]: crate::message::ProtectedPart
+//! [RFC 4210 Section 5.1.3]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3
+
+#[cfg(feature = "pbm")]
+mod pbm;
+#[cfg(feature = "signature")]
+mod signing;
+
+use alloc::vec::Vec;
+use core::fmt;
+use der::asn1::ObjectIdentifier;
+use der::{Encode, Header, Length, Tag};
+
+use crate::message::{PkiMessage, SpannedPkiMessage};
+
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors
+    Asn1(der::Error),
+
+    /// The protection algorithm, or one of its components, isn't supported
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// The message lacks the protectionAlg or the protection
+    MissingProtection,
+
+    /// The protection doesn't verify
+    Verification,
+
+    /// Public key errors
+    #[cfg(feature = "signature")]
+    PublicKey(spki::Error),
+
+    /// Signing and verification errors
+    #[cfg(feature = "signature")]
+    Signature(signature::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::MissingProtection => f.write_str("message protection is missing"),
+            Error::Verification => f.write_str("message protection verification failed"),
+            #[cfg(feature = "signature")]
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            #[cfg(feature = "signature")]
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(other: der::Error) -> Self {
+        Self::Asn1(other)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<spki::Error> for Error {
+    fn from(other: spki::Error) -> Self {
+        Self::PublicKey(other)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<signature::Error> for Error {
+    fn from(other: signature::Error) -> Self {
+        Self::Signature(other)
+    }
+}
+
+impl PkiMessage<'_> {
+    /// Returns the DER encoding of the `ProtectedPart` of this message, i.e. the input of the
+    /// protection.
+    pub fn protected_part_der(&self) -> der::Result<Vec<u8>> {
+        protected_part_der(&self.header.to_der()?, &self.body.to_der()?)
+    }
+}
+
+impl SpannedPkiMessage<'_> {
+    /// Returns the encoding of the `ProtectedPart` of this message made up of the original
+    /// encodings of its header and body, i.e. the bytes which were protected.
+    pub fn protected_part_der(&self) -> der::Result<Vec<u8>> {
+        protected_part_der(self.header.as_bytes(), self.body.as_bytes())
+    }
+}
+
+/// Encodes a `ProtectedPart` from the encodings of its header and body.
+fn protected_part_der(header: &[u8], body: &[u8]) -> der::Result<Vec<u8>> {
+    let len = (Length::try_from(header.len())? + Length::try_from(body.len())?)?;
+    let mut der = Header::new(Tag::Sequence, len)?.to_der()?;
+    der.extend_from_slice(header);
+    der.extend_from_slice(body);
+    Ok(der)
+}
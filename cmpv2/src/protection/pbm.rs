@@ -0,0 +1,232 @@
+//! Password-based MAC protection as described in [RFC 4210 Section 5.1.3.1] and
+//! [RFC 9481 Section 6.1].
+//!
+//! [RFC 4210 Section 5.1.3.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.1
+//! [RFC 9481 Section 6.1]: https://www.rfc-editor.org/rfc/rfc9481#section-6.1
+
+use alloc::vec::Vec;
+use const_oid::db::rfc5911::HMAC_SHA_1;
+use const_oid::db::rfc5912::{
+    ID_PASSWORD_BASED_MAC, ID_SHA_1, ID_SHA_224, ID_SHA_256, ID_SHA_384, ID_SHA_512,
+};
+use const_oid::db::rfc6268::{
+    ID_HMAC_WITH_SHA_224, ID_HMAC_WITH_SHA_256, ID_HMAC_WITH_SHA_384, ID_HMAC_WITH_SHA_512,
+};
+use der::asn1::{Any, BitString};
+use der::Tag;
+use hmac::digest::{core_api::BlockSizeUser, Digest};
+use hmac::{KeyInit, Mac, SimpleHmac};
+use pkcs5::pbes2::{Pbkdf2Params, Pbkdf2Prf, HMAC_WITH_SHA1_OID, PBKDF2_OID};
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use spki::AlgorithmIdentifierOwned;
+use subtle::ConstantTimeEq;
+
+use super::{Error, Result};
+use crate::header::PkiHeader;
+use crate::message::{PkiMessage, PkiProtection, SpannedPkiMessage};
+use crate::parameter::{PbmParameter, Pbmac1Params, ID_PBMAC1};
+
+impl PbmParameter {
+    /// Implementation defined maximum iteration count, matching
+    /// [`Pbkdf2Params::MAX_ITERATION_COUNT`].
+    pub const MAX_ITERATION_COUNT: u64 = Pbkdf2Params::MAX_ITERATION_COUNT as u64;
+
+    /// Returns the `id-PasswordBasedMac` algorithm identifier with these parameters, for use
+    /// as the protectionAlg of a message.
+    pub fn to_algorithm_identifier(&self) -> der::Result<AlgorithmIdentifierOwned> {
+        Ok(AlgorithmIdentifierOwned {
+            oid: ID_PASSWORD_BASED_MAC,
+            parameters: Some(Any::encode_from(self)?),
+        })
+    }
+
+    /// Computes the MAC of `data` keyed with `password` as specified in
+    /// [RFC 4210 Section 5.1.3.1].
+    ///
+    /// The one-way function may be SHA-1 or SHA-2, and the MAC HMAC with either of them.
+    ///
+    /// [RFC 4210 Section 5.1.3.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.1
+    pub fn mac(&self, password: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        let (salt, count) = (self.salt.as_bytes(), self.iteration_count);
+        if count == 0 || count > Self::MAX_ITERATION_COUNT {
+            return Err(Tag::Integer.value_error().into());
+        }
+
+        let key = match self.owf.oid {
+            ID_SHA_1 => base_key::<Sha1>(password, salt, count),
+            ID_SHA_224 => base_key::<Sha224>(password, salt, count),
+            ID_SHA_256 => base_key::<Sha256>(password, salt, count),
+            ID_SHA_384 => base_key::<Sha384>(password, salt, count),
+            ID_SHA_512 => base_key::<Sha512>(password, salt, count),
+            oid => return Err(Error::UnsupportedAlgorithm(oid)),
+        };
+
+        mac(&self.mac, &key, data)
+    }
+}
+
+impl Pbmac1Params {
+    /// Returns the `id-PBMAC1` algorithm identifier with these parameters, for use as the
+    /// protectionAlg of a message.
+    pub fn to_algorithm_identifier(&self) -> der::Result<AlgorithmIdentifierOwned> {
+        Ok(AlgorithmIdentifierOwned {
+            oid: ID_PBMAC1,
+            parameters: Some(Any::encode_from(self)?),
+        })
+    }
+
+    /// Computes the MAC of `data` keyed with `password` as specified in
+    /// [RFC 9481 Section 6.1.2].
+    ///
+    /// The key derivation function must be PBKDF2 with an explicit keyLength, and the MAC
+    /// HMAC with SHA-1 or SHA-2.
+    ///
+    /// [RFC 9481 Section 6.1.2]: https://www.rfc-editor.org/rfc/rfc9481#section-6.1.2
+    pub fn mac(&self, password: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        let kdf = &self.key_derivation_func;
+        if kdf.oid != PBKDF2_OID {
+            return Err(Error::UnsupportedAlgorithm(kdf.oid));
+        }
+
+        let params = kdf
+            .parameters
+            .as_ref()
+            .ok_or_else(|| Tag::Sequence.value_error())?
+            .decode_as::<Pbkdf2Params>()?;
+        let (salt, rounds) = (params.salt.as_bytes(), params.iteration_count);
+        if rounds > Pbkdf2Params::MAX_ITERATION_COUNT {
+            return Err(Tag::Integer.value_error().into());
+        }
+
+        let key_length = params
+            .key_length
+            .ok_or_else(|| Tag::Integer.value_error())?;
+        let mut key = alloc::vec![0; usize::from(key_length)];
+        match params.prf {
+            Pbkdf2Prf::HmacWithSha1 => pbkdf2_hmac::<Sha1>(password, salt, rounds, &mut key),
+            Pbkdf2Prf::HmacWithSha224 => pbkdf2_hmac::<Sha224>(password, salt, rounds, &mut key),
+            Pbkdf2Prf::HmacWithSha256 => pbkdf2_hmac::<Sha256>(password, salt, rounds, &mut key),
+            Pbkdf2Prf::HmacWithSha384 => pbkdf2_hmac::<Sha384>(password, salt, rounds, &mut key),
+            Pbkdf2Prf::HmacWithSha512 => pbkdf2_hmac::<Sha512>(password, salt, rounds, &mut key),
+            prf => return Err(Error::UnsupportedAlgorithm(prf.oid())),
+        }?;
+
+        mac(&self.message_auth_scheme, &key, data)
+    }
+}
+
+impl PkiMessage<'_> {
+    /// Sets the protection of this message to the password-based MAC identified by the
+    /// protectionAlg of its header, which must be either `id-PasswordBasedMac` or `id-PBMAC1`.
+    pub fn protect_with_password(&mut self, password: &[u8]) -> Result<()> {
+        let mac = password_mac(&self.header, password, &self.protected_part_der()?)?;
+        self.protection = Some(BitString::from_bytes(&mac)?);
+        Ok(())
+    }
+
+    /// Verifies the password-based MAC protection of this message.
+    ///
+    /// The MAC is computed over the DER encoding of the `ProtectedPart` of the message. If the
+    /// message was decoded from an encoding which isn't canonical DER, this differs from the
+    /// protected bytes: use [`SpannedPkiMessage::verify_password`] to verify those instead.
+    pub fn verify_password(&self, password: &[u8]) -> Result<()> {
+        let protection = self.protection.as_ref().ok_or(Error::MissingProtection)?;
+        verify_password_mac(
+            &self.header,
+            protection,
+            password,
+            &self.protected_part_der()?,
+        )
+    }
+}
+
+impl SpannedPkiMessage<'_> {
+    /// Verifies the password-based MAC protection of this message.
+    ///
+    /// This is the same as [`PkiMessage::verify_password`], except that the MAC is computed
+    /// over the original encoding of the `ProtectedPart`.
+    pub fn verify_password(&self, password: &[u8]) -> Result<()> {
+        let protection = self.protection.as_ref().ok_or(Error::MissingProtection)?;
+        verify_password_mac(
+            &self.header,
+            protection,
+            password,
+            &self.protected_part_der()?,
+        )
+    }
+}
+
+/// Verifies that `protection` is the password-based MAC of `data` identified by the
+/// protectionAlg of `header`.
+fn verify_password_mac(
+    header: &PkiHeader<'_>,
+    protection: &PkiProtection,
+    password: &[u8],
+    data: &[u8],
+) -> Result<()> {
+    let mac = password_mac(header, password, data)?;
+
+    if protection.unused_bits() == 0 && bool::from(mac.ct_eq(protection.raw_bytes())) {
+        Ok(())
+    } else {
+        Err(Error::Verification)
+    }
+}
+
+/// Computes the password-based MAC of `data` identified by the protectionAlg of `header`.
+fn password_mac(header: &PkiHeader<'_>, password: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let alg = header
+        .protection_alg
+        .as_ref()
+        .ok_or(Error::MissingProtection)?;
+    let params = alg
+        .parameters
+        .as_ref()
+        .ok_or_else(|| Tag::Sequence.value_error())?;
+
+    match alg.oid {
+        ID_PASSWORD_BASED_MAC => params.decode_as::<PbmParameter>()?.mac(password, data),
+        ID_PBMAC1 => params.decode_as::<Pbmac1Params>()?.mac(password, data),
+        oid => Err(Error::UnsupportedAlgorithm(oid)),
+    }
+}
+
+/// Computes `BASEKEY` by applying the one-way function `D` `count` times to the password
+/// and salt.
+fn base_key<D: Digest>(password: &[u8], salt: &[u8], count: u64) -> Vec<u8> {
+    let mut key = D::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .finalize();
+    for _ in 1..count {
+        key = D::digest(&key);
+    }
+    key.to_vec()
+}
+
+fn pbkdf2_hmac<D>(password: &[u8], salt: &[u8], rounds: u32, key: &mut [u8]) -> Result<()>
+where
+    D: Digest + BlockSizeUser + Clone + Sync,
+{
+    pbkdf2::pbkdf2::<SimpleHmac<D>>(password, salt, rounds, key)
+        .map_err(|_| Tag::Integer.value_error().into())
+}
+
+fn mac(alg: &AlgorithmIdentifierOwned, key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    match alg.oid {
+        HMAC_SHA_1 | HMAC_WITH_SHA1_OID => mac_with::<Sha1>(key, data),
+        ID_HMAC_WITH_SHA_224 => mac_with::<Sha224>(key, data),
+        ID_HMAC_WITH_SHA_256 => mac_with::<Sha256>(key, data),
+        ID_HMAC_WITH_SHA_384 => mac_with::<Sha384>(key, data),
+        ID_HMAC_WITH_SHA_512 => mac_with::<Sha512>(key, data),
+        oid => Err(Error::UnsupportedAlgorithm(oid)),
+    }
+}
+
+fn mac_with<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = <SimpleHmac<D> as KeyInit>::new_from_slice(key)
+        .map_err(|_| Error::from(Tag::OctetString.value_error()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
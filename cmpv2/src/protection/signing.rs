@@ -0,0 +1,160 @@
+//! Signature-based protection as described in [RFC 4210 Section 5.1.3.3].
+//!
+//! [RFC 4210 Section 5.1.3.3]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.3
+
+use alloc::vec::Vec;
+use der::Encode;
+use rand_core::CryptoRngCore;
+use signature::{RandomizedSigner, SignatureEncoding, Signer, Verifier};
+use spki::{
+    DecodePublicKey, DynSignatureAlgorithmIdentifier, SignatureBitStringEncoding,
+    SubjectPublicKeyInfoOwned,
+};
+
+use super::{Error, Result};
+use crate::header::{CmpCertificate, PkiHeader};
+use crate::message::{PkiMessage, PkiProtection, SpannedPkiMessage};
+
+impl PkiMessage<'_> {
+    /// Returns the certificate used to protect this message, i.e. the first of its extraCerts
+    /// as required by [RFC 4210 Section 5.1.1].
+    ///
+    /// [RFC 4210 Section 5.1.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.1
+    pub fn protection_cert(&self) -> Option<&CmpCertificate> {
+        self.extra_certs.as_ref().and_then(|certs| certs.first())
+    }
+
+    /// Sets the protectionAlg of this message to the signature algorithm of `signer`, and the
+    /// protection to the signature over the resulting `ProtectedPart`.
+    ///
+    /// `certificate_chain` starts with the certificate of `signer` and is placed in front of the
+    /// extraCerts. Errors when the algorithm identifier encoding, message encoding, or
+    /// signature generation fails.
+    pub fn sign<S, Sig>(&mut self, signer: &S, certificate_chain: Vec<CmpCertificate>) -> Result<()>
+    where
+        S: Signer<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        self.header.protection_alg = Some(signer.signature_algorithm_identifier()?);
+        let signature = signer.try_sign(&self.protected_part_der()?)?;
+        self.protection = Some(signature.to_bitstring()?);
+        self.prepend_extra_certs(certificate_chain);
+        Ok(())
+    }
+
+    /// Sets the protectionAlg of this message to the signature algorithm of `signer`, and the
+    /// protection to the randomized signature over the resulting `ProtectedPart`.
+    ///
+    /// See [`PkiMessage::sign`].
+    pub fn sign_with_rng<S, Sig>(
+        &mut self,
+        signer: &S,
+        rng: &mut impl CryptoRngCore,
+        certificate_chain: Vec<CmpCertificate>,
+    ) -> Result<()>
+    where
+        S: RandomizedSigner<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        self.header.protection_alg = Some(signer.signature_algorithm_identifier()?);
+        let signature = signer.try_sign_with_rng(rng, &self.protected_part_der()?)?;
+        self.protection = Some(signature.to_bitstring()?);
+        self.prepend_extra_certs(certificate_chain);
+        Ok(())
+    }
+
+    /// Verifies the signature protection of this message with `key`, decoded as `VK`.
+    ///
+    /// The signature is verified over the re-encoded `ProtectedPart` of the message. If the
+    /// message was decoded from an encoding which isn't canonical DER, this differs from the
+    /// protected bytes: use [`SpannedPkiMessage::verify_signature_with`] to verify those
+    /// instead. This doesn't check whether `VK` matches the protectionAlg.
+    pub fn verify_signature_with<VK, S>(&self, key: &SubjectPublicKeyInfoOwned) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let protection = self.protection.as_ref().ok_or(Error::MissingProtection)?;
+        verify_signature::<VK, S>(&self.header, protection, key, &self.protected_part_der()?)
+    }
+
+    /// Verifies the signature protection of this message with the key of its
+    /// [`PkiMessage::protection_cert`].
+    ///
+    /// The protection certificate itself isn't validated, which is up to the caller.
+    pub fn verify_with_protection_cert<VK, S>(&self) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let cert = self.protection_cert().ok_or(Error::MissingProtection)?;
+        self.verify_signature_with::<VK, S>(&cert.tbs_certificate.subject_public_key_info)
+    }
+
+    fn prepend_extra_certs(&mut self, mut certificate_chain: Vec<CmpCertificate>) {
+        if certificate_chain.is_empty() {
+            return;
+        }
+        if let Some(extra_certs) = self.extra_certs.take() {
+            certificate_chain.extend(extra_certs);
+        }
+        self.extra_certs = Some(certificate_chain);
+    }
+}
+
+impl SpannedPkiMessage<'_> {
+    /// Returns the certificate used to protect this message, i.e. the first of its extraCerts.
+    ///
+    /// See [`PkiMessage::protection_cert`].
+    pub fn protection_cert(&self) -> Option<&CmpCertificate> {
+        self.extra_certs.as_ref().and_then(|certs| certs.first())
+    }
+
+    /// Verifies the signature protection of this message with `key`, decoded as `VK`.
+    ///
+    /// This is the same as [`PkiMessage::verify_signature_with`], except that the signature is
+    /// verified over the original encoding of the `ProtectedPart`.
+    pub fn verify_signature_with<VK, S>(&self, key: &SubjectPublicKeyInfoOwned) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let protection = self.protection.as_ref().ok_or(Error::MissingProtection)?;
+        verify_signature::<VK, S>(&self.header, protection, key, &self.protected_part_der()?)
+    }
+
+    /// Verifies the signature protection of this message with the key of its
+    /// [`SpannedPkiMessage::protection_cert`].
+    ///
+    /// This is the same as [`PkiMessage::verify_with_protection_cert`], except that the
+    /// signature is verified over the original encoding of the `ProtectedPart`.
+    pub fn verify_with_protection_cert<VK, S>(&self) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let cert = self.protection_cert().ok_or(Error::MissingProtection)?;
+        self.verify_signature_with::<VK, S>(&cert.tbs_certificate.subject_public_key_info)
+    }
+}
+
+/// Verifies that `protection` is the signature of `data` by `key`, decoded as `VK`.
+fn verify_signature<VK, S>(
+    header: &PkiHeader<'_>,
+    protection: &PkiProtection,
+    key: &SubjectPublicKeyInfoOwned,
+    data: &[u8],
+) -> Result<()>
+where
+    VK: DecodePublicKey + Verifier<S>,
+    S: SignatureEncoding,
+{
+    if header.protection_alg.is_none() {
+        return Err(Error::MissingProtection);
+    }
+
+    let verifying_key = VK::from_public_key_der(&key.to_der()?)?;
+    let signature = S::try_from(protection.raw_bytes()).map_err(|_| signature::Error::new())?;
+
+    Ok(verifying_key.verify(data, &signature)?)
+}
@@ -5,7 +5,8 @@ use alloc::vec::Vec;
 use der::asn1::{Int, OctetString};
 use der::Sequence;
 
-use crate::{certified_key_pair::CertifiedKeyPair, header::CmpCertificate, status::PkiStatusInfo};
+use crate::certified_key_pair::{CertOrEncCert, CertifiedKeyPair};
+use crate::{header::CmpCertificate, status::PkiStatusInfo};
 
 /// The `CertRepMessage` type is defined in [RFC 4210 Section 5.3.4].
 ///
@@ -62,3 +63,38 @@ pub struct CertResponse<'a> {
     pub certified_key_pair: Option<CertifiedKeyPair>,
     pub rsp_info: Option<OctetString>,
 }
+
+impl CertResponse<'_> {
+    /// Returns the issued certificate, unless it is absent or encrypted
+    pub fn certificate(&self) -> Option<&CmpCertificate> {
+        match &self.certified_key_pair.as_ref()?.cert_or_enc_cert {
+            CertOrEncCert::Certificate(cert) => Some(cert),
+            CertOrEncCert::EncryptedCert(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "digest")]
+mod cert_conf {
+    use super::CertRepMessage;
+    use crate::status::{CertConfirmContent, CertStatus};
+    use const_oid::AssociatedOid;
+    use digest::Digest;
+
+    impl CertRepMessage<'_> {
+        /// Returns the `certConf` content confirming all certificates of this response, with
+        /// certHashes computed using the [`Digest`] `D`, see [`CertStatus::from_cert`].
+        ///
+        /// Responses without a certificate, or with an encrypted one, are skipped.
+        pub fn cert_conf<D>(&self) -> der::Result<CertConfirmContent<'static>>
+        where
+            D: Digest + AssociatedOid,
+        {
+            self.response
+                .iter()
+                .filter_map(|rsp| Some((rsp.certificate()?, &rsp.cert_req_id)))
+                .map(|(cert, id)| CertStatus::from_cert::<D>(cert, id.clone()))
+                .collect()
+        }
+    }
+}
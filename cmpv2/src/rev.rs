@@ -7,7 +7,11 @@ use der::Sequence;
 use crmf::controls::CertId;
 use crmf::request::CertTemplate;
 use x509_cert::crl::CertificateList;
-use x509_cert::ext::Extensions;
+use x509_cert::ext::pkix::CrlReason;
+use x509_cert::ext::{AsExtension, Extensions};
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::Certificate;
 
 use crate::status::PkiStatusInfo;
 
@@ -41,6 +45,45 @@ pub struct RevDetails {
     pub crl_entry_details: Option<Extensions>,
 }
 
+impl RevDetails {
+    /// Returns `RevDetails` identifying the certificate to revoke by `issuer` and
+    /// `serial_number`, as required by [RFC 9483 Section 4.2].
+    ///
+    /// [RFC 9483 Section 4.2]: https://www.rfc-editor.org/rfc/rfc9483#section-4.2
+    pub fn new(issuer: Name, serial_number: SerialNumber) -> Self {
+        Self {
+            cert_details: CertTemplate {
+                version: None,
+                serial_number: Some(serial_number),
+                signature: None,
+                issuer: Some(issuer),
+                validity: None,
+                subject: None,
+                subject_public_key_info: None,
+                issuer_unique_id: None,
+                subject_unique_id: None,
+                extensions: None,
+            },
+            crl_entry_details: None,
+        }
+    }
+
+    /// Returns `RevDetails` requesting the revocation of `cert`
+    pub fn from_cert(cert: &Certificate) -> Self {
+        let tbs = &cert.tbs_certificate;
+        Self::new(tbs.issuer.clone(), tbs.serial_number.clone())
+    }
+
+    /// Adds the `reasonCode` CRL entry extension. Errors when the extension encoding fails.
+    pub fn with_reason(mut self, reason: CrlReason) -> der::Result<Self> {
+        let ext = reason.to_extension(&Name::default(), &[])?;
+        self.crl_entry_details
+            .get_or_insert_with(Vec::new)
+            .push(ext);
+        Ok(self)
+    }
+}
+
 /// The `RevRepContent` type is defined in [RFC 4210 Section 5.3.10].
 ///
 /// ```text
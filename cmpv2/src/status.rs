@@ -2,9 +2,13 @@
 
 use crate::header::PkiFreeText;
 use alloc::vec::Vec;
+use core::fmt;
 use der::asn1::{Int, OctetString};
 use der::flagset::{flags, FlagSet};
-use der::{Enumerated, Sequence};
+use der::{
+    DecodeValue, EncodeValue, Enumerated, FixedTag, Header, Length, Reader, Sequence, Tag, Writer,
+};
+use spki::AlgorithmIdentifierOwned;
 
 /// The `PKIStatus` type is defined in [RFC 4210 Section 5.2.3].
 ///
@@ -49,6 +53,27 @@ pub enum PkiStatus {
     KeyUpdateWarning = 6,
 }
 
+impl PkiStatus {
+    /// Returns the name of the status as used in the ASN.1 definition
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::GrantedWithMods => "grantedWithMods",
+            Self::Rejection => "rejection",
+            Self::Waiting => "waiting",
+            Self::RevocationWarning => "revocationWarning",
+            Self::RevocationNotification => "revocationNotification",
+            Self::KeyUpdateWarning => "keyUpdateWarning",
+        }
+    }
+}
+
+impl fmt::Display for PkiStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 flags! {
     /// The `PKIFailureInfo` type is defined in [RFC 4210 Section 5.2.3].
     ///
@@ -122,42 +147,155 @@ flags! {
     ///
     /// [RFC 4210 Section 5.2.3]: https://www.rfc-editor.org/rfc/rfc4210#section-5.2.3
     #[allow(missing_docs)]
-    pub enum PkiFailureInfoValues: u16 {
-        BadAlg = 0,
-        BadMessageCheck = 1,
-        BadRequest      = 2,
-        BadTime         = 2,
-        BadCertId       = 4,
-        BadDataFormat   = 5,
-        WrongAuthority  = 6,
-        IncorrectData   = 7,
-        MissingTimeStamp = 8,
-        BadPOP           = 9,
-        CertRevoked      = 10,
-        CertConfirmed    = 11,
-        WrongIntegrity   = 12,
-        BadRecipientNonce = 13,
-        TimeNotAvailable  = 14,
-        UnacceptedPolicy  = 15,
-        UnacceptedExtension = 16,
-        AddInfoNotAvailable = 17,
-        BadSenderNonce      = 18,
-        BadCertTemplate     = 19,
-        SignerNotTrusted    = 20,
-        TransactionIdInUse  = 21,
-        UnsupportedVersion  = 22,
-        NotAuthorized       = 23,
-        SystemUnavail       = 24,
-        SystemFailure       = 25,
-        DuplicateCertReq    = 26,
-    }
-}
-
-///  PKIFailureInfo provides a FlagSet for `PkiFailureInfoValues` as defined in
+    pub enum PkiFailureInfoValues: u32 {
+        BadAlg = 1 << 0,
+        BadMessageCheck = 1 << 1,
+        BadRequest = 1 << 2,
+        BadTime = 1 << 3,
+        BadCertId = 1 << 4,
+        BadDataFormat = 1 << 5,
+        WrongAuthority = 1 << 6,
+        IncorrectData = 1 << 7,
+        MissingTimeStamp = 1 << 8,
+        BadPOP = 1 << 9,
+        CertRevoked = 1 << 10,
+        CertConfirmed = 1 << 11,
+        WrongIntegrity = 1 << 12,
+        BadRecipientNonce = 1 << 13,
+        TimeNotAvailable = 1 << 14,
+        UnacceptedPolicy = 1 << 15,
+        UnacceptedExtension = 1 << 16,
+        AddInfoNotAvailable = 1 << 17,
+        BadSenderNonce = 1 << 18,
+        BadCertTemplate = 1 << 19,
+        SignerNotTrusted = 1 << 20,
+        TransactionIdInUse = 1 << 21,
+        UnsupportedVersion = 1 << 22,
+        NotAuthorized = 1 << 23,
+        SystemUnavail = 1 << 24,
+        SystemFailure = 1 << 25,
+        DuplicateCertReq = 1 << 26,
+    }
+}
+
+impl PkiFailureInfoValues {
+    /// Returns the name of the failure reason as used in the ASN.1 definition
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BadAlg => "badAlg",
+            Self::BadMessageCheck => "badMessageCheck",
+            Self::BadRequest => "badRequest",
+            Self::BadTime => "badTime",
+            Self::BadCertId => "badCertId",
+            Self::BadDataFormat => "badDataFormat",
+            Self::WrongAuthority => "wrongAuthority",
+            Self::IncorrectData => "incorrectData",
+            Self::MissingTimeStamp => "missingTimeStamp",
+            Self::BadPOP => "badPOP",
+            Self::CertRevoked => "certRevoked",
+            Self::CertConfirmed => "certConfirmed",
+            Self::WrongIntegrity => "wrongIntegrity",
+            Self::BadRecipientNonce => "badRecipientNonce",
+            Self::TimeNotAvailable => "timeNotAvailable",
+            Self::UnacceptedPolicy => "unacceptedPolicy",
+            Self::UnacceptedExtension => "unacceptedExtension",
+            Self::AddInfoNotAvailable => "addInfoNotAvailable",
+            Self::BadSenderNonce => "badSenderNonce",
+            Self::BadCertTemplate => "badCertTemplate",
+            Self::SignerNotTrusted => "signerNotTrusted",
+            Self::TransactionIdInUse => "transactionIdInUse",
+            Self::UnsupportedVersion => "unsupportedVersion",
+            Self::NotAuthorized => "notAuthorized",
+            Self::SystemUnavail => "systemUnavail",
+            Self::SystemFailure => "systemFailure",
+            Self::DuplicateCertReq => "duplicateCertReq",
+        }
+    }
+}
+
+impl fmt::Display for PkiFailureInfoValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// PKIFailureInfo provides a FlagSet for `PkiFailureInfoValues` as defined in
 /// [RFC 4210 Section 5.2.3].
 ///
+/// Its `Display` implementation lists the names of the failure reasons, separated by commas.
+///
 /// [RFC 4210 Section 5.2.3]: https://www.rfc-editor.org/rfc/rfc4210#section-5.2.3
-pub type PkiFailureInfo = FlagSet<PkiFailureInfoValues>;
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PkiFailureInfo(pub FlagSet<PkiFailureInfoValues>);
+
+impl PkiFailureInfo {
+    /// Returns whether `value` is one of the failure reasons
+    pub fn contains(&self, value: PkiFailureInfoValues) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Returns whether no failure reason is set
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the failure reasons
+    pub fn iter(&self) -> impl Iterator<Item = PkiFailureInfoValues> {
+        self.0.into_iter()
+    }
+}
+
+impl From<FlagSet<PkiFailureInfoValues>> for PkiFailureInfo {
+    fn from(flags: FlagSet<PkiFailureInfoValues>) -> Self {
+        Self(flags)
+    }
+}
+
+impl From<PkiFailureInfoValues> for PkiFailureInfo {
+    fn from(value: PkiFailureInfoValues) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<PkiFailureInfo> for FlagSet<PkiFailureInfoValues> {
+    fn from(info: PkiFailureInfo) -> Self {
+        info.0
+    }
+}
+
+impl fmt::Display for PkiFailureInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(value.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> DecodeValue<'a> for PkiFailureInfo {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        FlagSet::decode_value(reader, header).map(Self)
+    }
+}
+
+impl EncodeValue for PkiFailureInfo {
+    fn value_len(&self) -> der::Result<Length> {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.0.encode_value(writer)
+    }
+}
+
+impl FixedTag for PkiFailureInfo {
+    const TAG: Tag = Tag::BitString;
+}
 
 /// The `PKIStatusInfo` type is defined in [RFC 4210 Section 5.2.3].
 ///
@@ -177,6 +315,37 @@ pub struct PkiStatusInfo<'a> {
     pub fail_info: Option<PkiFailureInfo>,
 }
 
+impl PkiStatusInfo<'_> {
+    /// Returns whether the status is `accepted` or `grantedWithMods`
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self.status,
+            PkiStatus::Accepted | PkiStatus::GrantedWithMods
+        )
+    }
+
+    /// Returns whether `value` is one of the failure reasons of the failInfo
+    pub fn has_failure(&self, value: PkiFailureInfoValues) -> bool {
+        self.fail_info.is_some_and(|info| info.contains(value))
+    }
+}
+
+/// Formats the status followed by the failure reasons and status strings, if any, e.g.
+/// `rejection (badCertId): wrong certid`.
+impl fmt::Display for PkiStatusInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.status)?;
+        if let Some(info) = self.fail_info.filter(|info| !info.is_empty()) {
+            write!(f, " ({})", info)?;
+        }
+        for (i, text) in self.status_string.iter().flatten().enumerate() {
+            f.write_str(if i == 0 { ": " } else { "; " })?;
+            f.write_str(text.as_str())?;
+        }
+        Ok(())
+    }
+}
+
 /// The `ErrorMsgContent` type is defined in [RFC 4210 Section 5.2.21].
 ///
 /// ```text
@@ -193,9 +362,9 @@ pub struct PkiStatusInfo<'a> {
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct ErrorMsgContent<'a> {
-    pki_status_info: PkiStatusInfo<'a>,
-    error_code: Option<u64>,
-    error_details: Option<PkiFreeText<'a>>,
+    pub pki_status_info: PkiStatusInfo<'a>,
+    pub error_code: Option<u64>,
+    pub error_details: Option<PkiFreeText<'a>>,
 }
 
 /// The `CertConfirmContent` type is defined in [RFC 4210 Section 5.2.18].
@@ -207,7 +376,8 @@ pub struct ErrorMsgContent<'a> {
 /// [RFC 4210 Section 5.2.18]: https://www.rfc-editor.org/rfc/rfc4210#section-5.2.18
 pub type CertConfirmContent<'a> = Vec<CertStatus<'a>>;
 
-/// The `CertStatus` type is defined in [RFC 4210 Section 5.2.18].
+/// The `CertStatus` type is defined in [RFC 4210 Section 5.2.18] and updated by
+/// [RFC 9480].
 ///
 /// ```text
 ///  CertStatus ::= SEQUENCE {
@@ -216,14 +386,104 @@ pub type CertConfirmContent<'a> = Vec<CertStatus<'a>>;
 ///      -- as is used to create and verify the certificate signature
 ///      certReqId   INTEGER,
 ///      -- to match this confirmation with the corresponding req/rep
-///      statusInfo  PKIStatusInfo OPTIONAL }
+///      statusInfo  PKIStatusInfo OPTIONAL,
+///      hashAlg [0] AlgorithmIdentifier{DIGEST-ALGORITHM, {...}}
+///        OPTIONAL
+///      -- the hash algorithm to use for calculating certHash
+///      -- SHOULD NOT be used in all cases where the AlgorithmIdentifier
+///      -- of the certificate signature specifies a hash algorithm
+///     }
 /// ```
 ///
 /// [RFC 4210 Section 5.2.18]: https://www.rfc-editor.org/rfc/rfc4210#section-5.2.18
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct CertStatus<'a> {
     pub cert_hash: OctetString,
     pub cert_req_id: Int,
     pub status_info: Option<PkiStatusInfo<'a>>,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub hash_alg: Option<AlgorithmIdentifierOwned>,
+}
+
+#[cfg(feature = "digest")]
+mod cert_hash {
+    use super::CertStatus;
+    use crate::header::CmpCertificate;
+    use const_oid::db::{fips202, rfc5912, rfc8410};
+    use const_oid::{AssociatedOid, ObjectIdentifier};
+    use der::asn1::{Int, OctetString};
+    use der::Encode;
+    use digest::Digest;
+    use spki::AlgorithmIdentifierOwned;
+
+    impl CertStatus<'_> {
+        /// Returns the hash algorithm used to compute the certHash of `cert` as described in
+        /// [RFC 4210 Section 5.3.18] and [RFC 9481], i.e. the hash algorithm of
+        /// its signature algorithm.
+        ///
+        /// Returns `None` when the signature algorithm doesn't imply a hash algorithm by its
+        /// OID, e.g. for RSASSA-PSS.
+        ///
+        /// [RFC 4210 Section 5.3.18]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.18
+        /// [RFC 9481]: https://www.rfc-editor.org/rfc/rfc9481
+        pub fn implied_hash_algorithm(cert: &CmpCertificate) -> Option<ObjectIdentifier> {
+            match cert.signature_algorithm.oid {
+                rfc5912::SHA_1_WITH_RSA_ENCRYPTION | rfc5912::DSA_WITH_SHA_1 => {
+                    Some(rfc5912::ID_SHA_1)
+                }
+                rfc5912::SHA_224_WITH_RSA_ENCRYPTION
+                | rfc5912::ECDSA_WITH_SHA_224
+                | rfc5912::DSA_WITH_SHA_224 => Some(rfc5912::ID_SHA_224),
+                rfc5912::SHA_256_WITH_RSA_ENCRYPTION
+                | rfc5912::ECDSA_WITH_SHA_256
+                | rfc5912::DSA_WITH_SHA_256 => Some(rfc5912::ID_SHA_256),
+                rfc5912::SHA_384_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_384 => {
+                    Some(rfc5912::ID_SHA_384)
+                }
+                rfc5912::SHA_512_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_512 => {
+                    Some(rfc5912::ID_SHA_512)
+                }
+                rfc8410::ID_ED_25519 => Some(rfc5912::ID_SHA_512),
+                rfc8410::ID_ED_448 => Some(fips202::ID_SHAKE_256),
+                _ => None,
+            }
+        }
+
+        /// Returns a `CertStatus` confirming `cert`, which was issued in response to the
+        /// request identified by `cert_req_id`, with a certHash computed using the [`Digest`]
+        /// `D`.
+        ///
+        /// The hashAlg is only set when `D` differs from the
+        /// [`CertStatus::implied_hash_algorithm`], in which case the message must be sent with
+        /// [`Pvno::Cmp2021`] as required by [RFC 9480].
+        ///
+        /// [`Pvno::Cmp2021`]: crate::header::Pvno::Cmp2021
+        /// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+        pub fn from_cert<D>(cert: &CmpCertificate, cert_req_id: Int) -> der::Result<Self>
+        where
+            D: Digest + AssociatedOid,
+        {
+            let hash_alg = match Self::implied_hash_algorithm(cert) {
+                Some(oid) if oid == D::OID => None,
+                _ => Some(AlgorithmIdentifierOwned {
+                    oid: D::OID,
+                    parameters: None,
+                }),
+            };
+
+            Ok(Self {
+                cert_hash: OctetString::new(D::digest(cert.to_der()?).to_vec())?,
+                cert_req_id,
+                status_info: None,
+                hash_alg,
+            })
+        }
+    }
 }
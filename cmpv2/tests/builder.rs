@@ -0,0 +1,97 @@
+//! PKIMessage builder tests
+
+#![cfg(feature = "builder")]
+
+use cmpv2::body::PkiBody;
+use cmpv2::builder::{PkiMessageBuilder, NONCE_LENGTH};
+use cmpv2::header::{PkiHeader, Pvno};
+use cmpv2::message::PkiMessage;
+use const_oid::db::rfc5912::ID_IT_IMPLICIT_CONFIRM;
+use core::time::Duration;
+use der::asn1::{GeneralizedTime, Null, OctetString, Utf8StringRef};
+use der::{Decode, Encode};
+use hex_literal::hex;
+use x509_cert::ext::pkix::name::GeneralName;
+
+#[test]
+fn build_ir_req_header() {
+    let header_01 = include_bytes!("examples/ir_req_header_01.bin");
+    let header = PkiHeader::from_der(header_01).unwrap();
+
+    let msg = PkiMessageBuilder::new(
+        GeneralName::DirectoryName("CN=MyName".parse().unwrap()),
+        GeneralName::DirectoryName("CN=CMPserver".parse().unwrap()),
+    )
+    .with_message_time(header.message_time.unwrap())
+    .with_protection_alg(header.protection_alg.clone().unwrap())
+    .with_sender_kid(OctetString::new(*b"1234").unwrap())
+    .with_transaction_id(OctetString::new(hex!("5D8A64219A3253B4FE8673BB2156F04D")).unwrap())
+    .with_sender_nonce(OctetString::new(hex!("89348B3E0661335315C8A264586F0A59")).unwrap())
+    .build(PkiBody::PkiConf(Null));
+
+    assert_eq!(msg.header, header);
+    assert_eq!(msg.header.to_der().unwrap(), header_01);
+    assert!(msg.protection.is_none());
+    assert!(msg.extra_certs.is_none());
+}
+
+#[test]
+fn build_with_rng() {
+    let mut rng = rand::thread_rng();
+    let sender = GeneralName::DirectoryName("CN=MyName".parse().unwrap());
+    let recipient = GeneralName::DirectoryName("CN=CMPserver".parse().unwrap());
+
+    let req = PkiMessageBuilder::new(sender.clone(), recipient.clone())
+        .with_implicit_confirm()
+        .build_with_rng(PkiBody::PkiConf(Null), &mut rng)
+        .unwrap();
+    assert_eq!(req.header.pvno, Pvno::Cmp2000);
+    let trans_id = req.header.trans_id.clone().unwrap();
+    let sender_nonce = req.header.sender_nonce.clone().unwrap();
+    assert_eq!(trans_id.as_bytes().len(), NONCE_LENGTH);
+    assert_eq!(sender_nonce.as_bytes().len(), NONCE_LENGTH);
+    assert_ne!(trans_id, sender_nonce);
+    assert!(req.header.recip_nonce.is_none());
+
+    let general_info = req.header.general_info.as_ref().unwrap();
+    assert_eq!(general_info.len(), 1);
+    assert_eq!(general_info[0].oid, ID_IT_IMPLICIT_CONFIRM);
+    assert!(general_info[0].value.as_ref().unwrap().is_null());
+
+    let encoded = req.to_der().unwrap();
+    assert_eq!(PkiMessage::from_der(&encoded).unwrap(), req);
+
+    // A response continues the transaction and echoes the nonce
+    let rsp = PkiMessageBuilder::response_to(&req.header)
+        .build_with_rng(PkiBody::PkiConf(Null), &mut rng)
+        .unwrap();
+    assert_eq!(rsp.header.sender, recipient);
+    assert_eq!(rsp.header.recipient, sender);
+    assert_eq!(rsp.header.trans_id, Some(trans_id));
+    assert_eq!(rsp.header.recip_nonce, Some(sender_nonce.clone()));
+    assert_ne!(rsp.header.sender_nonce, Some(sender_nonce));
+    assert!(rsp.header.general_info.is_none());
+}
+
+#[test]
+fn build_general_info() {
+    let time = GeneralizedTime::from_unix_duration(Duration::from_secs(1674070261)).unwrap();
+    let profile = Utf8StringRef::new("tls-server").unwrap();
+
+    let msg = PkiMessageBuilder::new(
+        GeneralName::DirectoryName("CN=MyName".parse().unwrap()),
+        GeneralName::DirectoryName("CN=CMPserver".parse().unwrap()),
+    )
+    .with_implicit_confirm()
+    .with_confirm_wait_time(time)
+    .unwrap()
+    .with_cert_profile([profile])
+    .unwrap()
+    .build(PkiBody::PkiConf(Null));
+
+    let der = msg.to_der().unwrap();
+    let msg = PkiMessage::from_der(&der).unwrap();
+    assert!(msg.header.implicit_confirm());
+    assert_eq!(msg.header.confirm_wait_time().unwrap(), Some(time));
+    assert_eq!(msg.header.cert_profile().unwrap(), Some(vec![profile]));
+}
@@ -1,8 +1,11 @@
 use cmpv2::body::PkiBody;
+use cmpv2::certified_key_pair::{CertOrEncCert, CertifiedKeyPair};
 use cmpv2::message::PkiMessage;
 use cmpv2::status::PkiStatus;
 use const_oid::ObjectIdentifier;
+use crmf::controls::{EncryptedKey, EncryptedValue};
 use crmf::pop::ProofOfPossession;
+use der::asn1::BitString;
 use der::{Decode, Encode};
 use hex_literal::hex;
 use x509_cert::ext::pkix::name::GeneralName;
@@ -177,3 +180,69 @@ fn cr_rsp_message_test() {
     println!("Reencoded: {:02X?}", reencoded_req_01);
     assert_eq!(req_01, reencoded_req_01.as_slice());
 }
+
+#[cfg(feature = "digest")]
+#[test]
+fn cr_rsp_cert_conf_test() {
+    use cmpv2::status::CertStatus;
+    use const_oid::db::rfc5912::{ID_SHA_256, ID_SHA_384};
+
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin")).unwrap();
+    let rep = match &message.body {
+        PkiBody::Cp(rep) => rep,
+        _ => panic!(),
+    };
+    let cert = rep.response[0].certificate().unwrap();
+    assert_eq!(CertStatus::implied_hash_algorithm(cert), Some(ID_SHA_256));
+
+    // The hash algorithm of the ecdsa-with-SHA256 signature is implied
+    let statuses = rep.cert_conf::<sha2::Sha256>().unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].cert_req_id.as_bytes(), &[0]);
+    assert_eq!(
+        statuses[0].cert_hash.as_bytes(),
+        hex!("cc4af1606cf936edbe89e2a2946edc357035f55bca5b97b80cdb83d4d8b15ae7")
+    );
+    assert!(statuses[0].hash_alg.is_none());
+    assert!(statuses[0].status_info.is_none());
+
+    let statuses = rep.cert_conf::<sha2::Sha384>().unwrap();
+    assert_eq!(statuses[0].hash_alg.as_ref().unwrap().oid, ID_SHA_384);
+    assert_eq!(
+        statuses[0].cert_hash.as_bytes(),
+        hex!(
+            "37ecefbb2327c7fc061a806bcb0bead563b32dc6ab95a61d887eb8eb5277408d"
+            "2f6084213c4f54807d5c83487278a0b7"
+        )
+    );
+
+    let body = PkiBody::cert_conf(statuses);
+    let der = body.to_der().unwrap();
+    assert_eq!(PkiBody::from_der(&der).unwrap(), body);
+}
+
+#[test]
+fn certified_key_pair_encrypted_value_test() {
+    // the deprecated EncryptedValue of RFC 4210 peers is still decoded as EncryptedKey
+    let cert = Certificate::from_der(include_bytes!("examples/ec384-server-key.der")).unwrap();
+    let value = EncryptedValue {
+        intended_alg: None,
+        sym_alg: None,
+        enc_sym_key: None,
+        key_alg: None,
+        value_hint: None,
+        enc_value: BitString::from_bytes(&[0xAB]).unwrap(),
+    };
+    let key_pair = CertifiedKeyPair {
+        cert_or_enc_cert: CertOrEncCert::Certificate(Box::new(cert)),
+        priv_key: Some(EncryptedKey::from(value.clone())),
+        publication_info: None,
+    };
+    let der = key_pair.to_der().unwrap();
+    assert!(der.ends_with(&hex!("A0063004030200AB")));
+
+    let decoded = CertifiedKeyPair::from_der(&der).unwrap();
+    assert_eq!(decoded, key_pair);
+    let priv_key = decoded.priv_key.unwrap();
+    assert_eq!(priv_key.encrypted_value(), Some(&value));
+}
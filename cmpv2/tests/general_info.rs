@@ -0,0 +1,167 @@
+//! InfoTypeAndValue tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::gen::{
+    CertProfileValue, CrlSource, CrlStatus, InfoTypeAndValue, RootCaKeyUpdateContent,
+    ID_IT_CA_CERTS, ID_IT_CERT_PROFILE, ID_IT_CRLS, ID_IT_CRL_STATUS_LIST, ID_IT_ROOT_CA_CERT,
+    ID_IT_ROOT_CA_KEY_UPDATE,
+};
+use cmpv2::header::PkiHeader;
+use const_oid::db::rfc5912::ID_IT_IMPLICIT_CONFIRM;
+use core::time::Duration;
+use der::asn1::{GeneralizedTime, Utf8StringRef};
+use der::{Decode, Encode};
+use hex_literal::hex;
+use x509_cert::crl::CertificateList;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::Certificate;
+
+#[test]
+fn implicit_confirm() {
+    let info = InfoTypeAndValue::implicit_confirm();
+    let der = info.to_der().unwrap();
+    assert_eq!(der, hex!("300C06082B0601050507040D0500"));
+    assert!(InfoTypeAndValue::from_der(&der)
+        .unwrap()
+        .is_implicit_confirm());
+
+    // Other entries don't carry the values of typed accessors
+    assert_eq!(info.to_confirm_wait_time().unwrap(), None);
+    assert_eq!(info.to_cert_profile().unwrap(), None);
+    assert_eq!(info.to_ca_certs().unwrap(), None);
+}
+
+#[test]
+fn confirm_wait_time_and_cert_profile() {
+    let time = GeneralizedTime::from_unix_duration(Duration::from_secs(1674070261)).unwrap();
+    let info = InfoTypeAndValue::confirm_wait_time(time).unwrap();
+    let info = InfoTypeAndValue::from_der(&info.to_der().unwrap()).unwrap();
+    assert!(!info.is_implicit_confirm());
+    assert_eq!(info.to_confirm_wait_time().unwrap(), Some(time));
+
+    let profiles = [
+        Utf8StringRef::new("tls-server").unwrap(),
+        Utf8StringRef::new("tls-client").unwrap(),
+    ];
+    let info = InfoTypeAndValue::cert_profile(profiles).unwrap();
+    let info = InfoTypeAndValue::from_der(&info.to_der().unwrap()).unwrap();
+    assert_eq!(info.to_cert_profile().unwrap(), Some(profiles.to_vec()));
+
+    // CertProfileValue ::= SEQUENCE SIZE (1..MAX) OF UTF8String
+    assert!(InfoTypeAndValue::cert_profile([]).is_err());
+    let info = InfoTypeAndValue::new(ID_IT_CERT_PROFILE, &CertProfileValue::new()).unwrap();
+    assert!(info.to_cert_profile().is_err());
+}
+
+#[test]
+fn ca_certs() {
+    let request = InfoTypeAndValue::ca_certs_request();
+    assert_eq!(request.oid, ID_IT_CA_CERTS);
+    assert!(request.value.is_none());
+    assert!(request.to_ca_certs().is_err());
+
+    let cert = Certificate::from_der(include_bytes!("examples/ec384-server-key.der")).unwrap();
+    let info = InfoTypeAndValue::ca_certs(&vec![cert.clone()]).unwrap();
+    let info = InfoTypeAndValue::from_der(&info.to_der().unwrap()).unwrap();
+    assert_eq!(info.to_ca_certs().unwrap(), Some(vec![cert]));
+}
+
+#[test]
+fn header_general_info() {
+    let header_01 = include_bytes!("examples/genm_req_header_01.bin");
+    let mut header = PkiHeader::from_der(header_01).unwrap();
+    assert!(!header.implicit_confirm());
+    assert_eq!(header.confirm_wait_time().unwrap(), None);
+    assert_eq!(header.cert_profile().unwrap(), None);
+
+    header
+        .general_info
+        .as_mut()
+        .unwrap()
+        .push(InfoTypeAndValue::implicit_confirm());
+    assert!(header.implicit_confirm());
+    assert_eq!(
+        header.general_info.as_ref().unwrap()[1].oid,
+        ID_IT_IMPLICIT_CONFIRM
+    );
+}
+
+#[test]
+fn root_ca_update() {
+    let old_root = Certificate::from_der(include_bytes!("examples/ec384-server-key.der")).unwrap();
+    let new_root = Certificate::from_der(include_bytes!("examples/ec384-ee-key.der")).unwrap();
+
+    let request = InfoTypeAndValue::root_ca_cert(Some(&old_root)).unwrap();
+    let request = InfoTypeAndValue::from_der(&request.to_der().unwrap()).unwrap();
+    assert_eq!(request.oid, ID_IT_ROOT_CA_CERT);
+    assert_eq!(request.to_root_ca_cert().unwrap(), Some(old_root.clone()));
+    let request = InfoTypeAndValue::root_ca_cert(None).unwrap();
+    assert_eq!(request.to_root_ca_cert().unwrap(), None);
+
+    let update = RootCaKeyUpdateContent {
+        new_with_new: new_root.clone(),
+        new_with_old: None,
+        old_with_new: Some(old_root),
+    };
+    let der = update.to_der().unwrap();
+    assert!(der[4..].starts_with(&new_root.to_der().unwrap()));
+    assert_eq!(RootCaKeyUpdateContent::from_der(&der).unwrap(), update);
+
+    let response = InfoTypeAndValue::root_ca_key_update(Some(&update)).unwrap();
+    let body = PkiBody::GenP(vec![response]);
+    let der = body.to_der().unwrap();
+    let body = PkiBody::from_der(&der).unwrap();
+    let info = match &body {
+        PkiBody::GenP(infos) => &infos[0],
+        _ => panic!(),
+    };
+    assert_eq!(info.oid, ID_IT_ROOT_CA_KEY_UPDATE);
+    assert_eq!(info.to_root_ca_key_update().unwrap(), Some(update));
+    assert_eq!(info.to_root_ca_cert().unwrap(), None);
+
+    // No update is available
+    let response = InfoTypeAndValue::root_ca_key_update(None).unwrap();
+    assert!(response.value.is_none());
+    assert_eq!(response.to_root_ca_key_update().unwrap(), None);
+}
+
+#[test]
+fn crl_update_retrieval() {
+    let crl = CertificateList::from_der(include_bytes!("examples/GoodCACRL.crl")).unwrap();
+
+    let statuses = vec![CrlStatus {
+        source: CrlSource::Issuer(vec![GeneralName::DirectoryName(
+            crl.tbs_cert_list.issuer.clone(),
+        )]),
+        this_update: Some(crl.tbs_cert_list.this_update),
+    }];
+    let request = InfoTypeAndValue::crl_status_list(&statuses).unwrap();
+    let body = PkiBody::GenM(vec![request]);
+    let der = body.to_der().unwrap();
+    let body = PkiBody::from_der(&der).unwrap();
+    let request = match &body {
+        PkiBody::GenM(infos) => &infos[0],
+        _ => panic!(),
+    };
+    assert_eq!(request.oid, ID_IT_CRL_STATUS_LIST);
+    assert_eq!(request.to_crl_status_list().unwrap(), Some(statuses));
+    assert_eq!(request.to_crls().unwrap(), None);
+
+    let response = InfoTypeAndValue::crls(Some(&vec![crl.clone()])).unwrap();
+    let response = InfoTypeAndValue::from_der(&response.to_der().unwrap()).unwrap();
+    assert_eq!(response.oid, ID_IT_CRLS);
+    assert_eq!(response.to_crls().unwrap(), Some(vec![crl]));
+
+    // No newer CRL is available
+    let response = InfoTypeAndValue::crls(None).unwrap();
+    assert_eq!(response.to_crls().unwrap(), None);
+}
+
+#[test]
+fn crl_status_list_empty() {
+    assert!(InfoTypeAndValue::crl_status_list(&Vec::new()).is_err());
+
+    let request = InfoTypeAndValue::new(ID_IT_CRL_STATUS_LIST, &Vec::<CrlStatus>::new()).unwrap();
+    let request = InfoTypeAndValue::from_der(&request.to_der().unwrap()).unwrap();
+    assert!(request.to_crl_status_list().is_err());
+}
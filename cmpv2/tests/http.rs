@@ -0,0 +1,182 @@
+//! HTTP transport tests
+
+#![cfg(feature = "http")]
+
+use cmpv2::http::{AsyncHttpClient, Error, HttpClient, HttpResponse, HttpTransport, CONTENT_TYPE};
+use cmpv2::message::PkiMessage;
+use cmpv2::status::PkiStatus;
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use der::Decode;
+
+const CR_REQ_DER: &[u8] = include_bytes!("examples/cr_req_01.bin");
+const CR_RSP_DER: &[u8] = include_bytes!("examples/cr_rsp_01.bin");
+const IR_REQ_DER: &[u8] = include_bytes!("examples/ir_req_01.bin");
+
+/// Client answering every request with a fixed response
+struct MockClient {
+    response: HttpResponse,
+    requests: Vec<(String, String, Vec<u8>)>,
+}
+
+impl MockClient {
+    fn new(response: HttpResponse) -> Self {
+        Self {
+            response,
+            requests: Vec::new(),
+        }
+    }
+}
+
+impl HttpClient for MockClient {
+    type Error = &'static str;
+
+    fn post(
+        &mut self,
+        uri: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<HttpResponse, Self::Error> {
+        self.requests
+            .push((uri.into(), content_type.into(), body.to_vec()));
+        Ok(self.response.clone())
+    }
+}
+
+impl AsyncHttpClient for MockClient {
+    type Error = &'static str;
+
+    fn post(
+        &mut self,
+        uri: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> impl Future<Output = Result<HttpResponse, Self::Error>> {
+        let response = HttpClient::post(self, uri, content_type, body);
+        async move { response }
+    }
+}
+
+fn cr_rsp() -> HttpResponse {
+    HttpResponse {
+        status: 200,
+        content_type: Some(CONTENT_TYPE.into()),
+        body: CR_RSP_DER.to_vec(),
+    }
+}
+
+/// Polls `future`, which must complete immediately
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    match pin!(future).poll(&mut Context::from_waker(&waker)) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
+
+#[test]
+fn send_cr_req() {
+    let request = PkiMessage::from_der(CR_REQ_DER).unwrap();
+    let mut transport =
+        HttpTransport::new(MockClient::new(cr_rsp()), "http://127.0.0.1:8888/pkix/");
+
+    let response = transport.send(&request).unwrap();
+    assert_eq!(
+        response.message().unwrap(),
+        PkiMessage::from_der(CR_RSP_DER).unwrap()
+    );
+
+    let requests = &transport.client().requests;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, "http://127.0.0.1:8888/pkix/");
+    assert_eq!(requests[0].1, "application/pkixcmp");
+    assert_eq!(requests[0].2, CR_REQ_DER);
+
+    // The content type is matched case-insensitively, ignoring parameters
+    let mut rsp = cr_rsp();
+    rsp.content_type = Some("Application/PKIXCMP; charset=binary".into());
+    let mut transport = HttpTransport::new(MockClient::new(rsp), "http://127.0.0.1/");
+    transport.send(&request).unwrap();
+}
+
+#[test]
+fn send_cr_req_async() {
+    let request = PkiMessage::from_der(CR_REQ_DER).unwrap();
+    let mut transport = HttpTransport::new(MockClient::new(cr_rsp()), "http://127.0.0.1/");
+
+    let response = block_on(transport.send_async(&request)).unwrap();
+    assert_eq!(response.body, CR_RSP_DER);
+}
+
+#[test]
+fn invalid_responses() {
+    let request = PkiMessage::from_der(CR_REQ_DER).unwrap();
+    let send = |rsp: HttpResponse, request: &PkiMessage<'_>| {
+        HttpTransport::new(MockClient::new(rsp), "http://127.0.0.1/")
+            .send(request)
+            .unwrap_err()
+    };
+
+    let mut rsp = cr_rsp();
+    rsp.status = 500;
+    assert!(matches!(send(rsp, &request), Error::Status(500)));
+
+    let mut rsp = cr_rsp();
+    rsp.content_type = Some("text/html".into());
+    assert!(matches!(send(rsp, &request), Error::ContentType(Some(_))));
+
+    let mut rsp = cr_rsp();
+    rsp.content_type = None;
+    assert!(matches!(send(rsp, &request), Error::ContentType(None)));
+
+    let mut rsp = cr_rsp();
+    rsp.body.truncate(100);
+    assert!(matches!(send(rsp, &request), Error::Asn1(_)));
+
+    // The response doesn't answer a different transaction
+    let other = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    assert!(matches!(send(cr_rsp(), &other), Error::TransactionId));
+
+    let mut replayed = request.clone();
+    replayed.header.sender_nonce = other.header.sender_nonce;
+    assert!(matches!(send(cr_rsp(), &replayed), Error::RecipNonce));
+}
+
+#[test]
+fn error_message_response() {
+    let rsp = HttpResponse {
+        status: 200,
+        content_type: Some(CONTENT_TYPE.into()),
+        body: include_bytes!("examples/failed_kur_rsp_01.bin").to_vec(),
+    };
+
+    // The example error message answers a different request than the example kur
+    let header = rsp.message().unwrap().header;
+    let mut request = PkiMessage::from_der(include_bytes!("examples/kur_req_01.bin")).unwrap();
+    request.header.trans_id = header.trans_id;
+    request.header.sender_nonce = header.recip_nonce;
+
+    let mut transport = HttpTransport::new(MockClient::new(rsp.clone()), "http://127.0.0.1/");
+    assert!(matches!(
+        transport.send(&request),
+        Err(Error::ErrorMessage {
+            status: PkiStatus::Rejection,
+            fail_info: Some(_),
+        })
+    ));
+
+    // Error messages must answer the request as well
+    let other = PkiMessage::from_der(CR_REQ_DER).unwrap();
+    let mut transport = HttpTransport::new(MockClient::new(rsp), "http://127.0.0.1/");
+    assert!(matches!(transport.send(&other), Err(Error::TransactionId)));
+}
@@ -284,3 +284,22 @@ fn ir_rsp_message_test() {
     println!("Reencoded: {:02X?}", reencoded_req_01);
     assert_eq!(req_01, reencoded_req_01.as_slice());
 }
+
+#[test]
+fn ir_req_body_constructor_test() {
+    let body_01 = include_bytes!("examples/ir_req_body_01.bin");
+    let body = PkiBody::from_der(body_01).unwrap();
+    let request = body.cert_req_messages().unwrap()[0].clone();
+
+    let ir = PkiBody::ir([request.clone()]);
+    assert_eq!(ir, body);
+    assert_eq!(ir.to_der().unwrap(), body_01);
+
+    assert!(matches!(PkiBody::cr([request.clone()]), PkiBody::Cr(_)));
+    assert!(matches!(PkiBody::kur([request.clone()]), PkiBody::KUr(_)));
+    assert_eq!(
+        PkiBody::kur([request.clone()]).cert_req_messages(),
+        Some(&vec![request])
+    );
+    assert!(PkiBody::rr([]).cert_req_messages().is_none());
+}
@@ -0,0 +1,101 @@
+//! Lightweight CMP Profile tests
+
+#![cfg(feature = "lcmpp")]
+
+use cmpv2::body::PkiBody;
+use cmpv2::header::Pvno;
+use cmpv2::lcmpp::{validate, validate_unprotected, Violation};
+use cmpv2::message::PkiMessage;
+use cmpv2::pop::PopoDecKeyChallContent;
+use der::asn1::{Ia5String, OctetString};
+use der::{Decode, Tag, TagNumber};
+use x509_cert::ext::pkix::name::GeneralName;
+
+const IR_REQ_DER: &[u8] = include_bytes!("examples/ir_req_01.bin");
+const IR_RSP_DER: &[u8] = include_bytes!("examples/ir_rsp_01.bin");
+
+#[test]
+fn validate_examples() {
+    let messages: [&[u8]; 13] = [
+        include_bytes!("examples/cr_req_01.bin"),
+        include_bytes!("examples/cr_rsp_01.bin"),
+        include_bytes!("examples/failed_kur_rsp_01.bin"),
+        include_bytes!("examples/genm_req_01.bin"),
+        include_bytes!("examples/genm_rsp_01.bin"),
+        IR_REQ_DER,
+        IR_RSP_DER,
+        include_bytes!("examples/kur_req_01.bin"),
+        include_bytes!("examples/kur_rsp_01.bin"),
+        include_bytes!("examples/p10cr_req_01.bin"),
+        include_bytes!("examples/p10cr_rsp_01.bin"),
+        include_bytes!("examples/rr_req_01.bin"),
+        include_bytes!("examples/rr_rsp_01.bin"),
+    ];
+
+    for der in messages {
+        let message = PkiMessage::from_der(der).unwrap();
+        assert_eq!(validate(&message), Ok(()));
+        assert_eq!(validate_unprotected(&message), Ok(()));
+    }
+}
+
+#[test]
+fn header_violations() {
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    message.header.pvno = Pvno::Cmp1999;
+    message.header.sender = GeneralName::DnsName(Ia5String::new("example.com").unwrap());
+    message.header.sender_kid = None;
+    message.header.trans_id = Some(OctetString::new([0; 8]).unwrap());
+    message.header.sender_nonce = None;
+
+    assert_eq!(
+        validate(&message),
+        Err(vec![
+            Violation::Pvno(Pvno::Cmp1999),
+            Violation::Sender,
+            Violation::TransactionIdLength(8),
+            Violation::MissingSenderNonce,
+            Violation::MissingSenderKid,
+        ])
+    );
+
+    // Responses reference the request
+    let mut message = PkiMessage::from_der(IR_RSP_DER).unwrap();
+    message.header.recip_nonce = None;
+    assert_eq!(validate(&message), Err(vec![Violation::MissingRecipNonce]));
+}
+
+#[test]
+fn protection_violations() {
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    message.protection = None;
+    assert_eq!(validate(&message), Err(vec![Violation::MissingProtection]));
+    assert_eq!(validate_unprotected(&message), Ok(()));
+
+    message.header.protection_alg = None;
+    let expected = vec![Violation::MissingProtectionAlg];
+    assert_eq!(validate_unprotected(&message), Err(expected));
+
+    // Signature-protected requests carry the protection certificate
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    let rsp = PkiMessage::from_der(IR_RSP_DER).unwrap();
+    message.header.protection_alg = rsp.header.protection_alg;
+    assert_eq!(validate(&message), Err(vec![Violation::MissingExtraCerts]));
+}
+
+#[test]
+fn body_violations() {
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    let request = message.body.cert_req_messages().unwrap()[0].clone();
+    message.body = PkiBody::ir([request.clone(), request]);
+    assert_eq!(validate(&message), Err(vec![Violation::EntryCount(2)]));
+
+    message.body = PkiBody::Popdecc(PopoDecKeyChallContent::new());
+    assert_eq!(
+        validate(&message),
+        Err(vec![Violation::UnsupportedBody(Tag::ContextSpecific {
+            constructed: true,
+            number: TagNumber::new(5),
+        })])
+    );
+}
@@ -0,0 +1,95 @@
+//! Nested message tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::message::PkiMessage;
+use der::{Decode, Encode, ErrorKind};
+
+const IR_REQ_DER: &[u8] = include_bytes!("examples/ir_req_01.bin");
+const CR_REQ_DER: &[u8] = include_bytes!("examples/cr_req_01.bin");
+
+fn wrap<'a>(messages: impl IntoIterator<Item = PkiMessage<'a>>) -> PkiMessage<'a> {
+    let mut header = PkiMessage::from_der(IR_REQ_DER).unwrap().header;
+    header.protection_alg = None;
+
+    PkiMessage {
+        header,
+        body: PkiBody::nested(messages),
+        protection: None,
+        extra_certs: None,
+    }
+}
+
+#[test]
+fn nested_message_test() {
+    let ir = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    let cr = PkiMessage::from_der(CR_REQ_DER).unwrap();
+    assert!(ir.nested_messages().is_none());
+
+    let nested = wrap([ir.clone(), cr.clone()]);
+    assert_eq!(
+        nested.nested_messages(),
+        Some(&[ir.clone(), cr.clone()][..])
+    );
+
+    // The wrapped messages are encoded unchanged, keeping their protection intact
+    let der = nested.to_der().unwrap();
+    let wrapped = [IR_REQ_DER, CR_REQ_DER].concat();
+    assert!(der.windows(wrapped.len()).any(|w| w == wrapped));
+
+    let decoded = PkiMessage::from_der(&der).unwrap();
+    assert_eq!(decoded, nested);
+    assert_eq!(decoded.to_der().unwrap(), der);
+
+    let messages = decoded.into_nested_messages().unwrap();
+    assert_eq!(messages, vec![ir.clone(), cr]);
+    assert_eq!(ir.into_nested_messages(), None);
+}
+
+#[test]
+fn doubly_nested_message_test() {
+    let ir = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    let nested = wrap([wrap([ir.clone()])]);
+
+    let der = nested.to_der().unwrap();
+
+    let decoded = PkiMessage::from_der(&der).unwrap();
+    assert_eq!(decoded, nested);
+
+    let inner = &decoded.nested_messages().unwrap()[0];
+    assert_eq!(inner.nested_messages(), Some(&[ir][..]));
+}
+
+#[test]
+fn deeply_nested_message_test() {
+    let mut nested = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    for _ in 0..64 {
+        nested = wrap([nested]);
+    }
+
+    let der = nested.to_der().unwrap();
+    let err = PkiMessage::from_der(&der).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 64 });
+}
+
+#[cfg(feature = "pbm")]
+#[test]
+fn nested_message_protection_test() {
+    // secret used by the openssl cmp client when capturing the example requests
+    const PASSWORD: &[u8] = b"1234-5678-1234-5678";
+
+    let ir = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    let mut nested = wrap([ir]);
+    nested.header.protection_alg = PkiMessage::from_der(CR_REQ_DER)
+        .unwrap()
+        .header
+        .protection_alg;
+    nested.protect_with_password(b"ra secret").unwrap();
+
+    let der = nested.to_der().unwrap();
+
+    let decoded = PkiMessage::from_der(&der).unwrap();
+    decoded.verify_password(b"ra secret").unwrap();
+    for message in decoded.into_nested_messages().unwrap() {
+        message.verify_password(PASSWORD).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+//! Polling tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::message::PkiMessage;
+use cmpv2::poll::{PollRep, PollState};
+use cmpv2::status::PkiStatus;
+use core::time::Duration;
+use der::asn1::{Int, Utf8StringRef};
+use der::{Decode, Encode};
+use hex_literal::hex;
+
+#[test]
+fn poll_req_test() {
+    let body = PkiBody::poll_req([Int::new(&[0]).unwrap()]);
+    let der = body.to_der().unwrap();
+    assert_eq!(der, hex!("B90730053003020100"));
+    assert_eq!(PkiBody::from_der(&der).unwrap(), body);
+}
+
+#[test]
+fn poll_rep_test() {
+    let body = PkiBody::poll_rep([PollRep::new(Int::new(&[0]).unwrap(), 60)]);
+    let der = body.to_der().unwrap();
+    assert_eq!(der, hex!("BA0A3008300602010002013C"));
+    assert_eq!(PkiBody::from_der(&der).unwrap(), body);
+
+    let reason = vec![Utf8StringRef::new("pending approval").unwrap()];
+    let rep = PollRep::new(Int::new(&[1]).unwrap(), 5).with_reason(reason);
+    assert_eq!(rep.check_after_duration(), Duration::from_secs(5));
+    let body = PkiBody::poll_rep([rep]);
+    assert_eq!(PkiBody::from_der(&body.to_der().unwrap()).unwrap(), body);
+}
+
+#[test]
+fn poll_state_test() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin")).unwrap();
+    let id = Int::new(&[0]).unwrap();
+
+    let mut waiting = message.body.clone();
+    match &mut waiting {
+        PkiBody::Cp(rep) => {
+            rep.response[0].status.status = PkiStatus::Waiting;
+            rep.response[0].certified_key_pair = None;
+        }
+        _ => panic!(),
+    }
+
+    let mut state = PollState::new();
+    assert!(state.is_empty());
+    assert_eq!(state.check_after(), None);
+
+    // A waiting response starts polling right away
+    state.update(&waiting);
+    assert!(state.is_pending(&id));
+    assert_eq!(state.check_after(), Some(Duration::ZERO));
+    assert_eq!(state.poll_req(), PkiBody::poll_req([id.clone()]));
+
+    let other = Int::new(&[1]).unwrap();
+    state.update(&PkiBody::poll_rep([
+        PollRep::new(id.clone(), 30),
+        PollRep::new(other.clone(), 10),
+    ]));
+    assert_eq!(state.check_after_for(&id), Some(Duration::from_secs(30)));
+    assert_eq!(state.check_after(), Some(Duration::from_secs(10)));
+    assert_eq!(state.pending().count(), 2);
+
+    // The final response ends polling for its certReqId
+    state.update(&message.body);
+    assert!(!state.is_pending(&id));
+    assert_eq!(state.pending().collect::<Vec<_>>(), vec![&other]);
+}
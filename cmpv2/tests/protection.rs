@@ -0,0 +1,154 @@
+//! PKIMessage protection tests
+
+#![cfg(feature = "pbm")]
+
+use cmpv2::message::{PkiMessage, SpannedPkiMessage};
+use cmpv2::parameter::{PbmParameter, Pbmac1Params};
+use cmpv2::protection::Error;
+use const_oid::db::rfc5912::{ID_PASSWORD_BASED_MAC, ID_SHA_256};
+use const_oid::db::rfc6268::{ID_HMAC_WITH_SHA_256, ID_HMAC_WITH_SHA_512};
+use der::asn1::{Any, OctetString};
+use der::{Decode, Encode};
+use hex_literal::hex;
+use pkcs5::pbes2::{Pbkdf2Params, PBKDF2_OID};
+use spki::AlgorithmIdentifierOwned;
+
+// secret used by the openssl cmp client when capturing the example requests
+const PASSWORD: &[u8] = b"1234-5678-1234-5678";
+
+const SALT: [u8; 16] = hex!("000102030405060708090A0B0C0D0E0F");
+
+fn pbmac1_params(rounds: u32, key_length: Option<u16>) -> Pbmac1Params {
+    let mut kdf = Pbkdf2Params::hmac_with_sha256(rounds, &SALT).unwrap();
+    kdf.key_length = key_length;
+
+    Pbmac1Params {
+        key_derivation_func: AlgorithmIdentifierOwned {
+            oid: PBKDF2_OID,
+            parameters: Some(Any::encode_from(&kdf).unwrap()),
+        },
+        message_auth_scheme: AlgorithmIdentifierOwned {
+            oid: ID_HMAC_WITH_SHA_256,
+            parameters: None,
+        },
+    }
+}
+
+#[test]
+fn verify_pbm_requests() {
+    let requests: [&[u8]; 6] = [
+        include_bytes!("examples/cr_req_01.bin"),
+        include_bytes!("examples/genm_req_01.bin"),
+        include_bytes!("examples/ir_req_01.bin"),
+        include_bytes!("examples/kur_req_01.bin"),
+        include_bytes!("examples/p10cr_req_01.bin"),
+        include_bytes!("examples/rr_req_01.bin"),
+    ];
+
+    for der in requests {
+        let message = PkiMessage::from_der(der).unwrap();
+        assert_eq!(
+            message.header.protection_alg.as_ref().unwrap().oid,
+            ID_PASSWORD_BASED_MAC
+        );
+        message.verify_password(PASSWORD).unwrap();
+        assert!(matches!(
+            message.verify_password(b"1234-5678-1234-5679"),
+            Err(Error::Verification)
+        ));
+
+        // Recomputing the protection yields the original message
+        let mut reprotected = message.clone();
+        reprotected.protection = None;
+        assert!(matches!(
+            reprotected.verify_password(PASSWORD),
+            Err(Error::MissingProtection)
+        ));
+        reprotected.protect_with_password(PASSWORD).unwrap();
+        assert_eq!(reprotected.to_der().unwrap(), der);
+
+        // Verifying over the original encoding agrees with the re-encoded one
+        let spanned = SpannedPkiMessage::from_der(der).unwrap();
+        assert_eq!(
+            spanned.protected_part_der().unwrap(),
+            message.protected_part_der().unwrap()
+        );
+        spanned.verify_password(PASSWORD).unwrap();
+        assert!(matches!(
+            spanned.verify_password(b"1234-5678-1234-5679"),
+            Err(Error::Verification)
+        ));
+        assert_eq!(PkiMessage::from(spanned), message);
+    }
+}
+
+#[test]
+fn pbm_parameter_mac() {
+    let params = PbmParameter {
+        salt: OctetString::new(SALT).unwrap(),
+        owf: AlgorithmIdentifierOwned {
+            oid: ID_SHA_256,
+            parameters: None,
+        },
+        iteration_count: 1000,
+        mac: AlgorithmIdentifierOwned {
+            oid: ID_HMAC_WITH_SHA_512,
+            parameters: None,
+        },
+    };
+    assert_eq!(
+        params.mac(PASSWORD, b"protected part").unwrap(),
+        hex!(
+            "8028db6f2e4a01dd9864906ada7dd8c59b8581ca5233e7b2d7bd76ef17265906"
+            "192e24748a079477de7543d02ece277a5bd7059b103e16d836fc11f848c24f7e"
+        )
+    );
+
+    let mut unsupported = params.clone();
+    unsupported.owf.oid = ID_HMAC_WITH_SHA_256;
+    assert!(matches!(
+        unsupported.mac(PASSWORD, b"protected part"),
+        Err(Error::UnsupportedAlgorithm(oid)) if oid == ID_HMAC_WITH_SHA_256
+    ));
+
+    let mut unbounded = params;
+    unbounded.iteration_count = PbmParameter::MAX_ITERATION_COUNT + 1;
+    assert!(matches!(
+        unbounded.mac(PASSWORD, b"protected part"),
+        Err(Error::Asn1(_))
+    ));
+}
+
+#[test]
+fn pbmac1_protection() {
+    let params = pbmac1_params(10000, Some(32));
+    assert_eq!(
+        params.mac(PASSWORD, b"protected part").unwrap(),
+        hex!("c85d4bffb38f58f6278ec864dbb35a7b86b2968797f848f1f41b3615df0ad2a8")
+    );
+
+    // PBMAC1 requires an explicit key length
+    assert!(matches!(
+        pbmac1_params(10000, None).mac(PASSWORD, b"protected part"),
+        Err(Error::Asn1(_))
+    ));
+
+    let mut message = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    message.header.protection_alg = Some(params.to_algorithm_identifier().unwrap());
+    assert!(matches!(
+        message.verify_password(PASSWORD),
+        Err(Error::Verification)
+    ));
+
+    message.protect_with_password(PASSWORD).unwrap();
+    message.verify_password(PASSWORD).unwrap();
+    assert!(matches!(
+        message.verify_password(b"password"),
+        Err(Error::Verification)
+    ));
+
+    let der = message.to_der().unwrap();
+
+    let message = PkiMessage::from_der(&der).unwrap();
+    message.verify_password(PASSWORD).unwrap();
+}
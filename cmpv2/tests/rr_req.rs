@@ -1,7 +1,11 @@
 use cmpv2::body::PkiBody;
 use cmpv2::header::{PkiHeader, Pvno};
 use cmpv2::message::PkiMessage;
+use cmpv2::rev::RevDetails;
+use const_oid::db::rfc5280::ID_CE_CRL_REASONS;
 use der::{Decode, Encode};
+use x509_cert::ext::pkix::CrlReason;
+use x509_cert::Certificate;
 
 #[test]
 fn rr_rsp_header_test() {
@@ -80,3 +84,42 @@ fn rr_rsp_message_test() {
     println!("Reencoded: {:02X?}", reencoded_req_01);
     assert_eq!(req_01, reencoded_req_01.as_slice());
 }
+
+#[test]
+fn rr_req_body_constructor_test() {
+    let req_01 = include_bytes!("examples/rr_req_01.bin");
+    let message = PkiMessage::from_der(req_01).unwrap();
+    let details = match &message.body {
+        PkiBody::Rr(details) => &details[0],
+        _ => panic!(),
+    };
+
+    // openssl identifies the certificate by issuer and serial number only
+    let template = &details.cert_details;
+    let rev_details = RevDetails::new(
+        template.issuer.clone().unwrap(),
+        template.serial_number.clone().unwrap(),
+    );
+    assert_eq!(PkiBody::rr([rev_details.clone()]), message.body);
+
+    let rev_details = rev_details.with_reason(CrlReason::KeyCompromise).unwrap();
+    let ext = &rev_details.crl_entry_details.as_ref().unwrap()[0];
+    assert_eq!(ext.extn_id, ID_CE_CRL_REASONS);
+    assert!(!ext.critical);
+    assert_eq!(
+        CrlReason::from_der(ext.extn_value.as_bytes()).unwrap(),
+        CrlReason::KeyCompromise
+    );
+
+    let cert = Certificate::from_der(include_bytes!("examples/ec384-ee-key.der")).unwrap();
+    let rev_details = RevDetails::from_cert(&cert);
+    assert_eq!(
+        rev_details.cert_details.serial_number,
+        Some(cert.tbs_certificate.serial_number.clone())
+    );
+    assert_eq!(
+        rev_details.cert_details.issuer,
+        Some(cert.tbs_certificate.issuer.clone())
+    );
+    assert!(rev_details.crl_entry_details.is_none());
+}
@@ -0,0 +1,105 @@
+//! Signature-based protection tests
+
+#![cfg(feature = "signature")]
+
+use cmpv2::message::{PkiMessage, SpannedPkiMessage};
+use cmpv2::protection::Error;
+use const_oid::db::rfc5912::ECDSA_WITH_SHA_256;
+use der::{Decode, Encode};
+use p256::ecdsa::{DerSignature, SigningKey, VerifyingKey};
+use p256::pkcs8::EncodePublicKey;
+use spki::SubjectPublicKeyInfoOwned;
+use x509_cert::Certificate;
+
+const IR_REQ_DER: &[u8] = include_bytes!("examples/ir_req_01.bin");
+const SERVER_CERT_DER: &[u8] = include_bytes!("examples/ec384-server-key.der");
+
+fn spki(key: &VerifyingKey) -> SubjectPublicKeyInfoOwned {
+    SubjectPublicKeyInfoOwned::from_der(key.to_public_key_der().unwrap().as_bytes()).unwrap()
+}
+
+/// Returns a (not validly signed) certificate for `key`
+fn cert_for(key: &VerifyingKey) -> Certificate {
+    let mut cert = Certificate::from_der(SERVER_CERT_DER).unwrap();
+    cert.tbs_certificate.subject_public_key_info = spki(key);
+    cert
+}
+
+#[test]
+fn sign_and_verify() {
+    let signer = SigningKey::random(&mut rand::thread_rng());
+    let cert = cert_for(signer.verifying_key());
+
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    assert!(message.protection_cert().is_none());
+    assert!(matches!(
+        message.verify_with_protection_cert::<VerifyingKey, DerSignature>(),
+        Err(Error::MissingProtection)
+    ));
+
+    message
+        .sign::<_, DerSignature>(&signer, vec![cert.clone()])
+        .unwrap();
+    assert_eq!(
+        message.header.protection_alg.as_ref().unwrap().oid,
+        ECDSA_WITH_SHA_256
+    );
+    assert_eq!(message.protection_cert(), Some(&cert));
+    message
+        .verify_with_protection_cert::<VerifyingKey, DerSignature>()
+        .unwrap();
+
+    let der = message.to_der().unwrap();
+    let message = PkiMessage::from_der(&der).unwrap();
+    message
+        .verify_signature_with::<VerifyingKey, DerSignature>(&spki(signer.verifying_key()))
+        .unwrap();
+
+    // Verifying over the original encoding agrees with the re-encoded one
+    let spanned = SpannedPkiMessage::from_der(&der).unwrap();
+    assert_eq!(spanned.protection_cert(), Some(&cert));
+    spanned
+        .verify_with_protection_cert::<VerifyingKey, DerSignature>()
+        .unwrap();
+    spanned
+        .verify_signature_with::<VerifyingKey, DerSignature>(&spki(signer.verifying_key()))
+        .unwrap();
+
+    // The signature covers the header
+    let mut tampered = message.clone();
+    tampered.header.sender_nonce = None;
+    assert!(matches!(
+        tampered.verify_with_protection_cert::<VerifyingKey, DerSignature>(),
+        Err(Error::Signature(_))
+    ));
+
+    let other = SigningKey::random(&mut rand::thread_rng());
+    assert!(matches!(
+        message.verify_signature_with::<VerifyingKey, DerSignature>(&spki(other.verifying_key())),
+        Err(Error::Signature(_))
+    ));
+    assert!(matches!(
+        spanned.verify_signature_with::<VerifyingKey, DerSignature>(&spki(other.verifying_key())),
+        Err(Error::Signature(_))
+    ));
+}
+
+#[test]
+fn sign_with_rng_extra_certs() {
+    let mut rng = rand::thread_rng();
+    let signer = SigningKey::random(&mut rng);
+    let cert = cert_for(signer.verifying_key());
+    let other = Certificate::from_der(SERVER_CERT_DER).unwrap();
+
+    let mut message = PkiMessage::from_der(IR_REQ_DER).unwrap();
+    message.extra_certs = Some(vec![other.clone()]);
+    message
+        .sign_with_rng::<_, DerSignature>(&signer, &mut rng, vec![cert.clone()])
+        .unwrap();
+
+    // The protection certificate comes first
+    assert_eq!(message.extra_certs, Some(vec![cert, other]));
+    message
+        .verify_with_protection_cert::<VerifyingKey, DerSignature>()
+        .unwrap();
+}
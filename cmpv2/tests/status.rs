@@ -0,0 +1,60 @@
+//! Status tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::message::PkiMessage;
+use cmpv2::status::{PkiFailureInfo, PkiFailureInfoValues, PkiStatus, PkiStatusInfo};
+use der::{Decode, Encode};
+use hex_literal::hex;
+
+#[test]
+fn fail_info_encoding() {
+    let info =
+        PkiFailureInfo::from(PkiFailureInfoValues::BadTime | PkiFailureInfoValues::BadCertId);
+    let der = info.to_der().unwrap();
+    assert_eq!(der, hex!("03020318"));
+    assert_eq!(PkiFailureInfo::from_der(&der).unwrap(), info);
+    assert!(info.contains(PkiFailureInfoValues::BadTime));
+    assert!(!info.contains(PkiFailureInfoValues::BadRequest));
+    assert_eq!(info.to_string(), "badTime, badCertId");
+
+    let info = PkiFailureInfo::from(PkiFailureInfoValues::SystemFailure);
+    let der = info.to_der().unwrap();
+    assert_eq!(der, hex!("03050600000040"));
+    assert_eq!(PkiFailureInfo::from_der(&der).unwrap(), info);
+    assert_eq!(
+        info.iter().collect::<Vec<_>>(),
+        vec![PkiFailureInfoValues::SystemFailure]
+    );
+}
+
+#[test]
+fn failed_kur_rsp_status() {
+    let der = include_bytes!("examples/failed_kur_rsp_01.bin");
+    let message = PkiMessage::from_der(der).unwrap();
+    let status_info = match &message.body {
+        PkiBody::Error(content) => &content.pki_status_info,
+        _ => panic!(),
+    };
+
+    assert_eq!(status_info.status, PkiStatus::Rejection);
+    assert!(!status_info.is_success());
+    assert!(status_info.has_failure(PkiFailureInfoValues::BadRequest));
+    assert!(!status_info.has_failure(PkiFailureInfoValues::BadCertId));
+    assert_eq!(
+        status_info.to_string(),
+        "rejection (badRequest): wrong certid"
+    );
+    assert_eq!(message.to_der().unwrap(), der);
+}
+
+#[test]
+fn status_info_display() {
+    let status_info = PkiStatusInfo {
+        status: PkiStatus::Accepted,
+        status_string: None,
+        fail_info: None,
+    };
+    assert!(status_info.is_success());
+    assert_eq!(status_info.to_string(), "accepted");
+    assert_eq!(PkiStatus::GrantedWithMods.to_string(), "grantedWithMods");
+}
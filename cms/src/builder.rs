@@ -454,15 +454,14 @@ impl<'s> SignedDataBuilder<'s> {
         } else {
             false
         };
-        // v1 and v2 currently not supported
-        // let v2_certificates_are_present = if let Some(certificates) = &self.certificates {
-        //     certificates.iter().any(|certificate| match certificate {
-        //         CertificateChoices::V2AttrCert(_) => true,
-        //         _ => false,
-        //     })
-        // } else {
-        //     false
-        // };
+        let v2_certificates_are_present = if let Some(certificates) = &self.certificates {
+            certificates
+                .iter()
+                .any(|certificate| matches!(certificate, CertificateChoices::V2AttrCert(_)))
+        } else {
+            false
+        };
+        // v1 currently not supported
         // let v1_certificates_are_present = if let Some(certificates) = &self.certificates {
         //     certificates.iter().any(|certificate| match certificate {
         //         CertificateChoices::V1AttrCert(_) => true,
@@ -471,7 +470,6 @@ impl<'s> SignedDataBuilder<'s> {
         // } else {
         //     false
         // };
-        let v2_certificates_are_present = false;
         let v1_certificates_are_present = false;
         let other_crls_are_present = if let Some(crls) = &self.crls {
             crls.iter().any(|revocation_info_choice| {
@@ -929,19 +927,18 @@ impl<'c> EnvelopedDataBuilder<'c> {
         } else {
             false
         };
-        // v2 certificates currently not supported
-        // let v2_certificates_present = if let Some(certificate_option) = &self.originator_info {
-        //     if let Some(certificates) = certificate_option {
-        //         certificates
-        //             .iter()
-        //             .any(|certificate| matches!(certificate, CertificateChoices::V2AttrCert))
-        //     } else {
-        //         false
-        //     }
-        // } else {
-        //     false
-        // };
-        let v2_certificates_present = false;
+        let v2_certificates_present = if let Some(originator_info) = &self.originator_info {
+            if let Some(certificates) = &originator_info.certs {
+                certificates
+                    .0
+                    .iter()
+                    .any(|certificate| matches!(certificate, CertificateChoices::V2AttrCert(_)))
+            } else {
+                false
+            }
+        } else {
+            false
+        };
         let pwri_recipient_info_present = self.recipient_infos.iter().any(|recipient_info| {
             matches!(
                 recipient_info.recipient_info_type(),
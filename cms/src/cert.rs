@@ -4,12 +4,13 @@ pub use x509_cert as x509;
 
 use core::cmp::Ordering;
 use der::{asn1::ObjectIdentifier, Any, Choice, Sequence, ValueOrd};
+use x509_cert::attr_cert::AttributeCertificate;
 use x509_cert::name::Name;
 use x509_cert::serial_number::SerialNumber;
 use x509_cert::Certificate;
 
-/// The `CertificateChoices` type is defined in [RFC 5652 Section 10.2.2]. Attribute certificate
-/// support is not presently implemented.
+/// The `CertificateChoices` type is defined in [RFC 5652 Section 10.2.2]. Only version 2
+/// attribute certificates are supported.
 ///
 /// ```text
 ///   CertificateChoices ::= CHOICE {
@@ -29,9 +30,10 @@ use x509_cert::Certificate;
 #[allow(clippy::large_enum_variant)]
 pub enum CertificateChoices {
     Certificate(Certificate),
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", constructed = "true")]
+    V2AttrCert(AttributeCertificate),
     #[asn1(context_specific = "3", tag_mode = "EXPLICIT", constructed = "true")]
     Other(OtherCertificateFormat),
-    // TODO DEFER add more choices if desired (i.e., AttributeCertificateV2)
 }
 
 // TODO DEFER ValueOrd is not supported for CHOICE types (see new_enum in value_ord.rs)
@@ -43,9 +45,6 @@ impl ValueOrd for CertificateChoices {
     }
 }
 
-// TODO DEFER implement support for attribute certs if desired
-//   AttributeCertificateV2 ::= AttributeCertificate
-
 /// The `OtherCertificateFormat` type is defined in [RFC 5652 Section 10.2.2].
 ///
 /// ```text
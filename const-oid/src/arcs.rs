@@ -1,7 +1,6 @@
 //! Arcs are integer values which exist within an OID's hierarchy.
 
 use crate::{Error, Result};
-use core::mem;
 
 #[cfg(doc)]
 use crate::ObjectIdentifier;
@@ -24,12 +23,6 @@ pub(crate) const ARC_MAX_FIRST: Arc = 2;
 /// Maximum value of the second arc in an OID.
 pub(crate) const ARC_MAX_SECOND: Arc = 39;
 
-/// Maximum number of bytes supported in an arc.
-const ARC_MAX_BYTES: usize = mem::size_of::<Arc>();
-
-/// Maximum value of the last byte in an arc.
-const ARC_MAX_LAST_OCTET: u8 = 0b11110000; // Max bytes of leading 1-bits
-
 /// [`Iterator`] over [`Arc`] values (a.k.a. nodes) in an [`ObjectIdentifier`].
 ///
 /// This iterates over all arcs in an OID, including the root.
@@ -76,14 +69,14 @@ impl<'a> Arcs<'a> {
                     let len = checked_add!(offset, arc_bytes);
 
                     match self.bytes.get(len).cloned() {
-                        // The arithmetic below includes advance checks
-                        // against `ARC_MAX_BYTES` and `ARC_MAX_LAST_OCTET`
-                        // which ensure the operations will not overflow.
+                        // The arithmetic below includes an advance check
+                        // that the shifted value fits in an `Arc`, which
+                        // ensures the operations will not overflow.
                         #[allow(clippy::arithmetic_side_effects)]
                         Some(byte) => {
                             arc_bytes = checked_add!(arc_bytes, 1);
 
-                            if (arc_bytes > ARC_MAX_BYTES) && (byte & ARC_MAX_LAST_OCTET != 0) {
+                            if result > Arc::MAX >> 7 {
                                 return Err(Error::ArcTooBig);
                             }
 
@@ -112,6 +112,22 @@ fn from_str() {
     );
 }
 
+#[test]
+fn max_arc() {
+    let oid = ObjectIdentifier::from_arcs([1, 2, u32::MAX]).unwrap();
+    assert_eq!(oid.as_bytes(), &hex!("2A8FFFFFFF7F"));
+    assert_eq!(oid.arcs().collect::<Vec<_>>(), [1, 2, u32::MAX]);
+    assert_eq!(ObjectIdentifier::from_bytes(oid.as_bytes()).unwrap(), oid);
+}
+
+#[test]
+fn parse_arc_too_big() {
+    assert_eq!(
+        ObjectIdentifier::from_bytes(&hex!("2A9080808000")),
+        Err(Error::ArcTooBig)
+    );
+}
+
 #[test]
 fn display() {
     assert_eq!(EXAMPLE_OID_0.to_string(), EXAMPLE_OID_0_STR);
@@ -0,0 +1,263 @@
+//! CertReqMsg builder
+
+use alloc::vec::Vec;
+use const_oid::db::rfc5912::{
+    ID_REG_CTRL_AUTHENTICATOR, ID_REG_CTRL_OLD_CERT_ID, ID_REG_CTRL_PKI_PUBLICATION_INFO,
+    ID_REG_CTRL_PROTOCOL_ENCR_KEY, ID_REG_CTRL_REG_TOKEN, ID_REG_INFO_CERT_REQ,
+    ID_REG_INFO_UTF_8_PAIRS,
+};
+use der::asn1::{BitString, Int, Utf8StringRef};
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use x509_cert::attr::{Attribute, AttributeTypeAndValue};
+use x509_cert::ext::Extension;
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::time::Time;
+use x509_cert::Version;
+
+use crate::controls::{
+    control, AlgIdCtrl, AltCertTemplate, OldCertId, PkiArchiveOptions, PkiPublicationInfo,
+    ProtocolEncrKey, RsaKeyLenCtrl, ID_REG_CTRL_ALG_ID, ID_REG_CTRL_ALT_CERT_TEMPLATE,
+    ID_REG_CTRL_RSA_KEY_LEN,
+};
+use crate::pop::ProofOfPossession;
+use crate::reg_info::attribute;
+use crate::request::{CertReqMsg, CertRequest, CertTemplate, OptionalValidity};
+
+/// CertReqMsg builder
+///
+/// The builder assembles the [`CertRequest`] of a [`CertReqMsg`] as described in
+/// [RFC 4211 Section 5]. All fields of the [`CertTemplate`] are omitted unless set. The
+/// resulting message lacks a proof-of-possession unless set with
+/// [`CertReqMsgBuilder::with_pop`], as a signature is computed over the built [`CertRequest`].
+///
+/// ```
+/// use crmf::builder::CertReqMsgBuilder;
+/// use der::asn1::Int;
+///
+/// let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+///     .with_subject("CN=MyName".parse().unwrap())
+///     .build();
+/// assert!(msg.cert_req.cert_template.subject.is_some());
+/// assert!(msg.popo.is_none());
+/// ```
+///
+/// [RFC 4211 Section 5]: https://www.rfc-editor.org/rfc/rfc4211#section-5
+#[derive(Clone, Debug)]
+pub struct CertReqMsgBuilder {
+    cert_req: CertRequest,
+    popo: Option<ProofOfPossession>,
+    reg_info: Option<Vec<Attribute>>,
+}
+
+impl CertReqMsgBuilder {
+    /// Returns a `CertReqMsgBuilder` for a request identified by `cert_req_id`
+    pub fn new(cert_req_id: Int) -> Self {
+        Self {
+            cert_req: CertRequest {
+                cert_req_id,
+                cert_template: CertTemplate::default(),
+                controls: None,
+            },
+            popo: None,
+            reg_info: None,
+        }
+    }
+
+    /// Sets the certificate template, e.g. one returned by [`CertTemplate::from_certificate`] to
+    /// update a certificate. Its fields can be overridden by the other setters.
+    pub fn with_template(mut self, template: CertTemplate) -> Self {
+        self.cert_req.cert_template = template;
+        self
+    }
+
+    /// Sets the version of the certificate template
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.cert_req.cert_template.version = Some(version);
+        self
+    }
+
+    /// Sets the serialNumber of the certificate template
+    pub fn with_serial_number(mut self, serial_number: SerialNumber) -> Self {
+        self.cert_req.cert_template.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Sets the signingAlg of the certificate template
+    pub fn with_signing_alg(mut self, signing_alg: AlgorithmIdentifierOwned) -> Self {
+        self.cert_req.cert_template.signature = Some(signing_alg);
+        self
+    }
+
+    /// Sets the issuer of the certificate template
+    pub fn with_issuer(mut self, issuer: Name) -> Self {
+        self.cert_req.cert_template.issuer = Some(issuer);
+        self
+    }
+
+    /// Sets the notBefore time of the validity of the certificate template
+    pub fn with_not_before(mut self, not_before: Time) -> Self {
+        self.validity().not_before = Some(not_before);
+        self
+    }
+
+    /// Sets the notAfter time of the validity of the certificate template
+    pub fn with_not_after(mut self, not_after: Time) -> Self {
+        self.validity().not_after = Some(not_after);
+        self
+    }
+
+    /// Sets the subject of the certificate template
+    pub fn with_subject(mut self, subject: Name) -> Self {
+        self.cert_req.cert_template.subject = Some(subject);
+        self
+    }
+
+    /// Sets the publicKey of the certificate template
+    pub fn with_public_key(mut self, public_key: SubjectPublicKeyInfoOwned) -> Self {
+        self.cert_req.cert_template.subject_public_key_info = Some(public_key);
+        self
+    }
+
+    /// Sets the issuerUID of the certificate template
+    pub fn with_issuer_unique_id(mut self, issuer_unique_id: BitString) -> Self {
+        self.cert_req.cert_template.issuer_unique_id = Some(issuer_unique_id);
+        self
+    }
+
+    /// Sets the subjectUID of the certificate template
+    pub fn with_subject_unique_id(mut self, subject_unique_id: BitString) -> Self {
+        self.cert_req.cert_template.subject_unique_id = Some(subject_unique_id);
+        self
+    }
+
+    /// Adds an extension to the certificate template
+    pub fn with_extension(mut self, extension: Extension) -> Self {
+        self.cert_req
+            .cert_template
+            .extensions
+            .get_or_insert_with(Vec::new)
+            .push(extension);
+        self
+    }
+
+    /// Adds extensions to the certificate template
+    pub fn with_extensions(mut self, extensions: impl IntoIterator<Item = Extension>) -> Self {
+        self.cert_req
+            .cert_template
+            .extensions
+            .get_or_insert_with(Vec::new)
+            .extend(extensions);
+        self
+    }
+
+    /// Adds a control to the request
+    pub fn with_control(mut self, control: AttributeTypeAndValue) -> Self {
+        self.cert_req
+            .controls
+            .get_or_insert_with(Vec::new)
+            .push(control);
+        self
+    }
+
+    /// Adds the `id-regCtrl-regToken` control with the one-time secret `token`
+    pub fn with_reg_token(self, token: &str) -> der::Result<Self> {
+        let token = Utf8StringRef::new(token)?;
+        Ok(self.with_control(control(ID_REG_CTRL_REG_TOKEN, &token)?))
+    }
+
+    /// Adds the `id-regCtrl-authenticator` control with the long-term secret `authenticator`
+    pub fn with_authenticator(self, authenticator: &str) -> der::Result<Self> {
+        let authenticator = Utf8StringRef::new(authenticator)?;
+        Ok(self.with_control(control(ID_REG_CTRL_AUTHENTICATOR, &authenticator)?))
+    }
+
+    /// Adds the `id-regCtrl-pkiPublicationInfo` control
+    pub fn with_pki_publication_info(self, info: &PkiPublicationInfo) -> der::Result<Self> {
+        Ok(self.with_control(control(ID_REG_CTRL_PKI_PUBLICATION_INFO, info)?))
+    }
+
+    /// Adds the `id-regCtrl-oldCertID` control, identifying the certificate to be updated
+    pub fn with_old_cert_id(self, old_cert_id: &OldCertId) -> der::Result<Self> {
+        Ok(self.with_control(control(ID_REG_CTRL_OLD_CERT_ID, old_cert_id)?))
+    }
+
+    /// Adds the `id-regCtrl-protocolEncrKey` control, the key to encrypt responses with
+    pub fn with_protocol_encr_key(self, key: &ProtocolEncrKey) -> der::Result<Self> {
+        Ok(self.with_control(control(ID_REG_CTRL_PROTOCOL_ENCR_KEY, key)?))
+    }
+
+    /// Adds the `id-regCtrl-altCertTemplate` control, requesting a certificate of a type other
+    /// than X.509
+    pub fn with_alt_cert_template(self, template: &AltCertTemplate) -> der::Result<Self> {
+        Ok(self.with_control(control(ID_REG_CTRL_ALT_CERT_TEMPLATE, template)?))
+    }
+
+    /// Adds the `id-regCtrl-algId` control, requesting a centrally generated key pair of the
+    /// algorithm `alg_id`
+    pub fn with_alg_id(self, alg_id: &AlgIdCtrl) -> der::Result<Self> {
+        Ok(self.with_control(control(ID_REG_CTRL_ALG_ID, alg_id)?))
+    }
+
+    /// Adds the `id-regCtrl-rsaKeyLen` control, requesting a centrally generated RSA key pair
+    /// with a modulus of `key_len` bits. Errors when `key_len` is zero.
+    pub fn with_rsa_key_len(self, key_len: u32) -> der::Result<Self> {
+        let key_len = RsaKeyLenCtrl::new(key_len)?;
+        Ok(self.with_control(control(ID_REG_CTRL_RSA_KEY_LEN, &key_len)?))
+    }
+
+    /// Adds the `id-regCtrl-pkiArchiveOptions` control, e.g. to archive the private key of a
+    /// key pair generated by the requester
+    pub fn with_pki_archive_options(self, options: &PkiArchiveOptions) -> der::Result<Self> {
+        Ok(self.with_control(options.to_control()?))
+    }
+
+    /// Sets the proof-of-possession, e.g. a
+    /// [`ProofOfPossession::key_encipherment_subsequent`] for keys that can't sign
+    pub fn with_pop(mut self, popo: ProofOfPossession) -> Self {
+        self.popo = Some(popo);
+        self
+    }
+
+    /// Adds an attribute to the regInfo of the message
+    pub fn with_reg_info(mut self, reg_info: Attribute) -> Self {
+        self.reg_info.get_or_insert_with(Vec::new).push(reg_info);
+        self
+    }
+
+    /// Adds the `id-regInfo-utf8Pairs` attribute to the regInfo, whose `pairs` are formatted
+    /// as `name?value%` sequences
+    pub fn with_utf8_pairs(self, pairs: &str) -> der::Result<Self> {
+        let pairs = Utf8StringRef::new(pairs)?;
+        Ok(self.with_reg_info(attribute(ID_REG_INFO_UTF_8_PAIRS, &pairs)?))
+    }
+
+    /// Adds the `id-regInfo-certReq` attribute carrying `cert_req` to the regInfo
+    pub fn with_reg_info_cert_req(self, cert_req: &CertRequest) -> der::Result<Self> {
+        Ok(self.with_reg_info(attribute(ID_REG_INFO_CERT_REQ, cert_req)?))
+    }
+
+    /// Returns the [`CertRequest`] assembled so far, e.g. to compute a proof-of-possession
+    pub fn cert_request(&self) -> &CertRequest {
+        &self.cert_req
+    }
+
+    /// Consumes the builder and returns a [`CertReqMsg`]
+    pub fn build(self) -> CertReqMsg {
+        CertReqMsg {
+            cert_req: self.cert_req,
+            popo: self.popo,
+            reg_info: self.reg_info,
+        }
+    }
+
+    fn validity(&mut self) -> &mut OptionalValidity {
+        self.cert_req
+            .cert_template
+            .validity
+            .get_or_insert(OptionalValidity {
+                not_before: None,
+                not_after: None,
+            })
+    }
+}
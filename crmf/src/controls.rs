@@ -2,10 +2,19 @@
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
-use der::asn1::{BitString, OctetString, Utf8StringRef};
-use der::{Choice, Enumerated, Sequence};
+use const_oid::db::rfc5912::ID_REG_CTRL_PKI_ARCHIVE_OPTIONS;
+use const_oid::ObjectIdentifier;
+use der::asn1::{BitString, OctetString, SetOfVec, Utf8StringRef};
+use der::{
+    Any, Choice, Decode, DecodeValue, Encode, EncodeValue, Enumerated, FixedTag, Header, Length,
+    Reader, Sequence, Tag, Tagged, Writer,
+};
 
-use cms::enveloped_data::EnvelopedData;
+use cms::content_info::CmsVersion;
+use cms::enveloped_data::{
+    EncryptedContentInfo, EnvelopedData, KeyTransRecipientInfo, RecipientIdentifier, RecipientInfo,
+    RecipientInfos,
+};
 use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
 use x509_cert::attr::AttributeTypeAndValue;
 use x509_cert::ext::pkix::name::GeneralName;
@@ -21,6 +30,18 @@ use x509_cert::serial_number::SerialNumber;
 /// [RFC 4211 Section 6]: https://www.rfc-editor.org/rfc/rfc4211#section-6
 pub type Controls = Vec<AttributeTypeAndValue>;
 
+/// Returns a control of type `oid` carrying `value`, e.g. a [`RegToken`] for
+/// `id-regCtrl-regToken`
+pub fn control<T: Tagged + EncodeValue>(
+    oid: ObjectIdentifier,
+    value: &T,
+) -> der::Result<AttributeTypeAndValue> {
+    Ok(AttributeTypeAndValue {
+        oid,
+        value: Any::encode_from(value)?,
+    })
+}
+
 /// The `RegToken` control is defined in [RFC 4211 Section 6.1].
 ///
 /// ```text
@@ -128,12 +149,34 @@ pub enum SinglePubInfoMethod {
 pub enum PkiArchiveOptions {
     #[asn1(context_specific = "0", tag_mode = "EXPLICIT", constructed = "true")]
     EncryptedPrivKey(EncryptedKey),
-    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", constructed = "true")]
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "false")]
     KeyGenParameters(KeyGenParameters),
-    #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "false")]
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", constructed = "false")]
     ArchiveRemGenPrivKey(bool),
 }
 
+impl PkiArchiveOptions {
+    /// Returns options requesting the archival of the private key carried in
+    /// `enveloped_data`, i.e. encrypted for the archiving entity
+    pub fn encrypted_priv_key(enveloped_data: EnvelopedData) -> Self {
+        PkiArchiveOptions::EncryptedPrivKey(EncryptedKey::EnvelopedData(Box::new(enveloped_data)))
+    }
+
+    /// Returns the `id-regCtrl-pkiArchiveOptions` control carrying these options
+    pub fn to_control(&self) -> der::Result<AttributeTypeAndValue> {
+        control(ID_REG_CTRL_PKI_ARCHIVE_OPTIONS, self)
+    }
+
+    /// Decodes the options from `control`, if it is an `id-regCtrl-pkiArchiveOptions` control
+    pub fn from_control(control: &AttributeTypeAndValue) -> der::Result<Option<Self>> {
+        if control.oid != ID_REG_CTRL_PKI_ARCHIVE_OPTIONS {
+            return Ok(None);
+        }
+        // CHOICE values can't be decoded from the value alone
+        Self::from_der(&control.value.to_der()?).map(Some)
+    }
+}
+
 /// The `EncryptedKey` type is defined in [RFC 4211 Section 6.4].
 ///
 /// ```text
@@ -149,10 +192,68 @@ pub enum PkiArchiveOptions {
 #[allow(missing_docs)]
 pub enum EncryptedKey {
     EncryptedValue(Box<EncryptedValue>),
-    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", constructed = "true")]
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", constructed = "true")]
     EnvelopedData(Box<EnvelopedData>),
 }
 
+impl EncryptedKey {
+    /// Returns the EnvelopedData carrying the encrypted key, if the deprecated EncryptedValue
+    /// isn't used
+    pub fn enveloped_data(&self) -> Option<&EnvelopedData> {
+        match self {
+            EncryptedKey::EnvelopedData(enveloped_data) => Some(enveloped_data),
+            EncryptedKey::EncryptedValue(_) => None,
+        }
+    }
+
+    /// Returns the deprecated EncryptedValue carrying the encrypted key, if used
+    pub fn encrypted_value(&self) -> Option<&EncryptedValue> {
+        match self {
+            EncryptedKey::EncryptedValue(encrypted_value) => Some(encrypted_value),
+            EncryptedKey::EnvelopedData(_) => None,
+        }
+    }
+
+    /// Returns the encrypted key as EnvelopedData, converting a deprecated EncryptedValue as
+    /// described in [`EncryptedValue::to_enveloped_data`]
+    pub fn to_enveloped_data(
+        &self,
+        content_type: ObjectIdentifier,
+        rid: RecipientIdentifier,
+    ) -> der::Result<EnvelopedData> {
+        match self {
+            EncryptedKey::EnvelopedData(enveloped_data) => Ok(enveloped_data.as_ref().clone()),
+            EncryptedKey::EncryptedValue(encrypted_value) => {
+                encrypted_value.to_enveloped_data(content_type, rid)
+            }
+        }
+    }
+
+    /// Returns the encrypted key as deprecated EncryptedValue, for peers that don't support
+    /// EnvelopedData, converting an EnvelopedData as described in
+    /// [`EncryptedValue::try_from`]
+    pub fn to_encrypted_value(&self) -> der::Result<EncryptedValue> {
+        match self {
+            EncryptedKey::EnvelopedData(enveloped_data) => {
+                EncryptedValue::try_from(enveloped_data.as_ref())
+            }
+            EncryptedKey::EncryptedValue(encrypted_value) => Ok(encrypted_value.as_ref().clone()),
+        }
+    }
+}
+
+impl From<EnvelopedData> for EncryptedKey {
+    fn from(enveloped_data: EnvelopedData) -> Self {
+        EncryptedKey::EnvelopedData(Box::new(enveloped_data))
+    }
+}
+
+impl From<EncryptedValue> for EncryptedKey {
+    fn from(encrypted_value: EncryptedValue) -> Self {
+        EncryptedKey::EncryptedValue(Box::new(encrypted_value))
+    }
+}
+
 /// The `EncryptedValue` type is defined in [RFC 4211 Section 6.4].
 ///
 /// ```text
@@ -215,6 +316,86 @@ pub struct EncryptedValue {
     pub enc_value: BitString,
 }
 
+impl EncryptedValue {
+    /// Converts this EncryptedValue to an EnvelopedData with a single KeyTransRecipientInfo
+    /// identifying the recipient by `rid`, as EncryptedValue lacks a recipient identifier.
+    ///
+    /// The symmAlg, encSymmKey and keyAlg become the content encryption algorithm, encrypted
+    /// key and key encryption algorithm, and the encValue becomes the encrypted content of
+    /// type `content_type`. The intendedAlg and valueHint are dropped. Errors when symmAlg,
+    /// encSymmKey or keyAlg are missing, or a BIT STRING has unused bits.
+    pub fn to_enveloped_data(
+        &self,
+        content_type: ObjectIdentifier,
+        rid: RecipientIdentifier,
+    ) -> der::Result<EnvelopedData> {
+        let missing = || Tag::Sequence.value_error();
+        let enc_sym_key = self.enc_sym_key.as_ref().ok_or_else(missing)?;
+        let ktri = KeyTransRecipientInfo {
+            version: match rid {
+                RecipientIdentifier::IssuerAndSerialNumber(_) => CmsVersion::V0,
+                RecipientIdentifier::SubjectKeyIdentifier(_) => CmsVersion::V2,
+            },
+            rid,
+            key_enc_alg: self.key_alg.clone().ok_or_else(missing)?,
+            enc_key: OctetString::new(octets(enc_sym_key)?)?,
+        };
+        let version = ktri.version;
+
+        let mut recip_infos = SetOfVec::new();
+        recip_infos.insert(RecipientInfo::Ktri(ktri))?;
+        Ok(EnvelopedData {
+            version,
+            originator_info: None,
+            recip_infos: RecipientInfos(recip_infos),
+            encrypted_content: EncryptedContentInfo {
+                content_type,
+                content_enc_alg: self.sym_alg.clone().ok_or_else(missing)?,
+                encrypted_content: Some(OctetString::new(octets(&self.enc_value)?)?),
+            },
+            unprotected_attrs: None,
+        })
+    }
+}
+
+impl TryFrom<&EnvelopedData> for EncryptedValue {
+    type Error = der::Error;
+
+    /// Converts an EnvelopedData with a single KeyTransRecipientInfo, the reverse of
+    /// [`EncryptedValue::to_enveloped_data`]. Errors for other EnvelopedData, as well as for
+    /// those with originatorInfo or unprotectedAttrs, which EncryptedValue can't represent.
+    fn try_from(enveloped_data: &EnvelopedData) -> der::Result<Self> {
+        if enveloped_data.originator_info.is_some() || enveloped_data.unprotected_attrs.is_some() {
+            return Err(Tag::Sequence.value_error());
+        }
+        let ktri = match enveloped_data.recip_infos.0.as_slice() {
+            [RecipientInfo::Ktri(ktri)] => ktri,
+            _ => return Err(Tag::Set.value_error()),
+        };
+        let content = &enveloped_data.encrypted_content;
+        let enc_value = content
+            .encrypted_content
+            .as_ref()
+            .ok_or_else(|| Tag::Sequence.value_error())?;
+
+        Ok(Self {
+            intended_alg: None,
+            sym_alg: Some(content.content_enc_alg.clone()),
+            enc_sym_key: Some(BitString::from_bytes(ktri.enc_key.as_bytes())?),
+            key_alg: Some(ktri.key_enc_alg.clone()),
+            value_hint: None,
+            enc_value: BitString::from_bytes(enc_value.as_bytes())?,
+        })
+    }
+}
+
+/// Returns the octets of `bit_string`, erroring when it has unused bits
+fn octets(bit_string: &BitString) -> der::Result<&[u8]> {
+    bit_string
+        .as_bytes()
+        .ok_or_else(|| Tag::BitString.value_error())
+}
+
 /// The `KeyGenParameters` control is defined in [RFC 4211 Section 6.4].
 ///
 /// ```text
@@ -249,6 +430,106 @@ pub struct CertId {
     pub serial_number: SerialNumber,
 }
 
+/// The `id-regCtrl-altCertTemplate` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_REG_CTRL_ALT_CERT_TEMPLATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.5.1.7");
+
+/// The `id-regCtrl-algId` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_REG_CTRL_ALG_ID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.5.1.11");
+
+/// The `id-regCtrl-rsaKeyLen` object identifier is defined in [RFC 9480].
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub const ID_REG_CTRL_RSA_KEY_LEN: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.5.1.12");
+
+/// The `AltCertTemplate` control is defined in [RFC 9480]. It carries a template for a
+/// certificate of a type other than X.509, identified by the type of the value.
+///
+/// ```text
+///   AltCertTemplate ::= AttributeTypeAndValue
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type AltCertTemplate = AttributeTypeAndValue;
+
+/// The `AlgIdCtrl` control is defined in [RFC 9480]. It requests the key generation
+/// algorithm for a centrally generated key pair.
+///
+/// ```text
+///   AlgIdCtrl ::= AlgorithmIdentifier{PUBLIC-KEY, {...}}
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+pub type AlgIdCtrl = AlgorithmIdentifierOwned;
+
+/// The `RsaKeyLenCtrl` control is defined in [RFC 9480]. It requests the modulus length in
+/// bits for a centrally generated RSA key pair.
+///
+/// ```text
+///   RsaKeyLenCtrl ::= INTEGER (1..MAX)
+/// ```
+///
+/// [RFC 9480]: https://www.rfc-editor.org/rfc/rfc9480
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct RsaKeyLenCtrl(u32);
+
+impl RsaKeyLenCtrl {
+    /// Creates a new `RsaKeyLenCtrl` for a modulus of `key_len` bits, erroring when it is zero
+    pub fn new(key_len: u32) -> der::Result<Self> {
+        if key_len == 0 {
+            return Err(Tag::Integer.value_error());
+        }
+        Ok(Self(key_len))
+    }
+
+    /// Returns the requested modulus length in bits
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for RsaKeyLenCtrl {
+    type Error = der::Error;
+
+    fn try_from(key_len: u32) -> der::Result<Self> {
+        Self::new(key_len)
+    }
+}
+
+impl From<RsaKeyLenCtrl> for u32 {
+    fn from(key_len: RsaKeyLenCtrl) -> u32 {
+        key_len.0
+    }
+}
+
+impl<'a> DecodeValue<'a> for RsaKeyLenCtrl {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        u32::decode_value(reader, header).and_then(Self::new)
+    }
+}
+
+impl EncodeValue for RsaKeyLenCtrl {
+    fn value_len(&self) -> der::Result<Length> {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.0.encode_value(writer)
+    }
+}
+
+impl FixedTag for RsaKeyLenCtrl {
+    const TAG: Tag = Tag::Integer;
+}
+
 /// The `ProtocolEncrKey` control is defined in [RFC 4211 Section 6.6].
 ///
 /// ```text
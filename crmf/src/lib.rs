@@ -18,6 +18,7 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod builder;
 pub mod controls;
 pub mod pop;
 pub mod reg_info;
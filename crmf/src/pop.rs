@@ -1,6 +1,10 @@
 //! Proof of possession (POP)-related types
 
+#[cfg(feature = "signature")]
+mod signing;
+
 use alloc::boxed::Box;
+use core::fmt;
 use der::asn1::{BitString, Null, OctetString, Utf8StringRef};
 use der::{Choice, Enumerated, Sequence};
 
@@ -10,6 +14,69 @@ use x509_cert::ext::pkix::name::GeneralName;
 
 use cms::enveloped_data::EnvelopedData;
 
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors
+    Asn1(der::Error),
+
+    /// The message lacks a proof-of-possession of the expected kind
+    MissingPop,
+
+    /// The poposkInput is present although the certificate template contains the subject and
+    /// publicKey, or absent although it doesn't, or its publicKey differs from the template
+    PoposkInput,
+
+    /// Public key errors
+    #[cfg(feature = "signature")]
+    PublicKey(spki::Error),
+
+    /// Signing and verification errors
+    #[cfg(feature = "signature")]
+    Signature(signature::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::MissingPop => f.write_str("proof-of-possession is missing"),
+            Error::PoposkInput => f.write_str("poposkInput doesn't match the certificate template"),
+            #[cfg(feature = "signature")]
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            #[cfg(feature = "signature")]
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(other: der::Error) -> Self {
+        Self::Asn1(other)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<spki::Error> for Error {
+    fn from(other: spki::Error) -> Self {
+        Self::PublicKey(other)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl From<signature::Error> for Error {
+    fn from(other: signature::Error) -> Self {
+        Self::Signature(other)
+    }
+}
+
 /// The `ProofOfPossession` type is defined in [RFC 4211 Section 4].
 ///
 /// ```text
@@ -30,13 +97,60 @@ pub enum ProofOfPossession {
     RaVerified(Null),
     #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "true")]
     Signature(Box<PopoSigningKey>),
-    //todo review EXPLICIT tag here (does not compile as IMPLICIT)
+    // POPOPrivKey is a CHOICE, hence the tag is EXPLICIT despite the IMPLICIT TAGS module default
     #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "true")]
     KeyEncipherment(POPOPrivKey),
     #[asn1(context_specific = "3", tag_mode = "EXPLICIT", constructed = "true")]
     KeyAgreement(POPOPrivKey),
 }
 
+impl ProofOfPossession {
+    /// Returns a keyEncipherment proof-of-possession deferred to a subsequent message, where
+    /// [`SubsequentMessage::EncrCert`] asks for the certificate to be returned encrypted for
+    /// the requested key, and [`SubsequentMessage::ChallengeResp`] for a challenge-response
+    /// exchange.
+    ///
+    /// This is the proof-of-possession for keys that can't sign, such as KEM or encryption-only
+    /// keys.
+    pub fn key_encipherment_subsequent(method: SubsequentMessage) -> Self {
+        ProofOfPossession::KeyEncipherment(POPOPrivKey::SubsequentMessage(method))
+    }
+
+    /// Returns a keyAgreement proof-of-possession deferred to a subsequent message, see
+    /// [`ProofOfPossession::key_encipherment_subsequent`].
+    pub fn key_agreement_subsequent(method: SubsequentMessage) -> Self {
+        ProofOfPossession::KeyAgreement(POPOPrivKey::SubsequentMessage(method))
+    }
+
+    /// Returns a keyEncipherment proof-of-possession carrying the private key in
+    /// `enveloped_data`, whose encrypted content is an [`EncKeyWithID`] of type
+    /// `id-ct-encKeyWithID`.
+    pub fn encrypted_key(enveloped_data: EnvelopedData) -> Self {
+        ProofOfPossession::KeyEncipherment(POPOPrivKey::EncryptedKey(enveloped_data))
+    }
+
+    /// Returns a keyAgreement proof-of-possession carrying the MAC computed with the key
+    /// agreement key
+    pub fn agree_mac(mac: PkMacValue) -> Self {
+        ProofOfPossession::KeyAgreement(POPOPrivKey::AgreeMac(mac))
+    }
+
+    /// Returns the POPOPrivKey of a keyEncipherment or keyAgreement proof-of-possession
+    pub fn priv_key(&self) -> Option<&POPOPrivKey> {
+        match self {
+            ProofOfPossession::KeyEncipherment(priv_key)
+            | ProofOfPossession::KeyAgreement(priv_key) => Some(priv_key),
+            _ => None,
+        }
+    }
+
+    /// Returns the subsequentMessage method of a keyEncipherment or keyAgreement
+    /// proof-of-possession, if it is deferred to a subsequent message exchange
+    pub fn subsequent_message(&self) -> Option<SubsequentMessage> {
+        self.priv_key().and_then(POPOPrivKey::subsequent_message)
+    }
+}
+
 /// The `POPOSigningKey` type is defined in [RFC 4211 Section 4.1].
 ///
 /// ```text
@@ -154,18 +268,45 @@ pub struct PbmParameter {
 #[derive(Clone, Debug, PartialEq, Eq, Choice)]
 #[allow(missing_docs)]
 pub enum POPOPrivKey {
-    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", constructed = "false")]
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", constructed = "false")]
     ThisMessage(BitString),
-    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", constructed = "true")]
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "false")]
     SubsequentMessage(SubsequentMessage),
-    #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "false")]
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", constructed = "false")]
     DhMac(BitString),
-    #[asn1(context_specific = "3", tag_mode = "EXPLICIT", constructed = "true")]
+    #[asn1(context_specific = "3", tag_mode = "IMPLICIT", constructed = "true")]
     AgreeMac(PkMacValue),
-    #[asn1(context_specific = "4", tag_mode = "EXPLICIT", constructed = "true")]
+    #[asn1(context_specific = "4", tag_mode = "IMPLICIT", constructed = "true")]
     EncryptedKey(EnvelopedData),
 }
 
+impl POPOPrivKey {
+    /// Returns the subsequentMessage method, if the proof-of-possession is deferred to a
+    /// subsequent message exchange
+    pub fn subsequent_message(&self) -> Option<SubsequentMessage> {
+        match self {
+            POPOPrivKey::SubsequentMessage(method) => Some(*method),
+            _ => None,
+        }
+    }
+
+    /// Returns the EnvelopedData carrying the encrypted private key, if any
+    pub fn encrypted_key(&self) -> Option<&EnvelopedData> {
+        match self {
+            POPOPrivKey::EncryptedKey(enveloped_data) => Some(enveloped_data),
+            _ => None,
+        }
+    }
+
+    /// Returns the MAC computed with the key agreement key, if any
+    pub fn agree_mac(&self) -> Option<&PkMacValue> {
+        match self {
+            POPOPrivKey::AgreeMac(mac) => Some(mac),
+            _ => None,
+        }
+    }
+}
+
 /// The `SubsequentMessage` type is defined in [RFC 4211 Section 4.2].
 ///
 /// ```text
@@ -266,7 +407,7 @@ impl<'a> ::der::Tagged for EncKeyWithIdChoice<'a> {
     fn tag(&self) -> ::der::Tag {
         match self {
             Self::String(_) => <Utf8StringRef<'a> as ::der::FixedTag>::TAG,
-            Self::GeneralName(_) => self.tag(),
+            Self::GeneralName(variant) => variant.tag(),
         }
     }
 }
@@ -0,0 +1,159 @@
+//! Signature-based proof-of-possession as described in [RFC 4211 Section 4.1].
+//!
+//! [RFC 4211 Section 4.1]: https://www.rfc-editor.org/rfc/rfc4211#section-4.1
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use der::Encode;
+use rand_core::CryptoRngCore;
+use signature::{RandomizedSigner, SignatureEncoding, Signer, Verifier};
+use spki::{
+    DecodePublicKey, DynSignatureAlgorithmIdentifier, SignatureBitStringEncoding,
+    SubjectPublicKeyInfoOwned,
+};
+
+use super::{Error, PopoSigningKey, PopoSigningKeyInput, ProofOfPossession, Result};
+use crate::request::{CertReqMsg, CertRequest};
+
+impl PopoSigningKey {
+    /// Returns the DER encoding of the input of the signature over `cert_req`, i.e. the
+    /// `poposk_input` if present, and the certReq otherwise.
+    ///
+    /// The poposkInput must be present exactly when the certificate template lacks the subject
+    /// or publicKey, and must carry the publicKey of the template, if any.
+    pub fn signed_data(
+        cert_req: &CertRequest,
+        poposk_input: Option<&PopoSigningKeyInput>,
+    ) -> Result<Vec<u8>> {
+        let template = &cert_req.cert_template;
+        let complete = template.subject.is_some() && template.subject_public_key_info.is_some();
+        match poposk_input {
+            None if complete => Ok(cert_req.to_der()?),
+            Some(input) if !complete => match &template.subject_public_key_info {
+                Some(public_key) if *public_key != input.public_key => Err(Error::PoposkInput),
+                _ => Ok(input.to_der()?),
+            },
+            _ => Err(Error::PoposkInput),
+        }
+    }
+
+    /// Returns a `POPOSigningKey` with the signature of `signer` over `cert_req`, see
+    /// [`PopoSigningKey::signed_data`].
+    pub fn sign<S, Sig>(
+        cert_req: &CertRequest,
+        poposk_input: Option<PopoSigningKeyInput>,
+        signer: &S,
+    ) -> Result<Self>
+    where
+        S: Signer<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        let signed_data = Self::signed_data(cert_req, poposk_input.as_ref())?;
+        Ok(Self {
+            poposk_input,
+            alg_id: signer.signature_algorithm_identifier()?,
+            signature: signer.try_sign(&signed_data)?.to_bitstring()?,
+        })
+    }
+
+    /// Returns a `POPOSigningKey` with the randomized signature of `signer` over `cert_req`,
+    /// see [`PopoSigningKey::signed_data`].
+    pub fn sign_with_rng<S, Sig>(
+        cert_req: &CertRequest,
+        poposk_input: Option<PopoSigningKeyInput>,
+        signer: &S,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self>
+    where
+        S: RandomizedSigner<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        let signed_data = Self::signed_data(cert_req, poposk_input.as_ref())?;
+        Ok(Self {
+            poposk_input,
+            alg_id: signer.signature_algorithm_identifier()?,
+            signature: signer
+                .try_sign_with_rng(rng, &signed_data)?
+                .to_bitstring()?,
+        })
+    }
+
+    /// Returns the public key the signature is verified with, i.e. the publicKey of the
+    /// poposkInput if present, and of the certificate template of `cert_req` otherwise.
+    pub fn public_key<'a>(
+        &'a self,
+        cert_req: &'a CertRequest,
+    ) -> Option<&'a SubjectPublicKeyInfoOwned> {
+        match &self.poposk_input {
+            Some(input) => Some(&input.public_key),
+            None => cert_req.cert_template.subject_public_key_info.as_ref(),
+        }
+    }
+
+    /// Verifies the signature over `cert_req` with its public key, decoded as `VK`, see
+    /// [`PopoSigningKey::public_key`].
+    ///
+    /// This doesn't check whether `VK` matches the algorithmIdentifier, nor the authInfo of
+    /// the poposkInput, which is up to the caller.
+    pub fn verify<VK, S>(&self, cert_req: &CertRequest) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let signed_data = Self::signed_data(cert_req, self.poposk_input.as_ref())?;
+        let public_key = self.public_key(cert_req).ok_or(Error::PoposkInput)?;
+
+        let verifying_key = VK::from_public_key_der(&public_key.to_der()?)?;
+        let signature =
+            S::try_from(self.signature.raw_bytes()).map_err(|_| signature::Error::new())?;
+
+        Ok(verifying_key.verify(&signed_data, &signature)?)
+    }
+}
+
+impl CertReqMsg {
+    /// Sets the popo of this message to the signature of `signer` over the certReq, see
+    /// [`PopoSigningKey::sign`].
+    pub fn sign_pop<S, Sig>(
+        &mut self,
+        poposk_input: Option<PopoSigningKeyInput>,
+        signer: &S,
+    ) -> Result<()>
+    where
+        S: Signer<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        let popo = PopoSigningKey::sign(&self.cert_req, poposk_input, signer)?;
+        self.popo = Some(ProofOfPossession::Signature(Box::new(popo)));
+        Ok(())
+    }
+
+    /// Sets the popo of this message to the randomized signature of `signer` over the certReq,
+    /// see [`PopoSigningKey::sign_with_rng`].
+    pub fn sign_pop_with_rng<S, Sig>(
+        &mut self,
+        poposk_input: Option<PopoSigningKeyInput>,
+        signer: &S,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<()>
+    where
+        S: RandomizedSigner<Sig> + DynSignatureAlgorithmIdentifier,
+        Sig: SignatureBitStringEncoding,
+    {
+        let popo = PopoSigningKey::sign_with_rng(&self.cert_req, poposk_input, signer, rng)?;
+        self.popo = Some(ProofOfPossession::Signature(Box::new(popo)));
+        Ok(())
+    }
+
+    /// Verifies the signature-based popo of this message, see [`PopoSigningKey::verify`].
+    pub fn verify_pop<VK, S>(&self) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        match &self.popo {
+            Some(ProofOfPossession::Signature(popo)) => popo.verify::<VK, S>(&self.cert_req),
+            _ => Err(Error::MissingPop),
+        }
+    }
+}
@@ -1,10 +1,24 @@
 //! Registration Info-related types
 
-use der::asn1::Utf8StringRef;
+use const_oid::ObjectIdentifier;
+use der::asn1::{SetOfVec, Utf8StringRef};
+use der::{Any, EncodeValue, Tagged};
+use x509_cert::attr::Attribute;
 
 use crate::request::CertRequest;
 
-/// The `PrivateKeyInfo` type is defined in [RFC 4211 Section 7.1].
+/// Returns a regInfo attribute of type `oid` carrying the single `value`, e.g. a
+/// [`Utf8Pairs`] for `id-regInfo-utf8Pairs`
+pub fn attribute<T: Tagged + EncodeValue>(
+    oid: ObjectIdentifier,
+    value: &T,
+) -> der::Result<Attribute> {
+    let mut values = SetOfVec::new();
+    values.insert(Any::encode_from(value)?)?;
+    Ok(Attribute { oid, values })
+}
+
+/// The `UTF8Pairs` type is defined in [RFC 4211 Section 7.1].
 ///
 /// ```text
 ///   UTF8Pairs ::= UTF8String
@@ -13,7 +27,7 @@ use crate::request::CertRequest;
 /// [RFC 4211 Section 7.1]: https://www.rfc-editor.org/rfc/rfc4211#section-7.1
 pub type Utf8Pairs<'a> = Utf8StringRef<'a>;
 
-/// The `PrivateKeyInfo` type is defined in [RFC 4211 Section 7.2].
+/// The `CertReq` type is defined in [RFC 4211 Section 7.2].
 ///
 /// ```text
 ///   CertReq ::= CertRequest
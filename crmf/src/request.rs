@@ -1,19 +1,31 @@
 //! Request-related types
 
 use alloc::vec::Vec;
+use const_oid::db::rfc5912::{
+    ID_REG_CTRL_AUTHENTICATOR, ID_REG_CTRL_OLD_CERT_ID, ID_REG_CTRL_PKI_ARCHIVE_OPTIONS,
+    ID_REG_CTRL_PKI_PUBLICATION_INFO, ID_REG_CTRL_PROTOCOL_ENCR_KEY, ID_REG_CTRL_REG_TOKEN,
+    ID_REG_INFO_CERT_REQ, ID_REG_INFO_UTF_8_PAIRS,
+};
+use const_oid::{AssociatedOid, ObjectIdentifier};
 use der::asn1::{BitString, Int};
-use der::Sequence;
+use der::{Choice, DecodeValue, Sequence, Tag};
 
 use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
-use x509_cert::attr::Attribute;
+use x509_cert::attr::{Attribute, AttributeTypeAndValue};
+use x509_cert::ext::pkix::{AuthorityKeyIdentifier, SubjectKeyIdentifier};
 use x509_cert::ext::Extensions;
 use x509_cert::name::Name;
 use x509_cert::serial_number::SerialNumber;
 use x509_cert::time::Time;
-use x509_cert::Version;
+use x509_cert::{Certificate, Version};
 
-use crate::controls::Controls;
+use crate::controls::{
+    AlgIdCtrl, AltCertTemplate, Authenticator, Controls, OldCertId, PkiArchiveOptions,
+    PkiPublicationInfo, ProtocolEncrKey, RegToken, RsaKeyLenCtrl, ID_REG_CTRL_ALG_ID,
+    ID_REG_CTRL_ALT_CERT_TEMPLATE, ID_REG_CTRL_RSA_KEY_LEN,
+};
 use crate::pop::ProofOfPossession;
+use crate::reg_info::{CertReq, Utf8Pairs};
 
 /// The `CertReqMessages` type is defined in [RFC 4211 Section 3].
 ///
@@ -52,6 +64,39 @@ pub struct CertReqMsg {
 /// ```
 pub type AttributeSeq = Vec<Attribute>;
 
+impl CertReqMsg {
+    /// Returns the first regInfo attribute of type `oid`, if any
+    pub fn reg_info(&self, oid: ObjectIdentifier) -> Option<&Attribute> {
+        self.reg_info
+            .as_ref()
+            .and_then(|attributes| attributes.iter().find(|attribute| attribute.oid == oid))
+    }
+
+    /// Returns the value of the `id-regInfo-utf8Pairs` attribute, if present
+    pub fn utf8_pairs(&self) -> der::Result<Option<Utf8Pairs<'_>>> {
+        self.reg_info_value(ID_REG_INFO_UTF_8_PAIRS)
+    }
+
+    /// Returns the value of the `id-regInfo-certReq` attribute, if present
+    pub fn reg_info_cert_req(&self) -> der::Result<Option<CertReq>> {
+        self.reg_info_value(ID_REG_INFO_CERT_REQ)
+    }
+
+    /// Decodes the first value of the regInfo attribute of type `oid`, if present
+    fn reg_info_value<'a, T>(&'a self, oid: ObjectIdentifier) -> der::Result<Option<T>>
+    where
+        T: Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        match self.reg_info(oid) {
+            Some(attribute) => match attribute.values.iter().next() {
+                Some(value) => value.decode_as().map(Some),
+                None => Err(Tag::Set.value_error()),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
 /// The `CertRequest` type is defined in [RFC 4211 Section 5].
 ///
 /// ```text
@@ -73,6 +118,73 @@ pub struct CertRequest {
     pub controls: Option<Controls>,
 }
 
+impl CertRequest {
+    /// Returns the first control of type `oid`, if any
+    pub fn control(&self, oid: ObjectIdentifier) -> Option<&AttributeTypeAndValue> {
+        self.controls
+            .as_ref()
+            .and_then(|controls| controls.iter().find(|control| control.oid == oid))
+    }
+
+    /// Returns the value of the `id-regCtrl-regToken` control, if present
+    pub fn reg_token(&self) -> der::Result<Option<RegToken<'_>>> {
+        self.control_value(ID_REG_CTRL_REG_TOKEN)
+    }
+
+    /// Returns the value of the `id-regCtrl-authenticator` control, if present
+    pub fn authenticator(&self) -> der::Result<Option<Authenticator<'_>>> {
+        self.control_value(ID_REG_CTRL_AUTHENTICATOR)
+    }
+
+    /// Returns the value of the `id-regCtrl-pkiPublicationInfo` control, if present
+    pub fn pki_publication_info(&self) -> der::Result<Option<PkiPublicationInfo>> {
+        self.control_value(ID_REG_CTRL_PKI_PUBLICATION_INFO)
+    }
+
+    /// Returns the value of the `id-regCtrl-oldCertID` control, if present
+    pub fn old_cert_id(&self) -> der::Result<Option<OldCertId>> {
+        self.control_value(ID_REG_CTRL_OLD_CERT_ID)
+    }
+
+    /// Returns the value of the `id-regCtrl-protocolEncrKey` control, if present
+    pub fn protocol_encr_key(&self) -> der::Result<Option<ProtocolEncrKey>> {
+        self.control_value(ID_REG_CTRL_PROTOCOL_ENCR_KEY)
+    }
+
+    /// Returns the value of the `id-regCtrl-altCertTemplate` control, if present
+    pub fn alt_cert_template(&self) -> der::Result<Option<AltCertTemplate>> {
+        self.control_value(ID_REG_CTRL_ALT_CERT_TEMPLATE)
+    }
+
+    /// Returns the value of the `id-regCtrl-algId` control, if present
+    pub fn alg_id(&self) -> der::Result<Option<AlgIdCtrl>> {
+        self.control_value(ID_REG_CTRL_ALG_ID)
+    }
+
+    /// Returns the value of the `id-regCtrl-rsaKeyLen` control, if present
+    pub fn rsa_key_len(&self) -> der::Result<Option<RsaKeyLenCtrl>> {
+        self.control_value(ID_REG_CTRL_RSA_KEY_LEN)
+    }
+
+    /// Returns the value of the `id-regCtrl-pkiArchiveOptions` control, if present
+    pub fn pki_archive_options(&self) -> der::Result<Option<PkiArchiveOptions>> {
+        match self.control(ID_REG_CTRL_PKI_ARCHIVE_OPTIONS) {
+            Some(control) => PkiArchiveOptions::from_control(control),
+            None => Ok(None),
+        }
+    }
+
+    /// Decodes the value of the control of type `oid`, if present
+    fn control_value<'a, T>(&'a self, oid: ObjectIdentifier) -> der::Result<Option<T>>
+    where
+        T: Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        self.control(oid)
+            .map(|control| control.value.decode_as())
+            .transpose()
+    }
+}
+
 /// The `CertTemplate` type is defined in [RFC 4211 Section 5].
 ///
 /// ```text
@@ -101,7 +213,7 @@ pub struct CertRequest {
 /// ```
 ///
 /// [RFC 4211 Section 5]: https://www.rfc-editor.org/rfc/rfc4211#section-5
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct CertTemplate {
     #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
@@ -126,6 +238,37 @@ pub struct CertTemplate {
     pub extensions: Option<Extensions>,
 }
 
+impl CertTemplate {
+    /// Returns a template requesting a certificate like `cert`, as used to update or rekey a
+    /// certificate.
+    ///
+    /// The subject, issuer and extensions are copied from `cert`, except for the subject and
+    /// authority key identifiers, which are tied to the keys. The publicKey and validity are
+    /// left to the caller, as are the version, serialNumber and signingAlg, which are chosen
+    /// by the CA.
+    pub fn from_certificate(cert: &Certificate) -> Self {
+        let tbs = &cert.tbs_certificate;
+        let extensions = tbs.extensions.as_ref().and_then(|extensions| {
+            let extensions: Extensions = extensions
+                .iter()
+                .filter(|ext| {
+                    ext.extn_id != SubjectKeyIdentifier::OID
+                        && ext.extn_id != AuthorityKeyIdentifier::OID
+                })
+                .cloned()
+                .collect();
+            (!extensions.is_empty()).then_some(extensions)
+        });
+
+        Self {
+            issuer: Some(tbs.issuer.clone()),
+            subject: Some(tbs.subject.clone()),
+            extensions,
+            ..Default::default()
+        }
+    }
+}
+
 /// The `OptionalValidity` type is defined in [RFC 4211 Section 5].
 ///
 /// ```text
@@ -0,0 +1,65 @@
+use const_oid::db::rfc5280::ID_CE_BASIC_CONSTRAINTS;
+use const_oid::db::rfc5912::ID_REG_CTRL_REG_TOKEN;
+use crmf::builder::CertReqMsgBuilder;
+use crmf::request::{CertReqMsg, CertRequest, CertTemplate};
+use der::asn1::{Int, OctetString, Utf8StringRef};
+use der::{Any, Decode, Encode};
+use x509_cert::attr::AttributeTypeAndValue;
+use x509_cert::ext::pkix::BasicConstraints;
+use x509_cert::ext::Extension;
+use x509_cert::time::Time;
+
+#[test]
+fn build_from_template_test() {
+    // rebuild the CertRequest cracked from request object used in the cmpv2 req_message_test
+    let template = CertTemplate::from_der(include_bytes!("examples/certtemplate.bin")).unwrap();
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_subject(template.subject.unwrap())
+        .with_public_key(template.subject_public_key_info.unwrap())
+        .build();
+    assert!(msg.popo.is_none());
+    assert!(msg.reg_info.is_none());
+    assert_eq!(
+        include_bytes!("examples/certrequest.bin"),
+        msg.cert_req.to_der().unwrap().as_slice()
+    );
+}
+
+#[test]
+fn build_template_fields_test() {
+    let not_after = Time::from_der(b"\x18\x0f20500101000000Z").unwrap();
+    let constraints = BasicConstraints {
+        ca: false,
+        path_len_constraint: None,
+    };
+    let extension = Extension {
+        extn_id: ID_CE_BASIC_CONSTRAINTS,
+        critical: true,
+        extn_value: OctetString::new(constraints.to_der().unwrap()).unwrap(),
+    };
+    let token = Utf8StringRef::new("token").unwrap();
+    let control = AttributeTypeAndValue {
+        oid: ID_REG_CTRL_REG_TOKEN,
+        value: Any::encode_from(&token).unwrap(),
+    };
+
+    let builder = CertReqMsgBuilder::new(Int::new(&[1]).unwrap())
+        .with_subject("CN=MyName".parse().unwrap())
+        .with_not_after(not_after)
+        .with_extension(extension.clone())
+        .with_control(control.clone());
+    assert!(builder.cert_request().controls.is_some());
+
+    let msg = builder.build();
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    let template = &msg.cert_req.cert_template;
+    let validity = template.validity.as_ref().unwrap();
+    assert!(validity.not_before.is_none());
+    assert_eq!(validity.not_after, Some(not_after));
+    assert_eq!(template.extensions, Some(vec![extension]));
+    assert!(template.subject_public_key_info.is_none());
+    assert_eq!(msg.cert_req.controls, Some(vec![control]));
+
+    let request = CertRequest::from_der(&msg.cert_req.to_der().unwrap()).unwrap();
+    assert_eq!(request.cert_req_id, Int::new(&[1]).unwrap());
+}
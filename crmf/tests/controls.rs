@@ -1,11 +1,21 @@
 use cms::content_info::ContentInfo;
-use cms::enveloped_data::EnvelopedData;
-use crmf::controls::{EncryptedKey, PkiArchiveOptions};
-use der::{Decode, Encode};
+use cms::enveloped_data::{EnvelopedData, RecipientInfo};
+use const_oid::db::rfc5912::{ID_REG_CTRL_PKI_ARCHIVE_OPTIONS, ID_REG_CTRL_REG_TOKEN};
+use crmf::builder::CertReqMsgBuilder;
+use crmf::controls::{
+    control, AltCertTemplate, CertId, EncryptedKey, EncryptedValue, PkiArchiveOptions,
+    PkiPublicationInfo, PkiPublicationInfoAction, RsaKeyLenCtrl, SinglePubInfo,
+    SinglePubInfoMethod,
+};
+use crmf::request::{CertReqMsg, CertTemplate};
+use der::asn1::{Int, OctetString, Utf8StringRef};
+use der::{Any, Decode, Encode, Tag, TagNumber};
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::serial_number::SerialNumber;
 
 #[test]
 fn pki_archive_options_test() {
-    let der_ci = include_bytes!("../../cms/tests/examples/enveloped_data_ktri.bin");
+    let der_ci = include_bytes!("examples/enveloped_data_ktri.bin");
     let ci = ContentInfo::from_der(der_ci).unwrap();
     assert_eq!(ci.content_type, const_oid::db::rfc5911::ID_ENVELOPED_DATA);
 
@@ -29,3 +39,251 @@ fn pki_archive_options_test() {
         _ => panic!(),
     }
 }
+
+#[test]
+fn pki_archive_options_control_test() {
+    let der_ci = include_bytes!("examples/enveloped_data_ktri.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let bytes = ci.content.to_der().unwrap();
+    let data = EnvelopedData::from_der(bytes.as_slice()).unwrap();
+
+    // the EnvelopedData is IMPLICIT tagged within the EXPLICIT tagged EncryptedKey CHOICE
+    let pao = PkiArchiveOptions::encrypted_priv_key(data.clone());
+    let mut inner = bytes.clone();
+    inner[0] = 0xA0;
+    let expected = Any::new(
+        Tag::ContextSpecific {
+            constructed: true,
+            number: TagNumber::new(0),
+        },
+        inner,
+    )
+    .unwrap();
+    assert_eq!(pao.to_der().unwrap(), expected.to_der().unwrap());
+
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_pki_archive_options(&pao)
+        .unwrap()
+        .build();
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    let control = msg
+        .cert_req
+        .control(ID_REG_CTRL_PKI_ARCHIVE_OPTIONS)
+        .unwrap();
+    assert_eq!(
+        PkiArchiveOptions::from_control(control).unwrap(),
+        Some(pao.clone())
+    );
+    let pao2 = msg.cert_req.pki_archive_options().unwrap().unwrap();
+    match &pao2 {
+        PkiArchiveOptions::EncryptedPrivKey(key) => assert_eq!(key.enveloped_data(), Some(&data)),
+        _ => panic!(),
+    }
+
+    let pao = PkiArchiveOptions::ArchiveRemGenPrivKey(true);
+    assert_eq!(pao.to_der().unwrap(), [0x82, 0x01, 0xFF]);
+    assert_eq!(
+        PkiArchiveOptions::from_der(&[0x82, 0x01, 0xFF]).unwrap(),
+        pao
+    );
+
+    let pao = PkiArchiveOptions::KeyGenParameters(OctetString::new([1, 2]).unwrap());
+    assert_eq!(pao.to_der().unwrap(), [0x81, 0x02, 0x01, 0x02]);
+    assert!(CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .build()
+        .cert_req
+        .pki_archive_options()
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn reg_controls_test() {
+    let token = control(ID_REG_CTRL_REG_TOKEN, &Utf8StringRef::new("token").unwrap()).unwrap();
+    assert_eq!(
+        token.to_der().unwrap(),
+        [
+            0x30, 0x12, 0x06, 0x09, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x05, 0x01, 0x01, 0x0C,
+            0x05, 0x74, 0x6F, 0x6B, 0x65, 0x6E
+        ]
+    );
+
+    let template = CertTemplate::from_der(include_bytes!("examples/certtemplate.bin")).unwrap();
+    let key = template.subject_public_key_info.unwrap();
+    let old_cert_id = CertId {
+        issuer: GeneralName::DirectoryName("CN=CA".parse().unwrap()),
+        serial_number: SerialNumber::new(&[1, 2, 3]).unwrap(),
+    };
+    let publication_info = PkiPublicationInfo {
+        action: PkiPublicationInfoAction::PleasePublish,
+        pub_infos: Some(vec![SinglePubInfo {
+            pub_method: SinglePubInfoMethod::Ldap,
+            pub_location: None,
+        }]),
+    };
+
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_reg_token("token")
+        .unwrap()
+        .with_authenticator("secret")
+        .unwrap()
+        .with_pki_publication_info(&publication_info)
+        .unwrap()
+        .with_old_cert_id(&old_cert_id)
+        .unwrap()
+        .with_protocol_encr_key(&key)
+        .unwrap()
+        .build();
+    assert_eq!(msg.cert_req.controls.as_ref().unwrap()[0], token);
+
+    let der = msg.to_der().unwrap();
+    let msg = CertReqMsg::from_der(&der).unwrap();
+    let cert_req = &msg.cert_req;
+    assert_eq!(cert_req.reg_token().unwrap().unwrap().as_str(), "token");
+    assert_eq!(
+        cert_req.authenticator().unwrap().unwrap().as_str(),
+        "secret"
+    );
+    assert_eq!(
+        cert_req.pki_publication_info().unwrap(),
+        Some(publication_info)
+    );
+    assert_eq!(cert_req.old_cert_id().unwrap(), Some(old_cert_id));
+    assert_eq!(cert_req.protocol_encr_key().unwrap(), Some(key));
+    assert_eq!(cert_req.pki_archive_options().unwrap(), None);
+}
+
+#[test]
+fn reg_info_test() {
+    let other = CertReqMsgBuilder::new(Int::new(&[1]).unwrap())
+        .with_subject("CN=Other".parse().unwrap())
+        .build()
+        .cert_req;
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_utf8_pairs("name?value%")
+        .unwrap()
+        .with_reg_info_cert_req(&other)
+        .unwrap()
+        .build();
+
+    let der = msg.to_der().unwrap();
+    let msg = CertReqMsg::from_der(&der).unwrap();
+    assert_eq!(msg.utf8_pairs().unwrap().unwrap().as_str(), "name?value%");
+    assert_eq!(msg.reg_info_cert_req().unwrap(), Some(other));
+    assert_eq!(msg.cert_req.reg_token().unwrap(), None);
+}
+
+#[test]
+fn encrypted_value_enveloped_data_test() {
+    let der_ci = include_bytes!("examples/enveloped_data_ktri.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let data = EnvelopedData::from_der(&ci.content.to_der().unwrap()).unwrap();
+    let ktri = match data.recip_infos.0.as_slice() {
+        [RecipientInfo::Ktri(ktri)] => ktri.clone(),
+        _ => panic!(),
+    };
+    let content_type = data.encrypted_content.content_type;
+
+    let value = EncryptedValue::try_from(&data).unwrap();
+    assert_eq!(
+        value.sym_alg.as_ref(),
+        Some(&data.encrypted_content.content_enc_alg)
+    );
+    assert_eq!(value.key_alg.as_ref(), Some(&ktri.key_enc_alg));
+    assert_eq!(
+        value.enc_sym_key.as_ref().unwrap().raw_bytes(),
+        ktri.enc_key.as_bytes()
+    );
+    assert_eq!(
+        value.enc_value.raw_bytes(),
+        data.encrypted_content
+            .encrypted_content
+            .as_ref()
+            .unwrap()
+            .as_bytes()
+    );
+
+    // the conversion is lossless for a single KeyTransRecipientInfo
+    let converted = value
+        .to_enveloped_data(content_type, ktri.rid.clone())
+        .unwrap();
+    assert_eq!(converted, data);
+
+    // both forms are decoded wherever EncryptedKey appears
+    let old = EncryptedKey::from(value.clone());
+    let new = EncryptedKey::from(data.clone());
+    for key in [&old, &new] {
+        let options = PkiArchiveOptions::EncryptedPrivKey(key.clone());
+        let decoded = PkiArchiveOptions::from_der(&options.to_der().unwrap()).unwrap();
+        assert_eq!(decoded, options);
+        assert_eq!(key.to_encrypted_value().unwrap(), value);
+        assert_eq!(
+            key.to_enveloped_data(content_type, ktri.rid.clone())
+                .unwrap(),
+            data
+        );
+    }
+    assert_eq!(old.encrypted_value(), Some(&value));
+    assert!(old.enveloped_data().is_none());
+    assert_eq!(new.enveloped_data(), Some(&data));
+    assert!(new.encrypted_value().is_none());
+
+    // EncryptedValue lacks the fields required by EnvelopedData
+    let mut incomplete = value;
+    incomplete.key_alg = None;
+    assert!(incomplete
+        .to_enveloped_data(content_type, ktri.rid)
+        .is_err());
+}
+
+#[test]
+fn rfc9480_controls_test() {
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_rsa_key_len(2048)
+        .unwrap()
+        .build();
+    assert_eq!(
+        msg.cert_req.controls.as_ref().unwrap()[0].to_der().unwrap(),
+        [
+            0x30, 0x0F, 0x06, 0x09, 0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x05, 0x01, 0x0C, 0x02,
+            0x02, 0x08, 0x00
+        ]
+    );
+
+    let template = CertTemplate::from_der(include_bytes!("examples/certtemplate.bin")).unwrap();
+    let alg_id = template.subject_public_key_info.unwrap().algorithm;
+    let alt_cert_template = AltCertTemplate {
+        oid: const_oid::ObjectIdentifier::new_unwrap("1.2.3.4"),
+        value: Any::encode_from(&OctetString::new([1, 2, 3]).unwrap()).unwrap(),
+    };
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_alg_id(&alg_id)
+        .unwrap()
+        .with_alt_cert_template(&alt_cert_template)
+        .unwrap()
+        .with_rsa_key_len(3072)
+        .unwrap()
+        .build();
+
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    let cert_req = &msg.cert_req;
+    assert_eq!(cert_req.alg_id().unwrap(), Some(alg_id));
+    assert_eq!(
+        cert_req.alt_cert_template().unwrap(),
+        Some(alt_cert_template)
+    );
+    assert_eq!(cert_req.rsa_key_len().unwrap().map(u32::from), Some(3072));
+    assert_eq!(cert_req.reg_token().unwrap(), None);
+
+    // RsaKeyLenCtrl ::= INTEGER (1..MAX)
+    assert!(CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_rsa_key_len(0)
+        .is_err());
+    assert!(RsaKeyLenCtrl::from_der(&[0x02, 0x01, 0x00]).is_err());
+    assert_eq!(
+        RsaKeyLenCtrl::from_der(&[0x02, 0x02, 0x08, 0x00])
+            .unwrap()
+            .get(),
+        2048
+    );
+}
@@ -0,0 +1,123 @@
+//! Signature-based proof-of-possession tests
+
+#![cfg(feature = "signature")]
+
+use const_oid::db::rfc5912::ECDSA_WITH_SHA_256;
+use crmf::builder::CertReqMsgBuilder;
+use crmf::pop::{
+    Error, PopoSigningKey, PopoSigningKeyInput, PopoSigningKeyInputChoice, ProofOfPossession,
+};
+use crmf::request::CertReqMsg;
+use der::asn1::Int;
+use der::{Decode, Encode};
+use p256::ecdsa::{DerSignature, SigningKey, VerifyingKey};
+use p256::pkcs8::EncodePublicKey;
+use spki::SubjectPublicKeyInfoOwned;
+use x509_cert::ext::pkix::name::GeneralName;
+
+fn spki(key: &VerifyingKey) -> SubjectPublicKeyInfoOwned {
+    SubjectPublicKeyInfoOwned::from_der(key.to_public_key_der().unwrap().as_bytes()).unwrap()
+}
+
+fn builder() -> CertReqMsgBuilder {
+    CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+}
+
+#[test]
+fn sign_and_verify_cert_req() {
+    let signer = SigningKey::random(&mut rand::thread_rng());
+    let mut msg = builder()
+        .with_subject("CN=MyName".parse().unwrap())
+        .with_public_key(spki(signer.verifying_key()))
+        .build();
+    assert!(matches!(
+        msg.verify_pop::<VerifyingKey, DerSignature>(),
+        Err(Error::MissingPop)
+    ));
+
+    msg.sign_pop::<_, DerSignature>(None, &signer).unwrap();
+    let popo = match &msg.popo {
+        Some(ProofOfPossession::Signature(popo)) => popo,
+        _ => panic!("unexpected popo"),
+    };
+    assert!(popo.poposk_input.is_none());
+    assert_eq!(popo.alg_id.oid, ECDSA_WITH_SHA_256);
+
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    msg.verify_pop::<VerifyingKey, DerSignature>().unwrap();
+
+    // The signature covers the certReq
+    let mut tampered = msg.clone();
+    tampered.cert_req.cert_req_id = Int::new(&[1]).unwrap();
+    assert!(matches!(
+        tampered.verify_pop::<VerifyingKey, DerSignature>(),
+        Err(Error::Signature(_))
+    ));
+
+    // The template contains subject and publicKey, so poposkInput must be omitted
+    let input = PopoSigningKeyInput {
+        auth_info: PopoSigningKeyInputChoice::Sender(GeneralName::DirectoryName(
+            "CN=MyName".parse().unwrap(),
+        )),
+        public_key: spki(signer.verifying_key()),
+    };
+    assert!(matches!(
+        PopoSigningKey::sign::<_, DerSignature>(&msg.cert_req, Some(input), &signer),
+        Err(Error::PoposkInput)
+    ));
+}
+
+#[test]
+fn sign_and_verify_poposk_input() {
+    let mut rng = rand::thread_rng();
+    let signer = SigningKey::random(&mut rng);
+    let input = PopoSigningKeyInput {
+        auth_info: PopoSigningKeyInputChoice::Sender(GeneralName::DirectoryName(
+            "CN=MyName".parse().unwrap(),
+        )),
+        public_key: spki(signer.verifying_key()),
+    };
+
+    // The template lacks the subject, so poposkInput must be present
+    let mut msg = builder()
+        .with_public_key(spki(signer.verifying_key()))
+        .build();
+    assert!(matches!(
+        msg.sign_pop::<_, DerSignature>(None, &signer),
+        Err(Error::PoposkInput)
+    ));
+
+    msg.sign_pop_with_rng::<_, DerSignature>(Some(input.clone()), &signer, &mut rng)
+        .unwrap();
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    msg.verify_pop::<VerifyingKey, DerSignature>().unwrap();
+
+    let popo = match &msg.popo {
+        Some(ProofOfPossession::Signature(popo)) => popo,
+        _ => panic!("unexpected popo"),
+    };
+    assert_eq!(popo.poposk_input, Some(input.clone()));
+    assert_eq!(
+        popo.public_key(&msg.cert_req),
+        Some(&spki(signer.verifying_key()))
+    );
+    assert_eq!(
+        PopoSigningKey::signed_data(&msg.cert_req, Some(&input)).unwrap(),
+        input.to_der().unwrap()
+    );
+
+    // The publicKey of the poposkInput must match the one of the template
+    let other = SigningKey::random(&mut rng);
+    let mut mismatch = msg.clone();
+    mismatch.cert_req.cert_template.subject_public_key_info = Some(spki(other.verifying_key()));
+    assert!(matches!(
+        mismatch.verify_pop::<VerifyingKey, DerSignature>(),
+        Err(Error::PoposkInput)
+    ));
+
+    // Without a public key in the template, the one of the poposkInput is used
+    let mut msg = builder().build();
+    msg.sign_pop::<_, DerSignature>(Some(input), &signer)
+        .unwrap();
+    msg.verify_pop::<VerifyingKey, DerSignature>().unwrap();
+}
@@ -0,0 +1,102 @@
+//! Key encipherment and key agreement proof-of-possession tests
+
+use cms::content_info::ContentInfo;
+use cms::enveloped_data::EnvelopedData;
+use crmf::builder::CertReqMsgBuilder;
+use crmf::pop::{
+    EncKeyWithID, EncKeyWithIdChoice, POPOPrivKey, PkMacValue, PrivateKeyInfo, ProofOfPossession,
+    SubsequentMessage,
+};
+use crmf::request::CertReqMsg;
+use der::asn1::{BitString, Ia5String, Int, ObjectIdentifier, OctetString};
+use der::{Decode, Encode};
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::ext::pkix::name::GeneralName;
+
+#[test]
+fn subsequent_message_test() {
+    let popo = ProofOfPossession::key_encipherment_subsequent(SubsequentMessage::EncrCert);
+    let der = popo.to_der().unwrap();
+    assert_eq!(der, [0xA2, 0x03, 0x81, 0x01, 0x00]);
+    let popo = ProofOfPossession::from_der(&der).unwrap();
+    assert_eq!(popo.subsequent_message(), Some(SubsequentMessage::EncrCert));
+    assert!(popo.priv_key().unwrap().encrypted_key().is_none());
+
+    let popo = ProofOfPossession::key_agreement_subsequent(SubsequentMessage::ChallengeResp);
+    let der = popo.to_der().unwrap();
+    assert_eq!(der, [0xA3, 0x03, 0x81, 0x01, 0x01]);
+    let popo = ProofOfPossession::from_der(&der).unwrap();
+    assert_eq!(
+        popo.subsequent_message(),
+        Some(SubsequentMessage::ChallengeResp)
+    );
+
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_subject("CN=MyName".parse().unwrap())
+        .with_pop(popo.clone())
+        .build();
+    let msg = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    assert_eq!(msg.popo, Some(popo));
+}
+
+#[test]
+fn agree_mac_test() {
+    let mac = PkMacValue {
+        alg_id: AlgorithmIdentifierOwned {
+            oid: ObjectIdentifier::new_unwrap("1.2.840.113549.2.9"),
+            parameters: None,
+        },
+        value: BitString::from_bytes(&[0xAA, 0xBB]).unwrap(),
+    };
+    let popo = ProofOfPossession::agree_mac(mac.clone());
+    let der = popo.to_der().unwrap();
+    assert_eq!(
+        der,
+        [
+            0xA3, 0x13, 0xA3, 0x11, 0x30, 0x0A, 0x06, 0x08, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D,
+            0x02, 0x09, 0x03, 0x03, 0x00, 0xAA, 0xBB
+        ]
+    );
+    let popo = ProofOfPossession::from_der(&der).unwrap();
+    assert_eq!(popo.priv_key().unwrap().agree_mac(), Some(&mac));
+    assert!(popo.subsequent_message().is_none());
+}
+
+#[test]
+fn encrypted_key_test() {
+    let der_ci = include_bytes!("examples/enveloped_data_ktri.bin");
+    let ci = ContentInfo::from_der(der_ci).unwrap();
+    let data = EnvelopedData::from_der(&ci.content.to_der().unwrap()).unwrap();
+
+    let popo = ProofOfPossession::encrypted_key(data.clone());
+    let der = popo.to_der().unwrap();
+    assert_eq!(&der[..1], &[0xA2]);
+    let popo = ProofOfPossession::from_der(&der).unwrap();
+    match &popo {
+        ProofOfPossession::KeyEncipherment(POPOPrivKey::EncryptedKey(decoded)) => {
+            assert_eq!(decoded, &data)
+        }
+        _ => panic!("unexpected popo"),
+    }
+    assert_eq!(popo.priv_key().unwrap().encrypted_key(), Some(&data));
+}
+
+#[test]
+fn enc_key_with_id_test() {
+    let key = EncKeyWithID {
+        priv_key: PrivateKeyInfo {
+            version: 0,
+            priv_key_alg: AlgorithmIdentifierOwned {
+                oid: ObjectIdentifier::new_unwrap("1.2.840.10045.2.1"),
+                parameters: None,
+            },
+            priv_key: OctetString::new([1, 2, 3]).unwrap(),
+            attrs: None,
+        },
+        identifier: Some(EncKeyWithIdChoice::GeneralName(GeneralName::DnsName(
+            Ia5String::new("example.com").unwrap(),
+        ))),
+    };
+    let der = key.to_der().unwrap();
+    assert_eq!(EncKeyWithID::from_der(&der).unwrap(), key);
+}
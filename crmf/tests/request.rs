@@ -1,5 +1,9 @@
+use const_oid::db::rfc5280::{ID_CE_AUTHORITY_KEY_IDENTIFIER, ID_CE_SUBJECT_KEY_IDENTIFIER};
+use crmf::builder::CertReqMsgBuilder;
 use crmf::request::{CertReqMessages, CertReqMsg, CertRequest, CertTemplate};
+use der::asn1::Int;
 use der::{Decode, Encode};
+use x509_cert::Certificate;
 
 #[test]
 fn certtemplate_test() {
@@ -71,3 +75,40 @@ fn certreqmsgs_test() {
     println!("Reencoded: {:02X?}", reencoded_header_01);
     assert_eq!(header_01, reencoded_header_01.as_slice());
 }
+
+#[test]
+fn certtemplate_from_certificate_test() {
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let template = CertTemplate::from_certificate(&cert);
+    assert_eq!(template.subject, Some(cert.tbs_certificate.subject.clone()));
+    assert_eq!(template.issuer, Some(cert.tbs_certificate.issuer.clone()));
+    assert!(template.version.is_none());
+    assert!(template.serial_number.is_none());
+    assert!(template.signature.is_none());
+    assert!(template.validity.is_none());
+    assert!(template.subject_public_key_info.is_none());
+
+    // the key identifiers are omitted
+    let extensions = template.extensions.as_ref().unwrap();
+    let cert_extensions = cert.tbs_certificate.extensions.as_ref().unwrap();
+    assert_eq!(extensions.len(), cert_extensions.len() - 2);
+    assert!(extensions
+        .iter()
+        .all(|ext| ext.extn_id != ID_CE_SUBJECT_KEY_IDENTIFIER
+            && ext.extn_id != ID_CE_AUTHORITY_KEY_IDENTIFIER));
+
+    // the public key and validity are set by the caller
+    let msg = CertReqMsgBuilder::new(Int::new(&[0]).unwrap())
+        .with_template(template.clone())
+        .with_public_key(cert.tbs_certificate.subject_public_key_info.clone())
+        .with_not_after(cert.tbs_certificate.validity.not_after)
+        .build();
+    let reencoded = CertReqMsg::from_der(&msg.to_der().unwrap()).unwrap();
+    let reencoded_template = &reencoded.cert_req.cert_template;
+    assert_eq!(reencoded_template.subject, template.subject);
+    assert_eq!(reencoded_template.extensions, template.extensions);
+    assert_eq!(
+        reencoded_template.subject_public_key_info.as_ref(),
+        Some(&cert.tbs_certificate.subject_public_key_info)
+    );
+}
@@ -1,6 +1,6 @@
 //! Attribute-related types used by the proc macro
 
-use crate::{Asn1Type, Tag, TagMode, TagNumber};
+use crate::{Asn1Type, Tag, TagClass, TagMode, TagNumber};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::{fmt::Debug, str::FromStr};
@@ -59,8 +59,15 @@ pub(crate) struct FieldAttrs {
     /// Value of the `#[asn1(type = "...")]` attribute if provided.
     pub asn1_type: Option<Asn1Type>,
 
-    /// Value of the `#[asn1(context_specific = "...")] attribute if provided.
-    pub context_specific: Option<TagNumber>,
+    /// Tag number supplied as either `#[asn1(context_specific = "...")]` or
+    /// `#[asn1(tag_number = "...")]`, if provided.
+    pub tag_number: Option<TagNumber>,
+
+    /// Class of the tag identified by `tag_number`, supplied as
+    /// `#[asn1(tag_class = "...")]`.
+    ///
+    /// The default value is `CONTEXT-SPECIFIC`.
+    pub tag_class: TagClass,
 
     /// Indicates name of function that supplies the default value, which will be used in cases
     /// where encoding is omitted per DER and to omit the encoding per DER
@@ -81,37 +88,88 @@ pub(crate) struct FieldAttrs {
 
     /// Is the inner type constructed?
     pub constructed: bool,
+
+    /// Path to a function used to decode this field in place of its
+    /// `Decode` impl, supplied as `#[asn1(decode_with = "...")]` or derived
+    /// from `#[asn1(with = "...")]`.
+    pub decode_with: Option<Path>,
+
+    /// Path to a function used to encode this field in place of its
+    /// `Encode` impl, supplied as `#[asn1(encode_with = "...")]` or derived
+    /// from `#[asn1(with = "...")]`.
+    pub encode_with: Option<Path>,
+
+    /// Should this field be ignored by the derived `ValueOrd` impl, supplied
+    /// as `#[asn1(skip_ord)]`?
+    pub skip_ord: bool,
+
+    /// ASN.1 identifier of this field, supplied as `#[asn1(rename = "...")]`,
+    /// which is reported in error paths in place of its Rust name.
+    #[cfg_attr(not(feature = "error-context"), allow(dead_code))]
+    pub rename: Option<String>,
 }
 
 impl FieldAttrs {
     /// Return true when either an optional or default ASN.1 attribute is associated
     /// with a field. Default signifies optionality due to omission of default values in
     /// DER encodings.
-    fn is_optional(&self) -> bool {
+    pub fn is_optional(&self) -> bool {
         self.optional || self.default.is_some()
     }
 
+    /// Name of the field with the given identifier as reported in error paths.
+    #[cfg(feature = "error-context")]
+    pub fn field_name(&self, ident: &Ident) -> String {
+        self.rename.clone().unwrap_or_else(|| ident.to_string())
+    }
+
+    /// Does this field use custom `decode_with` and/or `encode_with` hooks?
+    pub fn has_custom_hooks(&self) -> bool {
+        self.decode_with.is_some() || self.encode_with.is_some()
+    }
+
     /// Parse attributes from a struct field or enum variant.
     pub fn parse(attrs: &[Attribute], type_attrs: &TypeAttrs) -> syn::Result<Self> {
         let mut asn1_type = None;
         let mut context_specific = None;
+        let mut tag_number = None;
+        let mut tag_class = None;
         let mut default = None;
         let mut extensible = None;
         let mut optional = None;
         let mut tag_mode = None;
         let mut constructed = None;
+        let mut with = None;
+        let mut decode_with = None;
+        let mut encode_with = None;
+        let mut skip_ord = None;
+        let mut rename = None;
 
         let mut parsed_attrs = Vec::new();
         AttrNameValue::from_attributes(attrs, &mut parsed_attrs)?;
 
         for attr in parsed_attrs {
             // `context_specific = "..."` attribute
-            if let Some(tag_number) = attr.parse_value("context_specific")? {
-                if context_specific.is_some() {
-                    abort!(attr.name, "duplicate ASN.1 `context_specific` attribute");
+            if let Some(number) = attr.parse_value("context_specific")? {
+                if context_specific.is_some() || tag_number.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 tag number attribute");
                 }
 
-                context_specific = Some(tag_number);
+                context_specific = Some(number);
+            // `tag_number = "..."` attribute
+            } else if let Some(number) = attr.parse_value("tag_number")? {
+                if context_specific.is_some() || tag_number.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 tag number attribute");
+                }
+
+                tag_number = Some(number);
+            // `tag_class = "..."` attribute
+            } else if let Some(class) = attr.parse_value("tag_class")? {
+                if tag_class.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `tag_class` attribute");
+                }
+
+                tag_class = Some((attr.name.clone(), class));
             // `default` attribute
             } else if attr.parse_value::<String>("default")?.is_some() {
                 if default.is_some() {
@@ -159,33 +217,94 @@ impl FieldAttrs {
                 }
 
                 constructed = Some(ty);
+            // `with = "..."` attribute
+            } else if attr.parse_value::<String>("with")?.is_some() {
+                if with.is_some() || decode_with.is_some() || encode_with.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `with` attribute");
+                }
+
+                with = Some(attr.parse_path("with")?);
+            // `decode_with = "..."` attribute
+            } else if attr.parse_value::<String>("decode_with")?.is_some() {
+                if with.is_some() || decode_with.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `decode_with` attribute");
+                }
+
+                decode_with = Some(attr.parse_path("decode_with")?);
+            // `encode_with = "..."` attribute
+            } else if attr.parse_value::<String>("encode_with")?.is_some() {
+                if with.is_some() || encode_with.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `encode_with` attribute");
+                }
+
+                encode_with = Some(attr.parse_path("encode_with")?);
+            // `skip_ord` attribute
+            } else if let Some(skip) = attr.parse_value("skip_ord")? {
+                if skip_ord.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `skip_ord` attribute");
+                }
+
+                skip_ord = Some(skip);
+            // `rename = "..."` attribute
+            } else if let Some(name) = attr.parse_value("rename")? {
+                if rename.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `rename` attribute");
+                }
+
+                rename = Some(name);
             } else {
                 abort!(
                     attr.name,
                     "unknown field-level `asn1` attribute \
-                    (valid options are `context_specific`, `type`)",
+                    (valid options are `context_specific`, `tag_class`, `tag_number`, `type`, \
+                    `with`, `decode_with`, `encode_with`, `skip_ord`, `rename`)",
                 );
             }
         }
 
+        // `with = "module"` is shorthand for `module::decode` and `module::encode`
+        if let Some(module) = with {
+            decode_with = Some(module_fn(&module, "decode"));
+            encode_with = Some(module_fn(&module, "encode"));
+        }
+
+        let tag_class = match tag_class {
+            Some((name, _)) if tag_number.is_none() && context_specific.is_none() => {
+                abort!(name, "ASN.1 `tag_class` attribute requires a `tag_number`");
+            }
+            Some((name, class))
+                if context_specific.is_some() && class != TagClass::ContextSpecific =>
+            {
+                abort!(
+                    name,
+                    "ASN.1 `context_specific` attribute can't be used with `tag_class`; \
+                    use `tag_number` instead"
+                );
+            }
+            Some((_, class)) => class,
+            None => TagClass::default(),
+        };
+
         Ok(Self {
             asn1_type,
-            context_specific,
+            tag_number: tag_number.or(context_specific),
+            tag_class,
             default,
             extensible: extensible.unwrap_or_default(),
             optional: optional.unwrap_or_default(),
             tag_mode: tag_mode.unwrap_or(type_attrs.tag_mode),
             constructed: constructed.unwrap_or_default(),
+            decode_with,
+            encode_with,
+            skip_ord: skip_ord.unwrap_or_default(),
+            rename,
         })
     }
 
     /// Get the expected [`Tag`] for this field.
     pub fn tag(&self) -> syn::Result<Option<Tag>> {
-        match self.context_specific {
-            Some(tag_number) => Ok(Some(Tag::ContextSpecific {
-                constructed: self.constructed,
-                number: tag_number,
-            })),
+        match self.tag_number {
+            Some(tag_number) => Ok(Some(self.tag_class.tag(self.constructed, tag_number))),
 
             None => match self.tag_mode {
                 TagMode::Explicit => Ok(self.asn1_type.map(Tag::Universal)),
@@ -199,22 +318,23 @@ impl FieldAttrs {
 
     /// Get a `der::Decoder` object which respects these field attributes.
     pub fn decoder(&self) -> TokenStream {
-        if let Some(tag_number) = self.context_specific {
+        if let Some(tag_number) = self.tag_number {
             let type_params = self.asn1_type.map(|ty| ty.type_path()).unwrap_or_default();
             let tag_number = tag_number.to_tokens();
+            let class_type = self.tag_class.type_path();
 
-            let context_specific = match self.tag_mode {
+            let custom_class = match self.tag_mode {
                 TagMode::Explicit => {
                     if self.extensible || self.is_optional() {
                         quote! {
-                            ::der::asn1::ContextSpecific::<#type_params>::decode_explicit(
+                            #class_type::<#type_params>::decode_explicit(
                                 reader,
                                 #tag_number
                             )?
                         }
                     } else {
                         quote! {
-                            match ::der::asn1::ContextSpecific::<#type_params>::decode(reader)? {
+                            match #class_type::<#type_params>::decode(reader)? {
                                 field if field.tag_number == #tag_number => Some(field),
                                 _ => None
                             }
@@ -223,7 +343,7 @@ impl FieldAttrs {
                 }
                 TagMode::Implicit => {
                     quote! {
-                        ::der::asn1::ContextSpecific::<#type_params>::decode_implicit(
+                        #class_type::<#type_params>::decode_implicit(
                             reader,
                             #tag_number
                         )?
@@ -233,16 +353,17 @@ impl FieldAttrs {
 
             if self.is_optional() {
                 if let Some(default) = &self.default {
-                    quote!(#context_specific.map(|cs| cs.value).unwrap_or_else(#default))
+                    quote!(#custom_class.map(|cs| cs.value).unwrap_or_else(#default))
                 } else {
-                    quote!(#context_specific.map(|cs| cs.value))
+                    quote!(#custom_class.map(|cs| cs.value))
                 }
             } else {
                 // TODO(tarcieri): better error handling?
                 let constructed = self.constructed;
+                let tag_variant = self.tag_class.tag_variant();
                 quote! {
-                    #context_specific.ok_or_else(|| {
-                        der::Tag::ContextSpecific {
+                    #custom_class.ok_or_else(|| {
+                        der::Tag::#tag_variant {
                             number: #tag_number,
                             constructed: #constructed
                         }.value_error()
@@ -265,12 +386,13 @@ impl FieldAttrs {
 
     /// Get tokens to encode the binding using `::der::EncodeValue`.
     pub fn value_encode(&self, binding: &TokenStream) -> TokenStream {
-        match self.context_specific {
+        match self.tag_number {
             Some(tag_number) => {
                 let tag_number = tag_number.to_tokens();
                 let tag_mode = self.tag_mode.to_tokens();
+                let class_ref_type = self.tag_class.ref_type_path();
                 quote! {
-                    ::der::asn1::ContextSpecificRef {
+                    #class_ref_type {
                         tag_number: #tag_number,
                         tag_mode: #tag_mode,
                         value: #binding,
@@ -289,7 +411,17 @@ impl FieldAttrs {
     }
 }
 
+/// Get the path to the function with the given name in `module`.
+fn module_fn(module: &Path, name: &str) -> Path {
+    let mut path = module.clone();
+    path.segments
+        .push(Ident::new(name, Span::call_site()).into());
+    path
+}
+
 /// Name/value pair attribute.
+///
+/// A bare name such as `skip_ord` is shorthand for `skip_ord = "true"`.
 pub(crate) struct AttrNameValue {
     /// Attribute name.
     pub name: Path,
@@ -315,8 +447,11 @@ impl Parse for AttrNameValue {
                 }
             }
         };
-        input.parse::<Token![=]>()?;
-        let value = input.parse()?;
+        let value = if input.parse::<Option<Token![=]>>()?.is_some() {
+            input.parse()?
+        } else {
+            LitStr::new("true", Span::call_site())
+        };
         Ok(Self { name, value })
     }
 }
@@ -342,6 +477,16 @@ impl AttrNameValue {
         Ok(())
     }
 
+    /// Parse the attribute value as a [`Path`], using `name` in error messages.
+    pub fn parse_path(&self, name: &str) -> syn::Result<Path> {
+        self.value.parse().map_err(|e| {
+            syn::Error::new_spanned(
+                &self.value,
+                format_args!("error parsing ASN.1 `{name}` attribute: {e}"),
+            )
+        })
+    }
+
     /// Parse an attribute value if the name matches the specified one.
     pub fn parse_value<T>(&self, name: &str) -> syn::Result<Option<T>>
     where
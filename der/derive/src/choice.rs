@@ -140,7 +140,7 @@ impl DeriveChoice {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::DeriveChoice;
-    use crate::{Asn1Type, Tag, TagMode};
+    use crate::{Asn1Type, Tag, TagClass, TagMode};
     use syn::parse_quote;
 
     /// Based on `Time` as defined in RFC 5280:
@@ -171,7 +171,7 @@ mod tests {
         let utc_time = &ir.variants[0];
         assert_eq!(utc_time.ident, "UtcTime");
         assert_eq!(utc_time.attrs.asn1_type, Some(Asn1Type::UtcTime));
-        assert_eq!(utc_time.attrs.context_specific, None);
+        assert_eq!(utc_time.attrs.tag_number, None);
         assert_eq!(utc_time.attrs.tag_mode, TagMode::Explicit);
         assert_eq!(utc_time.tag, Tag::Universal(Asn1Type::UtcTime));
 
@@ -181,7 +181,7 @@ mod tests {
             general_time.attrs.asn1_type,
             Some(Asn1Type::GeneralizedTime)
         );
-        assert_eq!(general_time.attrs.context_specific, None);
+        assert_eq!(general_time.attrs.tag_number, None);
         assert_eq!(general_time.attrs.tag_mode, TagMode::Explicit);
         assert_eq!(general_time.tag, Tag::Universal(Asn1Type::GeneralizedTime));
     }
@@ -211,10 +211,7 @@ mod tests {
         let bit_string = &ir.variants[0];
         assert_eq!(bit_string.ident, "BitString");
         assert_eq!(bit_string.attrs.asn1_type, Some(Asn1Type::BitString));
-        assert_eq!(
-            bit_string.attrs.context_specific,
-            Some("0".parse().unwrap())
-        );
+        assert_eq!(bit_string.attrs.tag_number, Some("0".parse().unwrap()));
         assert_eq!(bit_string.attrs.tag_mode, TagMode::Implicit);
         assert_eq!(
             bit_string.tag,
@@ -227,7 +224,7 @@ mod tests {
         let time = &ir.variants[1];
         assert_eq!(time.ident, "Time");
         assert_eq!(time.attrs.asn1_type, Some(Asn1Type::GeneralizedTime));
-        assert_eq!(time.attrs.context_specific, Some("1".parse().unwrap()));
+        assert_eq!(time.attrs.tag_number, Some("1".parse().unwrap()));
         assert_eq!(time.attrs.tag_mode, TagMode::Implicit);
         assert_eq!(
             time.tag,
@@ -240,10 +237,7 @@ mod tests {
         let utf8_string = &ir.variants[2];
         assert_eq!(utf8_string.ident, "Utf8String");
         assert_eq!(utf8_string.attrs.asn1_type, Some(Asn1Type::Utf8String));
-        assert_eq!(
-            utf8_string.attrs.context_specific,
-            Some("2".parse().unwrap())
-        );
+        assert_eq!(utf8_string.attrs.tag_number, Some("2".parse().unwrap()));
         assert_eq!(utf8_string.attrs.tag_mode, TagMode::Implicit);
         assert_eq!(
             utf8_string.tag,
@@ -253,4 +247,55 @@ mod tests {
             }
         );
     }
+
+    /// `APPLICATION` and `PRIVATE` class tagged example
+    #[test]
+    fn tag_class_example() {
+        let input = parse_quote! {
+            #[asn1(tag_mode = "IMPLICIT")]
+            pub enum TagClassChoice {
+                #[asn1(tag_class = "APPLICATION", tag_number = "3", type = "UTF8String")]
+                Application(String),
+
+                #[asn1(tag_class = "PRIVATE", tag_number = "4", type = "UTF8String")]
+                Private(String),
+            }
+        };
+
+        let ir = DeriveChoice::new(input).unwrap();
+        assert_eq!(ir.variants.len(), 2);
+
+        let application = &ir.variants[0];
+        assert_eq!(application.attrs.tag_class, TagClass::Application);
+        assert_eq!(application.attrs.tag_number, Some("3".parse().unwrap()));
+        assert_eq!(
+            application.tag,
+            Tag::Application {
+                constructed: false,
+                number: "3".parse().unwrap()
+            }
+        );
+
+        let private = &ir.variants[1];
+        assert_eq!(private.attrs.tag_class, TagClass::Private);
+        assert_eq!(
+            private.tag,
+            Tag::Private {
+                constructed: false,
+                number: "4".parse().unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn tag_class_requires_tag_number() {
+        let input = parse_quote! {
+            pub enum MissingTagNumber {
+                #[asn1(tag_class = "APPLICATION", type = "UTF8String")]
+                Application(String),
+            }
+        };
+
+        assert!(DeriveChoice::new(input).is_err());
+    }
 }
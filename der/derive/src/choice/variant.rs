@@ -82,6 +82,13 @@ impl ChoiceVariant {
             abort!(&ident, "`extensible` is not allowed on CHOICE");
         }
 
+        if attrs.has_custom_hooks() {
+            abort!(
+                &ident,
+                "`with`, `decode_with`, and `encode_with` are not allowed on CHOICE"
+            );
+        }
+
         // Validate that variant is a 1-element tuple struct
         match &input.fields {
             // TODO(tarcieri): handle 0 bindings for ASN.1 NULL
@@ -123,13 +130,14 @@ impl ChoiceVariant {
     pub(super) fn to_value_len_tokens(&self) -> TokenStream {
         let ident = &self.ident;
 
-        match self.attrs.context_specific {
+        match self.attrs.tag_number {
             Some(tag_number) => {
                 let tag_number = tag_number.to_tokens();
                 let tag_mode = self.attrs.tag_mode.to_tokens();
+                let class_ref_type = self.attrs.tag_class.ref_type_path();
 
                 quote! {
-                    Self::#ident(variant) => ::der::asn1::ContextSpecificRef {
+                    Self::#ident(variant) => #class_ref_type {
                         tag_number: #tag_number,
                         tag_mode: #tag_mode,
                         value: variant,
@@ -254,7 +262,7 @@ mod tests {
                 let ident = Ident::new("ExplicitVariant", Span::call_site());
                 let attrs = FieldAttrs {
                     constructed,
-                    context_specific: Some(TagNumber(tag_number)),
+                    tag_number: Some(TagNumber(tag_number)),
                     ..Default::default()
                 };
                 assert_eq!(attrs.tag_mode, TagMode::Explicit);
@@ -339,7 +347,7 @@ mod tests {
 
                 let attrs = FieldAttrs {
                     constructed,
-                    context_specific: Some(TagNumber(tag_number)),
+                    tag_number: Some(TagNumber(tag_number)),
                     tag_mode: TagMode::Implicit,
                     ..Default::default()
                 };
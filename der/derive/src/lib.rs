@@ -57,6 +57,20 @@
 //!
 //! The value must be quoted and contain a number, e.g. `#[asn1(context_specific = "29")]`.
 //!
+//! ### `#[asn1(tag_class = "...", tag_number = "...")]` attributes: `APPLICATION` and `PRIVATE` support
+//!
+//! These attributes can be added to associate a tag of a particular class
+//! with a given enum variant or struct field.
+//!
+//! The `tag_class` attribute accepts `APPLICATION`, `CONTEXT-SPECIFIC`, or
+//! `PRIVATE`, and defaults to `CONTEXT-SPECIFIC` when omitted, in which case
+//! `tag_number` behaves the same as `context_specific`. For example:
+//! `#[asn1(tag_class = "APPLICATION", tag_number = "0")]`.
+//!
+//! These fields are otherwise handled exactly like `CONTEXT-SPECIFIC` fields,
+//! including support for the `tag_mode`, `optional`, and `extensible`
+//! attributes.
+//!
 //! ### `#[asn1(default = "...")]` attribute: `DEFAULT` support
 //!
 //! This behaves like `serde_derive`'s `default` attribute, allowing you to
@@ -72,6 +86,25 @@
 //!
 //! This attribute explicitly annotates a field as `OPTIONAL`.
 //!
+//! ### `#[asn1(skip_ord)]` attribute: exclude a field from ordering
+//!
+//! This attribute can be applied to the fields of `struct` types which derive
+//! `DerOrd` (or `ValueOrd`), and causes the field to be ignored when comparing
+//! values. This is useful for fields which don't affect the identity of a
+//! value, e.g. cached or volatile data.
+//!
+//! Structs with `OPTIONAL` or `DEFAULT` fields are ordered by comparing their
+//! complete encodings rather than field-by-field, and can't use this
+//! attribute.
+//!
+//! ### `#[asn1(rename = "...")]` attribute: ASN.1 field name
+//!
+//! This attribute can be applied to the fields of `struct` types to give the
+//! name of the field in the ASN.1 module, e.g. `tbsCertificate`. With the
+//! `error-context` feature, this name is recorded in the path of errors which
+//! occur while decoding (`Sequence`) or comparing field-by-field
+//! (`DerOrd`/`ValueOrd`) the field, in place of its Rust name.
+//!
 //! ### `#[asn1(type = "...")]` attribute: ASN.1 type declaration
 //!
 //! This attribute can be used to specify the ASN.1 type for a particular
@@ -98,6 +131,23 @@
 //! This attribute can be used to specify that an "inner" type is constructed. It is most
 //! commonly used when a `CHOICE` has a constructed inner type.
 //!
+//! ### `#[asn1(with = "...")]`, `decode_with`, and `encode_with` attributes: custom field logic
+//!
+//! These attributes behave like their `serde_derive` counterparts, allowing a
+//! single `struct` field to be decoded and/or encoded with custom logic:
+//!
+//! - `decode_with = "path"`: a function with the signature
+//!   `fn<'a, R: der::Reader<'a>>(reader: &mut R) -> der::Result<T>` which
+//!   decodes the complete TLV production for the field.
+//! - `encode_with = "path"`: a function with the signature
+//!   `fn(&T) -> der::Result<impl der::Encode>` which returns the value to be
+//!   encoded in place of the field.
+//! - `with = "module"`: shorthand for `decode_with = "module::decode"` and
+//!   `encode_with = "module::encode"`.
+//!
+//! These attributes can't be combined with the `type`, `default`, or tag number
+//! attributes, and aren't supported on `enum` variants.
+//!
 //! Note: please open a GitHub Issue if you would like to request support
 //! for additional ASN.1 types.
 //!
@@ -141,7 +191,7 @@ use crate::{
     choice::DeriveChoice,
     enumerated::DeriveEnumerated,
     sequence::DeriveSequence,
-    tag::{Tag, TagMode, TagNumber},
+    tag::{Tag, TagClass, TagMode, TagNumber},
     value_ord::DeriveValueOrd,
 };
 use proc_macro::TokenStream;
@@ -284,6 +334,9 @@ pub fn derive_sequence(input: TokenStream) -> TokenStream {
 /// This trait is used in conjunction with ASN.1 `SET OF` types to determine
 /// the lexicographical order of their DER encodings.
 ///
+/// Prefer the equivalent [`DerOrd`][`macro@DerOrd`] derive, which is named
+/// after the trait most callers use.
+///
 /// [1]: https://docs.rs/der/latest/der/trait.ValueOrd.html
 #[proc_macro_derive(ValueOrd, attributes(asn1))]
 pub fn derive_value_ord(input: TokenStream) -> TokenStream {
@@ -293,3 +346,19 @@ pub fn derive_value_ord(input: TokenStream) -> TokenStream {
         Err(e) => e.to_compile_error().into(),
     }
 }
+
+/// Derive the [`DerOrd`][1] trait on a `struct`.
+///
+/// This emits a [`ValueOrd`][2] impl, from which `DerOrd` is provided for any
+/// type which also impls `EncodeValue` and `Tagged` (e.g. via `Sequence`).
+///
+/// Fields annotated with `#[asn1(skip_ord)]` are ignored when comparing. The
+/// complete encodings of structs with `OPTIONAL` or `DEFAULT` fields are
+/// compared instead.
+///
+/// [1]: https://docs.rs/der/latest/der/trait.DerOrd.html
+/// [2]: https://docs.rs/der/latest/der/trait.ValueOrd.html
+#[proc_macro_derive(DerOrd, attributes(asn1))]
+pub fn derive_der_ord(input: TokenStream) -> TokenStream {
+    derive_value_ord(input)
+}
@@ -76,7 +76,7 @@ impl DeriveSequence {
         let mut encode_fields = Vec::new();
 
         for field in &self.fields {
-            decode_body.push(field.to_decode_tokens());
+            decode_body.push(self.field_decode_tokens(field));
             decode_result.push(&field.ident);
 
             let field = field.to_encode_tokens();
@@ -125,6 +125,31 @@ impl DeriveSequence {
             impl #impl_generics ::der::Sequence<#lifetime> for #ident #ty_generics #where_clause {}
         }
     }
+
+    /// Decode a field, recording its name in any errors which occur while
+    /// decoding it.
+    #[cfg(feature = "error-context")]
+    fn field_decode_tokens(&self, field: &SequenceField) -> TokenStream {
+        let type_name = self.ident.to_string();
+        let field_ident = &field.ident;
+        let field_name = field.attrs.field_name(field_ident);
+        let decode_field = field.to_decode_tokens();
+
+        quote! {
+            #[allow(clippy::redundant_closure_call)]
+            let #field_ident = (|| -> ::der::Result<_> {
+                #decode_field
+                Ok(#field_ident)
+            })()
+            .map_err(|e| e.in_field(#type_name, #field_name))?;
+        }
+    }
+
+    /// Decode a field.
+    #[cfg(not(feature = "error-context"))]
+    fn field_decode_tokens(&self, field: &SequenceField) -> TokenStream {
+        field.to_decode_tokens()
+    }
 }
 
 #[cfg(test)]
@@ -133,7 +158,8 @@ impl DeriveSequence {
 mod tests {
     use super::DeriveSequence;
     use crate::{Asn1Type, TagMode};
-    use syn::parse_quote;
+    use quote::quote;
+    use syn::{parse_quote, Path};
 
     /// X.509 SPKI `AlgorithmIdentifier`.
     #[test]
@@ -157,13 +183,13 @@ mod tests {
         let algorithm_field = &ir.fields[0];
         assert_eq!(algorithm_field.ident, "algorithm");
         assert_eq!(algorithm_field.attrs.asn1_type, None);
-        assert_eq!(algorithm_field.attrs.context_specific, None);
+        assert_eq!(algorithm_field.attrs.tag_number, None);
         assert_eq!(algorithm_field.attrs.tag_mode, TagMode::Explicit);
 
         let parameters_field = &ir.fields[1];
         assert_eq!(parameters_field.ident, "parameters");
         assert_eq!(parameters_field.attrs.asn1_type, None);
-        assert_eq!(parameters_field.attrs.context_specific, None);
+        assert_eq!(parameters_field.attrs.tag_number, None);
         assert_eq!(parameters_field.attrs.tag_mode, TagMode::Explicit);
     }
 
@@ -191,7 +217,7 @@ mod tests {
         let algorithm_field = &ir.fields[0];
         assert_eq!(algorithm_field.ident, "algorithm");
         assert_eq!(algorithm_field.attrs.asn1_type, None);
-        assert_eq!(algorithm_field.attrs.context_specific, None);
+        assert_eq!(algorithm_field.attrs.tag_number, None);
         assert_eq!(algorithm_field.attrs.tag_mode, TagMode::Explicit);
 
         let subject_public_key_field = &ir.fields[1];
@@ -200,7 +226,7 @@ mod tests {
             subject_public_key_field.attrs.asn1_type,
             Some(Asn1Type::BitString)
         );
-        assert_eq!(subject_public_key_field.attrs.context_specific, None);
+        assert_eq!(subject_public_key_field.attrs.tag_number, None);
         assert_eq!(subject_public_key_field.attrs.tag_mode, TagMode::Explicit);
     }
 
@@ -259,7 +285,7 @@ mod tests {
         let version_field = &ir.fields[0];
         assert_eq!(version_field.ident, "version");
         assert_eq!(version_field.attrs.asn1_type, None);
-        assert_eq!(version_field.attrs.context_specific, None);
+        assert_eq!(version_field.attrs.tag_number, None);
         assert_eq!(version_field.attrs.extensible, false);
         assert_eq!(version_field.attrs.optional, false);
         assert_eq!(version_field.attrs.tag_mode, TagMode::Explicit);
@@ -267,7 +293,7 @@ mod tests {
         let algorithm_field = &ir.fields[1];
         assert_eq!(algorithm_field.ident, "private_key_algorithm");
         assert_eq!(algorithm_field.attrs.asn1_type, None);
-        assert_eq!(algorithm_field.attrs.context_specific, None);
+        assert_eq!(algorithm_field.attrs.tag_number, None);
         assert_eq!(algorithm_field.attrs.extensible, false);
         assert_eq!(algorithm_field.attrs.optional, false);
         assert_eq!(algorithm_field.attrs.tag_mode, TagMode::Explicit);
@@ -278,7 +304,7 @@ mod tests {
             private_key_field.attrs.asn1_type,
             Some(Asn1Type::OctetString)
         );
-        assert_eq!(private_key_field.attrs.context_specific, None);
+        assert_eq!(private_key_field.attrs.tag_number, None);
         assert_eq!(private_key_field.attrs.extensible, false);
         assert_eq!(private_key_field.attrs.optional, false);
         assert_eq!(private_key_field.attrs.tag_mode, TagMode::Explicit);
@@ -287,7 +313,7 @@ mod tests {
         assert_eq!(attributes_field.ident, "attributes");
         assert_eq!(attributes_field.attrs.asn1_type, None);
         assert_eq!(
-            attributes_field.attrs.context_specific,
+            attributes_field.attrs.tag_number,
             Some("0".parse().unwrap())
         );
         assert_eq!(attributes_field.attrs.extensible, true);
@@ -298,7 +324,7 @@ mod tests {
         assert_eq!(public_key_field.ident, "public_key");
         assert_eq!(public_key_field.attrs.asn1_type, Some(Asn1Type::BitString));
         assert_eq!(
-            public_key_field.attrs.context_specific,
+            public_key_field.attrs.tag_number,
             Some("1".parse().unwrap())
         );
         assert_eq!(public_key_field.attrs.extensible, true);
@@ -334,25 +360,66 @@ mod tests {
         let bit_string = &ir.fields[0];
         assert_eq!(bit_string.ident, "bit_string");
         assert_eq!(bit_string.attrs.asn1_type, Some(Asn1Type::BitString));
-        assert_eq!(
-            bit_string.attrs.context_specific,
-            Some("0".parse().unwrap())
-        );
+        assert_eq!(bit_string.attrs.tag_number, Some("0".parse().unwrap()));
         assert_eq!(bit_string.attrs.tag_mode, TagMode::Implicit);
 
         let time = &ir.fields[1];
         assert_eq!(time.ident, "time");
         assert_eq!(time.attrs.asn1_type, Some(Asn1Type::GeneralizedTime));
-        assert_eq!(time.attrs.context_specific, Some("1".parse().unwrap()));
+        assert_eq!(time.attrs.tag_number, Some("1".parse().unwrap()));
         assert_eq!(time.attrs.tag_mode, TagMode::Implicit);
 
         let utf8_string = &ir.fields[2];
         assert_eq!(utf8_string.ident, "utf8_string");
         assert_eq!(utf8_string.attrs.asn1_type, Some(Asn1Type::Utf8String));
-        assert_eq!(
-            utf8_string.attrs.context_specific,
-            Some("2".parse().unwrap())
-        );
+        assert_eq!(utf8_string.attrs.tag_number, Some("2".parse().unwrap()));
         assert_eq!(utf8_string.attrs.tag_mode, TagMode::Implicit);
     }
+
+    /// Custom `with`, `decode_with`, and `encode_with` hooks
+    #[test]
+    fn custom_hooks_example() {
+        let input = parse_quote! {
+            pub struct CustomHooks {
+                #[asn1(with = "flags_codec")]
+                flags: Flags,
+
+                #[asn1(decode_with = "decode_version", encode_with = "encode_version")]
+                version: u8,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert_eq!(ir.fields.len(), 2);
+
+        let flags = &ir.fields[0].attrs;
+        let path = |attr: &Option<Path>| quote!(#attr).to_string();
+        assert_eq!(path(&flags.decode_with), "flags_codec :: decode");
+        assert_eq!(path(&flags.encode_with), "flags_codec :: encode");
+
+        let version = &ir.fields[1].attrs;
+        assert_eq!(path(&version.decode_with), "decode_version");
+        assert_eq!(path(&version.encode_with), "encode_version");
+    }
+
+    #[test]
+    fn custom_hooks_conflicting_attrs() {
+        let input = parse_quote! {
+            pub struct Conflicting {
+                #[asn1(with = "flags_codec", type = "BIT STRING")]
+                flags: Flags,
+            }
+        };
+
+        assert!(DeriveSequence::new(input).is_err());
+
+        let input = parse_quote! {
+            pub struct Duplicate {
+                #[asn1(with = "flags_codec", decode_with = "decode_flags")]
+                flags: Flags,
+            }
+        };
+
+        assert!(DeriveSequence::new(input).is_err());
+    }
 }
@@ -1,6 +1,6 @@
 //! Sequence field IR and lowerings
 
-use crate::{Asn1Type, FieldAttrs, TagMode, TagNumber, TypeAttrs};
+use crate::{Asn1Type, FieldAttrs, TagClass, TagMode, TagNumber, TypeAttrs};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Field, Ident, Path, Type};
@@ -36,6 +36,16 @@ impl SequenceField {
             ));
         }
 
+        if attrs.has_custom_hooks()
+            && (attrs.asn1_type.is_some() || attrs.default.is_some() || attrs.tag_number.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`with`, `decode_with`, and `encode_with` can't be combined with \
+                `type`, `default`, or tag number attributes",
+            ));
+        }
+
         if attrs.default.is_some() && attrs.optional {
             return Err(syn::Error::new_spanned(
                 ident,
@@ -52,6 +62,13 @@ impl SequenceField {
 
     /// Derive code for decoding a field of a sequence.
     pub(super) fn to_decode_tokens(&self) -> TokenStream {
+        if let Some(decode_with) = &self.attrs.decode_with {
+            let ident = &self.ident;
+            return quote! {
+                let #ident = #decode_with(reader)?;
+            };
+        }
+
         let mut lowerer = LowerFieldDecoder::new(&self.attrs);
 
         if self.attrs.asn1_type.is_some() {
@@ -65,8 +82,8 @@ impl SequenceField {
                 "`type` and `default` are mutually exclusive"
             );
 
-            // TODO(tarcieri): support for context-specific fields with defaults?
-            if self.attrs.context_specific.is_none() {
+            // TODO(tarcieri): support for tagged fields with defaults?
+            if self.attrs.tag_number.is_none() {
                 lowerer.apply_default(default, &self.field_type);
             }
         }
@@ -76,6 +93,11 @@ impl SequenceField {
 
     /// Derive code for encoding a field of a sequence.
     pub(super) fn to_encode_tokens(&self) -> TokenStream {
+        if let Some(encode_with) = &self.attrs.encode_with {
+            let ident = &self.ident;
+            return quote!(#encode_with(&self.#ident)?);
+        }
+
         let mut lowerer = LowerFieldEncoder::new(&self.ident);
         let attrs = &self.attrs;
 
@@ -88,8 +110,13 @@ impl SequenceField {
             lowerer.apply_asn1_type(ty, attrs.optional);
         }
 
-        if let Some(tag_number) = &attrs.context_specific {
-            lowerer.apply_context_specific(tag_number, &attrs.tag_mode, attrs.optional);
+        if let Some(tag_number) = &attrs.tag_number {
+            lowerer.apply_tag_class(
+                &attrs.tag_class,
+                tag_number,
+                &attrs.tag_mode,
+                attrs.optional,
+            );
         }
 
         if let Some(default) = &attrs.default {
@@ -201,21 +228,23 @@ impl LowerFieldEncoder {
         };
     }
 
-    /// Make this field context-specific.
-    fn apply_context_specific(
+    /// Make this field context-specific (or of another non-`UNIVERSAL` class).
+    fn apply_tag_class(
         &mut self,
+        tag_class: &TagClass,
         tag_number: &TagNumber,
         tag_mode: &TagMode,
         optional: bool,
     ) {
         let encoder = &self.encoder;
+        let class_ref_type = tag_class.ref_type_path();
         let number_tokens = tag_number.to_tokens();
         let mode_tokens = tag_mode.to_tokens();
 
         if optional {
             self.encoder = quote! {
                 #encoder.as_ref().map(|field| {
-                    ::der::asn1::ContextSpecificRef {
+                    #class_ref_type {
                         tag_number: #number_tokens,
                         tag_mode: #mode_tokens,
                         value: field,
@@ -224,7 +253,7 @@ impl LowerFieldEncoder {
             };
         } else {
             self.encoder = quote! {
-                ::der::asn1::ContextSpecificRef {
+                #class_ref_type {
                     tag_number: #number_tokens,
                     tag_mode: #mode_tokens,
                     value: &#encoder,
@@ -237,7 +266,7 @@ impl LowerFieldEncoder {
 #[cfg(test)]
 mod tests {
     use super::SequenceField;
-    use crate::{FieldAttrs, TagMode, TagNumber};
+    use crate::{FieldAttrs, TagClass, TagMode, TagNumber};
     use proc_macro2::Span;
     use quote::quote;
     use syn::{punctuated::Punctuated, Ident, Path, PathSegment, Type, TypePath};
@@ -266,12 +295,17 @@ mod tests {
 
         let attrs = FieldAttrs {
             asn1_type: None,
-            context_specific: None,
+            tag_number: None,
+            tag_class: TagClass::ContextSpecific,
             default: None,
             extensible: false,
             optional: false,
             tag_mode: TagMode::Explicit,
             constructed: false,
+            decode_with: None,
+            encode_with: None,
+            skip_ord: false,
+            rename: None,
         };
 
         let field_type = Ident::new("String", span);
@@ -306,12 +340,17 @@ mod tests {
 
         let attrs = FieldAttrs {
             asn1_type: None,
-            context_specific: Some(TagNumber(0)),
+            tag_number: Some(TagNumber(0)),
+            tag_class: TagClass::ContextSpecific,
             default: None,
             extensible: false,
             optional: false,
             tag_mode: TagMode::Implicit,
             constructed: false,
+            decode_with: None,
+            encode_with: None,
+            skip_ord: false,
+            rename: None,
         };
 
         let field_type = Ident::new("String", span);
@@ -353,4 +392,65 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn application() {
+        let span = Span::call_site();
+        let ident = Ident::new("application_field", span);
+
+        let attrs = FieldAttrs {
+            asn1_type: None,
+            tag_number: Some(TagNumber(1)),
+            tag_class: TagClass::Application,
+            default: None,
+            extensible: false,
+            optional: false,
+            tag_mode: TagMode::Explicit,
+            constructed: false,
+            decode_with: None,
+            encode_with: None,
+            skip_ord: false,
+            rename: None,
+        };
+
+        let field_type = Ident::new("String", span);
+
+        let field = SequenceField {
+            ident,
+            attrs,
+            field_type: type_path(field_type),
+        };
+
+        assert_eq!(
+            field.to_decode_tokens().to_string(),
+            quote! {
+                let application_field =
+                    match ::der::asn1::Application::<>::decode(reader)? {
+                        field if field.tag_number == ::der::TagNumber::N1 => Some(field),
+                        _ => None
+                    }
+                    .ok_or_else(|| {
+                        der::Tag::Application {
+                            number: ::der::TagNumber::N1,
+                            constructed: false
+                        }
+                        .value_error()
+                    })?
+                    .value;
+            }
+            .to_string()
+        );
+
+        assert_eq!(
+            field.to_encode_tokens().to_string(),
+            quote! {
+                ::der::asn1::ApplicationRef {
+                    tag_number: ::der::TagNumber::N1,
+                    tag_mode: ::der::TagMode::Explicit,
+                    value: &self.application_field,
+                }
+            }
+            .to_string()
+        );
+    }
 }
@@ -14,6 +14,15 @@ pub(crate) enum Tag {
     /// Universal tags with an associated [`Asn1Type`].
     Universal(Asn1Type),
 
+    /// Application tags with an associated [`TagNumber`].
+    Application {
+        /// Is the inner ASN.1 type constructed?
+        constructed: bool,
+
+        /// Application tag number
+        number: TagNumber,
+    },
+
     /// Context-specific tags with an associated [`TagNumber`].
     ContextSpecific {
         /// Is the inner ASN.1 type constructed?
@@ -22,36 +31,140 @@ pub(crate) enum Tag {
         /// Context-specific tag number
         number: TagNumber,
     },
+
+    /// Private tags with an associated [`TagNumber`].
+    Private {
+        /// Is the inner ASN.1 type constructed?
+        constructed: bool,
+
+        /// Private tag number
+        number: TagNumber,
+    },
 }
 
 impl Tag {
     /// Lower this [`Tag`] to a [`TokenStream`].
     pub fn to_tokens(self) -> TokenStream {
-        match self {
-            Tag::Universal(ty) => ty.tag(),
+        let (class, constructed, number) = match self {
+            Tag::Universal(ty) => return ty.tag(),
+            Tag::Application {
+                constructed,
+                number,
+            } => (TagClass::Application, constructed, number),
             Tag::ContextSpecific {
                 constructed,
                 number,
-            } => {
-                let constructed = if constructed {
-                    quote!(true)
-                } else {
-                    quote!(false)
-                };
-
-                let number = number.to_tokens();
-
-                quote! {
-                    ::der::Tag::ContextSpecific {
-                        constructed: #constructed,
-                        number: #number,
-                    }
-                }
+            } => (TagClass::ContextSpecific, constructed, number),
+            Tag::Private {
+                constructed,
+                number,
+            } => (TagClass::Private, constructed, number),
+        };
+
+        let variant = class.tag_variant();
+
+        let constructed = if constructed {
+            quote!(true)
+        } else {
+            quote!(false)
+        };
+
+        let number = number.to_tokens();
+
+        quote! {
+            ::der::Tag::#variant {
+                constructed: #constructed,
+                number: #number,
             }
         }
     }
 }
 
+/// Tag classes other than `UNIVERSAL`, as selected using the
+/// `#[asn1(tag_class = "...")]` attribute.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub(crate) enum TagClass {
+    /// `APPLICATION` class.
+    Application,
+
+    /// `CONTEXT-SPECIFIC` class.
+    #[default]
+    ContextSpecific,
+
+    /// `PRIVATE` class.
+    Private,
+}
+
+impl TagClass {
+    /// Create a [`Tag`] of this class.
+    pub fn tag(self, constructed: bool, number: TagNumber) -> Tag {
+        match self {
+            TagClass::Application => Tag::Application {
+                constructed,
+                number,
+            },
+            TagClass::ContextSpecific => Tag::ContextSpecific {
+                constructed,
+                number,
+            },
+            TagClass::Private => Tag::Private {
+                constructed,
+                number,
+            },
+        }
+    }
+
+    /// Get the name of the `der::Tag` variant for this class.
+    pub fn tag_variant(self) -> TokenStream {
+        match self {
+            TagClass::Application => quote!(Application),
+            TagClass::ContextSpecific => quote!(ContextSpecific),
+            TagClass::Private => quote!(Private),
+        }
+    }
+
+    /// Get the path to the `der` crate's owned field type for this class.
+    pub fn type_path(self) -> TokenStream {
+        match self {
+            TagClass::Application => quote!(::der::asn1::Application),
+            TagClass::ContextSpecific => quote!(::der::asn1::ContextSpecific),
+            TagClass::Private => quote!(::der::asn1::Private),
+        }
+    }
+
+    /// Get the path to the `der` crate's field reference type for this class.
+    pub fn ref_type_path(self) -> TokenStream {
+        match self {
+            TagClass::Application => quote!(::der::asn1::ApplicationRef),
+            TagClass::ContextSpecific => quote!(::der::asn1::ContextSpecificRef),
+            TagClass::Private => quote!(::der::asn1::PrivateRef),
+        }
+    }
+}
+
+impl FromStr for TagClass {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        match s {
+            "APPLICATION" | "application" => Ok(TagClass::Application),
+            "CONTEXT-SPECIFIC" | "context-specific" => Ok(TagClass::ContextSpecific),
+            "PRIVATE" | "private" => Ok(TagClass::Private),
+            _ => Err(ParseError),
+        }
+    }
+}
+
+impl Display for TagClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagClass::Application => f.write_str("APPLICATION"),
+            TagClass::ContextSpecific => f.write_str("CONTEXT-SPECIFIC"),
+            TagClass::Private => f.write_str("PRIVATE"),
+        }
+    }
+}
+
 /// Tagging modes: `EXPLICIT` versus `IMPLICIT`.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub(crate) enum TagMode {
@@ -99,14 +212,11 @@ impl Display for TagMode {
     }
 }
 
-/// ASN.1 tag numbers (i.e. lower 5 bits of a [`Tag`]).
+/// ASN.1 tag numbers.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-pub(crate) struct TagNumber(pub u8);
+pub(crate) struct TagNumber(pub u32);
 
 impl TagNumber {
-    /// Maximum tag number supported (inclusive).
-    pub const MAX: u8 = 30;
-
     /// Get tokens describing this tag.
     pub fn to_tokens(self) -> TokenStream {
         match self.0 {
@@ -141,7 +251,7 @@ impl TagNumber {
             28 => quote!(::der::TagNumber::N28),
             29 => quote!(::der::TagNumber::N29),
             30 => quote!(::der::TagNumber::N30),
-            _ => unreachable!("tag number out of range: {}", self),
+            n => quote!(::der::TagNumber::new(#n)),
         }
     }
 }
@@ -150,13 +260,7 @@ impl FromStr for TagNumber {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, ParseError> {
-        let n = s.parse::<u8>().map_err(|_| ParseError)?;
-
-        if n <= Self::MAX {
-            Ok(Self(n))
-        } else {
-            Err(ParseError)
-        }
+        s.parse::<u32>().map(Self).map_err(|_| ParseError)
     }
 }
 
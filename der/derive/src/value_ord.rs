@@ -2,6 +2,18 @@
 //!
 //! This trait is used in conjunction with ASN.1 `SET OF` types to determine
 //! the lexicographical order of their DER encodings.
+//!
+//! Structs are compared field-by-field, which avoids computing the complete
+//! encoding of either value. Fields annotated with `#[asn1(skip_ord)]` are
+//! ignored.
+//!
+//! This only agrees with the order of the encodings if the same fields are
+//! present in both values: the encodings of structs with `OPTIONAL` or
+//! `DEFAULT` fields are instead compared as a whole, see
+//! `der::encoded_value_cmp`. Such structs can't have `skip_ord` fields.
+//!
+//! With the `error-context` feature, errors which occur while comparing a field
+//! record its name, or the name given by `#[asn1(rename = "...")]`.
 
 // TODO(tarcieri): enum support
 
@@ -23,6 +35,9 @@ pub(crate) struct DeriveValueOrd {
 
     /// Type of input provided (`enum` or `struct`).
     input_type: InputType,
+
+    /// Are the complete encodings compared, rather than field-by-field?
+    compare_encodings: bool,
 }
 
 impl DeriveValueOrd {
@@ -38,7 +53,7 @@ impl DeriveValueOrd {
             .next()
             .map(|lt| lt.lifetime.clone());
 
-        let (fields, input_type) = match input.data {
+        let (fields, input_type): (Vec<ValueField>, _) = match input.data {
             syn::Data::Enum(data) => (
                 data.variants
                     .into_iter()
@@ -60,11 +75,24 @@ impl DeriveValueOrd {
             ),
         };
 
+        // Omitted fields shift the encodings of the following ones
+        let compare_encodings =
+            input_type == InputType::Struct && fields.iter().any(|field| field.attrs.is_optional());
+
+        if compare_encodings && fields.iter().any(|field| field.attrs.skip_ord) {
+            abort!(
+                ident,
+                "`skip_ord` is not supported on structs with `OPTIONAL` or `DEFAULT` fields: \
+                 their complete encodings are compared"
+            );
+        }
+
         Ok(Self {
             ident,
             lifetime,
             fields,
             input_type,
+            compare_encodings,
         })
     }
 
@@ -83,10 +111,15 @@ impl DeriveValueOrd {
         let mut body = Vec::new();
 
         for field in &self.fields {
-            body.push(field.to_tokens());
+            body.push(field.to_tokens(ident));
         }
 
         let body = match self.input_type {
+            _ if self.compare_encodings => {
+                quote! {
+                    ::der::encoded_value_cmp(self, other)
+                }
+            }
             InputType::Enum => {
                 quote! {
                     #[allow(unused_imports)]
@@ -145,6 +178,15 @@ impl ValueField {
         let ident = variant.ident;
 
         let attrs = FieldAttrs::parse(&variant.attrs, type_attrs)?;
+
+        if attrs.skip_ord {
+            abort!(
+                ident,
+                "`skip_ord` is not supported on enum variants: \
+                 only one variant can be present"
+            );
+        }
+
         Ok(Self {
             ident,
             attrs,
@@ -168,7 +210,7 @@ impl ValueField {
     }
 
     /// Lower to [`TokenStream`].
-    fn to_tokens(&self) -> TokenStream {
+    fn to_tokens(&self, type_ident: &Ident) -> TokenStream {
         let ident = &self.ident;
 
         if self.is_enum {
@@ -177,6 +219,8 @@ impl ValueField {
             quote! {
                 (#binding1, #binding2) => this.value_cmp(other),
             }
+        } else if self.attrs.skip_ord {
+            TokenStream::new()
         } else {
             let mut binding1 = quote!(self.#ident);
             let mut binding2 = quote!(other.#ident);
@@ -186,12 +230,29 @@ impl ValueField {
                 binding2 = ty.encoder(&binding2);
             }
 
+            let context = self.error_context(type_ident);
+
             quote! {
-                match #binding1.der_cmp(&#binding2)? {
+                match #binding1.der_cmp(&#binding2)#context? {
                     ::core::cmp::Ordering::Equal => (),
                     other => return Ok(other),
                 }
             }
         }
     }
+
+    /// Record the name of this field in any errors which occur while
+    /// comparing it.
+    #[cfg(feature = "error-context")]
+    fn error_context(&self, type_ident: &Ident) -> TokenStream {
+        let type_name = type_ident.to_string();
+        let field_name = self.attrs.field_name(&self.ident);
+        quote!(.map_err(|e| e.in_field(#type_name, #field_name)))
+    }
+
+    /// Errors which occur while comparing fields aren't annotated.
+    #[cfg(not(feature = "error-context"))]
+    fn error_context(&self, _type_ident: &Ident) -> TokenStream {
+        TokenStream::new()
+    }
 }
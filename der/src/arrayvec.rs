@@ -25,16 +25,34 @@ impl<T, const N: usize> ArrayVec<T, N> {
 
     /// Push an item into this [`ArrayVec`].
     pub fn push(&mut self, item: T) -> Result<()> {
+        self.try_push(item)
+            .map_err(|_| ErrorKind::Overlength.into())
+    }
+
+    /// Push an item into this [`ArrayVec`], returning it back if the
+    /// [`ArrayVec`] is already full.
+    pub fn try_push(&mut self, item: T) -> core::result::Result<(), T> {
         match self.length.checked_add(1) {
             Some(n) if n <= N => {
                 self.elements[self.length] = Some(item);
                 self.length = n;
                 Ok(())
             }
-            _ => Err(ErrorKind::Overlength.into()),
+            _ => Err(item),
         }
     }
 
+    /// Insert an item at the given index, shifting all subsequent items.
+    pub fn insert(&mut self, index: usize, item: T) -> Result<()> {
+        if index > self.length {
+            return Err(ErrorKind::Overflow.into());
+        }
+
+        self.push(item)?;
+        self.elements[index..self.length].rotate_right(1);
+        Ok(())
+    }
+
     /// Get an element from this [`ArrayVec`].
     pub fn get(&self, index: usize) -> Option<&T> {
         match self.elements.get(index) {
@@ -45,7 +63,7 @@ impl<T, const N: usize> ArrayVec<T, N> {
 
     /// Iterate over the elements in this [`ArrayVec`].
     pub fn iter(&self) -> Iter<'_, T> {
-        Iter::new(&self.elements)
+        Iter::new(self.as_ref())
     }
 
     /// Is this [`ArrayVec`] empty?
@@ -53,6 +71,11 @@ impl<T, const N: usize> ArrayVec<T, N> {
         self.length == 0
     }
 
+    /// Is this [`ArrayVec`] full?
+    pub fn is_full(&self) -> bool {
+        self.length == N
+    }
+
     /// Get the number of elements in this [`ArrayVec`].
     pub fn len(&self) -> usize {
         self.length
@@ -142,5 +165,30 @@ mod tests {
 
         assert_eq!(vec.push(4).err().unwrap(), ErrorKind::Overlength.into());
         assert_eq!(vec.len(), 3);
+        assert_eq!(vec.try_push(4), Err(4));
+    }
+
+    #[test]
+    fn insert() {
+        let mut vec = ArrayVec::<u8, 4>::new();
+        vec.push(1).unwrap();
+        vec.push(3).unwrap();
+        vec.insert(1, 2).unwrap();
+        vec.insert(0, 0).unwrap();
+
+        assert!(vec.is_full());
+        assert!(vec.iter().copied().eq([0, 1, 2, 3]));
+        assert_eq!(
+            vec.insert(0, 4).err().unwrap(),
+            ErrorKind::Overlength.into()
+        );
+    }
+
+    #[test]
+    fn iter_len() {
+        let mut vec = ArrayVec::<u8, 4>::new();
+        vec.push(1).unwrap();
+        vec.push(2).unwrap();
+        assert_eq!(vec.iter().len(), 2);
     }
 }
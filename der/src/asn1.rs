@@ -5,6 +5,7 @@
 mod internal_macros;
 
 mod any;
+mod application;
 mod bit_string;
 #[cfg(feature = "alloc")]
 mod bmp_string;
@@ -14,17 +15,21 @@ mod context_specific;
 mod generalized_time;
 mod ia5_string;
 mod integer;
+mod lenient_string;
 mod null;
 mod octet_string;
 #[cfg(feature = "oid")]
 mod oid;
 mod optional;
 mod printable_string;
+mod private;
 #[cfg(feature = "real")]
 mod real;
+mod relative_oid;
 mod sequence;
 mod sequence_of;
 mod set_of;
+mod tagged;
 mod teletex_string;
 mod utc_time;
 mod utf8_string;
@@ -32,18 +37,23 @@ mod videotex_string;
 
 pub use self::{
     any::AnyRef,
+    application::{Application, ApplicationRef},
     bit_string::{BitStringIter, BitStringRef},
     choice::Choice,
     context_specific::{ContextSpecific, ContextSpecificRef},
     generalized_time::GeneralizedTime,
     ia5_string::Ia5StringRef,
     integer::{int::IntRef, uint::UintRef},
+    lenient_string::{LenientIa5StringRef, LenientPrintableStringRef},
     null::Null,
     octet_string::OctetStringRef,
     printable_string::PrintableStringRef,
+    private::{Private, PrivateRef},
+    relative_oid::{RelativeOid, RelativeOidArcs},
     sequence::{Sequence, SequenceRef},
     sequence_of::{SequenceOf, SequenceOfIter},
-    set_of::{SetOf, SetOfIter},
+    set_of::{SetOf, SetOfIter, SetOfPolicy},
+    tagged::{Explicit, Implicit},
     teletex_string::TeletexStringRef,
     utc_time::UtcTime,
     utf8_string::Utf8StringRef,
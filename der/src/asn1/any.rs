@@ -3,8 +3,8 @@
 #![cfg_attr(feature = "arbitrary", allow(clippy::arithmetic_side_effects))]
 
 use crate::{
-    BytesRef, Choice, Decode, DecodeValue, DerOrd, EncodeValue, Error, ErrorKind, Header, Length,
-    Reader, SliceReader, Tag, Tagged, ValueOrd, Writer,
+    BytesRef, Choice, Decode, DecodeValue, DerOrd, EncodeValue, Error, ErrorKind, FixedTag, Header,
+    Length, Reader, SliceReader, Tag, Tagged, ValueOrd, Writer,
 };
 use core::cmp::Ordering;
 
@@ -56,6 +56,10 @@ impl<'a> AnyRef<'a> {
     }
 
     /// Attempt to decode this [`AnyRef`] type into the inner value.
+    ///
+    /// The value is decoded with a new reader, so the nesting depth of the
+    /// reader this value was read from doesn't carry over to it. See
+    /// [`Reader::max_depth`].
     pub fn decode_as<T>(self) -> Result<T, <T as DecodeValue<'a>>::Error>
     where
         T: Choice<'a> + DecodeValue<'a>,
@@ -74,6 +78,29 @@ impl<'a> AnyRef<'a> {
         Ok(decoder.finish(result)?)
     }
 
+    /// Attempt to decode this [`AnyRef`] as an `IMPLICIT`-tagged value of
+    /// type `T`, i.e. decoding the value as though it were tagged with
+    /// `T::TAG` rather than the tag it was actually encoded with.
+    ///
+    /// The constructed bit of the actual tag must match that of `T::TAG`.
+    pub fn decode_as_implicit<T>(self) -> Result<T, <T as DecodeValue<'a>>::Error>
+    where
+        T: DecodeValue<'a> + FixedTag,
+    {
+        if self.tag.is_constructed() != T::TAG.is_constructed() {
+            return Err(self.tag.non_canonical_error().into());
+        }
+
+        let header = Header {
+            tag: T::TAG,
+            length: self.value.len(),
+        };
+
+        let mut decoder = SliceReader::new(self.value())?;
+        let result = T::decode_value(&mut decoder, header)?;
+        Ok(decoder.finish(result)?)
+    }
+
     /// Is this value an ASN.1 `NULL` value?
     pub fn is_null(self) -> bool {
         self == Self::NULL
@@ -201,6 +228,15 @@ mod allocating {
             AnyRef::from(self).decode_as()
         }
 
+        /// Attempt to decode this [`Any`] as an `IMPLICIT`-tagged value of
+        /// type `T`. See [`AnyRef::decode_as_implicit`].
+        pub fn decode_as_implicit<'a, T>(&'a self) -> Result<T, <T as DecodeValue<'a>>::Error>
+        where
+            T: DecodeValue<'a> + FixedTag,
+        {
+            AnyRef::from(self).decode_as_implicit()
+        }
+
         /// Encode the provided type as an [`Any`] value.
         pub fn encode_from<T>(msg: &T) -> Result<Self, Error>
         where
@@ -323,3 +359,37 @@ mod allocating {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::AnyRef;
+    use crate::{asn1::OctetStringRef, Decode, ErrorKind, Tag, TagNumber};
+    use hex_literal::hex;
+
+    #[test]
+    fn decode_as_implicit() {
+        // [0] IMPLICIT OCTET STRING
+        let any = AnyRef::from_der(&hex!("8002ABCD")).unwrap();
+        assert!(any.decode_as::<OctetStringRef<'_>>().is_err());
+
+        let octet_string = any.decode_as_implicit::<OctetStringRef<'_>>().unwrap();
+        assert_eq!(octet_string.as_bytes(), &hex!("ABCD"));
+    }
+
+    #[test]
+    fn decode_as_implicit_constructed_mismatch() {
+        // [0] (constructed) can't be an IMPLICIT OCTET STRING
+        let any = AnyRef::from_der(&hex!("A002ABCD")).unwrap();
+        let err = any.decode_as_implicit::<OctetStringRef<'_>>().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Noncanonical {
+                tag: Tag::ContextSpecific {
+                    constructed: true,
+                    number: TagNumber::N0
+                }
+            }
+        );
+    }
+}
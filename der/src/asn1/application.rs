@@ -0,0 +1,67 @@
+//! Application class field.
+
+use crate::{
+    asn1::AnyRef, Choice, Class, Decode, DecodeValue, DerOrd, Encode, EncodeValue, EncodeValueRef,
+    Error, Header, Length, Reader, Tag, TagMode, TagNumber, Tagged, ValueOrd, Writer,
+};
+use core::cmp::Ordering;
+
+impl_custom_class!(Application, Application, "APPLICATION", "0b01000000");
+impl_custom_class_ref!(ApplicationRef, Application, "APPLICATION", "0b01000000");
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Application, ApplicationRef};
+    use crate::{Decode, Encode, SliceReader, Tag, TagMode, TagNumber, Tagged};
+    use hex_literal::hex;
+
+    #[test]
+    fn round_trip() {
+        let field = Application::<u8>::from_der(&hex!("6103020100")).unwrap();
+        assert_eq!(field.tag_number, TagNumber::N1);
+        assert_eq!(field.tag_mode, TagMode::Explicit);
+        assert_eq!(field.value, 0);
+
+        let mut buf = [0u8; 5];
+        let encoded = field.encode_to_slice(&mut buf).unwrap();
+        assert_eq!(encoded, &hex!("6103020100"));
+    }
+
+    #[test]
+    fn implicit_field() {
+        let mut reader = SliceReader::new(&hex!("4101FF")).unwrap();
+        let field = Application::<bool>::decode_implicit(&mut reader, TagNumber::N1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.tag_mode, TagMode::Implicit);
+        assert!(field.value);
+
+        let field_ref = ApplicationRef {
+            tag_number: TagNumber::N1,
+            tag_mode: TagMode::Implicit,
+            value: &true,
+        };
+        assert_eq!(
+            field_ref.tag(),
+            Tag::Application {
+                constructed: false,
+                number: TagNumber::N1
+            }
+        );
+
+        let mut buf = [0u8; 3];
+        let encoded = field_ref.encode_to_slice(&mut buf).unwrap();
+        assert_eq!(encoded, &hex!("4101FF"));
+    }
+
+    #[test]
+    fn ignores_other_classes() {
+        // `[1] EXPLICIT INTEGER` in the `CONTEXT-SPECIFIC` class
+        let mut reader = SliceReader::new(&hex!("A103020100")).unwrap();
+        assert_eq!(
+            Application::<u8>::decode_explicit(&mut reader, TagNumber::N1).unwrap(),
+            None
+        );
+    }
+}
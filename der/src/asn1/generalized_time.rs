@@ -13,7 +13,7 @@ use core::time::Duration;
 use {crate::asn1::AnyRef, std::time::SystemTime};
 
 #[cfg(feature = "time")]
-use time::PrimitiveDateTime;
+use time::{OffsetDateTime, PrimitiveDateTime};
 
 /// ASN.1 `GeneralizedTime` type.
 ///
@@ -313,6 +313,42 @@ impl TryFrom<GeneralizedTime> for PrimitiveDateTime {
     }
 }
 
+#[cfg(feature = "time")]
+impl TryFrom<OffsetDateTime> for GeneralizedTime {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<GeneralizedTime> {
+        DateTime::try_from(time).map(Into::into)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<GeneralizedTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTime) -> Result<OffsetDateTime> {
+        time.to_date_time().try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for GeneralizedTime {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<GeneralizedTime> {
+        DateTime::try_from(time).map(Into::into)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<GeneralizedTime> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTime) -> Result<chrono::DateTime<chrono::Utc>> {
+        time.to_date_time().try_into()
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -81,6 +81,19 @@ impl<'a> From<Ia5StringRef<'a>> for AnyRef<'a> {
     }
 }
 
+// Implement by hand because the derive would create invalid values.
+// Use the constructor to create a valid value.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Ia5StringRef<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::new(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&'a [u8]>::size_hint(depth)
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub use self::allocation::Ia5String;
 
@@ -149,6 +162,19 @@ mod allocation {
         }
     }
 
+    // Implement by hand because the derive would create invalid values.
+    // Use the constructor to create a valid value.
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Ia5String {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            Self::new(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            <&'a [u8]>::size_hint(depth)
+        }
+    }
+
     impl<'a> RefToOwned<'a> for Ia5StringRef<'a> {
         type Owned = Ia5String;
         fn ref_to_owned(&self) -> Self::Owned {
@@ -3,6 +3,11 @@
 pub(super) mod int;
 pub(super) mod uint;
 
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
+
 use core::{cmp::Ordering, mem};
 
 use crate::{EncodeValue, Result, SliceWriter};
@@ -0,0 +1,98 @@
+//! Conversions between ASN.1 `INTEGER` types and [`crypto_bigint::Uint`].
+
+use super::uint::UintRef;
+use crate::{Error, Result, Tag};
+use crypto_bigint::{Limb, Word};
+
+impl<'a, const LIMBS: usize> TryFrom<UintRef<'a>> for crypto_bigint::Uint<LIMBS> {
+    type Error = Error;
+
+    fn try_from(uint: UintRef<'a>) -> Result<Self> {
+        let bytes = uint.as_bytes();
+
+        if bytes.len() > Self::BYTES {
+            return Err(Tag::Integer.length_error());
+        }
+
+        // Limbs are stored least significant first, whereas the bytes of an
+        // `INTEGER` are big endian
+        let mut words = [0; LIMBS];
+
+        for (word, chunk) in words.iter_mut().zip(bytes.rchunks(Limb::BYTES)) {
+            *word = chunk
+                .iter()
+                .fold(0, |acc: Word, &byte| acc << 8 | Word::from(byte));
+        }
+
+        Ok(Self::from_words(words))
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod allocating {
+    use crate::{asn1::Uint, referenced::OwnedToRef, Error, Result};
+    use alloc::vec::Vec;
+
+    impl<const LIMBS: usize> TryFrom<&Uint> for crypto_bigint::Uint<LIMBS> {
+        type Error = Error;
+
+        fn try_from(uint: &Uint) -> Result<Self> {
+            uint.owned_to_ref().try_into()
+        }
+    }
+
+    impl<const LIMBS: usize> TryFrom<&crypto_bigint::Uint<LIMBS>> for Uint {
+        type Error = Error;
+
+        fn try_from(uint: &crypto_bigint::Uint<LIMBS>) -> Result<Self> {
+            let bytes = uint
+                .as_words()
+                .iter()
+                .rev()
+                .flat_map(|word| word.to_be_bytes())
+                .collect::<Vec<u8>>();
+
+            // Leading zeroes are stripped by `Uint::new`
+            Self::new(&bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{asn1::UintRef, ErrorKind, Tag};
+    use crypto_bigint::{U128, U64};
+
+    #[test]
+    fn try_from_uintref() {
+        let uint = UintRef::new(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]).unwrap();
+        assert_eq!(
+            U128::try_from(uint).unwrap(),
+            U128::from_be_hex("00000000000000010203040506070809")
+        );
+
+        let zero = UintRef::new(&[0]).unwrap();
+        assert_eq!(U64::try_from(zero).unwrap(), U64::ZERO);
+    }
+
+    #[test]
+    fn reject_oversized_uintref() {
+        let uint = UintRef::new(&[0x01; 9]).unwrap();
+        assert_eq!(
+            U64::try_from(uint).unwrap_err().kind(),
+            ErrorKind::Length { tag: Tag::Integer }
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn uint_round_trip() {
+        use crate::asn1::Uint;
+
+        let n = U128::from_be_hex("000000000000000000000000000080FF");
+        let uint = Uint::try_from(&n).unwrap();
+        assert_eq!(uint.as_bytes(), &[0x80, 0xFF]);
+        assert_eq!(U128::try_from(&uint).unwrap(), n);
+    }
+}
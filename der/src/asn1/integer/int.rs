@@ -18,7 +18,7 @@ macro_rules! impl_encoding_traits {
 
                 fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> $crate::Result<Self> {
                     let mut buf = [0u8; Self::BITS as usize / 8];
-                    let max_length = u32::from(header.length) as usize;
+                    let max_length = usize::try_from(header.length)?;
 
                     if max_length > buf.len() {
                         return Err(Self::TAG.non_canonical_error());
@@ -164,6 +164,19 @@ impl<'a> FixedTag for IntRef<'a> {
 
 impl<'a> OrdIsValueOrd for IntRef<'a> {}
 
+// Implement by hand because the derive would create invalid values.
+// Use the constructor to create a valid value.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for IntRef<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::new(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&'a [u8]>::size_hint(depth)
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod allocating {
     use super::{strip_leading_ones, validate_canonical, IntRef};
@@ -275,6 +288,19 @@ mod allocating {
 
     impl OrdIsValueOrd for Int {}
 
+    // Implement by hand because the derive would create invalid values.
+    // Use the constructor to create a valid value.
+    #[cfg(feature = "arbitrary")]
+    impl<'a> arbitrary::Arbitrary<'a> for Int {
+        fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+            Self::new(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            <&'a [u8]>::size_hint(depth)
+        }
+    }
+
     impl<'a> RefToOwned<'a> for IntRef<'a> {
         type Owned = Int;
         fn ref_to_owned(&self) -> Self::Owned {
@@ -0,0 +1,132 @@
+//! Conversions between ASN.1 `INTEGER` types and [`num_bigint`]'s
+//! [`BigUint`] and [`BigInt`].
+
+use super::{
+    int::{Int, IntRef},
+    uint::{Uint, UintRef},
+};
+use crate::{referenced::OwnedToRef, Error, Result, Tag};
+use num_bigint::{BigInt, BigUint, Sign};
+
+impl<'a> From<UintRef<'a>> for BigUint {
+    fn from(uint: UintRef<'a>) -> BigUint {
+        BigUint::from_bytes_be(uint.as_bytes())
+    }
+}
+
+impl From<&Uint> for BigUint {
+    fn from(uint: &Uint) -> BigUint {
+        uint.owned_to_ref().into()
+    }
+}
+
+impl<'a> From<UintRef<'a>> for BigInt {
+    fn from(uint: UintRef<'a>) -> BigInt {
+        BigInt::from_bytes_be(Sign::Plus, uint.as_bytes())
+    }
+}
+
+impl<'a> From<IntRef<'a>> for BigInt {
+    fn from(int: IntRef<'a>) -> BigInt {
+        BigInt::from_signed_bytes_be(int.as_bytes())
+    }
+}
+
+impl From<&Int> for BigInt {
+    fn from(int: &Int) -> BigInt {
+        int.owned_to_ref().into()
+    }
+}
+
+impl TryFrom<&BigUint> for Uint {
+    type Error = Error;
+
+    fn try_from(n: &BigUint) -> Result<Uint> {
+        Uint::new(&n.to_bytes_be())
+    }
+}
+
+impl TryFrom<&BigInt> for Uint {
+    type Error = Error;
+
+    fn try_from(n: &BigInt) -> Result<Uint> {
+        match n.to_bytes_be() {
+            (Sign::Minus, _) => Err(Tag::Integer.value_error()),
+            (_, bytes) => Uint::new(&bytes),
+        }
+    }
+}
+
+impl TryFrom<&BigInt> for Int {
+    type Error = Error;
+
+    fn try_from(n: &BigInt) -> Result<Int> {
+        // Two's complement encoding of minimal length, as required for DER
+        Int::new(&n.to_signed_bytes_be())
+    }
+}
+
+impl<'a> TryFrom<IntRef<'a>> for BigUint {
+    type Error = Error;
+
+    fn try_from(int: IntRef<'a>) -> Result<BigUint> {
+        BigInt::from(int)
+            .to_biguint()
+            .ok_or_else(|| Tag::Integer.value_error())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        asn1::{Int, IntRef, Uint, UintRef},
+        Decode, Encode, ErrorKind, Tag,
+    };
+    use hex_literal::hex;
+    use num_bigint::{BigInt, BigUint};
+
+    #[test]
+    fn uint_conversions() {
+        let uint = UintRef::from_der(&hex!("0203 00 80FF")).unwrap();
+        let n = BigUint::from(uint);
+        assert_eq!(n, BigUint::from(0x80FFu32));
+
+        let uint = Uint::try_from(&n).unwrap();
+        assert_eq!(uint.to_der().unwrap(), hex!("0203 00 80FF"));
+        assert_eq!(BigUint::from(&uint), n);
+
+        let zero = Uint::try_from(&BigUint::from(0u8)).unwrap();
+        assert_eq!(zero.to_der().unwrap(), hex!("020100"));
+    }
+
+    #[test]
+    fn int_conversions() {
+        for (value, der) in [
+            (-129i32, &hex!("0202 FF7F")[..]),
+            (-128, &hex!("0201 80")),
+            (0, &hex!("0201 00")),
+            (128, &hex!("0202 0080")),
+        ] {
+            let int = IntRef::from_der(der).unwrap();
+            let n = BigInt::from(int);
+            assert_eq!(n, BigInt::from(value));
+
+            let int = Int::try_from(&n).unwrap();
+            assert_eq!(int.to_der().unwrap(), der);
+            assert_eq!(BigInt::from(&int), n);
+        }
+    }
+
+    #[test]
+    fn reject_negative_unsigned() {
+        let n = BigInt::from(-1);
+        assert_eq!(
+            Uint::try_from(&n).unwrap_err().kind(),
+            ErrorKind::Value { tag: Tag::Integer }
+        );
+
+        let int = IntRef::new(&[0xFF]).unwrap();
+        assert!(BigUint::try_from(int).is_err());
+    }
+}
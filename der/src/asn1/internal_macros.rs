@@ -75,3 +75,265 @@ macro_rules! impl_string_type {
         }
     };
 }
+
+/// Implement a type which wraps an owned field of a non-`UNIVERSAL` tag class.
+macro_rules! impl_custom_class {
+    ($class_type:ident, $class_enum_name:ident, $asn1_class_name:literal, $class_bits_str:literal) => {
+        #[doc = concat!("`", $asn1_class_name, "` field which wraps an owned inner value.")]
+        ///
+        /// This type decodes/encodes a field which is identified by a [`TagNumber`]
+        #[doc = concat!("of the `", $asn1_class_name, "` class.")]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        pub struct $class_type<T> {
+            #[doc = concat!("`", $asn1_class_name, "` tag number sans the leading `", $class_bits_str, "` class")]
+            /// identifier bits and `0b100000` constructed flag.
+            pub tag_number: TagNumber,
+
+            /// Tag mode: `EXPLICIT` VS `IMPLICIT`.
+            pub tag_mode: TagMode,
+
+            /// Value of the field.
+            pub value: T,
+        }
+
+        impl<T> $class_type<T> {
+            #[doc = concat!("Attempt to decode an `EXPLICIT` ASN.1 `", $asn1_class_name, "` field with the")]
+            /// provided [`TagNumber`].
+            ///
+            /// This method has the following behavior which is designed to simplify
+            /// handling of extension fields, which are denoted in an ASN.1 schema
+            /// using the `...` ellipsis extension marker:
+            ///
+            #[doc = concat!("- Skips over [`", stringify!($class_type), "`] fields with a tag number lower than")]
+            ///   the current one, consuming and ignoring them.
+            #[doc = concat!("- Returns `Ok(None)` if a [`", stringify!($class_type), "`] field with a higher tag")]
+            ///   number is encountered. These fields are not consumed in this case,
+            ///   allowing a field with a lower tag number to be omitted, then the
+            ///   higher numbered field consumed as a follow-up.
+            #[doc = concat!("- Returns `Ok(None)` if anything other than a [`", stringify!($class_type), "`] field")]
+            ///   is encountered.
+            pub fn decode_explicit<'a, R: Reader<'a>>(
+                reader: &mut R,
+                tag_number: TagNumber,
+            ) -> Result<Option<Self>, T::Error>
+            where
+                T: Decode<'a>,
+            {
+                Self::decode_with(reader, tag_number, |reader| Self::decode(reader))
+            }
+
+            #[doc = concat!("Attempt to decode an `IMPLICIT` ASN.1 `", $asn1_class_name, "` field with the")]
+            /// provided [`TagNumber`].
+            ///
+            /// This method otherwise behaves the same as `decode_explicit`,
+            /// but should be used in cases where the particular fields are `IMPLICIT`
+            /// as opposed to `EXPLICIT`.
+            pub fn decode_implicit<'a, R: Reader<'a>>(
+                reader: &mut R,
+                tag_number: TagNumber,
+            ) -> Result<Option<Self>, T::Error>
+            where
+                T: DecodeValue<'a> + Tagged,
+            {
+                Self::decode_with::<_, _, T::Error>(reader, tag_number, |reader| {
+                    let header = Header::decode(reader)?;
+                    let value = T::decode_value(reader, header)?;
+
+                    if header.tag.is_constructed() != value.tag().is_constructed() {
+                        return Err(header.tag.non_canonical_error().into());
+                    }
+
+                    Ok(Self {
+                        tag_number,
+                        tag_mode: TagMode::Implicit,
+                        value,
+                    })
+                })
+            }
+
+            #[doc = concat!("Attempt to decode an `", $asn1_class_name, "` field with the given")]
+            /// helper callback.
+            fn decode_with<'a, F, R: Reader<'a>, E>(
+                reader: &mut R,
+                tag_number: TagNumber,
+                f: F,
+            ) -> Result<Option<Self>, E>
+            where
+                F: FnOnce(&mut R) -> Result<Self, E>,
+                E: From<Error>,
+            {
+                while !reader.is_finished() {
+                    let tag = reader.peek_tag()?;
+
+                    if tag.class() != Class::$class_enum_name || (tag.number() > tag_number) {
+                        break;
+                    } else if tag.number() == tag_number {
+                        return Some(f(reader)).transpose();
+                    } else {
+                        AnyRef::decode(reader)?;
+                    }
+                }
+
+                Ok(None)
+            }
+        }
+
+        impl<'a, T> Choice<'a> for $class_type<T>
+        where
+            T: Decode<'a> + Tagged,
+        {
+            fn can_decode(tag: Tag) -> bool {
+                tag.class() == Class::$class_enum_name
+            }
+        }
+
+        impl<'a, T> Decode<'a> for $class_type<T>
+        where
+            T: Decode<'a>,
+        {
+            type Error = T::Error;
+
+            fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self, Self::Error> {
+                let header = Header::decode(reader)?;
+
+                match header.tag {
+                    Tag::$class_enum_name {
+                        number,
+                        constructed: true,
+                    } => Ok(Self {
+                        tag_number: number,
+                        tag_mode: TagMode::default(),
+                        value: reader.read_nested(header.length, |reader| T::decode(reader))?,
+                    }),
+                    tag => Err(tag.unexpected_error(None).into()),
+                }
+            }
+        }
+
+        impl<T> EncodeValue for $class_type<T>
+        where
+            T: EncodeValue + Tagged,
+        {
+            fn value_len(&self) -> Result<Length, Error> {
+                match self.tag_mode {
+                    TagMode::Explicit => self.value.encoded_len(),
+                    TagMode::Implicit => self.value.value_len(),
+                }
+            }
+
+            fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+                match self.tag_mode {
+                    TagMode::Explicit => self.value.encode(writer),
+                    TagMode::Implicit => self.value.encode_value(writer),
+                }
+            }
+        }
+
+        impl<T> Tagged for $class_type<T>
+        where
+            T: Tagged,
+        {
+            fn tag(&self) -> Tag {
+                let constructed = match self.tag_mode {
+                    TagMode::Explicit => true,
+                    TagMode::Implicit => self.value.tag().is_constructed(),
+                };
+
+                Tag::$class_enum_name {
+                    number: self.tag_number,
+                    constructed,
+                }
+            }
+        }
+
+        impl<'a, T> TryFrom<AnyRef<'a>> for $class_type<T>
+        where
+            T: Decode<'a>,
+        {
+            type Error = T::Error;
+
+            fn try_from(any: AnyRef<'a>) -> Result<$class_type<T>, Self::Error> {
+                match any.tag() {
+                    Tag::$class_enum_name {
+                        number,
+                        constructed: true,
+                    } => Ok(Self {
+                        tag_number: number,
+                        tag_mode: TagMode::default(),
+                        value: T::from_der(any.value())?,
+                    }),
+                    tag => Err(tag.unexpected_error(None).into()),
+                }
+            }
+        }
+
+        impl<T> ValueOrd for $class_type<T>
+        where
+            T: EncodeValue + ValueOrd + Tagged,
+        {
+            fn value_cmp(&self, other: &Self) -> Result<Ordering, Error> {
+                match self.tag_mode {
+                    TagMode::Explicit => self.der_cmp(other),
+                    TagMode::Implicit => self.value_cmp(other),
+                }
+            }
+        }
+    };
+}
+
+/// Implement an encode-only reference type for a field of a non-`UNIVERSAL`
+/// tag class.
+macro_rules! impl_custom_class_ref {
+    ($ref_class_type:ident, $class_type:ident, $asn1_class_name:literal, $class_bits_str:literal) => {
+        #[doc = concat!("`", $asn1_class_name, "` field reference.")]
+        ///
+        /// This type encodes a field which is identified by a [`TagNumber`]
+        #[doc = concat!("of the `", $asn1_class_name, "` class.")]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+        pub struct $ref_class_type<'a, T> {
+            #[doc = concat!("`", $asn1_class_name, "` tag number sans the leading `", $class_bits_str, "` class")]
+            /// identifier bits and `0b100000` constructed flag.
+            pub tag_number: TagNumber,
+
+            /// Tag mode: `EXPLICIT` VS `IMPLICIT`.
+            pub tag_mode: TagMode,
+
+            /// Value of the field.
+            pub value: &'a T,
+        }
+
+        impl<'a, T> $ref_class_type<'a, T> {
+            #[doc = concat!("Convert to a [`", stringify!($class_type), "`].")]
+            fn encoder(&self) -> $class_type<EncodeValueRef<'a, T>> {
+                $class_type {
+                    tag_number: self.tag_number,
+                    tag_mode: self.tag_mode,
+                    value: EncodeValueRef(self.value),
+                }
+            }
+        }
+
+        impl<'a, T> EncodeValue for $ref_class_type<'a, T>
+        where
+            T: EncodeValue + Tagged,
+        {
+            fn value_len(&self) -> Result<Length, Error> {
+                self.encoder().value_len()
+            }
+
+            fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+                self.encoder().encode_value(writer)
+            }
+        }
+
+        impl<'a, T> Tagged for $ref_class_type<'a, T>
+        where
+            T: Tagged,
+        {
+            fn tag(&self) -> Tag {
+                self.encoder().tag()
+            }
+        }
+    };
+}
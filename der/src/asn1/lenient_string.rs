@@ -0,0 +1,198 @@
+//! Lenient ASN.1 string types which tolerate invalid characters.
+//!
+//! Certificates in the wild frequently contain `PrintableString` values with
+//! characters outside of the permitted set (e.g. `@` or `_`), and `IA5String`
+//! values containing bytes above `0x7F`. The strict [`PrintableStringRef`] and
+//! [`Ia5StringRef`] types reject such values, which in turn makes any message
+//! containing them unparseable.
+//!
+//! The types in this module can be used as fallbacks in such cases: they
+//! accept any contents with the expected tag, preserve the raw bytes so they
+//! can be re-encoded verbatim, and provide (lossy) conversions to strings.
+
+use crate::{
+    asn1::{AnyRef, Ia5StringRef, PrintableStringRef},
+    ord::OrdIsValueOrd,
+    BytesRef, DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, Tag,
+    Writer,
+};
+use core::{fmt, str};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+macro_rules! impl_lenient_string {
+    ($type:ident, $strict:ident, $asn1_name:literal) => {
+        #[doc = concat!("Lenient ASN.1 `", $asn1_name, "` type.")]
+        ///
+        /// Accepts any contents octets, including characters which are not
+        #[doc = concat!("permitted in a `", $asn1_name, "`. See [`", stringify!($strict), "`] for")]
+        /// the strict form of this type.
+        ///
+        /// This is a zero-copy reference type which borrows from the input data.
+        #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+        pub struct $type<'a> {
+            /// Raw contents octets.
+            inner: BytesRef<'a>,
+        }
+
+        impl<'a> $type<'a> {
+            #[doc = concat!("Create a new lenient ASN.1 `", $asn1_name, "` from raw bytes.")]
+            pub fn new(bytes: &'a [u8]) -> Result<Self> {
+                BytesRef::new(bytes).map(|inner| Self { inner })
+            }
+
+            /// Borrow the raw contents octets.
+            pub fn as_bytes(&self) -> &'a [u8] {
+                self.inner.as_slice()
+            }
+
+            /// Get the length of the contents octets.
+            pub fn len(&self) -> Length {
+                self.inner.len()
+            }
+
+            /// Are the contents octets empty?
+            pub fn is_empty(&self) -> bool {
+                self.inner.is_empty()
+            }
+
+            #[doc = concat!("Do the contents octets form a valid `", $asn1_name, "`?")]
+            pub fn is_valid(&self) -> bool {
+                self.to_strict().is_ok()
+            }
+
+            #[doc = concat!("Attempt to convert into a strict [`", stringify!($strict), "`].")]
+            pub fn to_strict(&self) -> Result<$strict<'a>> {
+                $strict::new(self.as_bytes())
+            }
+
+            /// Borrow the contents octets as a string, if they are valid UTF-8.
+            pub fn to_str(&self) -> Option<&'a str> {
+                str::from_utf8(self.as_bytes()).ok()
+            }
+
+            /// Convert the contents octets to a string, replacing any invalid
+            /// UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+            #[cfg(feature = "alloc")]
+            pub fn to_string_lossy(&self) -> Cow<'a, str> {
+                String::from_utf8_lossy(self.as_bytes())
+            }
+        }
+
+        impl_any_conversions!($type<'a>, 'a);
+
+        impl<'a> DecodeValue<'a> for $type<'a> {
+            type Error = Error;
+
+            fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+                BytesRef::decode_value(reader, header).map(|inner| Self { inner })
+            }
+        }
+
+        impl EncodeValue for $type<'_> {
+            fn value_len(&self) -> Result<Length> {
+                self.inner.value_len()
+            }
+
+            fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+                self.inner.encode_value(writer)
+            }
+        }
+
+        impl FixedTag for $type<'_> {
+            const TAG: Tag = $strict::TAG;
+        }
+
+        impl OrdIsValueOrd for $type<'_> {}
+
+        impl<'a> From<$strict<'a>> for $type<'a> {
+            fn from(string: $strict<'a>) -> $type<'a> {
+                Self {
+                    inner: BytesRef::from(*string),
+                }
+            }
+        }
+
+        impl<'a> TryFrom<$type<'a>> for $strict<'a> {
+            type Error = Error;
+
+            fn try_from(string: $type<'a>) -> Result<$strict<'a>> {
+                string.to_strict()
+            }
+        }
+
+        impl<'a> From<$type<'a>> for AnyRef<'a> {
+            fn from(string: $type<'a>) -> AnyRef<'a> {
+                AnyRef::from_tag_and_value($strict::TAG, string.inner)
+            }
+        }
+
+        impl fmt::Debug for $type<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.to_str() {
+                    Some(s) => write!(f, concat!($asn1_name, "({:?})"), s),
+                    None => write!(f, concat!($asn1_name, "({:?})"), self.as_bytes()),
+                }
+            }
+        }
+    };
+}
+
+impl_lenient_string!(
+    LenientPrintableStringRef,
+    PrintableStringRef,
+    "PrintableString"
+);
+impl_lenient_string!(LenientIa5StringRef, Ia5StringRef, "IA5String");
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{LenientIa5StringRef, LenientPrintableStringRef};
+    use crate::{
+        asn1::{Ia5StringRef, PrintableStringRef},
+        Decode, Encode, SliceWriter,
+    };
+    use hex_literal::hex;
+
+    #[test]
+    fn decode_invalid_printable_string() {
+        // "a@b_c"
+        let example_bytes = hex!("13 05 61 40 62 5f 63");
+        assert!(PrintableStringRef::from_der(&example_bytes).is_err());
+
+        let string = LenientPrintableStringRef::from_der(&example_bytes).unwrap();
+        assert_eq!(string.as_bytes(), b"a@b_c");
+        assert_eq!(string.to_str(), Some("a@b_c"));
+        assert!(!string.is_valid());
+        assert!(string.to_strict().is_err());
+
+        let mut buf = [0u8; 7];
+        let mut writer = SliceWriter::new(&mut buf);
+        string.encode(&mut writer).unwrap();
+        assert_eq!(writer.finish().unwrap(), &example_bytes);
+    }
+
+    #[test]
+    fn decode_invalid_ia5_string() {
+        // "caf\xE9"
+        let example_bytes = hex!("16 04 63 61 66 e9");
+        assert!(Ia5StringRef::from_der(&example_bytes).is_err());
+
+        let string = LenientIa5StringRef::from_der(&example_bytes).unwrap();
+        assert_eq!(string.as_bytes(), b"caf\xE9");
+        assert_eq!(string.to_str(), None);
+
+        #[cfg(feature = "alloc")]
+        assert_eq!(string.to_string_lossy(), "caf\u{FFFD}");
+    }
+
+    #[test]
+    fn strict_conversions() {
+        let strict = Ia5StringRef::new("example").unwrap();
+        let lenient = LenientIa5StringRef::from(strict);
+        assert!(lenient.is_valid());
+        assert_eq!(Ia5StringRef::try_from(lenient).unwrap(), strict);
+    }
+}
@@ -1,6 +1,6 @@
 //! ASN.1 `OPTIONAL` as mapped to Rust's `Option` type
 
-use crate::{Choice, Decode, DerOrd, Encode, Error, Length, Reader, Tag, Writer};
+use crate::{Choice, Decode, DerOrd, Encode, Error, Length, Reader, Writer};
 use core::cmp::Ordering;
 
 impl<'a, T> Decode<'a> for Option<T>
@@ -10,10 +10,8 @@ where
     type Error = T::Error;
 
     fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Option<T>, Self::Error> {
-        if let Some(byte) = reader.peek_byte() {
-            if T::can_decode(Tag::try_from(byte)?) {
-                return T::decode(reader).map(Some);
-            }
+        if !reader.is_finished() && T::can_decode(reader.peek_tag()?) {
+            return T::decode(reader).map(Some);
         }
 
         Ok(None)
@@ -25,12 +23,11 @@ where
     T: DerOrd,
 {
     fn der_cmp(&self, other: &Self) -> Result<Ordering, Error> {
-        match self {
-            Some(a) => match other {
-                Some(b) => a.der_cmp(b),
-                None => Ok(Ordering::Greater),
-            },
-            None => Ok(Ordering::Less),
+        match (self, other) {
+            (Some(a), Some(b)) => a.der_cmp(b),
+            (Some(_), None) => Ok(Ordering::Greater),
+            (None, Some(_)) => Ok(Ordering::Less),
+            (None, None) => Ok(Ordering::Equal),
         }
     }
 }
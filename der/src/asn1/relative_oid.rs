@@ -0,0 +1,389 @@
+//! ASN.1 `RELATIVE-OID` support.
+
+use crate::{
+    asn1::AnyRef, ord::OrdIsValueOrd, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header,
+    Length, Reader, Result, Tag, Writer,
+};
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "alloc")]
+use super::Any;
+
+/// Maximum number of bytes in the base 128 encoding of a single arc.
+const ARC_MAX_BYTES: usize = 5;
+
+/// Bits which must be clear in the leading octet of a 5-byte arc for it to
+/// fit in a `u32`.
+const ARC_MAX_LEADING_OCTET: u8 = 0b01110000;
+
+/// ASN.1 `RELATIVE-OID` type.
+///
+/// Relative OIDs are sequences of arcs which are interpreted relative to some
+/// (contextually known) base `OBJECT IDENTIFIER`. Unlike `OBJECT IDENTIFIER`,
+/// the first two arcs are not combined into a single octet, and a relative OID
+/// may consist of as little as one arc.
+///
+/// Arcs are represented as `u32`, and the BER/DER encoding of a relative OID
+/// must be no longer than [`RelativeOid::MAX_SIZE`].
+#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct RelativeOid {
+    /// Length of the BER/DER-serialized bytes in `bytes`.
+    length: u8,
+
+    /// Array containing BER/DER-serialized bytes (sans ASN.1 tag/length).
+    bytes: [u8; Self::MAX_SIZE],
+}
+
+impl RelativeOid {
+    /// Maximum size of a BER/DER-encoded relative OID in bytes.
+    pub const MAX_SIZE: usize = 39;
+
+    /// Parse a [`RelativeOid`] from the dot-delimited string form, e.g. `8571.3.2`.
+    pub fn new(s: &str) -> Result<Self> {
+        let mut arcs = [0u32; Self::MAX_SIZE];
+        let mut count = 0;
+
+        for arc in s.split('.') {
+            if arc.is_empty() || !arc.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ErrorKind::OidMalformed.into());
+            }
+
+            let slot = arcs
+                .get_mut(count)
+                .ok_or_else(|| Self::TAG.length_error())?;
+
+            *slot = arc.parse().map_err(|_| ErrorKind::OidMalformed)?;
+            count += 1;
+        }
+
+        Self::from_arcs(arcs[..count].iter().copied())
+    }
+
+    /// Create a [`RelativeOid`] from a sequence of arcs (i.e. integers).
+    pub fn from_arcs(arcs: impl IntoIterator<Item = u32>) -> Result<Self> {
+        let mut bytes = [0u8; Self::MAX_SIZE];
+        let mut length = 0usize;
+
+        for arc in arcs {
+            let nbytes = base128_len(arc);
+            let end = length + nbytes;
+
+            let dst = bytes
+                .get_mut(length..end)
+                .ok_or_else(|| Self::TAG.length_error())?;
+
+            for (i, byte) in dst.iter_mut().enumerate() {
+                let shift = 7 * (nbytes - i - 1);
+                let continued = if i + 1 < nbytes { 0b10000000 } else { 0 };
+                *byte = ((arc >> shift) & 0b1111111) as u8 | continued;
+            }
+
+            length = end;
+        }
+
+        if length == 0 {
+            return Err(ErrorKind::OidMalformed.into());
+        }
+
+        Ok(Self {
+            length: length.try_into()?,
+            bytes,
+        })
+    }
+
+    /// Parse a [`RelativeOid`] from its BER/DER encoding (sans tag/length).
+    pub fn from_bytes(ber_bytes: &[u8]) -> Result<Self> {
+        let mut bytes = [0u8; Self::MAX_SIZE];
+
+        bytes
+            .get_mut(..ber_bytes.len())
+            .ok_or_else(|| Self::TAG.length_error())?
+            .copy_from_slice(ber_bytes);
+
+        let oid = Self {
+            length: ber_bytes.len().try_into()?,
+            bytes,
+        };
+
+        // Ensure all of the arcs are well-formed
+        let mut arcs = oid.arcs();
+        let mut count = 0usize;
+
+        while arcs.try_next()?.is_some() {
+            count += 1;
+        }
+
+        if count == 0 {
+            return Err(ErrorKind::OidMalformed.into());
+        }
+
+        Ok(oid)
+    }
+
+    /// Get the BER/DER serialization of this relative OID as bytes.
+    ///
+    /// Note that this encoding omits the ASN.1 tag/length, and only contains
+    /// the value portion of the encoded relative OID.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..usize::from(self.length)]
+    }
+
+    /// Iterate over the arcs (a.k.a. nodes) of this relative OID.
+    pub fn arcs(&self) -> RelativeOidArcs<'_> {
+        RelativeOidArcs {
+            bytes: self.as_bytes(),
+            cursor: 0,
+        }
+    }
+}
+
+impl_any_conversions!(RelativeOid);
+
+impl AsRef<[u8]> for RelativeOid {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> DecodeValue<'a> for RelativeOid {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let mut buf = [0u8; Self::MAX_SIZE];
+        let slice = buf
+            .get_mut(..header.length.try_into()?)
+            .ok_or_else(|| Self::TAG.length_error())?;
+
+        let bytes = reader.read_into(slice)?;
+        Self::from_bytes(bytes)
+    }
+}
+
+impl EncodeValue for RelativeOid {
+    fn value_len(&self) -> Result<Length> {
+        Length::try_from(self.as_bytes().len())
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.as_bytes())
+    }
+}
+
+impl FixedTag for RelativeOid {
+    const TAG: Tag = Tag::RelativeOid;
+}
+
+impl OrdIsValueOrd for RelativeOid {}
+
+impl<'a> From<&'a RelativeOid> for AnyRef<'a> {
+    fn from(oid: &'a RelativeOid) -> AnyRef<'a> {
+        // Note: ensuring an infallible conversion is possible relies on the
+        // invariant that `RelativeOid::MAX_SIZE <= Length::max()`.
+        let value = oid
+            .as_bytes()
+            .try_into()
+            .expect("relative OID length invariant violated");
+
+        AnyRef::from_tag_and_value(Tag::RelativeOid, value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<RelativeOid> for Any {
+    fn from(oid: RelativeOid) -> Any {
+        AnyRef::from(&oid).into()
+    }
+}
+
+impl TryFrom<&[u8]> for RelativeOid {
+    type Error = Error;
+
+    fn try_from(ber_bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(ber_bytes)
+    }
+}
+
+impl FromStr for RelativeOid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for RelativeOid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, arc) in self.arcs().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+
+            write!(f, "{}", arc)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for RelativeOid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RelativeOid({})", self)
+    }
+}
+
+/// [`Iterator`] over the arcs of a [`RelativeOid`].
+#[derive(Clone, Debug)]
+pub struct RelativeOidArcs<'a> {
+    /// Relative OID bytes we're iterating over.
+    bytes: &'a [u8],
+
+    /// Current position within the serialized BER bytes.
+    cursor: usize,
+}
+
+impl RelativeOidArcs<'_> {
+    /// Try to parse the next arc, validating its base 128 encoding.
+    fn try_next(&mut self) -> Result<Option<u32>> {
+        let remaining = match self.bytes.get(self.cursor..) {
+            Some(remaining) if !remaining.is_empty() => remaining,
+            _ => return Ok(None),
+        };
+
+        // Leading `0x80` octets are not minimally encoded
+        if remaining[0] == 0b10000000 {
+            return Err(Tag::RelativeOid.non_canonical_error());
+        }
+
+        let mut result = 0u32;
+
+        for (i, &byte) in remaining.iter().enumerate() {
+            // Arcs must fit in a `u32`
+            if i >= ARC_MAX_BYTES
+                || (i == ARC_MAX_BYTES - 1 && remaining[0] & ARC_MAX_LEADING_OCTET != 0)
+            {
+                return Err(ErrorKind::OidMalformed.into());
+            }
+
+            result = result << 7 | u32::from(byte & 0b1111111);
+
+            if byte & 0b10000000 == 0 {
+                self.cursor += i + 1;
+                return Ok(Some(result));
+            }
+        }
+
+        // Last octet had its continuation bit set
+        Err(ErrorKind::OidMalformed.into())
+    }
+}
+
+impl Iterator for RelativeOidArcs<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        // `RelativeOid` constructors ensure the encoding is well-formed
+        self.try_next().ok().flatten()
+    }
+}
+
+/// Compute the number of bytes needed to encode an arc in base 128.
+fn base128_len(arc: u32) -> usize {
+    match arc {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        0x4000..=0x1fffff => 3,
+        0x200000..=0xfffffff => 4,
+        _ => 5,
+    }
+}
+
+// Implement by hand because the derive would create invalid values.
+// Use the constructor to create a valid value.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for RelativeOid {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::from_bytes(<&'a [u8]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&'a [u8]>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::RelativeOid;
+    use crate::{Decode, Encode, ErrorKind, Length, Tag};
+
+    /// Relative OID `8571.3.2` encoded as ASN.1 DER.
+    const EXAMPLE_BYTES: &[u8] = &[0x0D, 0x04, 0xC2, 0x7B, 0x03, 0x02];
+
+    #[test]
+    fn decode() {
+        let oid = RelativeOid::from_der(EXAMPLE_BYTES).unwrap();
+        assert!(oid.arcs().eq([8571, 3, 2]));
+    }
+
+    #[test]
+    fn encode() {
+        let oid = RelativeOid::new("8571.3.2").unwrap();
+        let mut buffer = [0u8; 6];
+        assert_eq!(EXAMPLE_BYTES, oid.encode_to_slice(&mut buffer).unwrap());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display() {
+        use alloc::string::ToString;
+
+        let oid = RelativeOid::from_der(EXAMPLE_BYTES).unwrap();
+        assert_eq!(oid.to_string(), "8571.3.2");
+    }
+
+    #[test]
+    fn single_arc() {
+        let oid = RelativeOid::new("0").unwrap();
+        assert_eq!(oid.as_bytes(), &[0x00]);
+
+        let oid = RelativeOid::new("4294967295").unwrap();
+        assert_eq!(oid.as_bytes(), &[0x8F, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert!(oid.arcs().eq([u32::MAX]));
+    }
+
+    #[test]
+    fn reject_malformed_strings() {
+        for s in ["", ".", "1.", ".1", "1..2", "a.1", "4294967296"] {
+            assert!(RelativeOid::new(s).is_err(), "accepted {:?}", s);
+        }
+    }
+
+    #[test]
+    fn reject_malformed_bytes() {
+        // Truncated arc
+        assert_eq!(
+            RelativeOid::from_bytes(&[0x86]).unwrap_err().kind(),
+            ErrorKind::OidMalformed
+        );
+
+        // Non-minimal arc encoding
+        assert_eq!(
+            RelativeOid::from_bytes(&[0x80, 0x01]).unwrap_err().kind(),
+            ErrorKind::Noncanonical {
+                tag: Tag::RelativeOid
+            }
+        );
+
+        // Arc larger than 32-bits
+        assert!(RelativeOid::from_bytes(&[0x90, 0x80, 0x80, 0x80, 0x00]).is_err());
+
+        // Empty
+        assert!(RelativeOid::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn length() {
+        // Ensure an infallible `From` conversion to `Any` will never panic
+        assert!(RelativeOid::MAX_SIZE <= Length::MAX.try_into().unwrap());
+    }
+}
@@ -1,8 +1,8 @@
 //! ASN.1 `SEQUENCE OF` support.
 
 use crate::{
-    arrayvec, ord::iter_cmp, ArrayVec, Decode, DecodeValue, DerOrd, Encode, EncodeValue, Error,
-    FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer,
+    arrayvec, error::element_error, ord::iter_cmp, ArrayVec, Decode, DecodeValue, DerOrd, Encode,
+    EncodeValue, Error, FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer,
 };
 use core::cmp::Ordering;
 
@@ -28,11 +28,37 @@ impl<T, const N: usize> SequenceOf<T, N> {
         }
     }
 
+    /// Create a new [`SequenceOf`] from the given iterator.
+    ///
+    /// Returns an error if the iterator yields more than `N` elements.
+    ///
+    /// Note: this is an inherent method instead of an impl of the
+    /// [`FromIterator`] trait in order to be fallible.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut sequence_of = Self::new();
+
+        for element in iter {
+            sequence_of.add(element)?;
+        }
+
+        Ok(sequence_of)
+    }
+
     /// Add an element to this [`SequenceOf`].
     pub fn add(&mut self, element: T) -> Result<(), Error> {
         self.inner.push(element)
     }
 
+    /// Attempt to add an element to this [`SequenceOf`], returning it back
+    /// if the [`SequenceOf`] is already at capacity.
+    pub fn try_push(&mut self, element: T) -> Result<(), T> {
+        self.inner.try_push(element)
+    }
+
     /// Get an element of this [`SequenceOf`].
     pub fn get(&self, index: usize) -> Option<&T> {
         self.inner.get(index)
@@ -50,10 +76,20 @@ impl<T, const N: usize> SequenceOf<T, N> {
         self.inner.is_empty()
     }
 
+    /// Is this [`SequenceOf`] at capacity?
+    pub fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+
     /// Number of elements in this [`SequenceOf`].
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// Maximum number of elements this [`SequenceOf`] can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
 }
 
 impl<T, const N: usize> Default for SequenceOf<T, N> {
@@ -73,7 +109,8 @@ where
             let mut sequence_of = Self::new();
 
             while !reader.is_finished() {
-                sequence_of.add(T::decode(reader)?)?;
+                let index = sequence_of.len();
+                sequence_of.add(T::decode(reader).map_err(|e| element_error(e, index))?)?;
             }
 
             Ok(sequence_of)
@@ -99,6 +136,29 @@ where
     }
 }
 
+// Implement by hand in order to respect the capacity of the inner array.
+#[cfg(feature = "arbitrary")]
+impl<'a, T, const N: usize> arbitrary::Arbitrary<'a> for SequenceOf<T, N>
+where
+    T: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut sequence_of = Self::new();
+
+        for _ in 0..u.int_in_range(0..=N)? {
+            sequence_of
+                .add(T::arbitrary(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        Ok(sequence_of)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
 impl<T, const N: usize> FixedTag for SequenceOf<T, N> {
     const TAG: Tag = Tag::Sequence;
 }
@@ -125,10 +185,23 @@ impl<'a, T> Iterator for SequenceOfIter<'a, T> {
     fn next(&mut self) -> Option<&'a T> {
         self.inner.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
 impl<'a, T> ExactSizeIterator for SequenceOfIter<'a, T> {}
 
+impl<'a, T, const N: usize> IntoIterator for &'a SequenceOf<T, N> {
+    type Item = &'a T;
+    type IntoIter = SequenceOfIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a, T, const N: usize> DecodeValue<'a> for [T; N]
 where
     T: Decode<'a>,
@@ -193,7 +266,8 @@ where
             let mut sequence_of = Self::new();
 
             while !reader.is_finished() {
-                sequence_of.push(T::decode(reader)?);
+                let index = sequence_of.len();
+                sequence_of.push(T::decode(reader).map_err(|e| element_error(e, index))?);
             }
 
             Ok(sequence_of)
@@ -234,3 +308,34 @@ where
         iter_cmp(self.iter(), other.iter())
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::SequenceOf;
+    use crate::{Decode, Encode};
+    use hex_literal::hex;
+
+    #[test]
+    fn sequenceof_from_iter() {
+        let sequence_of = SequenceOf::<u8, 3>::from_iter([1, 2, 3]).unwrap();
+        assert!(sequence_of.is_full());
+        assert_eq!(sequence_of.iter().len(), 3);
+        assert!((&sequence_of).into_iter().copied().eq([1, 2, 3]));
+
+        assert!(SequenceOf::<u8, 2>::from_iter([1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn sequenceof_try_push() {
+        let mut sequence_of = SequenceOf::<u8, 1>::new();
+        assert_eq!(sequence_of.capacity(), 1);
+        assert_eq!(sequence_of.try_push(1), Ok(()));
+        assert_eq!(sequence_of.try_push(2), Err(2));
+
+        let mut buf = [0u8; 5];
+        let der = sequence_of.encode_to_slice(&mut buf).unwrap();
+        assert_eq!(der, hex!("3003020101"));
+        assert_eq!(SequenceOf::<u8, 1>::from_der(der).unwrap(), sequence_of);
+    }
+}
@@ -7,30 +7,60 @@
 //! enough that most DER decoders tolerate it. Unfortunately because
 //! of that, we must also follow suit.
 //!
-//! However, all types in this module sort elements of a set at decode-time,
-//! ensuring they'll be in the proper order if reserialized.
+//! By default, all types in this module sort elements of a set at decode-time,
+//! ensuring they'll be in the proper order if reserialized. This behavior can
+//! be changed on a per-[`Reader`] basis using [`SetOfPolicy`].
 
 use crate::{
-    arrayvec, ord::iter_cmp, ArrayVec, Decode, DecodeValue, DerOrd, Encode, EncodeValue, Error,
-    ErrorKind, FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer,
+    arrayvec, error::element_error, ord::iter_cmp, ArrayVec, Decode, DecodeValue, DerOrd, Encode,
+    EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Tag, ValueOrd, Writer,
 };
 use core::cmp::Ordering;
 
 #[cfg(feature = "alloc")]
 use {alloc::vec::Vec, core::slice};
 
+/// Policy applied when decoding a `SET OF` whose elements are not in
+/// canonical DER order.
+///
+/// The policy is a property of the [`Reader`] (see [`Reader::set_of_policy`])
+/// and only affects decoding: encoders always require `SET OF` elements to be
+/// in canonical order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub enum SetOfPolicy {
+    /// Reject elements which are out of order or duplicated with
+    /// [`ErrorKind::SetOrdering`] or [`ErrorKind::SetDuplicate`].
+    Strict,
+
+    /// Re-sort out-of-order elements into canonical order, rejecting
+    /// duplicates with [`ErrorKind::SetDuplicate`].
+    #[default]
+    Sort,
+
+    /// Preserve the order in which elements were received, including any
+    /// duplicates.
+    ///
+    /// Sets decoded this way can't be re-encoded unless they happen to be in
+    /// canonical order.
+    Preserve,
+}
+
 /// ASN.1 `SET OF` backed by an array.
 ///
 /// This type implements an append-only `SET OF` type which is stack-based
 /// and does not depend on `alloc` support.
 // TODO(tarcieri): use `ArrayVec` when/if it's merged into `core`
 // See: https://github.com/rust-lang/rfcs/pull/2990
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug)]
 pub struct SetOf<T, const N: usize>
 where
     T: DerOrd,
 {
     inner: ArrayVec<T, N>,
+
+    /// Are the elements in canonical order? Only sets decoded with
+    /// [`SetOfPolicy::Preserve`] may not be.
+    canonical: bool,
 }
 
 impl<T, const N: usize> SetOf<T, N>
@@ -41,7 +71,32 @@ where
     pub fn new() -> Self {
         Self {
             inner: ArrayVec::default(),
+            canonical: true,
+        }
+    }
+
+    /// Create a new [`SetOf`] from the given iterator of elements in
+    /// arbitrary order.
+    ///
+    /// The elements are sorted once all of them have been collected. Returns
+    /// an error if the iterator yields more than `N` elements or duplicate
+    /// elements.
+    ///
+    /// Note: this is an inherent method instead of an impl of the
+    /// [`FromIterator`] trait in order to be fallible.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut result = Self::new();
+
+        for elem in iter {
+            result.inner.push(elem)?;
         }
+
+        der_sort(result.inner.as_mut())?;
+        Ok(result)
     }
 
     /// Add an item to this [`SetOf`].
@@ -53,10 +108,19 @@ where
         self.insert_ordered(new_elem)
     }
 
-    /// Insert an item into this [`SetOf`].
+    /// Insert an item into this [`SetOf`]. Must be unique.
+    ///
+    /// The insertion position is located using a binary search. If the set
+    /// isn't in canonical order (see [`SetOfPolicy::Preserve`]) the item is
+    /// appended instead, after checking every element for duplicates.
     pub fn insert(&mut self, item: T) -> Result<(), Error> {
-        self.inner.push(item)?;
-        der_sort(self.inner.as_mut())
+        if !self.canonical {
+            check_not_contained(self.iter(), &item)?;
+            return self.inner.push(item);
+        }
+
+        let index = insertion_point(self.inner.len(), &item, |i| self.inner.get(i))?;
+        self.inner.insert(index, item)
     }
 
     /// Insert an item into this [`SetOf`].
@@ -89,10 +153,20 @@ where
         self.inner.is_empty()
     }
 
+    /// Is this [`SetOf`] at capacity?
+    pub fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+
     /// Number of elements in this [`SetOf`].
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// Maximum number of elements this [`SetOf`] can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
 }
 
 impl<T, const N: usize> Default for SetOf<T, N>
@@ -104,6 +178,37 @@ where
     }
 }
 
+// Implement by hand so sets compare equal regardless of the policy they were
+// decoded with.
+impl<T, const N: usize> PartialEq for SetOf<T, N>
+where
+    T: DerOrd + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, const N: usize> Eq for SetOf<T, N> where T: DerOrd + Eq {}
+
+impl<T, const N: usize> PartialOrd for SetOf<T, N>
+where
+    T: DerOrd + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<T, const N: usize> Ord for SetOf<T, N>
+where
+    T: DerOrd + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
 impl<'a, T, const N: usize> DecodeValue<'a> for SetOf<T, N>
 where
     T: Decode<'a> + DerOrd,
@@ -115,19 +220,21 @@ where
             let mut result = Self::new();
 
             while !reader.is_finished() {
-                result.inner.push(T::decode(reader)?)?;
+                let index = result.inner.len();
+                result
+                    .inner
+                    .push(T::decode(reader).map_err(|e| element_error(e, index))?)?;
             }
 
-            der_sort(result.inner.as_mut())?;
-            validate(result.inner.as_ref())?;
+            result.canonical = apply_policy(result.inner.as_mut(), reader.set_of_policy())?;
             Ok(result)
         })
     }
 }
 
-impl<'a, T, const N: usize> EncodeValue for SetOf<T, N>
+impl<T, const N: usize> EncodeValue for SetOf<T, N>
 where
-    T: 'a + Decode<'a> + Encode + DerOrd,
+    T: Encode + DerOrd,
 {
     fn value_len(&self) -> Result<Length, Error> {
         self.iter()
@@ -135,6 +242,10 @@ where
     }
 
     fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+        if !self.canonical {
+            validate(self.inner.as_ref())?;
+        }
+
         for elem in self.iter() {
             elem.encode(writer)?;
         }
@@ -143,9 +254,33 @@ where
     }
 }
 
-impl<'a, T, const N: usize> FixedTag for SetOf<T, N>
+// Implement by hand because the derive would create invalid values.
+// Use insertion to create a valid value.
+#[cfg(feature = "arbitrary")]
+impl<'a, T, const N: usize> arbitrary::Arbitrary<'a> for SetOf<T, N>
 where
-    T: Decode<'a> + DerOrd,
+    T: DerOrd + arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut set_of = Self::new();
+
+        for _ in 0..u.int_in_range(0..=N)? {
+            set_of
+                .insert(T::arbitrary(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        Ok(set_of)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+impl<T, const N: usize> FixedTag for SetOf<T, N>
+where
+    T: DerOrd,
 {
     const TAG: Tag = Tag::Set;
 }
@@ -191,29 +326,82 @@ impl<'a, T> Iterator for SetOfIter<'a, T> {
     fn next(&mut self) -> Option<&'a T> {
         self.inner.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
 impl<'a, T> ExactSizeIterator for SetOfIter<'a, T> {}
 
+impl<'a, T, const N: usize> IntoIterator for &'a SetOf<T, N>
+where
+    T: DerOrd,
+{
+    type Item = &'a T;
+    type IntoIter = SetOfIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 /// ASN.1 `SET OF` backed by a [`Vec`].
 ///
 /// This type implements an append-only `SET OF` type which is heap-backed
 /// and depends on `alloc` support.
 #[cfg(feature = "alloc")]
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug)]
 pub struct SetOfVec<T>
 where
     T: DerOrd,
 {
     inner: Vec<T>,
+
+    /// Are the elements in canonical order? Only sets decoded with
+    /// [`SetOfPolicy::Preserve`] may not be.
+    canonical: bool,
 }
 
 #[cfg(feature = "alloc")]
 impl<T: DerOrd> Default for SetOfVec<T> {
     fn default() -> Self {
-        Self {
-            inner: Default::default(),
-        }
+        Self::new()
+    }
+}
+
+// Implement by hand so sets compare equal regardless of the policy they were
+// decoded with.
+#[cfg(feature = "alloc")]
+impl<T> PartialEq for SetOfVec<T>
+where
+    T: DerOrd + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Eq for SetOfVec<T> where T: DerOrd + Eq {}
+
+#[cfg(feature = "alloc")]
+impl<T> PartialOrd for SetOfVec<T>
+where
+    T: DerOrd + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Ord for SetOfVec<T>
+where
+    T: DerOrd + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
     }
 }
 
@@ -226,6 +414,7 @@ where
     pub fn new() -> Self {
         Self {
             inner: Vec::default(),
+            canonical: true,
         }
     }
 
@@ -241,6 +430,35 @@ where
         Vec::from_iter(iter).try_into()
     }
 
+    /// Create a new [`SetOfVec`] from the given iterator of elements in
+    /// arbitrary order.
+    ///
+    /// Each element is encoded exactly once and the elements are then sorted
+    /// by their cached DER encodings, which is considerably faster than
+    /// [`SetOfVec::from_iter`] for large sets. Returns an error if the
+    /// iterator yields duplicate elements.
+    pub fn from_iter_unsorted<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Encode,
+    {
+        let mut elements = iter
+            .into_iter()
+            .map(|elem| Ok((elem.to_der()?, elem)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        elements.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        if elements.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(ErrorKind::SetDuplicate.into());
+        }
+
+        Ok(Self {
+            inner: elements.into_iter().map(|(_, elem)| elem).collect(),
+            canonical: true,
+        })
+    }
+
     /// Add an element to this [`SetOfVec`].
     ///
     /// Items MUST be added in lexicographical order according to the
@@ -252,20 +470,51 @@ where
 
     /// Extend a [`SetOfVec`] using an iterator.
     ///
+    /// The set is left unchanged if the iterator yields duplicate elements.
+    ///
     /// Note: this is an inherent method instead of an impl of the
     /// [`Extend`] trait in order to be fallible.
     pub fn extend<I>(&mut self, iter: I) -> Result<(), Error>
     where
         I: IntoIterator<Item = T>,
     {
-        self.inner.extend(iter);
-        der_sort(&mut self.inner)
+        let mut elements = Vec::from_iter(iter);
+        der_sort(&mut elements)?;
+
+        // Rule out duplicates before modifying the set
+        if self.canonical {
+            for elem in &elements {
+                insertion_point(self.inner.len(), elem, |i| self.inner.get(i))?;
+            }
+        } else {
+            let mut rest = self.inner.iter();
+
+            while let Some(elem) = rest.next() {
+                check_not_contained(rest.clone().chain(&elements), elem)?;
+            }
+        }
+
+        self.inner.extend(elements);
+        der_sort(&mut self.inner)?;
+        self.canonical = true;
+        Ok(())
     }
 
     /// Insert an item into this [`SetOfVec`]. Must be unique.
+    ///
+    /// The insertion position is located using a binary search. If the set
+    /// isn't in canonical order (see [`SetOfPolicy::Preserve`]) the item is
+    /// appended instead, after checking every element for duplicates.
     pub fn insert(&mut self, item: T) -> Result<(), Error> {
-        self.inner.push(item);
-        der_sort(&mut self.inner)
+        if !self.canonical {
+            check_not_contained(self.iter(), &item)?;
+            self.inner.push(item);
+            return Ok(());
+        }
+
+        let index = insertion_point(self.inner.len(), &item, |i| self.inner.get(i))?;
+        self.inner.insert(index, item);
+        Ok(())
     }
 
     /// Insert an item into this [`SetOfVec`]. Must be unique.
@@ -335,12 +584,12 @@ where
             let mut inner = Vec::new();
 
             while !reader.is_finished() {
-                inner.push(T::decode(reader)?);
+                let index = inner.len();
+                inner.push(T::decode(reader).map_err(|e| element_error(e, index))?);
             }
 
-            der_sort(inner.as_mut())?;
-            validate(inner.as_ref())?;
-            Ok(Self { inner })
+            let canonical = apply_policy(inner.as_mut(), reader.set_of_policy())?;
+            Ok(Self { inner, canonical })
         })
     }
 }
@@ -356,6 +605,10 @@ where
     }
 
     fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+        if !self.canonical {
+            validate(self.inner.as_ref())?;
+        }
+
         for elem in self.iter() {
             elem.encode(writer)?;
         }
@@ -392,7 +645,10 @@ where
     fn try_from(mut vec: Vec<T>) -> Result<SetOfVec<T>, Error> {
         // TODO(tarcieri): use `[T]::sort_by` here?
         der_sort(vec.as_mut_slice())?;
-        Ok(SetOfVec { inner: vec })
+        Ok(SetOfVec {
+            inner: vec,
+            canonical: true,
+        })
     }
 }
 
@@ -444,6 +700,48 @@ fn check_der_ordering<T: DerOrd>(a: &T, b: &T) -> Result<(), Error> {
     }
 }
 
+/// Ensure the elements yielded by `iter` don't include `item`.
+fn check_not_contained<'a, T, I>(mut iter: I, item: &T) -> Result<(), Error>
+where
+    T: DerOrd + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    iter.try_for_each(|elem| match elem.der_cmp(item)? {
+        Ordering::Equal => Err(ErrorKind::SetDuplicate.into()),
+        _ => Ok(()),
+    })
+}
+
+/// Locate the index at which `item` should be inserted into a set of `len`
+/// elements in canonical order using a binary search, where `get` returns the
+/// element at a given index.
+///
+/// Returns an error if the set already contains `item`.
+#[allow(clippy::arithmetic_side_effects)]
+fn insertion_point<'a, T, F>(len: usize, item: &T, get: F) -> Result<usize, Error>
+where
+    T: DerOrd + 'a,
+    F: Fn(usize) -> Option<&'a T>,
+{
+    let mut low = 0;
+    let mut high = len;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        match get(mid)
+            .ok_or_else(|| Tag::Set.value_error())?
+            .der_cmp(item)?
+        {
+            Ordering::Less => low = mid + 1,
+            Ordering::Equal => return Err(ErrorKind::SetDuplicate.into()),
+            Ordering::Greater => high = mid,
+        }
+    }
+
+    Ok(low)
+}
+
 /// Sort a mut slice according to its [`DerOrd`], returning any errors which
 /// might occur during the comparison.
 ///
@@ -481,11 +779,7 @@ fn validate<T: DerOrd>(slice: &[T]) -> Result<(), Error> {
             let j = i.checked_add(1).ok_or(ErrorKind::Overflow)?;
 
             match slice.get(i..=j) {
-                Some([a, b]) => {
-                    if a.der_cmp(b)? != Ordering::Less {
-                        return Err(ErrorKind::SetOrdering.into());
-                    }
-                }
+                Some([a, b]) => check_der_ordering(a, b)?,
                 _ => return Err(Tag::Set.value_error()),
             }
         }
@@ -494,13 +788,40 @@ fn validate<T: DerOrd>(slice: &[T]) -> Result<(), Error> {
     Ok(())
 }
 
+/// Apply the given [`SetOfPolicy`] to the elements of a decoded `SET OF`,
+/// returning whether they are in canonical order.
+fn apply_policy<T: DerOrd>(slice: &mut [T], policy: SetOfPolicy) -> Result<bool, Error> {
+    match policy {
+        SetOfPolicy::Strict => validate(slice)?,
+        SetOfPolicy::Sort => {
+            der_sort(slice)?;
+            validate(slice)?;
+        }
+        SetOfPolicy::Preserve => return Ok(validate(slice).is_ok()),
+    }
+
+    Ok(true)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
-    use super::SetOf;
     #[cfg(feature = "alloc")]
     use super::SetOfVec;
-    use crate::ErrorKind;
+    use super::{SetOf, SetOfPolicy};
+    use crate::{Decode, Encode, ErrorKind, Reader, SliceReader};
+
+    /// `SET OF INTEGER` containing `{ 2, 1 }` (out of order).
+    const UNSORTED_SET: &[u8] = &[0x31, 0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01];
+
+    /// `SET OF INTEGER` containing `{ 1, 1 }` (duplicate).
+    const DUPLICATE_SET: &[u8] = &[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+
+    fn decode_with_policy(bytes: &[u8], policy: SetOfPolicy) -> crate::Result<SetOf<u8, 2>> {
+        let mut reader = SliceReader::new(bytes)?.with_set_of_policy(policy);
+        let set = SetOf::decode(&mut reader)?;
+        reader.finish(set)
+    }
 
     #[test]
     fn setof_tryfrom_array() {
@@ -516,6 +837,103 @@ mod tests {
         assert_eq!(err.kind(), ErrorKind::SetDuplicate);
     }
 
+    #[test]
+    fn setof_from_iter() {
+        let set = SetOf::<u16, 5>::from_iter([3, 2, 1, 65535, 0]).unwrap();
+        assert!(set.is_full());
+        assert_eq!(set.iter().len(), 5);
+        assert!((&set).into_iter().copied().eq([0, 1, 2, 3, 65535]));
+
+        let err = SetOf::<u16, 2>::from_iter([1, 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+
+        let err = SetOf::<u16, 2>::from_iter([1, 2, 3]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Overlength);
+    }
+
+    #[test]
+    fn setof_insert() {
+        let mut set = SetOf::<u16, 4>::new();
+        assert_eq!(set.capacity(), 4);
+
+        for elem in [3, 1, 2, 0] {
+            set.insert(elem).unwrap();
+        }
+
+        assert!(set.iter().copied().eq([0, 1, 2, 3]));
+        assert_eq!(set.insert(4).unwrap_err().kind(), ErrorKind::Overlength);
+
+        let mut set = SetOf::<u16, 4>::new();
+        set.insert(1).unwrap();
+        assert_eq!(set.insert(1).unwrap_err().kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[test]
+    fn setof_policy_strict() {
+        let err = decode_with_policy(UNSORTED_SET, SetOfPolicy::Strict).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetOrdering);
+
+        let err = decode_with_policy(DUPLICATE_SET, SetOfPolicy::Strict).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[test]
+    fn setof_policy_sort() {
+        let set = decode_with_policy(UNSORTED_SET, SetOfPolicy::Sort).unwrap();
+        assert!(set.iter().copied().eq([1, 2]));
+
+        let err = decode_with_policy(DUPLICATE_SET, SetOfPolicy::Sort).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[test]
+    fn setof_policy_preserve() {
+        let set = decode_with_policy(UNSORTED_SET, SetOfPolicy::Preserve).unwrap();
+        assert!(set.iter().copied().eq([2, 1]));
+
+        let set = decode_with_policy(DUPLICATE_SET, SetOfPolicy::Preserve).unwrap();
+        assert!(set.iter().copied().eq([1, 1]));
+
+        // Non-canonical sets can't be re-encoded
+        let mut buf = [0u8; 8];
+        let err = set.encode_to_slice(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[test]
+    fn setof_policy_preserve_insert() {
+        // The binary search can't be used to find duplicates in `{ 2, 1 }`
+        let mut set = decode_with_policy(UNSORTED_SET, SetOfPolicy::Preserve).unwrap();
+        assert_eq!(set.insert(2).unwrap_err().kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_policy_preserve_insert() {
+        let mut reader = SliceReader::new(UNSORTED_SET)
+            .unwrap()
+            .with_set_of_policy(SetOfPolicy::Preserve);
+        let mut set = SetOfVec::<u8>::decode(&mut reader).unwrap();
+
+        assert_eq!(set.insert(2).unwrap_err().kind(), ErrorKind::SetDuplicate);
+        set.insert(3).unwrap();
+        assert_eq!(set.as_slice(), &[2, 1, 3]);
+
+        // Re-sorting restores the canonical order
+        set.extend([0]).unwrap();
+        assert_eq!(set.as_slice(), &[0, 1, 2, 3]);
+        assert_eq!(
+            set.to_der().unwrap(),
+            [0x31, 0x0C, 2, 1, 0, 2, 1, 1, 2, 1, 2, 2, 1, 3]
+        );
+    }
+
+    #[test]
+    fn setof_policy_default() {
+        let set = SetOf::<u8, 2>::from_der(UNSORTED_SET).unwrap();
+        assert!(set.iter().copied().eq([1, 2]));
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn setofvec_tryfrom_array() {
@@ -539,4 +957,46 @@ mod tests {
         let err = SetOfVec::try_from(vec).err().unwrap();
         assert_eq!(err.kind(), ErrorKind::SetDuplicate);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_from_iter_unsorted() {
+        let set = SetOfVec::from_iter_unsorted([3u16, 2, 1, 65535, 0]).unwrap();
+        assert_eq!(set.as_ref(), &[0, 1, 2, 3, 65535]);
+
+        let err = SetOfVec::from_iter_unsorted([2u16, 1, 2]).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_extend_duplicate() {
+        let mut set = SetOfVec::try_from([1u16, 3]).unwrap();
+
+        let err = set.extend([4, 2, 3]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+        assert_eq!(set.as_slice(), &[1, 3]);
+
+        let err = set.extend([2, 2]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+        assert_eq!(set.as_slice(), &[1, 3]);
+
+        set.extend([2, 0]).unwrap();
+        assert_eq!(set.as_slice(), &[0, 1, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_insert() {
+        let mut set = SetOfVec::new();
+
+        for n in [3u16, 2, 1, 65535, 0] {
+            set.insert(n).unwrap();
+        }
+
+        assert_eq!(set.as_ref(), &[0, 1, 2, 3, 65535]);
+
+        let err = set.insert(2).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+    }
 }
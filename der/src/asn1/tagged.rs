@@ -0,0 +1,323 @@
+//! Context-specific fields with a tag number fixed at compile time.
+//!
+//! Unlike [`ContextSpecific`][`crate::asn1::ContextSpecific`], the tag number
+//! and tagging mode of these types are part of the type itself, which makes it
+//! possible to express context-specific tagging in plain type signatures, e.g.
+//! as the variants of a hand-written `CHOICE`.
+
+use crate::{
+    Choice, Class, Decode, DecodeValue, DerOrd, Encode, EncodeValue, Error, FixedTag, Header,
+    Length, Reader, Tag, TagNumber, Tagged, ValueOrd, Writer,
+};
+use core::cmp::Ordering;
+
+/// `EXPLICIT`ly tagged `CONTEXT-SPECIFIC` field with the tag number `TAG`.
+///
+/// The inner value is encoded as a complete TLV production inside of a
+/// constructed `[TAG]` field.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Explicit<const TAG: u32, T> {
+    /// Value of the field.
+    pub value: T,
+}
+
+impl<const TAG: u32, T> Explicit<TAG, T> {
+    /// Tag number of this field.
+    pub const TAG_NUMBER: TagNumber = TagNumber::new(TAG);
+
+    /// Create a new [`Explicit`] field wrapping the given value.
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Borrow the inner value.
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<'a, const TAG: u32, T> DecodeValue<'a> for Explicit<TAG, T>
+where
+    T: Decode<'a>,
+{
+    type Error = T::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self, T::Error> {
+        reader
+            .read_nested(header.length, |reader| T::decode(reader))
+            .map(Self::new)
+    }
+}
+
+impl<const TAG: u32, T> EncodeValue for Explicit<TAG, T>
+where
+    T: Encode,
+{
+    fn value_len(&self) -> Result<Length, Error> {
+        self.value.encoded_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+        self.value.encode(writer)
+    }
+}
+
+impl<const TAG: u32, T> FixedTag for Explicit<TAG, T> {
+    const TAG: Tag = Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::new(TAG),
+    };
+}
+
+impl<const TAG: u32, T> ValueOrd for Explicit<TAG, T>
+where
+    T: DerOrd,
+{
+    fn value_cmp(&self, other: &Self) -> Result<Ordering, Error> {
+        self.value.der_cmp(&other.value)
+    }
+}
+
+impl<const TAG: u32, T> From<T> for Explicit<TAG, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// `IMPLICIT`ly tagged `CONTEXT-SPECIFIC` field with the tag number `TAG`.
+///
+/// The tag of the inner value is replaced by `[TAG]`, retaining its
+/// constructed bit.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Implicit<const TAG: u32, T> {
+    /// Value of the field.
+    pub value: T,
+}
+
+impl<const TAG: u32, T> Implicit<TAG, T> {
+    /// Tag number of this field.
+    pub const TAG_NUMBER: TagNumber = TagNumber::new(TAG);
+
+    /// Create a new [`Implicit`] field wrapping the given value.
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Borrow the inner value.
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<'a, const TAG: u32, T> Choice<'a> for Implicit<TAG, T>
+where
+    T: DecodeValue<'a> + Tagged + 'a,
+{
+    fn can_decode(tag: Tag) -> bool {
+        tag.class() == Class::ContextSpecific && tag.number() == Self::TAG_NUMBER
+    }
+}
+
+impl<'a, const TAG: u32, T> Decode<'a> for Implicit<TAG, T>
+where
+    T: DecodeValue<'a> + Tagged + 'a,
+{
+    type Error = T::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self, T::Error> {
+        let header = Header::decode(reader)?;
+
+        if !Self::can_decode(header.tag) {
+            return Err(header.tag.unexpected_error(None).into());
+        }
+
+        let value = T::decode_value(reader, header)?;
+
+        if header.tag.is_constructed() != value.tag().is_constructed() {
+            return Err(header.tag.non_canonical_error().into());
+        }
+
+        Ok(Self::new(value))
+    }
+}
+
+impl<const TAG: u32, T> EncodeValue for Implicit<TAG, T>
+where
+    T: EncodeValue,
+{
+    fn value_len(&self) -> Result<Length, Error> {
+        self.value.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+        self.value.encode_value(writer)
+    }
+}
+
+impl<const TAG: u32, T> Tagged for Implicit<TAG, T>
+where
+    T: Tagged,
+{
+    fn tag(&self) -> Tag {
+        Tag::ContextSpecific {
+            constructed: self.value.tag().is_constructed(),
+            number: Self::TAG_NUMBER,
+        }
+    }
+}
+
+impl<const TAG: u32, T> ValueOrd for Implicit<TAG, T>
+where
+    T: ValueOrd,
+{
+    fn value_cmp(&self, other: &Self) -> Result<Ordering, Error> {
+        self.value.value_cmp(&other.value)
+    }
+}
+
+impl<const TAG: u32, T> From<T> for Implicit<TAG, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Explicit, Implicit};
+    use crate::{
+        asn1::{Null, OctetStringRef, SequenceOf},
+        Choice, Decode, Encode, EncodeValue, Error, Length, Reader, Result, SliceWriter, Tag,
+        Tagged, Writer,
+    };
+    use hex_literal::hex;
+
+    #[test]
+    fn explicit_round_trip() {
+        let der = hex!("A203020105");
+        let field = Explicit::<2, u8>::from_der(&der).unwrap();
+        assert_eq!(field.value, 5);
+
+        let mut buf = [0u8; 5];
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), der);
+
+        // Wrong tag number
+        assert!(Explicit::<1, u8>::from_der(&der).is_err());
+    }
+
+    #[test]
+    fn implicit_round_trip() {
+        let der = hex!("8103010203");
+        let field = Implicit::<1, OctetStringRef<'_>>::from_der(&der).unwrap();
+        assert_eq!(field.value.as_bytes(), &[1, 2, 3]);
+
+        let mut buf = [0u8; 5];
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), der);
+
+        // Constructed inner types retain the constructed bit
+        let der = hex!("A303020101");
+        let field = Implicit::<3, SequenceOf<u8, 1>>::from_der(&der).unwrap();
+        assert_eq!(field.value.get(0), Some(&1));
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), der);
+
+        // Mismatched constructed bit
+        assert!(Implicit::<3, SequenceOf<u8, 1>>::from_der(&hex!("8303020101")).is_err());
+    }
+
+    /// Hand-written `CHOICE` using tagged wrappers as its variants.
+    #[derive(Debug, Eq, PartialEq)]
+    enum Example {
+        Number(Explicit<0, u8>),
+        Empty(Implicit<1, Null>),
+    }
+
+    impl<'a> Choice<'a> for Example {
+        fn can_decode(tag: Tag) -> bool {
+            <Explicit<0, u8> as Choice<'a>>::can_decode(tag)
+                || <Implicit<1, Null> as Choice<'a>>::can_decode(tag)
+        }
+    }
+
+    impl<'a> Decode<'a> for Example {
+        type Error = Error;
+
+        fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
+            let tag = reader.peek_tag()?;
+
+            if <Explicit<0, u8> as Choice<'a>>::can_decode(tag) {
+                reader.decode().map(Self::Number)
+            } else if <Implicit<1, Null> as Choice<'a>>::can_decode(tag) {
+                reader.decode().map(Self::Empty)
+            } else {
+                Err(tag.unexpected_error(None))
+            }
+        }
+    }
+
+    impl EncodeValue for Example {
+        fn value_len(&self) -> Result<Length> {
+            match self {
+                Self::Number(field) => field.value_len(),
+                Self::Empty(field) => field.value_len(),
+            }
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+            match self {
+                Self::Number(field) => field.encode_value(writer),
+                Self::Empty(field) => field.encode_value(writer),
+            }
+        }
+    }
+
+    impl Tagged for Example {
+        fn tag(&self) -> Tag {
+            match self {
+                Self::Number(field) => field.tag(),
+                Self::Empty(field) => field.tag(),
+            }
+        }
+    }
+
+    #[test]
+    fn choice() {
+        let mut buf = [0u8; 5];
+
+        let der = hex!("A003020107");
+        let number = Example::from_der(&der).unwrap();
+        assert_eq!(number, Example::Number(7.into()));
+        assert_eq!(number.encode_to_slice(&mut buf).unwrap(), der);
+
+        let der = hex!("8100");
+        let empty = Example::from_der(&der).unwrap();
+        assert_eq!(empty, Example::Empty(Null.into()));
+        assert_eq!(empty.encode_to_slice(&mut buf).unwrap(), der);
+
+        assert!(Example::from_der(&hex!("A203020107")).is_err());
+    }
+
+    #[test]
+    fn optional() {
+        let mut writer_buf = [0u8; 5];
+        let mut writer = SliceWriter::new(&mut writer_buf);
+        Some(Explicit::<0, u8>::new(1)).encode(&mut writer).unwrap();
+        let der = writer.finish().unwrap();
+
+        assert_eq!(
+            Option::<Explicit<0, u8>>::from_der(der).unwrap(),
+            Some(Explicit::new(1))
+        );
+    }
+}
@@ -11,6 +11,9 @@ use core::time::Duration;
 #[cfg(feature = "std")]
 use std::time::SystemTime;
 
+#[cfg(feature = "time")]
+use time::OffsetDateTime;
+
 /// ASN.1 `UTCTime` type.
 ///
 /// This type implements the validity requirements specified in
@@ -190,6 +193,42 @@ impl From<UtcTime> for SystemTime {
     }
 }
 
+#[cfg(feature = "time")]
+impl TryFrom<OffsetDateTime> for UtcTime {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<UtcTime> {
+        DateTime::try_from(time)?.try_into()
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<UtcTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: UtcTime) -> Result<OffsetDateTime> {
+        time.to_date_time().try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for UtcTime {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<UtcTime> {
+        DateTime::try_from(time)?.try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<UtcTime> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(time: UtcTime) -> Result<chrono::DateTime<chrono::Utc>> {
+        time.to_date_time().try_into()
+    }
+}
+
 // Implement by hand because the derive would create invalid values.
 // Use the conversion from DateTime to create a valid value.
 // The DateTime type has a way bigger range of valid years than UtcTime,
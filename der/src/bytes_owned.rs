@@ -151,11 +151,8 @@ impl TryFrom<Vec<u8>> for BytesOwned {
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for BytesOwned {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let length = u.arbitrary()?;
-        Ok(Self {
-            length,
-            inner: Box::from(u.bytes(u32::from(length) as usize)?),
-        })
+        let length = u.arbitrary_len::<u8>()?;
+        Self::new(u.bytes(length)?).map_err(|_| arbitrary::Error::IncorrectFormat)
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
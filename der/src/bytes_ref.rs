@@ -128,11 +128,8 @@ impl<'a> TryFrom<&'a [u8]> for BytesRef<'a> {
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for BytesRef<'a> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let length = u.arbitrary()?;
-        Ok(Self {
-            length,
-            inner: u.bytes(u32::from(length) as usize)?,
-        })
+        let length = u.arbitrary_len::<u8>()?;
+        Self::new(u.bytes(length)?).map_err(|_| arbitrary::Error::IncorrectFormat)
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
@@ -0,0 +1,231 @@
+//! Canonical Encoding Rules (CER) output support.
+//!
+//! CER is described in X.690 Section 9, and differs from DER in the following
+//! respects:
+//!
+//! - Constructed values use the indefinite length form (X.690 9.1).
+//! - String types with more than 1000 contents octets are encoded in constructed
+//!   form, split into 1000-octet primitive segments (X.690 9.2).
+//! - The components of a `SET OF` are sorted by their CER encodings rather than
+//!   their DER encodings (X.690 11.6).
+//!
+//! Rather than duplicating the encoders of every type, CER is produced by
+//! transcoding the DER encoding of a value. Note that only `UNIVERSAL` string
+//! types are segmented: strings with an `IMPLICIT` tag can't be identified as
+//! such from their encoding, and are left in primitive form. Likewise, a `SET`
+//! has the same tag as a `SET OF`, so its components are sorted by encoding
+//! rather than in the tag order of X.690 9.3.
+
+use crate::{
+    length::MAX_DER_OCTETS, Decode, Encode, Header, Length, Reader, Result, SliceReader, Tag,
+};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Maximum number of contents octets in a primitive string encoding.
+const SEGMENT_LEN: usize = 1000;
+
+/// Indicator bit for constructed form encoding (i.e. vs primitive form).
+const CONSTRUCTED_FLAG: u8 = 0b100000;
+
+/// Length octet which indicates the indefinite length form.
+const INDEFINITE_LENGTH_OCTET: u8 = 0x80;
+
+/// Maximum length of an encoded header: a tag of up to 6 octets followed by a
+/// length of up to 9 octets.
+const MAX_HEADER_LEN: usize = Tag::MAX_LEN + MAX_DER_OCTETS;
+
+/// End-of-contents octets which terminate an indefinite length value.
+const END_OF_CONTENTS: [u8; 2] = [0, 0];
+
+/// Transcode a DER document into CER, appending the output to `out`.
+pub(crate) fn transcode(der: &[u8], out: &mut Vec<u8>) -> Result<()> {
+    let mut reader = SliceReader::new(der)?;
+
+    while !reader.is_finished() {
+        transcode_tlv(&mut reader, out)?;
+    }
+
+    reader.finish(())
+}
+
+/// Transcode a single TLV production.
+fn transcode_tlv(reader: &mut SliceReader<'_>, out: &mut Vec<u8>) -> Result<()> {
+    let header = Header::decode(reader)?;
+
+    if header.tag.is_constructed() {
+        let mut elements = Vec::new();
+        let mut nested = reader.read_nested_slice(header.length)?;
+
+        while !nested.is_finished() {
+            let mut element = Vec::new();
+            transcode_tlv(&mut nested, &mut element)?;
+            elements.push(element);
+        }
+
+        // X.690 11.6: components of a `SET OF` are ordered by their CER
+        // encodings. A `SET` can't be told apart from a `SET OF` by its
+        // encoding, so it is ordered in the same way rather than by tag.
+        if header.tag == Tag::Set {
+            elements.sort_by(|a, b| padded_cmp(a, b));
+        }
+
+        push_tag(header.tag, out)?;
+        out.push(INDEFINITE_LENGTH_OCTET);
+
+        for element in elements {
+            out.extend_from_slice(&element);
+        }
+
+        out.extend_from_slice(&END_OF_CONTENTS);
+    } else if is_string(header.tag) && usize::try_from(header.length)? > SEGMENT_LEN {
+        let value = reader.read_slice(header.length)?;
+        out.extend_from_slice(&[
+            header.tag.octet() | CONSTRUCTED_FLAG,
+            INDEFINITE_LENGTH_OCTET,
+        ]);
+
+        if header.tag == Tag::BitString {
+            // Each segment begins with its own unused bits octet, which is
+            // zero for all but the last segment
+            let (&unused_bits, bits) = value
+                .split_first()
+                .ok_or_else(|| Tag::BitString.length_error())?;
+
+            let mut segments = bits.chunks(SEGMENT_LEN - 1).peekable();
+
+            while let Some(segment) = segments.next() {
+                let unused = if segments.peek().is_some() {
+                    0
+                } else {
+                    unused_bits
+                };
+
+                let length = (Length::try_from(segment.len())? + Length::ONE)?;
+                push_header(Header::new(Tag::BitString, length)?, out)?;
+                out.push(unused);
+                out.extend_from_slice(segment);
+            }
+        } else {
+            for segment in value.chunks(SEGMENT_LEN) {
+                push_header(Header::new(header.tag, segment.len())?, out)?;
+                out.extend_from_slice(segment);
+            }
+        }
+
+        out.extend_from_slice(&END_OF_CONTENTS);
+    } else {
+        push_header(header, out)?;
+        out.extend_from_slice(reader.read_slice(header.length)?);
+    }
+
+    Ok(())
+}
+
+/// Append the encoding of the given header to `out`.
+fn push_header(header: Header, out: &mut Vec<u8>) -> Result<()> {
+    let mut buf = [0u8; MAX_HEADER_LEN];
+    out.extend_from_slice(header.encode_to_slice(&mut buf)?);
+    Ok(())
+}
+
+/// Append the encoding of the given tag to `out`.
+fn push_tag(tag: Tag, out: &mut Vec<u8>) -> Result<()> {
+    let mut buf = [0u8; Tag::MAX_LEN];
+    out.extend_from_slice(tag.encode_to_slice(&mut buf)?);
+    Ok(())
+}
+
+/// Is the given tag for a string type which is segmented per X.690 9.2?
+fn is_string(tag: Tag) -> bool {
+    matches!(
+        tag,
+        Tag::BitString
+            | Tag::OctetString
+            | Tag::Utf8String
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::TeletexString
+            | Tag::VideotexString
+            | Tag::Ia5String
+            | Tag::VisibleString
+            | Tag::BmpString
+    )
+}
+
+/// Compare two encodings as octet strings, with the shorter one padded at its
+/// trailing end with 0-octets (X.690 9.3).
+fn padded_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().max(b.len());
+    let a = a.iter().copied().chain(core::iter::repeat(0)).take(len);
+    let b = b.iter().copied().chain(core::iter::repeat(0)).take(len);
+    a.cmp(b)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::transcode;
+    use crate::{
+        asn1::{BitStringRef, OctetStringRef, SetOfVec},
+        Encode, ErrorKind, Header, Tag,
+    };
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    #[test]
+    fn constructed_indefinite_length() {
+        let value = [1u8, 2];
+        assert_eq!(value.to_cer().unwrap(), hex!("3080 020101 020102 0000"));
+
+        // Primitive values are unchanged
+        assert_eq!(7u8.to_cer().unwrap(), 7u8.to_der().unwrap());
+    }
+
+    #[test]
+    fn segmented_octet_string() {
+        let bytes = [0xAAu8; 1001];
+        let cer = OctetStringRef::new(&bytes).unwrap().to_cer().unwrap();
+
+        let mut expected = Vec::from(hex!("2480 048203E8"));
+        expected.extend_from_slice(&bytes[..1000]);
+        expected.extend_from_slice(&hex!("0401AA 0000"));
+        assert_eq!(cer, expected);
+
+        // Strings of up to 1000 bytes are primitive
+        let string = OctetStringRef::new(&bytes[..1000]).unwrap();
+        assert_eq!(string.to_cer().unwrap(), string.to_der().unwrap());
+    }
+
+    #[test]
+    fn segmented_bit_string() {
+        let bytes = [0xF0u8; 1000];
+        let cer = BitStringRef::new(4, &bytes).unwrap().to_cer().unwrap();
+
+        let mut expected = Vec::from(hex!("2380 038203E8 00"));
+        expected.extend_from_slice(&bytes[..999]);
+        expected.extend_from_slice(&hex!("030204F0 0000"));
+        assert_eq!(cer, expected);
+    }
+
+    #[test]
+    fn set_of_ordering() {
+        let set = SetOfVec::try_from([1u8, 2]).unwrap();
+        assert_eq!(set.to_cer().unwrap(), hex!("3180 020101 020102 0000"));
+    }
+
+    #[test]
+    fn nesting_depth() {
+        let nested = |depth| {
+            (0..depth).fold(Vec::new(), |inner, _| {
+                let header = Header::new(Tag::Sequence, inner.len()).unwrap();
+                [header.to_der().unwrap(), inner].concat()
+            })
+        };
+
+        assert!(transcode(&nested(64), &mut Vec::new()).is_ok());
+
+        let err = transcode(&nested(65), &mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 64 });
+    }
+}
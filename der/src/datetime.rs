@@ -12,7 +12,7 @@ use core::{fmt, str::FromStr, time::Duration};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "time")]
-use time::PrimitiveDateTime;
+use time::{OffsetDateTime, PrimitiveDateTime};
 
 /// Minimum year allowed in [`DateTime`] values.
 const MIN_YEAR: u16 = 1970;
@@ -357,6 +357,53 @@ impl TryFrom<PrimitiveDateTime> for DateTime {
     }
 }
 
+#[cfg(feature = "time")]
+impl TryFrom<DateTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: DateTime) -> Result<OffsetDateTime> {
+        Ok(PrimitiveDateTime::try_from(time)?.assume_utc())
+    }
+}
+
+/// Converts to UTC, truncating any fractional seconds.
+///
+/// Returns an error if the time is before `UNIX_EPOCH` or after
+/// 9999-12-31T23:59:59Z.
+#[cfg(feature = "time")]
+impl TryFrom<OffsetDateTime> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<DateTime> {
+        let secs = u64::try_from(time.unix_timestamp()).map_err(|_| ErrorKind::DateTime)?;
+        DateTime::from_unix_duration(Duration::from_secs(secs))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(time: DateTime) -> Result<chrono::DateTime<chrono::Utc>> {
+        let secs = i64::try_from(time.unix_duration().as_secs())?;
+        chrono::DateTime::from_timestamp(secs, 0).ok_or_else(|| ErrorKind::DateTime.into())
+    }
+}
+
+/// Truncates any fractional seconds.
+///
+/// Returns an error if the time is before `UNIX_EPOCH` or after
+/// 9999-12-31T23:59:59Z.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> Result<DateTime> {
+        let secs = u64::try_from(time.timestamp()).map_err(|_| ErrorKind::DateTime)?;
+        DateTime::from_unix_duration(Duration::from_secs(secs))
+    }
+}
+
 // Implement by hand because the derive would create invalid values.
 // Use the conversion from Duration to create a valid value.
 #[cfg(feature = "arbitrary")]
@@ -438,6 +485,40 @@ mod tests {
         assert_eq!(datetime.seconds(), 14);
     }
 
+    #[cfg(feature = "time")]
+    #[test]
+    fn offset_date_time_conversions() {
+        use time::{OffsetDateTime, UtcOffset};
+
+        let datetime = DateTime::new(2001, 1, 2, 12, 13, 14).unwrap();
+        let offset_date_time = OffsetDateTime::try_from(datetime).unwrap();
+        assert_eq!(offset_date_time.unix_timestamp(), 978437594);
+        assert_eq!(DateTime::try_from(offset_date_time).unwrap(), datetime);
+
+        // Non-UTC offsets are normalized
+        let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+        assert_eq!(
+            DateTime::try_from(offset_date_time.to_offset(offset)).unwrap(),
+            datetime
+        );
+
+        // Times before the Unix epoch are out of range
+        let before_epoch = OffsetDateTime::from_unix_timestamp(-1).unwrap();
+        assert!(DateTime::try_from(before_epoch).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        let datetime = DateTime::new(2001, 1, 2, 12, 13, 14).unwrap();
+        let chrono_date_time = chrono::DateTime::<chrono::Utc>::try_from(datetime).unwrap();
+        assert_eq!(chrono_date_time.timestamp(), 978437594);
+        assert_eq!(DateTime::try_from(chrono_date_time).unwrap(), datetime);
+
+        let before_epoch = chrono::DateTime::from_timestamp(-1, 0).unwrap();
+        assert!(DateTime::try_from(before_epoch).is_err());
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn display() {
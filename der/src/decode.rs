@@ -4,7 +4,10 @@ use crate::{Error, FixedTag, Header, Reader, SliceReader};
 use core::marker::PhantomData;
 
 #[cfg(feature = "pem")]
-use crate::{pem::PemLabel, PemReader};
+use crate::{
+    pem::{self, PemLabel},
+    PemReader,
+};
 
 #[cfg(doc)]
 use crate::{Length, Tag};
@@ -24,11 +27,68 @@ pub trait Decode<'a>: Sized + 'a {
     fn decode<R: Reader<'a>>(decoder: &mut R) -> Result<Self, Self::Error>;
 
     /// Parse `Self` from the provided DER-encoded byte slice.
+    ///
+    /// Nesting depth is counted from zero, see [`Reader::max_depth`].
     fn from_der(bytes: &'a [u8]) -> Result<Self, Self::Error> {
         let mut reader = SliceReader::new(bytes)?;
         let result = Self::decode(&mut reader)?;
         Ok(reader.finish(result)?)
     }
+
+    /// Parse `Self` from the provided PEM-encoded document, checking that its
+    /// type label is `label`.
+    ///
+    /// Unlike [`DecodePem`], this method doesn't require the type label to be
+    /// fixed by the type being decoded, e.g. when decoding a type from a
+    /// document with a custom label.
+    ///
+    /// Note: types which borrow from the input can't be decoded from PEM.
+    #[cfg(feature = "pem")]
+    fn decode_pem<P>(label: &'static str, pem: &'a P) -> Result<Self, Self::Error>
+    where
+        P: AsRef<[u8]> + ?Sized,
+    {
+        Self::decode_pem_with_labels(&[label], pem)
+    }
+
+    /// Parse `Self` from the provided PEM-encoded document, checking that its
+    /// type label is one of the given `labels`, e.g. both `"CERTIFICATE"` and
+    /// `"X509 CERTIFICATE"`.
+    ///
+    /// If the type label doesn't match, the returned error references the
+    /// first of the given `labels`.
+    #[cfg(feature = "pem")]
+    fn decode_pem_with_labels<P>(labels: &[&'static str], pem: &'a P) -> Result<Self, Self::Error>
+    where
+        P: AsRef<[u8]> + ?Sized,
+    {
+        let mut reader = PemReader::new(pem.as_ref())?;
+
+        if !labels.contains(&reader.type_label()) {
+            let err = match labels.first() {
+                Some(&expected) => pem::Error::UnexpectedTypeLabel { expected },
+                None => pem::Error::Label,
+            };
+
+            return Err(Error::from(err).into());
+        }
+
+        let result = Self::decode(&mut reader)?;
+        Ok(reader.finish(result)?)
+    }
+
+    /// Parse `Self` from the provided PEM-encoded document regardless of its
+    /// type label, returning the label along with the decoded value.
+    #[cfg(feature = "pem")]
+    fn decode_pem_with_any_label<P>(pem: &'a P) -> Result<(&'a str, Self), Self::Error>
+    where
+        P: AsRef<[u8]> + ?Sized,
+    {
+        let mut reader = PemReader::new(pem.as_ref())?;
+        let label = reader.type_label();
+        let result = Self::decode(&mut reader)?;
+        Ok((label, reader.finish(result)?))
+    }
 }
 
 impl<'a, T> Decode<'a> for T
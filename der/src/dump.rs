@@ -0,0 +1,426 @@
+//! `dumpasn1`-style pretty printer for arbitrary BER/DER documents.
+//!
+//! Walks a document without any knowledge of its schema, printing an
+//! indented tree of tag/length/value triples, e.g.:
+//!
+//! ```text
+//!     0 30   19: SEQUENCE {
+//!     2 06    7:   OBJECT IDENTIFIER 1.2.840.10045.2.1
+//!    11 06    8:   OBJECT IDENTIFIER 1.2.840.10045.3.1.7
+//!              : }
+//! ```
+//!
+//! The columns are the offset of each TLV production within the input, the
+//! tag octet, and the length of the value (or `NDEF` for BER indefinite
+//! lengths).
+//!
+//! When the `oid-db` feature is enabled, OIDs are annotated with their names
+//! from the [`const_oid::db`] database.
+
+use crate::{Decode, ErrorKind, Reader, Result, SliceReader, Tag};
+use core::{fmt, str};
+
+#[cfg(feature = "oid")]
+use crate::asn1::ObjectIdentifier;
+
+/// Maximum nesting depth which will be printed.
+const MAX_DEPTH: usize = 64;
+
+/// Number of bytes printed per line of hex output.
+const HEX_LINE_LEN: usize = 16;
+
+/// Width of the offset/tag/length columns (sans trailing `: `).
+const PREFIX_WIDTH: usize = 13;
+
+/// Initial length octet which indicates the indefinite length form, and the
+/// long form flag of other initial length octets.
+const INDEFINITE_LENGTH_OCTET: u8 = 0x80;
+
+/// Initial length octet reserved for future extensions (X.690 8.1.3.5).
+const RESERVED_LENGTH_OCTET: u8 = 0xFF;
+
+/// `dumpasn1`-style pretty printer for a BER/DER document.
+///
+/// The [`fmt::Display`] impl renders the document as an indented tree.
+/// Malformed input does not cause formatting to fail: an error is printed
+/// inline at the position where it was encountered instead.
+#[derive(Copy, Clone, Debug)]
+pub struct Dump<'a> {
+    /// Document being dumped.
+    der: &'a [u8],
+}
+
+impl<'a> Dump<'a> {
+    /// Create a new pretty printer for the given BER/DER document.
+    pub fn new(der: &'a [u8]) -> Self {
+        Self { der }
+    }
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        dump_elements(f, self.der, 0, 0, false).map(|_| ())
+    }
+}
+
+/// Write an indented tree for the given BER/DER document to `out`.
+pub fn dump(der: &[u8], out: &mut impl fmt::Write) -> fmt::Result {
+    dump_elements(out, der, 0, 0, false).map(|_| ())
+}
+
+/// Parsed TLV header: tag, value length (`None` if indefinite), header length.
+type RawHeader = (Tag, Option<usize>, usize);
+
+/// Parse the header at the start of `bytes`.
+///
+/// Unlike [`Length`](crate::Length), this accepts long form lengths which aren't minimally
+/// encoded, as permitted by BER.
+fn parse_header(bytes: &[u8]) -> Result<RawHeader> {
+    let mut reader = SliceReader::new(bytes)?;
+    let tag = Tag::decode(&mut reader)?;
+
+    let length = match reader.read_byte()? {
+        INDEFINITE_LENGTH_OCTET => None,
+        octet if octet < INDEFINITE_LENGTH_OCTET => Some(usize::from(octet)),
+        RESERVED_LENGTH_OCTET => return Err(reader.error(ErrorKind::Length { tag })),
+        octet => {
+            let mut length = 0usize;
+
+            for _ in 0..(octet & !INDEFINITE_LENGTH_OCTET) {
+                let byte = usize::from(reader.read_byte()?);
+                length = length
+                    .checked_mul(0x100)
+                    .and_then(|length| length.checked_add(byte))
+                    .ok_or_else(|| reader.error(ErrorKind::Overflow))?;
+            }
+
+            Some(length)
+        }
+    };
+
+    let header_len = usize::try_from(reader.position())?;
+    Ok((tag, length, header_len))
+}
+
+/// Dump a series of TLV productions, returning the number of bytes consumed.
+///
+/// If `indefinite` is set, stops after an end-of-contents marker.
+#[allow(clippy::arithmetic_side_effects)]
+fn dump_elements(
+    w: &mut impl fmt::Write,
+    bytes: &[u8],
+    offset: usize,
+    depth: usize,
+    indefinite: bool,
+) -> core::result::Result<usize, fmt::Error> {
+    let mut pos = 0;
+
+    while let Some(rest) = bytes.get(pos..).filter(|rest| !rest.is_empty()) {
+        if indefinite && rest.starts_with(&[0, 0]) {
+            return Ok(pos + 2);
+        }
+
+        if depth >= MAX_DEPTH {
+            write_message(w, depth, format_args!("Error: nesting too deep"))?;
+            return Ok(bytes.len());
+        }
+
+        let (tag, length, header_len) = match parse_header(rest) {
+            Ok(header) => header,
+            Err(err) => {
+                write_message(w, depth, format_args!("Error: {}", err.kind()))?;
+                return Ok(bytes.len());
+            }
+        };
+
+        write!(w, "{:>5} {:02X} ", offset + pos, tag.octet())?;
+
+        match length {
+            Some(length) => write!(w, "{:>4}: ", length)?,
+            None => w.write_str("NDEF: ")?,
+        }
+
+        write_indent(w, depth)?;
+        write_tag_name(w, tag)?;
+
+        let value_offset = offset + pos + header_len;
+
+        match length {
+            Some(length) => {
+                let end = header_len.checked_add(length);
+                let value = match end.and_then(|end| rest.get(header_len..end)) {
+                    Some(value) => value,
+                    None => {
+                        w.write_str("\n")?;
+                        write_message(w, depth, format_args!("Error: value truncated"))?;
+                        return Ok(bytes.len());
+                    }
+                };
+
+                if tag.is_constructed() {
+                    w.write_str(" {\n")?;
+                    dump_elements(w, value, value_offset, depth + 1, false)?;
+                    write_message(w, depth, format_args!("}}"))?;
+                } else {
+                    dump_primitive(w, tag, value, value_offset, depth)?;
+                }
+
+                pos += header_len + value.len();
+            }
+            None => {
+                w.write_str(" {\n")?;
+                let body = rest.get(header_len..).unwrap_or_default();
+                let used = dump_elements(w, body, value_offset, depth + 1, true)?;
+                write_message(w, depth, format_args!("}}"))?;
+                pos += header_len + used;
+            }
+        }
+    }
+
+    if indefinite {
+        write_message(w, depth, format_args!("Error: missing end-of-contents"))?;
+    }
+
+    Ok(pos)
+}
+
+/// Dump the value of a primitive TLV production, following the tag name.
+fn dump_primitive(
+    w: &mut impl fmt::Write,
+    tag: Tag,
+    value: &[u8],
+    offset: usize,
+    depth: usize,
+) -> fmt::Result {
+    match tag {
+        Tag::Boolean if value.len() == 1 => {
+            writeln!(w, " {}", if value[0] == 0 { "FALSE" } else { "TRUE" })
+        }
+        Tag::Integer | Tag::Enumerated if !value.is_empty() && value.len() <= 8 => {
+            let sign = if value[0] & 0x80 != 0 { -1i64 } else { 0 };
+            let n = value.iter().fold(sign, |n, &b| n << 8 | i64::from(b));
+            writeln!(w, " {}", n)
+        }
+        #[cfg(feature = "oid")]
+        Tag::ObjectIdentifier => match ObjectIdentifier::from_bytes(value) {
+            Ok(oid) => {
+                #[cfg(feature = "oid-db")]
+                if let Some(name) = const_oid::db::DB.by_oid(&oid) {
+                    return writeln!(w, " {} ({})", name, oid);
+                }
+
+                writeln!(w, " {}", oid)
+            }
+            Err(_) => write_hex(w, value, depth),
+        },
+        Tag::RelativeOid => match crate::asn1::RelativeOid::from_bytes(value) {
+            Ok(oid) => writeln!(w, " {}", oid),
+            Err(_) => write_hex(w, value, depth),
+        },
+        Tag::Null if value.is_empty() => writeln!(w),
+        Tag::Utf8String
+        | Tag::NumericString
+        | Tag::PrintableString
+        | Tag::TeletexString
+        | Tag::VideotexString
+        | Tag::Ia5String
+        | Tag::UtcTime
+        | Tag::GeneralizedTime
+        | Tag::VisibleString => match str::from_utf8(value) {
+            Ok(s) => writeln!(w, " '{}'", s.escape_debug()),
+            Err(_) => write_hex(w, value, depth),
+        },
+        Tag::BitString => match value.split_first() {
+            Some((0, bits)) if encapsulates(bits) => {
+                w.write_str(", encapsulates {\n")?;
+                dump_elements(w, bits, offset + 1, depth + 1, false)?;
+                write_message(w, depth, format_args!("}}"))
+            }
+            Some((&unused_bits, bits)) => {
+                if unused_bits != 0 {
+                    write!(w, " {} unused bits", unused_bits)?;
+                }
+
+                write_hex(w, bits, depth)
+            }
+            None => write_hex(w, value, depth),
+        },
+        Tag::OctetString if encapsulates(value) => {
+            w.write_str(", encapsulates {\n")?;
+            dump_elements(w, value, offset, depth + 1, false)?;
+            write_message(w, depth, format_args!("}}"))
+        }
+        _ => write_hex(w, value, depth),
+    }
+}
+
+/// Does the given value look like it contains encapsulated DER?
+///
+/// This is a heuristic: it must begin with a constructed universal tag and be
+/// entirely consumed by a well-formed series of TLV productions.
+fn encapsulates(bytes: &[u8]) -> bool {
+    match parse_header(bytes) {
+        Ok((tag, _, _)) if tag.is_universal() && tag.is_constructed() => {
+            validate(bytes, 0) == Some(bytes.len())
+        }
+        _ => false,
+    }
+}
+
+/// Validate a series of definite-length TLV productions, returning the number
+/// of bytes consumed.
+#[allow(clippy::arithmetic_side_effects)]
+fn validate(bytes: &[u8], depth: usize) -> Option<usize> {
+    let mut pos = 0;
+
+    while let Some(rest) = bytes.get(pos..).filter(|rest| !rest.is_empty()) {
+        let (tag, length, header_len) = parse_header(rest).ok()?;
+        let value = rest.get(header_len..header_len.checked_add(length?)?)?;
+
+        if tag.is_constructed()
+            && (depth >= MAX_DEPTH || validate(value, depth + 1)? != value.len())
+        {
+            return None;
+        }
+
+        pos += header_len + value.len();
+    }
+
+    Some(pos)
+}
+
+/// Write the name of a tag.
+fn write_tag_name(w: &mut impl fmt::Write, tag: Tag) -> fmt::Result {
+    match tag {
+        Tag::Application { number, .. } => write!(w, "[APPLICATION {}]", number),
+        Tag::ContextSpecific { number, .. } => write!(w, "[{}]", number),
+        Tag::Private { number, .. } => write!(w, "[PRIVATE {}]", number),
+        _ => write!(w, "{}", tag),
+    }
+}
+
+/// Write a hex dump of the given bytes on the lines following the tag name.
+fn write_hex(w: &mut impl fmt::Write, bytes: &[u8], depth: usize) -> fmt::Result {
+    w.write_str("\n")?;
+
+    for chunk in bytes.chunks(HEX_LINE_LEN) {
+        write_continuation(w, depth + 1)?;
+
+        for (i, byte) in chunk.iter().enumerate() {
+            if i > 0 {
+                w.write_str(" ")?;
+            }
+
+            write!(w, "{:02X}", byte)?;
+        }
+
+        w.write_str("\n")?;
+    }
+
+    Ok(())
+}
+
+/// Write a message on its own line at the given depth.
+fn write_message(w: &mut impl fmt::Write, depth: usize, args: fmt::Arguments<'_>) -> fmt::Result {
+    write_continuation(w, depth)?;
+    w.write_fmt(args)?;
+    w.write_str("\n")
+}
+
+/// Write the prefix for a line which does not begin a TLV production.
+fn write_continuation(w: &mut impl fmt::Write, depth: usize) -> fmt::Result {
+    write!(w, "{:width$}: ", "", width = PREFIX_WIDTH)?;
+    write_indent(w, depth)
+}
+
+/// Write indentation for the given depth.
+fn write_indent(w: &mut impl fmt::Write, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        w.write_str("  ")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Dump;
+    use alloc::string::ToString;
+    use hex_literal::hex;
+
+    #[test]
+    fn primitives() {
+        let der = hex!("300A 0101FF 020180 0500 0C00");
+        let expected = "    0 30   10: SEQUENCE {\n    \
+                           2 01    1:   BOOLEAN TRUE\n    \
+                           5 02    1:   INTEGER -128\n    \
+                           8 05    0:   NULL\n   \
+                          10 0C    0:   UTF8String ''\n             \
+                                    : }\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[test]
+    fn encapsulated_octet_string() {
+        let der = hex!("0405 3003 020107");
+        let expected = "    0 04    5: OCTET STRING, encapsulates {\n    \
+                           2 30    3:   SEQUENCE {\n    \
+                           4 02    1:     INTEGER 7\n             \
+                                    :   }\n             \
+                                    : }\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[test]
+    fn hex_and_context_specific() {
+        let der = hex!("A004 8002ABCD");
+        let expected = "    0 A0    4: [0] {\n    \
+                           2 80    2:   [0]\n             \
+                                    :     AB CD\n             \
+                                    : }\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[test]
+    fn indefinite_length() {
+        let der = hex!("3080 020101 0000");
+        let expected = "    0 30 NDEF: SEQUENCE {\n    \
+                           2 02    1:   INTEGER 1\n             \
+                                    : }\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[test]
+    fn noncanonical_length() {
+        // BER permits long form lengths which aren't minimally encoded
+        let der = hex!("3082 0004 02810107");
+        let expected = "    0 30    4: SEQUENCE {\n    \
+                           4 02    1:   INTEGER 7\n             \
+                                    : }\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[cfg(feature = "oid-db")]
+    #[test]
+    fn oid_names() {
+        let der = hex!("0603550403");
+        let expected = "    0 06    3: OBJECT IDENTIFIER cn (2.5.4.3)\n";
+        assert_eq!(Dump::new(&der).to_string(), expected);
+    }
+
+    #[test]
+    fn truncated() {
+        let der = hex!("3005 0201");
+        let output = Dump::new(&der).to_string();
+        assert!(output.contains("Error: value truncated"));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn length_overflow() {
+        let der = hex!("3088 FFFFFFFFFFFFFFFF");
+        let output = Dump::new(&der).to_string();
+        assert!(output.contains("Error: value truncated"));
+    }
+}
@@ -4,7 +4,12 @@ use crate::{Header, Length, Result, SliceWriter, Tagged, Writer};
 use core::marker::PhantomData;
 
 #[cfg(feature = "alloc")]
-use {alloc::boxed::Box, alloc::vec::Vec, core::iter};
+use {
+    crate::{cer, writer::vec::VecWriter},
+    alloc::boxed::Box,
+    alloc::vec::Vec,
+    core::iter,
+};
 
 #[cfg(feature = "pem")]
 use {
@@ -59,10 +64,25 @@ pub trait Encode {
     }
 
     /// Encode this type as DER, returning a byte vector.
+    ///
+    /// Constructed values are encoded in a single pass, without recomputing
+    /// the lengths of their nested values.
     #[cfg(feature = "alloc")]
     fn to_der(&self) -> Result<Vec<u8>> {
+        let mut writer = VecWriter::new();
+        self.encode(&mut writer)?;
+        writer.finish()
+    }
+
+    /// Encode this type as CER (Canonical Encoding Rules), returning a byte
+    /// vector.
+    ///
+    /// CER is produced by transcoding the DER encoding of this value: see
+    /// X.690 Section 9 for how the two encodings differ.
+    #[cfg(feature = "alloc")]
+    fn to_cer(&self) -> Result<Vec<u8>> {
         let mut buf = Vec::new();
-        self.encode_to_vec(&mut buf)?;
+        cer::transcode(&self.to_der()?, &mut buf)?;
         Ok(buf)
     }
 }
@@ -78,8 +98,7 @@ where
 
     /// Encode this value as ASN.1 DER using the provided [`Writer`].
     fn encode(&self, writer: &mut impl Writer) -> Result<()> {
-        self.header()?.encode(writer)?;
-        self.encode_value(writer)
+        writer.encode_tlv(self)
     }
 }
 
@@ -11,25 +11,32 @@ use crate::asn1::ObjectIdentifier;
 #[cfg(feature = "pem")]
 use crate::pem;
 
+#[cfg(feature = "error-context")]
+use {alloc::vec::Vec, core::any::Any};
+
 /// Result type.
 pub type Result<T> = core::result::Result<T, Error>;
 
 /// Error type.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Error {
     /// Kind of error.
     kind: ErrorKind,
 
     /// Position inside of message where error occurred.
     position: Option<Length>,
+
+    /// Path to the field inside of message where error occurred.
+    #[cfg(feature = "error-context")]
+    path: ErrorPath,
 }
 
 impl Error {
     /// Create a new [`Error`].
     pub fn new(kind: ErrorKind, position: Length) -> Error {
         Error {
-            kind,
             position: Some(position),
+            ..kind.into()
         }
     }
 
@@ -48,25 +55,48 @@ impl Error {
     }
 
     /// Get the [`ErrorKind`] which occurred.
-    pub fn kind(self) -> ErrorKind {
+    pub fn kind(&self) -> ErrorKind {
         self.kind
     }
 
     /// Get the position inside of the message where the error occurred.
-    pub fn position(self) -> Option<Length> {
+    pub fn position(&self) -> Option<Length> {
         self.position
     }
 
+    /// Get the path to the field inside of the message where the error
+    /// occurred.
+    #[cfg(feature = "error-context")]
+    pub fn path(&self) -> &ErrorPath {
+        &self.path
+    }
+
+    /// Annotate this error as having occurred while decoding or comparing the
+    /// field named `field_name` of the type named `type_name`.
+    ///
+    /// This is used by the code generated by the `Sequence` and `ValueOrd`
+    /// custom derives.
+    #[cfg(feature = "error-context")]
+    pub fn in_field(mut self, type_name: &'static str, field_name: &'static str) -> Self {
+        self.path.push(type_name, field_name);
+        self
+    }
+
+    /// Annotate this error as having occurred while decoding the element at
+    /// `index` of a `SEQUENCE OF` or `SET OF`.
+    #[cfg(feature = "error-context")]
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.push_index(index);
+        self
+    }
+
     /// For errors occurring inside of a nested message, extend the position
     /// count by the location where the nested message occurs.
     pub(crate) fn nested(self, nested_position: Length) -> Self {
         // TODO(tarcieri): better handle length overflows occurring in this calculation?
         let position = (nested_position + self.position.unwrap_or_default()).ok();
 
-        Self {
-            kind: self.kind,
-            position,
-        }
+        Self { position, ..self }
     }
 }
 
@@ -77,6 +107,11 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.kind)?;
 
+        #[cfg(feature = "error-context")]
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path)?;
+        }
+
         if let Some(pos) = self.position {
             write!(f, " at DER byte {}", pos)?;
         }
@@ -90,6 +125,8 @@ impl From<ErrorKind> for Error {
         Error {
             kind,
             position: None,
+            #[cfg(feature = "error-context")]
+            path: ErrorPath::default(),
         }
     }
 }
@@ -102,19 +139,13 @@ impl From<Infallible> for Error {
 
 impl From<TryFromIntError> for Error {
     fn from(_: TryFromIntError) -> Error {
-        Error {
-            kind: ErrorKind::Overflow,
-            position: None,
-        }
+        ErrorKind::Overflow.into()
     }
 }
 
 impl From<Utf8Error> for Error {
     fn from(err: Utf8Error) -> Error {
-        Error {
-            kind: ErrorKind::Utf8(err),
-            position: None,
-        }
+        ErrorKind::Utf8(err).into()
     }
 }
 
@@ -158,6 +189,111 @@ impl From<time::error::ComponentRange> for Error {
     }
 }
 
+/// Record the index of the `SEQUENCE OF` or `SET OF` element being decoded
+/// in `err`, if it's an [`Error`].
+///
+/// Element decoding errors are generic, so this is a no-op for other types.
+#[allow(unused_mut, unused_variables)]
+pub(crate) fn element_error<E: 'static>(mut err: E, index: usize) -> E {
+    #[cfg(feature = "error-context")]
+    if let Some(err) = (&mut err as &mut dyn Any).downcast_mut::<Error>() {
+        err.path.push_index(index);
+    }
+
+    err
+}
+
+/// Path to the field inside of a message where an error occurred, e.g.
+/// `SignedData.signer_infos[0].digest_algorithm`.
+///
+/// Segments are recorded as the error propagates outwards, so the path is only
+/// allocated when an error occurs inside of a `Sequence` custom derive or an
+/// element of a `SEQUENCE OF` or `SET OF`.
+#[cfg(feature = "error-context")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorPath {
+    /// Name of the outermost type.
+    type_name: Option<&'static str>,
+
+    /// Segments, innermost first.
+    segments: Vec<ErrorPathSegment>,
+}
+
+#[cfg(feature = "error-context")]
+impl ErrorPath {
+    /// Get the name of the outermost type.
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Iterate over the segments of this path, outermost first.
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = ErrorPathSegment> + '_ {
+        self.segments.iter().rev().copied()
+    }
+
+    /// Iterate over the field names in this path, outermost first.
+    pub fn fields(&self) -> impl DoubleEndedIterator<Item = &'static str> + '_ {
+        self.segments().filter_map(|segment| match segment {
+            ErrorPathSegment::Field(name) => Some(name),
+            ErrorPathSegment::Index(_) => None,
+        })
+    }
+
+    /// Is this path empty?
+    pub fn is_empty(&self) -> bool {
+        self.type_name.is_none() && self.segments.is_empty()
+    }
+
+    /// Record the field named `field_name` of the type named `type_name` as
+    /// the new outermost component of this path.
+    fn push(&mut self, type_name: &'static str, field_name: &'static str) {
+        self.type_name = Some(type_name);
+        self.segments.push(ErrorPathSegment::Field(field_name));
+    }
+
+    /// Record the element at `index` as the new outermost component of this
+    /// path.
+    ///
+    /// The element's type is no longer the outermost type, so its name is
+    /// cleared until the enclosing field is recorded.
+    fn push_index(&mut self, index: usize) {
+        self.type_name = None;
+        self.segments.push(ErrorPathSegment::Index(index));
+    }
+}
+
+#[cfg(feature = "error-context")]
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(type_name) = self.type_name {
+            f.write_str(type_name)?;
+        }
+
+        for (i, segment) in self.segments().enumerate() {
+            match segment {
+                ErrorPathSegment::Field(name) if i > 0 || self.type_name.is_some() => {
+                    write!(f, ".{}", name)?
+                }
+                ErrorPathSegment::Field(name) => f.write_str(name)?,
+                ErrorPathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Segment of an [`ErrorPath`].
+#[cfg(feature = "error-context")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorPathSegment {
+    /// Name of a field of a `SEQUENCE`.
+    Field(&'static str),
+
+    /// Index of an element of a `SEQUENCE OF` or `SET OF`.
+    Index(usize),
+}
+
 /// Error type.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -165,6 +301,12 @@ pub enum ErrorKind {
     /// Date-and-time related errors.
     DateTime,
 
+    /// Maximum nesting depth of constructed values exceeded.
+    DepthExceeded {
+        /// Maximum nesting depth permitted by the reader.
+        max_depth: usize,
+    },
+
     /// This error indicates a previous DER parsing operation resulted in
     /// an error and tainted the state of a `Decoder` or `Encoder`.
     ///
@@ -255,9 +397,10 @@ pub enum ErrorKind {
 
     /// Invalid tag number.
     ///
-    /// The "tag number" is the lower 5-bits of a tag's octet.
-    /// This error occurs in the case that all 5-bits are set to `1`,
-    /// which indicates a multi-byte tag which is unsupported by this library.
+    /// This error occurs when a tag in the high tag number form is malformed,
+    /// either because it is non-minimally encoded, encodes a tag number which
+    /// fits in a single octet, overflows a `u32`, or uses the `UNIVERSAL`
+    /// class.
     TagNumberInvalid,
 
     /// Unexpected tag.
@@ -309,6 +452,9 @@ impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ErrorKind::DateTime => write!(f, "date/time error"),
+            ErrorKind::DepthExceeded { max_depth } => {
+                write!(f, "maximum nesting depth of {} exceeded", max_depth)
+            }
             ErrorKind::Failed => write!(f, "operation failed"),
             #[cfg(feature = "std")]
             ErrorKind::FileNotFound => write!(f, "file not found"),
@@ -367,3 +513,56 @@ impl fmt::Display for ErrorKind {
         }
     }
 }
+
+#[cfg(all(test, feature = "error-context"))]
+mod tests {
+    use super::ErrorKind;
+    use alloc::string::ToString;
+
+    #[test]
+    fn error_path() {
+        let err = ErrorKind::Failed
+            .at(4u8.into())
+            .in_field("Inner", "b")
+            .in_field("Outer", "a");
+
+        assert_eq!(err.path().type_name(), Some("Outer"));
+        assert_eq!(
+            err.to_string(),
+            "operation failed in Outer.a.b at DER byte 4"
+        );
+    }
+
+    #[test]
+    fn error_path_deep() {
+        let mut err = ErrorKind::Failed.into();
+
+        for _ in 0..16 {
+            err = super::Error::in_field(err, "Example", "field");
+        }
+
+        assert_eq!(err.path().fields().count(), 16);
+        assert!(err.path().to_string().starts_with("Example.field.field"));
+    }
+
+    #[test]
+    fn error_path_index() {
+        let err = ErrorKind::Failed
+            .at(4u8.into())
+            .in_field("SignerInfo", "digest_algorithm")
+            .at_index(1)
+            .in_field("SignedData", "signer_infos");
+
+        assert_eq!(err.path().type_name(), Some("SignedData"));
+        assert!(err.path().fields().eq(["signer_infos", "digest_algorithm"]));
+        assert_eq!(
+            err.path().to_string(),
+            "SignedData.signer_infos[1].digest_algorithm"
+        );
+
+        let err = ErrorKind::Failed.into();
+        let err = super::element_error::<super::Error>(err, 2).at_index(0);
+        assert_eq!(err.path().type_name(), None);
+        assert_eq!(err.path().to_string(), "[0][2]");
+    }
+}
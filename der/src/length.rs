@@ -8,11 +8,9 @@ use core::{
 };
 
 /// Maximum number of octets in a DER encoding of a [`Length`] using the
-/// rules implemented by this crate.
-const MAX_DER_OCTETS: usize = 5;
-
-/// Maximum length as a `u32` (256 MiB).
-const MAX_U32: u32 = 0xfff_ffff;
+/// rules implemented by this crate: an initial octet followed by up to 8
+/// subsequent octets.
+pub(crate) const MAX_DER_OCTETS: usize = 9;
 
 /// Octet identifying an indefinite length as described in X.690 Section
 /// 8.1.3.6.1:
@@ -23,9 +21,14 @@ const INDEFINITE_LENGTH_OCTET: u8 = 0b10000000; // 0x80
 
 /// ASN.1-encoded length.
 ///
-/// Maximum length is defined by the [`Length::MAX`] constant (256 MiB).
+/// Lengths are represented internally as a [`u64`], which permits encoding
+/// and decoding values larger than 4 GiB, e.g. the contents of firmware or
+/// disk images. Converting a [`Length`] into a [`usize`] returns an error on
+/// platforms where the value can't be represented.
+///
+/// Maximum length is defined by the [`Length::MAX`] constant.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
-pub struct Length(u32);
+pub struct Length(u64);
 
 impl Length {
     /// Length of `0`
@@ -34,14 +37,14 @@ impl Length {
     /// Length of `1`
     pub const ONE: Self = Self(1);
 
-    /// Maximum length currently supported: 256 MiB
-    pub const MAX: Self = Self(MAX_U32);
+    /// Maximum length currently supported: `u64::MAX`
+    pub const MAX: Self = Self(u64::MAX);
 
     /// Create a new [`Length`] for any value which fits inside of a [`u16`].
     ///
     /// This function is const-safe and therefore useful for [`Length`] constants.
     pub const fn new(value: u16) -> Self {
-        Self(value as u32)
+        Self(value as u64)
     }
 
     /// Is this length equal to zero?
@@ -78,14 +81,20 @@ impl Length {
     /// >    most significant bit;
     /// > c) the value 11111111₂ shall not be used.
     fn initial_octet(self) -> Option<u8> {
-        match self.0 {
-            0x80..=0xFF => Some(0x81),
-            0x100..=0xFFFF => Some(0x82),
-            0x10000..=0xFFFFFF => Some(0x83),
-            0x1000000..=MAX_U32 => Some(0x84),
-            _ => None,
+        if self.0 < u64::from(INDEFINITE_LENGTH_OCTET) {
+            None
+        } else {
+            Some(INDEFINITE_LENGTH_OCTET | self.subsequent_octets())
         }
     }
+
+    /// Get the number of subsequent octets required to encode this length in
+    /// the long form (i.e. the number of non-leading-zero octets).
+    #[allow(clippy::cast_possible_truncation)]
+    fn subsequent_octets(self) -> u8 {
+        // At most 8, so the cast can't truncate
+        (8 - self.0.leading_zeros() / 8) as u8
+    }
 }
 
 impl Add for Length {
@@ -95,7 +104,7 @@ impl Add for Length {
         self.0
             .checked_add(other.0)
             .ok_or_else(|| ErrorKind::Overflow.into())
-            .and_then(TryInto::try_into)
+            .map(Self)
     }
 }
 
@@ -119,7 +128,7 @@ impl Add<u32> for Length {
     type Output = Result<Self>;
 
     fn add(self, other: u32) -> Result<Self> {
-        self + Length::try_from(other)?
+        self + Length::from(other)
     }
 }
 
@@ -146,7 +155,7 @@ impl Sub for Length {
         self.0
             .checked_sub(other.0)
             .ok_or_else(|| ErrorKind::Overflow.into())
-            .and_then(TryInto::try_into)
+            .map(Self)
     }
 }
 
@@ -170,21 +179,29 @@ impl From<u16> for Length {
     }
 }
 
-impl From<Length> for u32 {
-    fn from(length: Length) -> u32 {
+impl From<u32> for Length {
+    fn from(len: u32) -> Length {
+        Length(len.into())
+    }
+}
+
+impl From<u64> for Length {
+    fn from(len: u64) -> Length {
+        Length(len)
+    }
+}
+
+impl From<Length> for u64 {
+    fn from(length: Length) -> u64 {
         length.0
     }
 }
 
-impl TryFrom<u32> for Length {
+impl TryFrom<Length> for u32 {
     type Error = Error;
 
-    fn try_from(len: u32) -> Result<Length> {
-        if len <= Self::MAX.0 {
-            Ok(Length(len))
-        } else {
-            Err(ErrorKind::Overflow.into())
-        }
+    fn try_from(length: Length) -> Result<u32> {
+        length.0.try_into().map_err(|_| ErrorKind::Overflow.into())
     }
 }
 
@@ -192,9 +209,9 @@ impl TryFrom<usize> for Length {
     type Error = Error;
 
     fn try_from(len: usize) -> Result<Length> {
-        u32::try_from(len)
-            .map_err(|_| ErrorKind::Overflow)?
-            .try_into()
+        u64::try_from(len)
+            .map(Length)
+            .map_err(|_| ErrorKind::Overflow.into())
     }
 }
 
@@ -215,18 +232,18 @@ impl<'a> Decode<'a> for Length {
             // lengths, which are not allowed in DER, so disallow that byte.
             len if len < INDEFINITE_LENGTH_OCTET => Ok(len.into()),
             INDEFINITE_LENGTH_OCTET => Err(ErrorKind::IndefiniteLength.into()),
-            // 1-4 byte variable-sized length prefix
-            tag @ 0x81..=0x84 => {
+            // 1-8 byte variable-sized length prefix
+            tag @ 0x81..=0x88 => {
                 let nbytes = tag.checked_sub(0x80).ok_or(ErrorKind::Overlength)? as usize;
-                debug_assert!(nbytes <= 4);
+                debug_assert!(nbytes <= 8);
 
-                let mut decoded_len = 0u32;
+                let mut decoded_len = 0u64;
                 for _ in 0..nbytes {
                     decoded_len = decoded_len.checked_shl(8).ok_or(ErrorKind::Overflow)?
-                        | u32::from(reader.read_byte()?);
+                        | u64::from(reader.read_byte()?);
                 }
 
-                let length = Length::try_from(decoded_len)?;
+                let length = Length(decoded_len);
 
                 // X.690 Section 10.1: DER lengths must be encoded with a minimum
                 // number of octets
@@ -237,7 +254,7 @@ impl<'a> Decode<'a> for Length {
                 }
             }
             _ => {
-                // We specialize to a maximum 4-byte length (including initial octet)
+                // We specialize to a maximum 8-byte length (excluding initial octet)
                 Err(ErrorKind::Overlength.into())
             }
         }
@@ -246,13 +263,9 @@ impl<'a> Decode<'a> for Length {
 
 impl Encode for Length {
     fn encoded_len(&self) -> Result<Length> {
-        match self.0 {
-            0..=0x7F => Ok(Length(1)),
-            0x80..=0xFF => Ok(Length(2)),
-            0x100..=0xFFFF => Ok(Length(3)),
-            0x10000..=0xFFFFFF => Ok(Length(4)),
-            0x1000000..=MAX_U32 => Ok(Length(5)),
-            _ => Err(ErrorKind::Overflow.into()),
+        match self.initial_octet() {
+            Some(_) => Ok(Length(1 + u64::from(self.subsequent_octets()))),
+            None => Ok(Length(1)),
         }
     }
 
@@ -262,12 +275,9 @@ impl Encode for Length {
                 writer.write_byte(tag_byte)?;
 
                 // Strip leading zeroes
-                match self.0.to_be_bytes() {
-                    [0, 0, 0, byte] => writer.write_byte(byte),
-                    [0, 0, bytes @ ..] => writer.write(&bytes),
-                    [0, bytes @ ..] => writer.write(&bytes),
-                    bytes => writer.write(&bytes),
-                }
+                let bytes = self.0.to_be_bytes();
+                let leading_zeroes = bytes.len() - usize::from(self.subsequent_octets());
+                writer.write(&bytes[leading_zeroes..])
             }
             #[allow(clippy::cast_possible_truncation)]
             None => writer.write_byte(self.0 as u8),
@@ -296,12 +306,12 @@ impl fmt::Display for Length {
     }
 }
 
-// Implement by hand because the derive would create invalid values.
-// Generate a u32 with a valid range.
+// Implement by hand because the derive would create lengths far larger than
+// any input used in practice. Generate a u64 within the former 256 MiB limit.
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for Length {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        Ok(Self(u.int_in_range(0..=MAX_U32)?))
+        Ok(Self(u.int_in_range(0..=0xfff_ffff)?))
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
@@ -444,7 +454,7 @@ mod tests {
         );
 
         assert_eq!(
-            Length::try_from(0x10000u32).unwrap(),
+            Length::from(0x10000u32),
             Length::from_der(&[0x83, 0x01, 0x00, 0x00]).unwrap()
         );
     }
@@ -477,13 +487,45 @@ mod tests {
 
         assert_eq!(
             &[0x83, 0x01, 0x00, 0x00],
-            Length::try_from(0x10000u32)
-                .unwrap()
+            Length::from(0x10000u32)
                 .encode_to_slice(&mut buffer)
                 .unwrap()
         );
     }
 
+    #[test]
+    fn large_lengths() {
+        // 4 GiB + 1
+        let length = Length::from(0x1_0000_0001u64);
+        let encoded = [0x85, 0x01, 0x00, 0x00, 0x00, 0x01];
+        assert_eq!(length, Length::from_der(&encoded).unwrap());
+
+        let mut buffer = [0u8; 9];
+        assert_eq!(&encoded, length.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(length.encoded_len().unwrap(), Length::from(6u8));
+
+        assert_eq!(
+            Length::MAX,
+            Length::from_der(&[0x88, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).unwrap()
+        );
+        assert_eq!(
+            u32::try_from(length).err().map(|err| err.kind()),
+            Some(ErrorKind::Overflow)
+        );
+        assert_eq!(u64::from(length), 0x1_0000_0001);
+
+        // Non-minimal encoding
+        assert_eq!(
+            Length::from_der(&[0x85, 0x00, 0xFF, 0xFF, 0xFF, 0xFF])
+                .err()
+                .map(|err| err.kind()),
+            Some(ErrorKind::Overlength)
+        );
+
+        // More than 8 subsequent octets
+        assert!(Length::from_der(&[0x89, 1, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
     #[test]
     fn indefinite_lengths() {
         // DER disallows indefinite lengths
@@ -497,10 +539,7 @@ mod tests {
         // It also supports definite lengths.
         let length = IndefiniteLength::from_der(&[0x83, 0x01, 0x00, 0x00]).unwrap();
         assert!(length.is_definite());
-        assert_eq!(
-            Length::try_from(0x10000u32).unwrap(),
-            length.try_into().unwrap()
-        );
+        assert_eq!(Length::from(0x10000u32), length.try_into().unwrap());
     }
 
     #[test]
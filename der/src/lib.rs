@@ -50,10 +50,13 @@
 //! - [`BitString`], [`BitStringRef`]: ASN.1 `BIT STRING`
 //! - [`GeneralizedTime`]: ASN.1 `GeneralizedTime`.
 //! - [`Ia5StringRef`]: ASN.1 `IA5String`.
+//! - [`LenientIa5StringRef`], [`LenientPrintableStringRef`]: ASN.1 `IA5String`
+//!   and `PrintableString` which tolerate invalid characters.
 //! - [`Null`]: ASN.1 `NULL`.
 //! - [`ObjectIdentifier`]: ASN.1 `OBJECT IDENTIFIER`.
 //! - [`OctetString`], [`OctetStringRef`]: ASN.1 `OCTET STRING`.
 //! - [`PrintableStringRef`]: ASN.1 `PrintableString` (ASCII subset).
+//! - [`RelativeOid`]: ASN.1 `RELATIVE-OID`.
 //! - [`TeletexStringRef`]: ASN.1 `TeletexString`.
 //! - [`VideotexStringRef`]: ASN.1 `VideotexString`.
 //! - [`SequenceOf`]: ASN.1 `SEQUENCE OF`.
@@ -65,6 +68,10 @@
 //! Context specific fields can be modeled using these generic types:
 //! - [`ContextSpecific`]: decoder/encoder for owned context-specific fields
 //! - [`ContextSpecificRef`]: encode-only type for references to context-specific fields
+//! - [`Explicit`], [`Implicit`]: context-specific fields whose tag number is part of the type
+//!
+//! Fields with `APPLICATION` and `PRIVATE` class tags are modeled similarly
+//! using [`Application`]/[`ApplicationRef`] and [`Private`]/[`PrivateRef`].
 //!
 //! ## Example
 //! The following example implements X.509's `AlgorithmIdentifier` message type
@@ -303,17 +310,26 @@
 //!
 //! [`Any`]: asn1::Any
 //! [`AnyRef`]: asn1::AnyRef
+//! [`Application`]: asn1::Application
+//! [`ApplicationRef`]: asn1::ApplicationRef
 //! [`ContextSpecific`]: asn1::ContextSpecific
 //! [`ContextSpecificRef`]: asn1::ContextSpecificRef
 //! [`BitString`]: asn1::BitString
+//! [`Explicit`]: asn1::Explicit
+//! [`Implicit`]: asn1::Implicit
 //! [`BitStringRef`]: asn1::BitStringRef
 //! [`GeneralizedTime`]: asn1::GeneralizedTime
 //! [`Ia5StringRef`]: asn1::Ia5StringRef
+//! [`LenientIa5StringRef`]: asn1::LenientIa5StringRef
+//! [`LenientPrintableStringRef`]: asn1::LenientPrintableStringRef
 //! [`Null`]: asn1::Null
 //! [`ObjectIdentifier`]: asn1::ObjectIdentifier
 //! [`OctetString`]: asn1::OctetString
+//! [`Private`]: asn1::Private
+//! [`PrivateRef`]: asn1::PrivateRef
 //! [`OctetStringRef`]: asn1::OctetStringRef
 //! [`PrintableStringRef`]: asn1::PrintableStringRef
+//! [`RelativeOid`]: asn1::RelativeOid
 //! [`TeletexStringRef`]: asn1::TeletexStringRef
 //! [`VideotexStringRef`]: asn1::VideotexStringRef
 //! [`SequenceOf`]: asn1::SequenceOf
@@ -331,6 +347,7 @@ extern crate alloc;
 extern crate std;
 
 pub mod asn1;
+pub mod dump;
 pub mod referenced;
 
 pub(crate) mod arrayvec;
@@ -345,6 +362,7 @@ mod header;
 mod length;
 mod ord;
 mod reader;
+mod spanned;
 mod str_ref;
 mod tag;
 mod writer;
@@ -352,12 +370,14 @@ mod writer;
 #[cfg(feature = "alloc")]
 mod bytes_owned;
 #[cfg(feature = "alloc")]
+mod cer;
+#[cfg(feature = "alloc")]
 mod document;
 #[cfg(feature = "alloc")]
 mod str_owned;
 
 pub use crate::{
-    asn1::{AnyRef, Choice, Sequence},
+    asn1::{AnyRef, Choice, Sequence, SetOfPolicy},
     datetime::DateTime,
     decode::{Decode, DecodeOwned, DecodeValue},
     encode::{Encode, EncodeValue},
@@ -366,8 +386,9 @@ pub use crate::{
     error::{Error, ErrorKind, Result},
     header::Header,
     length::{IndefiniteLength, Length},
-    ord::{DerOrd, ValueOrd},
+    ord::{encoded_value_cmp, DerOrd, ValueOrd},
     reader::{nested::NestedReader, slice::SliceReader, Reader},
+    spanned::{Span, Spanned},
     tag::{Class, FixedTag, Tag, TagMode, TagNumber, Tagged},
     writer::{slice::SliceWriter, Writer},
 };
@@ -375,15 +396,24 @@ pub use crate::{
 #[cfg(feature = "alloc")]
 pub use crate::{asn1::Any, document::Document};
 
+#[cfg(feature = "error-context")]
+pub use crate::error::{ErrorPath, ErrorPathSegment};
+
 #[cfg(feature = "bigint")]
 pub use crypto_bigint as bigint;
 
+#[cfg(feature = "chrono")]
+pub use chrono;
+
 #[cfg(feature = "derive")]
-pub use der_derive::{Choice, Enumerated, Sequence, ValueOrd};
+pub use der_derive::{Choice, DerOrd, Enumerated, Sequence, ValueOrd};
 
 #[cfg(feature = "flagset")]
 pub use flagset;
 
+#[cfg(feature = "num-bigint")]
+pub use num_bigint;
+
 #[cfg(feature = "oid")]
 pub use const_oid as oid;
 
@@ -3,6 +3,12 @@
 use crate::{EncodeValue, Result, Tagged};
 use core::{cmp::Ordering, marker::PhantomData};
 
+#[cfg(feature = "alloc")]
+use crate::writer::vec::VecWriter;
+
+#[cfg(not(feature = "alloc"))]
+use crate::Writer;
+
 /// DER ordering trait.
 ///
 /// Compares the ordering of two values based on their ASN.1 DER
@@ -52,6 +58,109 @@ where
     }
 }
 
+/// Compare the DER encodings of the values of `a` and `b` byte by byte.
+///
+/// This can be used to implement [`ValueOrd`] for types whose encodings can't
+/// be compared piece by piece, e.g. a `SEQUENCE` with `OPTIONAL` or `DEFAULT`
+/// components, whose encodings don't line up when the components present in
+/// `a` and `b` differ.
+///
+/// Without the `alloc` feature, the encodings are compared 256 bytes at a
+/// time, encoding both values once for each of these windows.
+pub fn encoded_value_cmp<T>(a: &T, b: &T) -> Result<Ordering>
+where
+    T: EncodeValue + ?Sized,
+{
+    #[cfg(feature = "alloc")]
+    {
+        Ok(encode_value(a)?.cmp(&encode_value(b)?))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    {
+        let mut offset = 0;
+
+        loop {
+            let window_a = Window::encode(a, offset)?;
+            let window_b = Window::encode(b, offset)?;
+
+            match window_a.as_bytes().cmp(window_b.as_bytes()) {
+                Ordering::Equal if window_a.len == WINDOW_LEN => offset += WINDOW_LEN,
+                ordering => return Ok(ordering),
+            }
+        }
+    }
+}
+
+/// Encode the value of `value` into a [`Vec`].
+///
+/// [`Vec`]: alloc::vec::Vec
+#[cfg(feature = "alloc")]
+fn encode_value<T>(value: &T) -> Result<alloc::vec::Vec<u8>>
+where
+    T: EncodeValue + ?Sized,
+{
+    let mut writer = VecWriter::new();
+    value.encode_value(&mut writer)?;
+    writer.finish()
+}
+
+/// Number of bytes compared at once by [`encoded_value_cmp`].
+#[cfg(not(feature = "alloc"))]
+const WINDOW_LEN: usize = 256;
+
+/// [`Writer`] which captures up to [`WINDOW_LEN`] bytes of an encoding,
+/// starting at a given offset.
+#[cfg(not(feature = "alloc"))]
+struct Window {
+    /// Number of bytes left to skip before the window.
+    skip: usize,
+
+    /// Captured bytes.
+    bytes: [u8; WINDOW_LEN],
+
+    /// Number of captured bytes.
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Window {
+    /// Capture the window of the encoding of the value of `value` which
+    /// starts at `offset`.
+    fn encode<T>(value: &T, offset: usize) -> Result<Self>
+    where
+        T: EncodeValue + ?Sized,
+    {
+        let mut window = Self {
+            skip: offset,
+            bytes: [0; WINDOW_LEN],
+            len: 0,
+        };
+
+        value.encode_value(&mut window)?;
+        Ok(window)
+    }
+
+    /// Borrow the captured bytes.
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Writer for Window {
+    fn write(&mut self, slice: &[u8]) -> Result<()> {
+        let skipped = self.skip.min(slice.len());
+        self.skip -= skipped;
+
+        let slice = &slice[skipped..];
+        let captured = slice.len().min(WINDOW_LEN - self.len);
+        self.bytes[self.len..][..captured].copy_from_slice(&slice[..captured]);
+        self.len += captured;
+        Ok(())
+    }
+}
+
 /// Compare the order of two iterators using [`DerCmp`] on the values.
 pub(crate) fn iter_cmp<'a, I, T: 'a>(a: I, b: I) -> Result<Ordering>
 where
@@ -83,3 +192,30 @@ impl<T> DerOrd for PhantomData<T> {
         Ok(Ordering::Equal)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::encoded_value_cmp;
+    use crate::asn1::OctetStringRef;
+    use core::cmp::Ordering;
+
+    #[test]
+    fn encoded_value_cmp_long() {
+        let a = [1u8; 600];
+        let mut b = a;
+        b[599] = 2;
+        let mut c = a;
+        c[300] = 0;
+
+        let a = OctetStringRef::new(&a).unwrap();
+        let b = OctetStringRef::new(&b).unwrap();
+        let c = OctetStringRef::new(&c).unwrap();
+        assert_eq!(encoded_value_cmp(&a, &a).unwrap(), Ordering::Equal);
+        assert_eq!(encoded_value_cmp(&a, &b).unwrap(), Ordering::Less);
+        assert_eq!(encoded_value_cmp(&b, &c).unwrap(), Ordering::Greater);
+        assert_eq!(
+            encoded_value_cmp(&a, &OctetStringRef::new(&[1; 599]).unwrap()).unwrap(),
+            Ordering::Greater
+        );
+    }
+}
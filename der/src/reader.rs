@@ -9,12 +9,15 @@ pub(crate) use nested::NestedReader;
 
 use crate::{
     asn1::ContextSpecific, Decode, DecodeValue, Encode, EncodingRules, Error, ErrorKind, FixedTag,
-    Header, Length, Tag, TagMode, TagNumber,
+    Header, Length, SetOfPolicy, Tag, TagMode, TagNumber,
 };
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+/// Default maximum nesting depth of constructed values.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 64;
+
 /// Reader trait which reads DER-encoded input.
 pub trait Reader<'r>: Sized {
     /// Get the [`EncodingRules`] which should be applied when decoding the input.
@@ -23,6 +26,36 @@ pub trait Reader<'r>: Sized {
     /// Get the length of the input.
     fn input_len(&self) -> Length;
 
+    /// Get the [`SetOfPolicy`] which should be applied when decoding `SET OF`
+    /// values from the input.
+    fn set_of_policy(&self) -> SetOfPolicy {
+        SetOfPolicy::default()
+    }
+
+    /// Get the nesting depth of this reader, i.e. the number of constructed
+    /// values it is nested within.
+    fn depth(&self) -> usize {
+        0
+    }
+
+    /// Get the maximum nesting depth of constructed values which may be
+    /// decoded from the input.
+    ///
+    /// Attempting to read a value nested more deeply than this results in an
+    /// [`ErrorKind::DepthExceeded`] error.
+    ///
+    /// The limit is best-effort: it only covers the values read through this
+    /// reader. Decoding a value with [`Decode::from_der`] or
+    /// [`AnyRef::decode_as`] starts over from a depth of zero, so types which
+    /// recursively decode such values should use [`SliceReader::new_nested`]
+    /// to carry the depth of the enclosing reader over.
+    ///
+    /// [`AnyRef::decode_as`]: crate::asn1::AnyRef::decode_as
+    /// [`SliceReader::new_nested`]: crate::SliceReader::new_nested
+    fn max_depth(&self) -> usize {
+        DEFAULT_MAX_DEPTH
+    }
+
     /// Peek at the next byte of input without modifying the cursor.
     fn peek_byte(&self) -> Option<u8>;
 
@@ -106,6 +139,7 @@ pub trait Reader<'r>: Sized {
     /// Does not modify the decoder's state.
     fn peek_tag(&self) -> Result<Tag, Error> {
         match self.peek_byte() {
+            Some(byte) if Tag::is_high_form(byte) => self.peek_header().map(|header| header.tag),
             Some(byte) => byte.try_into(),
             None => Err(Error::incomplete(self.input_len())),
         }
@@ -144,6 +178,15 @@ pub trait Reader<'r>: Sized {
     /// Read a byte vector of the given length.
     #[cfg(feature = "alloc")]
     fn read_vec(&mut self, len: Length) -> Result<Vec<u8>, Error> {
+        // Avoid allocating a buffer for a length the input can't satisfy
+        if len > self.remaining_len() {
+            let expected_len = (self.position() + len)?;
+            return Err(self.error(ErrorKind::Incomplete {
+                expected_len,
+                actual_len: self.input_len(),
+            }));
+        }
+
         let mut bytes = vec![0u8; usize::try_from(len)?];
         self.read_into(&mut bytes)?;
         Ok(bytes)
@@ -1,6 +1,8 @@
 //! Reader type for consuming nested TLV records within a DER document.
 
-use crate::{reader::Reader, EncodingRules, Error, ErrorKind, Header, Length, Result};
+use crate::{
+    reader::Reader, EncodingRules, Error, ErrorKind, Header, Length, Result, SetOfPolicy, Tag,
+};
 
 /// Reader type used by [`Reader::read_nested`].
 pub struct NestedReader<'i, R> {
@@ -17,7 +19,12 @@ pub struct NestedReader<'i, R> {
 impl<'i, 'r, R: Reader<'r>> NestedReader<'i, R> {
     /// Create a new nested reader which can read the given [`Length`].
     pub(crate) fn new(inner: &'i mut R, len: Length) -> Result<Self> {
-        if len <= inner.remaining_len() {
+        if inner.depth() >= inner.max_depth() {
+            Err(ErrorKind::DepthExceeded {
+                max_depth: inner.max_depth(),
+            }
+            .at(inner.offset()))
+        } else if len <= inner.remaining_len() {
             Ok(Self {
                 inner,
                 input_len: len,
@@ -59,6 +66,20 @@ impl<'i, 'r, R: Reader<'r>> Reader<'r> for NestedReader<'i, R> {
         self.input_len
     }
 
+    fn set_of_policy(&self) -> SetOfPolicy {
+        self.inner.set_of_policy()
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    fn depth(&self) -> usize {
+        // Can't overflow: `new` ensures the inner depth is below `max_depth`
+        self.inner.depth() + 1
+    }
+
+    fn max_depth(&self) -> usize {
+        self.inner.max_depth()
+    }
+
     fn peek_byte(&self) -> Option<u8> {
         if self.is_finished() {
             None
@@ -67,6 +88,14 @@ impl<'i, 'r, R: Reader<'r>> Reader<'r> for NestedReader<'i, R> {
         }
     }
 
+    fn peek_tag(&self) -> Result<Tag> {
+        if self.is_finished() {
+            Err(Error::incomplete(self.offset()))
+        } else {
+            self.inner.peek_tag()
+        }
+    }
+
     fn peek_header(&self) -> Result<Header> {
         if self.is_finished() {
             Err(Error::incomplete(self.offset()))
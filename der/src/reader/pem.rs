@@ -1,7 +1,7 @@
 //! Streaming PEM reader.
 
-use super::Reader;
-use crate::{Decode, EncodingRules, Error, ErrorKind, Header, Length, Result};
+use super::{Reader, DEFAULT_MAX_DEPTH};
+use crate::{Decode, EncodingRules, Error, ErrorKind, Header, Length, Result, SetOfPolicy, Tag};
 use core::cell::RefCell;
 
 #[allow(clippy::arithmetic_side_effects)]
@@ -136,6 +136,18 @@ pub struct PemReader<'i> {
 
     /// Position in the input buffer (in bytes after Base64 decoding).
     position: Length,
+
+    /// Policy to apply when decoding `SET OF` values.
+    set_of_policy: SetOfPolicy,
+
+    /// Maximum nesting depth of constructed values.
+    ///
+    /// A [`PemReader`] always begins a document, so its own depth is zero:
+    /// the depth of nested values is tracked by the [`NestedReader`]s
+    /// returned by [`Reader::read_nested`].
+    ///
+    /// [`NestedReader`]: crate::NestedReader
+    max_depth: usize,
 }
 
 #[cfg(feature = "pem")]
@@ -152,9 +164,27 @@ impl<'i> PemReader<'i> {
             encoding_rules: EncodingRules::default(),
             input_len,
             position: Length::ZERO,
+            set_of_policy: SetOfPolicy::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
         })
     }
 
+    /// Set the [`SetOfPolicy`] to apply when decoding `SET OF` values.
+    pub fn with_set_of_policy(mut self, set_of_policy: SetOfPolicy) -> Self {
+        self.set_of_policy = set_of_policy;
+        self
+    }
+
+    /// Set the maximum nesting depth of constructed values.
+    ///
+    /// Decoding values which are nested more deeply than this returns an
+    /// [`ErrorKind::DepthExceeded`] error rather than risking a stack
+    /// overflow. Defaults to 64.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Get the PEM label which will be used in the encapsulation boundaries
     /// for this document.
     pub fn type_label(&self) -> &'i str {
@@ -172,6 +202,14 @@ impl<'i> Reader<'i> for PemReader<'i> {
         self.input_len
     }
 
+    fn set_of_policy(&self) -> SetOfPolicy {
+        self.set_of_policy
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     fn peek_byte(&self) -> Option<u8> {
         if self.is_finished() {
             None
@@ -180,6 +218,14 @@ impl<'i> Reader<'i> for PemReader<'i> {
         }
     }
 
+    fn peek_tag(&self) -> Result<Tag> {
+        if self.is_finished() {
+            Err(Error::incomplete(self.offset()))
+        } else {
+            Tag::decode(&mut self.clone())
+        }
+    }
+
     fn peek_header(&self) -> Result<Header> {
         if self.is_finished() {
             Err(Error::incomplete(self.offset()))
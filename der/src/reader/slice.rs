@@ -1,6 +1,9 @@
 //! Slice reader.
 
-use crate::{BytesRef, Decode, EncodingRules, Error, ErrorKind, Header, Length, Reader, Tag};
+use crate::{
+    reader::DEFAULT_MAX_DEPTH, BytesRef, Decode, EncodingRules, Error, ErrorKind, Header, Length,
+    Reader, SetOfPolicy, Tag,
+};
 
 /// [`Reader`] which consumes an input byte slice.
 #[derive(Clone, Debug)]
@@ -16,6 +19,18 @@ pub struct SliceReader<'a> {
 
     /// Position within the decoded slice.
     position: Length,
+
+    /// Offset of the decoded slice within the outermost input.
+    offset: Length,
+
+    /// Policy to apply when decoding `SET OF` values.
+    set_of_policy: SetOfPolicy,
+
+    /// Nesting depth of the input within an enclosing document.
+    depth: usize,
+
+    /// Maximum nesting depth of constructed values.
+    max_depth: usize,
 }
 
 impl<'a> SliceReader<'a> {
@@ -26,14 +41,70 @@ impl<'a> SliceReader<'a> {
             encoding_rules: EncodingRules::default(),
             failed: false,
             position: Length::ZERO,
+            offset: Length::ZERO,
+            set_of_policy: SetOfPolicy::default(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        })
+    }
+
+    /// Create a new slice reader for a TLV production borrowed from the input
+    /// of `parent`, inheriting its decoding options and nesting depth.
+    ///
+    /// `bytes` are expected to be the bytes most recently read from `parent`,
+    /// e.g. with [`Reader::read_slice`] or [`Reader::tlv_bytes`], so that the
+    /// offsets reported by the new reader are measured from the start of the
+    /// outermost input of `parent`.
+    pub fn new_nested<'r, R: Reader<'r>>(bytes: &'a [u8], parent: &R) -> Result<Self, Error> {
+        let len = Length::try_from(bytes.len())?;
+        Ok(Self {
+            encoding_rules: parent.encoding_rules(),
+            set_of_policy: parent.set_of_policy(),
+            depth: parent.depth(),
+            max_depth: parent.max_depth(),
+            offset: parent.offset().saturating_sub(len),
+            ..Self::new(bytes)?
         })
     }
 
+    /// Read a slice of the given length, returning a reader for it which is
+    /// nested one level deeper than this one.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn read_nested_slice(&mut self, len: Length) -> Result<Self, Error> {
+        if self.depth >= self.max_depth {
+            return Err(self.error(ErrorKind::DepthExceeded {
+                max_depth: self.max_depth,
+            }));
+        }
+
+        let bytes = self.read_slice(len)?;
+        Ok(Self {
+            depth: self.depth + 1,
+            ..Self::new_nested(bytes, self)?
+        })
+    }
+
+    /// Set the [`SetOfPolicy`] to apply when decoding `SET OF` values.
+    pub fn with_set_of_policy(mut self, set_of_policy: SetOfPolicy) -> Self {
+        self.set_of_policy = set_of_policy;
+        self
+    }
+
+    /// Set the maximum nesting depth of constructed values.
+    ///
+    /// Decoding values which are nested more deeply than this returns an
+    /// [`ErrorKind::DepthExceeded`] error rather than risking a stack
+    /// overflow. Defaults to 64.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     /// Return an error with the given [`ErrorKind`], annotating it with
     /// context about where the error occurred.
     pub fn error(&mut self, kind: ErrorKind) -> Error {
         self.failed = true;
-        kind.at(self.position)
+        kind.at(self.offset())
     }
 
     /// Return an error for an invalid value with the given tag.
@@ -50,7 +121,7 @@ impl<'a> SliceReader<'a> {
     /// position.
     fn remaining(&self) -> Result<&'a [u8], Error> {
         if self.is_failed() {
-            Err(ErrorKind::Failed.at(self.position))
+            Err(ErrorKind::Failed.at(self.offset()))
         } else {
             self.bytes
                 .as_slice()
@@ -69,12 +140,28 @@ impl<'a> Reader<'a> for SliceReader<'a> {
         self.bytes.len()
     }
 
+    fn set_of_policy(&self) -> SetOfPolicy {
+        self.set_of_policy
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     fn peek_byte(&self) -> Option<u8> {
         self.remaining()
             .ok()
             .and_then(|bytes| bytes.first().cloned())
     }
 
+    fn peek_tag(&self) -> Result<Tag, Error> {
+        Tag::decode(&mut self.clone())
+    }
+
     fn peek_header(&self) -> Result<Header, Error> {
         Header::decode(&mut self.clone())
     }
@@ -83,6 +170,10 @@ impl<'a> Reader<'a> for SliceReader<'a> {
         self.position
     }
 
+    fn offset(&self) -> Length {
+        self.offset.saturating_add(self.position)
+    }
+
     fn read_slice(&mut self, len: Length) -> Result<&'a [u8], Error> {
         if self.is_failed() {
             return Err(self.error(ErrorKind::Failed));
@@ -113,18 +204,18 @@ impl<'a> Reader<'a> for SliceReader<'a> {
 
     fn error(&mut self, kind: ErrorKind) -> Error {
         self.failed = true;
-        kind.at(self.position)
+        kind.at(self.offset())
     }
 
     fn finish<T>(self, value: T) -> Result<T, Error> {
         if self.is_failed() {
-            Err(ErrorKind::Failed.at(self.position))
+            Err(ErrorKind::Failed.at(self.offset()))
         } else if !self.is_finished() {
             Err(ErrorKind::TrailingData {
                 decoded: self.position,
                 remaining: self.remaining_len(),
             }
-            .at(self.position))
+            .at(self.offset()))
         } else {
             Ok(value)
         }
@@ -140,7 +231,10 @@ impl<'a> Reader<'a> for SliceReader<'a> {
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use super::SliceReader;
-    use crate::{Decode, ErrorKind, Length, Reader, Tag};
+    use crate::{
+        asn1::{OctetStringRef, SequenceOf},
+        Decode, ErrorKind, Length, Reader, Tag,
+    };
     use hex_literal::hex;
 
     // INTEGER: 42
@@ -184,6 +278,25 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_vec_overlength() {
+        let mut reader = SliceReader::new(EXAMPLE_MSG).unwrap();
+        let err = reader.read_vec(Length::MAX).err().unwrap();
+        assert_eq!(Some(Length::ZERO), err.position());
+
+        match err.kind() {
+            ErrorKind::Incomplete {
+                expected_len,
+                actual_len,
+            } => {
+                assert_eq!(actual_len, EXAMPLE_MSG.len().try_into().unwrap());
+                assert_eq!(expected_len, Length::MAX);
+            }
+            other => panic!("unexpected error kind: {:?}", other),
+        }
+    }
+
     #[test]
     fn trailing_data() {
         let mut reader = SliceReader::new(EXAMPLE_MSG).unwrap();
@@ -220,4 +333,52 @@ mod tests {
         assert_eq!(header.length, Length::ONE);
         assert_eq!(reader.position(), Length::ZERO); // Position unchanged
     }
+
+    #[test]
+    fn large_length() {
+        // OCTET STRING with a length of 4 GiB but only one octet of contents
+        let mut reader = SliceReader::new(&hex!("04 85 0100000000 00")).unwrap();
+        let err = OctetStringRef::decode(&mut reader).unwrap_err();
+
+        match err.kind() {
+            ErrorKind::Incomplete {
+                expected_len,
+                actual_len,
+            } => {
+                assert_eq!(expected_len, Length::from(0x1_0000_0007u64));
+                assert_eq!(actual_len, Length::from(8u8));
+            }
+            other => panic!("unexpected error kind: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        // SEQUENCE { SEQUENCE { INTEGER 1 } }
+        const NESTED_MSG: &[u8] = &hex!("3005 3003 020101");
+        type Nested = SequenceOf<SequenceOf<u8, 1>, 1>;
+
+        let mut reader = SliceReader::new(NESTED_MSG).unwrap().with_max_depth(2);
+        assert!(Nested::decode(&mut reader).is_ok());
+
+        let mut reader = SliceReader::new(NESTED_MSG).unwrap().with_max_depth(1);
+        let err = Nested::decode(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 1 });
+        assert_eq!(err.position(), Some(Length::from(4u8)));
+    }
+
+    #[test]
+    fn new_nested_inherits_depth() {
+        let mut reader = SliceReader::new(EXAMPLE_MSG).unwrap().with_max_depth(3);
+        let len = reader.remaining_len();
+
+        reader
+            .read_nested(len, |nested| {
+                let inner = SliceReader::new_nested(EXAMPLE_MSG, nested)?;
+                assert_eq!(inner.depth(), 1);
+                assert_eq!(inner.max_depth(), 3);
+                nested.read_slice(len)
+            })
+            .unwrap();
+    }
 }
@@ -0,0 +1,257 @@
+//! Byte span tracking for decoded values.
+
+use crate::{
+    Choice, Decode, DerOrd, Encode, Error, Header, Length, Reader, Result, SliceReader, Tag,
+    Tagged, Writer,
+};
+use core::{cmp::Ordering, ops::Deref};
+
+/// Range of bytes within a DER document.
+///
+/// Offsets are measured from the start of the outermost input being decoded.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Span {
+    /// Offset of the first byte of the span.
+    start: Length,
+
+    /// Offset one byte past the end of the span.
+    end: Length,
+}
+
+impl Span {
+    /// Create a new [`Span`] starting at `start` with the given length.
+    pub fn new(start: Length, len: Length) -> Result<Self> {
+        Ok(Self {
+            start,
+            end: (start + len)?,
+        })
+    }
+
+    /// Offset of the first byte of the span.
+    pub fn start(self) -> Length {
+        self.start
+    }
+
+    /// Offset one byte past the end of the span.
+    pub fn end(self) -> Length {
+        self.end
+    }
+
+    /// Length of the span in bytes.
+    pub fn len(self) -> Length {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Is this span empty?
+    pub fn is_empty(self) -> bool {
+        self.len().is_zero()
+    }
+
+    /// Does this span contain the given offset?
+    pub fn contains(self, offset: Length) -> bool {
+        self.start <= offset && offset < self.end
+    }
+}
+
+/// Wrapper type which records the [`Span`] a value was decoded from along
+/// with its original TLV encoding.
+///
+/// Encoding a [`Spanned`] value re-emits the original bytes verbatim, which
+/// allows higher layers to pass through untouched (and possibly
+/// non-canonical) TLV productions byte-for-byte.
+///
+/// Decoding requires a [`Reader`] which is able to borrow from its input,
+/// such as [`SliceReader`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Spanned<'a, T> {
+    /// Decoded value.
+    value: T,
+
+    /// Header of the TLV production the value was decoded from.
+    header: Header,
+
+    /// Location of the TLV production within the input.
+    span: Span,
+
+    /// Complete TLV production the value was decoded from.
+    tlv: &'a [u8],
+}
+
+impl<'a, T> Spanned<'a, T> {
+    /// Borrow the decoded value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the [`Header`] of the TLV production the value was decoded from.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Get the [`Span`] of the TLV production the value was decoded from.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Get the complete TLV production the value was decoded from.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.tlv
+    }
+
+    /// Get the value portion of the TLV production, sans tag and length.
+    pub fn value_bytes(&self) -> Result<&'a [u8]> {
+        let header_len = usize::try_from(self.header.encoded_len()?)?;
+        Ok(self.tlv.get(header_len..).unwrap_or_default())
+    }
+
+    /// Discard the span information, returning the decoded value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> AsRef<T> for Spanned<'_, T> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for Spanned<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> Decode<'a> for Spanned<'a, T>
+where
+    T: Decode<'a, Error = Error>,
+{
+    type Error = Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
+        let start = reader.offset();
+        let header = reader.peek_header()?;
+        let tlv = reader.tlv_bytes()?;
+        let span = Span::new(start, tlv.len().try_into()?)?;
+
+        // Decode the value from its own TLV production, which reports offsets
+        // and error positions within the original input. Errors without a
+        // position are attributed to the start of the value.
+        let mut nested = SliceReader::new_nested(tlv, reader)?;
+        let value = T::decode(&mut nested)
+            .and_then(|value| nested.finish(value))
+            .map_err(|err| match err.position() {
+                Some(_) => err,
+                None => err.nested(start),
+            })?;
+
+        Ok(Self {
+            value,
+            header,
+            span,
+            tlv,
+        })
+    }
+}
+
+impl<T> Encode for Spanned<'_, T> {
+    fn encoded_len(&self) -> Result<Length> {
+        self.tlv.len().try_into()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.tlv)
+    }
+}
+
+impl<T> Tagged for Spanned<'_, T> {
+    fn tag(&self) -> Tag {
+        self.header.tag
+    }
+}
+
+impl<'a, T> Choice<'a> for Spanned<'a, T>
+where
+    T: Choice<'a> + Decode<'a, Error = Error>,
+{
+    fn can_decode(tag: Tag) -> bool {
+        T::can_decode(tag)
+    }
+}
+
+impl<T> DerOrd for Spanned<'_, T> {
+    fn der_cmp(&self, other: &Self) -> core::result::Result<Ordering, Error> {
+        Ok(self.tlv.cmp(other.tlv))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Span, Spanned};
+    use crate::{
+        asn1::{OctetStringRef, SequenceOf},
+        Decode, Encode, Length, Tag,
+    };
+    use hex_literal::hex;
+
+    /// `SEQUENCE OF OCTET STRING` containing `{ 'AB'H, 'CDEF'H }`.
+    const EXAMPLE_MSG: &[u8] = &hex!("3007 0401AB 0402CDEF");
+
+    #[test]
+    fn decode_spans() {
+        let seq = SequenceOf::<Spanned<'_, OctetStringRef<'_>>, 2>::from_der(EXAMPLE_MSG).unwrap();
+
+        let first = seq.get(0).unwrap();
+        assert_eq!(first.span(), Span::new(2u8.into(), 3u8.into()).unwrap());
+        assert_eq!(first.as_bytes(), &hex!("0401AB"));
+        assert_eq!(first.value_bytes().unwrap(), &hex!("AB"));
+        assert_eq!(first.value().as_bytes(), &hex!("AB"));
+
+        let second = seq.get(1).unwrap();
+        assert_eq!(second.span().start(), Length::from(5u8));
+        assert_eq!(second.span().end(), Length::from(9u8));
+        assert_eq!(second.header().tag, Tag::OctetString);
+    }
+
+    #[test]
+    fn nested_error_position() {
+        // Second element (at offset 5) has an invalid boolean value
+        let msg = hex!("3006 0101FF 010102");
+        let err = SequenceOf::<Spanned<'_, bool>, 2>::from_der(&msg).unwrap_err();
+        assert_eq!(err.position(), Some(Length::from(5u8)));
+    }
+
+    #[test]
+    fn nested_spans() {
+        // The example message wrapped in another `SEQUENCE OF`
+        let msg = hex!("3009 3007 0401AB 0402CDEF");
+        type Inner<'a> = SequenceOf<Spanned<'a, OctetStringRef<'a>>, 2>;
+        let seq = SequenceOf::<Spanned<'_, Inner<'_>>, 1>::from_der(&msg).unwrap();
+
+        let inner = seq.get(0).unwrap();
+        assert_eq!(inner.span(), Span::new(2u8.into(), 9u8.into()).unwrap());
+        assert_eq!(inner.get(0).unwrap().span().start(), Length::from(4u8));
+        assert_eq!(inner.get(1).unwrap().span().start(), Length::from(7u8));
+        assert_eq!(inner.get(1).unwrap().as_bytes(), &hex!("0402CDEF"));
+    }
+
+    #[test]
+    fn nested_spans_error_position() {
+        // Second element (at offset 7) has an invalid boolean value
+        let msg = hex!("3008 3006 0101FF 010102");
+        type Inner<'a> = SequenceOf<Spanned<'a, bool>, 2>;
+        let err = SequenceOf::<Spanned<'_, Inner<'_>>, 1>::from_der(&msg).unwrap_err();
+        assert_eq!(err.position(), Some(Length::from(7u8)));
+    }
+
+    #[test]
+    fn reencode_verbatim() {
+        let msg = hex!("0402ABCD");
+        let spanned = Spanned::<'_, OctetStringRef<'_>>::from_der(&msg).unwrap();
+        let mut buf = [0u8; 4];
+        assert_eq!(spanned.encode_to_slice(&mut buf).unwrap(), &msg);
+    }
+}
@@ -34,7 +34,7 @@ impl<T: FixedTag> Tagged for T {
 
 /// ASN.1 tags.
 ///
-/// Tags are the leading identifier octet of the Tag-Length-Value encoding
+/// Tags are the leading identifier octets of the Tag-Length-Value encoding
 /// used by ASN.1 DER and identify the type of the subsequent value.
 ///
 /// They are described in X.690 Section 8.1.2: Identifier octets, and
@@ -47,6 +47,10 @@ impl<T: FixedTag> Tagged for T {
 /// - Bits 8/7: [`Class`]
 /// - Bit 6: primitive (0) or constructed (1)
 /// - Bits 5-1: tag number
+///
+/// Tag numbers greater than 30 are encoded in the high tag number form: bits
+/// 5-1 of the leading octet are all set, and the tag number follows in base
+/// 128 across one or more subsequent octets (X.690 Section 8.1.2.4).
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
 #[non_exhaustive]
@@ -78,6 +82,9 @@ pub enum Tag {
     /// `UTF8String` tag: `12`.
     Utf8String,
 
+    /// `RELATIVE-OID` tag: `13`.
+    RelativeOid,
+
     /// `SEQUENCE` tag: `16`.
     Sequence,
 
@@ -140,6 +147,10 @@ pub enum Tag {
 }
 
 impl Tag {
+    /// Maximum length of an encoded tag: a leading identifier octet followed
+    /// by up to 5 subsequent octets encoding a `u32` tag number.
+    pub(crate) const MAX_LEN: usize = 6;
+
     /// Assert that this [`Tag`] matches the provided expected tag.
     ///
     /// On mismatch, returns an [`Error`] with [`ErrorKind::TagUnexpected`].
@@ -161,9 +172,14 @@ impl Tag {
         }
     }
 
-    /// Get the [`TagNumber`] (lower 6-bits) for this tag.
+    /// Get the [`TagNumber`] for this tag.
     pub fn number(self) -> TagNumber {
-        TagNumber(self.octet() & TagNumber::MASK)
+        match self {
+            Tag::Application { number, .. }
+            | Tag::ContextSpecific { number, .. }
+            | Tag::Private { number, .. } => number,
+            _ => TagNumber(u32::from(self.octet() & TagNumber::MASK)),
+        }
     }
 
     /// Does this tag represent a constructed (as opposed to primitive) field?
@@ -191,7 +207,10 @@ impl Tag {
         self.class() == Class::Universal
     }
 
-    /// Get the octet encoding for this [`Tag`].
+    /// Get the leading identifier octet of the encoding of this [`Tag`].
+    ///
+    /// For tags using the high tag number form, the tag number is not
+    /// included in this octet: use [`Encode`] to obtain the full encoding.
     pub fn octet(self) -> u8 {
         match self {
             Tag::Boolean => 0x01,
@@ -203,6 +222,7 @@ impl Tag {
             Tag::Real => 0x09,
             Tag::Enumerated => 0x0A,
             Tag::Utf8String => 0x0C,
+            Tag::RelativeOid => 0x0D,
             Tag::Sequence => 0x10 | CONSTRUCTED_FLAG,
             Tag::Set => 0x11 | CONSTRUCTED_FLAG,
             Tag::NumericString => 0x12,
@@ -229,6 +249,58 @@ impl Tag {
         }
     }
 
+    /// Does the given leading identifier octet indicate a tag encoded in the
+    /// high tag number form?
+    pub(crate) fn is_high_form(octet: u8) -> bool {
+        octet & TagNumber::MASK == TagNumber::MASK
+    }
+
+    /// Encode this tag into the provided buffer, returning the encoded octets.
+    fn encode_to_buf(self, buf: &mut [u8; Self::MAX_LEN]) -> &[u8] {
+        buf[0] = self.octet();
+
+        let number = self.number();
+        let high_len = usize::from(number.high_len());
+
+        for (i, octet) in buf[1..=high_len].iter_mut().enumerate() {
+            let shift = 7 * (high_len - 1 - i);
+            let continuation = if i + 1 < high_len { 0x80 } else { 0 };
+            *octet = ((number.get() >> shift).to_le_bytes()[0] & 0x7F) | continuation;
+        }
+
+        &buf[..=high_len]
+    }
+
+    /// Decode the subsequent identifier octets of a tag number encoded in the
+    /// high tag number form.
+    fn decode_high_number<'a, R: Reader<'a>>(reader: &mut R) -> Result<TagNumber> {
+        let mut number = 0u32;
+
+        for i in 0..(Self::MAX_LEN - 1) {
+            let octet = reader.read_byte()?;
+
+            // X.690 8.1.2.4.2(c): bits 7 to 1 of the first subsequent octet
+            // shall not all be zero
+            if (i == 0 && octet == 0x80) || number > (u32::MAX >> 7) {
+                return Err(ErrorKind::TagNumberInvalid.into());
+            }
+
+            number = (number << 7) | u32::from(octet & 0x7F);
+
+            if octet & 0x80 == 0 {
+                // DER requires the single octet form for tag numbers below 31
+                let number = TagNumber(number);
+                return if number.is_high() {
+                    Ok(number)
+                } else {
+                    Err(ErrorKind::TagNumberInvalid.into())
+                };
+            }
+        }
+
+        Err(ErrorKind::TagNumberInvalid.into())
+    }
+
     /// Create an [`Error`] for an invalid [`Length`].
     pub fn length_error(self) -> Error {
         ErrorKind::Length { tag: self }.into()
@@ -261,8 +333,12 @@ impl TryFrom<u8> for Tag {
     type Error = Error;
 
     fn try_from(byte: u8) -> Result<Tag> {
+        if Self::is_high_form(byte) {
+            return Err(ErrorKind::TagNumberInvalid.into());
+        }
+
         let constructed = byte & CONSTRUCTED_FLAG != 0;
-        let number = TagNumber::try_from(byte & TagNumber::MASK)?;
+        let number = TagNumber(u32::from(byte & TagNumber::MASK));
 
         match byte {
             0x01 => Ok(Tag::Boolean),
@@ -274,6 +350,7 @@ impl TryFrom<u8> for Tag {
             0x09 => Ok(Tag::Real),
             0x0A => Ok(Tag::Enumerated),
             0x0C => Ok(Tag::Utf8String),
+            0x0D => Ok(Tag::RelativeOid),
             0x12 => Ok(Tag::NumericString),
             0x13 => Ok(Tag::PrintableString),
             0x14 => Ok(Tag::TeletexString),
@@ -302,15 +379,25 @@ impl TryFrom<u8> for Tag {
     }
 }
 
-impl From<Tag> for u8 {
-    fn from(tag: Tag) -> u8 {
-        tag.octet()
+impl TryFrom<Tag> for u8 {
+    type Error = Error;
+
+    /// Convert a tag which is encoded as a single identifier octet, failing
+    /// for tags in the high tag number form.
+    fn try_from(tag: Tag) -> Result<u8> {
+        if tag.number().is_high() {
+            Err(ErrorKind::TagNumberInvalid.into())
+        } else {
+            Ok(tag.octet())
+        }
     }
 }
 
-impl From<&Tag> for u8 {
-    fn from(tag: &Tag) -> u8 {
-        u8::from(*tag)
+impl TryFrom<&Tag> for u8 {
+    type Error = Error;
+
+    fn try_from(tag: &Tag) -> Result<u8> {
+        u8::try_from(*tag)
     }
 }
 
@@ -318,23 +405,55 @@ impl<'a> Decode<'a> for Tag {
     type Error = Error;
 
     fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
-        reader.read_byte().and_then(Self::try_from)
+        let byte = reader.read_byte()?;
+
+        if !Self::is_high_form(byte) {
+            return Self::try_from(byte);
+        }
+
+        let constructed = byte & CONSTRUCTED_FLAG != 0;
+        let class = byte & !(CONSTRUCTED_FLAG | TagNumber::MASK);
+
+        if class == Class::Universal as u8 {
+            return Err(ErrorKind::TagNumberInvalid.into());
+        }
+
+        let number = Self::decode_high_number(reader)?;
+
+        Ok(if class == Class::Application as u8 {
+            Tag::Application {
+                constructed,
+                number,
+            }
+        } else if class == Class::ContextSpecific as u8 {
+            Tag::ContextSpecific {
+                constructed,
+                number,
+            }
+        } else {
+            Tag::Private {
+                constructed,
+                number,
+            }
+        })
     }
 }
 
 impl Encode for Tag {
     fn encoded_len(&self) -> Result<Length> {
-        Ok(Length::ONE)
+        Ok(Length::from(1 + self.number().high_len()))
     }
 
     fn encode(&self, writer: &mut impl Writer) -> Result<()> {
-        writer.write_byte(self.into())
+        let mut buf = [0u8; Self::MAX_LEN];
+        writer.write(self.encode_to_buf(&mut buf))
     }
 }
 
 impl DerOrd for Tag {
     fn der_cmp(&self, other: &Self) -> Result<Ordering> {
-        Ok(self.octet().cmp(&other.octet()))
+        let (mut a, mut b) = ([0u8; Self::MAX_LEN], [0u8; Self::MAX_LEN]);
+        Ok(self.encode_to_buf(&mut a).cmp(other.encode_to_buf(&mut b)))
     }
 }
 
@@ -352,6 +471,7 @@ impl fmt::Display for Tag {
             Tag::Real => f.write_str("REAL"),
             Tag::Enumerated => f.write_str("ENUMERATED"),
             Tag::Utf8String => f.write_str("UTF8String"),
+            Tag::RelativeOid => f.write_str("RELATIVE-OID"),
             Tag::Set => f.write_str("SET"),
             Tag::NumericString => f.write_str("NumericString"),
             Tag::PrintableString => f.write_str("PrintableString"),
@@ -396,14 +516,18 @@ impl fmt::Display for Tag {
 
 impl fmt::Debug for Tag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Tag(0x{:02x}: {})", u8::from(*self), self)
+        write!(f, "Tag(0x{:02x}: {})", self.octet(), self)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::TagNumber;
     use super::{Class, Tag};
+    use crate::{Decode, DerOrd, Encode, ErrorKind, SliceWriter};
+    use core::cmp::Ordering;
+    use hex_literal::hex;
 
     #[test]
     fn tag_class() {
@@ -416,6 +540,7 @@ mod tests {
         assert_eq!(Tag::Real.class(), Class::Universal);
         assert_eq!(Tag::Enumerated.class(), Class::Universal);
         assert_eq!(Tag::Utf8String.class(), Class::Universal);
+        assert_eq!(Tag::RelativeOid.class(), Class::Universal);
         assert_eq!(Tag::Set.class(), Class::Universal);
         assert_eq!(Tag::NumericString.class(), Class::Universal);
         assert_eq!(Tag::PrintableString.class(), Class::Universal);
@@ -459,4 +584,91 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn high_tag_number_round_trip() {
+        let examples: &[(&[u8], Tag)] = &[
+            (
+                &hex!("9F1F"),
+                Tag::ContextSpecific {
+                    constructed: false,
+                    number: TagNumber::new(31),
+                },
+            ),
+            (
+                &hex!("7F817F"),
+                Tag::Application {
+                    constructed: true,
+                    number: TagNumber::new(255),
+                },
+            ),
+            (
+                &hex!("DF8FFFFFFF7F"),
+                Tag::Private {
+                    constructed: false,
+                    number: TagNumber::new(u32::MAX),
+                },
+            ),
+        ];
+
+        for &(bytes, tag) in examples {
+            assert_eq!(Tag::from_der(bytes).unwrap(), tag);
+            assert_eq!(
+                usize::try_from(tag.encoded_len().unwrap()).unwrap(),
+                bytes.len()
+            );
+
+            let mut buf = [0u8; Tag::MAX_LEN];
+            let mut writer = SliceWriter::new(&mut buf);
+            tag.encode(&mut writer).unwrap();
+            assert_eq!(writer.finish().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn high_tag_number_invalid() {
+        for bytes in [
+            // Universal class
+            &hex!("1F1F")[..],
+            // Leading zero subsequent octet
+            &hex!("9F801F"),
+            // Tag number which fits in the single octet form
+            &hex!("9F1E"),
+            // Tag number which overflows `u32`
+            &hex!("9F908080808000"),
+        ] {
+            let err = Tag::from_der(bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::TagNumberInvalid);
+        }
+
+        assert!(Tag::try_from(0x9F).is_err());
+    }
+
+    #[test]
+    fn high_tag_number_octet_conversions() {
+        let low = TagNumber::new(30).context_specific(false);
+        let high = TagNumber::new(31).context_specific(false);
+        assert_eq!(u8::try_from(low).unwrap(), 0x9E);
+        assert_eq!(
+            u8::try_from(high).unwrap_err().kind(),
+            ErrorKind::TagNumberInvalid
+        );
+
+        assert_eq!(TagNumber::try_from(30u8).unwrap(), TagNumber::N30);
+        assert!(TagNumber::try_from(31u8).is_err());
+        assert!(u8::try_from(TagNumber::new(31)).is_err());
+    }
+
+    #[test]
+    fn high_tag_number_der_ord() {
+        let low = Tag::ContextSpecific {
+            constructed: false,
+            number: TagNumber::new(30),
+        };
+        let high = Tag::ContextSpecific {
+            constructed: false,
+            number: TagNumber::new(31),
+        };
+        assert_eq!(low.der_cmp(&high).unwrap(), Ordering::Less);
+    }
 }
@@ -31,10 +31,10 @@ pub enum Class {
 }
 
 impl Class {
-    /// Compute the identifier octet for a tag number of this class.
+    /// Compute the leading identifier octet for a tag number of this class.
     #[allow(clippy::arithmetic_side_effects)]
     pub(super) fn octet(self, constructed: bool, number: TagNumber) -> u8 {
-        self as u8 | number.value() | (u8::from(constructed) * CONSTRUCTED_FLAG)
+        self as u8 | number.low_bits() | (u8::from(constructed) * CONSTRUCTED_FLAG)
     }
 }
 
@@ -5,6 +5,7 @@ use core::{fmt, str::FromStr};
 
 /// Tagging modes: `EXPLICIT` versus `IMPLICIT`.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TagMode {
     /// `EXPLICIT` tagging.
     ///
@@ -4,22 +4,22 @@ use super::Tag;
 use crate::{Error, ErrorKind, Result};
 use core::fmt;
 
-/// ASN.1 tag numbers (i.e. lower 5 bits of a [`Tag`]).
+/// ASN.1 tag numbers.
 ///
 /// From X.690 Section 8.1.2.2:
 ///
 /// > bits 5 to 1 shall encode the number of the tag as a binary integer with
 /// > bit 5 as the most significant bit.
 ///
-/// This library supports tag numbers ranging from zero to 30 (inclusive),
-/// which can be represented as a single identifier octet.
+/// Tag numbers ranging from zero to 30 (inclusive) are represented in the
+/// lower 5 bits of a single identifier octet.
 ///
-/// Section 8.1.2.4 describes how to support multi-byte tag numbers, which are
-/// encoded by using a leading tag number of 31 (`0b11111`). This library
-/// deliberately does not support this: tag numbers greater than 30 are
-/// disallowed.
+/// Section 8.1.2.4 describes the high tag number form used for tag numbers
+/// greater than 30, which is encoded by using a leading tag number of 31
+/// (`0b11111`) followed by the tag number in base 128 in subsequent octets.
+/// This library supports tag numbers up to `u32::MAX` in this form.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-pub struct TagNumber(pub(super) u8);
+pub struct TagNumber(pub(super) u32);
 
 impl TagNumber {
     /// Tag number `0`
@@ -118,20 +118,19 @@ impl TagNumber {
     /// Mask value used to obtain the tag number from a tag octet.
     pub(super) const MASK: u8 = 0b11111;
 
-    /// Maximum tag number supported (inclusive).
-    const MAX: u8 = 30;
+    /// Maximum tag number which can be encoded in a single identifier octet
+    /// (inclusive).
+    const MAX_LOW: u32 = 30;
 
     /// Create a new tag number (const-friendly).
-    ///
-    /// Panics if the tag number is greater than `30`.
-    /// For a fallible conversion, use [`TryFrom`] instead.
-    pub const fn new(byte: u8) -> Self {
-        #[allow(clippy::panic)]
-        if byte > Self::MAX {
-            panic!("tag number out of range");
-        }
+    pub const fn new(number: u32) -> Self {
+        Self(number)
+    }
 
-        Self(byte)
+    /// Is this tag number encoded using the high tag number form, i.e. does
+    /// it require more than one identifier octet?
+    pub fn is_high(self) -> bool {
+        self.0 > Self::MAX_LOW
     }
 
     /// Create an `APPLICATION` tag with this tag number.
@@ -158,29 +157,84 @@ impl TagNumber {
         }
     }
 
+    /// Get the tag number bits of the leading identifier octet, which are all
+    /// set when the high tag number form is used.
+    pub(super) fn low_bits(self) -> u8 {
+        if self.is_high() {
+            Self::MASK
+        } else {
+            u8::try_from(self.0).unwrap_or(Self::MASK)
+        }
+    }
+
+    /// Get the number of subsequent identifier octets used to encode this
+    /// tag number in the high tag number form (zero for the low form).
+    pub(super) fn high_len(self) -> u8 {
+        if !self.is_high() {
+            return 0;
+        }
+
+        // Each subsequent octet holds 7 bits of the tag number
+        let mut len = 1;
+        while len < 5 && self.0 >> (7 * len) != 0 {
+            len += 1;
+        }
+        len
+    }
+
     /// Get the inner value.
-    pub fn value(self) -> u8 {
+    pub fn get(self) -> u32 {
         self.0
     }
+
+    /// Get the inner value as the tag number bits of the leading identifier
+    /// octet.
+    ///
+    /// Tag numbers greater than 30 don't fit in these bits, in which case
+    /// this returns `31` (`0b11111`), the marker for the high tag number form.
+    #[deprecated(since = "0.8.0", note = "use `TagNumber::get` instead")]
+    pub fn value(self) -> u8 {
+        self.low_bits()
+    }
 }
 
 impl TryFrom<u8> for TagNumber {
     type Error = Error;
 
+    /// Convert a tag number which fits in a single identifier octet, i.e. is
+    /// `30` or less. Use `From<u32>` for tag numbers in the high form.
     fn try_from(byte: u8) -> Result<Self> {
-        match byte {
-            0..=Self::MAX => Ok(Self(byte)),
+        match u32::from(byte) {
+            number @ 0..=Self::MAX_LOW => Ok(Self(number)),
             _ => Err(ErrorKind::TagNumberInvalid.into()),
         }
     }
 }
 
-impl From<TagNumber> for u8 {
-    fn from(tag_number: TagNumber) -> u8 {
+impl From<u32> for TagNumber {
+    fn from(number: u32) -> Self {
+        Self(number)
+    }
+}
+
+impl From<TagNumber> for u32 {
+    fn from(tag_number: TagNumber) -> u32 {
         tag_number.0
     }
 }
 
+impl TryFrom<TagNumber> for u8 {
+    type Error = Error;
+
+    fn try_from(tag_number: TagNumber) -> Result<u8> {
+        if tag_number.is_high() {
+            Err(ErrorKind::TagNumberInvalid.into())
+        } else {
+            Ok(tag_number.low_bits())
+        }
+    }
+}
+
 impl fmt::Display for TagNumber {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -192,10 +246,10 @@ impl fmt::Display for TagNumber {
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for TagNumber {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        Ok(Self::new(u.int_in_range(0..=Self::MAX)?))
+        Ok(Self::new(u.arbitrary()?))
     }
 
     fn size_hint(depth: usize) -> (usize, Option<usize>) {
-        u8::size_hint(depth)
+        u32::size_hint(depth)
     }
 }
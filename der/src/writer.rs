@@ -3,8 +3,10 @@
 #[cfg(feature = "pem")]
 pub(crate) mod pem;
 pub(crate) mod slice;
+#[cfg(feature = "alloc")]
+pub(crate) mod vec;
 
-use crate::Result;
+use crate::{Encode, EncodeValue, Result, Tagged};
 
 #[cfg(feature = "std")]
 use std::io;
@@ -18,6 +20,20 @@ pub trait Writer {
     fn write_byte(&mut self, byte: u8) -> Result<()> {
         self.write(&[byte])
     }
+
+    /// Encode the given value as a Tag-Length-Value production.
+    ///
+    /// The default implementation computes the length of the value up-front
+    /// in order to write its [`Header`][`crate::Header`]. Writers may
+    /// override this in order to avoid doing so.
+    fn encode_tlv<T>(&mut self, value: &T) -> Result<()>
+    where
+        Self: Sized,
+        T: EncodeValue + Tagged,
+    {
+        value.header()?.encode(self)?;
+        value.encode_value(self)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -0,0 +1,157 @@
+//! Vector writer.
+
+use crate::{
+    length::MAX_DER_OCTETS, Encode, EncodeValue, ErrorKind, Header, Result, Tag, Tagged, Writer,
+};
+use alloc::vec::Vec;
+
+/// Maximum length of an encoded header: a tag of up to 6 octets followed by a
+/// length of up to 9 octets.
+const MAX_HEADER_LEN: usize = Tag::MAX_LEN + MAX_DER_OCTETS;
+
+/// [`Writer`] which encodes a message into a [`Vec`] in a single pass.
+///
+/// Computing the [`Header`] of a constructed value requires the lengths of all
+/// of its nested values, so encoding a message with the default strategy
+/// recomputes the length of every value once for each of its ancestors.
+///
+/// Instead, this writer reserves a placeholder large enough to hold any
+/// header before encoding a constructed value, and patches in the header once
+/// the length of the value is known. The unused parts of the placeholders are
+/// removed in a single pass by [`VecWriter::finish`].
+#[derive(Debug, Default)]
+pub(crate) struct VecWriter {
+    /// Encoded bytes, including any unused placeholder bytes.
+    bytes: Vec<u8>,
+
+    /// Unused placeholder bytes as `(position, len)` pairs.
+    gaps: Vec<(usize, usize)>,
+
+    /// Total number of unused placeholder bytes.
+    gaps_len: usize,
+}
+
+impl VecWriter {
+    /// Create a new [`VecWriter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finish encoding, returning the encoded message.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        if self.gaps.is_empty() {
+            return Ok(self.bytes);
+        }
+
+        self.gaps.sort_unstable();
+
+        let len = self
+            .bytes
+            .len()
+            .checked_sub(self.gaps_len)
+            .ok_or(ErrorKind::Overflow)?;
+
+        let mut out = Vec::with_capacity(len);
+        let mut pos = 0;
+
+        for (start, gap_len) in self.gaps {
+            out.extend_from_slice(self.bytes.get(pos..start).ok_or(ErrorKind::Overflow)?);
+            pos = start.checked_add(gap_len).ok_or(ErrorKind::Overflow)?;
+        }
+
+        out.extend_from_slice(self.bytes.get(pos..).ok_or(ErrorKind::Overflow)?);
+        Ok(out)
+    }
+}
+
+impl Writer for VecWriter {
+    fn write(&mut self, slice: &[u8]) -> Result<()> {
+        self.bytes.extend_from_slice(slice);
+        Ok(())
+    }
+
+    fn encode_tlv<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: EncodeValue + Tagged,
+    {
+        let tag = value.tag();
+
+        // The lengths of primitive values are cheap to compute
+        if !tag.is_constructed() {
+            value.header()?.encode(self)?;
+            return value.encode_value(self);
+        }
+
+        let start = self.bytes.len();
+        let value_start = start
+            .checked_add(MAX_HEADER_LEN)
+            .ok_or(ErrorKind::Overflow)?;
+        let gaps_len = self.gaps_len;
+
+        self.bytes.resize(value_start, 0);
+        value.encode_value(self)?;
+
+        // Exclude the unused placeholder bytes of any nested values
+        let value_len = self
+            .bytes
+            .len()
+            .checked_sub(value_start)
+            .and_then(|len| len.checked_sub(self.gaps_len.checked_sub(gaps_len)?))
+            .ok_or(ErrorKind::Overflow)?;
+
+        let mut buf = [0u8; MAX_HEADER_LEN];
+        let header = Header::new(tag, value_len)?.encode_to_slice(&mut buf)?;
+        let unused = MAX_HEADER_LEN
+            .checked_sub(header.len())
+            .ok_or(ErrorKind::Overflow)?;
+
+        self.bytes
+            .get_mut(start.checked_add(unused).ok_or(ErrorKind::Overflow)?..value_start)
+            .ok_or(ErrorKind::Overflow)?
+            .copy_from_slice(header);
+
+        if unused > 0 {
+            self.gaps.push((start, unused));
+            self.gaps_len = self
+                .gaps_len
+                .checked_add(unused)
+                .ok_or(ErrorKind::Overflow)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::VecWriter;
+    use crate::{
+        asn1::{OctetStringRef, SequenceOf},
+        Encode, SliceWriter,
+    };
+    use alloc::vec;
+
+    #[test]
+    fn nested_sequences() {
+        type Nested<'a> = SequenceOf<SequenceOf<OctetStringRef<'a>, 2>, 2>;
+
+        let long = vec![0xAA; 300];
+        let mut inner = SequenceOf::new();
+        inner.add(OctetStringRef::new(&[1, 2, 3]).unwrap()).unwrap();
+        inner.add(OctetStringRef::new(&long).unwrap()).unwrap();
+
+        let mut outer: Nested<'_> = SequenceOf::new();
+        outer.add(SequenceOf::new()).unwrap();
+        outer.add(inner).unwrap();
+
+        let mut writer = VecWriter::new();
+        outer.encode(&mut writer).unwrap();
+        let actual = writer.finish().unwrap();
+
+        let mut buf = vec![0u8; 512];
+        let mut writer = SliceWriter::new(&mut buf);
+        outer.encode(&mut writer).unwrap();
+        assert_eq!(actual, writer.finish().unwrap());
+    }
+}
@@ -461,3 +461,363 @@ mod sequence {
         );
     }
 }
+
+/// Custom derive test cases using the heapless `SequenceOf` and `SetOf` types
+/// in place of `Vec` and `SetOfVec`.
+mod heapless {
+    use der::{
+        asn1::{SequenceOf, SetOf},
+        Decode, Encode, Sequence, ValueOrd,
+    };
+    use hex_literal::hex;
+
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+    pub struct Bounded {
+        pub values: SequenceOf<u8, 4>,
+        #[asn1(context_specific = "0", optional = "true")]
+        pub flags: Option<SetOf<u8, 4>>,
+    }
+
+    const BOUNDED_DER: &[u8] = &hex!("300f 3006 020101 020102 a005 3103 020103");
+
+    #[test]
+    fn round_trip() {
+        let bounded = Bounded {
+            values: SequenceOf::from_iter([1, 2]).unwrap(),
+            flags: Some(SetOf::from_iter([3]).unwrap()),
+        };
+
+        let mut buf = [0u8; 32];
+        assert_eq!(BOUNDED_DER, bounded.encode_to_slice(&mut buf).unwrap());
+        assert_eq!(Bounded::from_der(BOUNDED_DER).unwrap(), bounded);
+    }
+
+    #[test]
+    fn capacity_exceeded() {
+        // `values` contains five elements
+        let der = hex!("3011 300f 020101 020102 020103 020104 020105");
+        assert!(Bounded::from_der(&der).is_err());
+    }
+}
+
+/// Custom derive test cases for the `ValueOrd` macro.
+mod value_ord {
+    use core::cmp::Ordering;
+    use der::{asn1::SetOfVec, Decode, DerOrd, Encode, Sequence, ValueOrd};
+
+    /// Compared field-by-field.
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence, DerOrd)]
+    pub struct Counted {
+        pub id: u8,
+        pub name: bool,
+        #[asn1(skip_ord)]
+        pub hits: u8,
+    }
+
+    /// Compared by complete encodings.
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence, DerOrd)]
+    pub struct Entry {
+        pub id: u8,
+        #[asn1(default = "flag_default")]
+        pub flag: bool,
+        #[asn1(context_specific = "0", optional = "true")]
+        pub label: Option<u8>,
+    }
+
+    fn flag_default() -> bool {
+        false
+    }
+
+    fn entry(id: u8, flag: bool, label: Option<u8>) -> Entry {
+        Entry { id, flag, label }
+    }
+
+    fn byte_cmp<T: Encode>(a: &T, b: &T) -> Ordering {
+        a.to_der().unwrap().cmp(&b.to_der().unwrap())
+    }
+
+    #[test]
+    fn skip_ord() {
+        let a = Counted {
+            id: 1,
+            name: false,
+            hits: 1,
+        };
+        let b = Counted {
+            hits: 2,
+            ..a.clone()
+        };
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Equal);
+        assert_eq!(a.der_cmp(&b).unwrap(), Ordering::Equal);
+    }
+
+    #[test]
+    fn field_order() {
+        let a = Counted {
+            id: 1,
+            name: true,
+            hits: 0,
+        };
+        let b = Counted {
+            id: 2,
+            name: false,
+            hits: 0,
+        };
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Less);
+        assert_eq!(b.value_cmp(&a).unwrap(), Ordering::Greater);
+    }
+
+    #[test]
+    fn default_fields() {
+        // `flag` is omitted from the encoding when it's `false`
+        let a = entry(1, false, None);
+        let b = entry(1, true, None);
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Less);
+        assert_eq!(b.value_cmp(&a).unwrap(), Ordering::Greater);
+    }
+
+    #[test]
+    fn omitted_default_fields() {
+        // `flag` is omitted from the encoding, so it decodes to its default
+        let a = entry(1, false, None);
+        let b = Entry::from_der(&a.to_der().unwrap()).unwrap();
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Equal);
+        assert_eq!(b.der_cmp(&entry(1, true, None)).unwrap(), Ordering::Less);
+    }
+
+    #[test]
+    fn optional_fields() {
+        let a = entry(1, false, Some(2));
+        let b = entry(1, false, Some(3));
+        assert_eq!(a.value_cmp(&b).unwrap(), Ordering::Less);
+        assert_eq!(entry(1, false, None).value_cmp(&a).unwrap(), Ordering::Less);
+    }
+
+    #[test]
+    fn equal_length_encodings() {
+        // `020101 800102` vs `020101 0101FF`: the omitted `flag` shifts `label`
+        let a = entry(1, false, Some(2));
+        let b = entry(1, true, None);
+        assert_eq!(a.to_der().unwrap().len(), b.to_der().unwrap().len());
+        assert_eq!(byte_cmp(&a, &b), Ordering::Greater);
+        assert_eq!(a.der_cmp(&b).unwrap(), Ordering::Greater);
+        assert_eq!(b.der_cmp(&a).unwrap(), Ordering::Less);
+
+        let set = SetOfVec::try_from(vec![a.clone(), b.clone()]).unwrap();
+        assert_eq!(set.as_slice(), [b, a]);
+    }
+}
+
+/// Error breadcrumbs recorded by the `Sequence` macro.
+#[cfg(feature = "error-context")]
+mod error_context {
+    use der::{Decode, ErrorKind, ErrorPathSegment, Sequence, Tag};
+    use hex_literal::hex;
+
+    #[derive(Debug, Sequence)]
+    pub struct Inner {
+        pub version: u8,
+        pub flag: bool,
+    }
+
+    #[derive(Debug, Sequence)]
+    pub struct Outer {
+        pub id: u8,
+        pub inner: Inner,
+    }
+
+    #[test]
+    fn field_path() {
+        // `inner.flag` is an INTEGER instead of a BOOLEAN
+        let err = Outer::from_der(&hex!("300B 020101 3006 020101 020101")).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(Tag::Boolean),
+                actual: Tag::Integer
+            }
+        );
+
+        let path = err.path();
+        assert_eq!(path.type_name(), Some("Outer"));
+        assert!(path.fields().eq(["inner", "flag"]));
+        assert_eq!(path.to_string(), "Outer.inner.flag");
+        assert!(err.to_string().ends_with(" in Outer.inner.flag"));
+    }
+
+    #[derive(Debug, Sequence)]
+    pub struct List {
+        pub items: Vec<Inner>,
+    }
+
+    #[test]
+    fn element_path() {
+        // `items[1].flag` is an INTEGER instead of a BOOLEAN
+        let err =
+            List::from_der(&hex!("3012 3010 3006 020101 0101FF 3006 020101 020101")).unwrap_err();
+
+        let path = err.path();
+        assert_eq!(path.type_name(), Some("List"));
+        assert!(path.segments().eq([
+            ErrorPathSegment::Field("items"),
+            ErrorPathSegment::Index(1),
+            ErrorPathSegment::Field("flag")
+        ]));
+        assert_eq!(path.to_string(), "List.items[1].flag");
+    }
+
+    #[derive(Debug, Sequence)]
+    pub struct Renamed {
+        #[asn1(rename = "serialNumber")]
+        pub serial_number: u8,
+    }
+
+    #[test]
+    fn renamed_field_path() {
+        let err = Renamed::from_der(&hex!("3003 010100")).unwrap_err();
+        assert_eq!(err.path().to_string(), "Renamed.serialNumber");
+    }
+}
+
+/// Custom derive test cases for `APPLICATION` and `PRIVATE` class tags.
+mod tag_class {
+    use der::{Choice, Decode, Encode, Sequence};
+    use hex_literal::hex;
+
+    /// `Sequence` containing fields with `APPLICATION` and `PRIVATE` tags.
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct TagClassExample {
+        #[asn1(tag_class = "APPLICATION", tag_number = "0")]
+        pub explicit: u8,
+
+        #[asn1(
+            tag_class = "APPLICATION",
+            tag_number = "1",
+            tag_mode = "IMPLICIT",
+            optional = "true"
+        )]
+        pub implicit_optional: Option<bool>,
+
+        #[asn1(tag_class = "PRIVATE", tag_number = "2", tag_mode = "IMPLICIT")]
+        pub private: u8,
+    }
+
+    /// `Choice` with `APPLICATION` and `PRIVATE` tagged variants.
+    #[derive(Choice, Debug, Eq, PartialEq)]
+    #[asn1(tag_mode = "IMPLICIT")]
+    pub enum TagClassChoice {
+        #[asn1(tag_class = "APPLICATION", tag_number = "3", type = "UTF8String")]
+        Application(String),
+
+        #[asn1(tag_class = "PRIVATE", tag_number = "3", type = "UTF8String")]
+        Private(String),
+    }
+
+    const EXAMPLE_DER: &[u8] = &hex!("300B 6003020101 4101FF C20102");
+
+    #[test]
+    fn sequence_round_trip() {
+        let example = TagClassExample::from_der(EXAMPLE_DER).unwrap();
+        assert_eq!(
+            example,
+            TagClassExample {
+                explicit: 1,
+                implicit_optional: Some(true),
+                private: 2,
+            }
+        );
+        assert_eq!(example.to_der().unwrap(), EXAMPLE_DER);
+
+        // Optional field omitted
+        let der = hex!("3008 6003020101 C20102");
+        let example = TagClassExample::from_der(&der).unwrap();
+        assert_eq!(example.implicit_optional, None);
+        assert_eq!(example.to_der().unwrap(), der);
+    }
+
+    #[test]
+    fn choice_round_trip() {
+        let application = TagClassChoice::from_der(&hex!("4302 6869")).unwrap();
+        assert_eq!(application, TagClassChoice::Application("hi".into()));
+        assert_eq!(application.to_der().unwrap(), hex!("4302 6869"));
+
+        let private = TagClassChoice::from_der(&hex!("C302 6869")).unwrap();
+        assert_eq!(private, TagClassChoice::Private("hi".into()));
+        assert_eq!(private.to_der().unwrap(), hex!("C302 6869"));
+    }
+}
+
+/// Custom per-field encode/decode hooks.
+mod custom_hooks {
+    use der::{Decode, Encode, Reader, Sequence};
+    use hex_literal::hex;
+
+    /// Flags which are encoded as a two-bit `BIT STRING`.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub struct Flags {
+        pub first: bool,
+        pub second: bool,
+    }
+
+    /// Hooks for use with `#[asn1(with = "...")]`.
+    mod flags_as_bit_string {
+        use super::Flags;
+        use der::{asn1::BitString, Decode, Reader};
+
+        pub fn decode<'a, R: Reader<'a>>(reader: &mut R) -> der::Result<Flags> {
+            let bit_string = BitString::decode(reader)?;
+            let mut bits = bit_string.bits();
+
+            Ok(Flags {
+                first: bits.next().unwrap_or_default(),
+                second: bits.next().unwrap_or_default(),
+            })
+        }
+
+        pub fn encode(flags: &Flags) -> der::Result<BitString> {
+            let byte = (u8::from(flags.first) << 7) | (u8::from(flags.second) << 6);
+            BitString::new(6, [byte])
+        }
+    }
+
+    /// Decode a version number which is encoded as one less than its value.
+    fn decode_version<'a, R: Reader<'a>>(reader: &mut R) -> der::Result<u8> {
+        u8::decode(reader)?
+            .checked_add(1)
+            .ok_or_else(|| der::Tag::Integer.value_error())
+    }
+
+    /// Encode a version number as one less than its value.
+    fn encode_version(version: &u8) -> der::Result<u8> {
+        version
+            .checked_sub(1)
+            .ok_or_else(|| der::Tag::Integer.value_error())
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct CustomHooksExample {
+        #[asn1(with = "flags_as_bit_string")]
+        pub flags: Flags,
+
+        #[asn1(decode_with = "decode_version", encode_with = "encode_version")]
+        pub version: u8,
+    }
+
+    const EXAMPLE_DER: &[u8] = &hex!("3007 030206C0 020102");
+
+    #[test]
+    fn round_trip() {
+        let example = CustomHooksExample::from_der(EXAMPLE_DER).unwrap();
+        assert_eq!(
+            example,
+            CustomHooksExample {
+                flags: Flags {
+                    first: true,
+                    second: true,
+                },
+                version: 3,
+            }
+        );
+        assert_eq!(example.to_der().unwrap(), EXAMPLE_DER);
+    }
+}
@@ -5,7 +5,7 @@
 use der::{
     asn1::{BitString, ObjectIdentifier},
     pem::{LineEnding, PemLabel},
-    Decode, DecodePem, EncodePem, Sequence,
+    Decode, DecodePem, EncodePem, ErrorKind, PemReader, Sequence,
 };
 
 /// Example SPKI document encoded as DER.
@@ -65,3 +65,40 @@ fn to_pem() {
     let pem = spki.to_pem(LineEnding::LF).unwrap();
     assert_eq!(&pem, SPKI_PEM);
 }
+
+#[test]
+fn decode_pem_with_custom_labels() {
+    let spki = SpkiOwned::decode_pem("PUBLIC KEY", SPKI_PEM).unwrap();
+    assert_eq!(spki, SpkiOwned::from_pem(SPKI_PEM).unwrap());
+
+    let labels = ["SUBJECT PUBLIC KEY INFO", "PUBLIC KEY"];
+    let spki2 = SpkiOwned::decode_pem_with_labels(&labels, SPKI_PEM).unwrap();
+    assert_eq!(spki, spki2);
+
+    let err = SpkiOwned::decode_pem("PRIVATE KEY", SPKI_PEM).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        der::ErrorKind::Pem(der::pem::Error::UnexpectedTypeLabel {
+            expected: "PRIVATE KEY"
+        })
+    );
+
+    let (label, spki3) = SpkiOwned::decode_pem_with_any_label(SPKI_PEM).unwrap();
+    assert_eq!(label, "PUBLIC KEY");
+    assert_eq!(spki, spki3);
+}
+
+#[test]
+fn max_depth() {
+    // `SubjectPublicKeyInfo` contains a nested `AlgorithmIdentifier`
+    let mut reader = PemReader::new(SPKI_PEM.as_bytes())
+        .unwrap()
+        .with_max_depth(2);
+    assert!(SpkiOwned::decode(&mut reader).is_ok());
+
+    let mut reader = PemReader::new(SPKI_PEM.as_bytes())
+        .unwrap()
+        .with_max_depth(1);
+    let err = SpkiOwned::decode(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DepthExceeded { max_depth: 1 });
+}
@@ -2,16 +2,22 @@
 
 use crate::{Error, Result};
 use der::{
-    asn1::{AnyRef, ContextSpecificRef, ObjectIdentifier},
+    asn1::{AnyRef, ContextSpecificRef, ObjectIdentifier, OctetStringRef},
     oid::AssociatedOid,
-    Decode, DecodeValue, Encode, EncodeValue, FixedTag, Length, Reader, Sequence, Tag, TagMode,
-    TagNumber, Writer,
+    Decode, DecodeValue, Encode, EncodeValue, ErrorKind, FixedTag, Length, Reader, Sequence, Tag,
+    TagMode, TagNumber, Writer,
 };
 use spki::{AlgorithmIdentifier, AlgorithmIdentifierRef};
 
 const OID_SHA_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+const OID_SHA_224: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.4");
+const OID_SHA_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const OID_SHA_384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const OID_SHA_512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
 const OID_MGF_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.8");
 const OID_PSPECIFIED: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.9");
+const OID_RSAES_OAEP: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.7");
+const OID_RSASSA_PSS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
 
 const SHA_1_AI: AlgorithmIdentifierRef<'_> = AlgorithmIdentifierRef {
     oid: OID_SHA_1,
@@ -100,18 +106,61 @@ impl<'a> RsaPssParams<'a> {
     where
         D: AssociatedOid,
     {
+        Self::with_hash(D::OID, salt_len)
+    }
+
+    /// Create new RsaPssParams for SHA-224, MGF1 with SHA-224 and a 28-byte salt
+    pub fn sha224() -> Self {
+        Self::with_hash(OID_SHA_224, 28)
+    }
+
+    /// Create new RsaPssParams for SHA-256, MGF1 with SHA-256 and a 32-byte salt
+    pub fn sha256() -> Self {
+        Self::with_hash(OID_SHA_256, 32)
+    }
+
+    /// Create new RsaPssParams for SHA-384, MGF1 with SHA-384 and a 48-byte salt
+    pub fn sha384() -> Self {
+        Self::with_hash(OID_SHA_384, 48)
+    }
+
+    /// Create new RsaPssParams for SHA-512, MGF1 with SHA-512 and a 64-byte salt
+    pub fn sha512() -> Self {
+        Self::with_hash(OID_SHA_512, 64)
+    }
+
+    /// Get the [`ObjectIdentifier`] of the hash algorithm
+    pub fn hash_oid(&self) -> ObjectIdentifier {
+        self.hash.oid
+    }
+
+    /// Get the [`ObjectIdentifier`] of the hash algorithm of MGF1, or `None`
+    /// if the mask generation function isn't MGF1
+    pub fn mgf1_hash_oid(&self) -> Option<ObjectIdentifier> {
+        mgf1_hash_oid(&self.mask_gen)
+    }
+
+    /// Are these the default parameters, i.e. SHA-1, MGF1 with SHA-1, a
+    /// 20-byte salt and [`TrailerField::BC`]?
+    pub fn is_default(&self) -> bool {
+        self.is_equivalent(&Self::default())
+    }
+
+    /// Are these parameters equivalent to `other`?
+    ///
+    /// Unlike `==`, hash algorithms with absent and with NULL parameters are
+    /// considered equal, as both encodings are found in the wild.
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        is_same_hash(&self.hash, &other.hash)
+            && is_same_mask_gen(&self.mask_gen, &other.mask_gen)
+            && self.salt_len == other.salt_len
+            && self.trailer_field == other.trailer_field
+    }
+
+    fn with_hash(oid: ObjectIdentifier, salt_len: u8) -> Self {
         Self {
-            hash: AlgorithmIdentifierRef {
-                oid: D::OID,
-                parameters: Some(AnyRef::NULL),
-            },
-            mask_gen: AlgorithmIdentifier {
-                oid: OID_MGF_1,
-                parameters: Some(AlgorithmIdentifierRef {
-                    oid: D::OID,
-                    parameters: Some(AnyRef::NULL),
-                }),
-            },
+            hash: hash_algorithm(oid),
+            mask_gen: mgf1(oid),
             salt_len,
             trailer_field: Default::default(),
         }
@@ -229,6 +278,21 @@ impl<'a> TryFrom<&'a [u8]> for RsaPssParams<'a> {
     }
 }
 
+impl<'a> TryFrom<AlgorithmIdentifierRef<'a>> for RsaPssParams<'a> {
+    type Error = Error;
+
+    /// Get the parameters of an `id-RSASSA-PSS` algorithm identifier, e.g. of
+    /// the signature algorithm of a certificate
+    fn try_from(alg: AlgorithmIdentifierRef<'a>) -> Result<Self> {
+        Ok(algorithm_parameters(alg, OID_RSASSA_PSS)?.decode_as()?)
+    }
+}
+
+impl AssociatedOid for RsaPssParams<'_> {
+    /// `id-RSASSA-PSS`
+    const OID: ObjectIdentifier = OID_RSASSA_PSS;
+}
+
 /// Default Mask Generation Function (MGF): SHA-1.
 fn default_mgf1_sha1<'a>() -> AlgorithmIdentifier<AlgorithmIdentifierRef<'a>> {
     AlgorithmIdentifier::<AlgorithmIdentifierRef<'a>> {
@@ -237,6 +301,67 @@ fn default_mgf1_sha1<'a>() -> AlgorithmIdentifier<AlgorithmIdentifierRef<'a>> {
     }
 }
 
+/// Hash algorithm identified by `oid`, with NULL parameters
+fn hash_algorithm<'a>(oid: ObjectIdentifier) -> AlgorithmIdentifierRef<'a> {
+    AlgorithmIdentifierRef {
+        oid,
+        parameters: Some(AnyRef::NULL),
+    }
+}
+
+/// Mask Generation Function (MGF) MGF1 with the hash algorithm identified by `oid`
+fn mgf1<'a>(oid: ObjectIdentifier) -> AlgorithmIdentifier<AlgorithmIdentifierRef<'a>> {
+    AlgorithmIdentifier {
+        oid: OID_MGF_1,
+        parameters: Some(hash_algorithm(oid)),
+    }
+}
+
+fn mgf1_hash_oid(
+    mask_gen: &AlgorithmIdentifier<AlgorithmIdentifierRef<'_>>,
+) -> Option<ObjectIdentifier> {
+    if mask_gen.oid == OID_MGF_1 {
+        mask_gen.parameters.as_ref().map(|hash| hash.oid)
+    } else {
+        None
+    }
+}
+
+/// Compares hash algorithms, considering absent and NULL parameters equal
+fn is_same_hash(a: &AlgorithmIdentifierRef<'_>, b: &AlgorithmIdentifierRef<'_>) -> bool {
+    let is_null =
+        |parameters: Option<AnyRef<'_>>| parameters.is_none() || parameters == Some(AnyRef::NULL);
+
+    a.oid == b.oid
+        && (a.parameters == b.parameters || (is_null(a.parameters) && is_null(b.parameters)))
+}
+
+/// Compares mask generation functions, considering absent and NULL parameters of
+/// the hash algorithm of MGF1 equal
+fn is_same_mask_gen(
+    a: &AlgorithmIdentifier<AlgorithmIdentifierRef<'_>>,
+    b: &AlgorithmIdentifier<AlgorithmIdentifierRef<'_>>,
+) -> bool {
+    a.oid == b.oid
+        && match (&a.parameters, &b.parameters) {
+            (Some(a), Some(b)) => is_same_hash(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+}
+
+/// Get the parameters of `alg`, which must be identified by `oid`
+fn algorithm_parameters<'a>(
+    alg: AlgorithmIdentifierRef<'a>,
+    oid: ObjectIdentifier,
+) -> der::Result<AnyRef<'a>> {
+    if alg.oid != oid {
+        return Err(ErrorKind::OidUnknown { oid: alg.oid }.into());
+    }
+
+    alg.parameters.ok_or_else(|| Tag::Sequence.value_error())
+}
+
 /// PKCS#1 RSAES-OAEP parameters as defined in [RFC 8017 Appendix 2.1]
 ///
 /// ASN.1 structure containing a serialized RSAES-OAEP parameters:
@@ -279,21 +404,84 @@ impl<'a> RsaOaepParams<'a> {
         D: AssociatedOid,
     {
         Self {
-            hash: AlgorithmIdentifierRef {
-                oid: D::OID,
-                parameters: Some(AnyRef::NULL),
-            },
-            mask_gen: AlgorithmIdentifier {
-                oid: OID_MGF_1,
-                parameters: Some(AlgorithmIdentifierRef {
-                    oid: D::OID,
-                    parameters: Some(AnyRef::NULL),
-                }),
-            },
+            hash: hash_algorithm(D::OID),
+            mask_gen: mgf1(D::OID),
             p_source: pspecicied_algorithm_identifier(label),
         }
     }
 
+    /// Create new RsaOaepParams for SHA-224, MGF1 with SHA-224 and the default
+    /// (empty) label
+    pub fn sha224() -> Self {
+        Self::with_hash(OID_SHA_224)
+    }
+
+    /// Create new RsaOaepParams for SHA-256, MGF1 with SHA-256 and the default
+    /// (empty) label
+    pub fn sha256() -> Self {
+        Self::with_hash(OID_SHA_256)
+    }
+
+    /// Create new RsaOaepParams for SHA-384, MGF1 with SHA-384 and the default
+    /// (empty) label
+    pub fn sha384() -> Self {
+        Self::with_hash(OID_SHA_384)
+    }
+
+    /// Create new RsaOaepParams for SHA-512, MGF1 with SHA-512 and the default
+    /// (empty) label
+    pub fn sha512() -> Self {
+        Self::with_hash(OID_SHA_512)
+    }
+
+    /// Get the [`ObjectIdentifier`] of the hash algorithm
+    pub fn hash_oid(&self) -> ObjectIdentifier {
+        self.hash.oid
+    }
+
+    /// Get the [`ObjectIdentifier`] of the hash algorithm of MGF1, or `None`
+    /// if the mask generation function isn't MGF1
+    pub fn mgf1_hash_oid(&self) -> Option<ObjectIdentifier> {
+        mgf1_hash_oid(&self.mask_gen)
+    }
+
+    /// Get the label L, or `None` if its source isn't `id-pSpecified`
+    pub fn label(&self) -> Option<&'a [u8]> {
+        if self.p_source.oid != OID_PSPECIFIED {
+            return None;
+        }
+
+        self.p_source
+            .parameters?
+            .decode_as::<OctetStringRef<'a>>()
+            .ok()
+            .map(|label| label.as_bytes())
+    }
+
+    /// Are these the default parameters, i.e. SHA-1, MGF1 with SHA-1 and an
+    /// empty label?
+    pub fn is_default(&self) -> bool {
+        self.is_equivalent(&Self::default())
+    }
+
+    /// Are these parameters equivalent to `other`?
+    ///
+    /// Unlike `==`, hash algorithms with absent and with NULL parameters are
+    /// considered equal, as both encodings are found in the wild.
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        is_same_hash(&self.hash, &other.hash)
+            && is_same_mask_gen(&self.mask_gen, &other.mask_gen)
+            && self.p_source == other.p_source
+    }
+
+    fn with_hash(oid: ObjectIdentifier) -> Self {
+        Self {
+            hash: hash_algorithm(oid),
+            mask_gen: mgf1(oid),
+            p_source: default_pempty_string(),
+        }
+    }
+
     fn context_specific_hash(&self) -> Option<ContextSpecificRef<'_, AlgorithmIdentifierRef<'a>>> {
         if self.hash == SHA_1_AI {
             None
@@ -389,6 +577,21 @@ impl<'a> TryFrom<&'a [u8]> for RsaOaepParams<'a> {
     }
 }
 
+impl<'a> TryFrom<AlgorithmIdentifierRef<'a>> for RsaOaepParams<'a> {
+    type Error = Error;
+
+    /// Get the parameters of an `id-RSAES-OAEP` algorithm identifier, e.g. of
+    /// the key encryption algorithm of a CMS `KeyTransRecipientInfo`
+    fn try_from(alg: AlgorithmIdentifierRef<'a>) -> Result<Self> {
+        Ok(algorithm_parameters(alg, OID_RSAES_OAEP)?.decode_as()?)
+    }
+}
+
+impl AssociatedOid for RsaOaepParams<'_> {
+    /// `id-RSAES-OAEP`
+    const OID: ObjectIdentifier = OID_RSAES_OAEP;
+}
+
 fn pspecicied_algorithm_identifier(label: &impl AsRef<[u8]>) -> AlgorithmIdentifierRef<'_> {
     AlgorithmIdentifierRef {
         oid: OID_PSPECIFIED,
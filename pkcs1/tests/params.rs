@@ -8,12 +8,16 @@ use der::{
 };
 use hex_literal::hex;
 use pkcs1::{RsaOaepParams, RsaPssParams, TrailerField};
+use spki::AlgorithmIdentifierRef;
 
 /// Default PSS parameters using all default values (SHA1, MGF1)
 const RSA_PSS_PARAMETERS_DEFAULTS: &[u8] = &hex!("3000");
 /// Example PSS parameters using SHA256 instead of SHA1
 const RSA_PSS_PARAMETERS_SHA2_256: &[u8] = &hex!("3034a00f300d06096086480165030402010500a11c301a06092a864886f70d010108300d06096086480165030402010500a203020120");
 
+/// Default PSS parameters with an explicit SHA1 hash algorithm lacking the NULL parameters
+const RSA_PSS_PARAMETERS_SHA1_ABSENT_PARAMS: &[u8] = &hex!("300ba009300706052b0e03021a");
+
 /// Default OAEP parameters using all default values (SHA1, MGF1, Empty)
 const RSA_OAEP_PARAMETERS_DEFAULTS: &[u8] = &hex!("3000");
 /// Example OAEP parameters using SHA256 instead of SHA1
@@ -221,3 +225,120 @@ fn new_oaep_param() {
         RSA_OAEP_PARAMETERS_SHA2_256
     );
 }
+
+#[test]
+fn pss_param_constructors() {
+    let mut buf = [0_u8; 256];
+
+    let param = RsaPssParams::sha256();
+    assert_eq!(param, RsaPssParams::new::<Sha256Mock>(32));
+    assert_eq!(
+        param.encode_to_slice(&mut buf).unwrap(),
+        RSA_PSS_PARAMETERS_SHA2_256
+    );
+
+    for (param, oid, salt_len) in [
+        (RsaPssParams::sha224(), db::rfc5912::ID_SHA_224, 28),
+        (RsaPssParams::sha384(), db::rfc5912::ID_SHA_384, 48),
+        (RsaPssParams::sha512(), db::rfc5912::ID_SHA_512, 64),
+    ] {
+        assert_eq!(param.hash_oid(), oid);
+        assert_eq!(param.mgf1_hash_oid(), Some(oid));
+        assert_eq!(param.salt_len, salt_len);
+        assert!(!param.is_default());
+    }
+}
+
+#[test]
+fn pss_param_sha1_absent_params() {
+    let mut buf = [0_u8; 256];
+    let param = RsaPssParams::try_from(RSA_PSS_PARAMETERS_SHA1_ABSENT_PARAMS).unwrap();
+
+    assert_eq!(param.hash.parameters, None);
+    assert_ne!(param, RsaPssParams::default());
+    assert!(param.is_default());
+    assert!(param.is_equivalent(&RsaPssParams::new::<Sha1Mock>(20)));
+    assert!(!param.is_equivalent(&RsaPssParams::new::<Sha1Mock>(32)));
+    assert_eq!(
+        param.encode_to_slice(&mut buf).unwrap(),
+        RSA_PSS_PARAMETERS_SHA1_ABSENT_PARAMS
+    );
+}
+
+#[test]
+fn pss_param_from_algorithm_identifier() {
+    let alg = AlgorithmIdentifierRef {
+        oid: db::rfc5912::ID_RSASSA_PSS,
+        parameters: Some(AnyRef::try_from(RSA_PSS_PARAMETERS_SHA2_256).unwrap()),
+    };
+    assert_eq!(RsaPssParams::OID, db::rfc5912::ID_RSASSA_PSS);
+    assert_eq!(RsaPssParams::try_from(alg).unwrap(), RsaPssParams::sha256());
+
+    let alg = AlgorithmIdentifierRef {
+        oid: db::rfc5912::ID_RSAES_OAEP,
+        ..alg
+    };
+    assert!(RsaPssParams::try_from(alg).is_err());
+
+    let alg = AlgorithmIdentifierRef {
+        oid: db::rfc5912::ID_RSASSA_PSS,
+        parameters: None,
+    };
+    assert!(RsaPssParams::try_from(alg).is_err());
+}
+
+#[test]
+fn oaep_param_constructors() {
+    let mut buf = [0_u8; 256];
+
+    let param = RsaOaepParams::sha256();
+    assert_eq!(param, RsaOaepParams::new::<Sha256Mock>());
+    assert_eq!(
+        param.encode_to_slice(&mut buf).unwrap(),
+        RSA_OAEP_PARAMETERS_SHA2_256
+    );
+
+    for (param, oid) in [
+        (RsaOaepParams::sha224(), db::rfc5912::ID_SHA_224),
+        (RsaOaepParams::sha384(), db::rfc5912::ID_SHA_384),
+        (RsaOaepParams::sha512(), db::rfc5912::ID_SHA_512),
+    ] {
+        assert_eq!(param.hash_oid(), oid);
+        assert_eq!(param.mgf1_hash_oid(), Some(oid));
+        assert_eq!(param.label(), Some(&[][..]));
+        assert!(!param.is_default());
+    }
+
+    assert!(RsaOaepParams::new::<Sha1Mock>().is_default());
+}
+
+#[test]
+fn oaep_param_label() {
+    let mut buf = [0_u8; 256];
+    let label = b"label";
+
+    let param = RsaOaepParams::new_with_label::<Sha256Mock>(label);
+    assert_eq!(param.label(), Some(&label[..]));
+    assert!(!param.is_equivalent(&RsaOaepParams::sha256()));
+
+    let encoded = param.encode_to_slice(&mut buf).unwrap();
+    let decoded = RsaOaepParams::try_from(&*encoded).unwrap();
+    assert_eq!(decoded.label(), Some(&label[..]));
+    assert!(decoded.is_equivalent(&param));
+}
+
+#[test]
+fn oaep_param_from_algorithm_identifier() {
+    let alg = AlgorithmIdentifierRef {
+        oid: db::rfc5912::ID_RSAES_OAEP,
+        parameters: Some(AnyRef::try_from(RSA_OAEP_PARAMETERS_DEFAULTS).unwrap()),
+    };
+    assert_eq!(RsaOaepParams::OID, db::rfc5912::ID_RSAES_OAEP);
+    assert!(RsaOaepParams::try_from(alg).unwrap().is_default());
+
+    let alg = AlgorithmIdentifierRef {
+        oid: db::rfc5912::ID_RSASSA_PSS,
+        ..alg
+    };
+    assert!(RsaOaepParams::try_from(alg).is_err());
+}
@@ -2,6 +2,13 @@
 
 use alloc::vec::Vec;
 use cms::content_info::ContentInfo;
+use cms::encrypted_data::EncryptedData;
+use cms::enveloped_data::EnvelopedData;
+use const_oid::db::rfc5911::{ID_DATA, ID_ENCRYPTED_DATA, ID_ENVELOPED_DATA};
+use der::asn1::{Any, OctetString};
+use der::{Decode, Encode, ErrorKind};
+
+use crate::safe_bag::SafeContents;
 
 /// The `AuthenticatedSafe` type is defined in [RFC 7292 Section 4.1].
 ///
@@ -14,3 +21,64 @@ use cms::content_info::ContentInfo;
 ///
 /// [RFC 7292 Section 4.1]: https://www.rfc-editor.org/rfc/rfc7292#section-4.1
 pub type AuthenticatedSafe<'a> = Vec<ContentInfo>;
+
+/// A `ContentInfo` of an [`AuthenticatedSafe`], decoded according to its content type.
+///
+/// Only the SafeContents of the `Data` variant are readily available, the other variants
+/// carry the encrypted encoding of the SafeContents.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SafeContentsInfo {
+    /// Unencrypted SafeContents
+    Data(SafeContents),
+
+    /// Password-encrypted SafeContents
+    EncryptedData(EncryptedData),
+
+    /// Public key-encrypted SafeContents
+    EnvelopedData(EnvelopedData),
+}
+
+impl SafeContentsInfo {
+    /// Returns the `ContentInfo` of this value, for inclusion in an [`AuthenticatedSafe`]
+    pub fn to_content_info(&self) -> der::Result<ContentInfo> {
+        Ok(match self {
+            Self::Data(safe_contents) => ContentInfo {
+                content_type: ID_DATA,
+                content: Any::encode_from(&OctetString::new(safe_contents.to_der()?)?)?,
+            },
+            Self::EncryptedData(data) => ContentInfo {
+                content_type: ID_ENCRYPTED_DATA,
+                content: Any::encode_from(data)?,
+            },
+            Self::EnvelopedData(data) => ContentInfo {
+                content_type: ID_ENVELOPED_DATA,
+                content: Any::encode_from(data)?,
+            },
+        })
+    }
+}
+
+impl TryFrom<&ContentInfo> for SafeContentsInfo {
+    type Error = der::Error;
+
+    fn try_from(content_info: &ContentInfo) -> der::Result<Self> {
+        let content = &content_info.content;
+        match content_info.content_type {
+            ID_DATA => {
+                let data = content.decode_as::<OctetString>()?;
+                Ok(Self::Data(SafeContents::from_der(data.as_bytes())?))
+            }
+            ID_ENCRYPTED_DATA => Ok(Self::EncryptedData(content.decode_as()?)),
+            ID_ENVELOPED_DATA => Ok(Self::EnvelopedData(content.decode_as()?)),
+            oid => Err(ErrorKind::OidUnknown { oid }.into()),
+        }
+    }
+}
+
+impl TryFrom<ContentInfo> for SafeContentsInfo {
+    type Error = der::Error;
+
+    fn try_from(content_info: ContentInfo) -> der::Result<Self> {
+        Self::try_from(&content_info)
+    }
+}
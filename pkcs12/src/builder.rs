@@ -0,0 +1,214 @@
+//! PFX builder
+
+use alloc::{vec, vec::Vec};
+use cms::content_info::CmsVersion;
+use cms::encrypted_data::EncryptedData;
+use cms::enveloped_data::EncryptedContentInfo;
+use const_oid::db::rfc2985::{PKCS_9_AT_FRIENDLY_NAME, PKCS_9_AT_LOCAL_KEY_ID};
+use const_oid::db::rfc5911::ID_DATA;
+use der::asn1::{Any, BmpString, ObjectIdentifier, OctetString, SetOfVec};
+use der::{Decode, Encode};
+use pkcs5::pbes2;
+use pkcs8::PrivateKeyInfo;
+use rand_core::CryptoRngCore;
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::attr::{Attribute, Attributes};
+use x509_cert::crl::CertificateList;
+use x509_cert::Certificate;
+use zeroize::Zeroizing;
+
+use crate::authenticated_safe::SafeContentsInfo;
+use crate::cert_type::CertBag;
+use crate::crl_type::CrlBag;
+use crate::pbe_params::EncryptedPrivateKeyInfo;
+use crate::pfx::Pfx;
+use crate::profile::Profile;
+use crate::safe_bag::{BagValue, SafeBag, SafeContents};
+use crate::secret_bag::SecretBag;
+use crate::Result;
+
+/// PFX builder
+///
+/// The builder emits a PFX in password privacy and password integrity mode, laid out as
+/// OpenSSL and Windows do:
+///
+/// - an `EncryptedData` with the certBags of the certificate and the chain, followed by the
+///   crlBags and the secretBags, if any,
+/// - a `Data` with the pkcs8ShroudedKeyBag of the private key,
+/// - a `MacData` over both of them.
+///
+/// The algorithms and parameters protecting the PFX are those of a [`Profile`], by default
+/// [`Profile::modern`]. The friendlyName and localKeyId attributes, if set, are added to the
+/// bags of the key and the certificate.
+///
+/// ```
+/// use pkcs12::builder::PfxBuilder;
+///
+/// # fn build(
+/// #     key: &pkcs8::PrivateKeyInfo<'_>,
+/// #     cert: x509_cert::Certificate,
+/// #     rng: &mut impl rand_core::CryptoRngCore,
+/// # ) -> pkcs12::Result<()> {
+/// let pfx = PfxBuilder::new(key, cert)?
+///     .with_friendly_name("my key")?
+///     .with_local_key_id(&[1, 2, 3, 4])?
+///     .build("password", rng)?;
+/// pfx.verify_mac("password")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PfxBuilder {
+    key: Zeroizing<Vec<u8>>,
+    cert: Certificate,
+    chain: Vec<Certificate>,
+    crls: Vec<CertificateList>,
+    secrets: Vec<SecretBag>,
+    friendly_name: Option<BmpString>,
+    local_key_id: Option<OctetString>,
+    profile: Profile,
+}
+
+impl PfxBuilder {
+    /// Returns a `PfxBuilder` for the private key `key` and its certificate `cert`
+    pub fn new(key: &PrivateKeyInfo<'_>, cert: Certificate) -> Result<Self> {
+        Ok(Self {
+            key: Zeroizing::new(key.to_der()?),
+            cert,
+            chain: Vec::new(),
+            crls: Vec::new(),
+            secrets: Vec::new(),
+            friendly_name: None,
+            local_key_id: None,
+            profile: Profile::modern(),
+        })
+    }
+
+    /// Adds a certificate of the chain of the certificate, e.g. of an intermediate CA
+    pub fn with_ca_cert(mut self, cert: Certificate) -> Self {
+        self.chain.push(cert);
+        self
+    }
+
+    /// Adds the certificates of the chain of the certificate
+    pub fn with_chain(mut self, chain: impl IntoIterator<Item = Certificate>) -> Self {
+        self.chain.extend(chain);
+        self
+    }
+
+    /// Adds a CRL, e.g. of the issuer of the certificate
+    pub fn with_crl(mut self, crl: CertificateList) -> Self {
+        self.crls.push(crl);
+        self
+    }
+
+    /// Adds a secret, see [`SecretBag::new`]
+    pub fn with_secret(mut self, secret: SecretBag) -> Self {
+        self.secrets.push(secret);
+        self
+    }
+
+    /// Sets the friendlyName attribute of the key and the certificate
+    pub fn with_friendly_name(mut self, friendly_name: &str) -> Result<Self> {
+        self.friendly_name = Some(BmpString::from_utf8(friendly_name)?);
+        Ok(self)
+    }
+
+    /// Sets the localKeyId attribute of the key and the certificate, which associates them
+    /// with each other, e.g. the SHA-1 hash of the certificate
+    pub fn with_local_key_id(mut self, local_key_id: &[u8]) -> Result<Self> {
+        self.local_key_id = Some(OctetString::new(local_key_id)?);
+        Ok(self)
+    }
+
+    /// Sets the profile protecting the PFX
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Consumes the builder and returns a PFX protected with `password`, generating the
+    /// salts and IVs with `rng`
+    pub fn build(self, password: &str, rng: &mut impl CryptoRngCore) -> Result<Pfx> {
+        let attributes = self.attributes()?;
+
+        let mut cert_bags = SafeContents::new();
+        for (i, cert) in core::iter::once(&self.cert).chain(&self.chain).enumerate() {
+            let cert_bag = BagValue::Cert(CertBag::new(cert)?);
+            let bag_attributes = if i == 0 { attributes.clone() } else { None };
+            cert_bags.push(SafeBag::new(&cert_bag, bag_attributes)?);
+        }
+        for crl in &self.crls {
+            cert_bags.push(SafeBag::new(&BagValue::Crl(CrlBag::new(crl)?), None)?);
+        }
+        for secret in &self.secrets {
+            cert_bags.push(SafeBag::new(&BagValue::Secret(secret.clone()), None)?);
+        }
+
+        let scheme = self.profile.pbes2_params(rng)?;
+        let encrypted_certs = EncryptedData {
+            version: CmsVersion::V0,
+            enc_content_info: EncryptedContentInfo {
+                content_type: ID_DATA,
+                content_enc_alg: algorithm_identifier(&scheme)?,
+                encrypted_content: Some(OctetString::new(
+                    scheme.encrypt(password, &cert_bags.to_der()?)?,
+                )?),
+            },
+            unprotected_attrs: None,
+        };
+
+        let scheme = self.profile.pbes2_params(rng)?;
+        let shrouded_key = EncryptedPrivateKeyInfo {
+            encryption_algorithm: algorithm_identifier(&scheme)?,
+            encrypted_data: OctetString::new(scheme.encrypt(password, &self.key)?)?,
+        };
+        let key_bags = SafeContents::from([SafeBag::new(
+            &BagValue::Pkcs8ShroudedKey(shrouded_key),
+            attributes,
+        )?]);
+
+        let auth_safe = vec![
+            SafeContentsInfo::EncryptedData(encrypted_certs).to_content_info()?,
+            SafeContentsInfo::Data(key_bags).to_content_info()?,
+        ];
+        let mut pfx = Pfx::new(&auth_safe)?;
+
+        let mut mac_salt = vec![0u8; self.profile.salt_len];
+        rng.fill_bytes(&mut mac_salt);
+        pfx.set_mac(
+            self.profile.mac_digest,
+            password,
+            &mac_salt,
+            self.profile.mac_iterations,
+        )?;
+        Ok(pfx)
+    }
+
+    fn attributes(&self) -> Result<Option<Attributes>> {
+        let mut attributes = Attributes::new();
+        if let Some(friendly_name) = &self.friendly_name {
+            attributes.insert(attribute(
+                PKCS_9_AT_FRIENDLY_NAME,
+                Any::encode_from(friendly_name)?,
+            )?)?;
+        }
+        if let Some(local_key_id) = &self.local_key_id {
+            attributes.insert(attribute(
+                PKCS_9_AT_LOCAL_KEY_ID,
+                Any::encode_from(local_key_id)?,
+            )?)?;
+        }
+        Ok((!attributes.is_empty()).then_some(attributes))
+    }
+}
+
+fn attribute(oid: ObjectIdentifier, value: Any) -> der::Result<Attribute> {
+    let mut values = SetOfVec::new();
+    values.insert(value)?;
+    Ok(Attribute { oid, values })
+}
+
+fn algorithm_identifier(scheme: &pbes2::Parameters) -> der::Result<AlgorithmIdentifierOwned> {
+    let scheme = pkcs5::EncryptionScheme::from(scheme.clone());
+    AlgorithmIdentifierOwned::from_der(&scheme.to_der()?)
+}
@@ -1,7 +1,10 @@
 //! CertBag-related types
 
 use der::asn1::{ObjectIdentifier, OctetString};
-use der::Sequence;
+use der::{Decode, Encode, ErrorKind, Sequence};
+use x509_cert::Certificate;
+
+use crate::PKCS_12_X509_CERT_OID;
 
 /// The `CertBag` type is defined in [RFC 7292 Section 4.2.3].
 ///
@@ -21,6 +24,24 @@ pub struct CertBag {
     pub cert_value: CertTypes,
 }
 
+impl CertBag {
+    /// Returns an `x509Certificate` bag carrying `cert`
+    pub fn new(cert: &Certificate) -> der::Result<Self> {
+        Ok(Self {
+            cert_id: PKCS_12_X509_CERT_OID,
+            cert_value: OctetString::new(cert.to_der()?)?,
+        })
+    }
+
+    /// Decodes the X.509 certificate, or returns an error if the certId isn't `x509Certificate`.
+    pub fn certificate(&self) -> der::Result<Certificate> {
+        match self.cert_id {
+            PKCS_12_X509_CERT_OID => Certificate::from_der(self.cert_value.as_bytes()),
+            oid => Err(ErrorKind::OidUnknown { oid }.into()),
+        }
+    }
+}
+
 // todo defer: add sdsiCertificate support
 /// The `CertTypes` type is defined in [RFC 7292 Section 4.2.3].
 ///
@@ -1,9 +1,12 @@
-//! CertBag-related types
+//! CRLBag-related types
 
 use der::asn1::{ObjectIdentifier, OctetString};
-use der::Sequence;
+use der::{Decode, Encode, ErrorKind, Sequence};
+use x509_cert::crl::CertificateList;
 
-/// The `CertBag` type is defined in [RFC 7292 Section 4.2.4].
+use crate::PKCS_12_X509_CRL_OID;
+
+/// The `CRLBag` type is defined in [RFC 7292 Section 4.2.4].
 ///
 ///```text
 ///     CRLBag ::= SEQUENCE {
@@ -21,6 +24,24 @@ pub struct CrlBag {
     pub crl_value: CrlTypes,
 }
 
+impl CrlBag {
+    /// Returns an `x509CRL` bag carrying `crl`
+    pub fn new(crl: &CertificateList) -> der::Result<Self> {
+        Ok(Self {
+            crl_id: PKCS_12_X509_CRL_OID,
+            crl_value: OctetString::new(crl.to_der()?)?,
+        })
+    }
+
+    /// Decodes the X.509 CRL, or returns an error if the crlId isn't `x509CRL`.
+    pub fn crl(&self) -> der::Result<CertificateList> {
+        match self.crl_id {
+            PKCS_12_X509_CRL_OID => CertificateList::from_der(self.crl_value.as_bytes()),
+            oid => Err(ErrorKind::OidUnknown { oid }.into()),
+        }
+    }
+}
+
 // todo defer: add support for other CRL types
 /// The `CRLTypes` type is defined in [RFC 7292 Section 4.2.4].
 ///
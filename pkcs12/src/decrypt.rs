@@ -0,0 +1,217 @@
+//! Password privacy mode as described in [RFC 7292 Section 3.1].
+//!
+//! Shrouded keys and password-encrypted SafeContents may be protected with PBES2 (requires the
+//! `pbes2` feature) or with one of the legacy PKCS#12 schemes (requires the `legacy` feature),
+//! see [`crate::pbe`].
+//!
+//! [RFC 7292 Section 3.1]: https://www.rfc-editor.org/rfc/rfc7292#section-3.1
+
+use alloc::{vec, vec::Vec};
+use const_oid::db::rfc5911::ID_ENVELOPED_DATA;
+use der::{Decode, Tag};
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::crl::CertificateList;
+use x509_cert::Certificate;
+use zeroize::Zeroizing;
+
+use crate::authenticated_safe::SafeContentsInfo;
+use crate::pbe_params::EncryptedPrivateKeyInfo;
+use crate::pfx::{KeyPair, Pfx};
+use crate::safe_bag::{BagValue, PrivateKeyInfo, SafeContents};
+use crate::secret_bag::SecretBag;
+use crate::{Error, Result, PKCS_12_X509_CERT_OID, PKCS_12_X509_CRL_OID};
+
+impl EncryptedPrivateKeyInfo {
+    /// Decrypts the private key with `password`.
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKeyInfo> {
+        let plaintext = decrypt(
+            &self.encryption_algorithm,
+            password,
+            self.encrypted_data.as_bytes(),
+        )?;
+        Ok(PrivateKeyInfo::from_der(&plaintext)?)
+    }
+}
+
+impl SafeContentsInfo {
+    /// Returns the SafeContents, decrypting them with `password` if they are
+    /// password-encrypted.
+    ///
+    /// Public key-encrypted SafeContents aren't supported.
+    pub fn decrypt(&self, password: &str) -> Result<SafeContents> {
+        match self {
+            Self::Data(safe_contents) => Ok(safe_contents.clone()),
+            Self::EncryptedData(data) => {
+                let info = &data.enc_content_info;
+                let ciphertext = info
+                    .encrypted_content
+                    .as_ref()
+                    .ok_or_else(|| Tag::OctetString.value_error())?;
+                let plaintext = decrypt(&info.content_enc_alg, password, ciphertext.as_bytes())?;
+                Ok(SafeContents::from_der(&plaintext)?)
+            }
+            Self::EnvelopedData(_) => Err(Error::UnsupportedAlgorithm(ID_ENVELOPED_DATA)),
+        }
+    }
+}
+
+impl Pfx {
+    /// Returns the bags of all SafeContents of the AuthenticatedSafe, decrypted with
+    /// `password`, see [`SafeContentsInfo::decrypt`]. The bags of nested SafeContents are
+    /// included in place of the safeContentsBags.
+    pub fn bags(&self, password: &str) -> Result<SafeContents> {
+        let mut bags = SafeContents::new();
+        for safe_contents in self.safe_contents()? {
+            flatten(safe_contents.decrypt(password)?, &mut bags)?;
+        }
+        Ok(bags)
+    }
+
+    /// Returns the private keys of the keyBags and pkcs8ShroudedKeyBags, the latter decrypted
+    /// with `password`.
+    pub fn private_keys(&self, password: &str) -> Result<Vec<PrivateKeyInfo>> {
+        let mut keys = Vec::new();
+        for bag in self.bags(password)? {
+            match bag.value()? {
+                BagValue::Key(key) => keys.push(key),
+                BagValue::Pkcs8ShroudedKey(key) => keys.push(key.decrypt(password)?),
+                _ => {}
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Returns the X.509 certificates of the certBags. Certificates of other types are
+    /// skipped.
+    pub fn certificates(&self, password: &str) -> Result<Vec<Certificate>> {
+        let mut certs = Vec::new();
+        for bag in self.bags(password)? {
+            if let BagValue::Cert(cert_bag) = bag.value()? {
+                if cert_bag.cert_id == PKCS_12_X509_CERT_OID {
+                    certs.push(cert_bag.certificate()?);
+                }
+            }
+        }
+        Ok(certs)
+    }
+
+    /// Returns the X.509 CRLs of the crlBags. CRLs of other types are skipped.
+    pub fn crls(&self, password: &str) -> Result<Vec<CertificateList>> {
+        let mut crls = Vec::new();
+        for bag in self.bags(password)? {
+            if let BagValue::Crl(crl_bag) = bag.value()? {
+                if crl_bag.crl_id == PKCS_12_X509_CRL_OID {
+                    crls.push(crl_bag.crl()?);
+                }
+            }
+        }
+        Ok(crls)
+    }
+
+    /// Returns the secrets of the secretBags, see [`SecretBag::bag_value`].
+    pub fn secrets(&self, password: &str) -> Result<Vec<SecretBag>> {
+        let mut secrets = Vec::new();
+        for bag in self.bags(password)? {
+            if let BagValue::Secret(secret_bag) = bag.value()? {
+                secrets.push(secret_bag);
+            }
+        }
+        Ok(secrets)
+    }
+
+    /// Returns the private keys along with their certificates, see [`Pfx::private_keys`].
+    ///
+    /// A private key is paired with the certificate carrying the same localKeyId attribute,
+    /// or otherwise with the first certificate whose public key matches, see
+    /// [`PrivateKeyInfo::matches_public_key`].
+    pub fn key_pairs(&self, password: &str) -> Result<Vec<KeyPair>> {
+        let mut keys = Vec::new();
+        let mut certs = Vec::new();
+        for bag in self.bags(password)? {
+            let local_key_id = bag.local_key_id()?;
+            match bag.value()? {
+                BagValue::Key(key) => keys.push((key, local_key_id, bag.friendly_name()?)),
+                BagValue::Pkcs8ShroudedKey(key) => {
+                    keys.push((key.decrypt(password)?, local_key_id, bag.friendly_name()?))
+                }
+                BagValue::Cert(cert_bag) if cert_bag.cert_id == PKCS_12_X509_CERT_OID => {
+                    certs.push((cert_bag.certificate()?, local_key_id))
+                }
+                _ => {}
+            }
+        }
+
+        let mut key_pairs = Vec::with_capacity(keys.len());
+        for (private_key, local_key_id, friendly_name) in keys {
+            let mut certificate = local_key_id.and_then(|local_key_id| {
+                certs
+                    .iter()
+                    .find(|(_, id)| id.as_ref() == Some(&local_key_id))
+                    .map(|(cert, _)| cert.clone())
+            });
+            if certificate.is_none() {
+                for (cert, _) in &certs {
+                    let spki = &cert.tbs_certificate.subject_public_key_info;
+                    if private_key.matches_public_key(spki)? {
+                        certificate = Some(cert.clone());
+                        break;
+                    }
+                }
+            }
+            key_pairs.push(KeyPair {
+                private_key,
+                certificate,
+                friendly_name,
+            });
+        }
+        Ok(key_pairs)
+    }
+}
+
+/// Appends the bags of `safe_contents` to `bags`, replacing each safeContentsBag with its bags.
+///
+/// Nested SafeContents are walked with an explicit stack, so arbitrarily deep nesting can't
+/// overflow the call stack.
+fn flatten(safe_contents: SafeContents, bags: &mut SafeContents) -> Result<()> {
+    let mut stack = vec![safe_contents.into_iter()];
+    while let Some(safe_contents) = stack.last_mut() {
+        match safe_contents.next() {
+            Some(bag) => match bag.value()? {
+                BagValue::SafeContents(nested) => stack.push(nested.into_iter()),
+                _ => bags.push(bag),
+            },
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decrypts `ciphertext` with `password` using the scheme identified by `alg`
+fn decrypt(
+    alg: &AlgorithmIdentifierOwned,
+    password: &str,
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    match alg.oid {
+        #[cfg(feature = "pbes2")]
+        pkcs5::pbes2::PBES2_OID => {
+            use der::Encode;
+
+            let scheme = pkcs5::EncryptionScheme::try_from(alg.to_der()?.as_slice())?;
+            scheme
+                .decrypt(password, ciphertext)
+                .map(Zeroizing::new)
+                .map_err(|err| match err {
+                    pkcs5::Error::DecryptFailed => Error::Decryption,
+                    err => err.into(),
+                })
+        }
+        #[cfg(feature = "legacy")]
+        oid if crate::pbe::EncryptionScheme::try_from(oid).is_ok() => {
+            crate::pbe::Algorithm::try_from(alg)?.decrypt(password, ciphertext)
+        }
+        oid => Err(Error::UnsupportedAlgorithm(oid)),
+    }
+}
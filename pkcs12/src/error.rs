@@ -0,0 +1,58 @@
+//! Error types
+
+use core::fmt;
+use der::asn1::ObjectIdentifier;
+
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors
+    Asn1(der::Error),
+
+    /// The algorithm, or one of its components, isn't supported
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// The PFX lacks the macData
+    MissingMac,
+
+    /// The MAC doesn't verify, e.g. due to a wrong password
+    MacVerification,
+
+    /// Decryption failed, e.g. due to a wrong password
+    Decryption,
+
+    /// Password-based encryption errors
+    #[cfg(feature = "pbes2")]
+    Pbes2(pkcs5::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "PKCS#12 ASN.1 error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::MissingMac => f.write_str("PFX lacks the macData"),
+            Error::MacVerification => f.write_str("PFX MAC verification failed"),
+            Error::Decryption => f.write_str("PKCS#12 decryption failed"),
+            #[cfg(feature = "pbes2")]
+            Error::Pbes2(err) => write!(f, "PBES2 error: {}", err),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(other: der::Error) -> Self {
+        Self::Asn1(other)
+    }
+}
+
+#[cfg(feature = "pbes2")]
+impl From<pkcs5::Error> for Error {
+    fn from(other: pkcs5::Error) -> Self {
+        Self::Pbes2(other)
+    }
+}
@@ -26,32 +26,47 @@ pub mod mac_data;
 pub mod pbe_params;
 pub mod pfx;
 pub mod safe_bag;
+pub mod secret_bag;
 
+#[cfg(feature = "builder")]
+pub mod builder;
 #[cfg(feature = "kdf")]
 pub mod kdf;
+#[cfg(feature = "legacy")]
+pub mod pbe;
+#[cfg(feature = "pbes2")]
+pub mod profile;
+
+#[cfg(any(feature = "legacy", feature = "pbes2"))]
+mod decrypt;
+mod error;
+#[cfg(feature = "mac")]
+mod mac;
+
+pub use crate::error::{Error, Result};
 
 // pbe oids
 /// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
 pub const PKCS_12_PBE_WITH_SHAAND128_BIT_RC4: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.1");
 
-/// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
+/// `pbeWithSHAAnd40BitRC4` Object Identifier (OID).
 pub const PKCS_12_PBE_WITH_SHAAND40_BIT_RC4: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.2");
 
-/// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
+/// `pbeWithSHAAnd3-KeyTripleDES-CBC` Object Identifier (OID).
 pub const PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.3");
 
-/// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
+/// `pbeWithSHAAnd2-KeyTripleDES-CBC` Object Identifier (OID).
 pub const PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.4");
 
-/// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
+/// `pbeWithSHAAnd128BitRC2-CBC` Object Identifier (OID).
 pub const PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.5");
 
-/// `pbeWithSHAAnd128BitRC4` Object Identifier (OID).
+/// `pbeWithSHAAnd40BitRC2-CBC` Object Identifier (OID).
 pub const PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.6");
 
@@ -89,9 +104,9 @@ pub const PKCS_12_X509_CERT_OID: ObjectIdentifier =
 pub const PKCS_12_SDSI_CERT_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.22.2");
 
-// todo: return the friendly name if present? (minimally, defer until BMPString support is available)
-// todo: support separate mac and encryption passwords?
-// todo: add decryption support
+// crl types
+/// `pkcs-9 x509CRL for pkcs-12` Object Identifier (OID).
+pub const PKCS_12_X509_CRL_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.23.1");
+
 // todo: add more encryption tests
-// todo: add a builder
-// todo: add RC2 support
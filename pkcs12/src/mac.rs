@@ -0,0 +1,141 @@
+//! Password integrity mode as described in [RFC 7292 Section 5.1].
+//!
+//! The MAC is an HMAC over the contents of the authSafe, keyed with the output of the
+//! PKCS#12 KDF for the password, see [`crate::kdf`].
+//!
+//! [RFC 7292 Section 5.1]: https://www.rfc-editor.org/rfc/rfc7292#section-5.1
+
+use alloc::vec::Vec;
+use const_oid::db::rfc5912::{ID_SHA_1, ID_SHA_224, ID_SHA_256, ID_SHA_384, ID_SHA_512};
+use der::asn1::{Any, ObjectIdentifier, OctetString};
+use der::Tag;
+use digest::{core_api::BlockSizeUser, Digest, FixedOutputReset, OutputSizeUser};
+use hmac::{KeyInit, Mac, SimpleHmac};
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use spki::AlgorithmIdentifierOwned;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+use crate::digest_info::DigestInfo;
+use crate::kdf::{derive_key_utf8, Pkcs12KeyType};
+use crate::mac_data::MacData;
+use crate::pfx::Pfx;
+use crate::{Error, Result};
+
+impl MacData {
+    /// Implementation defined maximum iteration count, matching the maximum PBKDF2 iteration
+    /// count of the `pkcs5` crate.
+    ///
+    /// The iteration count of a PFX is chosen by its producer: larger counts are rejected so
+    /// that verifying an untrusted PFX can't take an unbounded amount of time.
+    pub const MAX_ITERATIONS: i32 = 100_000_000;
+
+    /// Returns the `MacData` over `data` keyed with `password`, using an HMAC with the digest
+    /// algorithm `digest`, which may be SHA-1 or SHA-2.
+    ///
+    /// The password is converted to a BMPString, i.e. UTF-16BE, followed by two zero bytes,
+    /// as described in [RFC 7292 Appendix B.1]. An empty password thus yields two zero bytes.
+    ///
+    /// [RFC 7292 Appendix B.1]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.1
+    pub fn new(
+        digest: ObjectIdentifier,
+        password: &str,
+        mac_salt: &[u8],
+        iterations: i32,
+        data: &[u8],
+    ) -> Result<Self> {
+        let mac = mac(digest, password, mac_salt, iterations, data)?;
+        Ok(Self {
+            mac: DigestInfo {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid: digest,
+                    parameters: Some(Any::null()),
+                },
+                digest: OctetString::new(mac)?,
+            },
+            mac_salt: OctetString::new(mac_salt)?,
+            iterations,
+        })
+    }
+
+    /// Verifies the MAC over `data` keyed with `password`, see [`MacData::new`].
+    pub fn verify(&self, password: &str, data: &[u8]) -> Result<()> {
+        let mac = mac(
+            self.mac.algorithm.oid,
+            password,
+            self.mac_salt.as_bytes(),
+            self.iterations,
+            data,
+        )?;
+
+        if bool::from(mac.ct_eq(self.mac.digest.as_bytes())) {
+            Ok(())
+        } else {
+            Err(Error::MacVerification)
+        }
+    }
+}
+
+impl Pfx {
+    /// Sets the macData of this PFX to the MAC over its authSafe, see [`MacData::new`].
+    pub fn set_mac(
+        &mut self,
+        digest: ObjectIdentifier,
+        password: &str,
+        mac_salt: &[u8],
+        iterations: i32,
+    ) -> Result<()> {
+        let data = self.auth_safe_data()?;
+        let mac_data = MacData::new(digest, password, mac_salt, iterations, data.as_bytes())?;
+        self.mac_data = Some(mac_data);
+        Ok(())
+    }
+
+    /// Verifies the MAC over the authSafe of this PFX, see [`MacData::verify`].
+    pub fn verify_mac(&self, password: &str) -> Result<()> {
+        let mac_data = self.mac_data.as_ref().ok_or(Error::MissingMac)?;
+        let data = self.auth_safe_data()?;
+        mac_data.verify(password, data.as_bytes())
+    }
+}
+
+fn mac(
+    digest: ObjectIdentifier,
+    password: &str,
+    salt: &[u8],
+    iterations: i32,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    if !(1..=MacData::MAX_ITERATIONS).contains(&iterations) {
+        return Err(Tag::Integer.value_error().into());
+    }
+
+    match digest {
+        ID_SHA_1 => mac_with::<Sha1>(password, salt, iterations, data),
+        ID_SHA_224 => mac_with::<Sha224>(password, salt, iterations, data),
+        ID_SHA_256 => mac_with::<Sha256>(password, salt, iterations, data),
+        ID_SHA_384 => mac_with::<Sha384>(password, salt, iterations, data),
+        ID_SHA_512 => mac_with::<Sha512>(password, salt, iterations, data),
+        oid => Err(Error::UnsupportedAlgorithm(oid)),
+    }
+}
+
+fn mac_with<D>(password: &str, salt: &[u8], iterations: i32, data: &[u8]) -> Result<Vec<u8>>
+where
+    D: Digest + FixedOutputReset + BlockSizeUser,
+{
+    let key_len = <D as OutputSizeUser>::output_size();
+    let key = Zeroizing::new(derive_key_utf8::<D>(
+        password,
+        salt,
+        Pkcs12KeyType::Mac,
+        iterations,
+        key_len,
+    )?);
+
+    let mut mac = <SimpleHmac<D> as KeyInit>::new_from_slice(&key)
+        .map_err(|_| Error::from(Tag::OctetString.value_error()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
@@ -0,0 +1,228 @@
+//! Legacy password-based encryption schemes as described in [RFC 7292 Appendix C].
+//!
+//! The encryption key and IV are derived from the password with the PKCS#12 KDF over SHA-1,
+//! see [`crate::kdf`].
+//!
+//! ## ⚠️ Security Warning
+//!
+//! These schemes are only provided to decrypt PFX files produced by older software, e.g.
+//! Windows or Java keystores. New PFX files should use PBES2 instead.
+//!
+//! [RFC 7292 Appendix C]: https://www.rfc-editor.org/rfc/rfc7292#appendix-C
+
+use alloc::{vec, vec::Vec};
+use cbc::cipher::{
+    block_padding::Pkcs7, BlockCipher, BlockDecryptMut, BlockEncryptMut, InnerIvInit, KeyInit,
+};
+use der::asn1::ObjectIdentifier;
+use der::{ErrorKind, Tag};
+use des::{TdesEde2, TdesEde3};
+use rc2::Rc2;
+use sha1::Sha1;
+use spki::AlgorithmIdentifierOwned;
+use zeroize::Zeroizing;
+
+use crate::kdf::{derive_key_utf8, Pkcs12KeyType};
+use crate::pbe_params::Pkcs12PbeParams;
+use crate::{
+    Error, Result, PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC, PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC,
+    PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC, PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+};
+
+/// Block size of both DES and RC2
+const BLOCK_SIZE: usize = 8;
+
+impl Pkcs12PbeParams {
+    /// Implementation defined maximum iteration count, matching the maximum PBKDF2 iteration
+    /// count of the `pkcs5` crate.
+    ///
+    /// The iteration count is chosen by the producer of the encrypted data: larger counts are
+    /// rejected so that decrypting untrusted data can't take an unbounded amount of time.
+    pub const MAX_ITERATIONS: i32 = 100_000_000;
+}
+
+/// A legacy PKCS#12 password-based encryption algorithm, i.e. an [`EncryptionScheme`] and
+/// its parameters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Algorithm {
+    /// Encryption scheme
+    pub encryption: EncryptionScheme,
+
+    /// Scheme parameters
+    pub parameters: Pkcs12PbeParams,
+}
+
+impl Algorithm {
+    /// Get the [`ObjectIdentifier`] (a.k.a OID) for this algorithm.
+    pub fn oid(&self) -> ObjectIdentifier {
+        self.encryption.oid()
+    }
+
+    /// Decrypts `ciphertext` with the key and IV derived from `password`.
+    ///
+    /// The password is converted to a BMPString as described in [RFC 7292 Appendix B.1].
+    ///
+    /// [RFC 7292 Appendix B.1]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.1
+    pub fn decrypt(&self, password: &str, ciphertext: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let (key, iv) = self.key_and_iv(password)?;
+        match self.encryption {
+            EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc => {
+                decrypt::<TdesEde3>(new_cipher(&key)?, &iv, ciphertext)
+            }
+            EncryptionScheme::PbeWithShaAnd2KeyTripleDesCbc => {
+                decrypt::<TdesEde2>(new_cipher(&key)?, &iv, ciphertext)
+            }
+            EncryptionScheme::PbeWithShaAnd128BitRc2Cbc
+            | EncryptionScheme::PbeWithShaAnd40BitRc2Cbc => decrypt(rc2(&key), &iv, ciphertext),
+        }
+    }
+
+    /// Encrypts `plaintext` with the key and IV derived from `password`, see
+    /// [`Algorithm::decrypt`].
+    pub fn encrypt(&self, password: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let (key, iv) = self.key_and_iv(password)?;
+        match self.encryption {
+            EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc => {
+                encrypt::<TdesEde3>(new_cipher(&key)?, &iv, plaintext)
+            }
+            EncryptionScheme::PbeWithShaAnd2KeyTripleDesCbc => {
+                encrypt::<TdesEde2>(new_cipher(&key)?, &iv, plaintext)
+            }
+            EncryptionScheme::PbeWithShaAnd128BitRc2Cbc
+            | EncryptionScheme::PbeWithShaAnd40BitRc2Cbc => encrypt(rc2(&key), &iv, plaintext),
+        }
+    }
+
+    fn key_and_iv(&self, password: &str) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+        let salt = self.parameters.salt.as_bytes();
+        let iterations = self.parameters.iterations;
+        if !(1..=Pkcs12PbeParams::MAX_ITERATIONS).contains(&iterations) {
+            return Err(Tag::Integer.value_error().into());
+        }
+
+        let key = Zeroizing::new(derive_key_utf8::<Sha1>(
+            password,
+            salt,
+            Pkcs12KeyType::EncryptionKey,
+            iterations,
+            self.encryption.key_len(),
+        )?);
+        let iv =
+            derive_key_utf8::<Sha1>(password, salt, Pkcs12KeyType::Iv, iterations, BLOCK_SIZE)?;
+        Ok((key, iv))
+    }
+}
+
+impl TryFrom<&AlgorithmIdentifierOwned> for Algorithm {
+    type Error = Error;
+
+    fn try_from(alg: &AlgorithmIdentifierOwned) -> Result<Self> {
+        let encryption = EncryptionScheme::try_from(alg.oid)?;
+        let parameters = alg
+            .parameters
+            .as_ref()
+            .ok_or_else(|| Tag::Sequence.value_error())?
+            .decode_as()?;
+
+        Ok(Self {
+            encryption,
+            parameters,
+        })
+    }
+}
+
+/// Legacy PKCS#12 password-based encryption schemes as defined in [RFC 7292 Appendix C].
+///
+/// The RC4 based schemes aren't supported.
+///
+/// [RFC 7292 Appendix C]: https://www.rfc-editor.org/rfc/rfc7292#appendix-C
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EncryptionScheme {
+    /// `pbeWithSHAAnd3-KeyTripleDES-CBC`
+    PbeWithShaAnd3KeyTripleDesCbc,
+
+    /// `pbeWithSHAAnd2-KeyTripleDES-CBC`
+    PbeWithShaAnd2KeyTripleDesCbc,
+
+    /// `pbeWithSHAAnd128BitRC2-CBC`
+    PbeWithShaAnd128BitRc2Cbc,
+
+    /// `pbeWithSHAAnd40BitRC2-CBC`
+    PbeWithShaAnd40BitRc2Cbc,
+}
+
+impl EncryptionScheme {
+    /// Get the [`ObjectIdentifier`] (a.k.a OID) for this algorithm.
+    pub fn oid(self) -> ObjectIdentifier {
+        match self {
+            Self::PbeWithShaAnd3KeyTripleDesCbc => PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+            Self::PbeWithShaAnd2KeyTripleDesCbc => PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC,
+            Self::PbeWithShaAnd128BitRc2Cbc => PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC,
+            Self::PbeWithShaAnd40BitRc2Cbc => PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC,
+        }
+    }
+
+    /// Length of the key in bytes
+    pub fn key_len(self) -> usize {
+        match self {
+            Self::PbeWithShaAnd3KeyTripleDesCbc => 24,
+            Self::PbeWithShaAnd2KeyTripleDesCbc => 16,
+            Self::PbeWithShaAnd128BitRc2Cbc => 16,
+            Self::PbeWithShaAnd40BitRc2Cbc => 5,
+        }
+    }
+}
+
+impl TryFrom<ObjectIdentifier> for EncryptionScheme {
+    type Error = Error;
+
+    fn try_from(oid: ObjectIdentifier) -> Result<Self> {
+        match oid {
+            PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC => Ok(Self::PbeWithShaAnd3KeyTripleDesCbc),
+            PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC => Ok(Self::PbeWithShaAnd2KeyTripleDesCbc),
+            PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC => Ok(Self::PbeWithShaAnd128BitRc2Cbc),
+            PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC => Ok(Self::PbeWithShaAnd40BitRc2Cbc),
+            crate::PKCS_12_PBE_WITH_SHAAND128_BIT_RC4
+            | crate::PKCS_12_PBE_WITH_SHAAND40_BIT_RC4 => Err(Error::UnsupportedAlgorithm(oid)),
+            _ => Err(der::Error::from(ErrorKind::OidUnknown { oid }).into()),
+        }
+    }
+}
+
+fn new_cipher<C: KeyInit>(key: &[u8]) -> Result<C> {
+    C::new_from_slice(key).map_err(|_| Error::from(Tag::OctetString.value_error()))
+}
+
+/// Returns RC2 with an effective key length of the key size
+fn rc2(key: &[u8]) -> Rc2 {
+    Rc2::new_with_eff_key_len(key, key.len() * 8)
+}
+
+fn decrypt<C>(cipher: C, iv: &[u8], ciphertext: &[u8]) -> Result<Zeroizing<Vec<u8>>>
+where
+    C: BlockCipher + BlockDecryptMut,
+{
+    let mut buffer = Zeroizing::new(ciphertext.to_vec());
+    let len = cbc::Decryptor::<C>::inner_iv_slice_init(cipher, iv)
+        .map_err(|_| Error::from(Tag::OctetString.value_error()))?
+        .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+        .map_err(|_| Error::Decryption)?
+        .len();
+    buffer.truncate(len);
+    Ok(buffer)
+}
+
+fn encrypt<C>(cipher: C, iv: &[u8], plaintext: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockCipher + BlockEncryptMut,
+{
+    let mut buffer = vec![0u8; plaintext.len() + BLOCK_SIZE];
+    buffer[..plaintext.len()].copy_from_slice(plaintext);
+    let len = cbc::Encryptor::<C>::inner_iv_slice_init(cipher, iv)
+        .map_err(|_| Error::from(Tag::OctetString.value_error()))?
+        .encrypt_padded_mut::<Pkcs7>(&mut buffer, plaintext.len())
+        .map_err(|_| Error::from(Tag::OctetString.value_error()))?
+        .len();
+    buffer.truncate(len);
+    Ok(buffer)
+}
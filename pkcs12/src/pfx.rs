@@ -1,11 +1,17 @@
 //! PFX-related types
 
+use alloc::vec::Vec;
 use core::cmp::Ordering;
 
-use der::{Enumerated, Sequence, ValueOrd};
+use const_oid::db::rfc5911::ID_DATA;
+use der::asn1::{Any, OctetString};
+use der::{Decode, Encode, Enumerated, ErrorKind, Sequence, ValueOrd};
 
+use crate::authenticated_safe::{AuthenticatedSafe, SafeContentsInfo};
 use crate::mac_data::MacData;
 use cms::content_info::ContentInfo;
+#[cfg(any(feature = "legacy", feature = "pbes2"))]
+use {crate::safe_bag::PrivateKeyInfo, der::asn1::BmpString, x509_cert::Certificate};
 
 /// just the version v3
 #[derive(Clone, Copy, Debug, Enumerated, Eq, PartialEq, PartialOrd, Ord)]
@@ -45,3 +51,54 @@ pub struct Pfx {
     /// the message digest info
     pub mac_data: Option<MacData>,
 }
+
+impl Pfx {
+    /// Returns a `PFX` whose authSafe of type `Data` carries `auth_safe`, without macData
+    pub fn new(auth_safe: &AuthenticatedSafe<'_>) -> der::Result<Self> {
+        Ok(Self {
+            version: Version::V3,
+            auth_safe: ContentInfo {
+                content_type: ID_DATA,
+                content: Any::encode_from(&OctetString::new(auth_safe.to_der()?)?)?,
+            },
+            mac_data: None,
+        })
+    }
+
+    /// Decodes the AuthenticatedSafe from the authSafe, which must be of type `Data` as used
+    /// in password integrity mode.
+    pub fn authenticated_safe(&self) -> der::Result<AuthenticatedSafe<'_>> {
+        AuthenticatedSafe::from_der(self.auth_safe_data()?.as_bytes())
+    }
+
+    /// Decodes each ContentInfo of the AuthenticatedSafe, see [`Pfx::authenticated_safe`].
+    pub fn safe_contents(&self) -> der::Result<Vec<SafeContentsInfo>> {
+        self.authenticated_safe()?
+            .into_iter()
+            .map(SafeContentsInfo::try_from)
+            .collect()
+    }
+
+    /// Returns the content of the authSafe of type `Data`, i.e. the DER encoding of the
+    /// AuthenticatedSafe.
+    pub(crate) fn auth_safe_data(&self) -> der::Result<OctetString> {
+        match self.auth_safe.content_type {
+            ID_DATA => self.auth_safe.content.decode_as(),
+            oid => Err(ErrorKind::OidUnknown { oid }.into()),
+        }
+    }
+}
+
+/// A private key of a PFX along with its certificate, see [`Pfx::key_pairs`].
+#[cfg(any(feature = "legacy", feature = "pbes2"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyPair {
+    /// the private key
+    pub private_key: PrivateKeyInfo,
+
+    /// the certificate of the private key, if found
+    pub certificate: Option<Certificate>,
+
+    /// the friendlyName of the private key, if present
+    pub friendly_name: Option<BmpString>,
+}
@@ -0,0 +1,137 @@
+//! PFX protection profiles
+
+use alloc::vec::Vec;
+use const_oid::db::rfc5912::ID_SHA_256;
+use der::asn1::ObjectIdentifier;
+use der::AnyRef;
+use pkcs5::pbes2::{self, Kdf, Pbkdf2Prf, PBES2_OID};
+use spki::AlgorithmIdentifierOwned;
+
+use crate::authenticated_safe::SafeContentsInfo;
+use crate::pfx::Pfx;
+use crate::safe_bag::BagValue;
+use crate::Result;
+
+/// Algorithms and parameters protecting a PFX in password privacy and password integrity
+/// mode, e.g. as used by [`PfxBuilder`](crate::builder::PfxBuilder).
+///
+/// The private key and the certificates are encrypted with PBES2, using PBKDF2 with
+/// HMAC-SHA-256 and AES-256-CBC, and the MAC is an HMAC with the digest algorithm
+/// `mac_digest`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Profile {
+    /// PBKDF2 iteration count
+    pub iterations: u32,
+
+    /// Digest algorithm of the MAC, SHA-1 or SHA-2
+    pub mac_digest: ObjectIdentifier,
+
+    /// MAC iteration count
+    pub mac_iterations: i32,
+
+    /// Length of the PBKDF2 and MAC salts in bytes
+    pub salt_len: usize,
+}
+
+impl Profile {
+    /// Returns the profile matching the defaults of OpenSSL 3.x: 2048 iterations of PBKDF2
+    /// and of the HMAC-SHA-256 MAC, with 8-byte salts.
+    pub const fn modern() -> Self {
+        Self {
+            iterations: 2048,
+            mac_digest: ID_SHA_256,
+            mac_iterations: 2048,
+            salt_len: 8,
+        }
+    }
+
+    /// Returns the profile of `pfx`, or `None` if it isn't protected as described by a
+    /// `Profile`, e.g. as it uses legacy encryption schemes or lacks the macData.
+    ///
+    /// All encrypted bags must use the same iteration count and the same salt length as the
+    /// MAC. Only the shrouded keys of the unencrypted SafeContents are inspected, as the
+    /// bags of the encrypted ones can't be read without the password.
+    pub fn from_pfx(pfx: &Pfx) -> Result<Option<Self>> {
+        let mac_data = match &pfx.mac_data {
+            Some(mac_data) => mac_data,
+            None => return Ok(None),
+        };
+
+        let mut algorithms = Vec::new();
+        for safe_contents in pfx.safe_contents()? {
+            match safe_contents {
+                SafeContentsInfo::Data(bags) => {
+                    for bag in bags {
+                        if let BagValue::Pkcs8ShroudedKey(key) = bag.value()? {
+                            algorithms.push(key.encryption_algorithm);
+                        }
+                    }
+                }
+                SafeContentsInfo::EncryptedData(data) => {
+                    algorithms.push(data.enc_content_info.content_enc_alg)
+                }
+                SafeContentsInfo::EnvelopedData(_) => return Ok(None),
+            }
+        }
+
+        let salt_len = mac_data.mac_salt.as_bytes().len();
+        let mut iterations = None;
+        for algorithm in &algorithms {
+            let count = match pbkdf2_iterations(algorithm, salt_len) {
+                Some(count) => count,
+                None => return Ok(None),
+            };
+            if *iterations.get_or_insert(count) != count {
+                return Ok(None);
+            }
+        }
+
+        Ok(iterations.map(|iterations| Self {
+            iterations,
+            mac_digest: mac_data.mac.algorithm.oid,
+            mac_iterations: mac_data.iterations,
+            salt_len,
+        }))
+    }
+
+    /// Returns PBES2 parameters with a random salt and IV
+    #[cfg(feature = "builder")]
+    pub(crate) fn pbes2_params(
+        &self,
+        rng: &mut impl rand_core::CryptoRngCore,
+    ) -> Result<pbes2::Parameters> {
+        let mut salt = alloc::vec![0u8; self.salt_len];
+        let mut iv = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        Ok(pbes2::Parameters::pbkdf2_sha256_aes256cbc(
+            self.iterations,
+            &salt,
+            iv,
+        )?)
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::modern()
+    }
+}
+
+/// Returns the PBKDF2 iteration count of `algorithm` if it's PBES2 with PBKDF2-HMAC-SHA-256
+/// and AES-256-CBC, and the salt is `salt_len` bytes long
+fn pbkdf2_iterations(algorithm: &AlgorithmIdentifierOwned, salt_len: usize) -> Option<u32> {
+    if algorithm.oid != PBES2_OID {
+        return None;
+    }
+    let params = algorithm.parameters.as_ref()?;
+    match pbes2::Parameters::try_from(AnyRef::from(params)).ok()? {
+        pbes2::Parameters {
+            kdf: Kdf::Pbkdf2(kdf),
+            encryption: pbes2::EncryptionScheme::Aes256Cbc { .. },
+        } if kdf.prf == Pbkdf2Prf::HmacWithSha256 && kdf.salt.as_bytes().len() == salt_len => {
+            Some(kdf.iteration_count)
+        }
+        _ => None,
+    }
+}
@@ -1,12 +1,23 @@
 //! SafeBag-related types
 
 use alloc::vec::Vec;
+use const_oid::db::rfc2985::{PKCS_9_AT_FRIENDLY_NAME, PKCS_9_AT_LOCAL_KEY_ID};
+use const_oid::db::rfc5912::{ID_EC_PUBLIC_KEY, RSA_ENCRYPTION};
 use const_oid::ObjectIdentifier;
-use der::asn1::OctetString;
-use der::{AnyRef, Decode, Enumerated, Sequence};
-use spki::AlgorithmIdentifierOwned;
+use der::asn1::{
+    BitStringRef, BmpString, ContextSpecific, ContextSpecificRef, OctetString, OctetStringRef,
+    UintRef,
+};
+use der::{AnyRef, Choice, Decode, DecodeValue, Encode, EncodeValue, Enumerated, Sequence, Tagged};
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
 use x509_cert::attr::Attributes;
 
+use crate::bag_type::BagType;
+use crate::cert_type::CertBag;
+use crate::crl_type::CrlBag;
+use crate::pbe_params::EncryptedPrivateKeyInfo;
+use crate::secret_bag::SecretBag;
+
 /// The `SafeContents` type is defined in [RFC 7292 Section 4.1].
 ///
 /// ```text
@@ -59,18 +70,14 @@ impl<'a> ::der::DecodeValue<'a> for SafeBag {
         })
     }
 }
+// bag_value holds the encoding of the [0] EXPLICIT bagValue, which is written as is
 impl ::der::EncodeValue for SafeBag {
     fn value_len(&self) -> ::der::Result<::der::Length> {
         let content = AnyRef::from_der(&self.bag_value)?;
         use ::der::Encode as _;
         [
             self.bag_id.encoded_len()?,
-            ::der::asn1::ContextSpecificRef {
-                tag_number: ::der::TagNumber::N0,
-                tag_mode: ::der::TagMode::Explicit,
-                value: &content,
-            }
-            .encoded_len()?,
+            content.encoded_len()?,
             self.bag_attributes.encoded_len()?,
         ]
         .into_iter()
@@ -79,19 +86,144 @@ impl ::der::EncodeValue for SafeBag {
     fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
         use ::der::Encode as _;
         self.bag_id.encode(writer)?;
-        let content = AnyRef::from_der(&self.bag_value)?;
-        ::der::asn1::ContextSpecificRef {
-            tag_number: ::der::TagNumber::N0,
-            tag_mode: ::der::TagMode::Explicit,
-            value: &content,
-        }
-        .encode(writer)?;
+        AnyRef::from_der(&self.bag_value)?.encode(writer)?;
         self.bag_attributes.encode(writer)?;
         Ok(())
     }
 }
 impl<'a> ::der::Sequence<'a> for SafeBag {}
 
+impl SafeBag {
+    /// Returns a `SafeBag` carrying `value` with the given attributes
+    pub fn new(value: &BagValue, bag_attributes: Option<Attributes>) -> der::Result<Self> {
+        Ok(Self {
+            bag_id: value.bag_type().into(),
+            bag_value: value.to_explicit_der()?,
+            bag_attributes,
+        })
+    }
+
+    /// Returns the type of this bag, or an error if the bagId is unknown.
+    pub fn bag_type(&self) -> der::Result<BagType> {
+        BagType::try_from(self.bag_id)
+    }
+
+    /// Decodes the bagValue according to the bagId.
+    pub fn value(&self) -> der::Result<BagValue> {
+        Ok(match self.bag_type()? {
+            BagType::Key => BagValue::Key(self.decode_value_as()?),
+            BagType::Pkcs8 => BagValue::Pkcs8ShroudedKey(self.decode_value_as()?),
+            BagType::Cert => BagValue::Cert(self.decode_value_as()?),
+            BagType::Crl => BagValue::Crl(self.decode_value_as()?),
+            BagType::Secret => BagValue::Secret(self.decode_value_as()?),
+            BagType::SafeContents => BagValue::SafeContents(self.decode_value_as()?),
+        })
+    }
+
+    /// Returns the friendlyName attribute of this bag, if present.
+    pub fn friendly_name(&self) -> der::Result<Option<BmpString>> {
+        self.attribute(PKCS_9_AT_FRIENDLY_NAME)
+    }
+
+    /// Returns the localKeyId attribute of this bag, if present.
+    pub fn local_key_id(&self) -> der::Result<Option<OctetString>> {
+        self.attribute(PKCS_9_AT_LOCAL_KEY_ID)
+    }
+
+    /// Decodes the value of the attribute `oid`, which must be single-valued.
+    fn attribute<T>(&self, oid: ObjectIdentifier) -> der::Result<Option<T>>
+    where
+        T: for<'a> Choice<'a> + for<'a> DecodeValue<'a, Error = der::Error>,
+    {
+        let attribute = match self
+            .bag_attributes
+            .as_ref()
+            .and_then(|attributes| attributes.iter().find(|attribute| attribute.oid == oid))
+        {
+            Some(attribute) => attribute,
+            None => return Ok(None),
+        };
+        match attribute.values.get(0) {
+            Some(value) if attribute.values.len() == 1 => value.decode_as().map(Some),
+            _ => Err(der::Tag::Set.value_error()),
+        }
+    }
+
+    fn decode_value_as<T>(&self) -> der::Result<T>
+    where
+        T: for<'a> Decode<'a, Error = der::Error>,
+    {
+        let value = ContextSpecific::<T>::from_der(&self.bag_value)?;
+        if value.tag_number != der::TagNumber::N0 {
+            let tag = der::Tag::ContextSpecific {
+                constructed: true,
+                number: value.tag_number,
+            };
+            return Err(tag.value_error());
+        }
+        Ok(value.value)
+    }
+}
+
+/// The bagValue of a [`SafeBag`], decoded according to its bagId as described in
+/// [RFC 7292 Section 4.2].
+///
+/// ```text
+/// KeyBag ::= PrivateKeyInfo
+///
+/// PKCS8ShroudedKeyBag ::= EncryptedPrivateKeyInfo
+///
+/// SafeContents ::= SEQUENCE OF SafeBag
+/// ```
+///
+/// [RFC 7292 Section 4.2]: https://www.rfc-editor.org/rfc/rfc7292#section-4.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BagValue {
+    /// `keyBag`: a plaintext private key
+    Key(PrivateKeyInfo),
+
+    /// `pkcs8ShroudedKeyBag`: a password-encrypted private key
+    Pkcs8ShroudedKey(EncryptedPrivateKeyInfo),
+
+    /// `certBag`: a certificate
+    Cert(CertBag),
+
+    /// `crlBag`: a certificate revocation list
+    Crl(CrlBag),
+
+    /// `secretBag`: a user-defined secret
+    Secret(SecretBag),
+
+    /// `safeContentsBag`: a nested SafeContents
+    SafeContents(SafeContents),
+}
+
+impl BagValue {
+    /// Returns the type of this bag
+    pub fn bag_type(&self) -> BagType {
+        match self {
+            Self::Key(_) => BagType::Key,
+            Self::Pkcs8ShroudedKey(_) => BagType::Pkcs8,
+            Self::Cert(_) => BagType::Cert,
+            Self::Crl(_) => BagType::Crl,
+            Self::Secret(_) => BagType::Secret,
+            Self::SafeContents(_) => BagType::SafeContents,
+        }
+    }
+
+    /// Returns the DER encoding of this value tagged as `[0] EXPLICIT`
+    fn to_explicit_der(&self) -> der::Result<Vec<u8>> {
+        match self {
+            Self::Key(value) => explicit(value),
+            Self::Pkcs8ShroudedKey(value) => explicit(value),
+            Self::Cert(value) => explicit(value),
+            Self::Crl(value) => explicit(value),
+            Self::Secret(value) => explicit(value),
+            Self::SafeContents(value) => explicit(value),
+        }
+    }
+}
+
 /// Version for the PrivateKeyInfo structure as defined in [RFC 5208 Section 5].
 ///
 /// [RFC 5208 Section 5]: https://www.rfc-editor.org/rfc/rfc5208#section-5
@@ -130,3 +262,81 @@ pub struct PrivateKeyInfo {
     #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
     pub attributes: Option<Attributes>,
 }
+
+impl PrivateKeyInfo {
+    /// Returns whether `spki` is the public key of this private key.
+    ///
+    /// Only RSA keys, and EC keys carrying their public key, can be matched. `false` is
+    /// returned for other keys.
+    pub fn matches_public_key(&self, spki: &SubjectPublicKeyInfoOwned) -> der::Result<bool> {
+        if self.algorithm.oid != spki.algorithm.oid {
+            return Ok(false);
+        }
+
+        let private_key = self.private_key.as_bytes();
+        let public_key = spki.subject_public_key.raw_bytes();
+        match self.algorithm.oid {
+            RSA_ENCRYPTION => {
+                let private_key = RsaPrivateKey::from_der(private_key)?;
+                let public_key = RsaPublicKey::from_der(public_key)?;
+                Ok(private_key.modulus == public_key.modulus
+                    && private_key.public_exponent == public_key.public_exponent)
+            }
+            ID_EC_PUBLIC_KEY => {
+                let private_key = EcPrivateKey::from_der(private_key)?;
+                Ok(private_key
+                    .public_key
+                    .is_some_and(|key| key.raw_bytes() == public_key))
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// The `RSAPrivateKey` type is defined in [RFC 8017 Appendix A.1.2].
+///
+/// [RFC 8017 Appendix A.1.2]: https://www.rfc-editor.org/rfc/rfc8017#appendix-A.1.2
+#[derive(Sequence)]
+struct RsaPrivateKey<'a> {
+    version: UintRef<'a>,
+    modulus: UintRef<'a>,
+    public_exponent: UintRef<'a>,
+    private_exponent: UintRef<'a>,
+    prime1: UintRef<'a>,
+    prime2: UintRef<'a>,
+    exponent1: UintRef<'a>,
+    exponent2: UintRef<'a>,
+    coefficient: UintRef<'a>,
+    other_prime_infos: Option<AnyRef<'a>>,
+}
+
+/// The `RSAPublicKey` type is defined in [RFC 8017 Appendix A.1.1].
+///
+/// [RFC 8017 Appendix A.1.1]: https://www.rfc-editor.org/rfc/rfc8017#appendix-A.1.1
+#[derive(Sequence)]
+struct RsaPublicKey<'a> {
+    modulus: UintRef<'a>,
+    public_exponent: UintRef<'a>,
+}
+
+/// The `ECPrivateKey` type is defined in [RFC 5915 Section 3].
+///
+/// [RFC 5915 Section 3]: https://www.rfc-editor.org/rfc/rfc5915#section-3
+#[derive(Sequence)]
+struct EcPrivateKey<'a> {
+    version: u8,
+    private_key: OctetStringRef<'a>,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    parameters: Option<AnyRef<'a>>,
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    public_key: Option<BitStringRef<'a>>,
+}
+
+fn explicit<T: EncodeValue + Tagged>(value: &T) -> der::Result<Vec<u8>> {
+    ContextSpecificRef {
+        tag_number: der::TagNumber::N0,
+        tag_mode: der::TagMode::Explicit,
+        value,
+    }
+    .to_der()
+}
@@ -0,0 +1,70 @@
+//! SecretBag-related types
+
+use der::asn1::{ObjectIdentifier, OctetString};
+use der::{Any, Choice, Decode, DecodeValue, EncodeValue, Sequence, Tagged};
+
+use crate::bag_type::BagType;
+use crate::safe_bag::BagValue;
+
+/// The `SecretBag` type is defined in [RFC 7292 Section 4.2.5].
+///
+///```text
+///   SecretBag ::= SEQUENCE {
+///       secretTypeId   BAG-TYPE.&id ({SecretTypes}),
+///       secretValue    [0] EXPLICIT BAG-TYPE.&Type ({SecretTypes}
+///                          {@secretTypeId})
+///   }
+///
+///   SecretTypes BAG-TYPE ::= {
+///       ... -- For future extensions
+///   }
+///```
+///
+/// [RFC 7292 Section 4.2.5]: https://www.rfc-editor.org/rfc/rfc7292#section-4.2.5
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SecretBag {
+    pub secret_type_id: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    pub secret_value: Any,
+}
+
+impl SecretBag {
+    /// Returns a `SecretBag` carrying `secret_value` of the type `secret_type_id`
+    pub fn new(
+        secret_type_id: ObjectIdentifier,
+        secret_value: &(impl Tagged + EncodeValue),
+    ) -> der::Result<Self> {
+        Ok(Self {
+            secret_type_id,
+            secret_value: Any::encode_from(secret_value)?,
+        })
+    }
+
+    /// Decodes the secretValue as `T`
+    pub fn decode_as<'a, T>(&'a self) -> der::Result<T>
+    where
+        T: Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        self.secret_value.decode_as()
+    }
+
+    /// Decodes the secretValue as the value of a bag whose bagId is the secretTypeId.
+    ///
+    /// Java keystores use this to store secret keys: the secretTypeId is the
+    /// `pkcs8ShroudedKeyBag` OID, and the secretValue is an OCTET STRING containing the
+    /// DER encoding of the password-encrypted `PrivateKeyInfo` carrying the key.
+    pub fn bag_value(&self) -> der::Result<BagValue> {
+        let value = self.secret_value.decode_as::<OctetString>()?;
+        let der = value.as_bytes();
+
+        Ok(match BagType::try_from(self.secret_type_id)? {
+            BagType::Key => BagValue::Key(Decode::from_der(der)?),
+            BagType::Pkcs8 => BagValue::Pkcs8ShroudedKey(Decode::from_der(der)?),
+            BagType::Cert => BagValue::Cert(Decode::from_der(der)?),
+            BagType::Crl => BagValue::Crl(Decode::from_der(der)?),
+            BagType::Secret => BagValue::Secret(Decode::from_der(der)?),
+            BagType::SafeContents => BagValue::SafeContents(Decode::from_der(der)?),
+        })
+    }
+}
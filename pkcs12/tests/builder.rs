@@ -0,0 +1,240 @@
+#![cfg(feature = "builder")]
+
+use const_oid::db::rfc2985::{PKCS_9_AT_FRIENDLY_NAME, PKCS_9_AT_LOCAL_KEY_ID};
+use const_oid::db::rfc5912::{ID_MD_5, ID_SHA_256, ID_SHA_512};
+use der::asn1::{BmpString, ObjectIdentifier, OctetString};
+use der::{Decode, Encode};
+use pkcs12::authenticated_safe::SafeContentsInfo;
+use pkcs12::builder::PfxBuilder;
+use pkcs12::pfx::Pfx;
+use pkcs12::profile::Profile;
+use pkcs12::safe_bag::{BagValue, SafeContents};
+use pkcs12::secret_bag::SecretBag;
+use pkcs12::Error;
+use pkcs8::pkcs5::pbes2::PBES2_OID;
+use x509_cert::crl::CertificateList;
+use x509_cert::Certificate;
+
+const KEY: &[u8] = include_bytes!("examples/key.der");
+const CERT: &[u8] = include_bytes!("examples/cert.der");
+const CA_CERT: &[u8] = include_bytes!("examples/GoodCACert.der");
+const CRL: &[u8] = include_bytes!("../../x509-cert/tests/examples/GoodCACRL.crl");
+
+#[test]
+fn build_pfx() {
+    let key = pkcs8::PrivateKeyInfo::try_from(KEY).unwrap();
+    let pfx = PfxBuilder::new(&key, Certificate::from_der(CERT).unwrap())
+        .unwrap()
+        .with_ca_cert(Certificate::from_der(CA_CERT).unwrap())
+        .with_friendly_name("my key")
+        .unwrap()
+        .with_local_key_id(&[1, 2, 3, 4])
+        .unwrap()
+        .build("1234", &mut rand_core::OsRng)
+        .unwrap();
+
+    let pfx = Pfx::from_der(&pfx.to_der().unwrap()).unwrap();
+    let mac_data = pfx.mac_data.as_ref().unwrap();
+    assert_eq!(ID_SHA_256, mac_data.mac.algorithm.oid);
+    assert_eq!(2048, mac_data.iterations);
+    pfx.verify_mac("1234").unwrap();
+    assert_eq!(pfx.verify_mac("12345"), Err(Error::MacVerification));
+
+    let safe_contents = pfx.safe_contents().unwrap();
+    assert_eq!(2, safe_contents.len());
+
+    // the certificates are password-encrypted
+    let enc_data = match &safe_contents[0] {
+        SafeContentsInfo::EncryptedData(enc_data) => enc_data,
+        _ => panic!(),
+    };
+    let alg = &enc_data.enc_content_info.content_enc_alg;
+    assert_eq!(PBES2_OID, alg.oid);
+    let scheme = pkcs5::EncryptionScheme::from_der(&alg.to_der().unwrap()).unwrap();
+    let mut ciphertext = enc_data
+        .enc_content_info
+        .encrypted_content
+        .as_ref()
+        .unwrap()
+        .as_bytes()
+        .to_vec();
+    let plaintext = scheme.decrypt_in_place("1234", &mut ciphertext).unwrap();
+    let cert_bags = SafeContents::from_der(plaintext).unwrap();
+    assert_eq!(2, cert_bags.len());
+    for (bag, cert) in cert_bags.iter().zip([CERT, CA_CERT]) {
+        match bag.value().unwrap() {
+            BagValue::Cert(cert_bag) => assert_eq!(cert, cert_bag.cert_value.as_bytes()),
+            _ => panic!(),
+        }
+    }
+    assert!(cert_bags[1].bag_attributes.is_none());
+    assert_eq!(
+        "my key",
+        cert_bags[0].friendly_name().unwrap().unwrap().to_string()
+    );
+    assert_eq!(
+        &[1u8, 2, 3, 4],
+        cert_bags[0].local_key_id().unwrap().unwrap().as_bytes()
+    );
+
+    let attributes = cert_bags[0].bag_attributes.as_ref().unwrap();
+    assert_eq!(2, attributes.len());
+    for attribute in attributes.iter() {
+        let value = attribute.values.get(0).unwrap();
+        match attribute.oid {
+            PKCS_9_AT_FRIENDLY_NAME => assert_eq!(
+                "my key",
+                value.decode_as::<BmpString>().unwrap().to_string()
+            ),
+            PKCS_9_AT_LOCAL_KEY_ID => assert_eq!(
+                &[1u8, 2, 3, 4],
+                value.decode_as::<OctetString>().unwrap().as_bytes()
+            ),
+            _ => panic!(),
+        }
+    }
+
+    // the private key is shrouded
+    let key_bags = match &safe_contents[1] {
+        SafeContentsInfo::Data(key_bags) => key_bags,
+        _ => panic!(),
+    };
+    assert_eq!(1, key_bags.len());
+    assert_eq!(cert_bags[0].bag_attributes, key_bags[0].bag_attributes);
+    match key_bags[0].value().unwrap() {
+        BagValue::Pkcs8ShroudedKey(epki) => {
+            assert_eq!(PBES2_OID, epki.encryption_algorithm.oid);
+            let alg = epki.encryption_algorithm.to_der().unwrap();
+            let scheme = pkcs5::EncryptionScheme::from_der(&alg).unwrap();
+            let mut ciphertext = epki.encrypted_data.as_bytes().to_vec();
+            let plaintext = scheme.decrypt_in_place("1234", &mut ciphertext).unwrap();
+            assert_eq!(KEY, plaintext);
+        }
+        _ => panic!(),
+    }
+
+    let key_pairs = pfx.key_pairs("1234").unwrap();
+    assert_eq!(1, key_pairs.len());
+    assert_eq!(
+        CERT,
+        key_pairs[0].certificate.as_ref().unwrap().to_der().unwrap()
+    );
+    assert_eq!(
+        "my key",
+        key_pairs[0].friendly_name.as_ref().unwrap().to_string()
+    );
+}
+
+#[test]
+fn build_pfx_without_attributes() {
+    let key = pkcs8::PrivateKeyInfo::try_from(KEY).unwrap();
+    let pfx = PfxBuilder::new(&key, Certificate::from_der(CERT).unwrap())
+        .unwrap()
+        .build("", &mut rand_core::OsRng)
+        .unwrap();
+    pfx.verify_mac("").unwrap();
+
+    let safe_contents = pfx.safe_contents().unwrap();
+    match &safe_contents[1] {
+        SafeContentsInfo::Data(key_bags) => assert!(key_bags[0].bag_attributes.is_none()),
+        _ => panic!(),
+    }
+
+    // without localKeyId, the key is paired with the certificate by its public key
+    let key_pairs = pfx.key_pairs("").unwrap();
+    assert_eq!(1, key_pairs.len());
+    assert_eq!(
+        CERT,
+        key_pairs[0].certificate.as_ref().unwrap().to_der().unwrap()
+    );
+
+    let keys = pfx.private_keys("").unwrap();
+    assert_eq!(1, keys.len());
+    assert_eq!(KEY, keys[0].to_der().unwrap());
+    let certs = pfx.certificates("").unwrap();
+    assert_eq!(vec![Certificate::from_der(CERT).unwrap()], certs);
+}
+
+#[test]
+fn build_pfx_with_profile() {
+    let key = pkcs8::PrivateKeyInfo::try_from(KEY).unwrap();
+    let cert = Certificate::from_der(CERT).unwrap();
+    let pfx = PfxBuilder::new(&key, cert.clone())
+        .unwrap()
+        .build("1234", &mut rand_core::OsRng)
+        .unwrap();
+    assert_eq!(Some(Profile::modern()), Profile::from_pfx(&pfx).unwrap());
+
+    let profile = Profile {
+        iterations: 1000,
+        mac_digest: ID_SHA_512,
+        mac_iterations: 1000,
+        salt_len: 16,
+    };
+    let pfx = PfxBuilder::new(&key, cert.clone())
+        .unwrap()
+        .with_profile(profile)
+        .build("1234", &mut rand_core::OsRng)
+        .unwrap();
+    let pfx = Pfx::from_der(&pfx.to_der().unwrap()).unwrap();
+    assert_eq!(Some(profile), Profile::from_pfx(&pfx).unwrap());
+    pfx.verify_mac("1234").unwrap();
+    assert_eq!(vec![cert], pfx.certificates("1234").unwrap());
+
+    let profile = Profile {
+        mac_digest: ID_MD_5,
+        ..Profile::modern()
+    };
+    let result = PfxBuilder::new(&key, Certificate::from_der(CERT).unwrap())
+        .unwrap()
+        .with_profile(profile)
+        .build("1234", &mut rand_core::OsRng);
+    assert_eq!(result.err(), Some(Error::UnsupportedAlgorithm(ID_MD_5)));
+}
+
+#[test]
+fn build_pfx_with_crl_and_secret() {
+    let key = pkcs8::PrivateKeyInfo::try_from(KEY).unwrap();
+    let crl = CertificateList::from_der(CRL).unwrap();
+    let secret = SecretBag::new(
+        ObjectIdentifier::new_unwrap("1.3.6.1.4.1.22554.4.1"),
+        &OctetString::new([1, 2, 3, 4]).unwrap(),
+    )
+    .unwrap();
+    let pfx = PfxBuilder::new(&key, Certificate::from_der(CERT).unwrap())
+        .unwrap()
+        .with_crl(crl.clone())
+        .with_secret(secret.clone())
+        .build("1234", &mut rand_core::OsRng)
+        .unwrap();
+
+    let pfx = Pfx::from_der(&pfx.to_der().unwrap()).unwrap();
+    assert_eq!(vec![crl], pfx.crls("1234").unwrap());
+    let secrets = pfx.secrets("1234").unwrap();
+    assert_eq!(vec![secret], secrets);
+    assert_eq!(
+        &[1u8, 2, 3, 4],
+        secrets[0].decode_as::<OctetString>().unwrap().as_bytes()
+    );
+
+    // the CRL and the secret are stored along with the encrypted certificates
+    let safe_contents = pfx.safe_contents().unwrap();
+    assert!(matches!(
+        safe_contents[0],
+        SafeContentsInfo::EncryptedData(_)
+    ));
+    assert_eq!(3, safe_contents[0].decrypt("1234").unwrap().len());
+}
+
+#[test]
+fn reencode_safe_contents() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example2.pfx")).unwrap();
+    for content_info in pfx.authenticated_safe().unwrap() {
+        let data = content_info.content.decode_as::<OctetString>().unwrap();
+        let safe_contents = SafeContents::from_der(data.as_bytes()).unwrap();
+        assert_eq!(data.as_bytes(), safe_contents.to_der().unwrap().as_slice());
+
+        let info = SafeContentsInfo::try_from(&content_info).unwrap();
+        assert_eq!(content_info, info.to_content_info().unwrap());
+    }
+}
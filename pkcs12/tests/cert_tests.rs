@@ -6,12 +6,15 @@ use der::{Decode, Encode};
 use hex_literal::hex;
 
 use der::asn1::ContextSpecific;
-use pkcs12::authenticated_safe::AuthenticatedSafe;
+use pkcs12::authenticated_safe::{AuthenticatedSafe, SafeContentsInfo};
+use pkcs12::bag_type::BagType;
 use pkcs12::cert_type::CertBag;
+use pkcs12::crl_type::CrlBag;
 use pkcs12::pbe_params::Pbkdf2Params;
 use pkcs12::pfx::Pfx;
 use pkcs12::pfx::Version;
-use pkcs12::safe_bag::SafeContents;
+use pkcs12::safe_bag::{BagValue, SafeBag, SafeContents};
+use pkcs12::secret_bag::SecretBag;
 
 use pkcs8::pkcs5::pbes2::{AES_256_CBC_OID, HMAC_WITH_SHA256_OID, PBES2_OID, PBKDF2_OID};
 use pkcs8::{pkcs5, EncryptedPrivateKeyInfo};
@@ -647,3 +650,142 @@ fn decode_sample_pfx2() {
     );
     assert_eq!(2048, mac_data.iterations);
 }
+
+#[test]
+fn decode_sample_pfx_safe_bags() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example2.pfx")).unwrap();
+    let safe_contents = pfx.safe_contents().unwrap();
+    assert_eq!(2, safe_contents.len());
+
+    let bags = match &safe_contents[0] {
+        SafeContentsInfo::Data(bags) => bags,
+        _ => panic!(),
+    };
+    assert_eq!(1, bags.len());
+    assert_eq!(BagType::Cert, bags[0].bag_type().unwrap());
+    match bags[0].value().unwrap() {
+        BagValue::Cert(cert_bag) => {
+            assert_eq!(pkcs12::PKCS_12_X509_CERT_OID, cert_bag.cert_id);
+            assert_eq!(
+                include_bytes!("examples/cert.der"),
+                cert_bag.cert_value.as_bytes()
+            );
+        }
+        _ => panic!(),
+    }
+
+    let bags = match &safe_contents[1] {
+        SafeContentsInfo::Data(bags) => bags,
+        _ => panic!(),
+    };
+    assert_eq!(1, bags.len());
+    let attributes = bags[0].bag_attributes.as_ref().unwrap();
+    assert_eq!(1, attributes.len());
+    let value = bags[0].value().unwrap();
+    assert_eq!(BagType::Pkcs8, value.bag_type());
+    match value {
+        BagValue::Pkcs8ShroudedKey(epki) => {
+            assert_eq!(PBES2_OID, epki.encryption_algorithm.oid);
+            let alg = epki.encryption_algorithm.to_der().unwrap();
+            let scheme = pkcs5::EncryptionScheme::from_der(&alg).unwrap();
+            let mut ciphertext = epki.encrypted_data.as_bytes().to_vec();
+            let plaintext = scheme.decrypt_in_place("1234", &mut ciphertext).unwrap();
+            assert_eq!(include_bytes!("examples/key.der"), plaintext);
+        }
+        _ => panic!(),
+    }
+
+    // the certificates of example.pfx are password-encrypted
+    let pfx = Pfx::from_der(include_bytes!("examples/example.pfx")).unwrap();
+    let safe_contents = pfx.safe_contents().unwrap();
+    let enc_data = match &safe_contents[0] {
+        SafeContentsInfo::EncryptedData(enc_data) => enc_data,
+        _ => panic!(),
+    };
+    let params = enc_data
+        .enc_content_info
+        .content_enc_alg
+        .parameters
+        .as_ref()
+        .unwrap()
+        .to_der()
+        .unwrap();
+    let scheme =
+        pkcs5::EncryptionScheme::from(pkcs8::pkcs5::pbes2::Parameters::from_der(&params).unwrap());
+    let mut ciphertext = enc_data
+        .enc_content_info
+        .encrypted_content
+        .as_ref()
+        .unwrap()
+        .as_bytes()
+        .to_vec();
+    let plaintext = scheme.decrypt_in_place("", &mut ciphertext).unwrap();
+    let bags = SafeContents::from_der(plaintext).unwrap();
+    assert!(matches!(bags[0].value().unwrap(), BagValue::Cert(_)));
+    assert!(matches!(safe_contents[1], SafeContentsInfo::Data(_)));
+}
+
+#[test]
+fn decode_sample_pfx_attributes() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example7.pfx")).unwrap();
+    let safe_contents = pfx.safe_contents().unwrap();
+    let cert_bags = match &safe_contents[0] {
+        SafeContentsInfo::Data(cert_bags) => cert_bags,
+        _ => panic!(),
+    };
+    assert_eq!(
+        hex!("E1 28 46 4B E7 34 D0 F8 4B D9 28 51 6C 50 F1 5A 18 B5 2B 96"),
+        cert_bags[0].local_key_id().unwrap().unwrap().as_bytes()
+    );
+    assert!(cert_bags[0].friendly_name().unwrap().is_none());
+    assert!(cert_bags[1].local_key_id().unwrap().is_none());
+
+    // the private key is paired with the certificate of the end entity, not the CA
+    let key = match &safe_contents[1] {
+        SafeContentsInfo::Data(key_bags) => match key_bags[0].value().unwrap() {
+            BagValue::Key(key) => key,
+            _ => panic!(),
+        },
+        _ => panic!(),
+    };
+    let matches: Vec<bool> = cert_bags
+        .iter()
+        .map(|bag| match bag.value().unwrap() {
+            BagValue::Cert(cert_bag) => {
+                let cert = cert_bag.certificate().unwrap();
+                key.matches_public_key(&cert.tbs_certificate.subject_public_key_info)
+                    .unwrap()
+            }
+            _ => panic!(),
+        })
+        .collect();
+    assert_eq!(vec![true, false], matches);
+}
+
+#[test]
+fn crl_bag() {
+    let der = include_bytes!("../../x509-cert/tests/examples/GoodCACRL.crl");
+    let crl = x509_cert::crl::CertificateList::from_der(der).unwrap();
+    let crl_bag = CrlBag::new(&crl).unwrap();
+    assert_eq!(pkcs12::PKCS_12_X509_CRL_OID, crl_bag.crl_id);
+    assert_eq!(der, crl_bag.crl_value.as_bytes());
+    assert_eq!(crl, crl_bag.crl().unwrap());
+
+    let bag = SafeBag::new(&BagValue::Crl(crl_bag.clone()), None).unwrap();
+    let bag = SafeBag::from_der(&bag.to_der().unwrap()).unwrap();
+    assert_eq!(BagType::Crl, bag.bag_type().unwrap());
+    assert_eq!(BagValue::Crl(crl_bag), bag.value().unwrap());
+}
+
+#[test]
+fn secret_bag() {
+    let secret = OctetString::new([1, 2, 3, 4]).unwrap();
+    let secret_bag = SecretBag::new(pkcs12::PKCS_12_KEY_BAG_OID, &secret).unwrap();
+    assert_eq!(secret, secret_bag.decode_as::<OctetString>().unwrap());
+    // the secretValue must be a keyBag
+    assert!(secret_bag.bag_value().is_err());
+
+    let bag = SafeBag::new(&BagValue::Secret(secret_bag.clone()), None).unwrap();
+    let bag = SafeBag::from_der(&bag.to_der().unwrap()).unwrap();
+    assert_eq!(BagValue::Secret(secret_bag), bag.value().unwrap());
+}
@@ -0,0 +1,200 @@
+#![cfg(any(feature = "legacy", feature = "pbes2"))]
+
+use der::asn1::OctetString;
+use der::{Decode, Encode};
+use pkcs12::pbe_params::{EncryptedPrivateKeyInfo, Pkcs12PbeParams};
+use pkcs12::pfx::Pfx;
+use pkcs12::Error;
+use spki::AlgorithmIdentifierOwned;
+
+const KEY: &[u8] = include_bytes!("examples/key.der");
+const CERT: &[u8] = include_bytes!("examples/cert.der");
+const EE_KEY: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.key");
+const EE_CERT: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.crt");
+const CA_CERT: &[u8] = include_bytes!("examples/GoodCACert.der");
+
+/// Checks that the PFX carries the private key `key` and the certificates `certs`
+fn check_pfx(bytes: &[u8], password: &str, key: &[u8], certs: &[&[u8]]) {
+    let pfx = Pfx::from_der(bytes).unwrap();
+
+    let keys = pfx.private_keys(password).unwrap();
+    assert_eq!(1, keys.len());
+    let expected = pkcs8::PrivateKeyInfo::try_from(key).unwrap();
+    assert_eq!(expected.algorithm.oid, keys[0].algorithm.oid);
+    assert_eq!(expected.private_key, keys[0].private_key.as_bytes());
+
+    let decrypted: Vec<Vec<u8>> = pfx
+        .certificates(password)
+        .unwrap()
+        .iter()
+        .map(|cert| cert.to_der().unwrap())
+        .collect();
+    assert_eq!(certs, decrypted);
+}
+
+#[cfg(feature = "pbes2")]
+#[test]
+fn decrypt_pbes2() {
+    check_pfx(include_bytes!("examples/example.pfx"), "", KEY, &[CERT]);
+    check_pfx(
+        include_bytes!("examples/example2.pfx"),
+        "1234",
+        KEY,
+        &[CERT],
+    );
+    check_pfx(
+        include_bytes!("examples/example4.pfx"),
+        "1234",
+        KEY,
+        &[CERT],
+    );
+    check_pfx(
+        include_bytes!("examples/example17.pfx"),
+        "1234",
+        KEY,
+        &[CERT],
+    );
+    check_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE_windows_aes.p12.pfx"),
+        "password",
+        EE_KEY,
+        &[EE_CERT],
+    );
+}
+
+#[test]
+fn decrypt_key_bag() {
+    check_pfx(
+        include_bytes!("examples/example7.pfx"),
+        "1234",
+        EE_KEY,
+        &[EE_CERT, CA_CERT],
+    );
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn decrypt_legacy() {
+    use pkcs12::safe_bag::BagValue;
+
+    check_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE.p12"),
+        "password",
+        EE_KEY,
+        &[EE_CERT],
+    );
+    check_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE_macos.p12"),
+        "password",
+        EE_KEY,
+        &[EE_CERT],
+    );
+
+    let pfx = Pfx::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.p12")).unwrap();
+    assert_eq!(pfx.certificates("wrong"), Err(Error::Decryption));
+    let key_bags = pfx.safe_contents().unwrap()[1].decrypt("wrong").unwrap();
+    match key_bags[0].value().unwrap() {
+        BagValue::Pkcs8ShroudedKey(key) => assert_eq!(key.decrypt("wrong"), Err(Error::Decryption)),
+        _ => panic!(),
+    }
+}
+
+#[cfg(all(feature = "legacy", feature = "pbes2"))]
+#[test]
+fn decrypt_legacy_and_pbes2() {
+    // the certificates are encrypted with 3DES and the key with PBES2
+    check_pfx(
+        include_bytes!("examples/example14.pfx"),
+        "1234",
+        KEY,
+        &[CERT],
+    );
+}
+
+#[test]
+fn unsupported_scheme() {
+    let params = Pkcs12PbeParams {
+        salt: OctetString::new([1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+        iterations: 2048,
+    };
+    let key = EncryptedPrivateKeyInfo {
+        encryption_algorithm: AlgorithmIdentifierOwned {
+            oid: pkcs12::PKCS_12_PBE_WITH_SHAAND128_BIT_RC4,
+            parameters: Some(der::Any::encode_from(&params).unwrap()),
+        },
+        encrypted_data: OctetString::new([0u8; 16]).unwrap(),
+    };
+    assert_eq!(
+        key.decrypt("1234"),
+        Err(Error::UnsupportedAlgorithm(
+            pkcs12::PKCS_12_PBE_WITH_SHAAND128_BIT_RC4
+        ))
+    );
+}
+
+#[test]
+fn nested_safe_contents() {
+    use pkcs12::authenticated_safe::SafeContentsInfo;
+    use pkcs12::safe_bag::{BagValue, SafeBag};
+    use pkcs12::secret_bag::SecretBag;
+
+    let secret = |n: u8| {
+        let value = BagValue::Secret(SecretBag::new(pkcs12::PKCS_12_KEY_BAG_OID, &n).unwrap());
+        SafeBag::new(&value, None).unwrap()
+    };
+
+    let mut nested = vec![secret(1)];
+    for _ in 0..1000 {
+        nested = vec![SafeBag::new(&BagValue::SafeContents(nested), None).unwrap()];
+    }
+
+    let safe_contents = vec![secret(0), nested.remove(0), secret(2)];
+    let auth_safe = vec![SafeContentsInfo::Data(safe_contents)
+        .to_content_info()
+        .unwrap()];
+    let pfx = Pfx::new(&auth_safe).unwrap();
+
+    let bags = pfx.bags("").unwrap();
+    assert_eq!(bags, [secret(0), secret(1), secret(2)]);
+}
+
+#[test]
+fn key_pairs() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example7.pfx")).unwrap();
+    let key_pairs = pfx.key_pairs("1234").unwrap();
+    assert_eq!(1, key_pairs.len());
+    let certificate = key_pairs[0].certificate.as_ref().unwrap();
+    assert_eq!(EE_CERT, certificate.to_der().unwrap());
+    assert!(key_pairs[0].friendly_name.is_none());
+}
+
+#[cfg(feature = "pbes2")]
+#[test]
+fn java_secret_key() {
+    use pkcs12::safe_bag::BagValue;
+
+    let pfx = Pfx::from_der(include_bytes!("examples/java_secret_key.p12")).unwrap();
+    let bags = pfx.bags("123456").unwrap();
+    assert_eq!(1, bags.len());
+    assert_eq!(
+        "mysecret",
+        bags[0].friendly_name().unwrap().unwrap().to_string()
+    );
+
+    let secrets = pfx.secrets("123456").unwrap();
+    assert_eq!(1, secrets.len());
+    assert_eq!(pkcs12::PKCS_12_PKCS8_KEY_BAG_OID, secrets[0].secret_type_id);
+    let key = match secrets[0].bag_value().unwrap() {
+        BagValue::Pkcs8ShroudedKey(key) => key.decrypt("123456").unwrap(),
+        _ => panic!(),
+    };
+    assert_eq!(
+        der::asn1::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1"),
+        key.algorithm.oid
+    );
+    assert_eq!(
+        hex_literal::hex!("97 A0 A5 AE F9 C9 9A 9D 59 FB 74 DB 77 DA 98 0A"),
+        key.private_key.as_bytes()
+    );
+    assert!(pfx.private_keys("123456").unwrap().is_empty());
+}
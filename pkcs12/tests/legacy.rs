@@ -0,0 +1,131 @@
+#![cfg(feature = "legacy")]
+
+use der::asn1::OctetString;
+use der::Decode;
+use pkcs12::authenticated_safe::SafeContentsInfo;
+use pkcs12::pbe::{Algorithm, EncryptionScheme};
+use pkcs12::pbe_params::Pkcs12PbeParams;
+use pkcs12::pfx::Pfx;
+use pkcs12::safe_bag::{BagValue, SafeContents};
+use pkcs12::Error;
+use pkcs8::PrivateKeyInfo;
+
+const KEY: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.key");
+const CERT: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.crt");
+
+/// Decrypts the certificates and the key of a PFX protected with legacy schemes
+fn decrypt_pfx(bytes: &[u8], cert_scheme: EncryptionScheme) {
+    let pfx = Pfx::from_der(bytes).unwrap();
+    let (mut found_cert, mut found_key) = (false, false);
+    for safe_contents in pfx.safe_contents().unwrap() {
+        match safe_contents {
+            SafeContentsInfo::EncryptedData(enc_data) => {
+                let alg = Algorithm::try_from(&enc_data.enc_content_info.content_enc_alg).unwrap();
+                assert_eq!(cert_scheme, alg.encryption);
+                let ciphertext = enc_data.enc_content_info.encrypted_content.unwrap();
+                assert_eq!(
+                    alg.decrypt("wrong", ciphertext.as_bytes()),
+                    Err(Error::Decryption)
+                );
+                let plaintext = alg.decrypt("password", ciphertext.as_bytes()).unwrap();
+                for bag in SafeContents::from_der(&plaintext).unwrap() {
+                    if let BagValue::Cert(cert_bag) = bag.value().unwrap() {
+                        found_cert |= cert_bag.cert_value.as_bytes() == CERT;
+                    }
+                }
+            }
+            SafeContentsInfo::Data(bags) => {
+                for bag in bags {
+                    if let BagValue::Pkcs8ShroudedKey(epki) = bag.value().unwrap() {
+                        let alg = Algorithm::try_from(&epki.encryption_algorithm).unwrap();
+                        assert_eq!(
+                            EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc,
+                            alg.encryption
+                        );
+                        let plaintext = alg
+                            .decrypt("password", epki.encrypted_data.as_bytes())
+                            .unwrap();
+                        let key = PrivateKeyInfo::from_der(&plaintext).unwrap();
+                        let expected = PrivateKeyInfo::from_der(KEY).unwrap();
+                        assert_eq!(expected.private_key, key.private_key);
+                        found_key = true;
+                    }
+                }
+            }
+            _ => panic!("unexpected safe contents"),
+        }
+    }
+    assert!(found_cert && found_key);
+}
+
+#[test]
+fn decrypt_3des() {
+    decrypt_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE.p12"),
+        EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc,
+    );
+    decrypt_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE_windows_tdes.p12.pfx"),
+        EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc,
+    );
+}
+
+#[test]
+fn decrypt_rc2_40() {
+    decrypt_pfx(
+        include_bytes!("examples/ValidCertificatePathTest1EE_macos.p12"),
+        EncryptionScheme::PbeWithShaAnd40BitRc2Cbc,
+    );
+}
+
+#[test]
+fn encrypt_roundtrip() {
+    for encryption in [
+        EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc,
+        EncryptionScheme::PbeWithShaAnd2KeyTripleDesCbc,
+        EncryptionScheme::PbeWithShaAnd128BitRc2Cbc,
+        EncryptionScheme::PbeWithShaAnd40BitRc2Cbc,
+    ] {
+        let alg = Algorithm {
+            encryption,
+            parameters: Pkcs12PbeParams {
+                salt: OctetString::new([1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+                iterations: 2048,
+            },
+        };
+        let ciphertext = alg.encrypt("secret", KEY).unwrap();
+        assert_eq!(0, ciphertext.len() % 8);
+        assert!(ciphertext.len() > KEY.len());
+        assert_eq!(KEY, alg.decrypt("secret", &ciphertext).unwrap().as_slice());
+    }
+}
+
+#[test]
+fn max_iterations() {
+    let mut alg = Algorithm {
+        encryption: EncryptionScheme::PbeWithShaAnd3KeyTripleDesCbc,
+        parameters: Pkcs12PbeParams {
+            salt: OctetString::new([1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            iterations: Pkcs12PbeParams::MAX_ITERATIONS + 1,
+        },
+    };
+    assert!(matches!(alg.encrypt("secret", KEY), Err(Error::Asn1(_))));
+
+    // Untrusted parameters with an excessive iteration count are rejected up front
+    alg.parameters.iterations = i32::MAX;
+    assert!(matches!(
+        alg.decrypt("secret", &[0; 16]),
+        Err(Error::Asn1(_))
+    ));
+}
+
+#[test]
+fn unsupported_schemes() {
+    assert_eq!(
+        EncryptionScheme::try_from(pkcs12::PKCS_12_PBE_WITH_SHAAND128_BIT_RC4),
+        Err(Error::UnsupportedAlgorithm(
+            pkcs12::PKCS_12_PBE_WITH_SHAAND128_BIT_RC4
+        ))
+    );
+    assert!(EncryptionScheme::try_from(pkcs12::PKCS_12_CERT_BAG_OID).is_err());
+}
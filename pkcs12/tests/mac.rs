@@ -0,0 +1,70 @@
+#![cfg(feature = "mac")]
+
+use const_oid::db::rfc5912::{ID_MD_5, ID_SHA_1, ID_SHA_256, ID_SHA_384, ID_SHA_512};
+use der::asn1::ObjectIdentifier;
+use der::{Decode, Encode};
+use pkcs12::mac_data::MacData;
+use pkcs12::pfx::Pfx;
+use pkcs12::Error;
+
+#[test]
+fn verify_mac() {
+    let cases: [(&[u8], &str, ObjectIdentifier); 6] = [
+        (include_bytes!("examples/example.pfx"), "", ID_SHA_256),
+        (include_bytes!("examples/example2.pfx"), "1234", ID_SHA_256),
+        (include_bytes!("examples/example4.pfx"), "1234", ID_SHA_1),
+        (include_bytes!("examples/example8.pfx"), "1234", ID_SHA_384),
+        (include_bytes!("examples/example9.pfx"), "1234", ID_SHA_512),
+        (
+            include_bytes!("examples/ValidCertificatePathTest1EE_macos.p12"),
+            "password",
+            ID_SHA_1,
+        ),
+    ];
+    for (bytes, password, digest) in cases {
+        let pfx = Pfx::from_der(bytes).unwrap();
+        assert_eq!(digest, pfx.mac_data.as_ref().unwrap().mac.algorithm.oid);
+        pfx.verify_mac(password).unwrap();
+        assert_eq!(pfx.verify_mac("wrong"), Err(Error::MacVerification));
+    }
+}
+
+#[test]
+fn set_mac() {
+    let bytes = include_bytes!("examples/example2.pfx");
+    let mut pfx = Pfx::from_der(bytes).unwrap();
+    let mac_data = pfx.mac_data.take().unwrap();
+    assert_eq!(pfx.verify_mac("1234"), Err(Error::MissingMac));
+
+    // recomputing the MAC with the same parameters yields the original encoding
+    pfx.set_mac(ID_SHA_256, "1234", mac_data.mac_salt.as_bytes(), 2048)
+        .unwrap();
+    assert_eq!(pfx.mac_data, Some(mac_data));
+    assert_eq!(bytes, pfx.to_der().unwrap().as_slice());
+
+    pfx.set_mac(ID_SHA_1, "secret", &[1, 2, 3, 4, 5, 6, 7, 8], 1)
+        .unwrap();
+    let pfx = Pfx::from_der(&pfx.to_der().unwrap()).unwrap();
+    pfx.verify_mac("secret").unwrap();
+    assert_eq!(pfx.verify_mac("1234"), Err(Error::MacVerification));
+
+    let mut pfx = pfx;
+    assert_eq!(
+        pfx.set_mac(ID_MD_5, "secret", &[1, 2, 3, 4, 5, 6, 7, 8], 1),
+        Err(Error::UnsupportedAlgorithm(ID_MD_5))
+    );
+    assert!(pfx.set_mac(ID_SHA_256, "secret", &[1, 2, 3, 4], 0).is_err());
+}
+
+#[test]
+fn max_iterations() {
+    let mut pfx = Pfx::from_der(include_bytes!("examples/example2.pfx")).unwrap();
+    let max = MacData::MAX_ITERATIONS;
+    assert!(pfx
+        .set_mac(ID_SHA_256, "1234", &[1, 2, 3, 4], max + 1)
+        .is_err());
+
+    // An untrusted PFX with an excessive iteration count is rejected up front
+    pfx.mac_data.as_mut().unwrap().iterations = i32::MAX;
+    assert!(matches!(pfx.verify_mac("1234"), Err(Error::Asn1(_))));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "pbes2")]
+
+use der::Decode;
+use pkcs12::pfx::Pfx;
+use pkcs12::profile::Profile;
+
+fn profile(bytes: &[u8]) -> Option<Profile> {
+    Profile::from_pfx(&Pfx::from_der(bytes).unwrap()).unwrap()
+}
+
+#[test]
+fn modern_profile() {
+    let modern = Some(Profile::modern());
+    assert_eq!(modern, profile(include_bytes!("examples/example.pfx")));
+    assert_eq!(modern, profile(include_bytes!("examples/example2.pfx")));
+    assert_eq!(modern, profile(include_bytes!("examples/example17.pfx")));
+    assert_eq!(Profile::modern(), Profile::default());
+}
+
+#[test]
+fn custom_profile() {
+    let expected = Profile {
+        iterations: 1,
+        mac_iterations: 1,
+        ..Profile::modern()
+    };
+    assert_eq!(
+        Some(expected),
+        profile(include_bytes!("examples/example6.pfx"))
+    );
+}
+
+#[test]
+fn no_profile() {
+    // the certificates are encrypted with AES-192-CBC
+    assert_eq!(None, profile(include_bytes!("examples/example4.pfx")));
+    // the PFX lacks the macData
+    assert_eq!(None, profile(include_bytes!("examples/example5.pfx")));
+    // the key and the certificates are encrypted with legacy schemes
+    assert_eq!(
+        None,
+        profile(include_bytes!("examples/ValidCertificatePathTest1EE.p12"))
+    );
+}
@@ -22,7 +22,7 @@ pub enum Error {
     /// Encryption Failed
     EncryptFailed,
 
-    /// Pbes1 support is limited to parsing; encryption/decryption is not supported (won't fix)
+    /// Pbes1 encryption is not supported (won't fix), and decryption requires the `legacy` feature
     #[cfg(feature = "pbes2")]
     NoPbes1CryptSupport,
 
@@ -46,7 +46,7 @@ impl fmt::Display for Error {
             Error::EncryptFailed => f.write_str("PKCS#5 encryption failed"),
             #[cfg(feature = "pbes2")]
             Error::NoPbes1CryptSupport => {
-                f.write_str("PKCS#5 encryption/decryption unsupported for PBES1 (won't fix)")
+                f.write_str("PKCS#5 encryption unsupported for PBES1 (won't fix), decryption requires the `legacy` feature")
             }
             Error::UnsupportedAlgorithm { oid } => {
                 write!(f, "PKCS#5 algorithm {} is unsupported", oid)
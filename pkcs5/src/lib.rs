@@ -29,6 +29,9 @@
 #[cfg(all(feature = "alloc", feature = "pbes2"))]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod error;
 
 pub mod pbes1;
@@ -67,10 +70,15 @@ pub enum EncryptionScheme {
 impl EncryptionScheme {
     /// Attempt to decrypt the given ciphertext, allocating and returning a
     /// byte vector containing the plaintext.
+    ///
+    /// PBES1 ciphertexts can only be decrypted with the `legacy` feature.
     #[cfg(all(feature = "alloc", feature = "pbes2"))]
     pub fn decrypt(&self, password: impl AsRef<[u8]>, ciphertext: &[u8]) -> Result<Vec<u8>> {
         match self {
             Self::Pbes2(params) => params.decrypt(password, ciphertext),
+            #[cfg(feature = "legacy")]
+            Self::Pbes1(alg) => alg.decrypt(password, ciphertext),
+            #[cfg(not(feature = "legacy"))]
             Self::Pbes1(_) => Err(Error::NoPbes1CryptSupport),
         }
     }
@@ -81,6 +89,8 @@ impl EncryptionScheme {
     /// Returns an error if the algorithm specified in this scheme's parameters
     /// is unsupported, or if the ciphertext is malformed (e.g. not a multiple
     /// of a block mode's padding)
+    ///
+    /// PBES1 ciphertexts can only be decrypted with the `legacy` feature.
     #[cfg(feature = "pbes2")]
     pub fn decrypt_in_place<'a>(
         &self,
@@ -89,12 +99,17 @@ impl EncryptionScheme {
     ) -> Result<&'a [u8]> {
         match self {
             Self::Pbes2(params) => params.decrypt_in_place(password, buffer),
+            #[cfg(feature = "legacy")]
+            Self::Pbes1(alg) => alg.decrypt_in_place(password, buffer),
+            #[cfg(not(feature = "legacy"))]
             Self::Pbes1(_) => Err(Error::NoPbes1CryptSupport),
         }
     }
 
     /// Encrypt the given plaintext, allocating and returning a vector
     /// containing the ciphertext.
+    ///
+    /// PBES1 encryption isn't supported.
     #[cfg(all(feature = "alloc", feature = "pbes2"))]
     pub fn encrypt(&self, password: impl AsRef<[u8]>, plaintext: &[u8]) -> Result<Vec<u8>> {
         match self {
@@ -1,5 +1,14 @@
 //! Password-Based Encryption Scheme 1 as defined in [RFC 8018 Section 6.1].
 //!
+//! PBES1 algorithms can be decrypted with the `legacy` feature, e.g. to migrate
+//! keys encrypted by older software to PBES2. Encryption isn't supported.
+//!
+//! ## ⚠️ Security Warning
+//!
+//! PBES1 relies on DES or RC2 with 64-bit keys, derived with MD2, MD5 or
+//! SHA-1, and is completely broken. It should only be used to decrypt existing
+//! keys.
+//!
 //! [RFC 8018 Section 6.1]: https://tools.ietf.org/html/rfc8018#section-6.1
 
 use crate::AlgorithmIdentifierRef;
@@ -8,6 +17,16 @@ use der::{
     Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Writer,
 };
 
+#[cfg(feature = "legacy")]
+use {
+    crate::{Error, Result},
+    cbc::cipher::{block_padding::Pkcs7, BlockCipher, BlockDecryptMut, InnerIvInit, KeyInit},
+    sha1::Digest,
+};
+
+#[cfg(all(feature = "alloc", feature = "legacy"))]
+use alloc::vec::Vec;
+
 /// `pbeWithMD2AndDES-CBC` Object Identifier (OID).
 pub const PBE_WITH_MD2_AND_DES_CBC_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.1");
@@ -35,6 +54,15 @@ pub const PBE_WITH_SHA1_AND_RC2_CBC_OID: ObjectIdentifier =
 /// Length of a PBES1 salt (as defined in the `PBEParameter` ASN.1 message).
 pub const SALT_LENGTH: usize = 8;
 
+/// Length of the key and of the IV of both DES and RC2, which are derived from
+/// the 16-byte output of PBKDF1
+#[cfg(feature = "legacy")]
+const KEY_LENGTH: usize = 8;
+
+/// Effective key length of RC2 in bits as specified by PBES1
+#[cfg(feature = "legacy")]
+const RC2_EFFECTIVE_KEY_BITS: usize = 64;
+
 /// Password-Based Encryption Scheme 1 algorithms as defined in [RFC 8018 Appendix A.C].
 ///
 /// ```text
@@ -66,6 +94,88 @@ impl Algorithm {
     }
 }
 
+#[cfg(feature = "legacy")]
+impl Algorithm {
+    /// Attempt to decrypt the given ciphertext, allocating and returning a
+    /// byte vector containing the plaintext.
+    #[cfg(feature = "alloc")]
+    pub fn decrypt(&self, password: impl AsRef<[u8]>, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let mut buffer = ciphertext.to_vec();
+        let pt_len = self.decrypt_in_place(password, &mut buffer)?.len();
+        buffer.truncate(pt_len);
+        Ok(buffer)
+    }
+
+    /// Attempt to decrypt the given ciphertext in-place using the key and IV
+    /// derived from the provided password with PBKDF1.
+    ///
+    /// Returns an error if the iteration count is zero, or if the ciphertext
+    /// is malformed (e.g. not a multiple of the block size, or incorrectly
+    /// padded, as when the password is wrong)
+    pub fn decrypt_in_place<'a>(
+        &self,
+        password: impl AsRef<[u8]>,
+        buffer: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        let password = password.as_ref();
+        let derived_key = match self.encryption.digest() {
+            DigestAlgorithm::Md2 => self.pbkdf1::<md2::Md2>(password)?,
+            DigestAlgorithm::Md5 => self.pbkdf1::<md5::Md5>(password)?,
+            DigestAlgorithm::Sha1 => self.pbkdf1::<sha1::Sha1>(password)?,
+        };
+        let (key, iv) = derived_key.split_at(KEY_LENGTH);
+
+        match self.encryption.cipher() {
+            SymmetricCipher::DesCbc => {
+                let cipher = des::Des::new_from_slice(key).map_err(|_| self.invalid_params())?;
+                self.cbc_decrypt(cipher, iv, buffer)
+            }
+            SymmetricCipher::Rc2Cbc => {
+                let cipher = rc2::Rc2::new_with_eff_key_len(key, RC2_EFFECTIVE_KEY_BITS);
+                self.cbc_decrypt(cipher, iv, buffer)
+            }
+        }
+    }
+
+    /// Derives the key and the IV from `password` with PBKDF1 as defined in
+    /// [RFC 8018 Section 5.1]
+    ///
+    /// [RFC 8018 Section 5.1]: https://tools.ietf.org/html/rfc8018#section-5.1
+    fn pbkdf1<D: Digest>(&self, password: &[u8]) -> Result<[u8; 2 * KEY_LENGTH]> {
+        if self.parameters.iteration_count == 0 {
+            return Err(self.invalid_params());
+        }
+
+        let mut digest = D::new()
+            .chain_update(password)
+            .chain_update(self.parameters.salt)
+            .finalize();
+        for _ in 1..self.parameters.iteration_count {
+            digest = D::digest(&digest);
+        }
+
+        let mut derived_key = [0u8; 2 * KEY_LENGTH];
+        derived_key.copy_from_slice(&digest[..2 * KEY_LENGTH]);
+        Ok(derived_key)
+    }
+
+    fn cbc_decrypt<'a, C: BlockDecryptMut + BlockCipher>(
+        &self,
+        cipher: C,
+        iv: &[u8],
+        buffer: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        cbc::Decryptor::<C>::inner_iv_slice_init(cipher, iv)
+            .map_err(|_| self.invalid_params())?
+            .decrypt_padded_mut::<Pkcs7>(buffer)
+            .map_err(|_| Error::DecryptFailed)
+    }
+
+    fn invalid_params(&self) -> Error {
+        Error::AlgorithmParametersInvalid { oid: self.oid() }
+    }
+}
+
 impl<'a> DecodeValue<'a> for Algorithm {
     type Error = der::Error;
 
@@ -7,6 +7,12 @@ mod kdf;
 #[cfg(feature = "pbes2")]
 mod encryption;
 
+#[cfg(all(feature = "pbes2", feature = "rand_core"))]
+mod builder;
+
+#[cfg(all(feature = "pbes2", feature = "rand_core"))]
+pub use self::builder::Pbes2ParametersBuilder;
+
 pub use self::kdf::{
     Kdf, Pbkdf2Params, Pbkdf2Prf, Salt, ScryptParams, HMAC_WITH_SHA1_OID, HMAC_WITH_SHA256_OID,
     PBKDF2_OID, SCRYPT_OID,
@@ -18,6 +24,9 @@ use der::{
     Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Writer,
 };
 
+#[cfg(feature = "aes-gcm")]
+use spki::AlgorithmIdentifier;
+
 #[cfg(feature = "rand_core")]
 use rand_core::CryptoRngCore;
 
@@ -39,6 +48,24 @@ pub const AES_192_CBC_OID: ObjectIdentifier =
 pub const AES_256_CBC_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.42");
 
+/// 128-bit Advanced Encryption Standard (AES) algorithm with Galois/Counter
+/// Mode (GCM) of operation.
+#[cfg(feature = "aes-gcm")]
+pub const AES_128_GCM_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.6");
+
+/// 192-bit Advanced Encryption Standard (AES) algorithm with Galois/Counter
+/// Mode (GCM) of operation.
+#[cfg(feature = "aes-gcm")]
+pub const AES_192_GCM_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.26");
+
+/// 256-bit Advanced Encryption Standard (AES) algorithm with Galois/Counter
+/// Mode (GCM) of operation.
+#[cfg(feature = "aes-gcm")]
+pub const AES_256_GCM_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.46");
+
 /// DES operating in CBC mode
 #[cfg(feature = "des-insecure")]
 pub const DES_CBC_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.7");
@@ -55,6 +82,16 @@ pub const PBES2_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.11
 /// AES cipher block size
 const AES_BLOCK_SIZE: usize = 16;
 
+/// AES-GCM nonce size
+#[cfg(feature = "aes-gcm")]
+const GCM_NONCE_SIZE: usize = 12;
+
+/// Default length of an AES-GCM authentication tag as defined in [RFC 5084 Section 3.2]
+///
+/// [RFC 5084 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+#[cfg(feature = "aes-gcm")]
+const GCM_DEFAULT_ICV_LEN: u8 = 12;
+
 /// DES / Triple DES block size
 #[cfg(any(feature = "3des", feature = "des-insecure"))]
 const DES_BLOCK_SIZE: usize = 8;
@@ -138,6 +175,23 @@ impl Parameters {
         Ok(Self { kdf, encryption })
     }
 
+    /// Initialize PBES2 parameters using PBKDF2-SHA256 as the password-based
+    /// key derivation function and AES-256-GCM with a 16-byte authentication
+    /// tag as the symmetric cipher.
+    #[cfg(feature = "aes-gcm")]
+    pub fn pbkdf2_sha256_aes256gcm(
+        pbkdf2_iterations: u32,
+        pbkdf2_salt: &[u8],
+        aes_nonce: [u8; GCM_NONCE_SIZE],
+    ) -> Result<Self> {
+        let kdf = Pbkdf2Params::hmac_with_sha256(pbkdf2_iterations, pbkdf2_salt)?.into();
+        let encryption = EncryptionScheme::Aes256Gcm {
+            nonce: aes_nonce,
+            icv_len: 16,
+        };
+        Ok(Self { kdf, encryption })
+    }
+
     /// Generate PBES2 parameters using scrypt as the password hashing
     /// algorithm, using that algorithm's recommended algorithm settings
     /// along with a randomly generated salt and IV.
@@ -162,10 +216,30 @@ impl Parameters {
         let mut salt = [0u8; Self::DEFAULT_SALT_LEN];
         rng.fill_bytes(&mut salt);
 
-        scrypt::Params::new(14, 8, 1, 32)
-            .ok()
-            .and_then(|params| Self::scrypt_aes256cbc(params, &salt, iv).ok())
+        let kdf = ScryptParams::openssl(&salt)
             .expect("invalid scrypt parameters")
+            .into();
+        let encryption = EncryptionScheme::Aes256Cbc { iv };
+        Self { kdf, encryption }
+    }
+
+    /// Generate PBES2 parameters using scrypt as the password hashing
+    /// algorithm, with the largest cost parameter fitting in `max_memory`
+    /// bytes (see [`ScryptParams::with_max_memory`]), along with a randomly
+    /// generated salt and IV.
+    ///
+    /// This will use AES-256-CBC as the encryption algorithm.
+    #[cfg(all(feature = "pbes2", feature = "rand_core"))]
+    pub fn scrypt_with_max_memory(max_memory: u64, rng: &mut impl CryptoRngCore) -> Result<Self> {
+        let mut iv = [0u8; Self::DEFAULT_IV_LEN];
+        rng.fill_bytes(&mut iv);
+
+        let mut salt = [0u8; Self::DEFAULT_SALT_LEN];
+        rng.fill_bytes(&mut salt);
+
+        let kdf = ScryptParams::with_max_memory(max_memory, &salt)?.into();
+        let encryption = EncryptionScheme::Aes256Cbc { iv };
+        Ok(Self { kdf, encryption })
     }
 
     /// Initialize PBES2 parameters using scrypt as the password-based
@@ -232,9 +306,12 @@ impl Parameters {
 
     /// Encrypt the given plaintext, allocating and returning a vector
     /// containing the ciphertext.
+    ///
+    /// With AES-GCM, the authentication tag is appended to the ciphertext.
     #[cfg(all(feature = "alloc", feature = "pbes2"))]
     pub fn encrypt(&self, password: impl AsRef<[u8]>, plaintext: &[u8]) -> Result<Vec<u8>> {
         // TODO(tarcieri): support non-AES ciphers?
+        // the AES-CBC padding and the AES-GCM tags are at most a block long
         let mut buffer = Vec::with_capacity(plaintext.len() + AES_BLOCK_SIZE);
         buffer.extend_from_slice(plaintext);
         buffer.extend_from_slice(&[0u8; AES_BLOCK_SIZE]);
@@ -250,6 +327,9 @@ impl Parameters {
     /// Encrypt the given plaintext in-place using a key derived from the
     /// provided password and this scheme's parameters, writing the ciphertext
     /// into the same buffer.
+    ///
+    /// The plaintext is `buffer[..pos]`, and the rest of the buffer must be
+    /// large enough for the padding or the authentication tag.
     #[cfg(feature = "pbes2")]
     pub fn encrypt_in_place<'a>(
         &self,
@@ -334,6 +414,36 @@ pub enum EncryptionScheme {
         /// Initialisation vector
         iv: [u8; DES_BLOCK_SIZE],
     },
+
+    /// AES-128 in GCM mode
+    #[cfg(feature = "aes-gcm")]
+    Aes128Gcm {
+        /// Nonce. Only nonces of the recommended size of 12 bytes are supported.
+        nonce: [u8; GCM_NONCE_SIZE],
+
+        /// Length of the authentication tag in bytes, from 12 to 16
+        icv_len: u8,
+    },
+
+    /// AES-192 in GCM mode
+    #[cfg(feature = "aes-gcm")]
+    Aes192Gcm {
+        /// Nonce. Only nonces of the recommended size of 12 bytes are supported.
+        nonce: [u8; GCM_NONCE_SIZE],
+
+        /// Length of the authentication tag in bytes, from 12 to 16
+        icv_len: u8,
+    },
+
+    /// AES-256 in GCM mode
+    #[cfg(feature = "aes-gcm")]
+    Aes256Gcm {
+        /// Nonce. Only nonces of the recommended size of 12 bytes are supported.
+        nonce: [u8; GCM_NONCE_SIZE],
+
+        /// Length of the authentication tag in bytes, from 12 to 16
+        icv_len: u8,
+    },
 }
 
 impl EncryptionScheme {
@@ -347,6 +457,12 @@ impl EncryptionScheme {
             Self::DesCbc { .. } => 8,
             #[cfg(feature = "3des")]
             Self::DesEde3Cbc { .. } => 24,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes128Gcm { .. } => 16,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes192Gcm { .. } => 24,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes256Gcm { .. } => 32,
         }
     }
 
@@ -360,9 +476,31 @@ impl EncryptionScheme {
             Self::DesCbc { .. } => DES_CBC_OID,
             #[cfg(feature = "3des")]
             Self::DesEde3Cbc { .. } => DES_EDE3_CBC_OID,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes128Gcm { .. } => AES_128_GCM_OID,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes192Gcm { .. } => AES_192_GCM_OID,
+            #[cfg(feature = "aes-gcm")]
+            Self::Aes256Gcm { .. } => AES_256_GCM_OID,
         }
     }
 
+    /// Get the GCMParameters of the AES-GCM schemes
+    #[cfg(feature = "aes-gcm")]
+    fn gcm_parameters(&self) -> der::Result<Option<GcmParameters<'_>>> {
+        let (nonce, icv_len) = match self {
+            Self::Aes128Gcm { nonce, icv_len }
+            | Self::Aes192Gcm { nonce, icv_len }
+            | Self::Aes256Gcm { nonce, icv_len } => (nonce, *icv_len),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(GcmParameters {
+            nonce: OctetStringRef::new(nonce)?,
+            icv_len,
+        }))
+    }
+
     /// Convenience function to turn the OID (see [`oid`](Self::oid))
     /// of this [`EncryptionScheme`] into error case
     /// [`Error::AlgorithmParametersInvalid`]
@@ -383,6 +521,26 @@ impl TryFrom<AlgorithmIdentifierRef<'_>> for EncryptionScheme {
     type Error = der::Error;
 
     fn try_from(alg: AlgorithmIdentifierRef<'_>) -> der::Result<Self> {
+        #[cfg(feature = "aes-gcm")]
+        if let AES_128_GCM_OID | AES_192_GCM_OID | AES_256_GCM_OID = alg.oid {
+            let params = match alg.parameters {
+                Some(params) => params.decode_as::<GcmParameters<'_>>()?,
+                None => return Err(Tag::Sequence.value_error()),
+            };
+            let nonce = params
+                .nonce
+                .as_bytes()
+                .try_into()
+                .map_err(|_| Tag::OctetString.value_error())?;
+            let icv_len = params.icv_len;
+
+            return Ok(match alg.oid {
+                AES_128_GCM_OID => Self::Aes128Gcm { nonce, icv_len },
+                AES_192_GCM_OID => Self::Aes192Gcm { nonce, icv_len },
+                _ => Self::Aes256Gcm { nonce, icv_len },
+            });
+        }
+
         // TODO(tarcieri): support for non-AES algorithms?
         let iv = match alg.parameters {
             Some(params) => params.decode_as::<OctetStringRef<'_>>()?.as_bytes(),
@@ -422,6 +580,8 @@ impl TryFrom<AlgorithmIdentifierRef<'_>> for EncryptionScheme {
     }
 }
 
+/// Returns an error for the AES-GCM schemes, whose parameters are a SEQUENCE
+/// rather than the IV. They can be encoded with [`Encode`] instead.
 impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
     type Error = der::Error;
 
@@ -434,6 +594,10 @@ impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
             EncryptionScheme::DesCbc { iv } => iv.as_slice(),
             #[cfg(feature = "3des")]
             EncryptionScheme::DesEde3Cbc { iv } => iv.as_slice(),
+            #[cfg(feature = "aes-gcm")]
+            EncryptionScheme::Aes128Gcm { .. }
+            | EncryptionScheme::Aes192Gcm { .. }
+            | EncryptionScheme::Aes256Gcm { .. } => return Err(Tag::Sequence.value_error()),
         })?;
 
         Ok(AlgorithmIdentifierRef {
@@ -445,10 +609,97 @@ impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
 
 impl Encode for EncryptionScheme {
     fn encoded_len(&self) -> der::Result<Length> {
+        #[cfg(feature = "aes-gcm")]
+        if let Some(parameters) = self.gcm_parameters()? {
+            return AlgorithmIdentifier {
+                oid: self.oid(),
+                parameters: Some(parameters),
+            }
+            .encoded_len();
+        }
+
         AlgorithmIdentifierRef::try_from(self)?.encoded_len()
     }
 
     fn encode(&self, writer: &mut impl Writer) -> der::Result<()> {
+        #[cfg(feature = "aes-gcm")]
+        if let Some(parameters) = self.gcm_parameters()? {
+            return AlgorithmIdentifier {
+                oid: self.oid(),
+                parameters: Some(parameters),
+            }
+            .encode(writer);
+        }
+
         AlgorithmIdentifierRef::try_from(self)?.encode(writer)
     }
 }
+
+/// AES-GCM parameters as defined in [RFC 5084 Section 3.2].
+///
+/// ```text
+/// GCMParameters ::= SEQUENCE {
+///     aes-nonce        OCTET STRING, -- recommended size is 12 octets
+///     aes-ICVlen       AES-GCM-ICVlen DEFAULT 12 }
+///
+/// AES-GCM-ICVlen ::= INTEGER (12 | 13 | 14 | 15 | 16)
+/// ```
+///
+/// Decoding parameters with an aes-nonce of any size other than the recommended
+/// 12 octets returns an error.
+///
+/// [RFC 5084 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+#[cfg(feature = "aes-gcm")]
+struct GcmParameters<'a> {
+    nonce: OctetStringRef<'a>,
+    icv_len: u8,
+}
+
+#[cfg(feature = "aes-gcm")]
+impl<'a> DecodeValue<'a> for GcmParameters<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: der::Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let nonce: OctetStringRef<'a> = reader.decode()?;
+            if nonce.as_bytes().len() != GCM_NONCE_SIZE {
+                return Err(Tag::OctetString.value_error());
+            }
+
+            let icv_len = match Option::<u8>::decode(reader)? {
+                // DER forbids encoding the DEFAULT value
+                Some(GCM_DEFAULT_ICV_LEN) => return Err(Tag::Integer.non_canonical_error()),
+                Some(icv_len) => icv_len,
+                None => GCM_DEFAULT_ICV_LEN,
+            };
+            if !(12..=16).contains(&icv_len) {
+                return Err(Tag::Integer.value_error());
+            }
+
+            Ok(Self { nonce, icv_len })
+        })
+    }
+}
+
+#[cfg(feature = "aes-gcm")]
+impl EncodeValue for GcmParameters<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.nonce.encoded_len()? + self.icv_len().encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.nonce.encode(writer)?;
+        self.icv_len().encode(writer)
+    }
+}
+
+#[cfg(feature = "aes-gcm")]
+impl<'a> Sequence<'a> for GcmParameters<'a> {}
+
+#[cfg(feature = "aes-gcm")]
+impl GcmParameters<'_> {
+    /// Get the aes-ICVlen to encode, i.e. `None` if it's the DEFAULT
+    fn icv_len(&self) -> Option<u8> {
+        Some(self.icv_len).filter(|&icv_len| icv_len != GCM_DEFAULT_ICV_LEN)
+    }
+}
@@ -0,0 +1,261 @@
+//! PBES2 parameters builder.
+
+use super::{
+    EncryptionScheme, Kdf, Parameters, Pbkdf2Params, Pbkdf2Prf, Salt, ScryptParams,
+    AES_128_CBC_OID, AES_192_CBC_OID, AES_256_CBC_OID, AES_BLOCK_SIZE, PBES2_OID, PBKDF2_OID,
+};
+use crate::{Error, ObjectIdentifier, Result};
+use rand_core::CryptoRngCore;
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// PBES2 parameters builder
+///
+/// The builder selects the key derivation function, its cost parameters and the
+/// symmetric cipher of [`Parameters`]. The salt and the IV are generated with the
+/// RNG passed to [`Pbes2ParametersBuilder::build`] unless set explicitly.
+///
+/// By default, the parameters are PBKDF2-SHA256 with 600,000 iterations (as
+/// [`Parameters::pbkdf2`]), a 16-byte salt and AES-256-CBC.
+///
+/// ```
+/// use pkcs5::pbes2::{Pbes2ParametersBuilder, Pbkdf2Prf, AES_128_CBC_OID};
+///
+/// # fn build(rng: &mut impl rand_core::CryptoRngCore) -> pkcs5::Result<()> {
+/// let params = Pbes2ParametersBuilder::new()
+///     .with_pbkdf2(100_000)
+///     .with_prf(Pbkdf2Prf::HmacWithSha512)
+///     .with_cipher(AES_128_CBC_OID)
+///     .build(rng)?;
+/// assert!(params.kdf.is_pbkdf2());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pbes2ParametersBuilder {
+    iterations: u32,
+    prf: Pbkdf2Prf,
+    scrypt: Option<(u8, u16, u16)>,
+    cipher: ObjectIdentifier,
+    salt: Option<Salt>,
+    salt_len: usize,
+    iv: Option<([u8; AES_BLOCK_SIZE], usize)>,
+}
+
+impl Pbes2ParametersBuilder {
+    /// Default PBKDF2 iteration count (OWASP recommended default)
+    const DEFAULT_ITERATIONS: u32 = 600_000;
+
+    const INVALID_ERR: Error = Error::AlgorithmParametersInvalid { oid: PBES2_OID };
+
+    /// Returns a `Pbes2ParametersBuilder` with the default parameters
+    pub fn new() -> Self {
+        Self {
+            iterations: Self::DEFAULT_ITERATIONS,
+            prf: Pbkdf2Prf::HmacWithSha256,
+            scrypt: None,
+            cipher: AES_256_CBC_OID,
+            salt: None,
+            salt_len: Parameters::DEFAULT_SALT_LEN,
+            iv: None,
+        }
+    }
+
+    /// Selects PBKDF2 as the key derivation function with `iterations`
+    /// iterations, e.g. as suggested by
+    /// [`Pbes2ParametersBuilder::calibrate_iterations`]
+    pub fn with_pbkdf2(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self.scrypt = None;
+        self
+    }
+
+    /// Sets the pseudo-random function of PBKDF2
+    pub fn with_prf(mut self, prf: Pbkdf2Prf) -> Self {
+        self.prf = prf;
+        self
+    }
+
+    /// Selects scrypt as the key derivation function with the cost parameter
+    /// `N` = 2^`log_n`, the block size `r` and the parallelization parameter
+    /// `p`, see [`ScryptParams::new`]
+    pub fn with_scrypt(mut self, log_n: u8, block_size: u16, parallelization: u16) -> Self {
+        self.scrypt = Some((log_n, block_size, parallelization));
+        self
+    }
+
+    /// Sets the symmetric cipher, identified by its OID, e.g. [`AES_128_CBC_OID`]
+    pub fn with_cipher(mut self, cipher: ObjectIdentifier) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Sets the salt of the key derivation function instead of generating it
+    pub fn with_salt(mut self, salt: &[u8]) -> Result<Self> {
+        self.salt = Some(Salt::new(salt).map_err(|_| Self::INVALID_ERR)?);
+        Ok(self)
+    }
+
+    /// Sets the length of the generated salt in bytes, at most [`Salt::MAX_LEN`]
+    pub fn with_salt_len(mut self, salt_len: usize) -> Result<Self> {
+        if salt_len > Salt::MAX_LEN {
+            return Err(Self::INVALID_ERR);
+        }
+
+        self.salt_len = salt_len;
+        Ok(self)
+    }
+
+    /// Sets the IV (or the nonce of AES-GCM) of the cipher instead of
+    /// generating it
+    pub fn with_iv(mut self, iv: &[u8]) -> Result<Self> {
+        let mut buffer = [0u8; AES_BLOCK_SIZE];
+        buffer
+            .get_mut(..iv.len())
+            .ok_or(Self::INVALID_ERR)?
+            .copy_from_slice(iv);
+
+        self.iv = Some((buffer, iv.len()));
+        Ok(self)
+    }
+
+    /// Benchmarks PBKDF2 with the selected pseudo-random function on this host,
+    /// and returns the iteration count deriving a key for the selected cipher in
+    /// approximately `target`.
+    ///
+    /// The iteration count is doubled until a derivation takes a significant
+    /// part of `target`, and then scaled up to `target`.
+    #[cfg(feature = "std")]
+    pub fn calibrate_iterations(&self, target: Duration) -> Result<u32> {
+        let sample = (target / 4).min(Duration::from_millis(100));
+        let key_size = self
+            .encryption_scheme(&[0u8; AES_BLOCK_SIZE][..self.iv_len()])?
+            .key_size();
+        let salt = Salt::new([0u8; Parameters::DEFAULT_SALT_LEN]).map_err(|_| Self::INVALID_ERR)?;
+
+        let mut iterations = 1_000;
+        loop {
+            let kdf = Kdf::Pbkdf2(Pbkdf2Params {
+                salt: salt.clone(),
+                iteration_count: iterations,
+                key_length: None,
+                prf: self.prf,
+            });
+
+            let start = Instant::now();
+            super::encryption::EncryptionKey::derive_from_password(b"password", &kdf, key_size)?;
+            let elapsed = start.elapsed();
+
+            if elapsed >= sample || iterations >= Pbkdf2Params::MAX_ITERATION_COUNT {
+                let estimate =
+                    u128::from(iterations) * target.as_nanos() / elapsed.as_nanos().max(1);
+                let max = u128::from(Pbkdf2Params::MAX_ITERATION_COUNT);
+                return Ok(estimate.clamp(1, max) as u32);
+            }
+
+            iterations = iterations
+                .saturating_mul(2)
+                .min(Pbkdf2Params::MAX_ITERATION_COUNT);
+        }
+    }
+
+    /// Returns the PBES2 parameters, generating the salt and the IV with `rng`
+    /// unless set explicitly.
+    pub fn build(&self, rng: &mut impl CryptoRngCore) -> Result<Parameters> {
+        let salt = match &self.salt {
+            Some(salt) => salt.clone(),
+            None => {
+                let mut salt = [0u8; Salt::MAX_LEN];
+                rng.fill_bytes(&mut salt[..self.salt_len]);
+                Salt::new(&salt[..self.salt_len]).map_err(|_| Self::INVALID_ERR)?
+            }
+        };
+
+        let kdf = match self.scrypt {
+            Some((log_n, block_size, parallelization)) => {
+                ScryptParams::new(log_n, block_size, parallelization, salt.as_bytes())?.into()
+            }
+            None => {
+                if !(1..=Pbkdf2Params::MAX_ITERATION_COUNT).contains(&self.iterations) {
+                    return Err(Error::AlgorithmParametersInvalid { oid: PBKDF2_OID });
+                }
+
+                Kdf::Pbkdf2(Pbkdf2Params {
+                    salt,
+                    iteration_count: self.iterations,
+                    key_length: None,
+                    prf: self.prf,
+                })
+            }
+        };
+
+        let encryption = match self.iv {
+            Some((iv, len)) => self.encryption_scheme(&iv[..len])?,
+            None => {
+                let mut iv = [0u8; AES_BLOCK_SIZE];
+                let iv = &mut iv[..self.iv_len()];
+                rng.fill_bytes(iv);
+                self.encryption_scheme(iv)?
+            }
+        };
+
+        Ok(Parameters { kdf, encryption })
+    }
+
+    /// Length of the IV of the selected cipher
+    fn iv_len(&self) -> usize {
+        match self.cipher {
+            #[cfg(feature = "3des")]
+            super::DES_EDE3_CBC_OID => super::DES_BLOCK_SIZE,
+            #[cfg(feature = "aes-gcm")]
+            super::AES_128_GCM_OID | super::AES_192_GCM_OID | super::AES_256_GCM_OID => {
+                super::GCM_NONCE_SIZE
+            }
+            _ => AES_BLOCK_SIZE,
+        }
+    }
+
+    /// Returns the selected cipher with `iv`, using 16-byte tags with AES-GCM
+    fn encryption_scheme(&self, iv: &[u8]) -> Result<EncryptionScheme> {
+        let invalid = Error::AlgorithmParametersInvalid { oid: self.cipher };
+
+        Ok(match self.cipher {
+            AES_128_CBC_OID => EncryptionScheme::Aes128Cbc {
+                iv: iv.try_into().map_err(|_| invalid)?,
+            },
+            AES_192_CBC_OID => EncryptionScheme::Aes192Cbc {
+                iv: iv.try_into().map_err(|_| invalid)?,
+            },
+            AES_256_CBC_OID => EncryptionScheme::Aes256Cbc {
+                iv: iv.try_into().map_err(|_| invalid)?,
+            },
+            #[cfg(feature = "3des")]
+            super::DES_EDE3_CBC_OID => EncryptionScheme::DesEde3Cbc {
+                iv: iv.try_into().map_err(|_| invalid)?,
+            },
+            #[cfg(feature = "aes-gcm")]
+            super::AES_128_GCM_OID => EncryptionScheme::Aes128Gcm {
+                nonce: iv.try_into().map_err(|_| invalid)?,
+                icv_len: 16,
+            },
+            #[cfg(feature = "aes-gcm")]
+            super::AES_192_GCM_OID => EncryptionScheme::Aes192Gcm {
+                nonce: iv.try_into().map_err(|_| invalid)?,
+                icv_len: 16,
+            },
+            #[cfg(feature = "aes-gcm")]
+            super::AES_256_GCM_OID => EncryptionScheme::Aes256Gcm {
+                nonce: iv.try_into().map_err(|_| invalid)?,
+                icv_len: 16,
+            },
+            oid => return Err(Error::UnsupportedAlgorithm { oid }),
+        })
+    }
+}
+
+impl Default for Pbes2ParametersBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
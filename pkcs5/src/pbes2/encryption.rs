@@ -16,6 +16,21 @@ use pbkdf2::{
 };
 use scrypt::scrypt;
 
+#[cfg(feature = "aes-gcm")]
+use {
+    aes_gcm::{
+        aead::{
+            generic_array::{
+                typenum::{U12, U13, U14, U15, U16},
+                GenericArray,
+            },
+            AeadInPlace,
+        },
+        AesGcm, TagSize,
+    },
+    cbc::cipher::{BlockEncrypt, BlockSizeUser as CipherBlockSizeUser},
+};
+
 /// Maximum size of a derived encryption key
 const MAX_KEY_LEN: usize = 32;
 
@@ -44,6 +59,109 @@ fn cbc_decrypt<'a, C: BlockDecryptMut + BlockCipher + KeyInit>(
         .map_err(|_| Error::EncryptFailed)
 }
 
+#[cfg(feature = "aes-gcm")]
+fn gcm_encrypt<'a, C>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: &[u8],
+    icv_len: u8,
+    buffer: &'a mut [u8],
+    pos: usize,
+) -> Result<&'a [u8]>
+where
+    C: BlockCipher + CipherBlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    match icv_len {
+        12 => gcm_encrypt_with_tag::<C, U12>(es, key, nonce, buffer, pos),
+        13 => gcm_encrypt_with_tag::<C, U13>(es, key, nonce, buffer, pos),
+        14 => gcm_encrypt_with_tag::<C, U14>(es, key, nonce, buffer, pos),
+        15 => gcm_encrypt_with_tag::<C, U15>(es, key, nonce, buffer, pos),
+        16 => gcm_encrypt_with_tag::<C, U16>(es, key, nonce, buffer, pos),
+        _ => Err(es.to_alg_params_invalid()),
+    }
+}
+
+/// Encrypt `buffer[..pos]` and append the authentication tag
+#[cfg(feature = "aes-gcm")]
+fn gcm_encrypt_with_tag<'a, C, T>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: &[u8],
+    buffer: &'a mut [u8],
+    pos: usize,
+) -> Result<&'a [u8]>
+where
+    C: BlockCipher + CipherBlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+    T: TagSize,
+{
+    let ct_len = pos + T::USIZE;
+    if buffer.len() < ct_len {
+        return Err(Error::EncryptFailed);
+    }
+
+    let (plaintext, rest) = buffer.split_at_mut(pos);
+    let tag = AesGcm::<C, U12, T>::new_from_slice(key.as_slice())
+        .map_err(|_| es.to_alg_params_invalid())?
+        .encrypt_in_place_detached(GenericArray::from_slice(nonce), &[], plaintext)
+        .map_err(|_| Error::EncryptFailed)?;
+    rest[..T::USIZE].copy_from_slice(&tag);
+
+    Ok(&buffer[..ct_len])
+}
+
+#[cfg(feature = "aes-gcm")]
+fn gcm_decrypt<'a, C>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: &[u8],
+    icv_len: u8,
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8]>
+where
+    C: BlockCipher + CipherBlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    match icv_len {
+        12 => gcm_decrypt_with_tag::<C, U12>(es, key, nonce, buffer),
+        13 => gcm_decrypt_with_tag::<C, U13>(es, key, nonce, buffer),
+        14 => gcm_decrypt_with_tag::<C, U14>(es, key, nonce, buffer),
+        15 => gcm_decrypt_with_tag::<C, U15>(es, key, nonce, buffer),
+        16 => gcm_decrypt_with_tag::<C, U16>(es, key, nonce, buffer),
+        _ => Err(es.to_alg_params_invalid()),
+    }
+}
+
+/// Verify the authentication tag at the end of `buffer` and decrypt the
+/// ciphertext preceding it
+#[cfg(feature = "aes-gcm")]
+fn gcm_decrypt_with_tag<'a, C, T>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: &[u8],
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8]>
+where
+    C: BlockCipher + CipherBlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+    T: TagSize,
+{
+    let pt_len = buffer
+        .len()
+        .checked_sub(T::USIZE)
+        .ok_or(Error::DecryptFailed)?;
+
+    let (ciphertext, tag) = buffer.split_at_mut(pt_len);
+    AesGcm::<C, U12, T>::new_from_slice(key.as_slice())
+        .map_err(|_| es.to_alg_params_invalid())?
+        .decrypt_in_place_detached(
+            GenericArray::from_slice(nonce),
+            &[],
+            ciphertext,
+            GenericArray::from_slice(tag),
+        )
+        .map_err(|_| Error::DecryptFailed)?;
+
+    Ok(&buffer[..pt_len])
+}
+
 pub fn encrypt_in_place<'b>(
     params: &Parameters,
     password: impl AsRef<[u8]>,
@@ -67,6 +185,18 @@ pub fn encrypt_in_place<'b>(
         EncryptionScheme::DesCbc { .. } => Err(Error::UnsupportedAlgorithm {
             oid: super::DES_CBC_OID,
         }),
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes128Gcm { nonce, icv_len } => {
+            gcm_encrypt::<aes::Aes128>(es, key, &nonce, icv_len, buf, pos)
+        }
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes192Gcm { nonce, icv_len } => {
+            gcm_encrypt::<aes::Aes192>(es, key, &nonce, icv_len, buf, pos)
+        }
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes256Gcm { nonce, icv_len } => {
+            gcm_encrypt::<aes::Aes256>(es, key, &nonce, icv_len, buf, pos)
+        }
     }
 }
 
@@ -87,12 +217,24 @@ pub fn decrypt_in_place<'a>(
         EncryptionScheme::DesEde3Cbc { iv } => cbc_decrypt::<des::TdesEde3>(es, key, &iv, buf),
         #[cfg(feature = "des-insecure")]
         EncryptionScheme::DesCbc { iv } => cbc_decrypt::<des::Des>(es, key, &iv, buf),
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes128Gcm { nonce, icv_len } => {
+            gcm_decrypt::<aes::Aes128>(es, key, &nonce, icv_len, buf)
+        }
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes192Gcm { nonce, icv_len } => {
+            gcm_decrypt::<aes::Aes192>(es, key, &nonce, icv_len, buf)
+        }
+        #[cfg(feature = "aes-gcm")]
+        EncryptionScheme::Aes256Gcm { nonce, icv_len } => {
+            gcm_decrypt::<aes::Aes256>(es, key, &nonce, icv_len, buf)
+        }
     }
 }
 
 /// Encryption key as derived by PBKDF2
 // TODO(tarcieri): zeroize?
-struct EncryptionKey {
+pub(super) struct EncryptionKey {
     buffer: [u8; MAX_KEY_LEN],
     length: usize,
 }
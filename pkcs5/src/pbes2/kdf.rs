@@ -419,6 +419,61 @@ impl ScryptParams {
             key_length: None,
         })
     }
+
+    /// Initialize scrypt with the cost parameter `N` = 2^`log_n`, the block size `r`, the
+    /// parallelization parameter `p` and the given salt.
+    #[cfg(feature = "pbes2")]
+    pub fn new(log_n: u8, block_size: u16, parallelization: u16, salt: &[u8]) -> Result<Self> {
+        let params = scrypt::Params::new(
+            log_n,
+            block_size.into(),
+            parallelization.into(),
+            scrypt::Params::RECOMMENDED_LEN,
+        )
+        .map_err(|_| Self::INVALID_ERR)?;
+
+        Self::from_params_and_salt(params, salt)
+    }
+
+    /// Initialize scrypt with the parameters used by `openssl pkcs8 -scrypt`:
+    ///
+    /// - `log_n`: 14
+    /// - `r`: 8
+    /// - `p`: 1
+    ///
+    /// OpenSSL uses an 8-byte salt.
+    #[cfg(feature = "pbes2")]
+    pub fn openssl(salt: &[u8]) -> Result<Self> {
+        Self::new(14, 8, 1, salt)
+    }
+
+    /// Initialize scrypt with the largest cost parameter `N` whose
+    /// [`memory_cost`](Self::memory_cost) doesn't exceed `max_memory` bytes,
+    /// along with `r` = 8 and `p` = 1.
+    ///
+    /// E.g. a `max_memory` of 16 MiB selects the same parameters as
+    /// [`ScryptParams::openssl`].
+    #[cfg(feature = "pbes2")]
+    pub fn with_max_memory(max_memory: u64, salt: &[u8]) -> Result<Self> {
+        let block_size = 8;
+        let blocks = max_memory / (128 * u64::from(block_size));
+
+        // RFC 7914 requires `N` to be larger than 1
+        if blocks < 2 {
+            return Err(Self::INVALID_ERR);
+        }
+
+        let log_n = (u64::BITS - blocks.leading_zeros() - 1) as u8;
+        Self::new(log_n, block_size, 1, salt)
+    }
+
+    /// Get the amount of memory in bytes needed to derive a key, i.e.
+    /// 128 * `r` * `N`.
+    pub fn memory_cost(&self) -> u64 {
+        128u64
+            .saturating_mul(self.block_size.into())
+            .saturating_mul(self.cost_parameter)
+    }
 }
 
 impl<'a> DecodeValue<'a> for ScryptParams {
@@ -0,0 +1,146 @@
+//! PBES2 parameters builder tests
+
+#![cfg(all(feature = "pbes2", feature = "rand_core"))]
+
+use der::Encode;
+use hex_literal::hex;
+use pkcs5::pbes2::{self, Pbes2ParametersBuilder, Pbkdf2Prf};
+use rand_core::OsRng;
+
+/// PBES2 + scrypt + AES-256-CBC `AlgorithmIdentifier` example.
+///
+/// Generated by OpenSSL and extracted from the `pkcs8` crate's
+/// `ed25519-encpriv-aes256-scrypt.der` test vector.
+const PBES2_SCRYPT_AES256CBC_ALG_ID: &[u8] = &hex!(
+    "304f06092a864886f70d01050d3042302106092b06010401da47040b30140408
+    e6211e2348ad69e002024000020108020101301d060960864801650304012a041
+    09bd0a6251f2254f9fd5963887c27cf01"
+);
+
+#[test]
+fn build_default() {
+    let params = Pbes2ParametersBuilder::new().build(&mut OsRng).unwrap();
+
+    let pbkdf2_params = params.kdf.pbkdf2().unwrap();
+    assert_eq!(pbkdf2_params.salt.as_bytes().len(), 16);
+    assert_eq!(pbkdf2_params.iteration_count, 600_000);
+    assert_eq!(pbkdf2_params.prf, Pbkdf2Prf::HmacWithSha256);
+    assert!(matches!(
+        params.encryption,
+        pbes2::EncryptionScheme::Aes256Cbc { .. }
+    ));
+
+    let other = Pbes2ParametersBuilder::new().build(&mut OsRng).unwrap();
+    assert_ne!(params, other);
+}
+
+#[test]
+fn build_pbkdf2() {
+    let params = Pbes2ParametersBuilder::new()
+        .with_pbkdf2(10)
+        .with_prf(Pbkdf2Prf::HmacWithSha512)
+        .with_cipher(pbes2::AES_128_CBC_OID)
+        .with_salt_len(8)
+        .unwrap()
+        .build(&mut OsRng)
+        .unwrap();
+
+    let pbkdf2_params = params.kdf.pbkdf2().unwrap();
+    assert_eq!(pbkdf2_params.salt.as_bytes().len(), 8);
+    assert_eq!(pbkdf2_params.iteration_count, 10);
+    assert_eq!(pbkdf2_params.prf, Pbkdf2Prf::HmacWithSha512);
+    assert!(matches!(
+        params.encryption,
+        pbes2::EncryptionScheme::Aes128Cbc { .. }
+    ));
+
+    let plaintext = b"plaintext";
+    let mut buffer = [0u8; 16];
+    buffer[..plaintext.len()].copy_from_slice(plaintext);
+    let ciphertext = params
+        .encrypt_in_place("password", &mut buffer, plaintext.len())
+        .unwrap()
+        .to_vec();
+    assert_eq!(
+        params.decrypt_in_place("password", &mut ciphertext.clone()),
+        Ok(plaintext.as_slice())
+    );
+}
+
+#[test]
+fn build_scrypt() {
+    let params = Pbes2ParametersBuilder::new()
+        .with_scrypt(14, 8, 1)
+        .with_salt(&hex!("E6211E2348AD69E0"))
+        .unwrap()
+        .with_iv(&hex!("9BD0A6251F2254F9FD5963887C27CF01"))
+        .unwrap()
+        .build(&mut OsRng)
+        .unwrap();
+
+    let mut buffer = [0u8; 1024];
+    let mut encoder = der::SliceWriter::new(&mut buffer);
+    pkcs5::EncryptionScheme::from(params)
+        .encode(&mut encoder)
+        .unwrap();
+
+    let encoded_der = encoder.finish().unwrap();
+    assert_eq!(encoded_der, PBES2_SCRYPT_AES256CBC_ALG_ID);
+}
+
+#[test]
+fn build_invalid() {
+    let builder = Pbes2ParametersBuilder::new();
+    assert_eq!(
+        builder
+            .clone()
+            .with_cipher(pbes2::PBES2_OID)
+            .build(&mut OsRng),
+        Err(pkcs5::Error::UnsupportedAlgorithm {
+            oid: pbes2::PBES2_OID
+        })
+    );
+    assert_eq!(
+        builder
+            .clone()
+            .with_iv(&[0u8; 8])
+            .unwrap()
+            .build(&mut OsRng),
+        Err(pkcs5::Error::AlgorithmParametersInvalid {
+            oid: pbes2::AES_256_CBC_OID
+        })
+    );
+    assert_eq!(
+        builder.clone().with_pbkdf2(0).build(&mut OsRng),
+        Err(pkcs5::Error::AlgorithmParametersInvalid {
+            oid: pbes2::PBKDF2_OID
+        })
+    );
+    assert!(builder
+        .clone()
+        .with_scrypt(14, 0, 1)
+        .build(&mut OsRng)
+        .is_err());
+    assert!(builder.clone().with_salt_len(33).is_err());
+    assert!(builder.with_iv(&[0u8; 17]).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn calibrate_iterations() {
+    use std::time::Duration;
+
+    let builder = Pbes2ParametersBuilder::new();
+    let iterations = builder
+        .calibrate_iterations(Duration::from_millis(20))
+        .unwrap();
+    assert!(iterations >= 1);
+
+    let params = builder.with_pbkdf2(iterations).build(&mut OsRng).unwrap();
+    assert_eq!(params.kdf.pbkdf2().unwrap().iteration_count, iterations);
+
+    assert_eq!(
+        Pbes2ParametersBuilder::new().calibrate_iterations(Duration::ZERO),
+        Ok(1)
+    );
+}
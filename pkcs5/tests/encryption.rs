@@ -91,6 +91,40 @@ const ED25519_PKCS8_KEY_CIPHERTEXT_DESCBC: &[u8] = &hex!(
     93E4E3893840181FBC63D75297B416A0B96CB7F9AB45CEABA"
 );
 
+/// PBES2 + PBKDF2-SHA256 + AES-256-GCM `AlgorithmIdentifier` example with a
+/// 16-byte authentication tag.
+#[cfg(feature = "aes-gcm")]
+const PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID: &[u8] = &hex!(
+    "305806092a864886f70d01050d304b302906092a864886f70d01050c301c0408
+     79d982e70df91a8802020800300c06082a864886f70d02090500301e06096086
+     4801650304012e3011040cb2d02d78b2efd9dff694cf8e020110"
+);
+
+/// Ciphertext of Ed25519 PKCS#8 private key encrypted with AES-256-GCM and
+/// PBKDF2-SHA256, followed by the 16-byte authentication tag
+#[cfg(feature = "aes-gcm")]
+const ED25519_PKCS8_KEY_CIPHERTEXT_AES256GCM: &[u8] = &hex!(
+    "dc95a61bc03e10e1865d750036dcf2d287eb0390e5bd234979fcbb01271830c0
+     585bc98d993c20957687ae7e8a267f94aff98338af30f4908b0f714a94e09a11"
+);
+
+/// PBES2 + PBKDF2-SHA256 + AES-128-GCM `AlgorithmIdentifier` example with the
+/// default 12-byte authentication tag.
+#[cfg(feature = "aes-gcm")]
+const PBES2_PBKDF2_SHA256_AES128GCM_ALG_ID: &[u8] = &hex!(
+    "305506092a864886f70d01050d3048302906092a864886f70d01050c301c0408
+     79d982e70df91a8802020800300c06082a864886f70d02090500301b06096086
+     48016503040106300e040cb2d02d78b2efd9dff694cf8e"
+);
+
+/// Ciphertext of Ed25519 PKCS#8 private key encrypted with AES-128-GCM and
+/// PBKDF2-SHA256, followed by the 12-byte authentication tag
+#[cfg(feature = "aes-gcm")]
+const ED25519_PKCS8_KEY_CIPHERTEXT_AES128GCM: &[u8] = &hex!(
+    "3e0dc329ac5b06976de13282429bd11bb4b7e525648d21042711084fad57f779
+     27ddd619c2aa7174054107a2d8b0e2eba0249a2b66e4daf351d5dea1"
+);
+
 /// Password used to encrypt the keys.
 const PASSWORD: &[u8] = b"hunter42"; // Bad password; don't actually use outside tests!
 
@@ -110,6 +144,24 @@ fn decrypt_pbes2_scrypt_aes256cbc() {
     assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
 }
 
+#[test]
+fn encrypt_pbes2_scrypt_openssl_aes256cbc() {
+    let params = pkcs5::pbes2::Parameters {
+        kdf: pkcs5::pbes2::ScryptParams::openssl(&hex!("E6211E2348AD69E0"))
+            .unwrap()
+            .into(),
+        encryption: pkcs5::pbes2::EncryptionScheme::Aes256Cbc {
+            iv: hex!("9BD0A6251F2254F9FD5963887C27CF01"),
+        },
+    };
+
+    let mut buffer = [0u8; 64];
+    let pos = ED25519_PKCS8_KEY_PLAINTEXT.len();
+    buffer[..pos].copy_from_slice(ED25519_PKCS8_KEY_PLAINTEXT);
+    let ciphertext = params.encrypt_in_place(PASSWORD, &mut buffer, pos).unwrap();
+    assert_eq!(ciphertext, ED25519_PKCS8_KEY_CIPHERTEXT_SCRYPT);
+}
+
 #[test]
 #[cfg(feature = "3des")]
 fn decrypt_pbes2_pbkdf2_sha256_desede3cbc() {
@@ -127,3 +179,64 @@ fn decrypt_pbes2_pbkdf2_sha256_descbc() {
     let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
     assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
 }
+
+#[test]
+#[cfg(feature = "aes-gcm")]
+fn decrypt_pbes2_pbkdf2_sha256_aes256gcm() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_AES256GCM);
+    let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
+    assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
+}
+
+#[test]
+#[cfg(feature = "aes-gcm")]
+fn decrypt_pbes2_pbkdf2_sha256_aes128gcm() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES128GCM_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_AES128GCM);
+    let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
+    assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
+}
+
+#[test]
+#[cfg(feature = "aes-gcm")]
+fn decrypt_pbes2_aes256gcm_bad_tag() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_AES256GCM);
+    *buffer.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::DecryptFailed)
+    );
+
+    let mut buffer = Vec::from(&ED25519_PKCS8_KEY_CIPHERTEXT_AES256GCM[..8]);
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::DecryptFailed)
+    );
+}
+
+#[test]
+#[cfg(feature = "aes-gcm")]
+fn encrypt_pbes2_pbkdf2_sha256_aes256gcm() {
+    let params = pkcs5::pbes2::Parameters::pbkdf2_sha256_aes256gcm(
+        2048,
+        &hex!("79d982e70df91a88"),
+        hex!("b2d02d78b2efd9dff694cf8e"),
+    )
+    .unwrap();
+
+    let mut buffer = [0u8; 64];
+    let pos = ED25519_PKCS8_KEY_PLAINTEXT.len();
+    buffer[..pos].copy_from_slice(ED25519_PKCS8_KEY_PLAINTEXT);
+    let ciphertext = params.encrypt_in_place(PASSWORD, &mut buffer, pos).unwrap();
+    assert_eq!(ciphertext, ED25519_PKCS8_KEY_CIPHERTEXT_AES256GCM);
+
+    // no room for the tag
+    let mut buffer = [0u8; 60];
+    buffer[..pos].copy_from_slice(ED25519_PKCS8_KEY_PLAINTEXT);
+    assert_eq!(
+        params.encrypt_in_place(PASSWORD, &mut buffer, pos),
+        Err(pkcs5::Error::EncryptFailed)
+    );
+}
@@ -51,3 +51,26 @@ do
         extract "$aes_mode" "$prf" 88
     done
 done
+
+gen_pkcs8_pbes1 () {
+    local pbe_alg="${1:?}"
+
+    openssl pkcs8 \
+        -topk8 \
+        -in rsa_sk.pkcs1.pem \
+        -v1 "PBE-${pbe_alg}" \
+        -iter 10 \
+        -passout "$passout" \
+        -outform DER -out "rsa_sk_pbe-${pbe_alg,,}.pkcs8.der" \
+        -provider legacy -provider default
+
+    dd bs=1 skip=4 count=28 if="rsa_sk_pbe-${pbe_alg,,}.pkcs8.der" of="pbes1_${pbe_alg,,}_algid.der"
+    dd bs=1 skip=36 if="rsa_sk_pbe-${pbe_alg,,}.pkcs8.der" of="pbes1_${pbe_alg,,}_ciphertext.bin"
+}
+
+# PBE-MD2-DES and PBE-MD2-RC2-64 aren't supported by OpenSSL 3.x: pbes1_md2-des_*
+# were generated separately with PBKDF1-MD2, the salt 9e2f0ba1c43d5578 and 10 iterations
+for pbe_alg in "MD5-DES" "MD5-RC2-64" "SHA1-DES" "SHA1-RC2-64"
+do
+    gen_pkcs8_pbes1 "$pbe_alg"
+done
@@ -0,0 +1,93 @@
+//! PBES1 decryption tests
+
+#![cfg(feature = "legacy")]
+
+use std::fs;
+
+/// Password used to encrypt the keys.
+const PASSWORD: &[u8] = b"hunter2"; // Bad password; don't actually use outside tests!
+
+fn read_scheme(pbe_alg: &str) -> pkcs5::EncryptionScheme {
+    let algid_path = format!("./tests/examples/pbes1_{}_algid.der", pbe_alg);
+    let algid_bytes = fs::read(&algid_path)
+        .unwrap_or_else(|err| panic!("Failed to read from {algid_path}: {err}"));
+    pkcs5::EncryptionScheme::try_from(algid_bytes.as_slice())
+        .unwrap_or_else(|err| panic!("Failed to interpret scheme {pbe_alg}: {err}"))
+}
+
+fn read_ciphertext(pbe_alg: &str) -> Vec<u8> {
+    let ciphertext_path = format!("./tests/examples/pbes1_{}_ciphertext.bin", pbe_alg);
+    fs::read(&ciphertext_path)
+        .unwrap_or_else(|err| panic!("Failed to read from {ciphertext_path}: {err}"))
+}
+
+#[test]
+fn all_combinations() {
+    let sk_path = "./tests/examples/rsa_sk.pkcs8.der";
+    let sk_bytes =
+        fs::read(sk_path).unwrap_or_else(|err| panic!("Failed to read from {sk_path}: {err}"));
+
+    for pbe_alg in [
+        "md2-des",
+        "md5-des",
+        "md5-rc2-64",
+        "sha1-des",
+        "sha1-rc2-64",
+    ] {
+        let scheme = read_scheme(pbe_alg);
+        assert!(scheme.pbes1().is_some());
+
+        let mut ciphertext_bytes = read_ciphertext(pbe_alg);
+        assert_eq!(640, ciphertext_bytes.len());
+
+        let plaintext = scheme
+            .decrypt_in_place(PASSWORD, &mut ciphertext_bytes)
+            .unwrap_or_else(|err| panic!("pbes1 decryption of {pbe_alg}: {err}"));
+
+        assert_eq!(sk_bytes, plaintext);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn decrypt_alloc() {
+    let sk_bytes = fs::read("./tests/examples/rsa_sk.pkcs8.der").unwrap();
+    let scheme = read_scheme("sha1-des");
+
+    let plaintext = scheme
+        .decrypt(PASSWORD, &read_ciphertext("sha1-des"))
+        .unwrap();
+    assert_eq!(sk_bytes, plaintext);
+}
+
+#[test]
+fn decrypt_wrong_password() {
+    let scheme = read_scheme("md5-des");
+    let mut ciphertext_bytes = read_ciphertext("md5-des");
+
+    let result = scheme.decrypt_in_place(b"hunter3", &mut ciphertext_bytes);
+    assert_eq!(result, Err(pkcs5::Error::DecryptFailed));
+}
+
+#[test]
+fn zero_iterations() {
+    let mut alg = read_scheme("md5-des").pbes1().unwrap().clone();
+    alg.parameters.iteration_count = 0;
+    let mut ciphertext_bytes = read_ciphertext("md5-des");
+
+    let result = alg.decrypt_in_place(PASSWORD, &mut ciphertext_bytes);
+    assert_eq!(
+        result,
+        Err(pkcs5::Error::AlgorithmParametersInvalid {
+            oid: pkcs5::pbes1::PBE_WITH_MD5_AND_DES_CBC_OID
+        })
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encrypt_unsupported() {
+    let scheme = read_scheme("sha1-des");
+    let result = scheme.encrypt(PASSWORD, b"plaintext");
+    assert_eq!(result, Err(pkcs5::Error::NoPbes1CryptSupport));
+}
@@ -34,6 +34,24 @@ const PBES2_SCRYPT_AES256CBC_ALG_ID: &[u8] = &hex!(
     09bd0a6251f2254f9fd5963887c27cf01"
 );
 
+/// PBES2 + PBKDF2-SHA256 + AES-256-GCM `AlgorithmIdentifier` example with a
+/// 16-byte authentication tag.
+#[cfg(feature = "aes-gcm")]
+const PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID: &[u8] = &hex!(
+    "305806092a864886f70d01050d304b302906092a864886f70d01050c301c0408
+     79d982e70df91a8802020800300c06082a864886f70d02090500301e06096086
+     4801650304012e3011040cb2d02d78b2efd9dff694cf8e020110"
+);
+
+/// PBES2 + PBKDF2-SHA256 + AES-128-GCM `AlgorithmIdentifier` example with the
+/// default 12-byte authentication tag.
+#[cfg(feature = "aes-gcm")]
+const PBES2_PBKDF2_SHA256_AES128GCM_ALG_ID: &[u8] = &hex!(
+    "305506092a864886f70d01050d3048302906092a864886f70d01050c301c0408
+     79d982e70df91a8802020800300c06082a864886f70d02090500301b06096086
+     48016503040106300e040cb2d02d78b2efd9dff694cf8e"
+);
+
 /// PBES2 + DES-EDE3-CBC + PBKDF-SHA2 `AlgorithmIdentifier` example.
 ///
 /// Generated by OpenSSL and extracted from the `pkcs8` crate's
@@ -197,3 +215,127 @@ fn encode_pbes2_scrypt_aes256cbc() {
     let encoded_der = encoder.finish().unwrap();
     assert_eq!(encoded_der, PBES2_SCRYPT_AES256CBC_ALG_ID);
 }
+
+/// Encoding test for PBES2 + scrypt + AES-256-CBC `AlgorithmIdentifier` with the parameters
+/// of `openssl pkcs8 -scrypt`
+#[cfg(feature = "pbes2")]
+#[test]
+fn encode_pbes2_scrypt_openssl_aes256cbc() {
+    let params = pbes2::Parameters {
+        kdf: pbes2::ScryptParams::openssl(&hex!("E6211E2348AD69E0"))
+            .unwrap()
+            .into(),
+        encryption: pbes2::EncryptionScheme::Aes256Cbc {
+            iv: hex!("9BD0A6251F2254F9FD5963887C27CF01"),
+        },
+    };
+
+    let mut buffer = [0u8; 1024];
+    let mut encoder = der::SliceWriter::new(&mut buffer);
+    pkcs5::EncryptionScheme::from(params)
+        .encode(&mut encoder)
+        .unwrap();
+
+    let encoded_der = encoder.finish().unwrap();
+    assert_eq!(encoded_der, PBES2_SCRYPT_AES256CBC_ALG_ID);
+}
+
+#[cfg(feature = "pbes2")]
+#[test]
+fn scrypt_with_max_memory() {
+    let salt = hex!("E6211E2348AD69E0");
+    let openssl = pbes2::ScryptParams::openssl(&salt).unwrap();
+    assert_eq!(openssl.memory_cost(), 16 * 1024 * 1024);
+
+    assert_eq!(
+        pbes2::ScryptParams::with_max_memory(16 * 1024 * 1024, &salt).unwrap(),
+        openssl
+    );
+
+    let params = pbes2::ScryptParams::with_max_memory(48 * 1024 * 1024, &salt).unwrap();
+    assert_eq!(params.cost_parameter, 32768);
+    assert_eq!(params.block_size, 8);
+    assert_eq!(params.parallelization, 1);
+    assert_eq!(params.memory_cost(), 32 * 1024 * 1024);
+
+    assert!(pbes2::ScryptParams::with_max_memory(1024, &salt).is_err());
+}
+
+/// Decoding test for PBES2 + PBKDF2-SHA256 + AES-GCM `AlgorithmIdentifier`s
+#[cfg(feature = "aes-gcm")]
+#[test]
+fn decode_pbes2_pbkdf2_sha256_aesgcm() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID).unwrap();
+    match scheme.pbes2().unwrap().encryption {
+        pbes2::EncryptionScheme::Aes256Gcm { nonce, icv_len } => {
+            assert_eq!(nonce, hex!("b2d02d78b2efd9dff694cf8e"));
+            assert_eq!(icv_len, 16);
+        }
+        other => panic!("unexpected encryption scheme: {:?}", other),
+    }
+
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES128GCM_ALG_ID).unwrap();
+    match scheme.pbes2().unwrap().encryption {
+        pbes2::EncryptionScheme::Aes128Gcm { nonce, icv_len } => {
+            assert_eq!(nonce, hex!("b2d02d78b2efd9dff694cf8e"));
+            assert_eq!(icv_len, 12);
+        }
+        other => panic!("unexpected encryption scheme: {:?}", other),
+    }
+}
+
+/// Encoding test for PBES2 + PBKDF2-SHA256 + AES-GCM `AlgorithmIdentifier`s
+#[cfg(feature = "aes-gcm")]
+#[test]
+fn encode_pbes2_pbkdf2_sha256_aesgcm() {
+    for alg_id in [
+        PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID,
+        PBES2_PBKDF2_SHA256_AES128GCM_ALG_ID,
+    ] {
+        let mut buffer = [0u8; 1024];
+
+        let scheme = pkcs5::EncryptionScheme::try_from(alg_id).unwrap();
+        let mut encoder = der::SliceWriter::new(&mut buffer);
+        scheme.encode(&mut encoder).unwrap();
+
+        let encoded_der = encoder.finish().unwrap();
+        assert_eq!(encoded_der, alg_id);
+    }
+}
+
+/// The aes-ICVlen of AES-GCM must be from 12 to 16
+#[cfg(feature = "aes-gcm")]
+#[test]
+fn decode_pbes2_aesgcm_invalid_icv_len() {
+    let mut alg_id = PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID.to_vec();
+    *alg_id.last_mut().unwrap() = 8;
+    assert!(pkcs5::EncryptionScheme::try_from(alg_id.as_slice()).is_err());
+}
+
+/// DER forbids an explicitly encoded aes-ICVlen of 12, the DEFAULT
+#[cfg(feature = "aes-gcm")]
+#[test]
+fn decode_pbes2_aesgcm_explicit_default_icv_len() {
+    let mut alg_id = PBES2_PBKDF2_SHA256_AES256GCM_ALG_ID.to_vec();
+    *alg_id.last_mut().unwrap() = 12;
+    let err = pkcs5::EncryptionScheme::try_from(alg_id.as_slice()).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        der::ErrorKind::Noncanonical {
+            tag: der::Tag::Integer
+        }
+    );
+}
+
+/// Only 12-byte aes-nonces are supported
+#[cfg(feature = "aes-gcm")]
+#[test]
+fn decode_pbes2_aesgcm_unsupported_nonce_len() {
+    use der::Decode;
+
+    let alg_id = hex!(
+        "301f0609608648016503040106301204100001020304050607080910111213
+         1415"
+    );
+    assert!(pbes2::EncryptionScheme::from_der(&alg_id).is_err());
+}
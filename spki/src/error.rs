@@ -10,7 +10,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 use der::pem;
 
 /// Error type
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Error {
     /// Algorithm parameters are missing.
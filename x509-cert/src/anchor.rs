@@ -5,9 +5,14 @@ use crate::{ext::Extensions, name::Name};
 use crate::{Certificate, TbsCertificate};
 
 use alloc::string::String;
+use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
 use der::asn1::OctetString;
 use der::flagset::{flags, FlagSet};
-use der::{Choice, Enumerated, Sequence};
+use der::{
+    Choice, DecodeValue, EncodeValue, Enumerated, FixedTag, Header, Length, Reader, Sequence, Tag,
+    Writer,
+};
 use spki::SubjectPublicKeyInfoOwned;
 
 /// Version identifier for TrustAnchorInfo
@@ -17,7 +22,7 @@ use spki::SubjectPublicKeyInfoOwned;
 pub enum Version {
     /// Version 1 (default)
     #[default]
-    V1 = 0,
+    V1 = 1,
 }
 
 /// ```text
@@ -133,3 +138,97 @@ pub enum TrustAnchorChoice {
     #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "true")]
     TaInfo(TrustAnchorInfo),
 }
+
+impl TrustAnchorChoice {
+    /// The public key of the trust anchor.
+    pub fn public_key(&self) -> &SubjectPublicKeyInfoOwned {
+        match self {
+            Self::Certificate(cert) => &cert.tbs_certificate.subject_public_key_info,
+            Self::TbsCertificate(tbs) => &tbs.subject_public_key_info,
+            Self::TaInfo(info) => &info.pub_key,
+        }
+    }
+
+    /// The name of the trust anchor.
+    ///
+    /// This is the subject of a certificate, or the `taName` of the
+    /// `certPath` of a `TrustAnchorInfo`. Returns `None` if a
+    /// `TrustAnchorInfo` has no `certPath`, in which case the trust anchor
+    /// can't be used for certification path validation.
+    pub fn name(&self) -> Option<&Name> {
+        match self {
+            Self::Certificate(cert) => Some(&cert.tbs_certificate.subject),
+            Self::TbsCertificate(tbs) => Some(&tbs.subject),
+            Self::TaInfo(info) => info.cert_path.as_ref().map(|cp| &cp.ta_name),
+        }
+    }
+}
+
+/// Content type of a [`TrustAnchorList`] as defined in [RFC 5914 Section 4].
+///
+/// ```text
+/// id-ct-trustAnchorList OBJECT IDENTIFIER ::= { iso(1)
+///     member-body(2) us(840) rsadsi(113549) pkcs(1) pkcs9(9)
+///     id-smime(16) id-ct(1) 34 }
+/// ```
+///
+/// [RFC 5914 Section 4]: https://datatracker.ietf.org/doc/html/rfc5914#section-4
+pub const ID_CT_TRUST_ANCHOR_LIST: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.34");
+
+/// `TrustAnchorList` as defined in [RFC 5914 Section 4].
+///
+/// ```text
+/// TrustAnchorList ::= SEQUENCE SIZE (1..MAX) OF TrustAnchorChoice
+/// ```
+///
+/// [RFC 5914 Section 4]: https://datatracker.ietf.org/doc/html/rfc5914#section-4
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrustAnchorList(pub Vec<TrustAnchorChoice>);
+
+impl From<Vec<TrustAnchorChoice>> for TrustAnchorList {
+    fn from(value: Vec<TrustAnchorChoice>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<TrustAnchorList> for Vec<TrustAnchorChoice> {
+    fn from(value: TrustAnchorList) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<[TrustAnchorChoice]> for TrustAnchorList {
+    fn as_ref(&self) -> &[TrustAnchorChoice] {
+        &self.0
+    }
+}
+
+impl FixedTag for TrustAnchorList {
+    const TAG: Tag = Tag::Sequence;
+}
+
+impl<'a> DecodeValue<'a> for TrustAnchorList {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let anchors = Vec::<TrustAnchorChoice>::decode_value(reader, header)?;
+
+        // SIZE (1..MAX)
+        if anchors.is_empty() {
+            return Err(Tag::Sequence.value_error());
+        }
+
+        Ok(Self(anchors))
+    }
+}
+
+impl EncodeValue for TrustAnchorList {
+    fn value_len(&self) -> der::Result<Length> {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.0.encode_value(writer)
+    }
+}
@@ -1,8 +1,17 @@
 //! Attribute-related definitions as defined in X.501 (and updated by RFC 5280).
 
-use alloc::vec::Vec;
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
 use const_oid::db::{
-    rfc4519::{COUNTRY_NAME, DOMAIN_COMPONENT, SERIAL_NUMBER},
+    rfc3280::EMAIL_ADDRESS,
+    rfc4519::{
+        BUSINESS_CATEGORY, C, CN, COUNTRY_NAME, DC, DN_QUALIFIER, DOMAIN_COMPONENT,
+        GENERATION_QUALIFIER, GIVEN_NAME, INITIALS, L, NAME, O, OU, POSTAL_CODE, SERIAL_NUMBER, SN,
+        ST, STREET, TITLE, UID,
+    },
     Database, DB,
 };
 use core::{
@@ -11,8 +20,8 @@ use core::{
 };
 use der::{
     asn1::{
-        Any, Ia5StringRef, ObjectIdentifier, PrintableStringRef, SetOfVec, TeletexStringRef,
-        Utf8StringRef,
+        Any, BmpString, Ia5StringRef, ObjectIdentifier, PrintableStringRef, SetOfVec,
+        TeletexStringRef, Utf8StringRef,
     },
     Decode, Encode, Error, ErrorKind, Sequence, Tag, Tagged, ValueOrd,
 };
@@ -57,6 +66,7 @@ pub type AttributeValue = Any;
 ///
 /// [RFC 2986 Section 4]: https://datatracker.ietf.org/doc/html/rfc2986#section-4
 /// [RFC 5280 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, PartialEq, Eq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct Attribute {
@@ -84,10 +94,16 @@ pub type Attributes = SetOfVec<Attribute>;
 ///
 /// [RFC 5280 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct AttributeTypeAndValue {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::oid"))]
     pub oid: AttributeType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::attribute_value")
+    )]
     pub value: AttributeValue,
 }
 
@@ -141,6 +157,14 @@ impl Parser {
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
+
+    /// Ensure the input did not end in the middle of an escape sequence.
+    pub fn finish(&self) -> Result<(), Error> {
+        match self.state {
+            Escape::None => Ok(()),
+            _ => Err(ErrorKind::Failed.into()),
+        }
+    }
 }
 
 impl AttributeTypeAndValue {
@@ -184,20 +208,19 @@ impl AttributeTypeAndValue {
         for c in val.bytes() {
             parser.add(c)?;
         }
+        parser.finish()?;
 
-        let tag = match oid {
-            COUNTRY_NAME => Tag::PrintableString,
-            DOMAIN_COMPONENT => Tag::Ia5String,
-            // Serial numbers are formatted as Printable String as per RFC 5280 Appendix A.1:
+        let bytes = parser.as_bytes();
+        let value = match oid {
+            // Country names, serial numbers and DN qualifiers are formatted as
+            // PrintableString as per RFC 5280 Appendix A.1:
             // https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
-            SERIAL_NUMBER => Tag::PrintableString,
-            _ => Tag::Utf8String,
+            COUNTRY_NAME | SERIAL_NUMBER | DN_QUALIFIER => PrintableStringRef::new(bytes)?.into(),
+            DOMAIN_COMPONENT | EMAIL_ADDRESS => Ia5StringRef::new(bytes)?.into(),
+            _ => Utf8StringRef::new(bytes)?.into(),
         };
 
-        Ok(Self {
-            oid,
-            value: Any::new(tag, parser.as_bytes())?,
-        })
+        Ok(Self { oid, value })
     }
 
     /// Converts an AttributeTypeAndValue string into an encoded AttributeTypeAndValue
@@ -212,6 +235,119 @@ impl AttributeTypeAndValue {
     pub fn encode_from_string(s: &str) -> Result<Vec<u8>, Error> {
         Self::from_str(s)?.to_der()
     }
+
+    /// Get the value as a string, if it is one of the supported string types.
+    pub(crate) fn value_str(&self) -> Option<Cow<'_, str>> {
+        match self.value.tag() {
+            Tag::PrintableString => PrintableStringRef::try_from(&self.value)
+                .ok()
+                .map(|s| s.as_str().into()),
+            Tag::Utf8String => Utf8StringRef::try_from(&self.value)
+                .ok()
+                .map(|s| s.as_str().into()),
+            Tag::Ia5String => Ia5StringRef::try_from(&self.value)
+                .ok()
+                .map(|s| s.as_str().into()),
+            Tag::TeletexString => TeletexStringRef::try_from(&self.value)
+                .ok()
+                .map(|s| s.as_str().into()),
+            Tag::BmpString => self
+                .value
+                .decode_as::<BmpString>()
+                .ok()
+                .map(|s| s.to_string().into()),
+            _ => None,
+        }
+    }
+
+    /// Does this attribute match `other` following the name comparison rules
+    /// of [RFC 5280 Section 7.1]?
+    ///
+    /// The attribute types must be equal. Values of the supported string
+    /// types are compared case-insensitively, ignoring leading and trailing
+    /// whitespace and treating internal runs of whitespace as a single space,
+    /// regardless of which string type each is encoded as. Other values must
+    /// be byte-for-byte identical.
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn matches(&self, other: &Self) -> bool {
+        if self.oid != other.oid {
+            return false;
+        }
+
+        if self.value == other.value {
+            return true;
+        }
+
+        match (self.value_str(), other.value_str()) {
+            (Some(a), Some(b)) => normalize(&a) == normalize(&b),
+            _ => false,
+        }
+    }
+
+    /// Write the value as `#` followed by its hex-encoded DER.
+    fn fmt_hex(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.value.to_der().or(Err(fmt::Error))?;
+
+        f.write_char('#')?;
+        for c in value {
+            write!(f, "{:02x}", c)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the structure the way OpenSSL's default `oneline` name
+    /// format does, e.g. `CN = example`.
+    ///
+    /// Values containing special characters are quoted rather than escaped.
+    pub(crate) fn fmt_openssl(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match openssl_short_name(&self.oid) {
+            Some(key) => write!(f, "{} = ", key)?,
+            None => write!(f, "{} = ", self.oid)?,
+        }
+
+        let val = match self.value_str() {
+            Some(val) => val,
+            None => return self.fmt_hex(f),
+        };
+
+        let quote = val.starts_with(|c| matches!(c, '#' | ' '))
+            || val.ends_with(' ')
+            || val.contains(|c| matches!(c, ',' | '+' | ';' | '<' | '>'));
+
+        if quote {
+            f.write_char('"')?;
+        }
+
+        for c in val.chars() {
+            match c {
+                '"' | '\\' => write!(f, "\\{}", c)?,
+                '\x00'..='\x1f' | '\x7f' => write!(f, "\\{:02X}", c as u8)?,
+                _ => f.write_char(c)?,
+            }
+        }
+
+        if quote {
+            f.write_char('"')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Fold the case of `s` and collapse its whitespace for comparison.
+fn normalize(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+
+    for word in s.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        normalized.extend(word.chars().flat_map(char::to_lowercase));
+    }
+
+    normalized
 }
 
 /// Parse an [`AttributeTypeAndValue`] string.
@@ -246,21 +382,12 @@ impl FromStr for AttributeTypeAndValue {
 /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
 impl fmt::Display for AttributeTypeAndValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let val = match self.value.tag() {
-            Tag::PrintableString => PrintableStringRef::try_from(&self.value)
-                .ok()
-                .map(|s| s.as_str()),
-            Tag::Utf8String => Utf8StringRef::try_from(&self.value)
-                .ok()
-                .map(|s| s.as_str()),
-            Tag::Ia5String => Ia5StringRef::try_from(&self.value).ok().map(|s| s.as_str()),
-            Tag::TeletexString => TeletexStringRef::try_from(&self.value)
-                .ok()
-                .map(|s| s.as_str()),
-            _ => None,
-        };
+        // As per RFC 4514 Section 2.4, values of attribute types given in
+        // dotted-decimal form are always hex-encoded
+        let key = DB.shortest_name_by_oid(&self.oid);
+        let val = key.and_then(|_| self.value_str());
 
-        if let (Some(key), Some(val)) = (DB.shortest_name_by_oid(&self.oid), val) {
+        if let (Some(key), Some(val)) = (key, val) {
             write!(f, "{}=", key.to_ascii_uppercase())?;
 
             let mut iter = val.char_indices().peekable();
@@ -274,18 +401,47 @@ impl fmt::Display for AttributeTypeAndValue {
                 }
             }
         } else {
-            let value = self.value.to_der().or(Err(fmt::Error))?;
-
-            write!(f, "{}=#", self.oid)?;
-            for c in value {
-                write!(f, "{:02x}", c)?;
+            match key {
+                Some(key) => write!(f, "{}=", key.to_ascii_uppercase())?,
+                None => write!(f, "{}=", self.oid)?,
             }
+
+            self.fmt_hex(f)?;
         }
 
         Ok(())
     }
 }
 
+/// Short names used by OpenSSL for common attribute types.
+fn openssl_short_name(oid: &ObjectIdentifier) -> Option<&'static str> {
+    let name = match *oid {
+        C => "C",
+        CN => "CN",
+        DC => "DC",
+        L => "L",
+        O => "O",
+        OU => "OU",
+        SN => "SN",
+        ST => "ST",
+        UID => "UID",
+        GIVEN_NAME => "GN",
+        STREET => "street",
+        TITLE => "title",
+        NAME => "name",
+        INITIALS => "initials",
+        GENERATION_QUALIFIER => "generationQualifier",
+        DN_QUALIFIER => "dnQualifier",
+        SERIAL_NUMBER => "serialNumber",
+        POSTAL_CODE => "postalCode",
+        BUSINESS_CATEGORY => "businessCategory",
+        EMAIL_ADDRESS => "emailAddress",
+        _ => return DB.shortest_name_by_oid(oid),
+    };
+
+    Some(name)
+}
+
 /// Helper trait to bring shortest name by oid lookups to Database
 trait ShortestName {
     fn shortest_name_by_oid(&self, oid: &ObjectIdentifier) -> Option<&str>;
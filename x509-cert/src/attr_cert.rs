@@ -0,0 +1,480 @@
+//! Attribute certificate types as defined in [RFC 5755].
+//!
+//! [RFC 5755]: https://datatracker.ietf.org/doc/html/rfc5755
+
+use crate::{
+    attr::Attribute,
+    ext::{
+        pkix::name::{GeneralName, GeneralNames},
+        Extensions,
+    },
+    serial_number::SerialNumber,
+};
+use alloc::{string::String, vec::Vec};
+use const_oid::{
+    db::rfc5912::{ID_ACA_ACCESS_IDENTITY, ID_ACA_AUTHENTICATION_INFO, ID_AT_ROLE},
+    AssociatedOid, ObjectIdentifier,
+};
+use core::cmp::Ordering;
+use der::{
+    asn1::{Any, BitString, GeneralizedTime, OctetString, SetOfVec},
+    flagset::{flags, FlagSet},
+    Choice, Enumerated, Sequence, ValueOrd,
+};
+use spki::AlgorithmIdentifierOwned;
+
+pub use const_oid::db::rfc5912::{ID_ACA_CHARGING_IDENTITY, ID_ACA_GROUP};
+
+/// `id-at-clearance` as defined in [RFC 5755 Section 4.4.6].
+///
+/// [RFC 5755 Section 4.4.6]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.6
+pub const ID_AT_CLEARANCE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.55");
+
+/// `AttributeCertificate` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttributeCertificate ::= SEQUENCE {
+///     acinfo               AttributeCertificateInfo,
+///     signatureAlgorithm   AlgorithmIdentifier,
+///     signatureValue       BIT STRING
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttributeCertificate {
+    pub acinfo: AttributeCertificateInfo,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
+}
+
+/// `AttributeCertificateInfo` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttributeCertificateInfo ::= SEQUENCE {
+///     version        AttCertVersion, -- version is v2
+///     holder         Holder,
+///     issuer         AttCertIssuer,
+///     signature      AlgorithmIdentifier,
+///     serialNumber   CertificateSerialNumber,
+///     attrCertValidityPeriod   AttCertValidityPeriod,
+///     attributes     SEQUENCE OF Attribute,
+///     issuerUniqueID UniqueIdentifier OPTIONAL,
+///     extensions     Extensions     OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttributeCertificateInfo {
+    pub version: AttCertVersion,
+    pub holder: Holder,
+    pub issuer: AttCertIssuer,
+    pub signature: AlgorithmIdentifierOwned,
+    pub serial_number: SerialNumber,
+    pub attr_cert_validity_period: AttCertValidityPeriod,
+    pub attributes: Vec<Attribute>,
+    pub issuer_unique_id: Option<BitString>,
+    pub extensions: Option<Extensions>,
+}
+
+/// `AttCertVersion` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertVersion ::= INTEGER { v2(1) }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+pub enum AttCertVersion {
+    /// Version 2
+    #[default]
+    V2 = 1,
+}
+
+impl ValueOrd for AttCertVersion {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        (*self as u8).value_cmp(&(*other as u8))
+    }
+}
+
+/// `Holder` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// Holder ::= SEQUENCE {
+///     baseCertificateID   [0] IssuerSerial OPTIONAL,
+///         -- the issuer and serial number of
+///         -- the holder's Public Key Certificate
+///     entityName          [1] GeneralNames OPTIONAL,
+///         -- the name of the claimant or role
+///     objectDigestInfo    [2] ObjectDigestInfo OPTIONAL
+///         -- used to directly authenticate the holder,
+///         -- for example, an executable
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct Holder {
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub base_certificate_id: Option<IssuerSerial>,
+
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub entity_name: Option<GeneralNames>,
+
+    #[asn1(
+        context_specific = "2",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub object_digest_info: Option<ObjectDigestInfo>,
+}
+
+/// `ObjectDigestInfo` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// ObjectDigestInfo ::= SEQUENCE {
+///     digestedObjectType  ENUMERATED {
+///         publicKey            (0),
+///         publicKeyCert        (1),
+///         otherObjectTypes     (2) },
+///             -- otherObjectTypes MUST NOT
+///             -- be used in this profile
+///     otherObjectTypeID   OBJECT IDENTIFIER  OPTIONAL,
+///     digestAlgorithm     AlgorithmIdentifier,
+///     objectDigest        BIT STRING
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct ObjectDigestInfo {
+    pub digested_object_type: DigestedObjectType,
+    pub other_object_type_id: Option<ObjectIdentifier>,
+    pub digest_algorithm: AlgorithmIdentifierOwned,
+    pub object_digest: BitString,
+}
+
+/// Type of the object digested in an [`ObjectDigestInfo`].
+///
+/// See [RFC 5755 Section 7.3] for how the digest is computed.
+///
+/// [RFC 5755 Section 7.3]: https://datatracker.ietf.org/doc/html/rfc5755#section-7.3
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enumerated)]
+#[allow(missing_docs)]
+#[repr(u8)]
+pub enum DigestedObjectType {
+    PublicKey = 0,
+    PublicKeyCert = 1,
+    OtherObjectTypes = 2,
+}
+
+impl ValueOrd for DigestedObjectType {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        (*self as u8).value_cmp(&(*other as u8))
+    }
+}
+
+/// `AttCertIssuer` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertIssuer ::= CHOICE {
+///     v1Form   GeneralNames,  -- MUST NOT be used in this
+///                             -- profile
+///     v2Form   [0] V2Form     -- v2 only
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum AttCertIssuer {
+    V1Form(GeneralNames),
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", constructed = "true")]
+    V2Form(V2Form),
+}
+
+/// `V2Form` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// V2Form ::= SEQUENCE {
+///     issuerName            GeneralNames  OPTIONAL,
+///     baseCertificateID     [0] IssuerSerial  OPTIONAL,
+///     objectDigestInfo      [1] ObjectDigestInfo  OPTIONAL
+///         -- issuerName MUST be present in this profile
+///         -- baseCertificateID and objectDigestInfo MUST
+///         -- NOT be present in this profile
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct V2Form {
+    pub issuer_name: Option<GeneralNames>,
+
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub base_certificate_id: Option<IssuerSerial>,
+
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub object_digest_info: Option<ObjectDigestInfo>,
+}
+
+/// `IssuerSerial` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// IssuerSerial  ::=  SEQUENCE {
+///     issuer         GeneralNames,
+///     serial         CertificateSerialNumber,
+///     issuerUID      UniqueIdentifier OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct IssuerSerial {
+    pub issuer: GeneralNames,
+    pub serial: SerialNumber,
+    pub issuer_uid: Option<BitString>,
+}
+
+/// `AttCertValidityPeriod` as defined in [RFC 5755 Section 4.1].
+///
+/// ```text
+/// AttCertValidityPeriod  ::= SEQUENCE {
+///     notBeforeTime  GeneralizedTime,
+///     notAfterTime   GeneralizedTime
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttCertValidityPeriod {
+    pub not_before_time: GeneralizedTime,
+    pub not_after_time: GeneralizedTime,
+}
+
+/// `SvceAuthInfo` as defined in [RFC 5755 Section 4.4.1].
+///
+/// This is the syntax of the `authenticationInfo` and `accessIdentity`
+/// attributes.
+///
+/// ```text
+/// SvceAuthInfo ::= SEQUENCE {
+///     service   GeneralName,
+///     ident     GeneralName,
+///     authInfo  OCTET STRING OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct SvceAuthInfo {
+    pub service: GeneralName,
+    pub ident: GeneralName,
+    pub auth_info: Option<OctetString>,
+}
+
+/// `authenticationInfo` attribute as defined in [RFC 5755 Section 4.4.1].
+///
+/// [RFC 5755 Section 4.4.1]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthenticationInfo(pub SvceAuthInfo);
+
+impl AssociatedOid for AuthenticationInfo {
+    const OID: ObjectIdentifier = ID_ACA_AUTHENTICATION_INFO;
+}
+
+impl_newtype!(AuthenticationInfo, SvceAuthInfo);
+
+/// `accessIdentity` attribute as defined in [RFC 5755 Section 4.4.2].
+///
+/// [RFC 5755 Section 4.4.2]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessIdentity(pub SvceAuthInfo);
+
+impl AssociatedOid for AccessIdentity {
+    const OID: ObjectIdentifier = ID_ACA_ACCESS_IDENTITY;
+}
+
+impl_newtype!(AccessIdentity, SvceAuthInfo);
+
+/// `IetfAttrSyntax` as defined in [RFC 5755 Section 4.4].
+///
+/// This is the syntax of the `chargingIdentity` ([`ID_ACA_CHARGING_IDENTITY`])
+/// and `group` ([`ID_ACA_GROUP`]) attributes.
+///
+/// ```text
+/// IetfAttrSyntax ::= SEQUENCE {
+///     policyAuthority [0] GeneralNames    OPTIONAL,
+///     values          SEQUENCE OF CHOICE {
+///                         octets    OCTET STRING,
+///                         oid       OBJECT IDENTIFIER,
+///                         string    UTF8String
+///     }
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.4]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct IetfAttrSyntax {
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub policy_authority: Option<GeneralNames>,
+    pub values: Vec<IetfAttrValue>,
+}
+
+/// A value of an [`IetfAttrSyntax`].
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum IetfAttrValue {
+    Octets(OctetString),
+    Oid(ObjectIdentifier),
+    String(String),
+}
+
+/// `RoleSyntax` as defined in [RFC 5755 Section 4.4.5].
+///
+/// ```text
+/// RoleSyntax ::= SEQUENCE {
+///     roleAuthority  [0] GeneralNames OPTIONAL,
+///     roleName       [1] GeneralName
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.4.5]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.5
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct RoleSyntax {
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "IMPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub role_authority: Option<GeneralNames>,
+
+    // `GeneralName` is a `CHOICE`, so this is always explicitly tagged
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT")]
+    pub role_name: GeneralName,
+}
+
+impl AssociatedOid for RoleSyntax {
+    const OID: ObjectIdentifier = ID_AT_ROLE;
+}
+
+flags! {
+    /// Classification flags as defined in [RFC 5755 Section 4.4.6].
+    ///
+    /// ```text
+    /// ClassList  ::=  BIT STRING {
+    ///     unmarked       (0),
+    ///     unclassified   (1),
+    ///     restricted     (2),
+    ///     confidential   (3),
+    ///     secret         (4),
+    ///     topSecret      (5)
+    /// }
+    /// ```
+    ///
+    /// [RFC 5755 Section 4.4.6]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.6
+    #[allow(missing_docs)]
+    pub enum Classes: u8 {
+        Unmarked = 1 << 0,
+        Unclassified = 1 << 1,
+        Restricted = 1 << 2,
+        Confidential = 1 << 3,
+        Secret = 1 << 4,
+        TopSecret = 1 << 5,
+    }
+}
+
+/// `ClassList` as defined in [RFC 5755 Section 4.4.6].
+pub type ClassList = FlagSet<Classes>;
+
+/// `Clearance` as defined in [RFC 5755 Section 4.4.6].
+///
+/// ```text
+/// Clearance  ::=  SEQUENCE {
+///     policyId       OBJECT IDENTIFIER,
+///     classList      ClassList DEFAULT {unclassified},
+///     securityCategories  SET OF SecurityCategory  OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.4.6]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.6
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct Clearance {
+    pub policy_id: ObjectIdentifier,
+
+    #[asn1(default = "default_class_list")]
+    pub class_list: ClassList,
+
+    pub security_categories: Option<SetOfVec<SecurityCategory>>,
+}
+
+impl AssociatedOid for Clearance {
+    const OID: ObjectIdentifier = ID_AT_CLEARANCE;
+}
+
+fn default_class_list() -> ClassList {
+    Classes::Unclassified.into()
+}
+
+/// `SecurityCategory` as defined in [RFC 5755 Section 4.4.6].
+///
+/// ```text
+/// SecurityCategory ::= SEQUENCE {
+///     type      [0]  IMPLICIT OBJECT IDENTIFIER,
+///     value     [1]  ANY DEFINED BY type
+/// }
+/// ```
+///
+/// [RFC 5755 Section 4.4.6]: https://datatracker.ietf.org/doc/html/rfc5755#section-4.4.6
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct SecurityCategory {
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT")]
+    pub category_type: ObjectIdentifier,
+
+    // An open type can't be implicitly tagged
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT")]
+    pub value: Any,
+}
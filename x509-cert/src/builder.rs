@@ -1,7 +1,43 @@
 //! X509 Certificate builder
+//!
+//! [`CertificateBuilder`] assembles a [`TbsCertificate`] from its subject,
+//! issuer, validity, subject public key and serial number, along with any
+//! additional extensions, and signs it using any [`Signer`] to produce a
+//! [`Certificate`].
+//!
+//! The [`Profile`] of the certificate selects the default extensions:
+//!
+//! - [`Profile::Root`]: self-signed CA certificate with `BasicConstraints`
+//!   `cA=TRUE` and the `keyCertSign` and `cRLSign` key usages.
+//! - [`Profile::SubCA`]: intermediate CA certificate with an optional
+//!   `pathLenConstraint` and the same key usages as a root.
+//! - [`Profile::Leaf`]: end entity certificate with `BasicConstraints`
+//!   `cA=FALSE` and the `digitalSignature` and `nonRepudiation` key usages,
+//!   optionally with `keyEncipherment` and/or `keyAgreement`. Leaf
+//!   certificates for X25519 and X448 keys only get the `keyAgreement` key
+//!   usage.
+//!
+//! All of them get a `SubjectKeyIdentifier` derived from the subject's public
+//! key, which leaf certificates can opt out of with the `hazmat` feature. Both
+//! [`Profile::SubCA`] and [`Profile::Leaf`] certificates also get an
+//! `AuthorityKeyIdentifier` derived from the issuer's public key.
+//!
+//! [`RequestBuilder`] similarly builds a PKCS#10 [`CertReq`], and
+//! [`CertificateListBuilder`] builds a [`CertificateList`] (CRL).
+//!
+//! Additional extensions can be added to any of them one by one, or prepared
+//! up front using an [`ExtensionsBuilder`].
+//!
+//! The Ed25519, Ed448, X25519 and X448 algorithms of [RFC 8410] must be
+//! identified without `parameters`. The builders reject public keys and
+//! signers which encode them otherwise, e.g. with NULL parameters.
+//! [`rfc8410_public_key`] assembles the public key info of such a key.
+//!
+//! [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410
 
 use alloc::vec;
 use async_signature::{AsyncRandomizedSigner, AsyncSigner};
+use const_oid::db::rfc8410::{ID_ED_25519, ID_ED_448, ID_X_25519, ID_X_448};
 use core::fmt;
 use der::{asn1::BitString, referenced::OwnedToRef, Encode};
 use signature::{rand_core::CryptoRngCore, Keypair, RandomizedSigner, Signer};
@@ -12,16 +48,18 @@ use spki::{
 
 use crate::{
     certificate::{Certificate, TbsCertificate, Version},
+    crl::{CertificateList, RevokedCert, TbsCertList},
     ext::{
         pkix::{
-            AuthorityKeyIdentifier, BasicConstraints, KeyUsage, KeyUsages, SubjectKeyIdentifier,
+            AuthorityKeyIdentifier, BasicConstraints, CrlNumber, CrlReason, KeyUsage, KeyUsages,
+            SubjectKeyIdentifier,
         },
         AsExtension, Extension, Extensions,
     },
     name::Name,
     request::{attributes::AsAttribute, CertReq, CertReqInfo, ExtensionReq},
     serial_number::SerialNumber,
-    time::Validity,
+    time::{Time, Validity},
 };
 
 const NULL_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.0.0");
@@ -38,6 +76,12 @@ pub enum Error {
 
     /// Signing error propagated for the [`signature::Error`] type.
     Signature(signature::Error),
+
+    /// An extension with the given OID was added more than once.
+    DuplicateExtension(ObjectIdentifier),
+
+    /// The algorithm with the given OID has invalid parameters.
+    AlgorithmParameters(ObjectIdentifier),
 }
 
 #[cfg(feature = "std")]
@@ -49,6 +93,10 @@ impl fmt::Display for Error {
             Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
             Error::PublicKey(err) => write!(f, "public key error: {}", err),
             Error::Signature(err) => write!(f, "signature error: {}", err),
+            Error::DuplicateExtension(oid) => write!(f, "duplicate extension: {}", oid),
+            Error::AlgorithmParameters(oid) => {
+                write!(f, "invalid parameters for algorithm: {}", oid)
+            }
         }
     }
 }
@@ -74,6 +122,49 @@ impl From<signature::Error> for Error {
 /// Result type
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Assembles the [`SubjectPublicKeyInfoOwned`] of an Ed25519, Ed448, X25519
+/// or X448 `public_key` as defined in [RFC 8410 Section 4].
+///
+/// Returns an error if `algorithm` isn't one of these algorithms, or if the
+/// length of `public_key` doesn't match it.
+///
+/// [RFC 8410 Section 4]: https://datatracker.ietf.org/doc/html/rfc8410#section-4
+pub fn rfc8410_public_key(
+    algorithm: ObjectIdentifier,
+    public_key: &[u8],
+) -> Result<SubjectPublicKeyInfoOwned> {
+    let len = match algorithm {
+        ID_ED_25519 | ID_X_25519 => 32,
+        ID_ED_448 => 57,
+        ID_X_448 => 56,
+        oid => return Err(spki::Error::OidUnknown { oid }.into()),
+    };
+
+    if public_key.len() != len {
+        return Err(spki::Error::KeyMalformed.into());
+    }
+
+    Ok(SubjectPublicKeyInfoOwned {
+        algorithm: AlgorithmIdentifier {
+            oid: algorithm,
+            parameters: None,
+        },
+        subject_public_key: BitString::from_bytes(public_key)?,
+    })
+}
+
+/// Checks that the `parameters` of the algorithms of [RFC 8410] are absent.
+///
+/// [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410#section-3
+fn check_algorithm<P>(algorithm: &AlgorithmIdentifier<P>) -> Result<()> {
+    match algorithm.oid {
+        ID_ED_25519 | ID_ED_448 | ID_X_25519 | ID_X_448 if algorithm.parameters.is_some() => {
+            Err(Error::AlgorithmParameters(algorithm.oid))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// The type of certificate to build
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Profile {
@@ -141,6 +232,9 @@ impl Profile {
 
         let mut extensions: vec::Vec<Extension> = vec::Vec::new();
 
+        // X25519 and X448 keys can't sign, see RFC 8410 Section 5
+        let key_agreement_only = matches!(spk.algorithm.oid, ID_X_25519 | ID_X_448);
+
         match self {
             #[cfg(feature = "hazmat")]
             Profile::Leaf {
@@ -195,6 +289,12 @@ impl Profile {
                         .to_extension(&tbs.subject, &extensions)?,
                 );
             }
+            Profile::Leaf { .. } if key_agreement_only => {
+                extensions.push(
+                    KeyUsage(KeyUsages::KeyAgreement.into())
+                        .to_extension(&tbs.subject, &extensions)?,
+                );
+            }
             Profile::Leaf {
                 enable_key_agreement,
                 enable_key_encipherment,
@@ -280,6 +380,8 @@ impl CertificateBuilder {
             parameters: None,
         };
 
+        check_algorithm(&subject_public_key_info.algorithm)?;
+
         let issuer = profile.get_issuer(&subject);
 
         validity.not_before.rfc5280_adjust_utc_time()?;
@@ -313,14 +415,127 @@ impl CertificateBuilder {
     }
 
     /// Add an extension to this certificate
+    ///
+    /// Building the certificate fails if the [`Profile`] adds an extension of
+    /// the same type.
     pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
         let ext = extension.to_extension(&self.tbs.subject, &self.extensions)?;
-        self.extensions.push(ext);
+        extend_extensions(&mut self.extensions, [ext])
+    }
 
-        Ok(())
+    /// Add the extensions prepared by an [`ExtensionsBuilder`] to this
+    /// certificate
+    pub fn add_extensions(&mut self, extensions: Extensions) -> Result<()> {
+        extend_extensions(&mut self.extensions, extensions)
+    }
+}
+
+/// Builder for a set of X509 v3 [`Extensions`]
+///
+/// Extensions are marked with the criticality [RFC 5280] mandates for their
+/// type (see [`AsExtension::critical`]) unless explicitly overridden, and
+/// adding more than one instance of a particular extension is an error.
+///
+/// ```
+/// use der::asn1::Ia5String;
+/// use x509_cert::{
+///     builder::ExtensionsBuilder,
+///     ext::pkix::{name::GeneralName, KeyUsage, KeyUsages, SubjectAltName},
+///     name::Name,
+/// };
+/// use std::str::FromStr;
+///
+/// let subject = Name::from_str("CN=service.domination.world").unwrap();
+///
+/// let mut builder = ExtensionsBuilder::new(subject);
+/// builder
+///     .add(&KeyUsage(KeyUsages::DigitalSignature.into()))
+///     .unwrap();
+/// builder
+///     .add_with_criticality(
+///         &SubjectAltName(vec![GeneralName::DnsName(
+///             Ia5String::new("service.domination.world").unwrap(),
+///         )]),
+///         true,
+///     )
+///     .unwrap();
+///
+/// let extensions = builder.build();
+/// assert!(extensions.iter().all(|ext| ext.critical));
+/// ```
+///
+/// [RFC 5280]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2
+#[derive(Clone, Debug)]
+pub struct ExtensionsBuilder {
+    subject: Name,
+    extensions: Extensions,
+}
+
+impl ExtensionsBuilder {
+    /// Creates a new extensions builder
+    ///
+    /// The subject is used to determine the default criticality of some
+    /// extensions, e.g. `SubjectAltName`.
+    pub fn new(subject: Name) -> Self {
+        Self {
+            subject,
+            extensions: Extensions::default(),
+        }
+    }
+
+    /// Add an extension with its default criticality
+    pub fn add<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
+        let ext = extension.to_extension(&self.subject, &self.extensions)?;
+        self.add_encoded(ext)
+    }
+
+    /// Add an extension, overriding its default criticality
+    pub fn add_with_criticality<E: AsExtension>(
+        &mut self,
+        extension: &E,
+        critical: bool,
+    ) -> Result<()> {
+        let mut ext = extension.to_extension(&self.subject, &self.extensions)?;
+        ext.critical = critical;
+        self.add_encoded(ext)
+    }
+
+    /// Add an already encoded extension
+    pub fn add_encoded(&mut self, extension: Extension) -> Result<()> {
+        extend_extensions(&mut self.extensions, [extension])
+    }
+
+    /// Returns the extensions added to this builder
+    pub fn build(self) -> Extensions {
+        self.extensions
     }
 }
 
+/// Append `new` to `extensions`, rejecting duplicate extensions.
+///
+/// From [RFC 5280 Section 4.2]:
+///   A certificate MUST NOT include more than one instance of a particular
+///   extension.
+///
+/// [RFC 5280 Section 4.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2
+fn extend_extensions(
+    extensions: &mut Extensions,
+    new: impl IntoIterator<Item = Extension>,
+) -> Result<()> {
+    for extension in new {
+        if extensions
+            .iter()
+            .any(|ext| ext.extn_id == extension.extn_id)
+        {
+            return Err(Error::DuplicateExtension(extension.extn_id));
+        }
+
+        extensions.push(extension);
+    }
+
+    Ok(())
+}
+
 /// Builder for X509 Certificate Requests
 ///
 /// ```
@@ -386,10 +601,13 @@ impl RequestBuilder {
     /// Add an extension to this certificate request
     pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
         let ext = extension.to_extension(&self.info.subject, &self.extension_req.0)?;
+        extend_extensions(&mut self.extension_req.0, [ext])
+    }
 
-        self.extension_req.0.push(ext);
-
-        Ok(())
+    /// Add the extensions prepared by an [`ExtensionsBuilder`] to this
+    /// certificate request
+    pub fn add_extensions(&mut self, extensions: Extensions) -> Result<()> {
+        extend_extensions(&mut self.extension_req.0, extensions)
     }
 
     /// Add an attribute to this certificate request
@@ -401,6 +619,139 @@ impl RequestBuilder {
     }
 }
 
+/// Builder for X509 Certificate Revocation Lists
+///
+/// The `CRLNumber` extension and an `AuthorityKeyIdentifier` extension
+/// derived from the public key of the signer are always included, as required
+/// by [RFC 5280 Section 5.2].
+///
+/// ```
+/// # use p256::{pkcs8::DecodePrivateKey, NistP256, ecdsa::DerSignature};
+/// # const PKCS8_PRIVATE_KEY_DER: &[u8] = include_bytes!("../tests/examples/p256-priv.der");
+/// # fn ecdsa_signer() -> ecdsa::SigningKey<NistP256> {
+/// #     let secret_key = p256::SecretKey::from_pkcs8_der(PKCS8_PRIVATE_KEY_DER).unwrap();
+/// #     ecdsa::SigningKey::from(secret_key)
+/// # }
+/// use der::asn1::Uint;
+/// use x509_cert::{
+///     builder::{Builder, CertificateListBuilder},
+///     ext::pkix::{CrlNumber, CrlReason},
+///     name::Name,
+///     serial_number::SerialNumber,
+///     time::Time,
+/// };
+/// use std::{str::FromStr, time::{Duration, SystemTime}};
+///
+/// let issuer = Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+/// let this_update = Time::try_from(SystemTime::now()).unwrap();
+/// let next_update = Time::try_from(SystemTime::now() + Duration::from_secs(86400)).unwrap();
+/// let crl_number = CrlNumber(Uint::new(&[1]).unwrap());
+///
+/// let signer = ecdsa_signer();
+/// let mut builder =
+///     CertificateListBuilder::new(issuer, this_update, Some(next_update), crl_number)
+///         .expect("Create CRL builder");
+/// builder
+///     .add_revoked_certificate(
+///         SerialNumber::from(42u32),
+///         this_update,
+///         Some(CrlReason::KeyCompromise),
+///     )
+///     .unwrap();
+///
+/// let crl = builder.build::<_, DerSignature>(&signer).unwrap();
+/// ```
+///
+/// [RFC 5280 Section 5.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2
+pub struct CertificateListBuilder {
+    tbs: TbsCertList,
+    revoked_certificates: vec::Vec<RevokedCert>,
+    extensions: Extensions,
+    crl_number: CrlNumber,
+}
+
+impl CertificateListBuilder {
+    /// Creates a new certificate revocation list builder
+    pub fn new(
+        issuer: Name,
+        mut this_update: Time,
+        mut next_update: Option<Time>,
+        crl_number: CrlNumber,
+    ) -> Result<Self> {
+        let signature = AlgorithmIdentifier {
+            oid: NULL_OID,
+            parameters: None,
+        };
+
+        this_update.rfc5280_adjust_utc_time()?;
+        if let Some(next_update) = &mut next_update {
+            next_update.rfc5280_adjust_utc_time()?;
+        }
+
+        let tbs = TbsCertList {
+            // Extensions are always present, which requires v2
+            version: Version::V2,
+            signature,
+            issuer,
+            this_update,
+            next_update,
+            revoked_certificates: None,
+            crl_extensions: None,
+        };
+
+        Ok(Self {
+            tbs,
+            revoked_certificates: vec::Vec::new(),
+            extensions: Extensions::default(),
+            crl_number,
+        })
+    }
+
+    /// Add a revoked certificate to this CRL, with a `reasonCode` entry
+    /// extension if a `reason` is provided.
+    pub fn add_revoked_certificate(
+        &mut self,
+        serial_number: SerialNumber,
+        revocation_date: Time,
+        reason: Option<CrlReason>,
+    ) -> Result<()> {
+        let crl_entry_extensions = reason
+            .map(|reason| {
+                reason
+                    .to_extension(&self.tbs.issuer, &[])
+                    .map(|ext| vec![ext])
+            })
+            .transpose()?;
+
+        self.add_revoked_cert(RevokedCert {
+            serial_number,
+            revocation_date,
+            crl_entry_extensions,
+        })
+    }
+
+    /// Add a revoked certificate entry with arbitrary entry extensions to
+    /// this CRL.
+    pub fn add_revoked_cert(&mut self, mut revoked_cert: RevokedCert) -> Result<()> {
+        revoked_cert.revocation_date.rfc5280_adjust_utc_time()?;
+        self.revoked_certificates.push(revoked_cert);
+
+        Ok(())
+    }
+
+    /// Add an extension to this CRL
+    pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
+        let ext = extension.to_extension(&self.tbs.issuer, &self.extensions)?;
+        extend_extensions(&mut self.extensions, [ext])
+    }
+
+    /// Add the extensions prepared by an [`ExtensionsBuilder`] to this
+    /// certificate revocation list
+    pub fn add_extensions(&mut self, extensions: Extensions) -> Result<()> {
+        extend_extensions(&mut self.extensions, extensions)
+    }
+}
+
 /// Trait for X509 builders
 ///
 /// This trait defines the interface between builder and the signers.
@@ -465,16 +816,18 @@ impl Builder for CertificateBuilder {
     {
         let verifying_key = cert_signer.verifying_key();
         let signer_pub = SubjectPublicKeyInfoOwned::from_key(&verifying_key)?;
+        check_algorithm(&signer_pub.algorithm)?;
 
         self.tbs.signature = cert_signer.signature_algorithm_identifier()?;
+        check_algorithm(&self.tbs.signature)?;
 
-        let mut default_extensions = self.profile.build_extensions(
+        let default_extensions = self.profile.build_extensions(
             self.tbs.subject_public_key_info.owned_to_ref(),
             signer_pub.owned_to_ref(),
             &self.tbs,
         )?;
 
-        self.extensions.append(&mut default_extensions);
+        extend_extensions(&mut self.extensions, default_extensions)?;
 
         if !self.extensions.is_empty() {
             self.tbs.extensions = Some(self.extensions.clone());
@@ -516,11 +869,16 @@ impl Builder for RequestBuilder {
     {
         let verifying_key = signer.verifying_key();
         let public_key = SubjectPublicKeyInfoOwned::from_key(&verifying_key)?;
+        check_algorithm(&public_key.algorithm)?;
+        check_algorithm(&signer.signature_algorithm_identifier()?)?;
         self.info.public_key = public_key;
 
-        self.info
-            .attributes
-            .insert(self.extension_req.clone().try_into()?)?;
+        // `ExtensionReq` must contain at least one extension
+        if !self.extension_req.0.is_empty() {
+            self.info
+                .attributes
+                .insert(self.extension_req.clone().try_into()?)?;
+        }
 
         self.info.to_der().map_err(Error::from)
     }
@@ -540,6 +898,54 @@ impl Builder for RequestBuilder {
     }
 }
 
+impl Builder for CertificateListBuilder {
+    type Output = CertificateList;
+
+    fn finalize<S>(&mut self, signer: &S) -> Result<vec::Vec<u8>>
+    where
+        S: Keypair + DynSignatureAlgorithmIdentifier,
+        S::VerifyingKey: EncodePublicKey,
+    {
+        let verifying_key = signer.verifying_key();
+        let signer_pub = SubjectPublicKeyInfoOwned::from_key(&verifying_key)?;
+        check_algorithm(&signer_pub.algorithm)?;
+
+        self.tbs.signature = signer.signature_algorithm_identifier()?;
+        check_algorithm(&self.tbs.signature)?;
+
+        let authority_key_identifier = AuthorityKeyIdentifier::try_from(signer_pub.owned_to_ref())?
+            .to_extension(&self.tbs.issuer, &self.extensions)?;
+        extend_extensions(&mut self.extensions, [authority_key_identifier])?;
+
+        let crl_number = self
+            .crl_number
+            .to_extension(&self.tbs.issuer, &self.extensions)?;
+        extend_extensions(&mut self.extensions, [crl_number])?;
+
+        self.tbs.crl_extensions = Some(self.extensions.clone());
+
+        if !self.revoked_certificates.is_empty() {
+            self.tbs.revoked_certificates = Some(self.revoked_certificates.clone());
+        }
+
+        self.tbs.to_der().map_err(Error::from)
+    }
+
+    fn assemble<S>(self, signature: BitString, _signer: &S) -> Result<Self::Output>
+    where
+        S: Keypair + DynSignatureAlgorithmIdentifier,
+        S::VerifyingKey: EncodePublicKey,
+    {
+        let signature_algorithm = self.tbs.signature.clone();
+
+        Ok(CertificateList {
+            tbs_cert_list: self.tbs,
+            signature_algorithm,
+            signature,
+        })
+    }
+}
+
 /// Trait for async X509 builders
 ///
 /// This trait defines the interface between builder and the signers.
@@ -1,17 +1,34 @@
 //! Certificate types
 
-use crate::{name::Name, serial_number::SerialNumber, time::Validity};
+use crate::{
+    ext::{
+        pkix::{self, name::GeneralName, SubjectAltName},
+        Extension,
+    },
+    name::Name,
+    serial_number::SerialNumber,
+    time::Validity,
+};
 use alloc::vec::Vec;
-use const_oid::AssociatedOid;
+use const_oid::{db::rfc4519::COMMON_NAME, AssociatedOid};
 use core::{cmp::Ordering, fmt::Debug};
 use der::asn1::BitString;
-use der::{Decode, Enumerated, ErrorKind, Sequence, Tag, ValueOrd};
+use der::{Decode, Enumerated, Sequence, Spanned, Tag, ValueOrd};
 use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
 
+#[cfg(feature = "fingerprint")]
+use {
+    der::{referenced::OwnedToRef, Encode},
+    digest::{Digest, Output},
+};
+
 #[cfg(feature = "pem")]
-use der::{
-    pem::{self, PemLabel},
-    DecodePem,
+use {
+    alloc::string::String,
+    der::{
+        pem::{self, PemLabel},
+        DecodePem, EncodePem,
+    },
 };
 
 /// [`Profile`] allows the consumer of this crate to customize the behavior when parsing
@@ -59,6 +76,7 @@ impl Profile for Raw {
 ///
 /// [RFC 5280 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Enumerated)]
 #[asn1(type = "INTEGER")]
 #[repr(u8)]
@@ -114,6 +132,8 @@ pub type TbsCertificate = TbsCertificateInner<Rfc5280>;
 ///
 /// [RFC 5280 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct TbsCertificateInner<P: Profile + 'static = Rfc5280> {
@@ -127,16 +147,32 @@ pub struct TbsCertificateInner<P: Profile + 'static = Rfc5280> {
     pub version: Version,
 
     pub serial_number: SerialNumber<P>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::AlgorithmIdentifierDef")
+    )]
     pub signature: AlgorithmIdentifierOwned,
     pub issuer: Name,
     pub validity: Validity,
     pub subject: Name,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::SubjectPublicKeyInfoDef")
+    )]
     pub subject_public_key_info: SubjectPublicKeyInfoOwned,
 
     #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::option_bit_string")
+    )]
     pub issuer_unique_id: Option<BitString>,
 
     #[asn1(context_specific = "2", tag_mode = "IMPLICIT", optional = "true")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::option_bit_string")
+    )]
     pub subject_unique_id: Option<BitString>,
 
     #[asn1(context_specific = "3", tag_mode = "EXPLICIT", optional = "true")]
@@ -152,14 +188,22 @@ impl<P: Profile> TbsCertificateInner<P> {
     pub fn get<'a, T: Decode<'a> + AssociatedOid>(
         &'a self,
     ) -> Result<Option<(bool, T)>, <T as Decode<'a>>::Error> {
-        let mut iter = self.filter::<T>().peekable();
-        match iter.next() {
-            None => Ok(None),
-            Some(item) => match iter.peek() {
-                Some(..) => Err(der::Error::from(ErrorKind::Failed).into()),
-                None => Ok(Some(item?)),
-            },
-        }
+        crate::ext::get(&self.extensions)
+    }
+
+    /// Iterate over the extensions for which this crate does not provide a
+    /// typed representation.
+    ///
+    /// This is useful for rejecting unrecognized critical extensions, as
+    /// required by [RFC 5280 Section 4.2].
+    ///
+    /// [RFC 5280 Section 4.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2
+    pub fn unrecognized_extensions(&self) -> impl '_ + Iterator<Item = &'_ Extension> {
+        self.extensions
+            .as_deref()
+            .unwrap_or(&[])
+            .iter()
+            .filter(|e| !pkix::is_supported_certificate_extension(&e.extn_id))
     }
 
     /// Filters extensions by an associated OID
@@ -168,12 +212,7 @@ impl<P: Profile> TbsCertificateInner<P> {
     pub fn filter<'a, T: Decode<'a> + AssociatedOid>(
         &'a self,
     ) -> impl 'a + Iterator<Item = Result<(bool, T), <T as Decode<'a>>::Error>> {
-        self.extensions
-            .as_deref()
-            .unwrap_or(&[])
-            .iter()
-            .filter(|e| e.extn_id == T::OID)
-            .map(|e| Ok((e.critical, T::from_der(e.extn_value.as_bytes())?)))
+        crate::ext::filter(&self.extensions)
     }
 }
 
@@ -194,11 +233,18 @@ pub type Certificate = CertificateInner<Rfc5280>;
 ///
 /// [RFC 5280 Section 4.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct CertificateInner<P: Profile + 'static = Rfc5280> {
     pub tbs_certificate: TbsCertificateInner<P>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::AlgorithmIdentifierDef")
+    )]
     pub signature_algorithm: AlgorithmIdentifierOwned,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::bit_string"))]
     pub signature: BitString,
 }
 
@@ -207,6 +253,311 @@ impl<P: Profile> PemLabel for CertificateInner<P> {
     const PEM_LABEL: &'static str = "CERTIFICATE";
 }
 
+/// X.509 certificate which retains the original encoding of its
+/// `TBSCertificate`.
+///
+/// Decoding a [`CertificateInner`] and re-encoding its `TBSCertificate`
+/// doesn't necessarily reproduce the bytes which were signed, e.g. if the
+/// certificate isn't encoded in canonical DER and was decoded with a lenient
+/// [`Profile`]. This type records the exact encoding of the
+/// `TBSCertificate` as it was decoded, so that its signature can be verified
+/// over those bytes.
+///
+/// ```
+/// use der::{Decode, Encode};
+/// use x509_cert::{certificate::SpannedCertificate, Certificate};
+///
+/// let der = include_bytes!("../tests/examples/amazon.der");
+/// let cert = SpannedCertificate::<'_>::from_der(der).unwrap();
+///
+/// // This certificate is encoded in canonical DER
+/// let tbs_certificate = cert.tbs_certificate.value();
+/// assert_eq!(cert.tbs_certificate_der(), tbs_certificate.to_der().unwrap());
+///
+/// let cert = Certificate::from(cert);
+/// assert_eq!(cert.to_der().unwrap(), der);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SpannedCertificate<'a, P: Profile + 'static = Rfc5280> {
+    pub tbs_certificate: Spanned<'a, TbsCertificateInner<P>>,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
+}
+
+impl<'a, P: Profile> SpannedCertificate<'a, P> {
+    /// Get the original encoding of the `TBSCertificate`, i.e. the bytes
+    /// over which the certificate was signed.
+    pub fn tbs_certificate_der(&self) -> &'a [u8] {
+        self.tbs_certificate.as_bytes()
+    }
+}
+
+impl<P: Profile> From<SpannedCertificate<'_, P>> for CertificateInner<P> {
+    fn from(cert: SpannedCertificate<'_, P>) -> Self {
+        Self {
+            tbs_certificate: cert.tbs_certificate.into_inner(),
+            signature_algorithm: cert.signature_algorithm,
+            signature: cert.signature,
+        }
+    }
+}
+
+impl<P: Profile> CertificateInner<P> {
+    /// Is this certificate valid for `hostname`, following the rules of
+    /// [RFC 6125 Section 6.4]?
+    ///
+    /// The `dNSName` entries of the subject alternative name extension are
+    /// used if there are any. Only otherwise is the most specific common
+    /// name of the subject used. Names are compared case-insensitively,
+    /// ignoring a trailing `.`. A wildcard is only supported as the complete
+    /// left-most label of a name with at least two more labels, e.g.
+    /// `*.example.com`, and matches exactly one label.
+    ///
+    /// An IP address `hostname` is never compared to DNS names. With the
+    /// `std` feature it is matched against the `iPAddress` entries instead,
+    /// see [`CertificateInner::matches_ip`], otherwise it matches nothing.
+    ///
+    /// Returns `false` if the subject alternative name extension can't be
+    /// decoded.
+    ///
+    /// [RFC 6125 Section 6.4]: https://datatracker.ietf.org/doc/html/rfc6125#section-6.4
+    pub fn matches_hostname(&self, hostname: &str) -> bool {
+        #[cfg(feature = "std")]
+        if let Ok(ip) = hostname.parse::<std::net::IpAddr>() {
+            return self.matches_ip(ip);
+        }
+
+        let hostname = hostname.strip_suffix('.').unwrap_or(hostname);
+        if hostname.is_empty() || is_ip_literal(hostname) {
+            return false;
+        }
+
+        let tbs = &self.tbs_certificate;
+        let san = match tbs.get::<SubjectAltName>() {
+            Ok(san) => san,
+            Err(_) => return false,
+        };
+
+        let mut dns_names = san
+            .iter()
+            .flat_map(|(_, san)| san.0.iter())
+            .filter_map(|name| match name {
+                GeneralName::DnsName(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .peekable();
+
+        if dns_names.peek().is_some() {
+            return dns_names.any(|pattern| dns_pattern_matches(pattern, hostname));
+        }
+
+        tbs.subject
+            .0
+            .iter()
+            .rev()
+            .flat_map(|rdn| rdn.0.iter())
+            .find(|atv| atv.oid == COMMON_NAME)
+            .and_then(|atv| atv.value_str())
+            .is_some_and(|cn| dns_pattern_matches(&cn, hostname))
+    }
+
+    /// Is this certificate valid for the IP address `ip`?
+    ///
+    /// Only the `iPAddress` entries of the subject alternative name extension
+    /// are considered, as required by [RFC 6125 Section 6.4]. They are
+    /// compared as described by [`GeneralName::matches`]. Returns `false` if
+    /// the extension can't be decoded.
+    ///
+    /// [RFC 6125 Section 6.4]: https://datatracker.ietf.org/doc/html/rfc6125#section-6.4
+    #[cfg(feature = "std")]
+    pub fn matches_ip(&self, ip: std::net::IpAddr) -> bool {
+        let ip = GeneralName::from(ip);
+
+        match self.tbs_certificate.get::<SubjectAltName>() {
+            Ok(Some((_, san))) => san.0.iter().any(|name| name.matches(&ip)),
+            _ => false,
+        }
+    }
+
+    /// Is the current system time within the validity period of this
+    /// certificate?
+    ///
+    /// This only checks the validity period; it doesn't verify the signature
+    /// or the certification path of the certificate.
+    #[cfg(feature = "std")]
+    pub fn is_currently_valid(&self) -> bool {
+        self.tbs_certificate.validity.is_currently_valid()
+    }
+
+    /// Compute the fingerprint of this certificate, i.e. the digest `D` of
+    /// its DER encoding.
+    #[cfg(feature = "fingerprint")]
+    pub fn fingerprint<D: Digest>(&self) -> der::Result<Output<D>> {
+        Ok(D::digest(self.to_der()?))
+    }
+
+    /// Is this certificate self-issued, i.e. do its subject and issuer match?
+    ///
+    /// Names are compared following [RFC 5280 Section 7.1], see
+    /// [`Name::matches`].
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn is_self_issued(&self) -> bool {
+        let tbs = &self.tbs_certificate;
+        tbs.subject == tbs.issuer || tbs.subject.matches(&tbs.issuer)
+    }
+
+    /// Does the subject of this certificate match the issuer of `cert`?
+    ///
+    /// The names are first compared by their DER encodings, and then
+    /// following [RFC 5280 Section 7.1], see [`Name::matches`].
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn is_issuer_name_of(&self, cert: &Self) -> bool {
+        let subject = &self.tbs_certificate.subject;
+        let issuer = &cert.tbs_certificate.issuer;
+        subject == issuer || subject.matches(issuer)
+    }
+
+    /// Does the [`AuthorityKeyIdentifier`] extension of `cert` identify this
+    /// certificate as its issuer?
+    ///
+    /// Both forms of the extension described in [RFC 5280 Section 4.2.1.1]
+    /// are checked, when present:
+    ///
+    /// - the `keyIdentifier` is compared to the [`SubjectKeyIdentifier`] of
+    ///   this certificate. With the `fingerprint` feature, it's instead
+    ///   derived from the public key of this certificate if the latter has no
+    ///   such extension.
+    /// - the `authorityCertIssuer` and `authorityCertSerialNumber` are
+    ///   compared to the issuer and serial number of this certificate.
+    ///
+    /// Returns `Ok(None)` if there is nothing to compare, e.g. if `cert` has
+    /// no `AuthorityKeyIdentifier`, and otherwise whether all the present
+    /// forms match.
+    ///
+    /// [`AuthorityKeyIdentifier`]: pkix::AuthorityKeyIdentifier
+    /// [`SubjectKeyIdentifier`]: pkix::SubjectKeyIdentifier
+    /// [RFC 5280 Section 4.2.1.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.1
+    pub fn is_authority_key_of(&self, cert: &Self) -> der::Result<Option<bool>> {
+        let tbs = &self.tbs_certificate;
+        let aki = match cert.tbs_certificate.get::<pkix::AuthorityKeyIdentifier>()? {
+            Some((_, aki)) => aki,
+            None => return Ok(None),
+        };
+
+        let mut result = None;
+
+        if let Some(key_identifier) = &aki.key_identifier {
+            let matches = match tbs.get::<pkix::SubjectKeyIdentifier>()? {
+                Some((_, ski)) => Some(ski.0 == *key_identifier),
+                #[cfg(feature = "fingerprint")]
+                None => Some(
+                    pkix::KeyIdentifierMethod::identify(
+                        key_identifier.as_bytes(),
+                        tbs.subject_public_key_info.owned_to_ref(),
+                    )
+                    .is_some(),
+                ),
+                #[cfg(not(feature = "fingerprint"))]
+                None => None,
+            };
+
+            match matches {
+                Some(false) => return Ok(Some(false)),
+                Some(true) => result = Some(true),
+                None => {}
+            }
+        }
+
+        if let (Some(issuer), Some(serial_number)) = (
+            &aki.authority_cert_issuer,
+            &aki.authority_cert_serial_number,
+        ) {
+            let issuer_matches = issuer.iter().any(|name| match name {
+                GeneralName::DirectoryName(name) => tbs.issuer == *name || tbs.issuer.matches(name),
+                _ => false,
+            });
+
+            if !issuer_matches || serial_number.as_bytes() != tbs.serial_number.as_bytes() {
+                return Ok(Some(false));
+            }
+
+            result = Some(true);
+        }
+
+        Ok(result)
+    }
+
+    /// May the key of this certificate be used to sign certificates?
+    ///
+    /// This requires the [`BasicConstraints`] extension asserting `cA`, as
+    /// required by [RFC 5280 Section 6.1.4], and, if the [`KeyUsage`]
+    /// extension is present, the `keyCertSign` bit.
+    ///
+    /// Version 1 and 2 certificates can't contain these extensions, so this
+    /// is always `false` for them. Whether to accept them as trust anchors is
+    /// left to the caller.
+    ///
+    /// [`BasicConstraints`]: pkix::BasicConstraints
+    /// [`KeyUsage`]: pkix::KeyUsage
+    /// [RFC 5280 Section 6.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.4
+    pub fn can_sign_certificates(&self) -> der::Result<bool> {
+        let tbs = &self.tbs_certificate;
+
+        let ca = tbs
+            .get::<pkix::BasicConstraints>()?
+            .is_some_and(|(_, bc)| bc.ca);
+        let key_cert_sign = match tbs.get::<pkix::KeyUsage>()? {
+            Some((_, ku)) => ku.key_cert_sign(),
+            None => true,
+        };
+
+        Ok(ca && key_cert_sign)
+    }
+
+    /// Could this certificate plausibly have issued `cert`?
+    ///
+    /// This combines [`CertificateInner::is_issuer_name_of`],
+    /// [`CertificateInner::is_authority_key_of`], which must not indicate a
+    /// mismatch, and [`CertificateInner::can_sign_certificates`]. These are
+    /// the checks needed to select candidate issuers when building a
+    /// certification path. They don't verify the signature of `cert` nor any
+    /// other part of the path validation algorithm.
+    pub fn could_have_issued(&self, cert: &Self) -> der::Result<bool> {
+        Ok(self.is_issuer_name_of(cert)
+            && self.is_authority_key_of(cert)? != Some(false)
+            && self.can_sign_certificates()?)
+    }
+}
+
+/// Does `hostname` look like an IPv4 or IPv6 address rather than a DNS name?
+///
+/// No DNS name consists only of digits and dots, or contains a colon.
+fn is_ip_literal(hostname: &str) -> bool {
+    hostname.contains(':') || hostname.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+}
+
+/// Does the DNS name `pattern`, which may contain a wildcard, match `hostname`?
+fn dns_pattern_matches(pattern: &str, hostname: &str) -> bool {
+    let pattern = pattern.strip_suffix('.').unwrap_or(pattern);
+
+    match pattern.strip_prefix("*.") {
+        Some(rest) => {
+            if !rest.contains('.') || rest.contains('*') {
+                return false;
+            }
+
+            match hostname.split_once('.') {
+                Some((label, hostname)) => !label.is_empty() && hostname.eq_ignore_ascii_case(rest),
+                None => false,
+            }
+        }
+        None => !pattern.contains('*') && pattern.eq_ignore_ascii_case(hostname),
+    }
+}
+
 /// `PkiPath` as defined by X.509 and referenced by [RFC 6066].
 ///
 /// This contains a series of certificates in validation order from the
@@ -224,8 +575,12 @@ pub type PkiPath = Vec<Certificate>;
 impl<P: Profile> CertificateInner<P> {
     /// Parse a chain of pem-encoded certificates from a slice.
     ///
+    /// This handles concatenated PEM files such as a `fullchain.pem`. Like
+    /// OpenSSL, any text outside of the `CERTIFICATE` blocks (explanatory
+    /// text, other PEM blocks, blank lines) is ignored.
+    ///
     /// Returns the list of certificates.
-    pub fn load_pem_chain(mut input: &[u8]) -> Result<Vec<Self>, der::Error> {
+    pub fn load_pem_chain(input: &[u8]) -> Result<Vec<Self>, der::Error> {
         fn find_boundary<T>(haystack: &[T], needle: &[T]) -> Option<usize>
         where
             for<'a> &'a [T]: PartialEq,
@@ -235,39 +590,33 @@ impl<P: Profile> CertificateInner<P> {
                 .position(|window| window == needle)
         }
 
-        let mut certs = Vec::new();
-        let mut position: usize = 0;
-
+        let begin_boundary = &b"-----BEGIN CERTIFICATE-----"[..];
         let end_boundary = &b"-----END CERTIFICATE-----"[..];
 
-        // Strip the trailing whitespaces
-        loop {
-            if input.is_empty() {
-                break;
-            }
-            let last_pos = input.len() - 1;
+        let mut certs = Vec::new();
+        let mut rest = input;
 
-            match input.get(last_pos) {
-                Some(b'\r') | Some(b'\n') => {
-                    input = &input[..last_pos];
-                }
-                _ => break,
-            }
-        }
+        while let Some(begin_pos) = find_boundary(rest, begin_boundary) {
+            rest = &rest[begin_pos..];
 
-        while position < input.len() - 1 {
-            let rest = &input[position..];
             let end_pos = find_boundary(rest, end_boundary)
                 .ok_or(pem::Error::PostEncapsulationBoundary)?
                 + end_boundary.len();
 
-            let cert_buf = &rest[..end_pos];
-            let cert = Self::from_pem(cert_buf)?;
-            certs.push(cert);
-
-            position += end_pos;
+            certs.push(Self::from_pem(&rest[..end_pos])?);
+            rest = &rest[end_pos..];
         }
 
         Ok(certs)
     }
+
+    /// Serialize a chain of certificates as concatenated PEM blocks.
+    ///
+    /// This is the inverse of [`CertificateInner::load_pem_chain`].
+    pub fn to_pem_chain(
+        certs: &[Self],
+        line_ending: pem::LineEnding,
+    ) -> Result<String, der::Error> {
+        certs.iter().map(|cert| cert.to_pem(line_ending)).collect()
+    }
 }
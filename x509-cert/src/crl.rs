@@ -1,5 +1,11 @@
 //! Certificate Revocation List types
 
+use crate::ext::pkix::crl::{
+    BaseCrlNumber, CertificateIssuer, CrlNumber, CrlReason, FreshestCrl, InvalidityDate,
+    IssuingDistributionPoint,
+};
+use crate::ext::pkix::name::GeneralNames;
+use crate::ext::pkix::AuthorityKeyIdentifier;
 use crate::ext::Extensions;
 use crate::name::Name;
 use crate::serial_number::SerialNumber;
@@ -8,8 +14,9 @@ use crate::Version;
 
 use alloc::vec::Vec;
 
-use der::asn1::BitString;
-use der::{Sequence, ValueOrd};
+use const_oid::AssociatedOid;
+use der::asn1::{BitString, GeneralizedTime};
+use der::{Decode, Sequence, Tag, ValueOrd};
 use spki::AlgorithmIdentifierOwned;
 
 /// `CertificateList` as defined in [RFC 5280 Section 5.1].
@@ -23,6 +30,7 @@ use spki::AlgorithmIdentifierOwned;
 /// ```
 ///
 /// [RFC 5280 Section 5.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct CertificateList {
@@ -45,6 +53,7 @@ pub struct CertificateList {
 /// ```
 ///
 /// [RFC 5280 Section 5.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct RevokedCert {
@@ -53,6 +62,75 @@ pub struct RevokedCert {
     pub crl_entry_extensions: Option<Extensions>,
 }
 
+impl RevokedCert {
+    /// Decodes a single CRL entry extension
+    ///
+    /// Returns an error if multiple of these extensions is present. Returns
+    /// `Ok(None)` if the extension is not present. Returns a decoding error
+    /// if decoding failed. Otherwise returns the extension.
+    pub fn get<'a, T: Decode<'a> + AssociatedOid>(
+        &'a self,
+    ) -> Result<Option<(bool, T)>, <T as Decode<'a>>::Error> {
+        crate::ext::get(&self.crl_entry_extensions)
+    }
+
+    /// Returns a filtered iterator over all the CRL entry extensions with the OID.
+    pub fn filter<'a, T: Decode<'a> + AssociatedOid>(
+        &'a self,
+    ) -> impl 'a + Iterator<Item = Result<(bool, T), <T as Decode<'a>>::Error>> {
+        crate::ext::filter(&self.crl_entry_extensions)
+    }
+
+    /// The `reasonCode` extension, see [RFC 5280 Section 5.3.1].
+    ///
+    /// [RFC 5280 Section 5.3.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.1
+    pub fn reason(&self) -> der::Result<Option<CrlReason>> {
+        Ok(self.get::<CrlReason>()?.map(|(_, reason)| reason))
+    }
+
+    /// The `invalidityDate` extension, i.e. the date on which the private key
+    /// was compromised or the certificate otherwise became invalid, see
+    /// [RFC 5280 Section 5.3.2].
+    ///
+    /// [RFC 5280 Section 5.3.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.2
+    pub fn invalidity_date(&self) -> der::Result<Option<InvalidityDate>> {
+        Ok(self.get::<InvalidityDate>()?.map(|(_, date)| date))
+    }
+
+    /// The `certificateIssuer` extension of an entry in an indirect CRL, see
+    /// [RFC 5280 Section 5.3.3].
+    ///
+    /// [RFC 5280 Section 5.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.3
+    pub fn certificate_issuer(&self) -> der::Result<Option<CertificateIssuer>> {
+        Ok(self.get::<CertificateIssuer>()?.map(|(_, issuer)| issuer))
+    }
+}
+
+/// A revoked certificate with its CRL entry extensions decoded.
+///
+/// See [`TbsCertList::revoked`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevokedEntry<'a> {
+    /// The serial number of the revoked certificate.
+    pub serial_number: &'a SerialNumber,
+
+    /// The date on which the revocation occurred.
+    pub revocation_date: Time,
+
+    /// The `reasonCode` of the entry, if any.
+    pub reason: Option<CrlReason>,
+
+    /// The `invalidityDate` of the entry, if any.
+    pub invalidity_date: Option<GeneralizedTime>,
+
+    /// The issuer of the revoked certificate.
+    ///
+    /// This is the `certificateIssuer` of the entry, or of the closest
+    /// preceding entry which has one. `None` if the certificate was issued by
+    /// the CRL issuer.
+    pub certificate_issuer: Option<GeneralNames>,
+}
+
 /// `TbsCertList` as defined in [RFC 5280 Section 5.1].
 ///
 /// ```text
@@ -72,6 +150,7 @@ pub struct RevokedCert {
 /// ```
 ///
 /// [RFC 5280 Section 5.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct TbsCertList {
@@ -85,3 +164,164 @@ pub struct TbsCertList {
     #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
     pub crl_extensions: Option<Extensions>,
 }
+
+impl TbsCertList {
+    /// Decodes a single CRL extension
+    ///
+    /// Returns an error if multiple of these extensions is present. Returns
+    /// `Ok(None)` if the extension is not present. Returns a decoding error
+    /// if decoding failed. Otherwise returns the extension.
+    pub fn get<'a, T: Decode<'a> + AssociatedOid>(
+        &'a self,
+    ) -> Result<Option<(bool, T)>, <T as Decode<'a>>::Error> {
+        crate::ext::get(&self.crl_extensions)
+    }
+
+    /// Returns a filtered iterator over all the CRL extensions with the OID.
+    pub fn filter<'a, T: Decode<'a> + AssociatedOid>(
+        &'a self,
+    ) -> impl 'a + Iterator<Item = Result<(bool, T), <T as Decode<'a>>::Error>> {
+        crate::ext::filter(&self.crl_extensions)
+    }
+
+    /// The `authorityKeyIdentifier` extension, see [RFC 5280 Section 5.2.1].
+    ///
+    /// [RFC 5280 Section 5.2.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.1
+    pub fn authority_key_identifier(&self) -> der::Result<Option<AuthorityKeyIdentifier>> {
+        Ok(self.get::<AuthorityKeyIdentifier>()?.map(|(_, aki)| aki))
+    }
+
+    /// The `cRLNumber` extension, see [RFC 5280 Section 5.2.3].
+    ///
+    /// [RFC 5280 Section 5.2.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.3
+    pub fn crl_number(&self) -> der::Result<Option<CrlNumber>> {
+        Ok(self.get::<CrlNumber>()?.map(|(_, number)| number))
+    }
+
+    /// The `deltaCRLIndicator` extension, i.e. the number of the complete CRL
+    /// this delta CRL updates, see [RFC 5280 Section 5.2.4].
+    ///
+    /// [RFC 5280 Section 5.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.4
+    pub fn base_crl_number(&self) -> der::Result<Option<BaseCrlNumber>> {
+        Ok(self.get::<BaseCrlNumber>()?.map(|(_, number)| number))
+    }
+
+    /// The `issuingDistributionPoint` extension, see [RFC 5280 Section 5.2.5].
+    ///
+    /// [RFC 5280 Section 5.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.5
+    pub fn issuing_distribution_point(&self) -> der::Result<Option<IssuingDistributionPoint>> {
+        Ok(self.get::<IssuingDistributionPoint>()?.map(|(_, idp)| idp))
+    }
+
+    /// The `freshestCRL` extension, pointing to the delta CRLs for this
+    /// complete CRL, see [RFC 5280 Section 5.2.6].
+    ///
+    /// [RFC 5280 Section 5.2.6]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.6
+    pub fn freshest_crl(&self) -> der::Result<Option<FreshestCrl>> {
+        Ok(self.get::<FreshestCrl>()?.map(|(_, freshest)| freshest))
+    }
+
+    /// Returns an iterator over the revoked certificates with their CRL entry
+    /// extensions decoded.
+    ///
+    /// The `certificateIssuer` of an entry applies to all following entries
+    /// until another entry changes it, see [RFC 5280 Section 5.3.3].
+    ///
+    /// [RFC 5280 Section 5.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.3
+    pub fn revoked(&self) -> impl '_ + Iterator<Item = der::Result<RevokedEntry<'_>>> {
+        let mut certificate_issuer = None;
+        self.revoked_certificates.iter().flatten().map(move |cert| {
+            if let Some(issuer) = cert.certificate_issuer()? {
+                certificate_issuer = Some(issuer.0);
+            }
+
+            Ok(RevokedEntry {
+                serial_number: &cert.serial_number,
+                revocation_date: cert.revocation_date,
+                reason: cert.reason()?,
+                invalidity_date: cert.invalidity_date()?.map(|date| date.0),
+                certificate_issuer: certificate_issuer.clone(),
+            })
+        })
+    }
+
+    /// Is this a delta CRL?
+    ///
+    /// A CRL is a delta CRL if it carries the `deltaCRLIndicator` extension,
+    /// and a complete CRL otherwise.
+    pub fn is_delta_crl(&self) -> bool {
+        self.crl_extensions
+            .iter()
+            .flatten()
+            .any(|e| e.extn_id == BaseCrlNumber::OID)
+    }
+
+    /// Classifies the certificates covered by this CRL according to its
+    /// `issuingDistributionPoint` extension.
+    ///
+    /// Returns an error if the extension can't be decoded, or if more than one
+    /// of `onlyContainsUserCerts`, `onlyContainsCACerts` and
+    /// `onlyContainsAttributeCerts` is set.
+    pub fn scope(&self) -> der::Result<CrlScope> {
+        let idp = match self.issuing_distribution_point()? {
+            Some(idp) => idp,
+            None => return Ok(CrlScope::default()),
+        };
+
+        let certificates = match (
+            idp.only_contains_user_certs,
+            idp.only_contains_ca_certs,
+            idp.only_contains_attribute_certs,
+        ) {
+            (false, false, false) => CrlCertificates::All,
+            (true, false, false) => CrlCertificates::User,
+            (false, true, false) => CrlCertificates::Ca,
+            (false, false, true) => CrlCertificates::Attribute,
+            _ => return Err(Tag::Sequence.value_error()),
+        };
+
+        Ok(CrlScope {
+            certificates,
+            partitioned_by_reasons: idp.only_some_reasons.is_some(),
+            indirect: idp.indirect_crl,
+        })
+    }
+}
+
+/// The kind of certificates covered by a CRL.
+///
+/// See the `onlyContains*` fields of the `issuingDistributionPoint` extension
+/// in [RFC 5280 Section 5.2.5].
+///
+/// [RFC 5280 Section 5.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.5
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CrlCertificates {
+    /// All kinds of certificates.
+    #[default]
+    All,
+
+    /// Only end entity public key certificates.
+    User,
+
+    /// Only CA public key certificates.
+    Ca,
+
+    /// Only attribute certificates.
+    Attribute,
+}
+
+/// Scope of a CRL as described by its `issuingDistributionPoint` extension.
+///
+/// A CRL without the extension covers all certificates of its issuer for all
+/// revocation reasons, which is the [`Default`] scope.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct CrlScope {
+    /// The kind of certificates covered by the CRL.
+    pub certificates: CrlCertificates,
+
+    /// Only some revocation reasons are covered by the CRL.
+    pub partitioned_by_reasons: bool,
+
+    /// The CRL may list certificates issued by other CAs than the CRL issuer.
+    pub indirect: bool,
+}
@@ -1,7 +1,7 @@
 //! Standardized X.509 Certificate Extensions
 
 use const_oid::AssociatedOid;
-use der::{asn1::OctetString, Sequence, ValueOrd};
+use der::{asn1::OctetString, Decode, ErrorKind, Sequence, ValueOrd};
 use spki::ObjectIdentifier;
 
 pub mod pkix;
@@ -24,6 +24,14 @@ pub mod pkix;
 ///
 /// [RFC 5280 Section 4.1.2.9]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.9
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "crate::serde_helpers::ExtensionRepr",
+        try_from = "crate::serde_helpers::ExtensionRepr"
+    )
+)]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct Extension {
@@ -44,6 +52,37 @@ pub struct Extension {
 /// [RFC 5280 Section 4.1.2.9]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.9
 pub type Extensions = alloc::vec::Vec<Extension>;
 
+/// Decodes a single extension, see [`filter`] for the extensions considered.
+///
+/// Returns an error if multiple of these extensions is present. Returns
+/// `Ok(None)` if the extension is not present. Returns a decoding error
+/// if decoding failed. Otherwise returns the extension.
+pub(crate) fn get<'a, T: Decode<'a> + AssociatedOid>(
+    extensions: &'a Option<Extensions>,
+) -> Result<Option<(bool, T)>, <T as Decode<'a>>::Error> {
+    let mut iter = filter::<T>(extensions).peekable();
+    match iter.next() {
+        None => Ok(None),
+        Some(item) => match iter.peek() {
+            Some(..) => Err(der::Error::from(ErrorKind::Failed).into()),
+            None => Ok(Some(item?)),
+        },
+    }
+}
+
+/// Returns a filtered iterator over all the extensions with the OID
+/// associated with `T`, decoded along with their criticality.
+pub(crate) fn filter<'a, T: Decode<'a> + AssociatedOid>(
+    extensions: &'a Option<Extensions>,
+) -> impl 'a + Iterator<Item = Result<(bool, T), <T as Decode<'a>>::Error>> {
+    extensions
+        .as_deref()
+        .unwrap_or(&[])
+        .iter()
+        .filter(|e| e.extn_id == T::OID)
+        .map(|e| Ok((e.critical, T::from_der(e.extn_value.as_bytes())?)))
+}
+
 /// Trait to be implemented by extensions to allow them to be formated as x509 v3 extensions by
 /// builder.
 pub trait AsExtension: AssociatedOid + der::Encode {
@@ -4,9 +4,13 @@ pub mod certpolicy;
 pub mod constraints;
 pub mod crl;
 pub mod name;
+pub mod qcstatements;
+pub mod resources;
 
 mod access;
 mod authkeyid;
+#[cfg(feature = "fingerprint")]
+mod keyid;
 mod keyusage;
 mod policymap;
 #[cfg(feature = "sct")]
@@ -20,26 +24,34 @@ pub use certpolicy::CertificatePolicies;
 use const_oid::{AssociatedOid, ObjectIdentifier};
 pub use constraints::{BasicConstraints, NameConstraints, PolicyConstraints};
 pub use crl::{
-    BaseCrlNumber, CrlDistributionPoints, CrlNumber, CrlReason, FreshestCrl,
-    IssuingDistributionPoint,
+    BaseCrlNumber, CertificateIssuer, CrlDistributionPoints, CrlNumber, CrlReason, FreshestCrl,
+    InvalidityDate, IssuingDistributionPoint,
 };
+#[cfg(feature = "fingerprint")]
+pub use keyid::KeyIdentifierMethod;
 pub use keyusage::{ExtendedKeyUsage, KeyUsage, KeyUsages, PrivateKeyUsagePeriod};
 pub use policymap::{PolicyMapping, PolicyMappings};
+pub use qcstatements::{BiometricSyntax, QcStatements};
+pub use resources::{AsIdentifiers, IpAddrBlocks};
 
 #[cfg(feature = "sct")]
 pub use sct::{
-    Error, HashAlgorithm, SerializedSct, SignatureAlgorithm, SignatureAndHashAlgorithm,
-    SignedCertificateTimestamp, SignedCertificateTimestampList, Version,
+    Error, HashAlgorithm, PrecertificatePoison, SerializedSct, SignatureAlgorithm,
+    SignatureAndHashAlgorithm, SignedCertificateTimestamp, SignedCertificateTimestampList, Version,
 };
 
 pub use const_oid::db::rfc5280::{
     ID_CE_INHIBIT_ANY_POLICY, ID_CE_ISSUER_ALT_NAME, ID_CE_SUBJECT_ALT_NAME,
     ID_CE_SUBJECT_DIRECTORY_ATTRIBUTES, ID_CE_SUBJECT_KEY_IDENTIFIER,
 };
+pub use const_oid::db::rfc6960::ID_PKIX_OCSP_NOCHECK;
 
 use alloc::vec::Vec;
 
-use der::asn1::OctetString;
+use der::{
+    asn1::{Null, OctetString},
+    DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, Writer,
+};
 
 /// SubjectKeyIdentifier as defined in [RFC 5280 Section 4.2.1.2].
 ///
@@ -145,3 +157,80 @@ impl AssociatedOid for InhibitAnyPolicy {
 
 impl_newtype!(InhibitAnyPolicy, u32);
 impl_extension!(InhibitAnyPolicy, critical = true);
+
+/// OcspNoCheck as defined in [RFC 6960 Section 4.2.2.2.1].
+///
+/// This extension marks the certificate of an OCSP responder whose
+/// revocation status should not be checked by clients.
+///
+/// ```text
+/// OcspNoCheck ::= NULL
+/// ```
+///
+/// [RFC 6960 Section 4.2.2.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.2.2.1
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct OcspNoCheck;
+
+impl AssociatedOid for OcspNoCheck {
+    const OID: ObjectIdentifier = ID_PKIX_OCSP_NOCHECK;
+}
+
+impl_extension!(OcspNoCheck, critical = false);
+
+impl FixedTag for OcspNoCheck {
+    const TAG: Tag = Tag::Null;
+}
+
+impl<'a> DecodeValue<'a> for OcspNoCheck {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        Null::decode_value(reader, header).map(|_| Self)
+    }
+}
+
+impl EncodeValue for OcspNoCheck {
+    fn value_len(&self) -> der::Result<Length> {
+        Null.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        Null.encode_value(writer)
+    }
+}
+
+/// Is `oid` one of the certificate extensions for which this module provides
+/// a typed representation?
+pub(crate) fn is_supported_certificate_extension(oid: &ObjectIdentifier) -> bool {
+    #[cfg(feature = "sct")]
+    if *oid == SignedCertificateTimestampList::OID || *oid == PrecertificatePoison::OID {
+        return true;
+    }
+
+    [
+        AsIdentifiers::OID,
+        AuthorityInfoAccessSyntax::OID,
+        AuthorityKeyIdentifier::OID,
+        BasicConstraints::OID,
+        BiometricSyntax::OID,
+        CertificatePolicies::OID,
+        CrlDistributionPoints::OID,
+        ExtendedKeyUsage::OID,
+        FreshestCrl::OID,
+        InhibitAnyPolicy::OID,
+        IpAddrBlocks::OID,
+        IssuerAltName::OID,
+        KeyUsage::OID,
+        NameConstraints::OID,
+        OcspNoCheck::OID,
+        PolicyConstraints::OID,
+        PolicyMappings::OID,
+        PrivateKeyUsagePeriod::OID,
+        QcStatements::OID,
+        SubjectAltName::OID,
+        SubjectDirectoryAttributes::OID,
+        SubjectInfoAccessSyntax::OID,
+        SubjectKeyIdentifier::OID,
+    ]
+    .contains(oid)
+}
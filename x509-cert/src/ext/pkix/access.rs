@@ -3,7 +3,10 @@ use super::name::GeneralName;
 use alloc::vec::Vec;
 
 use const_oid::{
-    db::rfc5280::{ID_PE_AUTHORITY_INFO_ACCESS, ID_PE_SUBJECT_INFO_ACCESS},
+    db::rfc5280::{
+        ID_AD_CA_ISSUERS, ID_AD_CA_REPOSITORY, ID_AD_OCSP, ID_AD_TIME_STAMPING,
+        ID_PE_AUTHORITY_INFO_ACCESS, ID_PE_SUBJECT_INFO_ACCESS,
+    },
     AssociatedOid,
 };
 use der::{asn1::ObjectIdentifier, Sequence, ValueOrd};
@@ -25,6 +28,27 @@ impl AssociatedOid for AuthorityInfoAccessSyntax {
 impl_newtype!(AuthorityInfoAccessSyntax, Vec<AccessDescription>);
 impl_extension!(AuthorityInfoAccessSyntax, critical = false);
 
+impl AuthorityInfoAccessSyntax {
+    /// Iterate over the access descriptions with the given `access_method`.
+    pub fn filter(
+        &self,
+        access_method: ObjectIdentifier,
+    ) -> impl '_ + Iterator<Item = &'_ AccessDescription> {
+        filter(&self.0, access_method)
+    }
+
+    /// Iterate over the URIs of the OCSP responders (`id-ad-ocsp`).
+    pub fn ocsp_responders(&self) -> impl '_ + Iterator<Item = &'_ str> {
+        uris(&self.0, ID_AD_OCSP)
+    }
+
+    /// Iterate over the URIs where certificates issued to the issuer of the
+    /// certificate can be found (`id-ad-caIssuers`).
+    pub fn ca_issuers(&self) -> impl '_ + Iterator<Item = &'_ str> {
+        uris(&self.0, ID_AD_CA_ISSUERS)
+    }
+}
+
 /// SubjectInfoAccessSyntax as defined in [RFC 5280 Section 4.2.2.2].
 ///
 /// ```text
@@ -42,6 +66,28 @@ impl AssociatedOid for SubjectInfoAccessSyntax {
 impl_newtype!(SubjectInfoAccessSyntax, Vec<AccessDescription>);
 impl_extension!(SubjectInfoAccessSyntax, critical = false);
 
+impl SubjectInfoAccessSyntax {
+    /// Iterate over the access descriptions with the given `access_method`.
+    pub fn filter(
+        &self,
+        access_method: ObjectIdentifier,
+    ) -> impl '_ + Iterator<Item = &'_ AccessDescription> {
+        filter(&self.0, access_method)
+    }
+
+    /// Iterate over the URIs where certificates issued by the subject of the
+    /// certificate can be found (`id-ad-caRepository`).
+    pub fn ca_repositories(&self) -> impl '_ + Iterator<Item = &'_ str> {
+        uris(&self.0, ID_AD_CA_REPOSITORY)
+    }
+
+    /// Iterate over the URIs of the time stamping services offered by the
+    /// subject of the certificate (`id-ad-timeStamping`).
+    pub fn time_stamping(&self) -> impl '_ + Iterator<Item = &'_ str> {
+        uris(&self.0, ID_AD_TIME_STAMPING)
+    }
+}
+
 /// AccessDescription as defined in [RFC 5280 Section 4.2.2.1].
 ///
 /// ```text
@@ -58,3 +104,31 @@ pub struct AccessDescription {
     pub access_method: ObjectIdentifier,
     pub access_location: GeneralName,
 }
+
+impl AccessDescription {
+    /// Get the access location if it is a `uniformResourceIdentifier`.
+    pub fn uri(&self) -> Option<&str> {
+        match &self.access_location {
+            GeneralName::UniformResourceIdentifier(uri) => Some(uri.as_str()),
+            _ => None,
+        }
+    }
+}
+
+fn filter(
+    descriptions: &[AccessDescription],
+    access_method: ObjectIdentifier,
+) -> impl '_ + Iterator<Item = &'_ AccessDescription> {
+    descriptions
+        .iter()
+        .filter(move |description| description.access_method == access_method)
+}
+
+/// URIs of the access locations with the given `access_method`. Other kinds of
+/// access locations, e.g. `directoryName`, are skipped.
+fn uris(
+    descriptions: &[AccessDescription],
+    access_method: ObjectIdentifier,
+) -> impl '_ + Iterator<Item = &'_ str> {
+    filter(descriptions, access_method).filter_map(AccessDescription::uri)
+}
@@ -1,11 +1,17 @@
 //! PKIX Certificate Policies extension
 
 use alloc::{string::String, vec::Vec};
+use core::fmt;
 
+use const_oid::db::rfc5280::{ID_QT_CPS, ID_QT_UNOTICE};
 use const_oid::db::rfc5912::ID_CE_CERTIFICATE_POLICIES;
 use const_oid::AssociatedOid;
-use der::asn1::{GeneralizedTime, Ia5String, ObjectIdentifier, Uint};
-use der::{Any, Choice, Sequence, ValueOrd};
+use der::asn1::{AnyRef, BmpString, Ia5String, ObjectIdentifier, Uint};
+use der::{
+    Any, Choice, Decode, EncodeValue, Length, Reader, Sequence, Tag, Tagged, ValueOrd, Writer,
+};
+
+pub use const_oid::db::rfc5280::ANY_POLICY;
 
 /// CertificatePolicies as defined in [RFC 5280 Section 4.2.1.4].
 ///
@@ -47,6 +53,25 @@ pub struct PolicyInformation {
     pub policy_qualifiers: Option<Vec<PolicyQualifierInfo>>,
 }
 
+impl PolicyInformation {
+    /// Create a new [`PolicyInformation`] for `policy_identifier` without
+    /// any qualifiers.
+    pub fn new(policy_identifier: ObjectIdentifier) -> Self {
+        Self {
+            policy_identifier,
+            policy_qualifiers: None,
+        }
+    }
+
+    /// Append `qualifier` to the qualifiers of this policy.
+    pub fn with_qualifier(mut self, qualifier: &PolicyQualifier) -> der::Result<Self> {
+        self.policy_qualifiers
+            .get_or_insert_with(Vec::new)
+            .push(PolicyQualifierInfo::new(qualifier)?);
+        Ok(self)
+    }
+}
+
 /// PolicyQualifierInfo as defined in [RFC 5280 Section 4.2.1.4].
 ///
 /// ```text
@@ -64,6 +89,62 @@ pub struct PolicyQualifierInfo {
     pub qualifier: Option<Any>,
 }
 
+impl PolicyQualifierInfo {
+    /// Create a new [`PolicyQualifierInfo`] holding `qualifier`.
+    pub fn new(qualifier: &PolicyQualifier) -> der::Result<Self> {
+        let (policy_qualifier_id, qualifier) = match qualifier {
+            PolicyQualifier::CpsUri(uri) => (ID_QT_CPS, Any::encode_from(uri)?),
+            PolicyQualifier::UserNotice(notice) => (ID_QT_UNOTICE, Any::encode_from(notice)?),
+        };
+
+        Ok(Self {
+            policy_qualifier_id,
+            qualifier: Some(qualifier),
+        })
+    }
+
+    /// Decode the qualifier according to its `policyQualifierId`.
+    ///
+    /// Returns `Ok(None)` for qualifiers other than those defined in
+    /// RFC 5280, i.e. `id-qt-cps` and `id-qt-unotice`.
+    pub fn decode_qualifier(&self) -> der::Result<Option<PolicyQualifier>> {
+        let qualifier = match &self.qualifier {
+            Some(qualifier) => qualifier,
+            None => return Ok(None),
+        };
+
+        if self.policy_qualifier_id == ID_QT_CPS {
+            qualifier.decode_as().map(PolicyQualifier::CpsUri).map(Some)
+        } else if self.policy_qualifier_id == ID_QT_UNOTICE {
+            qualifier
+                .decode_as()
+                .map(PolicyQualifier::UserNotice)
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Policy qualifiers defined in [RFC 5280 Section 4.2.1.4].
+///
+/// ```text
+/// Qualifier ::= CHOICE {
+///     cPSuri           CPSuri,
+///     userNotice       UserNotice
+/// }
+/// ```
+///
+/// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyQualifier {
+    /// Pointer to a Certification Practice Statement (`id-qt-cps`).
+    CpsUri(CpsUri),
+
+    /// Notice to display to relying parties (`id-qt-unotice`).
+    UserNotice(UserNotice),
+}
+
 /// CpsUri as defined in [RFC 5280 Section 4.2.1.4].
 ///
 /// ```text
@@ -83,13 +164,24 @@ pub type CpsUri = Ia5String;
 /// ```
 ///
 /// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct UserNotice {
-    pub notice_ref: Option<GeneralizedTime>,
+    pub notice_ref: Option<NoticeReference>,
     pub explicit_text: Option<DisplayText>,
 }
 
+impl UserNotice {
+    /// Create a new [`UserNotice`] with `explicit_text` encoded as a
+    /// `UTF8String`, as recommended by RFC 5280.
+    pub fn new(explicit_text: &str) -> der::Result<Self> {
+        Ok(Self {
+            notice_ref: None,
+            explicit_text: Some(DisplayText::new(explicit_text)?),
+        })
+    }
+}
+
 /// NoticeReference as defined in [RFC 5280 Section 4.2.1.4].
 ///
 /// ```text
@@ -103,7 +195,7 @@ pub struct UserNotice {
 #[allow(missing_docs)]
 pub struct NoticeReference {
     pub organization: DisplayText,
-    pub notice_numbers: Option<Vec<Uint>>,
+    pub notice_numbers: Vec<Uint>,
 }
 
 /// DisplayText as defined in [RFC 5280 Section 4.2.1.4].
@@ -117,15 +209,102 @@ pub struct NoticeReference {
 /// }
 /// ```
 ///
-/// Only the ia5String and utf8String options are currently supported.
+/// The size constraints are only enforced by [`DisplayText::new`], since
+/// certificates in the wild frequently exceed them.
 ///
 /// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
-#[derive(Choice, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[allow(missing_docs)]
 pub enum DisplayText {
-    #[asn1(type = "IA5String")]
     Ia5String(Ia5String),
-
-    #[asn1(type = "UTF8String")]
+    VisibleString(String),
+    BmpString(BmpString),
     Utf8String(String),
 }
+
+impl DisplayText {
+    /// Maximum number of characters in a [`DisplayText`].
+    pub const MAX_LEN: usize = 200;
+
+    /// Create a new `UTF8String` [`DisplayText`] of 1 to [`DisplayText::MAX_LEN`]
+    /// characters.
+    pub fn new(text: &str) -> der::Result<Self> {
+        if text.is_empty() || text.chars().count() > Self::MAX_LEN {
+            return Err(Tag::Utf8String.length_error());
+        }
+
+        Ok(Self::Utf8String(text.into()))
+    }
+}
+
+impl fmt::Display for DisplayText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ia5String(s) => f.write_str(s.as_str()),
+            Self::VisibleString(s) | Self::Utf8String(s) => f.write_str(s),
+            Self::BmpString(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl<'a> Choice<'a> for DisplayText {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(
+            tag,
+            Tag::Ia5String | Tag::VisibleString | Tag::BmpString | Tag::Utf8String
+        )
+    }
+}
+
+impl<'a> Decode<'a> for DisplayText {
+    type Error = der::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        match reader.peek_tag()? {
+            Tag::Ia5String => Ia5String::decode(reader).map(Self::Ia5String),
+            Tag::VisibleString => {
+                let any = AnyRef::decode(reader)?;
+                match core::str::from_utf8(any.value()) {
+                    Ok(s) if s.bytes().all(|b| (0x20..=0x7e).contains(&b)) => {
+                        Ok(Self::VisibleString(s.into()))
+                    }
+                    _ => Err(Tag::VisibleString.value_error()),
+                }
+            }
+            Tag::BmpString => BmpString::decode(reader).map(Self::BmpString),
+            Tag::Utf8String => String::decode(reader).map(Self::Utf8String),
+            tag => Err(tag.unexpected_error(None)),
+        }
+    }
+}
+
+impl EncodeValue for DisplayText {
+    fn value_len(&self) -> der::Result<Length> {
+        match self {
+            Self::Ia5String(s) => s.value_len(),
+            Self::VisibleString(s) => Length::try_from(s.len()),
+            Self::BmpString(s) => s.value_len(),
+            Self::Utf8String(s) => s.value_len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        match self {
+            Self::Ia5String(s) => s.encode_value(writer),
+            Self::VisibleString(s) => writer.write(s.as_bytes()),
+            Self::BmpString(s) => s.encode_value(writer),
+            Self::Utf8String(s) => s.encode_value(writer),
+        }
+    }
+}
+
+impl Tagged for DisplayText {
+    fn tag(&self) -> Tag {
+        match self {
+            Self::Ia5String(_) => Tag::Ia5String,
+            Self::VisibleString(_) => Tag::VisibleString,
+            Self::BmpString(_) => Tag::BmpString,
+            Self::Utf8String(_) => Tag::Utf8String,
+        }
+    }
+}
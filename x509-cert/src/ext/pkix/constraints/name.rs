@@ -1,11 +1,16 @@
 //! PKIX Name Constraint extension
 
 use alloc::vec::Vec;
+use core::mem;
 
-use const_oid::{db::rfc5280::ID_CE_NAME_CONSTRAINTS, AssociatedOid, ObjectIdentifier};
-use der::Sequence;
+use const_oid::{
+    db::{rfc3280::EMAIL_ADDRESS, rfc5280::ID_CE_NAME_CONSTRAINTS},
+    AssociatedOid, ObjectIdentifier,
+};
+use der::{asn1::Ia5String, Sequence};
 
-use super::super::name::GeneralName;
+use super::super::{name::GeneralName, SubjectAltName};
+use crate::certificate::{Profile, TbsCertificateInner};
 
 /// NameConstraints extension as defined in [RFC 5280 Section 4.2.1.10].
 ///
@@ -33,6 +38,74 @@ impl AssociatedOid for NameConstraints {
 
 impl_extension!(NameConstraints, critical = true);
 
+impl NameConstraints {
+    /// Is `name` permitted by these constraints?
+    ///
+    /// As described in [RFC 5280 Section 4.2.1.10], a name is rejected if it
+    /// falls within any of the excluded subtrees. If there are permitted
+    /// subtrees of the same name type, the name must also fall within at
+    /// least one of them. Name types without any permitted subtree are not
+    /// restricted.
+    ///
+    /// See [`GeneralSubtree::contains`] for the matching rules.
+    ///
+    /// [RFC 5280 Section 4.2.1.10]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.10
+    pub fn permits(&self, name: &GeneralName) -> bool {
+        if self
+            .excluded_subtrees
+            .iter()
+            .flatten()
+            .any(|subtree| subtree.contains(name))
+        {
+            return false;
+        }
+
+        let mut permitted = self
+            .permitted_subtrees
+            .iter()
+            .flatten()
+            .filter(|subtree| mem::discriminant(&subtree.base) == mem::discriminant(name))
+            .peekable();
+
+        permitted.peek().is_none() || permitted.any(|subtree| subtree.contains(name))
+    }
+
+    /// Are the names of the certificate described by `tbs` permitted by these
+    /// constraints?
+    ///
+    /// This checks the subject (unless it is empty), the email addresses in
+    /// the subject's `emailAddress` attributes and every subject alternative
+    /// name. Returns an error if any of those can't be decoded.
+    pub fn permits_certificate<P: Profile>(
+        &self,
+        tbs: &TbsCertificateInner<P>,
+    ) -> der::Result<bool> {
+        if !tbs.subject.0.is_empty()
+            && !self.permits(&GeneralName::DirectoryName(tbs.subject.clone()))
+        {
+            return Ok(false);
+        }
+
+        for atv in tbs
+            .subject
+            .0
+            .iter()
+            .flat_map(|rdn| rdn.0.iter())
+            .filter(|atv| atv.oid == EMAIL_ADDRESS)
+        {
+            let email = atv.value.decode_as::<Ia5String>()?;
+            if !self.permits(&GeneralName::Rfc822Name(email)) {
+                return Ok(false);
+            }
+        }
+
+        Ok(match tbs.get::<SubjectAltName>()? {
+            Some((_, san)) => san.0.iter().all(|name| self.permits(name)),
+            None => true,
+        })
+    }
+}
+
 /// GeneralSubtrees as defined in [RFC 5280 Section 4.2.1.10].
 ///
 /// ```text
@@ -68,3 +141,128 @@ pub struct GeneralSubtree {
     #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
     pub maximum: Option<u32>,
 }
+
+impl GeneralSubtree {
+    /// Does `name` fall within this subtree?
+    ///
+    /// Names of a different type than the `base` never do. The matching rules
+    /// of [RFC 5280 Section 4.2.1.10] are:
+    ///
+    /// - `dNSName`: the name is the base or a subdomain of it, compared
+    ///   case-insensitively. An empty base matches every name, and a base with
+    ///   a leading `.` only matches subdomains.
+    /// - `rfc822Name`: a base with an `@` is a mailbox which must match
+    ///   exactly. Otherwise the base is a host, which must match the host of
+    ///   the mailbox exactly, or a domain when it has a leading `.`, which must
+    ///   contain the host of the mailbox.
+    /// - `uniformResourceIdentifier`: the same host or domain rules apply to
+    ///   the host of the URI. URIs without an authority never match.
+    /// - `iPAddress`: the base is an address followed by a mask of the same
+    ///   length, and the masked addresses must be equal.
+    /// - `directoryName`: the name must start with the RDNs of the base,
+    ///   compared as described by [`RelativeDistinguishedName::matches`].
+    /// - other name types must match the base, see [`GeneralName::matches`].
+    ///
+    /// The `minimum` and `maximum` fields are ignored, as RFC 5280 requires
+    /// them to be absent.
+    ///
+    /// [RFC 5280 Section 4.2.1.10]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.10
+    /// [`RelativeDistinguishedName::matches`]: crate::name::RelativeDistinguishedName::matches
+    pub fn contains(&self, name: &GeneralName) -> bool {
+        match (&self.base, name) {
+            (GeneralName::DnsName(base), GeneralName::DnsName(name)) => {
+                dns_name_within(base.as_str(), name.as_str())
+            }
+            (GeneralName::Rfc822Name(base), GeneralName::Rfc822Name(name)) => {
+                mailbox_within(base.as_str(), name.as_str())
+            }
+            (
+                GeneralName::UniformResourceIdentifier(base),
+                GeneralName::UniformResourceIdentifier(name),
+            ) => uri_host(name.as_str()).is_some_and(|host| host_within(base.as_str(), host)),
+            (GeneralName::IpAddress(base), GeneralName::IpAddress(name)) => {
+                ip_address_within(base.as_bytes(), name.as_bytes())
+            }
+            (GeneralName::DirectoryName(base), GeneralName::DirectoryName(name)) => {
+                name.0.len() >= base.0.len()
+                    && base
+                        .0
+                        .iter()
+                        .zip(&name.0)
+                        .all(|(base, rdn)| base.matches(rdn))
+            }
+            (base, name) => base.matches(name),
+        }
+    }
+}
+
+/// Does `name` end with `suffix`, ignoring ASCII case?
+fn ends_with_ignore_case(name: &str, suffix: &str) -> bool {
+    name.len() >= suffix.len()
+        && name.as_bytes()[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix.as_bytes())
+}
+
+fn dns_name_within(base: &str, name: &str) -> bool {
+    if base.is_empty() {
+        return true;
+    }
+
+    if base.starts_with('.') {
+        return name.len() > base.len() && ends_with_ignore_case(name, base);
+    }
+
+    name.eq_ignore_ascii_case(base)
+        || (name.len() > base.len()
+            && ends_with_ignore_case(name, base)
+            && name.as_bytes()[name.len() - base.len() - 1] == b'.')
+}
+
+fn host_within(base: &str, host: &str) -> bool {
+    if base.starts_with('.') {
+        host.len() > base.len() && ends_with_ignore_case(host, base)
+    } else {
+        host.eq_ignore_ascii_case(base)
+    }
+}
+
+fn mailbox_within(base: &str, mailbox: &str) -> bool {
+    let (local, host) = match mailbox.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    match base.rsplit_once('@') {
+        Some((base_local, base_host)) => {
+            local == base_local && host.eq_ignore_ascii_case(base_host)
+        }
+        None => host_within(base, host),
+    }
+}
+
+/// Extracts the host from the authority of `uri`.
+fn uri_host(uri: &str) -> Option<&str> {
+    let (_, rest) = uri.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    let host = match host.strip_prefix('[') {
+        Some(literal) => literal.split_once(']')?.0,
+        None => host.split_once(':').map_or(host, |(host, _)| host),
+    };
+
+    (!host.is_empty()).then_some(host)
+}
+
+fn ip_address_within(base: &[u8], address: &[u8]) -> bool {
+    if !matches!(base.len(), 8 | 32) || address.len() * 2 != base.len() {
+        return false;
+    }
+
+    let (base, mask) = base.split_at(address.len());
+    base.iter()
+        .zip(mask)
+        .zip(address)
+        .all(|((base, mask), address)| base & mask == address & mask)
+}
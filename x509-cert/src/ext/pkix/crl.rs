@@ -3,15 +3,20 @@
 pub mod dp;
 
 use const_oid::db::rfc5280::{
-    ID_CE_CRL_DISTRIBUTION_POINTS, ID_CE_CRL_NUMBER, ID_CE_CRL_REASONS, ID_CE_DELTA_CRL_INDICATOR,
-    ID_CE_FRESHEST_CRL,
+    ID_CE_CERTIFICATE_ISSUER, ID_CE_CRL_DISTRIBUTION_POINTS, ID_CE_CRL_NUMBER, ID_CE_CRL_REASONS,
+    ID_CE_DELTA_CRL_INDICATOR, ID_CE_FRESHEST_CRL, ID_CE_INVALIDITY_DATE,
 };
 use const_oid::{AssociatedOid, ObjectIdentifier};
 pub use dp::IssuingDistributionPoint;
 
+use crate::ext::pkix::name::GeneralNames;
+
 use alloc::vec::Vec;
 
-use der::{asn1::Uint, Enumerated};
+use der::{
+    asn1::{GeneralizedTime, Uint},
+    Enumerated,
+};
 
 /// CrlNumber as defined in [RFC 5280 Section 5.2.3].
 ///
@@ -99,6 +104,7 @@ impl_extension!(FreshestCrl, critical = false);
 /// ```
 ///
 /// [RFC 5280 Section 5.3.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Enumerated)]
 #[allow(missing_docs)]
 #[repr(u32)]
@@ -120,3 +126,37 @@ impl AssociatedOid for CrlReason {
 }
 
 impl_extension!(CrlReason, critical = false);
+
+/// InvalidityDate as defined in [RFC 5280 Section 5.3.2].
+///
+/// ```text
+/// InvalidityDate ::=  GeneralizedTime
+/// ```
+///
+/// [RFC 5280 Section 5.3.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.2
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidityDate(pub GeneralizedTime);
+
+impl AssociatedOid for InvalidityDate {
+    const OID: ObjectIdentifier = ID_CE_INVALIDITY_DATE;
+}
+
+impl_newtype!(InvalidityDate, GeneralizedTime);
+impl_extension!(InvalidityDate, critical = false);
+
+/// CertificateIssuer as defined in [RFC 5280 Section 5.3.3].
+///
+/// ```text
+/// CertificateIssuer ::=     GeneralNames
+/// ```
+///
+/// [RFC 5280 Section 5.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.3
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CertificateIssuer(pub GeneralNames);
+
+impl AssociatedOid for CertificateIssuer {
+    const OID: ObjectIdentifier = ID_CE_CERTIFICATE_ISSUER;
+}
+
+impl_newtype!(CertificateIssuer, GeneralNames);
+impl_extension!(CertificateIssuer, critical = true);
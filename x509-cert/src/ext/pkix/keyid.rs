@@ -0,0 +1,58 @@
+use der::asn1::OctetString;
+use sha1::{Digest, Sha1};
+use spki::SubjectPublicKeyInfoRef;
+
+/// Methods of deriving a `KeyIdentifier` from a public key, as described in
+/// [RFC 5280 Section 4.2.1.2].
+///
+/// Both methods hash the value of the `subjectPublicKey` BIT STRING,
+/// excluding the tag, length, and number of unused bits.
+///
+/// [RFC 5280 Section 4.2.1.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.2
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum KeyIdentifierMethod {
+    /// Method (1): the 160-bit SHA-1 hash of the public key.
+    #[default]
+    Sha1,
+
+    /// Method (2): a four-bit type field with the value `0100` followed by the
+    /// least significant 60 bits of the SHA-1 hash of the public key.
+    TruncatedSha1,
+}
+
+impl KeyIdentifierMethod {
+    /// All of the supported methods.
+    pub const ALL: [Self; 2] = [Self::Sha1, Self::TruncatedSha1];
+
+    /// Compute the key identifier of `spki` using this method.
+    pub fn compute(self, spki: SubjectPublicKeyInfoRef<'_>) -> der::Result<OctetString> {
+        let hash = Sha1::digest(spki.subject_public_key.raw_bytes());
+
+        match self {
+            Self::Sha1 => OctetString::new(hash.as_slice()),
+            Self::TruncatedSha1 => {
+                let mut truncated = [0u8; 8];
+                truncated.copy_from_slice(&hash.as_slice()[12..]);
+                truncated[0] = 0x40 | (truncated[0] & 0x0f);
+                OctetString::new(truncated)
+            }
+        }
+    }
+
+    /// Find the method which derives `key_identifier` from `spki`, if any.
+    ///
+    /// This is intended for matching the `keyIdentifier` of an
+    /// [`AuthorityKeyIdentifier`] against the public key of a candidate
+    /// issuer, or a [`SubjectKeyIdentifier`] against the public key of its
+    /// certificate.
+    ///
+    /// [`AuthorityKeyIdentifier`]: super::AuthorityKeyIdentifier
+    /// [`SubjectKeyIdentifier`]: super::SubjectKeyIdentifier
+    pub fn identify(key_identifier: &[u8], spki: SubjectPublicKeyInfoRef<'_>) -> Option<Self> {
+        Self::ALL.into_iter().find(|method| {
+            method
+                .compute(spki.clone())
+                .is_ok_and(|computed| computed.as_bytes() == key_identifier)
+        })
+    }
+}
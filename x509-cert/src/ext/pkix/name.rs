@@ -10,4 +10,7 @@ pub use dirstr::DirectoryString;
 pub use dp::DistributionPointName;
 pub use ediparty::EdiPartyName;
 pub use general::{GeneralName, GeneralNames};
-pub use other::OtherName;
+pub use other::{
+    HardwareModuleName, OtherName, PermanentIdentifier, SmtpUtf8Mailbox, UserPrincipalName,
+    ID_MS_SAN_UPN, ID_ON_HARDWARE_MODULE_NAME, ID_ON_PERMANENT_IDENTIFIER, ID_ON_SMTP_UTF8_MAILBOX,
+};
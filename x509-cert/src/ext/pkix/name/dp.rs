@@ -22,3 +22,27 @@ pub enum DistributionPointName {
     #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "true")]
     NameRelativeToCRLIssuer(RelativeDistinguishedName),
 }
+
+impl DistributionPointName {
+    /// Does this distribution point name match `other`?
+    ///
+    /// Full names match if any of their general names match, see
+    /// [`GeneralName::matches`], as required when checking the scope of a
+    /// CRL in [RFC 5280 Section 6.3.3]. Names relative to the CRL issuer
+    /// match if their RDNs do, which assumes both are relative to the same
+    /// issuer.
+    ///
+    /// [`GeneralName::matches`]: super::GeneralName::matches
+    /// [RFC 5280 Section 6.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.3.3
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::FullName(names), Self::FullName(others)) => names
+                .iter()
+                .any(|name| others.iter().any(|other| name.matches(other))),
+            (Self::NameRelativeToCRLIssuer(rdn), Self::NameRelativeToCRLIssuer(other)) => {
+                rdn.matches(other)
+            }
+            _ => false,
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use super::{EdiPartyName, OtherName};
 use crate::name::Name;
 
+use alloc::string::String;
 use der::asn1::{Ia5String, ObjectIdentifier, OctetString};
 use der::{Choice, ValueOrd};
 
@@ -34,6 +35,7 @@ pub type GeneralNames = alloc::vec::Vec<GeneralName>;
 /// This implementation does not currently support the `x400Address` choice.
 ///
 /// [RFC 5280 Section 4.2.1.6]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.6
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
 #[allow(missing_docs)]
 pub enum GeneralName {
@@ -62,6 +64,128 @@ pub enum GeneralName {
     RegisteredId(ObjectIdentifier),
 }
 
+impl GeneralName {
+    /// Does this name identify the same entity as `other`?
+    ///
+    /// Unlike `==`, which compares the encodings, this follows the comparison
+    /// rules of [RFC 5280] for each name type:
+    ///
+    /// - `dNSName`: compared case-insensitively, ignoring a trailing `.`.
+    /// - `rfc822Name`: the local-part is compared exactly, and the host
+    ///   case-insensitively.
+    /// - `uniformResourceIdentifier`: compared after the normalization of
+    ///   [RFC 3986 Section 6.2.2], i.e. the scheme and host are compared
+    ///   case-insensitively, as are the hex digits of percent-encoded octets,
+    ///   and percent-encoded unreserved characters are decoded.
+    /// - `iPAddress`: IPv4-mapped IPv6 addresses match the IPv4 address.
+    /// - `directoryName`: compared as described by [`Name::matches`].
+    /// - other name types must be equal.
+    ///
+    /// [RFC 5280]: https://datatracker.ietf.org/doc/html/rfc5280#section-7
+    /// [RFC 3986 Section 6.2.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-6.2.2
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::DnsName(a), Self::DnsName(b)) => {
+                let a = a.as_str().strip_suffix('.').unwrap_or(a.as_str());
+                let b = b.as_str().strip_suffix('.').unwrap_or(b.as_str());
+                a.eq_ignore_ascii_case(b)
+            }
+            (Self::Rfc822Name(a), Self::Rfc822Name(b)) => {
+                match (a.as_str().rsplit_once('@'), b.as_str().rsplit_once('@')) {
+                    (Some((a_local, a_host)), Some((b_local, b_host))) => {
+                        a_local == b_local && a_host.eq_ignore_ascii_case(b_host)
+                    }
+                    (None, None) => a.as_str().eq_ignore_ascii_case(b.as_str()),
+                    _ => false,
+                }
+            }
+            (Self::UniformResourceIdentifier(a), Self::UniformResourceIdentifier(b)) => {
+                normalize_uri(a.as_str()) == normalize_uri(b.as_str())
+            }
+            (Self::IpAddress(a), Self::IpAddress(b)) => {
+                canonical_ip(a.as_bytes()) == canonical_ip(b.as_bytes())
+            }
+            (Self::DirectoryName(a), Self::DirectoryName(b)) => a.matches(b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// Strips the `::ffff:0:0/96` prefix of an IPv4-mapped IPv6 address.
+fn canonical_ip(address: &[u8]) -> &[u8] {
+    match address {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, ipv4 @ ..] if ipv4.len() == 4 => ipv4,
+        _ => address,
+    }
+}
+
+/// Normalizes `uri` following [RFC 3986 Section 6.2.2].
+///
+/// [RFC 3986 Section 6.2.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-6.2.2
+fn normalize_uri(uri: &str) -> String {
+    let (scheme, rest) = match uri.split_once(':') {
+        Some(parts) => parts,
+        None => return normalize_percent_encoding(uri),
+    };
+
+    let mut normalized = scheme.to_ascii_lowercase();
+    normalized.push(':');
+
+    let rest = match rest.strip_prefix("//") {
+        Some(rest) => {
+            let (authority, rest) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+            let (userinfo, host) = match authority.rsplit_once('@') {
+                Some((userinfo, host)) => (Some(userinfo), host),
+                None => (None, authority),
+            };
+
+            normalized.push_str("//");
+            if let Some(userinfo) = userinfo {
+                normalized.push_str(&normalize_percent_encoding(userinfo));
+                normalized.push('@');
+            }
+            normalized.push_str(&normalize_percent_encoding(host).to_ascii_lowercase());
+            rest
+        }
+        None => rest,
+    };
+
+    normalized.push_str(&normalize_percent_encoding(rest));
+    normalized
+}
+
+/// Decodes percent-encoded unreserved characters, and uses uppercase hex
+/// digits for all other percent-encoded octets.
+fn normalize_percent_encoding(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(i) = rest.find('%') {
+        normalized.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let octet = rest
+            .get(1..3)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match octet {
+            Some(octet) if octet.is_ascii_alphanumeric() || b"-._~".contains(&octet) => {
+                normalized.push(char::from(octet))
+            }
+            Some(_) => normalized.push_str(&rest[..3].to_ascii_uppercase()),
+            None => {
+                normalized.push('%');
+                rest = &rest[1..];
+                continue;
+            }
+        }
+        rest = &rest[3..];
+    }
+
+    normalized.push_str(rest);
+    normalized
+}
+
 #[cfg(feature = "std")]
 impl From<std::net::IpAddr> for GeneralName {
     fn from(ip: std::net::IpAddr) -> Self {
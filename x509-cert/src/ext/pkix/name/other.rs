@@ -1,4 +1,28 @@
-use der::{asn1::ObjectIdentifier, Any, Sequence, ValueOrd};
+use alloc::string::String;
+use const_oid::AssociatedOid;
+use core::cmp::Ordering;
+use der::{
+    asn1::{ObjectIdentifier, OctetString},
+    Any, Choice, DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Sequence, Tag, Tagged,
+    ValueOrd, Writer,
+};
+
+pub use const_oid::db::rfc5911::ID_ON_HARDWARE_MODULE_NAME;
+
+/// Microsoft user principal name `otherName` type, `szOID_NT_PRINCIPAL_NAME`.
+pub const ID_MS_SAN_UPN: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.311.20.2.3");
+
+/// `id-on-permanentIdentifier` as defined in [RFC 4043 Section 2].
+///
+/// [RFC 4043 Section 2]: https://datatracker.ietf.org/doc/html/rfc4043#section-2
+pub const ID_ON_PERMANENT_IDENTIFIER: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.8.3");
+
+/// `id-on-SmtpUTF8Mailbox` as defined in [RFC 8398 Section 3].
+///
+/// [RFC 8398 Section 3]: https://datatracker.ietf.org/doc/html/rfc8398#section-3
+pub const ID_ON_SMTP_UTF8_MAILBOX: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.8.9");
 
 /// OtherName as defined in [RFC 5280 Section 4.2.1.6].
 ///
@@ -10,6 +34,7 @@ use der::{asn1::ObjectIdentifier, Any, Sequence, ValueOrd};
 /// ```
 ///
 /// [RFC 5280 Section 4.2.1.6]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.6
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 #[allow(missing_docs)]
 pub struct OtherName {
@@ -19,6 +44,172 @@ pub struct OtherName {
     pub value: Any,
 }
 
+impl OtherName {
+    /// Create an [`OtherName`] holding `value`, identified by the OID
+    /// associated with `T`.
+    pub fn new<T>(value: &T) -> der::Result<Self>
+    where
+        T: AssociatedOid + EncodeValue + Tagged,
+    {
+        Ok(Self {
+            type_id: T::OID,
+            value: Any::encode_from(value)?,
+        })
+    }
+
+    /// Decode the value of this [`OtherName`] as `T`.
+    ///
+    /// Returns `Ok(None)` if the `type-id` is not the OID associated with `T`.
+    pub fn decode_as<'a, T>(&'a self) -> der::Result<Option<T>>
+    where
+        T: AssociatedOid + Choice<'a> + DecodeValue<'a, Error = der::Error>,
+    {
+        if self.type_id != T::OID {
+            return Ok(None);
+        }
+
+        self.value.decode_as().map(Some)
+    }
+}
+
+/// Microsoft user principal name `otherName`.
+///
+/// ```text
+/// UPN ::= UTF8String
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserPrincipalName(pub String);
+
+impl AssociatedOid for UserPrincipalName {
+    const OID: ObjectIdentifier = ID_MS_SAN_UPN;
+}
+
+impl_newtype!(UserPrincipalName, String);
+
+/// SmtpUTF8Mailbox as defined in [RFC 8398 Section 3].
+///
+/// ```text
+/// SmtpUTF8Mailbox ::= UTF8String (SIZE (1..MAX))
+/// ```
+///
+/// Empty mailboxes are rejected when encoding and decoding.
+///
+/// [RFC 8398 Section 3]: https://datatracker.ietf.org/doc/html/rfc8398#section-3
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SmtpUtf8Mailbox(pub String);
+
+impl SmtpUtf8Mailbox {
+    /// Returns an error if the mailbox violates the `SIZE (1..MAX)` constraint.
+    fn check(&self) -> der::Result<()> {
+        if self.0.is_empty() {
+            return Err(Tag::Utf8String.value_error());
+        }
+        Ok(())
+    }
+}
+
+impl AssociatedOid for SmtpUtf8Mailbox {
+    const OID: ObjectIdentifier = ID_ON_SMTP_UTF8_MAILBOX;
+}
+
+impl From<String> for SmtpUtf8Mailbox {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SmtpUtf8Mailbox> for String {
+    fn from(value: SmtpUtf8Mailbox) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<String> for SmtpUtf8Mailbox {
+    fn as_ref(&self) -> &String {
+        &self.0
+    }
+}
+
+impl AsMut<String> for SmtpUtf8Mailbox {
+    fn as_mut(&mut self) -> &mut String {
+        &mut self.0
+    }
+}
+
+impl FixedTag for SmtpUtf8Mailbox {
+    const TAG: Tag = Tag::Utf8String;
+}
+
+impl<'a> DecodeValue<'a> for SmtpUtf8Mailbox {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let mailbox = Self(String::decode_value(reader, header)?);
+        mailbox.check()?;
+        Ok(mailbox)
+    }
+}
+
+impl EncodeValue for SmtpUtf8Mailbox {
+    fn value_len(&self) -> der::Result<Length> {
+        self.check()?;
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.check()?;
+        self.0.encode_value(writer)
+    }
+}
+
+impl ValueOrd for SmtpUtf8Mailbox {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        self.0.value_cmp(&other.0)
+    }
+}
+
+/// HardwareModuleName as defined in [RFC 4108 Section 5].
+///
+/// ```text
+/// HardwareModuleName ::= SEQUENCE {
+///     hwType OBJECT IDENTIFIER,
+///     hwSerialNum OCTET STRING
+/// }
+/// ```
+///
+/// [RFC 4108 Section 5]: https://datatracker.ietf.org/doc/html/rfc4108#section-5
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct HardwareModuleName {
+    pub hw_type: ObjectIdentifier,
+    pub hw_serial_num: OctetString,
+}
+
+impl AssociatedOid for HardwareModuleName {
+    const OID: ObjectIdentifier = ID_ON_HARDWARE_MODULE_NAME;
+}
+
+/// PermanentIdentifier as defined in [RFC 4043 Section 2].
+///
+/// ```text
+/// PermanentIdentifier ::= SEQUENCE {
+///     identifierValue    UTF8String             OPTIONAL,
+///     assigner           OBJECT IDENTIFIER      OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 4043 Section 2]: https://datatracker.ietf.org/doc/html/rfc4043#section-2
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct PermanentIdentifier {
+    pub identifier_value: Option<String>,
+    pub assigner: Option<ObjectIdentifier>,
+}
+
+impl AssociatedOid for PermanentIdentifier {
+    const OID: ObjectIdentifier = ID_ON_PERMANENT_IDENTIFIER;
+}
+
 #[test]
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
@@ -0,0 +1,352 @@
+//! Qualified Certificate Statements extension as defined in [RFC 3739] and
+//! [ETSI EN 319 412-5], and the Biometric Information extension as defined in
+//! [RFC 3739].
+//!
+//! [RFC 3739]: https://datatracker.ietf.org/doc/html/rfc3739
+//! [ETSI EN 319 412-5]: https://www.etsi.org/deliver/etsi_en/319400_319499/31941205/02.03.01_60/en_31941205v020301p.pdf
+
+use super::name::GeneralName;
+use alloc::vec::Vec;
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use core::cmp::Ordering;
+use der::{
+    asn1::{Ia5String, OctetString, PrintableString},
+    Any, Choice, DecodeValue, Enumerated, Sequence, Tag, ValueOrd,
+};
+use spki::AlgorithmIdentifierOwned;
+
+/// `id-pe-qcStatements` as defined in [RFC 3739 Section 3.2.6].
+///
+/// [RFC 3739 Section 3.2.6]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6
+pub const ID_PE_QC_STATEMENTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.3");
+
+/// `id-pe-biometricInfo` as defined in [RFC 3739 Section 3.2.5].
+///
+/// [RFC 3739 Section 3.2.5]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.5
+pub const ID_PE_BIOMETRIC_INFO: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.2");
+
+/// `id-qcs-pkixQCSyntax-v1` as defined in [RFC 3739 Section 3.2.6.1].
+///
+/// [RFC 3739 Section 3.2.6.1]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6.1
+pub const ID_QCS_PKIX_QC_SYNTAX_V1: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.11.1");
+
+/// `id-qcs-pkixQCSyntax-v2` as defined in [RFC 3739 Section 3.2.6.1].
+///
+/// [RFC 3739 Section 3.2.6.1]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6.1
+pub const ID_QCS_PKIX_QC_SYNTAX_V2: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.11.2");
+
+/// `id-etsi-qcs-QcCompliance` as defined in ETSI EN 319 412-5 Section 4.2.1.
+///
+/// This statement has no `statementInfo`.
+pub const ID_ETSI_QCS_QC_COMPLIANCE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("0.4.0.1862.1.1");
+
+/// `id-etsi-qcs-QcLimitValue` as defined in ETSI EN 319 412-5 Section 4.3.2.
+pub const ID_ETSI_QCS_QC_LIMIT_VALUE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("0.4.0.1862.1.2");
+
+/// `id-etsi-qcs-QcRetentionPeriod` as defined in ETSI EN 319 412-5 Section 4.3.3.
+pub const ID_ETSI_QCS_QC_RETENTION_PERIOD: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("0.4.0.1862.1.3");
+
+/// `id-etsi-qcs-QcSSCD` as defined in ETSI EN 319 412-5 Section 4.2.2.
+///
+/// This statement has no `statementInfo`.
+pub const ID_ETSI_QCS_QC_SSCD: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.4");
+
+/// `id-etsi-qcs-QcPDS` as defined in ETSI EN 319 412-5 Section 4.3.4.
+pub const ID_ETSI_QCS_QC_PDS: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.5");
+
+/// `id-etsi-qcs-QcType` as defined in ETSI EN 319 412-5 Section 4.2.3.
+pub const ID_ETSI_QCS_QC_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.6");
+
+/// `id-etsi-qct-esign` as defined in ETSI EN 319 412-5 Section 4.2.3.
+pub const ID_ETSI_QCT_ESIGN: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.6.1");
+
+/// `id-etsi-qct-eseal` as defined in ETSI EN 319 412-5 Section 4.2.3.
+pub const ID_ETSI_QCT_ESEAL: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.6.2");
+
+/// `id-etsi-qct-web` as defined in ETSI EN 319 412-5 Section 4.2.3.
+pub const ID_ETSI_QCT_WEB: ObjectIdentifier = ObjectIdentifier::new_unwrap("0.4.0.1862.1.6.3");
+
+/// `id-etsi-qcs-QcCClegislation` as defined in ETSI EN 319 412-5 Section 4.2.4.
+pub const ID_ETSI_QCS_QC_CC_LEGISLATION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("0.4.0.1862.1.7");
+
+/// QCStatements as defined in [RFC 3739 Section 3.2.6].
+///
+/// ```text
+/// QCStatements ::= SEQUENCE OF QCStatement
+/// ```
+///
+/// [RFC 3739 Section 3.2.6]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QcStatements(pub Vec<QcStatement>);
+
+impl AssociatedOid for QcStatements {
+    const OID: ObjectIdentifier = ID_PE_QC_STATEMENTS;
+}
+
+impl_newtype!(QcStatements, Vec<QcStatement>);
+impl_extension!(QcStatements, critical = false);
+
+impl QcStatements {
+    /// Is a statement with the given `statementId` present?
+    pub fn contains(&self, statement_id: ObjectIdentifier) -> bool {
+        self.0.iter().any(|s| s.statement_id == statement_id)
+    }
+
+    /// Does this certificate claim to be an EU qualified certificate?
+    ///
+    /// See the `QcCompliance` statement in ETSI EN 319 412-5 Section 4.2.1.
+    pub fn is_qc_compliant(&self) -> bool {
+        self.contains(ID_ETSI_QCS_QC_COMPLIANCE)
+    }
+
+    /// Decodes the `statementInfo` of the statement associated with `T`.
+    ///
+    /// Returns `Ok(None)` if the statement is not present, and an error if it
+    /// is present without a `statementInfo`.
+    pub fn get<'a, T>(&'a self) -> Result<Option<T>, <T as DecodeValue<'a>>::Error>
+    where
+        T: AssociatedOid + Choice<'a> + DecodeValue<'a>,
+    {
+        match self.0.iter().find(|s| s.statement_id == T::OID) {
+            None => Ok(None),
+            Some(statement) => match &statement.statement_info {
+                Some(info) => info.decode_as().map(Some),
+                None => Err(Tag::Sequence.value_error().into()),
+            },
+        }
+    }
+}
+
+/// QCStatement as defined in [RFC 3739 Section 3.2.6].
+///
+/// ```text
+/// QCStatement ::= SEQUENCE {
+///     statementId   QC-STATEMENT.&id({SupportedStatements}),
+///     statementInfo QC-STATEMENT.&Type
+///                   ({SupportedStatements}{@statementId}) OPTIONAL }
+/// ```
+///
+/// [RFC 3739 Section 3.2.6]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct QcStatement {
+    pub statement_id: ObjectIdentifier,
+    pub statement_info: Option<Any>,
+}
+
+/// SemanticsInformation as defined in [RFC 3739 Section 3.2.6.1].
+///
+/// This is the `statementInfo` of the `id-qcs-pkixQCSyntax-v2` statement.
+///
+/// ```text
+/// SemanticsInformation ::= SEQUENCE {
+///     semanticsIdentifier         OBJECT IDENTIFIER OPTIONAL,
+///     nameRegistrationAuthorities NameRegistrationAuthorities OPTIONAL
+/// } -- At least one field shall be present
+///
+/// NameRegistrationAuthorities ::= SEQUENCE SIZE (1..MAX) OF GeneralName
+/// ```
+///
+/// [RFC 3739 Section 3.2.6.1]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.6.1
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct SemanticsInformation {
+    pub semantics_identifier: Option<ObjectIdentifier>,
+    pub name_registration_authorities: Option<Vec<GeneralName>>,
+}
+
+impl AssociatedOid for SemanticsInformation {
+    const OID: ObjectIdentifier = ID_QCS_PKIX_QC_SYNTAX_V2;
+}
+
+/// MonetaryValue as defined in ETSI EN 319 412-5 Section 4.3.2.
+///
+/// This is the `statementInfo` of the `QcLimitValue` statement. The limit is
+/// `amount * 10^exponent` in the given currency.
+///
+/// ```text
+/// MonetaryValue ::= SEQUENCE {
+///     currency Iso4217CurrencyCode,
+///     amount   INTEGER,
+///     exponent INTEGER }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct MonetaryValue {
+    pub currency: Iso4217CurrencyCode,
+    pub amount: i64,
+    pub exponent: i64,
+}
+
+impl AssociatedOid for MonetaryValue {
+    const OID: ObjectIdentifier = ID_ETSI_QCS_QC_LIMIT_VALUE;
+}
+
+/// Iso4217CurrencyCode as defined in ETSI EN 319 412-5 Section 4.3.2.
+///
+/// ```text
+/// Iso4217CurrencyCode ::= CHOICE {
+///     alphabetic PrintableString (SIZE (3)), -- Recommended
+///     numeric    INTEGER (1..999) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum Iso4217CurrencyCode {
+    Alphabetic(PrintableString),
+    Numeric(u16),
+}
+
+/// QcEuRetentionPeriod as defined in ETSI EN 319 412-5 Section 4.3.3.
+///
+/// This is the `statementInfo` of the `QcRetentionPeriod` statement: the
+/// number of years after the expiry of the certificate during which
+/// registration information is retained.
+///
+/// ```text
+/// QcEuRetentionPeriod ::= INTEGER
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct QcRetentionPeriod(pub u32);
+
+impl AssociatedOid for QcRetentionPeriod {
+    const OID: ObjectIdentifier = ID_ETSI_QCS_QC_RETENTION_PERIOD;
+}
+
+impl_newtype!(QcRetentionPeriod, u32);
+
+/// PdsLocations as defined in ETSI EN 319 412-5 Section 4.3.4.
+///
+/// This is the `statementInfo` of the `QcPDS` statement.
+///
+/// ```text
+/// PdsLocations ::= SEQUENCE SIZE (1..MAX) OF PdsLocation
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PdsLocations(pub Vec<PdsLocation>);
+
+impl AssociatedOid for PdsLocations {
+    const OID: ObjectIdentifier = ID_ETSI_QCS_QC_PDS;
+}
+
+impl_newtype!(PdsLocations, Vec<PdsLocation>);
+
+/// PdsLocation as defined in ETSI EN 319 412-5 Section 4.3.4.
+///
+/// ```text
+/// PdsLocation ::= SEQUENCE {
+///     url      IA5String,
+///     language PrintableString (SIZE(2)) } -- ISO 639-1 language code
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct PdsLocation {
+    pub url: Ia5String,
+    pub language: PrintableString,
+}
+
+/// QcType as defined in ETSI EN 319 412-5 Section 4.2.3.
+///
+/// This is the `statementInfo` of the `QcType` statement, e.g.
+/// [`ID_ETSI_QCT_ESIGN`].
+///
+/// ```text
+/// QcType ::= SEQUENCE OF OBJECT IDENTIFIER
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QcType(pub Vec<ObjectIdentifier>);
+
+impl AssociatedOid for QcType {
+    const OID: ObjectIdentifier = ID_ETSI_QCS_QC_TYPE;
+}
+
+impl_newtype!(QcType, Vec<ObjectIdentifier>);
+
+/// BiometricSyntax as defined in [RFC 3739 Section 3.2.5].
+///
+/// This is the value of the Biometric Information extension, which binds
+/// hashes of biometric information, e.g. a picture of the subject, to the
+/// certificate.
+///
+/// ```text
+/// BiometricSyntax ::= SEQUENCE OF BiometricData
+/// ```
+///
+/// [RFC 3739 Section 3.2.5]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.5
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BiometricSyntax(pub Vec<BiometricData>);
+
+impl AssociatedOid for BiometricSyntax {
+    const OID: ObjectIdentifier = ID_PE_BIOMETRIC_INFO;
+}
+
+impl_newtype!(BiometricSyntax, Vec<BiometricData>);
+impl_extension!(BiometricSyntax, critical = false);
+
+/// BiometricData as defined in [RFC 3739 Section 3.2.5].
+///
+/// ```text
+/// BiometricData ::= SEQUENCE {
+///     typeOfBiometricData TypeOfBiometricData,
+///     hashAlgorithm       AlgorithmIdentifier,
+///     biometricDataHash   OCTET STRING,
+///     sourceDataUri       IA5String OPTIONAL }
+/// ```
+///
+/// [RFC 3739 Section 3.2.5]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.5
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct BiometricData {
+    pub type_of_biometric_data: TypeOfBiometricData,
+    pub hash_algorithm: AlgorithmIdentifierOwned,
+    pub biometric_data_hash: OctetString,
+    pub source_data_uri: Option<Ia5String>,
+}
+
+/// TypeOfBiometricData as defined in [RFC 3739 Section 3.2.5].
+///
+/// ```text
+/// TypeOfBiometricData ::= CHOICE {
+///     predefinedBiometricType PredefinedBiometricType,
+///     biometricDataOid        OBJECT IDENTIFIER }
+/// ```
+///
+/// [RFC 3739 Section 3.2.5]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.5
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum TypeOfBiometricData {
+    PredefinedBiometricType(PredefinedBiometricType),
+    BiometricDataOid(ObjectIdentifier),
+}
+
+/// PredefinedBiometricType as defined in [RFC 3739 Section 3.2.5].
+///
+/// ```text
+/// PredefinedBiometricType ::= INTEGER {
+///     picture(0), handwritten-signature(1) }
+///     (picture | handwritten-signature)
+/// ```
+///
+/// [RFC 3739 Section 3.2.5]: https://datatracker.ietf.org/doc/html/rfc3739#section-3.2.5
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+pub enum PredefinedBiometricType {
+    /// A picture of the subject
+    Picture = 0,
+
+    /// The handwritten signature of the subject
+    HandwrittenSignature = 1,
+}
+
+impl ValueOrd for PredefinedBiometricType {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        (*self as u8).value_cmp(&(*other as u8))
+    }
+}
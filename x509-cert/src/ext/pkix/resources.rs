@@ -0,0 +1,637 @@
+//! IP address and AS identifier delegation extensions as defined in
+//! [RFC 3779].
+//!
+//! These extensions bind Internet number resources to the subject of a
+//! certificate, e.g. in the Resource Public Key Infrastructure (RPKI).
+//!
+//! The canonical encoding of the extensions, as required by [RFC 3779],
+//! sorts their entries and merges adjacent and overlapping ones. Blocks of
+//! addresses which form a prefix must be encoded as such. The
+//! `canonicalize` methods rewrite an extension into this form.
+//!
+//! [RFC 3779]: https://datatracker.ietf.org/doc/html/rfc3779
+
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::{
+    asn1::{BitString, Null, OctetString},
+    Choice, Decode, DerOrd, EncodeValue, Length, Reader, Sequence, Tag, Tagged, ValueOrd, Writer,
+};
+
+/// `id-pe-ipAddrBlocks` as defined in [RFC 3779 Section 2.2.1].
+///
+/// [RFC 3779 Section 2.2.1]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.1
+pub const ID_PE_IP_ADDR_BLOCKS: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.7");
+
+/// `id-pe-autonomousSysIds` as defined in [RFC 3779 Section 3.2.1].
+///
+/// [RFC 3779 Section 3.2.1]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.1
+pub const ID_PE_AUTONOMOUS_SYS_IDS: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.8");
+
+/// Address Family Identifier of IPv4.
+pub const AFI_IPV4: u16 = 1;
+
+/// Address Family Identifier of IPv6.
+pub const AFI_IPV6: u16 = 2;
+
+/// IPAddrBlocks as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddrBlocks ::= SEQUENCE OF IPAddressFamily
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IpAddrBlocks(pub Vec<IpAddressFamily>);
+
+impl AssociatedOid for IpAddrBlocks {
+    const OID: ObjectIdentifier = ID_PE_IP_ADDR_BLOCKS;
+}
+
+impl_newtype!(IpAddrBlocks, Vec<IpAddressFamily>);
+impl_extension!(IpAddrBlocks, critical = true);
+
+impl IpAddrBlocks {
+    /// Find the entry for the given address family, if any.
+    pub fn get(&self, afi: u16, safi: Option<u8>) -> Option<&IpAddressFamily> {
+        self.0
+            .iter()
+            .find(|family| family.afi() == Some(afi) && family.safi() == safi)
+    }
+
+    /// Rewrite this extension into its canonical form.
+    ///
+    /// The address families are sorted by their `addressFamily`, and each of
+    /// them is canonicalized with [`IpAddressFamily::canonicalize`].
+    pub fn canonicalize(&mut self) -> der::Result<()> {
+        for family in &mut self.0 {
+            family.canonicalize()?;
+        }
+
+        self.0
+            .sort_by(|a, b| a.address_family.as_bytes().cmp(b.address_family.as_bytes()));
+        Ok(())
+    }
+}
+
+/// IPAddressFamily as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddressFamily ::= SEQUENCE { -- AFI & optional SAFI --
+///     addressFamily        OCTET STRING (SIZE (2..3)),
+///     ipAddressChoice      IPAddressChoice }
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct IpAddressFamily {
+    pub address_family: OctetString,
+    pub ip_address_choice: IpAddressChoice,
+}
+
+impl IpAddressFamily {
+    /// Create a new entry for the Address Family Identifier `afi` and the
+    /// optional Subsequent Address Family Identifier `safi`.
+    pub fn new(
+        afi: u16,
+        safi: Option<u8>,
+        ip_address_choice: IpAddressChoice,
+    ) -> der::Result<Self> {
+        let mut address_family = afi.to_be_bytes().to_vec();
+        address_family.extend(safi);
+
+        Ok(Self {
+            address_family: OctetString::new(address_family)?,
+            ip_address_choice,
+        })
+    }
+
+    /// Get the Address Family Identifier, e.g. [`AFI_IPV4`].
+    ///
+    /// Returns `None` if the `addressFamily` is too short.
+    pub fn afi(&self) -> Option<u16> {
+        let afi = self.address_family.as_bytes().get(..2)?;
+        Some(u16::from_be_bytes([afi[0], afi[1]]))
+    }
+
+    /// Get the Subsequent Address Family Identifier, if any.
+    pub fn safi(&self) -> Option<u8> {
+        self.address_family.as_bytes().get(2).copied()
+    }
+
+    /// Get the length in bytes of the addresses of this family, if known.
+    pub fn address_len(&self) -> Option<usize> {
+        match self.afi()? {
+            AFI_IPV4 => Some(4),
+            AFI_IPV6 => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Rewrite the addresses of this family into their canonical form, as
+    /// described in [RFC 3779 Section 2.2.3.6].
+    ///
+    /// The addresses are sorted, adjacent and overlapping blocks are merged,
+    /// and each block is encoded as a prefix if possible, as a range
+    /// otherwise. This fails for families other than IPv4 and IPv6, and if
+    /// any block is malformed.
+    ///
+    /// [RFC 3779 Section 2.2.3.6]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3.6
+    pub fn canonicalize(&mut self) -> der::Result<()> {
+        let len = self
+            .address_len()
+            .ok_or_else(|| Tag::OctetString.value_error())?;
+
+        let entries = match &mut self.ip_address_choice {
+            IpAddressChoice::Inherit => return Ok(()),
+            IpAddressChoice::AddressesOrRanges(entries) => entries,
+        };
+
+        let mut ranges = entries
+            .iter()
+            .map(
+                |entry| match (entry.min_address(len), entry.max_address(len)) {
+                    (Some(min), Some(max)) if min <= max => Ok((min, max)),
+                    _ => Err(Tag::BitString.value_error()),
+                },
+            )
+            .collect::<der::Result<Vec<_>>>()?;
+        ranges.sort();
+
+        let mut merged: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(ranges.len());
+        for (min, max) in ranges {
+            match merged.last_mut() {
+                Some((_, last)) if successor(last).map_or(true, |next| min <= next) => {
+                    if max > *last {
+                        *last = max;
+                    }
+                }
+                _ => merged.push((min, max)),
+            }
+        }
+
+        *entries = merged
+            .iter()
+            .map(|(min, max)| IpAddressOrRange::range(min, max))
+            .collect::<der::Result<_>>()?;
+        Ok(())
+    }
+}
+
+/// IPAddressChoice as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddressChoice ::= CHOICE {
+///     inherit              NULL, -- inherit from issuer --
+///     addressesOrRanges    SEQUENCE OF IPAddressOrRange }
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub enum IpAddressChoice {
+    Inherit,
+    AddressesOrRanges(Vec<IpAddressOrRange>),
+}
+
+impl<'a> Choice<'a> for IpAddressChoice {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(tag, Tag::Null | Tag::Sequence)
+    }
+}
+
+impl<'a> Decode<'a> for IpAddressChoice {
+    type Error = der::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        match reader.peek_tag()? {
+            Tag::Null => Null::decode(reader).map(|_| Self::Inherit),
+            Tag::Sequence => Vec::decode(reader).map(Self::AddressesOrRanges),
+            tag => Err(tag.unexpected_error(None)),
+        }
+    }
+}
+
+impl EncodeValue for IpAddressChoice {
+    fn value_len(&self) -> der::Result<Length> {
+        match self {
+            Self::Inherit => Null.value_len(),
+            Self::AddressesOrRanges(entries) => entries.value_len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        match self {
+            Self::Inherit => Null.encode_value(writer),
+            Self::AddressesOrRanges(entries) => entries.encode_value(writer),
+        }
+    }
+}
+
+impl Tagged for IpAddressChoice {
+    fn tag(&self) -> Tag {
+        match self {
+            Self::Inherit => Tag::Null,
+            Self::AddressesOrRanges(_) => Tag::Sequence,
+        }
+    }
+}
+
+impl ValueOrd for IpAddressChoice {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        match (self, other) {
+            (Self::AddressesOrRanges(a), Self::AddressesOrRanges(b)) => a.value_cmp(b),
+            _ => self.tag().der_cmp(&other.tag()),
+        }
+    }
+}
+
+/// IPAddressOrRange as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddressOrRange ::= CHOICE {
+///     addressPrefix        IPAddress,
+///     addressRange         IPAddressRange }
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+#[derive(Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum IpAddressOrRange {
+    AddressPrefix(IpAddress),
+    AddressRange(IpAddressRange),
+}
+
+impl IpAddressOrRange {
+    /// Create the prefix of `prefix_len` bits of `address`.
+    ///
+    /// The bits of `address` after the prefix are ignored.
+    pub fn prefix(address: &[u8], prefix_len: u8) -> der::Result<Self> {
+        let prefix_len = usize::from(prefix_len);
+        if prefix_len > address.len() * 8 {
+            return Err(Tag::BitString.value_error());
+        }
+
+        truncate(address, prefix_len).map(Self::AddressPrefix)
+    }
+
+    /// Create the block of the addresses from `min` to `max`, inclusive.
+    ///
+    /// The block is encoded as a prefix if possible, and as a range with the
+    /// trailing zero bits of `min` and the trailing one bits of `max` removed
+    /// otherwise, as required by [RFC 3779 Section 2.2.3.7]. The addresses
+    /// must have the same length.
+    ///
+    /// [RFC 3779 Section 2.2.3.7]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3.7
+    pub fn range(min: &[u8], max: &[u8]) -> der::Result<Self> {
+        if min.len() != max.len() || min > max {
+            return Err(Tag::BitString.value_error());
+        }
+
+        let bits = min.len() * 8;
+        let common = (0..bits)
+            .take_while(|&i| bit(min, i) == bit(max, i))
+            .count();
+
+        if (common..bits).all(|i| !bit(min, i) && bit(max, i)) {
+            return truncate(min, common).map(Self::AddressPrefix);
+        }
+
+        let min_len = (0..bits).rposition(|i| bit(min, i)).map_or(0, |i| i + 1);
+        let max_len = (0..bits).rposition(|i| !bit(max, i)).map_or(0, |i| i + 1);
+
+        Ok(Self::AddressRange(IpAddressRange {
+            min: truncate(min, min_len)?,
+            max: truncate(max, max_len)?,
+        }))
+    }
+
+    /// Get the lowest address of this block, given the address length `len`
+    /// in bytes of its family.
+    ///
+    /// Returns `None` if the encoded address is longer than `len`.
+    pub fn min_address(&self, len: usize) -> Option<Vec<u8>> {
+        match self {
+            Self::AddressPrefix(prefix) => expand(prefix, len, false),
+            Self::AddressRange(range) => expand(&range.min, len, false),
+        }
+    }
+
+    /// Get the highest address of this block, given the address length `len`
+    /// in bytes of its family.
+    ///
+    /// Returns `None` if the encoded address is longer than `len`.
+    pub fn max_address(&self, len: usize) -> Option<Vec<u8>> {
+        match self {
+            Self::AddressPrefix(prefix) => expand(prefix, len, true),
+            Self::AddressRange(range) => expand(&range.max, len, true),
+        }
+    }
+}
+
+/// IPAddressRange as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddressRange ::= SEQUENCE {
+///     min                  IPAddress,
+///     max                  IPAddress }
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct IpAddressRange {
+    pub min: IpAddress,
+    pub max: IpAddress,
+}
+
+/// IPAddress as defined in [RFC 3779 Section 2.2.3].
+///
+/// ```text
+/// IPAddress ::= BIT STRING
+/// ```
+///
+/// [RFC 3779 Section 2.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-2.2.3
+pub type IpAddress = BitString;
+
+/// ASIdentifiers as defined in [RFC 3779 Section 3.2.3].
+///
+/// ```text
+/// ASIdentifiers ::= SEQUENCE {
+///     asnum               [0] EXPLICIT ASIdentifierChoice OPTIONAL,
+///     rdi                 [1] EXPLICIT ASIdentifierChoice OPTIONAL}
+/// ```
+///
+/// [RFC 3779 Section 3.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3
+#[derive(Clone, Debug, Default, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AsIdentifiers {
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub asnum: Option<AsIdentifierChoice>,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    pub rdi: Option<AsIdentifierChoice>,
+}
+
+impl AssociatedOid for AsIdentifiers {
+    const OID: ObjectIdentifier = ID_PE_AUTONOMOUS_SYS_IDS;
+}
+
+impl_extension!(AsIdentifiers, critical = true);
+
+impl AsIdentifiers {
+    /// Rewrite this extension into its canonical form.
+    ///
+    /// See [`AsIdentifierChoice::canonicalize`].
+    pub fn canonicalize(&mut self) -> der::Result<()> {
+        for choice in [&mut self.asnum, &mut self.rdi].into_iter().flatten() {
+            choice.canonicalize()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// ASIdentifierChoice as defined in [RFC 3779 Section 3.2.3].
+///
+/// ```text
+/// ASIdentifierChoice ::= CHOICE {
+///     inherit              NULL, -- inherit from issuer --
+///     asIdsOrRanges        SEQUENCE OF ASIdOrRange }
+/// ```
+///
+/// [RFC 3779 Section 3.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub enum AsIdentifierChoice {
+    Inherit,
+    AsIdsOrRanges(Vec<AsIdOrRange>),
+}
+
+impl AsIdentifierChoice {
+    /// Rewrite the identifiers into their canonical form, as described in
+    /// [RFC 3779 Section 3.2.3.4].
+    ///
+    /// The identifiers are sorted, adjacent and overlapping ranges are
+    /// merged, and ranges of a single identifier are encoded as such. This
+    /// fails if any range has a `min` greater than its `max`.
+    ///
+    /// [RFC 3779 Section 3.2.3.4]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3.4
+    pub fn canonicalize(&mut self) -> der::Result<()> {
+        let entries = match self {
+            Self::Inherit => return Ok(()),
+            Self::AsIdsOrRanges(entries) => entries,
+        };
+
+        if entries.iter().any(|entry| entry.min() > entry.max()) {
+            return Err(Tag::Integer.value_error());
+        }
+
+        let mut ranges = entries
+            .iter()
+            .map(|entry| (entry.min(), entry.max()))
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(AsId, AsId)> = Vec::with_capacity(ranges.len());
+        for (min, max) in ranges {
+            match merged.last_mut() {
+                Some((_, last)) if min <= last.saturating_add(1) => *last = max.max(*last),
+                _ => merged.push((min, max)),
+            }
+        }
+
+        *entries = merged
+            .into_iter()
+            .map(|(min, max)| AsIdOrRange::range(min, max))
+            .collect::<der::Result<_>>()?;
+        Ok(())
+    }
+}
+
+impl<'a> Choice<'a> for AsIdentifierChoice {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(tag, Tag::Null | Tag::Sequence)
+    }
+}
+
+impl<'a> Decode<'a> for AsIdentifierChoice {
+    type Error = der::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        match reader.peek_tag()? {
+            Tag::Null => Null::decode(reader).map(|_| Self::Inherit),
+            Tag::Sequence => Vec::decode(reader).map(Self::AsIdsOrRanges),
+            tag => Err(tag.unexpected_error(None)),
+        }
+    }
+}
+
+impl EncodeValue for AsIdentifierChoice {
+    fn value_len(&self) -> der::Result<Length> {
+        match self {
+            Self::Inherit => Null.value_len(),
+            Self::AsIdsOrRanges(entries) => entries.value_len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        match self {
+            Self::Inherit => Null.encode_value(writer),
+            Self::AsIdsOrRanges(entries) => entries.encode_value(writer),
+        }
+    }
+}
+
+impl Tagged for AsIdentifierChoice {
+    fn tag(&self) -> Tag {
+        match self {
+            Self::Inherit => Tag::Null,
+            Self::AsIdsOrRanges(_) => Tag::Sequence,
+        }
+    }
+}
+
+impl ValueOrd for AsIdentifierChoice {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        match (self, other) {
+            (Self::AsIdsOrRanges(a), Self::AsIdsOrRanges(b)) => a.value_cmp(b),
+            _ => self.tag().der_cmp(&other.tag()),
+        }
+    }
+}
+
+/// ASIdOrRange as defined in [RFC 3779 Section 3.2.3].
+///
+/// ```text
+/// ASIdOrRange ::= CHOICE {
+///     id                  ASId,
+///     range               ASRange }
+/// ```
+///
+/// [RFC 3779 Section 3.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Choice, ValueOrd)]
+#[allow(missing_docs)]
+pub enum AsIdOrRange {
+    Id(AsId),
+    Range(AsRange),
+}
+
+impl AsIdOrRange {
+    /// Create the range of identifiers from `min` to `max`, inclusive.
+    ///
+    /// A range of a single identifier is encoded as an `id`, as required by
+    /// [RFC 3779 Section 3.2.3.8].
+    ///
+    /// [RFC 3779 Section 3.2.3.8]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3.8
+    pub fn range(min: AsId, max: AsId) -> der::Result<Self> {
+        match min.cmp(&max) {
+            Ordering::Less => Ok(Self::Range(AsRange { min, max })),
+            Ordering::Equal => Ok(Self::Id(min)),
+            Ordering::Greater => Err(Tag::Integer.value_error()),
+        }
+    }
+
+    /// Get the lowest identifier of this range.
+    pub fn min(&self) -> AsId {
+        match self {
+            Self::Id(id) => *id,
+            Self::Range(range) => range.min,
+        }
+    }
+
+    /// Get the highest identifier of this range.
+    pub fn max(&self) -> AsId {
+        match self {
+            Self::Id(id) => *id,
+            Self::Range(range) => range.max,
+        }
+    }
+}
+
+/// ASRange as defined in [RFC 3779 Section 3.2.3].
+///
+/// ```text
+/// ASRange ::= SEQUENCE {
+///     min                 ASId,
+///     max                 ASId }
+/// ```
+///
+/// [RFC 3779 Section 3.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AsRange {
+    pub min: AsId,
+    pub max: AsId,
+}
+
+/// ASId as defined in [RFC 3779 Section 3.2.3].
+///
+/// ```text
+/// ASId ::= INTEGER
+/// ```
+///
+/// [RFC 3779 Section 3.2.3]: https://datatracker.ietf.org/doc/html/rfc3779#section-3.2.3
+pub type AsId = u32;
+
+/// Get the bit `i` of `address`, counting from the most significant bit.
+fn bit(address: &[u8], i: usize) -> bool {
+    address[i / 8] & (0x80 >> (i % 8)) != 0
+}
+
+/// Encode the leading `bit_len` bits of `address`.
+fn truncate(address: &[u8], bit_len: usize) -> der::Result<IpAddress> {
+    let byte_len = bit_len.div_ceil(8);
+    let unused_bits = (byte_len * 8 - bit_len) as u8;
+
+    let mut bytes = address[..byte_len].to_vec();
+    if let Some(last) = bytes.last_mut() {
+        *last &= 0xff << unused_bits;
+    }
+
+    BitString::new(unused_bits, bytes)
+}
+
+/// Expand `address` to `len` bytes, setting the missing bits to `fill`.
+fn expand(address: &IpAddress, len: usize, fill: bool) -> Option<Vec<u8>> {
+    let bytes = address.raw_bytes();
+    if bytes.len() > len {
+        return None;
+    }
+
+    let mut expanded = vec![if fill { 0xff } else { 0 }; len];
+    expanded[..bytes.len()].copy_from_slice(bytes);
+
+    if let Some(last) = bytes.len().checked_sub(1) {
+        let unused = (1u8 << address.unused_bits()) - 1;
+        if fill {
+            expanded[last] |= unused;
+        } else {
+            expanded[last] &= !unused;
+        }
+    }
+
+    Some(expanded)
+}
+
+/// Get the address following `address`, if any.
+fn successor(address: &[u8]) -> Option<Vec<u8>> {
+    let mut next = address.to_vec();
+
+    for byte in next.iter_mut().rev() {
+        match byte.checked_add(1) {
+            Some(incremented) => {
+                *byte = incremented;
+                return Some(next);
+            }
+            None => *byte = 0,
+        }
+    }
+
+    None
+}
@@ -1,13 +1,30 @@
 //! Signed Certificate Timestamp list extension as defined in the
 //! [Certificate Transparency RFC 6962].
 //!
+//! This module also provides the precertificate poison extension and the
+//! `TBSCertificate` transformations needed to submit precertificates to a
+//! log and to verify the SCTs embedded in a final certificate.
+//!
 //! [Certificate Transparency RFC 6962]: https://datatracker.ietf.org/doc/html/rfc6962
 
 #![cfg(feature = "sct")]
 
+use crate::{
+    certificate::{CertificateInner, Profile, TbsCertificateInner},
+    ext::{
+        pkix::{AuthorityKeyIdentifier, ExtendedKeyUsage},
+        AsExtension,
+    },
+};
 use alloc::{format, vec::Vec};
-use const_oid::{db::rfc6962::CT_PRECERT_SCTS, AssociatedOid, ObjectIdentifier};
-use der::asn1::OctetString;
+use const_oid::{
+    db::rfc6962::{CT_PRECERT_POISON, CT_PRECERT_SCTS, CT_PRECERT_SIGNING_CERT},
+    AssociatedOid, ObjectIdentifier,
+};
+use der::{
+    asn1::{Null, OctetString},
+    DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, Writer,
+};
 use tls_codec::{
     DeserializeBytes, SerializeBytes, TlsByteVecU16, TlsDeserializeBytes, TlsSerializeBytes,
     TlsSize,
@@ -32,11 +49,16 @@ impl_extension!(SignedCertificateTimestampList, critical = false);
 
 /// Errors that are thrown by this module.
 #[derive(PartialEq, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// [Errors][der::Error] from the `der` crate.
     Der(der::Error),
     /// [Errors][tls_codec::Error] from the `tls_codec` crate.
     Tls(tls_codec::Error),
+    /// The certificate given as the Precertificate Signing Certificate does
+    /// not carry the `id-kp-precertificateSigning` extended key usage or did
+    /// not issue the precertificate.
+    InvalidPrecertSigningCertificate,
 }
 
 impl From<der::Error> for Error {
@@ -83,6 +105,149 @@ impl SignedCertificateTimestampList {
         }
         Ok(result)
     }
+
+    /// Parses the encoded [SignedCertificateTimestamp]s and returns a [Vec]
+    /// containing them.
+    ///
+    /// This is a shorthand for calling [SerializedSct::parse_timestamp] on
+    /// each of the results of [Self::parse_timestamps].
+    pub fn parse_signed_timestamps(&self) -> Result<Vec<SignedCertificateTimestamp>, Error> {
+        self.parse_timestamps()?
+            .iter()
+            .map(SerializedSct::parse_timestamp)
+            .collect()
+    }
+}
+
+/// Precertificate poison extension as defined in [RFC 6962 Section 3.1].
+///
+/// ```text
+/// extnValue ::= NULL
+/// ```
+///
+/// The extension must be marked critical so that a precertificate can't be
+/// mistaken for a final certificate.
+///
+/// [RFC 6962 Section 3.1]: https://datatracker.ietf.org/doc/html/rfc6962#section-3.1
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrecertificatePoison;
+
+impl AssociatedOid for PrecertificatePoison {
+    const OID: ObjectIdentifier = CT_PRECERT_POISON;
+}
+
+impl_extension!(PrecertificatePoison, critical = true);
+
+impl FixedTag for PrecertificatePoison {
+    const TAG: Tag = Tag::Null;
+}
+
+impl<'a> DecodeValue<'a> for PrecertificatePoison {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        Null::decode_value(reader, header).map(|_| Self)
+    }
+}
+
+impl EncodeValue for PrecertificatePoison {
+    fn value_len(&self) -> der::Result<Length> {
+        Null.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        Null.encode_value(writer)
+    }
+}
+
+impl<P: Profile> TbsCertificateInner<P> {
+    /// Is the precertificate poison extension present?
+    pub fn is_precertificate(&self) -> bool {
+        self.extensions
+            .iter()
+            .flatten()
+            .any(|e| e.extn_id == CT_PRECERT_POISON)
+    }
+
+    /// Turns this into the `TBSCertificate` of a precertificate by appending
+    /// the critical [`PrecertificatePoison`] extension.
+    ///
+    /// This does nothing if the poison extension is already present. Note
+    /// that extensions require the certificate to be a `v3` certificate.
+    pub fn add_precert_poison(&mut self) -> der::Result<()> {
+        if self.is_precertificate() {
+            return Ok(());
+        }
+
+        let extensions = self.extensions.get_or_insert_with(Vec::new);
+        let poison = PrecertificatePoison.to_extension(&self.subject, extensions)?;
+        extensions.push(poison);
+
+        Ok(())
+    }
+}
+
+impl<P: Profile> CertificateInner<P> {
+    /// Reconstructs the `TBSCertificate` signed by a log, as described in
+    /// [RFC 6962 Section 3.2].
+    ///
+    /// This accepts both a precertificate and a final certificate with
+    /// embedded SCTs: the poison and SCT list extensions are removed, while
+    /// the remaining extensions keep their order.
+    ///
+    /// If the precertificate was issued by a Precertificate Signing
+    /// Certificate, that certificate must be passed as `precert_signer`. The
+    /// `issuer` and the authority key identifier are then replaced by the
+    /// ones of the CA which will issue the final certificate, which are taken
+    /// from `precert_signer`.
+    ///
+    /// [RFC 6962 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc6962#section-3.2
+    pub fn precert_tbs_certificate(
+        &self,
+        precert_signer: Option<&CertificateInner<P>>,
+    ) -> Result<TbsCertificateInner<P>, Error> {
+        let mut tbs = self.tbs_certificate.clone();
+
+        if let Some(extensions) = tbs.extensions.as_mut() {
+            extensions.retain(|e| e.extn_id != CT_PRECERT_POISON && e.extn_id != CT_PRECERT_SCTS);
+        }
+
+        if let Some(signer) = precert_signer {
+            let signer = &signer.tbs_certificate;
+            let is_signing_cert = match signer.get::<ExtendedKeyUsage>()? {
+                Some((_, eku)) => eku.0.contains(&CT_PRECERT_SIGNING_CERT),
+                None => false,
+            };
+
+            if !is_signing_cert || signer.subject != tbs.issuer {
+                return Err(Error::InvalidPrecertSigningCertificate);
+            }
+
+            tbs.issuer = signer.issuer.clone();
+
+            let authority_key_id = signer
+                .extensions
+                .iter()
+                .flatten()
+                .find(|e| e.extn_id == AuthorityKeyIdentifier::OID);
+
+            if let Some(extensions) = tbs.extensions.as_mut() {
+                match authority_key_id {
+                    Some(authority_key_id) => extensions
+                        .iter_mut()
+                        .filter(|e| e.extn_id == AuthorityKeyIdentifier::OID)
+                        .for_each(|e| *e = authority_key_id.clone()),
+                    None => extensions.retain(|e| e.extn_id != AuthorityKeyIdentifier::OID),
+                }
+            }
+        }
+
+        if tbs.extensions.as_ref().is_some_and(Vec::is_empty) {
+            tbs.extensions = None;
+        }
+
+        Ok(tbs)
+    }
 }
 
 /// A byte string that contains a serialized [SignedCertificateTimestamp] as
@@ -622,10 +787,16 @@ mod tests {
                 OctetString::new(&SCT_EXAMPLE[3..]).expect("failed to convert to u8 array"),
             )),
         );
-        let scts = result
-            .expect("run_test failed")
-            .parse_timestamps()
-            .expect("parse_timestamps failed");
+        let list = result.expect("run_test failed");
+        let scts = list.parse_timestamps().expect("parse_timestamps failed");
+        let signed_timestamps = list
+            .parse_signed_timestamps()
+            .expect("parse_signed_timestamps failed");
+        assert_eq!(signed_timestamps.len(), 2);
+        assert_eq!(
+            scts[1].parse_timestamp().as_ref(),
+            Ok(&signed_timestamps[1])
+        );
         assert_eq!(
             scts[0].parse_timestamp(),
             Ok(SignedCertificateTimestamp {
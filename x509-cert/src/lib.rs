@@ -25,6 +25,7 @@ mod macros;
 
 pub mod anchor;
 pub mod attr;
+pub mod attr_cert;
 pub mod certificate;
 pub mod crl;
 pub mod ext;
@@ -36,6 +37,12 @@ pub mod time;
 #[cfg(feature = "builder")]
 pub mod builder;
 
+#[cfg(feature = "serde")]
+mod serde_helpers;
+
+#[cfg(feature = "verify")]
+pub mod verify;
+
 pub use certificate::{Certificate, PkiPath, TbsCertificate, Version};
 pub use der;
 pub use spki;
@@ -1,7 +1,11 @@
 //! Name-related definitions as defined in X.501 (and updated by RFC 5280).
 
 use crate::attr::AttributeTypeAndValue;
-use alloc::vec::Vec;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use core::{fmt, str::FromStr};
 use der::{asn1::SetOfVec, Encode};
 
@@ -22,6 +26,8 @@ pub type Name = RdnSequence;
 ///
 /// [RFC 5280 Section 4.1.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.4
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct RdnSequence(pub Vec<RelativeDistinguishedName>);
 
@@ -36,17 +42,85 @@ impl RdnSequence {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Serializes this name according to the rules in [RFC 4514].
+    ///
+    /// This is the same as the [`fmt::Display`] impl, and the result can be
+    /// parsed back using [`RdnSequence::from_str`].
+    ///
+    /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+    pub fn to_rfc4514_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns an object which formats this name the way OpenSSL's default
+    /// `oneline` name format does, e.g. `C = US, O = Acme, CN = example`.
+    ///
+    /// Unlike RFC 4514, RDNs are printed in the order they are encoded in.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use x509_cert::name::Name;
+    ///
+    /// let name = Name::from_str("CN=example,O=Acme\\, Inc.,C=US").unwrap();
+    /// assert_eq!(
+    ///     name.display_openssl().to_string(),
+    ///     "C = US, O = \"Acme, Inc.\", CN = example"
+    /// );
+    /// ```
+    pub fn display_openssl(&self) -> OpenSslDisplay<'_> {
+        OpenSslDisplay(self)
+    }
+
+    /// Does this name match `other` following the name comparison rules of
+    /// [RFC 5280 Section 7.1]?
+    ///
+    /// Unlike `==`, which compares the DER encodings, this ignores differences
+    /// in case, whitespace and string types between the attribute values. See
+    /// [`AttributeTypeAndValue::matches`].
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use x509_cert::name::Name;
+    ///
+    /// let a = Name::from_str("CN=Example  CA,O=Acme").unwrap();
+    /// let b = Name::from_str("CN=example ca,O=ACME").unwrap();
+    /// assert!(a.matches(&b));
+    /// assert_ne!(a, b);
+    /// ```
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn matches(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| a.matches(b))
+    }
 }
 
 /// Parse an [`RdnSequence`] string.
 ///
-/// Follows the rules in [RFC 4514].
+/// Follows the rules in [RFC 4514]: RDNs are separated by `,`, the attributes
+/// of a multi-valued RDN by `+`, and special characters may be escaped with
+/// `\` or given as `\XX` hex pairs. Values prefixed with `#` are parsed as
+/// hex-encoded DER.
+///
+/// ```
+/// use std::str::FromStr;
+/// use x509_cert::name::Name;
+///
+/// let name = Name::from_str("CN=example,O=Acme,C=US").unwrap();
+/// assert_eq!(name.0.len(), 3);
+/// assert_eq!(name.to_string(), "CN=example,O=Acme,C=US");
+/// ```
 ///
 /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
 impl FromStr for RdnSequence {
     type Err = der::Error;
 
     fn from_str(s: &str) -> der::Result<Self> {
+        // As per RFC 4514 Section 3, the empty string is the empty sequence
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
         let mut parts = split(s, b',')
             .map(RelativeDistinguishedName::from_str)
             .collect::<der::Result<Vec<_>>>()?;
@@ -74,22 +148,46 @@ impl fmt::Display for RdnSequence {
 
 impl_newtype!(RdnSequence, Vec<RelativeDistinguishedName>);
 
+/// Helper for formatting an [`RdnSequence`] the way OpenSSL does.
+///
+/// See [`RdnSequence::display_openssl`].
+#[derive(Copy, Clone, Debug)]
+pub struct OpenSslDisplay<'a>(&'a RdnSequence);
+
+impl fmt::Display for OpenSslDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rdn) in self.0 .0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            for (j, atv) in rdn.0.iter().enumerate() {
+                if j != 0 {
+                    f.write_str(" + ")?;
+                }
+
+                atv.fmt_openssl(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Find the indices of all non-escaped separators.
+///
+/// A separator is escaped if it is preceded by an odd number of backslashes.
 fn find(s: &str, b: u8) -> impl '_ + Iterator<Item = usize> {
     (0..s.len())
         .filter(move |i| s.as_bytes()[*i] == b)
         .filter(|i| {
-            let x = i
-                .checked_sub(2)
-                .map(|i| s.as_bytes()[i])
-                .unwrap_or_default();
+            let escapes = s.as_bytes()[..*i]
+                .iter()
+                .rev()
+                .take_while(|c| **c == b'\\')
+                .count();
 
-            let y = i
-                .checked_sub(1)
-                .map(|i| s.as_bytes()[i])
-                .unwrap_or_default();
-
-            y != b'\\' || x == b'\\'
+            escapes % 2 == 0
         })
 }
 
@@ -137,8 +235,13 @@ pub type DistinguishedName = RdnSequence;
 ///
 /// [RFC 5280 Section 4.1.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.4
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct RelativeDistinguishedName(pub SetOfVec<AttributeTypeAndValue>);
+pub struct RelativeDistinguishedName(
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::set_of"))]
+    pub  SetOfVec<AttributeTypeAndValue>,
+);
 
 impl RelativeDistinguishedName {
     /// Converts an RelativeDistinguishedName string into an encoded RelativeDistinguishedName
@@ -149,6 +252,32 @@ impl RelativeDistinguishedName {
     pub fn encode_from_string(s: &str) -> Result<Vec<u8>, der::Error> {
         Self::from_str(s)?.to_der()
     }
+
+    /// Does this RDN match `other` following the name comparison rules of
+    /// [RFC 5280 Section 7.1]?
+    ///
+    /// Both must contain the same number of attributes, and each attribute of
+    /// this RDN must match an attribute of `other` regardless of their order.
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn matches(&self, other: &Self) -> bool {
+        if self.0.len() != other.0.len() {
+            return false;
+        }
+
+        // Each attribute of `other` can only be matched once. Matching is an
+        // equivalence, so it's enough to take the first unmatched candidate.
+        let mut matched = vec![false; other.0.len()];
+        self.0.iter().all(|atv| {
+            other
+                .0
+                .iter()
+                .zip(matched.iter_mut())
+                .find(|(other, matched)| !**matched && atv.matches(other))
+                .map(|(_, matched)| *matched = true)
+                .is_some()
+        })
+    }
 }
 
 /// Parse a [`RelativeDistinguishedName`] string.
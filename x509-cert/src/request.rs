@@ -13,7 +13,7 @@ use const_oid::{AssociatedOid, ObjectIdentifier};
 use der::asn1::BitString;
 use der::{
     asn1::{Any, SetOfVec},
-    Decode, Enumerated, Sequence,
+    Decode, Enumerated, Sequence, Spanned,
 };
 use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
 
@@ -23,6 +23,7 @@ use der::pem::PemLabel;
 /// Version identifier for certification request information.
 ///
 /// (RFC 2986 designates `0` as the only valid version)
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Enumerated, Default)]
 #[asn1(type = "INTEGER")]
 #[repr(u8)]
@@ -44,6 +45,7 @@ pub enum Version {
 /// ```
 ///
 /// [RFC 2986 Section 4]: https://datatracker.ietf.org/doc/html/rfc2986#section-4
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, PartialEq, Eq, Sequence)]
 pub struct CertReqInfo {
     /// Certification request version.
@@ -71,6 +73,7 @@ pub struct CertReqInfo {
 /// ```
 ///
 /// [RFC 2986 Section 4]: https://datatracker.ietf.org/doc/html/rfc2986#section-4
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, PartialEq, Eq, Sequence)]
 pub struct CertReq {
     /// Certification request information.
@@ -96,6 +99,43 @@ impl<'a> TryFrom<&'a [u8]> for CertReq {
     }
 }
 
+/// PKCS#10 `CertificationRequest` which retains the original encoding of its
+/// `CertificationRequestInfo`.
+///
+/// Re-encoding a decoded [`CertReqInfo`] doesn't necessarily reproduce the
+/// bytes which were signed. This type records the exact encoding of the
+/// `CertificationRequestInfo` as it was decoded, so that the signature of the
+/// request can be verified over those bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Sequence)]
+pub struct SpannedCertReq<'a> {
+    /// Certification request information.
+    pub info: Spanned<'a, CertReqInfo>,
+
+    /// Signature algorithm identifier.
+    pub algorithm: AlgorithmIdentifierOwned,
+
+    /// Signature.
+    pub signature: BitString,
+}
+
+impl<'a> SpannedCertReq<'a> {
+    /// Get the original encoding of the `CertificationRequestInfo`, i.e. the
+    /// bytes over which the request was signed.
+    pub fn info_der(&self) -> &'a [u8] {
+        self.info.as_bytes()
+    }
+}
+
+impl From<SpannedCertReq<'_>> for CertReq {
+    fn from(req: SpannedCertReq<'_>) -> Self {
+        Self {
+            info: req.info.into_inner(),
+            algorithm: req.algorithm,
+            signature: req.signature,
+        }
+    }
+}
+
 /// `ExtensionReq` as defined in [RFC 5272 Section 3.1].
 ///
 /// ```text
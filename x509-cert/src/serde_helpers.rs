@@ -0,0 +1,504 @@
+//! [`serde`] helpers for the `der` and `spki` types used as fields of the
+//! types in this crate.
+//!
+//! These are used through `#[serde(with = "...")]` attributes. Byte strings
+//! are handled by [`serdect`], which serializes them as hex strings in
+//! human-readable formats and as raw bytes otherwise.
+
+use crate::ext::{
+    pkix::{
+        name::GeneralName, AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage,
+        KeyUsages, SubjectAltName, SubjectKeyIdentifier,
+    },
+    Extension,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use const_oid::AssociatedOid;
+use core::str::FromStr;
+use der::{
+    asn1::{
+        BitString, GeneralizedTime, Ia5String, ObjectIdentifier, OctetString, PrintableString,
+        SetOfVec, UtcTime,
+    },
+    flagset::FlagSet,
+    Any, DateTime, Decode, DerOrd, Encode, ErrorKind, Tag, Tagged,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+
+/// `OBJECT IDENTIFIER` using the dotted-decimal notation.
+pub(crate) mod oid {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        oid: &ObjectIdentifier,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(oid)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ObjectIdentifier, D::Error> {
+        let oid = String::deserialize(deserializer)?;
+        ObjectIdentifier::new(&oid).map_err(de::Error::custom)
+    }
+}
+
+/// `ANY` as its complete DER encoding.
+pub(crate) mod any {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(any: &Any, serializer: S) -> Result<S::Ok, S::Error> {
+        let der = any.to_der().map_err(ser::Error::custom)?;
+        serdect::slice::serialize_hex_upper_or_bin(&der, serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Any, D::Error> {
+        let der = serdect::slice::deserialize_hex_or_bin_vec(deserializer)?;
+        Any::from_der(&der).map_err(de::Error::custom)
+    }
+}
+
+/// `OPTIONAL` `ANY` as its complete DER encoding.
+pub(crate) mod option_any {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(transparent)]
+    struct Wrapper(#[serde(with = "any")] Any);
+
+    pub(crate) fn serialize<S: Serializer>(
+        any: &Option<Any>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        any.clone().map(Wrapper).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Any>, D::Error> {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+    }
+}
+
+/// Attribute value of the string types used in names as the type and its
+/// text, and any other value as its complete DER encoding.
+pub(crate) mod attribute_value {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    enum Repr {
+        PrintableString(String),
+        Utf8String(String),
+        Ia5String(String),
+        Der(
+            #[serde(
+                serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+                deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+            )]
+            Vec<u8>,
+        ),
+    }
+
+    pub(crate) fn serialize<S: Serializer>(any: &Any, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match any.tag() {
+            Tag::PrintableString => any
+                .decode_as::<PrintableString>()
+                .map(|s| Repr::PrintableString(s.to_string())),
+            Tag::Utf8String => any.decode_as::<String>().map(Repr::Utf8String),
+            Tag::Ia5String => any
+                .decode_as::<Ia5String>()
+                .map(|s| Repr::Ia5String(s.to_string())),
+            _ => any.to_der().map(Repr::Der),
+        };
+
+        repr.map_err(ser::Error::custom)?.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Any, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::PrintableString(s) => PrintableString::new(&s).and_then(|s| Any::encode_from(&s)),
+            Repr::Utf8String(s) => Any::encode_from(&s),
+            Repr::Ia5String(s) => Ia5String::new(&s).and_then(|s| Any::encode_from(&s)),
+            Repr::Der(der) => Any::from_der(&der),
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+/// `BIT STRING` as its number of unused bits and its contents.
+pub(crate) mod bit_string {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    struct Repr {
+        unused_bits: u8,
+        #[serde(
+            serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+            deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+        )]
+        bytes: Vec<u8>,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        bits: &BitString,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Repr {
+            unused_bits: bits.unused_bits(),
+            bytes: bits.raw_bytes().into(),
+        }
+        .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BitString, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        BitString::new(repr.unused_bits, repr.bytes).map_err(de::Error::custom)
+    }
+}
+
+/// `OPTIONAL` `BIT STRING` as its number of unused bits and its contents.
+pub(crate) mod option_bit_string {
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(transparent)]
+    struct Wrapper(#[serde(with = "bit_string")] BitString);
+
+    pub(crate) fn serialize<S: Serializer>(
+        bits: &Option<BitString>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        bits.clone().map(Wrapper).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<BitString>, D::Error> {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+    }
+}
+
+/// `UTCTime` as an RFC 3339 timestamp, e.g. `2024-01-31T12:00:00Z`.
+pub(crate) mod utc_time {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        time: &UtcTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.to_date_time())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<UtcTime, D::Error> {
+        let time = String::deserialize(deserializer)?;
+        DateTime::from_str(&time)
+            .and_then(UtcTime::from_date_time)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// `GeneralizedTime` as an RFC 3339 timestamp, e.g. `2024-01-31T12:00:00Z`.
+pub(crate) mod generalized_time {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        time: &GeneralizedTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.to_date_time())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<GeneralizedTime, D::Error> {
+        let time = String::deserialize(deserializer)?;
+        DateTime::from_str(&time)
+            .map(GeneralizedTime::from_date_time)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// `SET OF` as a sequence of its elements.
+///
+/// Deserialization sorts the elements into DER order.
+pub(crate) mod set_of {
+    use super::*;
+
+    pub(crate) fn serialize<S, T>(set: &SetOfVec<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: DerOrd + Serialize,
+    {
+        serializer.collect_seq(set.iter())
+    }
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<SetOfVec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: DerOrd + Deserialize<'de>,
+    {
+        SetOfVec::try_from(Vec::<T>::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// `AlgorithmIdentifier` as its OID and optional parameters.
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "AlgorithmIdentifierOwned")]
+pub(crate) struct AlgorithmIdentifierDef {
+    #[serde(with = "oid")]
+    oid: ObjectIdentifier,
+    #[serde(with = "option_any")]
+    parameters: Option<Any>,
+}
+
+/// `SubjectPublicKeyInfo` as its algorithm and public key.
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "SubjectPublicKeyInfoOwned")]
+pub(crate) struct SubjectPublicKeyInfoDef {
+    #[serde(with = "AlgorithmIdentifierDef")]
+    algorithm: AlgorithmIdentifierOwned,
+    #[serde(with = "bit_string")]
+    subject_public_key: BitString,
+}
+
+/// `Extension` with the value of the common extensions as their fields, and
+/// the value of any other extension as its DER encoding.
+///
+/// Values which don't re-encode to the same DER are always serialized as
+/// their DER encoding, so that deserialization reproduces the extension.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ExtensionRepr {
+    #[serde(with = "oid")]
+    extn_id: ObjectIdentifier,
+    critical: bool,
+    extn_value: ExtensionValue,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+enum ExtensionValue {
+    BasicConstraints {
+        ca: bool,
+        path_len_constraint: Option<u8>,
+    },
+    KeyUsage(Vec<String>),
+    ExtendedKeyUsage(Vec<String>),
+    SubjectKeyIdentifier(
+        #[serde(
+            serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+            deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+        )]
+        Vec<u8>,
+    ),
+    AuthorityKeyIdentifier {
+        #[serde(
+            serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+            deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+        )]
+        key_identifier: Vec<u8>,
+    },
+    SubjectAltName(Vec<GeneralNameRepr>),
+    Der(
+        #[serde(
+            serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+            deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+        )]
+        Vec<u8>,
+    ),
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+enum GeneralNameRepr {
+    Rfc822Name(String),
+    DnsName(String),
+    UniformResourceIdentifier(String),
+    IpAddress(
+        #[serde(
+            serialize_with = "serdect::slice::serialize_hex_upper_or_bin",
+            deserialize_with = "serdect::slice::deserialize_hex_or_bin_vec"
+        )]
+        Vec<u8>,
+    ),
+}
+
+impl ExtensionValue {
+    /// Decode the value of a common extension, or `None` for any other
+    /// extension as well as for those using names this can't represent.
+    fn decode(oid: ObjectIdentifier, der: &[u8]) -> der::Result<Option<Self>> {
+        Ok(Some(match oid {
+            BasicConstraints::OID => {
+                let bc = BasicConstraints::from_der(der)?;
+                Self::BasicConstraints {
+                    ca: bc.ca,
+                    path_len_constraint: bc.path_len_constraint,
+                }
+            }
+            KeyUsage::OID => Self::KeyUsage(
+                KeyUsage::from_der(der)?
+                    .0
+                    .into_iter()
+                    .map(|usage| format!("{:?}", usage))
+                    .collect(),
+            ),
+            ExtendedKeyUsage::OID => Self::ExtendedKeyUsage(
+                ExtendedKeyUsage::from_der(der)?
+                    .0
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ),
+            SubjectKeyIdentifier::OID => {
+                Self::SubjectKeyIdentifier(SubjectKeyIdentifier::from_der(der)?.0.into_bytes())
+            }
+            AuthorityKeyIdentifier::OID => match AuthorityKeyIdentifier::from_der(der)? {
+                AuthorityKeyIdentifier {
+                    key_identifier: Some(key_identifier),
+                    authority_cert_issuer: None,
+                    authority_cert_serial_number: None,
+                } => Self::AuthorityKeyIdentifier {
+                    key_identifier: key_identifier.into_bytes(),
+                },
+                _ => return Ok(None),
+            },
+            SubjectAltName::OID => {
+                let mut names = Vec::new();
+                for name in SubjectAltName::from_der(der)?.0 {
+                    names.push(match name {
+                        GeneralName::Rfc822Name(name) => {
+                            GeneralNameRepr::Rfc822Name(name.to_string())
+                        }
+                        GeneralName::DnsName(name) => GeneralNameRepr::DnsName(name.to_string()),
+                        GeneralName::UniformResourceIdentifier(name) => {
+                            GeneralNameRepr::UniformResourceIdentifier(name.to_string())
+                        }
+                        GeneralName::IpAddress(address) => {
+                            GeneralNameRepr::IpAddress(address.into_bytes())
+                        }
+                        _ => return Ok(None),
+                    });
+                }
+                Self::SubjectAltName(names)
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Object identifier of the extension this is the value of, if known.
+    fn oid(&self) -> Option<ObjectIdentifier> {
+        match self {
+            Self::BasicConstraints { .. } => Some(BasicConstraints::OID),
+            Self::KeyUsage(_) => Some(KeyUsage::OID),
+            Self::ExtendedKeyUsage(_) => Some(ExtendedKeyUsage::OID),
+            Self::SubjectKeyIdentifier(_) => Some(SubjectKeyIdentifier::OID),
+            Self::AuthorityKeyIdentifier { .. } => Some(AuthorityKeyIdentifier::OID),
+            Self::SubjectAltName(_) => Some(SubjectAltName::OID),
+            Self::Der(_) => None,
+        }
+    }
+
+    fn to_der(&self) -> der::Result<Vec<u8>> {
+        match self {
+            Self::BasicConstraints {
+                ca,
+                path_len_constraint,
+            } => BasicConstraints {
+                ca: *ca,
+                path_len_constraint: *path_len_constraint,
+            }
+            .to_der(),
+            Self::KeyUsage(usages) => {
+                let mut flags = FlagSet::<KeyUsages>::default();
+                for usage in usages {
+                    flags |= FlagSet::<KeyUsages>::full()
+                        .into_iter()
+                        .find(|flag| format!("{:?}", flag) == *usage)
+                        .ok_or(ErrorKind::Value {
+                            tag: Tag::BitString,
+                        })?;
+                }
+                KeyUsage(flags).to_der()
+            }
+            Self::ExtendedKeyUsage(usages) => ExtendedKeyUsage(
+                usages
+                    .iter()
+                    .map(|oid| ObjectIdentifier::new(oid))
+                    .collect::<Result<_, _>>()?,
+            )
+            .to_der(),
+            Self::SubjectKeyIdentifier(key_identifier) => {
+                SubjectKeyIdentifier(OctetString::new(key_identifier.clone())?).to_der()
+            }
+            Self::AuthorityKeyIdentifier { key_identifier } => AuthorityKeyIdentifier {
+                key_identifier: Some(OctetString::new(key_identifier.clone())?),
+                authority_cert_issuer: None,
+                authority_cert_serial_number: None,
+            }
+            .to_der(),
+            Self::SubjectAltName(names) => SubjectAltName(
+                names
+                    .iter()
+                    .map(|name| {
+                        Ok(match name {
+                            GeneralNameRepr::Rfc822Name(name) => {
+                                GeneralName::Rfc822Name(Ia5String::new(name)?)
+                            }
+                            GeneralNameRepr::DnsName(name) => {
+                                GeneralName::DnsName(Ia5String::new(name)?)
+                            }
+                            GeneralNameRepr::UniformResourceIdentifier(name) => {
+                                GeneralName::UniformResourceIdentifier(Ia5String::new(name)?)
+                            }
+                            GeneralNameRepr::IpAddress(address) => {
+                                GeneralName::IpAddress(OctetString::new(address.clone())?)
+                            }
+                        })
+                    })
+                    .collect::<der::Result<_>>()?,
+            )
+            .to_der(),
+            Self::Der(der) => Ok(der.clone()),
+        }
+    }
+}
+
+impl From<Extension> for ExtensionRepr {
+    fn from(extension: Extension) -> Self {
+        let der = extension.extn_value.as_bytes();
+        let extn_value = ExtensionValue::decode(extension.extn_id, der)
+            .ok()
+            .flatten()
+            .filter(|value| value.to_der().ok().as_deref() == Some(der))
+            .unwrap_or_else(|| ExtensionValue::Der(der.into()));
+
+        Self {
+            extn_id: extension.extn_id,
+            critical: extension.critical,
+            extn_value,
+        }
+    }
+}
+
+impl TryFrom<ExtensionRepr> for Extension {
+    type Error = der::Error;
+
+    fn try_from(repr: ExtensionRepr) -> der::Result<Self> {
+        if matches!(repr.extn_value.oid(), Some(oid) if oid != repr.extn_id) {
+            return Err(ErrorKind::OidUnknown { oid: repr.extn_id }.into());
+        }
+
+        Ok(Self {
+            extn_id: repr.extn_id,
+            critical: repr.critical,
+            extn_value: OctetString::new(repr.extn_value.to_der()?)?,
+        })
+    }
+}
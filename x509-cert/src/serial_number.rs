@@ -71,14 +71,29 @@ impl<P: Profile> SerialNumber<P> {
 
 #[cfg(feature = "builder")]
 impl<P: Profile> SerialNumber<P> {
-    /// Generates a random serial number from RNG.
+    /// Generates a random serial number of `len` octets from RNG.
     ///
-    /// This follows the recommendation the CAB forum [ballot 164] and uses a minimum of 64 bits
-    /// of output from the CSPRNG. This currently defaults to a 17-bytes long serial number.
+    /// The serial number is positive and encoded in exactly `len` octets: its most significant
+    /// bit is cleared so that it doesn't need a leading zero octet, and its first octet is never
+    /// zero. As one bit is lost this way, `len` must be at least 9 to follow the recommendation
+    /// of the CAB forum [ballot 164] of a minimum of 64 bits of output from the CSPRNG, and at
+    /// most 20 as required by [RFC 5280 Section 4.1.2.2].
     ///
     /// [ballot 164]: https://cabforum.org/2016/03/31/ballot-164/
-    pub fn generate(rng: &mut impl CryptoRngCore) -> Result<Self> {
-        Self::generate_with_prefix(&[], 17, rng)
+    /// [RFC 5280 Section 4.1.2.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.2
+    pub fn generate(rng: &mut impl CryptoRngCore, len: usize) -> Result<Self> {
+        if len < 9 || Length::try_from(len)? > Self::MAX_LEN {
+            return Err(ErrorKind::Failed.into());
+        }
+
+        let mut buf = vec![0; len];
+
+        while buf[0] == 0 {
+            rng.fill_bytes(&mut buf);
+            buf[0] &= 0x7f;
+        }
+
+        Self::new(&buf)
     }
 
     /// Generates a random serial number from RNG. Include a prefix value.
@@ -185,12 +200,43 @@ impl_from!(u32);
 impl_from!(u64);
 impl_from!(usize);
 
+/// Serialized as the big endian two's complement encoding of the integer,
+/// i.e. the contents of the DER `INTEGER`.
+#[cfg(feature = "serde")]
+impl<P: Profile> serde::Serialize for SerialNumber<P> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serdect::slice::serialize_hex_upper_or_bin(&self.as_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: Profile> serde::Deserialize<'de> for SerialNumber<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let bytes = serdect::slice::deserialize_hex_or_bin_vec(deserializer)?;
+        let serial = Self {
+            inner: Int::new(&bytes).map_err(D::Error::custom)?,
+            _profile: PhantomData,
+        };
+
+        P::check_serial_number(&serial).map_err(D::Error::custom)?;
+
+        Ok(serial)
+    }
+}
+
 // Implement by hand because the derive would create invalid values.
 // Use the constructor to create a valid value.
 #[cfg(feature = "arbitrary")]
 impl<'a, P: Profile> arbitrary::Arbitrary<'a> for SerialNumber<P> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let len = u.int_in_range(0u32..=Self::MAX_LEN.into())?;
+        let len = u.int_in_range(1u32..=Self::MAX_LEN.into())?;
 
         Self::new(u.bytes(len as usize)?).map_err(|_| arbitrary::Error::IncorrectFormat)
     }
@@ -251,12 +297,19 @@ mod tests {
     #[cfg(feature = "builder")]
     #[test]
     fn serial_number_generate() {
-        let sn = SerialNumber::<Rfc5280>::generate(&mut rand::thread_rng()).unwrap();
+        // The generated serial numbers are encoded in exactly the requested length
+        for len in 9..=20 {
+            let sn = SerialNumber::<Rfc5280>::generate(&mut rand::thread_rng(), len).unwrap();
+            assert_eq!(sn.as_bytes().len(), len);
+            assert_eq!(
+                sn.inner.value_len().unwrap(),
+                Length::try_from(len).unwrap()
+            );
+            assert!(sn.as_bytes()[0] != 0 && sn.as_bytes()[0] < 0x80);
+        }
 
-        // Underlying storage uses signed int for compatibility reasons,
-        // we may need to prefix the value with 0x00 to make it an unsigned.
-        // in which case the length is going to be 18.
-        assert!(matches!(sn.as_bytes().len(), 17..=18));
+        assert!(SerialNumber::<Rfc5280>::generate(&mut rand::thread_rng(), 8).is_err());
+        assert!(SerialNumber::<Rfc5280>::generate(&mut rand::thread_rng(), 21).is_err());
 
         let sn =
             SerialNumber::<Rfc5280>::generate_with_prefix(&[], 8, &mut rand::thread_rng()).unwrap();
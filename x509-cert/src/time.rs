@@ -3,10 +3,10 @@
 use core::fmt;
 use core::time::Duration;
 use der::asn1::{GeneralizedTime, UtcTime};
-use der::{Choice, DateTime, Sequence, ValueOrd};
+use der::{Choice, DateTime, ErrorKind, Sequence, ValueOrd};
 
 #[cfg(feature = "std")]
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// X.501 `Time` as defined in [RFC 5280 Section 4.1.2.5].
 ///
@@ -22,6 +22,7 @@ use std::time::SystemTime;
 /// [RFC 5280 Section 4.1.2.5]: https://tools.ietf.org/html/rfc5280#section-4.1.2.5
 /// [RFC 5280 Appendix A]: https://tools.ietf.org/html/rfc5280#page-117
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Choice, Copy, Clone, Debug, Eq, PartialEq, ValueOrd)]
 pub enum Time {
     /// Legacy UTC time (has 2-digit year, valid from 1970 to 2049).
@@ -29,10 +30,15 @@ pub enum Time {
     /// Note: RFC 5280 specifies 1950-2049, however due to common operations working on
     /// `UNIX_EPOCH` this implementation's lower bound is 1970.
     #[asn1(type = "UTCTime")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::utc_time"))]
     UtcTime(UtcTime),
 
     /// Modern [`GeneralizedTime`] encoding with 4-digit year.
     #[asn1(type = "GeneralizedTime")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::generalized_time")
+    )]
     GeneralTime(GeneralizedTime),
 }
 
@@ -41,6 +47,26 @@ impl Time {
     pub const INFINITY: Time =
         Time::GeneralTime(GeneralizedTime::from_date_time(DateTime::INFINITY));
 
+    /// Create a [`Time`] from a [`DateTime`].
+    ///
+    /// As per RFC 5280 Section 4.1.2.5, dates through 2049 are expressed as
+    /// `UTCTime` and later dates as `GeneralizedTime`.
+    pub fn from_date_time(date_time: DateTime) -> der::Result<Self> {
+        if date_time.year() <= UtcTime::MAX_YEAR {
+            Ok(Time::UtcTime(UtcTime::from_date_time(date_time)?))
+        } else {
+            Ok(Time::GeneralTime(GeneralizedTime::from_date_time(
+                date_time,
+            )))
+        }
+    }
+
+    /// Create a [`Time`] from a duration since `UNIX_EPOCH`, choosing the
+    /// encoding as [`Time::from_date_time`] does.
+    pub fn from_unix_duration(unix_duration: Duration) -> der::Result<Self> {
+        Self::from_date_time(DateTime::from_unix_duration(unix_duration)?)
+    }
+
     /// Get duration since `UNIX_EPOCH`.
     pub fn to_unix_duration(self) -> Duration {
         match self {
@@ -71,10 +97,7 @@ impl Time {
     #[cfg(feature = "builder")]
     pub(crate) fn rfc5280_adjust_utc_time(&mut self) -> der::Result<()> {
         if let Time::GeneralTime(t) = self {
-            let date = t.to_date_time();
-            if date.year() <= UtcTime::MAX_YEAR {
-                *self = Time::UtcTime(UtcTime::from_date_time(date)?);
-            }
+            *self = Time::from_date_time(t.to_date_time())?;
         }
 
         Ok(())
@@ -132,6 +155,7 @@ impl TryFrom<SystemTime> for Time {
 /// ```
 /// [RFC 5280 Section 4.1.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.5
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
 pub struct Validity {
     /// notBefore value
@@ -142,15 +166,49 @@ pub struct Validity {
 }
 
 impl Validity {
+    /// Creates a `Validity` which starts at `not_before`, given as a duration
+    /// since `UNIX_EPOCH`, and lasts for `duration`.
+    ///
+    /// Each bound is encoded as `UTCTime` or `GeneralizedTime` depending on
+    /// its year, as per RFC 5280 Section 4.1.2.5.
+    pub fn from_unix_duration(not_before: Duration, duration: Duration) -> der::Result<Self> {
+        let not_after = not_before
+            .checked_add(duration)
+            .ok_or(ErrorKind::DateTime)?;
+
+        Ok(Self {
+            not_before: Time::from_unix_duration(not_before)?,
+            not_after: Time::from_unix_duration(not_after)?,
+        })
+    }
+
     /// Creates a `Validity` which starts now and lasts for `duration`.
     #[cfg(feature = "std")]
     pub fn from_now(duration: Duration) -> der::Result<Self> {
-        let now = SystemTime::now();
-        let then = now + duration;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| ErrorKind::DateTime)?;
 
-        Ok(Self {
-            not_before: Time::try_from(now)?,
-            not_after: Time::try_from(then)?,
-        })
+        Self::from_unix_duration(now, duration)
+    }
+
+    /// Is `time` within this validity period?
+    ///
+    /// Both bounds are inclusive, as per RFC 5280 Section 4.1.2.5.
+    pub fn is_valid_at(&self, time: Time) -> bool {
+        self.contains(time.to_unix_duration())
+    }
+
+    /// Is the current system time within this validity period?
+    #[cfg(feature = "std")]
+    pub fn is_currently_valid(&self) -> bool {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .is_ok_and(|now| self.contains(now))
+    }
+
+    fn contains(&self, unix_duration: Duration) -> bool {
+        self.not_before.to_unix_duration() <= unix_duration
+            && unix_duration <= self.not_after.to_unix_duration()
     }
 }
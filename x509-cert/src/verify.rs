@@ -0,0 +1,510 @@
+//! Certificate signature verification.
+//!
+//! [`Certificate::verify_signature`] selects the verification algorithm from
+//! the `signatureAlgorithm` of the certificate. The supported algorithms
+//! depend on the enabled features:
+//!
+//! - `rsa`: RSASSA-PKCS1-v1_5 with SHA-256, SHA-384 or SHA-512, and
+//!   RSASSA-PSS with the hash and salt length given by its parameters.
+//! - `p256`: ECDSA over the NIST P-256 curve with SHA-256.
+//! - `p384`: ECDSA over the NIST P-384 curve with SHA-384.
+//! - `ed25519`: Ed25519, using strict verification.
+//!
+//! Other algorithms can be verified with any [`Verifier`] through
+//! [`Certificate::verify_signature_with`].
+//!
+//! A [`Certificate`] verifies its signature over the re-encoded
+//! `TBSCertificate`, which differs from the signed bytes if the original
+//! encoding wasn't canonical DER. A [`SpannedCertificate`] retains and
+//! verifies the original encoding instead.
+//!
+//! The self-signature of a certification request can be verified with
+//! [`CertReq::verify`], or [`SpannedCertReq::verify`] for its original
+//! encoding.
+//!
+//! [`Certificate`]: crate::Certificate
+//! [`Certificate::verify_signature`]: crate::certificate::CertificateInner::verify_signature
+//! [`Certificate::verify_signature_with`]: crate::certificate::CertificateInner::verify_signature_with
+
+use crate::{
+    certificate::{CertificateInner, Profile, SpannedCertificate, TbsCertificateInner},
+    request::{CertReq, CertReqInfo, SpannedCertReq},
+};
+use const_oid::db::{
+    rfc5912::{
+        ECDSA_WITH_SHA_224, ECDSA_WITH_SHA_256, ECDSA_WITH_SHA_384, ECDSA_WITH_SHA_512,
+        ID_EC_PUBLIC_KEY, ID_RSASSA_PSS, RSA_ENCRYPTION, SHA_1_WITH_RSA_ENCRYPTION,
+        SHA_224_WITH_RSA_ENCRYPTION, SHA_256_WITH_RSA_ENCRYPTION, SHA_384_WITH_RSA_ENCRYPTION,
+        SHA_512_WITH_RSA_ENCRYPTION,
+    },
+    rfc8410::{ID_ED_25519, ID_ED_448},
+};
+use core::fmt;
+use der::{
+    asn1::{BitString, ObjectIdentifier},
+    Encode,
+};
+use signature::{SignatureEncoding, Verifier};
+use spki::{AlgorithmIdentifierOwned, DecodePublicKey, SubjectPublicKeyInfoOwned};
+
+#[cfg(any(
+    feature = "ed25519",
+    feature = "p256",
+    feature = "p384",
+    feature = "rsa"
+))]
+use {der::referenced::OwnedToRef, spki::SubjectPublicKeyInfoRef};
+
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Public key errors propagated from the [`spki::Error`] type.
+    PublicKey(spki::Error),
+
+    /// Verification error propagated for the [`signature::Error`] type.
+    Signature(signature::Error),
+
+    /// The `signature` field of the `TBSCertificate` doesn't match the
+    /// `signatureAlgorithm` of the certificate.
+    AlgorithmMismatch,
+
+    /// The signature algorithm, or its parameters, is not supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// The signature algorithm doesn't correspond to the algorithm of the
+    /// public key.
+    KeyAlgorithmMismatch,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+            Error::AlgorithmMismatch => {
+                f.write_str("signature algorithm doesn't match the TBSCertificate")
+            }
+            Error::UnsupportedAlgorithm(oid) => {
+                write!(f, "unsupported signature algorithm: {}", oid)
+            }
+            Error::KeyAlgorithmMismatch => {
+                f.write_str("signature algorithm doesn't match the public key")
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<spki::Error> for Error {
+    fn from(err: spki::Error) -> Error {
+        Error::PublicKey(err)
+    }
+}
+
+impl From<signature::Error> for Error {
+    fn from(err: signature::Error) -> Error {
+        Error::Signature(err)
+    }
+}
+
+impl<P: Profile> CertificateInner<P> {
+    /// Verify the signature of this certificate with the public key of its
+    /// issuer, `issuer`.
+    ///
+    /// The verification algorithm is selected from the `signatureAlgorithm`
+    /// of the certificate, which must match the `signature` field of the
+    /// `TBSCertificate`. See the [module documentation](crate::verify) for
+    /// the supported algorithms.
+    ///
+    /// The signature is verified over the DER encoding of the
+    /// `TBSCertificate`. If the certificate was decoded from an encoding
+    /// which isn't canonical DER, this differs from the signed bytes: use
+    /// [`SpannedCertificate::verify_signature`] to verify those instead.
+    pub fn verify_signature(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()> {
+        let tbs = self.tbs_certificate.to_der()?;
+        SignedData::new(
+            &self.tbs_certificate,
+            &tbs,
+            &self.signature_algorithm,
+            &self.signature,
+        )?
+        .verify(issuer)
+    }
+
+    /// Verify the signature of this certificate with the public key of its
+    /// issuer, `issuer`, using the verifying key type `VK` and signature
+    /// type `S`.
+    ///
+    /// Unlike [`CertificateInner::verify_signature`], the algorithm is chosen
+    /// by the caller: `VK` and `S` must correspond to the
+    /// `signatureAlgorithm` of the certificate.
+    pub fn verify_signature_with<VK, S>(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let tbs = self.tbs_certificate.to_der()?;
+        SignedData::new(
+            &self.tbs_certificate,
+            &tbs,
+            &self.signature_algorithm,
+            &self.signature,
+        )?
+        .verify_with::<VK, S>(issuer)
+    }
+}
+
+impl<P: Profile> SpannedCertificate<'_, P> {
+    /// Verify the signature of this certificate with the public key of its
+    /// issuer, `issuer`.
+    ///
+    /// This is the same as [`CertificateInner::verify_signature`], except
+    /// that the signature is verified over the original encoding of the
+    /// `TBSCertificate`.
+    pub fn verify_signature(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()> {
+        self.signed_data()?.verify(issuer)
+    }
+
+    /// Verify the signature of this certificate with the public key of its
+    /// issuer, `issuer`, using the verifying key type `VK` and signature
+    /// type `S`.
+    ///
+    /// This is the same as [`CertificateInner::verify_signature_with`],
+    /// except that the signature is verified over the original encoding of
+    /// the `TBSCertificate`.
+    pub fn verify_signature_with<VK, S>(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        self.signed_data()?.verify_with::<VK, S>(issuer)
+    }
+
+    fn signed_data(&self) -> Result<SignedData<'_>> {
+        SignedData::new(
+            self.tbs_certificate.value(),
+            self.tbs_certificate.as_bytes(),
+            &self.signature_algorithm,
+            &self.signature,
+        )
+    }
+}
+
+impl CertReq {
+    /// Verify the self-signature of this certification request using the
+    /// subject public key it contains.
+    ///
+    /// The verifying key type `VK` and signature type `S` must correspond to
+    /// the algorithms of the subject public key and signature respectively.
+    /// The signature algorithm of the request must be one which is used with
+    /// keys of the algorithm of the subject public key, if it's known.
+    ///
+    /// The signature is verified over the DER encoding of the
+    /// `CertificationRequestInfo`: use [`SpannedCertReq::verify`] to verify
+    /// the original encoding instead.
+    pub fn verify<VK, S>(&self) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        verify_request::<VK, S>(
+            &self.info,
+            &self.info.to_der()?,
+            &self.algorithm,
+            &self.signature,
+        )
+    }
+}
+
+impl SpannedCertReq<'_> {
+    /// Verify the self-signature of this certification request using the
+    /// subject public key it contains.
+    ///
+    /// This is the same as [`CertReq::verify`], except that the signature is
+    /// verified over the original encoding of the `CertificationRequestInfo`.
+    pub fn verify<VK, S>(&self) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        verify_request::<VK, S>(
+            self.info.value(),
+            self.info_der(),
+            &self.algorithm,
+            &self.signature,
+        )
+    }
+}
+
+/// Verify the self-signature of the request `info`, encoded as `tbs`.
+fn verify_request<VK, S>(
+    info: &CertReqInfo,
+    tbs: &[u8],
+    signature_algorithm: &AlgorithmIdentifierOwned,
+    signature: &BitString,
+) -> Result<()>
+where
+    VK: DecodePublicKey + Verifier<S>,
+    S: SignatureEncoding,
+{
+    // Key algorithms used with the signature algorithm, if it's known
+    let key_algorithms: &[ObjectIdentifier] = match signature_algorithm.oid {
+        SHA_1_WITH_RSA_ENCRYPTION
+        | SHA_224_WITH_RSA_ENCRYPTION
+        | SHA_256_WITH_RSA_ENCRYPTION
+        | SHA_384_WITH_RSA_ENCRYPTION
+        | SHA_512_WITH_RSA_ENCRYPTION => &[RSA_ENCRYPTION],
+        ID_RSASSA_PSS => &[RSA_ENCRYPTION, ID_RSASSA_PSS],
+        ECDSA_WITH_SHA_224 | ECDSA_WITH_SHA_256 | ECDSA_WITH_SHA_384 | ECDSA_WITH_SHA_512 => {
+            &[ID_EC_PUBLIC_KEY]
+        }
+        ID_ED_25519 => &[ID_ED_25519],
+        ID_ED_448 => &[ID_ED_448],
+        _ => &[],
+    };
+
+    if !key_algorithms.is_empty() && !key_algorithms.contains(&info.public_key.algorithm.oid) {
+        return Err(Error::KeyAlgorithmMismatch);
+    }
+
+    let signature = signature
+        .as_bytes()
+        .ok_or_else(|| der::Tag::BitString.value_error())?;
+
+    SignedData {
+        tbs,
+        signature_algorithm,
+        signature,
+    }
+    .verify_with::<VK, S>(&info.public_key)
+}
+
+/// A signed `TBSCertificate` and the signature over it.
+struct SignedData<'a> {
+    tbs: &'a [u8],
+    signature_algorithm: &'a AlgorithmIdentifierOwned,
+    signature: &'a [u8],
+}
+
+impl<'a> SignedData<'a> {
+    /// Check that the signature algorithm of `tbs_certificate`, whose
+    /// encoding is `tbs`, matches `signature_algorithm`.
+    fn new<P: Profile>(
+        tbs_certificate: &TbsCertificateInner<P>,
+        tbs: &'a [u8],
+        signature_algorithm: &'a AlgorithmIdentifierOwned,
+        signature: &'a BitString,
+    ) -> Result<Self> {
+        if tbs_certificate.signature != *signature_algorithm {
+            return Err(Error::AlgorithmMismatch);
+        }
+
+        let signature = signature
+            .as_bytes()
+            .ok_or_else(|| der::Tag::BitString.value_error())?;
+
+        Ok(Self {
+            tbs,
+            signature_algorithm,
+            signature,
+        })
+    }
+
+    /// Verify the signature with the algorithm selected from the
+    /// `signatureAlgorithm`.
+    fn verify(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()> {
+        #[cfg(any(
+            feature = "ed25519",
+            feature = "p256",
+            feature = "p384",
+            feature = "rsa"
+        ))]
+        let (issuer, tbs, signature) = (issuer.owned_to_ref(), self.tbs, self.signature);
+        #[cfg(not(any(
+            feature = "ed25519",
+            feature = "p256",
+            feature = "p384",
+            feature = "rsa"
+        )))]
+        let _ = issuer;
+
+        match self.signature_algorithm.oid {
+            #[cfg(feature = "rsa")]
+            SHA_256_WITH_RSA_ENCRYPTION => {
+                rsassa::verify_pkcs1v15::<sha2::Sha256>(issuer, tbs, signature)
+            }
+            #[cfg(feature = "rsa")]
+            SHA_384_WITH_RSA_ENCRYPTION => {
+                rsassa::verify_pkcs1v15::<sha2::Sha384>(issuer, tbs, signature)
+            }
+            #[cfg(feature = "rsa")]
+            SHA_512_WITH_RSA_ENCRYPTION => {
+                rsassa::verify_pkcs1v15::<sha2::Sha512>(issuer, tbs, signature)
+            }
+            #[cfg(feature = "rsa")]
+            ID_RSASSA_PSS => rsassa::verify_pss(
+                issuer,
+                self.signature_algorithm.parameters.as_ref(),
+                tbs,
+                signature,
+            ),
+            #[cfg(feature = "p256")]
+            ECDSA_WITH_SHA_256 => {
+                verify::<::p256::ecdsa::VerifyingKey, ::p256::ecdsa::DerSignature>(
+                    issuer, tbs, signature,
+                )
+            }
+            #[cfg(feature = "p384")]
+            ECDSA_WITH_SHA_384 => {
+                verify::<::p384::ecdsa::VerifyingKey, ::p384::ecdsa::DerSignature>(
+                    issuer, tbs, signature,
+                )
+            }
+            #[cfg(feature = "ed25519")]
+            ID_ED_25519 => verify::<ed25519::StrictVerifyingKey, ::ed25519_dalek::Signature>(
+                issuer, tbs, signature,
+            ),
+            oid => Err(Error::UnsupportedAlgorithm(oid)),
+        }
+    }
+
+    /// Verify the signature as `S` with the key decoded as `VK`.
+    fn verify_with<VK, S>(&self, issuer: &SubjectPublicKeyInfoOwned) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        let verifying_key = VK::from_public_key_der(&issuer.to_der()?)?;
+        let signature = S::try_from(self.signature).map_err(|_| signature::Error::new())?;
+
+        Ok(verifying_key.verify(self.tbs, &signature)?)
+    }
+}
+
+/// Verify `signature` over `msg` with `issuer` decoded as `VK`.
+#[cfg(any(
+    feature = "ed25519",
+    feature = "p256",
+    feature = "p384",
+    feature = "rsa"
+))]
+fn verify<VK, S>(issuer: SubjectPublicKeyInfoRef<'_>, msg: &[u8], signature: &[u8]) -> Result<()>
+where
+    VK: for<'a> TryFrom<SubjectPublicKeyInfoRef<'a>, Error = spki::Error> + Verifier<S>,
+    S: SignatureEncoding,
+{
+    let verifying_key = VK::try_from(issuer)?;
+    let signature = S::try_from(signature).map_err(|_| signature::Error::new())?;
+
+    Ok(verifying_key.verify(msg, &signature)?)
+}
+
+#[cfg(feature = "rsa")]
+mod rsassa {
+    use super::{verify, Error, Result};
+    use ::rsa::{
+        pkcs1::{DecodeRsaPublicKey, RsaPssParams},
+        pkcs1v15, pss, RsaPublicKey,
+    };
+    use const_oid::{
+        db::rfc5912::{ID_MGF_1, ID_RSASSA_PSS, ID_SHA_256, ID_SHA_384, ID_SHA_512},
+        AssociatedOid,
+    };
+    use der::Any;
+    use sha2::{digest::Digest, Sha256, Sha384, Sha512};
+    use signature::Verifier;
+    use spki::SubjectPublicKeyInfoRef;
+
+    /// Verify an RSASSA-PKCS1-v1_5 signature using the digest `D`.
+    pub(super) fn verify_pkcs1v15<D>(
+        issuer: SubjectPublicKeyInfoRef<'_>,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<()>
+    where
+        D: Digest + AssociatedOid,
+    {
+        verify::<pkcs1v15::VerifyingKey<D>, pkcs1v15::Signature>(issuer, msg, signature)
+    }
+
+    /// Verify an RSASSA-PSS signature using the hash function and salt length
+    /// from `parameters`.
+    ///
+    /// Only MGF1 with the same hash function as the signature is supported.
+    pub(super) fn verify_pss(
+        issuer: SubjectPublicKeyInfoRef<'_>,
+        parameters: Option<&Any>,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        let params = parameters
+            .map(|params| params.decode_as::<RsaPssParams<'_>>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let mgf_hash = params.mask_gen.parameters.as_ref().map(|hash| hash.oid);
+        if params.mask_gen.oid != ID_MGF_1 || mgf_hash != Some(params.hash.oid) {
+            return Err(Error::UnsupportedAlgorithm(ID_RSASSA_PSS));
+        }
+
+        // The key may be identified either by `rsaEncryption` or by
+        // `id-RSASSA-PSS`, but its encoding is the same in both cases.
+        let key = RsaPublicKey::from_pkcs1_der(issuer.subject_public_key.raw_bytes())
+            .map_err(|_| spki::Error::KeyMalformed)?;
+        let salt_len = usize::from(params.salt_len);
+        let signature = pss::Signature::try_from(signature)?;
+
+        match params.hash.oid {
+            ID_SHA_256 => pss::VerifyingKey::<Sha256>::new_with_salt_len(key, salt_len)
+                .verify(msg, &signature)?,
+            ID_SHA_384 => pss::VerifyingKey::<Sha384>::new_with_salt_len(key, salt_len)
+                .verify(msg, &signature)?,
+            ID_SHA_512 => pss::VerifyingKey::<Sha512>::new_with_salt_len(key, salt_len)
+                .verify(msg, &signature)?,
+            _ => return Err(Error::UnsupportedAlgorithm(ID_RSASSA_PSS)),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ed25519")]
+mod ed25519 {
+    use ed25519_dalek::{Signature, VerifyingKey};
+    use signature::Verifier;
+    use spki::SubjectPublicKeyInfoRef;
+
+    /// Ed25519 verifying key which rejects non-canonical signatures and keys
+    /// of small order.
+    pub(super) struct StrictVerifyingKey(VerifyingKey);
+
+    impl TryFrom<SubjectPublicKeyInfoRef<'_>> for StrictVerifyingKey {
+        type Error = spki::Error;
+
+        fn try_from(spki: SubjectPublicKeyInfoRef<'_>) -> spki::Result<Self> {
+            VerifyingKey::try_from(spki).map(Self)
+        }
+    }
+
+    impl Verifier<Signature> for StrictVerifyingKey {
+        fn verify(&self, msg: &[u8], signature: &Signature) -> signature::Result<()> {
+            self.0.verify_strict(msg, signature)
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Round-trip checks for `Arbitrary` implementations
+
+use arbitrary::{Arbitrary, Unstructured};
+use der::{Decode, Encode};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Number of values of each type generated by [`round_trip`]
+const ITERATIONS: u64 = 256;
+
+/// Generates values of `T` from seeded random data, and checks that those
+/// which can be encoded decode to the same value.
+///
+/// Panics unless at least `min_round_trips` of the generated values were
+/// encoded, so that the check can't pass vacuously.
+pub fn round_trip<T>(min_round_trips: usize)
+where
+    T: for<'a> Arbitrary<'a>
+        + for<'a> Decode<'a, Error = der::Error>
+        + Encode
+        + Eq
+        + core::fmt::Debug,
+{
+    let mut data = vec![0u8; 4096];
+    let mut round_trips = 0;
+
+    for seed in 0..ITERATIONS {
+        StdRng::seed_from_u64(seed).fill_bytes(&mut data);
+
+        let value = match T::arbitrary(&mut Unstructured::new(&data)) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        if let Ok(encoded) = value.to_der() {
+            assert_eq!(T::from_der(&encoded).unwrap(), value, "seed {}", seed);
+            round_trips += 1;
+        }
+    }
+
+    assert!(
+        round_trips >= min_round_trips,
+        "only {} of {} values round-tripped",
+        round_trips,
+        ITERATIONS
+    );
+}
@@ -1,2 +1,4 @@
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 pub mod openssl;
 pub mod zlint;
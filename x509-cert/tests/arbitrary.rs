@@ -0,0 +1,26 @@
+//! Tests for the `Arbitrary` implementations
+
+#![cfg(feature = "arbitrary")]
+
+use x509_cert::{crl::CertificateList, name::Name, request::CertReq, Certificate};
+use x509_cert_test_support::arbitrary::round_trip;
+
+#[test]
+fn arbitrary_name() {
+    round_trip::<Name>(64);
+}
+
+#[test]
+fn arbitrary_certificate() {
+    round_trip::<Certificate>(64);
+}
+
+#[test]
+fn arbitrary_certificate_list() {
+    round_trip::<CertificateList>(64);
+}
+
+#[test]
+fn arbitrary_certificate_request() {
+    round_trip::<CertReq>(64);
+}
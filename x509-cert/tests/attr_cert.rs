@@ -0,0 +1,139 @@
+//! Attribute certificate tests
+
+use der::{
+    asn1::{Any, AnyRef, BitString, GeneralizedTime, Ia5String, ObjectIdentifier, SetOfVec},
+    Decode, Encode,
+};
+use hex_literal::hex;
+use spki::AlgorithmIdentifierOwned;
+use std::{str::FromStr, time::Duration};
+use x509_cert::{
+    attr::Attribute, attr_cert::*, ext::pkix::name::GeneralName, name::Name,
+    serial_number::SerialNumber,
+};
+
+const POLICY_ID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3");
+
+#[test]
+fn role_syntax() {
+    let der = hex!("3009A107860575726E3A78");
+    let role = RoleSyntax::from_der(&der).unwrap();
+    assert!(role.role_authority.is_none());
+    assert_eq!(
+        role.role_name,
+        GeneralName::UniformResourceIdentifier(Ia5String::new("urn:x").unwrap())
+    );
+    assert_eq!(role.to_der().unwrap(), der);
+}
+
+#[test]
+fn clearance() {
+    // `classList` defaults to `unclassified`
+    let der = hex!("300406022A03");
+    let clearance = Clearance::from_der(&der).unwrap();
+    assert_eq!(clearance.class_list, ClassList::from(Classes::Unclassified));
+    assert_eq!(clearance.to_der().unwrap(), der);
+
+    let der = hex!("300806022A0303020308");
+    let clearance = Clearance::from_der(&der).unwrap();
+    assert_eq!(clearance.policy_id, POLICY_ID);
+    assert_eq!(clearance.class_list, ClassList::from(Classes::Secret));
+    assert!(clearance.security_categories.is_none());
+    assert_eq!(clearance.to_der().unwrap(), der);
+}
+
+#[test]
+fn attribute_certificate_round_trip() {
+    let issuer = Name::from_str("CN=Attribute Authority,O=Acme,C=US").unwrap();
+    let holder = Name::from_str("CN=Holder,O=Acme,C=US").unwrap();
+    let not_before =
+        GeneralizedTime::from_unix_duration(Duration::from_secs(1_700_000_000)).unwrap();
+    let not_after =
+        GeneralizedTime::from_unix_duration(Duration::from_secs(1_800_000_000)).unwrap();
+    let signature_algorithm = AlgorithmIdentifierOwned {
+        oid: ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2"),
+        parameters: None,
+    };
+
+    let role = RoleSyntax {
+        role_authority: None,
+        role_name: GeneralName::UniformResourceIdentifier(Ia5String::new("urn:admin").unwrap()),
+    };
+    let group = IetfAttrSyntax {
+        policy_authority: None,
+        values: vec![IetfAttrValue::String("engineering".into())],
+    };
+
+    let acinfo = AttributeCertificateInfo {
+        version: AttCertVersion::V2,
+        holder: Holder {
+            base_certificate_id: Some(IssuerSerial {
+                issuer: vec![GeneralName::DirectoryName(issuer.clone())],
+                serial: SerialNumber::from(42u32),
+                issuer_uid: None,
+            }),
+            entity_name: Some(vec![GeneralName::DirectoryName(holder)]),
+            object_digest_info: None,
+        },
+        issuer: AttCertIssuer::V2Form(V2Form {
+            issuer_name: Some(vec![GeneralName::DirectoryName(issuer)]),
+            ..Default::default()
+        }),
+        signature: signature_algorithm.clone(),
+        serial_number: SerialNumber::from(1u32),
+        attr_cert_validity_period: AttCertValidityPeriod {
+            not_before_time: not_before,
+            not_after_time: not_after,
+        },
+        attributes: vec![
+            Attribute {
+                oid: ID_ACA_GROUP,
+                values: SetOfVec::try_from(vec![Any::encode_from(&group).unwrap()]).unwrap(),
+            },
+            Attribute {
+                oid: ID_AT_CLEARANCE,
+                values: SetOfVec::try_from(vec![Any::encode_from(&Clearance {
+                    policy_id: POLICY_ID,
+                    class_list: Classes::Confidential | Classes::Secret,
+                    security_categories: None,
+                })
+                .unwrap()])
+                .unwrap(),
+            },
+            Attribute {
+                oid: <RoleSyntax as const_oid::AssociatedOid>::OID,
+                values: SetOfVec::try_from(vec![Any::encode_from(&role).unwrap()]).unwrap(),
+            },
+        ],
+        issuer_unique_id: None,
+        extensions: None,
+    };
+
+    let cert = AttributeCertificate {
+        acinfo,
+        signature_algorithm,
+        signature: BitString::from_bytes(&[0u8; 8]).unwrap(),
+    };
+
+    let der = cert.to_der().unwrap();
+    let decoded = AttributeCertificate::from_der(&der).unwrap();
+    assert_eq!(decoded, cert);
+
+    // The holder's `baseCertificateID` and the `v2Form` issuer are both
+    // implicitly tagged `[0]`
+    let holder = decoded.acinfo.holder.to_der().unwrap();
+    assert_eq!(AnyRef::from_der(&holder).unwrap().value()[0], 0xA0);
+    let issuer = decoded.acinfo.issuer.to_der().unwrap();
+    assert_eq!(issuer[0], 0xA0);
+
+    let role: RoleSyntax = decoded.acinfo.attributes[2]
+        .values
+        .get(0)
+        .unwrap()
+        .decode_as()
+        .unwrap();
+    assert_eq!(
+        role.role_name,
+        GeneralName::UniformResourceIdentifier(Ia5String::new("urn:admin").unwrap())
+    );
+}
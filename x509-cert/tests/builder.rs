@@ -1,6 +1,11 @@
 #![cfg(all(feature = "builder", feature = "pem"))]
 
-use der::{asn1::PrintableString, pem::LineEnding, Decode, Encode, EncodePem};
+use const_oid::AssociatedOid;
+use der::{
+    asn1::{PrintableString, Uint},
+    pem::LineEnding,
+    Decode, Encode, EncodePem,
+};
 use p256::{ecdsa::DerSignature, pkcs8::DecodePrivateKey, NistP256};
 use rand::rngs::OsRng;
 use rsa::pkcs1::DecodeRsaPrivateKey;
@@ -9,15 +14,20 @@ use sha2::Sha256;
 use spki::SubjectPublicKeyInfoOwned;
 use std::{str::FromStr, time::Duration};
 use x509_cert::{
-    builder::{AsyncBuilder, Builder, CertificateBuilder, Profile, RequestBuilder},
+    builder::{
+        rfc8410_public_key, AsyncBuilder, Builder, CertificateBuilder, CertificateListBuilder,
+        Error, ExtensionsBuilder, Profile, RequestBuilder,
+    },
+    crl::CertificateList,
     ext::pkix::{
         name::{DirectoryString, GeneralName},
-        SubjectAltName,
+        AuthorityKeyIdentifier, BasicConstraints, CrlNumber, CrlReason, KeyUsage, KeyUsages,
+        SubjectAltName, SubjectKeyIdentifier,
     },
     name::Name,
     request,
     serial_number::SerialNumber,
-    time::Validity,
+    time::{Time, Validity},
 };
 use x509_cert_test_support::{openssl, zlint};
 
@@ -50,6 +60,58 @@ fn root_ca_certificate() {
     zlint::check_certificate(pem.as_bytes(), ignored);
 }
 
+#[test]
+fn key_identifiers_from_owned_spki() {
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+    let subject = Name::from_str("CN=Key identifiers,O=Acme,C=US").unwrap();
+    let pub_key =
+        SubjectPublicKeyInfoOwned::try_from(RSA_2048_DER_EXAMPLE).expect("get rsa pub key");
+
+    let signer = rsa_signer();
+    let builder = CertificateBuilder::new(Profile::Root, serial_number, validity, subject, pub_key)
+        .expect("Create certificate");
+    let certificate = builder.build(&signer).unwrap();
+    let spki = &certificate.tbs_certificate.subject_public_key_info;
+
+    let (_, skid) = certificate
+        .tbs_certificate
+        .get::<SubjectKeyIdentifier>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(SubjectKeyIdentifier::try_from(spki).unwrap(), skid);
+
+    // Self-signed, so the authority key identifier matches the subject's
+    let akid = AuthorityKeyIdentifier::try_from(spki).unwrap();
+    assert_eq!(akid.key_identifier, Some(skid.0));
+}
+
+#[test]
+fn duplicate_default_extension() {
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+    let subject = Name::from_str("CN=Duplicate extensions,O=Acme,C=US").unwrap();
+    let pub_key =
+        SubjectPublicKeyInfoOwned::try_from(RSA_2048_DER_EXAMPLE).expect("get rsa pub key");
+
+    let signer = rsa_signer();
+    let mut builder =
+        CertificateBuilder::new(Profile::Root, serial_number, validity, subject, pub_key)
+            .expect("Create certificate");
+
+    // The root profile adds basic constraints itself
+    builder
+        .add_extension(&BasicConstraints {
+            ca: true,
+            path_len_constraint: None,
+        })
+        .unwrap();
+    assert!(matches!(
+        builder.build(&signer),
+        Err(Error::DuplicateExtension(oid)) if oid == BasicConstraints::OID
+    ));
+}
+
 #[test]
 fn root_ca_certificate_ecdsa() {
     let serial_number = SerialNumber::from(42u32);
@@ -267,6 +329,61 @@ fn ecdsa_signer() -> ecdsa::SigningKey<NistP256> {
     ecdsa::SigningKey::from(secret_key)
 }
 
+#[test]
+fn rfc8410_certificate() {
+    use const_oid::db::rfc8410::{ID_ED_448, ID_X_25519};
+
+    let pub_key = rfc8410_public_key(ID_X_25519, &[0x42; 32]).unwrap();
+    assert_eq!(
+        pub_key.algorithm.to_der().unwrap(),
+        [0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e]
+    );
+    assert!(rfc8410_public_key(ID_ED_448, &[0x42; 32]).is_err());
+    assert!(rfc8410_public_key(const_oid::db::rfc5912::ID_EC_PUBLIC_KEY, &[0x42; 32]).is_err());
+
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+    let issuer =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let profile = Profile::Leaf {
+        issuer,
+        enable_key_agreement: false,
+        enable_key_encipherment: true,
+        #[cfg(feature = "hazmat")]
+        include_subject_key_identifier: true,
+    };
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+
+    // X25519 keys only get the keyAgreement key usage
+    let signer = ecdsa_signer();
+    let builder = CertificateBuilder::new(
+        profile.clone(),
+        serial_number.clone(),
+        validity,
+        subject.clone(),
+        pub_key.clone(),
+    )
+    .expect("Create certificate");
+    let certificate = builder.build::<_, DerSignature>(&signer).unwrap();
+    let tbs = &certificate.tbs_certificate;
+    assert_eq!(tbs.subject_public_key_info, pub_key);
+    assert_eq!(
+        tbs.get::<KeyUsage>().unwrap().unwrap().1,
+        KeyUsage(KeyUsages::KeyAgreement.into())
+    );
+
+    let pem = certificate.to_pem(LineEnding::LF).expect("generate pem");
+    println!("{}", openssl::check_certificate(pem.as_bytes()));
+
+    // NULL parameters are invalid
+    let mut pub_key = pub_key;
+    pub_key.algorithm.parameters = Some(der::Any::null());
+    assert!(matches!(
+        CertificateBuilder::new(profile, serial_number, validity, subject, pub_key),
+        Err(Error::AlgorithmParameters(oid)) if oid == ID_X_25519
+    ));
+}
+
 #[test]
 fn certificate_request() {
     use std::net::{IpAddr, Ipv4Addr};
@@ -281,6 +398,11 @@ fn certificate_request() {
         .unwrap();
 
     let cert_req = builder.build::<_, DerSignature>(&signer).unwrap();
+    #[cfg(feature = "verify")]
+    cert_req
+        .verify::<p256::ecdsa::VerifyingKey, DerSignature>()
+        .expect("verify certificate request");
+
     let pem = cert_req.to_pem(LineEnding::LF).expect("generate pem");
     use std::fs::File;
     use std::io::Write;
@@ -289,6 +411,47 @@ fn certificate_request() {
     println!("{}", openssl::check_request(pem.as_bytes()));
 }
 
+#[test]
+fn extensions_builder() {
+    use std::net::{IpAddr, Ipv4Addr};
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let san = SubjectAltName(vec![GeneralName::from(IpAddr::V4(Ipv4Addr::new(
+        192, 0, 2, 0,
+    )))]);
+
+    let mut extensions = ExtensionsBuilder::new(subject.clone());
+    extensions
+        .add(&KeyUsage(KeyUsages::DigitalSignature.into()))
+        .unwrap();
+    extensions.add(&san).unwrap();
+    assert!(matches!(
+        extensions.add_with_criticality(&san, true),
+        Err(Error::DuplicateExtension(oid)) if oid == SubjectAltName::OID
+    ));
+
+    let extensions = extensions.build();
+    assert_eq!(extensions.len(), 2);
+    assert!(extensions[0].critical);
+    assert!(!extensions[1].critical);
+
+    let signer = ecdsa_signer();
+    let mut builder = RequestBuilder::new(subject).expect("Create certificate request");
+    builder.add_extensions(extensions.clone()).unwrap();
+    assert!(builder.add_extension(&san).is_err());
+
+    let cert_req = builder.build::<_, DerSignature>(&signer).unwrap();
+    let attribute = cert_req.info.attributes.get(0).unwrap();
+    let extension_req =
+        request::ExtensionReq::from_der(&attribute.values.get(0).unwrap().to_der().unwrap())
+            .unwrap();
+    assert_eq!(extension_req.0, extensions);
+
+    // Duplicates of extensions which are already present are rejected
+    let mut builder = RequestBuilder::new(Name::default()).unwrap();
+    builder.add_extension(&san).unwrap();
+    assert!(builder.add_extensions(extensions).is_err());
+}
+
 #[test]
 fn certificate_request_attributes() {
     let subject = Name::from_str("CN=service.domination.world").unwrap();
@@ -313,6 +476,85 @@ fn certificate_request_attributes() {
     println!("{}", openssl::check_request(pem.as_bytes()));
 }
 
+#[test]
+fn certificate_list() {
+    let issuer =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let validity = Validity::from_now(Duration::new(86400, 0)).unwrap();
+    let crl_number = CrlNumber(Uint::new(&[7]).unwrap());
+
+    let signer = ecdsa_signer();
+    let mut builder = CertificateListBuilder::new(
+        issuer.clone(),
+        validity.not_before,
+        Some(validity.not_after),
+        crl_number.clone(),
+    )
+    .expect("Create CRL builder");
+
+    builder
+        .add_revoked_certificate(
+            SerialNumber::from(42u32),
+            validity.not_before,
+            Some(CrlReason::KeyCompromise),
+        )
+        .unwrap();
+    builder
+        .add_revoked_certificate(SerialNumber::from(43u32), validity.not_before, None)
+        .unwrap();
+
+    let crl = builder.build::<_, DerSignature>(&signer).unwrap();
+    let crl = CertificateList::from_der(&crl.to_der().unwrap()).unwrap();
+    let tbs = &crl.tbs_cert_list;
+
+    assert_eq!(tbs.version, x509_cert::Version::V2);
+    assert_eq!(tbs.issuer, issuer);
+    assert_eq!(
+        tbs.next_update.map(|time| time.to_date_time()),
+        Some(validity.not_after.to_date_time())
+    );
+
+    // Dates through 2049 are encoded as `UTCTime`
+    assert!(matches!(tbs.this_update, Time::UtcTime(_)));
+    assert_eq!(crl.signature_algorithm, tbs.signature);
+
+    let revoked = tbs.revoked_certificates.as_ref().unwrap();
+    assert_eq!(revoked.len(), 2);
+    assert_eq!(revoked[0].serial_number, SerialNumber::from(42u32));
+
+    let entry_extensions = revoked[0].crl_entry_extensions.as_ref().unwrap();
+    assert_eq!(entry_extensions.len(), 1);
+    assert_eq!(entry_extensions[0].extn_id, CrlReason::OID);
+    assert_eq!(
+        CrlReason::from_der(entry_extensions[0].extn_value.as_bytes()).unwrap(),
+        CrlReason::KeyCompromise
+    );
+    assert_eq!(revoked[1].crl_entry_extensions, None);
+
+    let extensions = tbs.crl_extensions.as_ref().unwrap();
+    assert_eq!(extensions.len(), 2);
+    assert_eq!(extensions[0].extn_id, AuthorityKeyIdentifier::OID);
+    assert_eq!(extensions[1].extn_id, CrlNumber::OID);
+    assert_eq!(
+        CrlNumber::from_der(extensions[1].extn_value.as_bytes()).unwrap(),
+        crl_number
+    );
+
+    // The CRL number is added by the builder itself
+    let mut builder = CertificateListBuilder::new(
+        issuer,
+        validity.not_before,
+        Some(validity.not_after),
+        crl_number.clone(),
+    )
+    .expect("Create CRL builder");
+    builder.add_extension(&crl_number).unwrap();
+    assert!(matches!(
+        builder.build::<_, DerSignature>(&signer),
+        Err(Error::DuplicateExtension(oid)) if oid == CrlNumber::OID
+    ));
+}
+
 #[test]
 fn dynamic_signer() {
     let subject = Name::from_str("CN=Test").expect("parse common name");
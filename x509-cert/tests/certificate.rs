@@ -441,14 +441,221 @@ fn decode_cert_overlength_serial_number() {
     assert_eq!(pem_encoded_cert, reencoded.as_bytes());
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn hostname_and_ip_matching() {
+    use const_oid::AssociatedOid;
+    use x509_cert::ext::{
+        pkix::{name::GeneralName, SubjectAltName},
+        AsExtension,
+    };
+
+    let mut cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+
+    assert!(cert.matches_hostname("www.amazon.com"));
+    assert!(cert.matches_hostname("WWW.Amazon.COM."));
+    assert!(cert.matches_hostname("x.peg.a2z.com"));
+    assert!(!cert.matches_hostname("peg.a2z.com"));
+    assert!(!cert.matches_hostname("a.b.peg.a2z.com"));
+    assert!(!cert.matches_hostname("amazon.com.evil.com"));
+    assert!(!cert.matches_hostname(""));
+
+    // The common name is only used without `dNSName` SANs
+    let subject = cert.tbs_certificate.subject.clone();
+    cert.tbs_certificate.subject = "CN=legacy.example.com".parse().unwrap();
+    assert!(!cert.matches_hostname("legacy.example.com"));
+
+    let ip = "192.0.2.1".parse::<std::net::IpAddr>().unwrap();
+    let san = SubjectAltName(vec![GeneralName::from(ip)])
+        .to_extension(&subject, &[])
+        .unwrap();
+    let extensions = cert.tbs_certificate.extensions.as_mut().unwrap();
+    extensions.retain(|e| e.extn_id != SubjectAltName::OID);
+    extensions.push(san);
+
+    assert!(cert.matches_hostname("legacy.example.com"));
+    assert!(!cert.matches_hostname("www.amazon.com"));
+    assert!(cert.matches_ip(ip));
+    assert!(!cert.matches_ip("192.0.2.2".parse().unwrap()));
+
+    // IP address hostnames are only matched against `iPAddress` SANs
+    assert!(cert.matches_hostname("192.0.2.1"));
+    assert!(!cert.matches_hostname("192.0.2.2"));
+    let dns = der::asn1::Ia5String::new("192.0.2.2").unwrap();
+    let san = SubjectAltName(vec![GeneralName::DnsName(dns)])
+        .to_extension(&subject, &[])
+        .unwrap();
+    let extensions = cert.tbs_certificate.extensions.as_mut().unwrap();
+    extensions.retain(|e| e.extn_id != SubjectAltName::OID);
+    extensions.push(san);
+    assert!(!cert.matches_hostname("192.0.2.2"));
+    assert!(!cert.matches_hostname("192.0.2.2."));
+
+    // Partial-label wildcards are not supported
+    cert.tbs_certificate.subject = "CN=w*.example.com".parse().unwrap();
+    assert!(!cert.matches_hostname("www.example.com"));
+    cert.tbs_certificate.subject = "CN=*.com".parse().unwrap();
+    assert!(!cert.matches_hostname("example.com"));
+}
+
+#[test]
+fn certificate_owns_its_data() {
+    fn assert_owned<T: 'static + Clone + Send + Sync>(value: T) -> T {
+        value
+    }
+
+    // The decoded certificate outlives the buffer it was decoded from
+    let cert = {
+        let der = include_bytes!("examples/amazon.der").to_vec();
+        assert_owned(Certificate::from_der(&der).unwrap())
+    };
+    assert_owned(cert.tbs_certificate.subject.clone());
+    assert_owned(cert.tbs_certificate.extensions.clone());
+    assert_eq!(
+        cert.to_der().unwrap(),
+        include_bytes!("examples/amazon.der")
+    );
+}
+
+#[cfg(feature = "fingerprint")]
+#[test]
+fn fingerprint_and_key_identifiers() {
+    use der::referenced::OwnedToRef;
+    use sha2::Sha256;
+    use x509_cert::ext::pkix::{KeyIdentifierMethod, SubjectKeyIdentifier};
+
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    assert_eq!(
+        cert.fingerprint::<Sha256>().unwrap().as_slice(),
+        hex!("5bf3d7e0e6927f773d5106c822c53f6f52c199f7eb1b3b8154b41f2924391c75")
+    );
+
+    let spki = cert.tbs_certificate.subject_public_key_info.owned_to_ref();
+    let sha1 = KeyIdentifierMethod::Sha1.compute(spki.clone()).unwrap();
+    assert_eq!(
+        sha1.as_bytes(),
+        hex!("9545143E3A401E9516F082AC457382586D9BF074")
+    );
+    let truncated = KeyIdentifierMethod::TruncatedSha1
+        .compute(spki.clone())
+        .unwrap();
+    assert_eq!(truncated.as_bytes(), hex!("457382586D9BF074"));
+
+    // The subject key identifier of the certificate uses the first method
+    let (_, skid) = cert
+        .tbs_certificate
+        .get::<SubjectKeyIdentifier>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        KeyIdentifierMethod::identify(skid.0.as_bytes(), spki.clone()),
+        Some(KeyIdentifierMethod::Sha1)
+    );
+    assert_eq!(
+        KeyIdentifierMethod::identify(truncated.as_bytes(), spki.clone()),
+        Some(KeyIdentifierMethod::TruncatedSha1)
+    );
+    assert_eq!(KeyIdentifierMethod::identify(&[0u8; 20], spki), None);
+}
+
 #[cfg(feature = "pem")]
 #[test]
 fn load_certificate_chains() {
+    use der::{pem::LineEnding, EncodePem};
+
     let pem_encoded_chain = include_bytes!("examples/crates.io-chain.pem");
 
     let chain = Certificate::load_pem_chain(pem_encoded_chain).expect("parse certificate chain");
 
     assert_eq!(chain.len(), 4, "4 certificates are expected in this chain");
+
+    // Round trip through the chain serializer
+    let reencoded = Certificate::to_pem_chain(&chain, LineEnding::LF).unwrap();
+    assert_eq!(
+        Certificate::load_pem_chain(reencoded.as_bytes()).unwrap(),
+        chain
+    );
+
+    // Text and non-certificate blocks between and after the certificates are
+    // skipped
+    let mut mixed = String::from("subject=CN = crates.io\n");
+    mixed.push_str(&chain[0].to_pem(LineEnding::LF).unwrap());
+    mixed.push_str("\n-----BEGIN X509 CRL-----\nAAAA\n-----END X509 CRL-----\n\n");
+    mixed.push_str(&chain[1].to_pem(LineEnding::CRLF).unwrap());
+    mixed.push_str("trailing text");
+    assert_eq!(
+        Certificate::load_pem_chain(mixed.as_bytes()).unwrap(),
+        chain[..2]
+    );
+
+    assert!(Certificate::load_pem_chain(b"").unwrap().is_empty());
+    assert!(Certificate::load_pem_chain(b"\n\n").unwrap().is_empty());
+
+    // Truncated chain
+    let truncated = &reencoded[..reencoded.len() - 10];
+    assert!(Certificate::load_pem_chain(truncated.as_bytes()).is_err());
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn certificate_chaining() {
+    use x509_cert::ext::pkix::{name::GeneralName, AuthorityKeyIdentifier};
+
+    let chain =
+        Certificate::load_pem_chain(include_bytes!("examples/crates.io-chain.pem")).unwrap();
+
+    for (cert, issuer) in chain.iter().zip(&chain[1..]) {
+        assert!(issuer.is_issuer_name_of(cert));
+        assert_eq!(issuer.is_authority_key_of(cert).unwrap(), Some(true));
+        assert!(issuer.can_sign_certificates().unwrap());
+        assert!(issuer.could_have_issued(cert).unwrap());
+
+        assert!(!cert.is_issuer_name_of(issuer));
+        assert_eq!(cert.is_authority_key_of(issuer).unwrap(), Some(false));
+        assert!(!cert.could_have_issued(issuer).unwrap());
+        assert!(!cert.is_self_issued());
+    }
+
+    // The end-entity certificate isn't a CA
+    assert!(!chain[0].can_sign_certificates().unwrap());
+    assert!(!chain[0].could_have_issued(&chain[0]).unwrap());
+
+    // Without an authority key identifier, only the names are compared
+    let cert = Certificate::from_der(include_bytes!("examples/rsa2048-crt.der")).unwrap();
+    assert!(cert.is_self_issued());
+    assert_eq!(cert.is_authority_key_of(&cert).unwrap(), None);
+    assert!(!cert.can_sign_certificates().unwrap());
+
+    // The issuer and serial number form of the authority key identifier
+    let with_aki = |issuer: &Certificate, serial_number| {
+        let aki = AuthorityKeyIdentifier {
+            key_identifier: None,
+            authority_cert_issuer: Some(vec![GeneralName::DirectoryName(
+                issuer.tbs_certificate.issuer.clone(),
+            )]),
+            authority_cert_serial_number: Some(serial_number),
+        };
+
+        let mut cert = chain[1].clone();
+        for ext in cert.tbs_certificate.extensions.iter_mut().flatten() {
+            if ext.extn_id == const_oid::db::rfc5280::ID_CE_AUTHORITY_KEY_IDENTIFIER {
+                ext.extn_value = der::asn1::OctetString::new(aki.to_der().unwrap()).unwrap();
+            }
+        }
+        cert
+    };
+
+    let issuer = &chain[2];
+    let cert = with_aki(issuer, issuer.tbs_certificate.serial_number.clone());
+    assert_eq!(issuer.is_authority_key_of(&cert).unwrap(), Some(true));
+    assert!(issuer.could_have_issued(&cert).unwrap());
+
+    let cert = with_aki(issuer, SerialNumber::from(1u8));
+    assert_eq!(issuer.is_authority_key_of(&cert).unwrap(), Some(false));
+    assert!(!issuer.could_have_issued(&cert).unwrap());
+
+    let cert = with_aki(&chain[1], issuer.tbs_certificate.serial_number.clone());
+    assert_eq!(issuer.is_authority_key_of(&cert).unwrap(), Some(false));
 }
 
 #[cfg(feature = "arbitrary")]
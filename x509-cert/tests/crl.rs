@@ -1,5 +1,18 @@
-use der::Decode;
-use x509_cert::crl::CertificateList;
+use const_oid::AssociatedOid;
+use core::time::Duration;
+use der::{
+    asn1::{GeneralizedTime, Uint},
+    Decode,
+};
+use hex_literal::hex;
+use x509_cert::crl::{CertificateList, CrlCertificates, CrlScope};
+use x509_cert::ext::pkix::crl::dp::Reasons;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::{
+    BaseCrlNumber, CertificateIssuer, CrlReason, InvalidityDate, IssuingDistributionPoint,
+};
+use x509_cert::ext::AsExtension;
+use x509_cert::name::Name;
 
 #[test]
 fn decode_crl() {
@@ -15,3 +28,115 @@ fn decode_crl() {
     assert_eq!(2, crl.tbs_cert_list.crl_extensions.unwrap().len());
     assert_eq!(4, crl.tbs_cert_list.revoked_certificates.unwrap().len());
 }
+
+#[test]
+fn classify_crl() {
+    let crl = CertificateList::from_der(include_bytes!("examples/GoodCACRL.crl")).unwrap();
+    let mut tbs = crl.tbs_cert_list;
+
+    assert!(!tbs.is_delta_crl());
+    assert!(tbs.base_crl_number().unwrap().is_none());
+    assert!(tbs.issuing_distribution_point().unwrap().is_none());
+    assert!(tbs.freshest_crl().unwrap().is_none());
+    assert_eq!(tbs.crl_number().unwrap().unwrap().0.as_bytes(), &[1u8][..]);
+    assert_eq!(tbs.scope().unwrap(), CrlScope::default());
+
+    // Turn it into a delta CRL for CA certificates and some reasons
+    let mut idp = IssuingDistributionPoint {
+        distribution_point: None,
+        only_contains_user_certs: false,
+        only_contains_ca_certs: true,
+        only_some_reasons: Some(Reasons::KeyCompromise | Reasons::CaCompromise),
+        indirect_crl: false,
+        only_contains_attribute_certs: false,
+    };
+    let issuer = tbs.issuer.clone();
+    let extensions = tbs.crl_extensions.as_mut().unwrap();
+    let extension = idp.to_extension(&issuer, extensions).unwrap();
+    assert_eq!(extension.extn_id.to_string(), "2.5.29.28");
+    extensions.push(extension);
+    let extension = BaseCrlNumber(Uint::new(&[1]).unwrap())
+        .to_extension(&issuer, extensions)
+        .unwrap();
+    extensions.push(extension);
+
+    assert!(tbs.is_delta_crl());
+    assert_eq!(tbs.issuing_distribution_point().unwrap(), Some(idp.clone()));
+    assert_eq!(
+        tbs.scope().unwrap(),
+        CrlScope {
+            certificates: CrlCertificates::Ca,
+            partitioned_by_reasons: true,
+            indirect: false,
+        }
+    );
+
+    // At most one of the `onlyContains*` flags may be set
+    idp.only_contains_user_certs = true;
+    let extensions = tbs.crl_extensions.as_mut().unwrap();
+    extensions.retain(|e| e.extn_id != IssuingDistributionPoint::OID);
+    let extension = idp.to_extension(&issuer, extensions).unwrap();
+    extensions.push(extension);
+    assert!(tbs.scope().is_err());
+}
+
+#[test]
+fn crl_entries() {
+    let crl = CertificateList::from_der(include_bytes!("examples/GoodCACRL.crl")).unwrap();
+    let mut tbs = crl.tbs_cert_list;
+
+    let aki = tbs.authority_key_identifier().unwrap().unwrap();
+    assert_eq!(
+        aki.key_identifier.unwrap().as_bytes(),
+        &hex!("580184241BBC2B52944A3DA510721451F5AF3AC9")[..]
+    );
+
+    let entries = tbs.revoked().collect::<der::Result<Vec<_>>>().unwrap();
+    assert_eq!(entries.len(), 2);
+    for (entry, serial) in entries.iter().zip([0x0e, 0x0f]) {
+        assert_eq!(entry.serial_number.as_bytes(), &[serial]);
+        assert_eq!(entry.reason, Some(CrlReason::KeyCompromise));
+        assert_eq!(entry.invalidity_date, None);
+        assert_eq!(entry.certificate_issuer, None);
+    }
+
+    // Entries with a certificateIssuer apply to the following entries
+    let other = GeneralName::DirectoryName("CN=Other CA".parse::<Name>().unwrap());
+    let invalidity_date = InvalidityDate(
+        GeneralizedTime::from_unix_duration(Duration::from_secs(1262334600)).unwrap(),
+    );
+    let issuer = tbs.issuer.clone();
+    let revoked = tbs.revoked_certificates.as_mut().unwrap();
+    let mut entry = revoked[0].clone();
+    let extensions = entry.crl_entry_extensions.as_mut().unwrap();
+    let extension = CertificateIssuer(vec![other.clone()])
+        .to_extension(&issuer, extensions)
+        .unwrap();
+    assert!(extension.critical);
+    extensions.push(extension);
+    let extension = invalidity_date.to_extension(&issuer, extensions).unwrap();
+    extensions.push(extension);
+    revoked.insert(1, entry);
+
+    let entries = tbs.revoked().collect::<der::Result<Vec<_>>>().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].certificate_issuer, None);
+    assert_eq!(entries[1].certificate_issuer, Some(vec![other.clone()]));
+    assert_eq!(entries[1].invalidity_date, Some(invalidity_date.0));
+    assert_eq!(entries[2].certificate_issuer, Some(vec![other]));
+    assert_eq!(entries[2].invalidity_date, None);
+
+    // Entries without a reasonCode extension have no reason, whether or not
+    // they carry other entry extensions
+    let crl = CertificateList::from_der(include_bytes!("examples/tscpbcasha256.crl")).unwrap();
+    let reasons = crl
+        .tbs_cert_list
+        .revoked()
+        .map(|entry| entry.map(|entry| entry.reason))
+        .collect::<der::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        reasons,
+        [None, Some(CrlReason::CessationOfOperation), None, None]
+    );
+}
@@ -1,6 +1,12 @@
-use x509_cert::ext::pkix::name::{GeneralName, GeneralNames};
+use x509_cert::ext::pkix::name::{
+    DistributionPointName, GeneralName, GeneralNames, HardwareModuleName, OtherName,
+    PermanentIdentifier, SmtpUtf8Mailbox, UserPrincipalName,
+};
 
-use der::{Decode, Encode};
+use der::{
+    asn1::{Ia5String, ObjectIdentifier, OctetString},
+    Any, Decode, Encode, Tag,
+};
 use hex_literal::hex;
 use rstest::rstest;
 
@@ -78,3 +84,119 @@ fn plural(#[case] idx: usize, #[case] value: &[u8]) {
     let encoded = decoded.to_der().unwrap();
     assert_eq!(value, encoded);
 }
+
+#[test]
+fn typed_other_names() {
+    let upn = UserPrincipalName("user@example.com".into());
+    let name = GeneralName::OtherName(OtherName::new(&upn).unwrap());
+    let decoded = GeneralName::from_der(&name.to_der().unwrap()).unwrap();
+    let other_name = match decoded {
+        GeneralName::OtherName(other_name) => other_name,
+        _ => panic!("expected otherName"),
+    };
+    assert_eq!(
+        other_name.decode_as::<UserPrincipalName>().unwrap(),
+        Some(upn)
+    );
+    assert_eq!(other_name.decode_as::<SmtpUtf8Mailbox>().unwrap(), None);
+
+    let mailbox = SmtpUtf8Mailbox("χρήστης@παράδειγμα.ελ".into());
+    let other_name = OtherName::new(&mailbox).unwrap();
+    assert_eq!(other_name.type_id.to_string(), "1.3.6.1.5.5.7.8.9");
+    assert_eq!(
+        other_name.decode_as::<SmtpUtf8Mailbox>().unwrap(),
+        Some(mailbox)
+    );
+
+    // SmtpUTF8Mailbox ::= UTF8String (SIZE (1..MAX))
+    assert!(OtherName::new(&SmtpUtf8Mailbox(String::new())).is_err());
+    let other_name = OtherName {
+        type_id: other_name.type_id,
+        value: Any::new(Tag::Utf8String, []).unwrap(),
+    };
+    assert!(other_name.decode_as::<SmtpUtf8Mailbox>().is_err());
+
+    // hwType 1.2.3, hwSerialNum 0102
+    let der = hex!("301606082B06010505070804A00A300806022A0304020102");
+    let other_name = OtherName::from_der(&der).unwrap();
+    let hw = other_name
+        .decode_as::<HardwareModuleName>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(hw.hw_type, ObjectIdentifier::new_unwrap("1.2.3"));
+    assert_eq!(hw.hw_serial_num.as_bytes(), &[1, 2]);
+    assert_eq!(OtherName::new(&hw).unwrap().to_der().unwrap(), der);
+
+    // The value must match the syntax associated with the OID
+    assert!(other_name
+        .decode_as::<PermanentIdentifier>()
+        .unwrap()
+        .is_none());
+    let other_name = OtherName {
+        type_id: ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.8.3"),
+        value: other_name.value,
+    };
+    assert!(other_name.decode_as::<PermanentIdentifier>().is_err());
+
+    let id = PermanentIdentifier {
+        identifier_value: Some("123456".into()),
+        assigner: None,
+    };
+    let other_name = OtherName::new(&id).unwrap();
+    assert_eq!(
+        other_name.decode_as::<PermanentIdentifier>().unwrap(),
+        Some(id)
+    );
+}
+
+#[test]
+fn general_name_matches() {
+    fn dns(name: &str) -> GeneralName {
+        GeneralName::DnsName(Ia5String::new(name).unwrap())
+    }
+    fn email(name: &str) -> GeneralName {
+        GeneralName::Rfc822Name(Ia5String::new(name).unwrap())
+    }
+    fn uri(name: &str) -> GeneralName {
+        GeneralName::UniformResourceIdentifier(Ia5String::new(name).unwrap())
+    }
+    fn ip(octets: &[u8]) -> GeneralName {
+        GeneralName::IpAddress(OctetString::new(octets).unwrap())
+    }
+    fn dn(name: &str) -> GeneralName {
+        GeneralName::DirectoryName(name.parse().unwrap())
+    }
+
+    assert!(dns("www.Example.COM").matches(&dns("www.example.com.")));
+    assert!(!dns("www.example.com").matches(&dns("example.com")));
+
+    assert!(email("user@Example.COM").matches(&email("user@example.com")));
+    assert!(!email("User@example.com").matches(&email("user@example.com")));
+    assert!(!email("example.com").matches(&email("user@example.com")));
+
+    assert!(uri("HTTP://User@Example.COM/%7euser/a%2fb")
+        .matches(&uri("http://User@example.com/~user/a%2Fb")));
+    assert!(!uri("http://user@example.com/").matches(&uri("http://User@example.com/")));
+    assert!(!uri("http://example.com/Path").matches(&uri("http://example.com/path")));
+    assert!(uri("urn:ISBN:0").matches(&uri("URN:ISBN:0")));
+
+    assert!(ip(&[192, 0, 2, 1]).matches(&ip(&[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 0, 2, 1
+    ])));
+    assert!(!ip(&[192, 0, 2, 1]).matches(&ip(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 192, 0, 2, 1])));
+
+    assert!(dn("CN=Example  CA,O=Acme").matches(&dn("CN=example ca,O=ACME")));
+    assert!(!dn("CN=Example CA").matches(&dns("Example CA")));
+
+    // Full distribution point names match if any of their names match
+    let full = DistributionPointName::FullName(vec![
+        uri("ldap://ldap.example.com/cn=CRL"),
+        uri("http://crl.example.com/ca.crl"),
+    ]);
+    assert!(full.matches(&DistributionPointName::FullName(vec![uri(
+        "HTTP://CRL.example.com/ca.crl"
+    )])));
+    assert!(!full.matches(&DistributionPointName::FullName(vec![uri(
+        "http://crl.example.com/other.crl"
+    )])));
+}
@@ -368,3 +368,96 @@ fn rdns_serde() {
         }
     }
 }
+
+#[test]
+fn rdns_parse() {
+    // The empty string is the empty sequence
+    assert!("".parse::<Name>().unwrap().is_empty());
+
+    // An escaped backslash does not escape the following separator
+    let name = r"CN=foo\\,O=bar".parse::<Name>().unwrap();
+    assert_eq!(name.0.len(), 2);
+    let name = r"CN=foo\\\,O=bar".parse::<Name>().unwrap();
+    assert_eq!(name.0.len(), 1);
+    assert_eq!(
+        name.0[0].0.get(0).unwrap().value,
+        Any::from(Utf8StringRef::new(r"foo\,O=bar").unwrap())
+    );
+
+    // Hex pairs may encode multi-byte UTF-8 characters
+    let name = r"CN=caf\C3\A9".parse::<Name>().unwrap();
+    assert_eq!(
+        name.0[0].0.get(0).unwrap().value,
+        Any::from(Utf8StringRef::new("café").unwrap())
+    );
+
+    // Per-attribute string types are validated
+    let name = "emailAddress=user@example.com".parse::<Name>().unwrap();
+    assert_eq!(name.0[0].0.get(0).unwrap().value.tag(), Tag::Ia5String);
+    assert!("C=U*".parse::<Name>().is_err());
+    assert!(r"CN=\FF".parse::<Name>().is_err());
+
+    // Dangling escapes are rejected
+    assert!(r"CN=foo\".parse::<Name>().is_err());
+    assert!(r"CN=foo\4".parse::<Name>().is_err());
+}
+
+#[test]
+fn rdns_display() {
+    let name = r"CN=caf\C3\A9+UID=x,O=Acme\, Inc.,C=US"
+        .parse::<Name>()
+        .unwrap();
+    assert_eq!(
+        name.to_rfc4514_string(),
+        r"CN=café+UID=x,O=Acme\, Inc.,C=US"
+    );
+    assert_eq!(name.to_rfc4514_string().parse::<Name>().unwrap(), name);
+    assert_eq!(
+        name.display_openssl().to_string(),
+        r#"C = US, O = "Acme, Inc.", CN = café + UID = x"#
+    );
+
+    // Values of known attribute types without a string representation are hex-encoded
+    let name = "CN=#04024869,1.2.3.4=foo".parse::<Name>().unwrap();
+    assert_eq!(name.to_rfc4514_string(), "CN=#04024869,1.2.3.4=#0c03666f6f");
+    assert_eq!(
+        name.display_openssl().to_string(),
+        "1.2.3.4 = foo, CN = #04024869"
+    );
+    assert_eq!(name.to_rfc4514_string().parse::<Name>().unwrap(), name);
+
+    // Control characters and backslashes
+    let name = r#"CN=a\\b\0dc\"d"#.parse::<Name>().unwrap();
+    assert_eq!(name.to_rfc4514_string(), r#"CN=a\\b\0dc\"d"#);
+    assert_eq!(name.display_openssl().to_string(), r#"CN = a\\b\0Dc\"d"#);
+}
+
+#[test]
+fn rdns_matches() {
+    let matches = |a: &str, b: &str| {
+        let (a, b) = (a.parse::<Name>().unwrap(), b.parse::<Name>().unwrap());
+        assert_eq!(a.matches(&b), b.matches(&a));
+        a.matches(&b)
+    };
+
+    assert!(matches("CN=Example  CA,O=Acme", "CN=example ca,O=ACME"));
+    assert!(matches("CN=caf\\C3\\89+UID=x", "UID=X+CN=café"));
+    assert!(matches("", ""));
+
+    // String types and surrounding whitespace are ignored
+    assert!(matches("CN=#13024142", "CN=ab"));
+    assert!(matches("CN=#0c03206120", "CN=a"));
+
+    // Other values are compared exactly
+    assert!(matches("CN=#04024869", "CN=#04024869"));
+    assert!(!matches("CN=#04024869", "CN=#04024868"));
+    assert!(!matches("CN=#04024869", "CN=Hi"));
+
+    assert!(!matches("CN=a", "UID=a"));
+    assert!(!matches("CN=a,O=b", "CN=a"));
+    assert!(!matches("CN=a+UID=b", "CN=a"));
+    assert!(!matches("CN=a+CN=A", "CN=a+CN=b"));
+    assert!(matches("CN=a+CN=b", "CN=B+CN=A"));
+    assert!(!matches("CN=a,O=b", "O=b,CN=a"));
+    assert!(!matches("CN=a b", "CN=ab"));
+}
@@ -358,7 +358,18 @@ fn decode_cert() {
                                             "1.3.6.1.5.5.7.2.2",
                                             pqi.policy_qualifier_id.to_string()
                                         );
-                                        // TODO VisibleString
+                                        let notice = match pqi.decode_qualifier().unwrap() {
+                                            Some(certpolicy::PolicyQualifier::UserNotice(
+                                                notice,
+                                            )) => notice,
+                                            _ => panic!("expected a user notice"),
+                                        };
+                                        assert!(notice.notice_ref.is_none());
+                                        let text = notice.explicit_text.unwrap();
+                                        assert_eq!(text.tag(), Tag::VisibleString);
+                                        assert!(text.to_string().starts_with(
+                                            "This is a test certificate. Do Not Rely."
+                                        ));
                                     }
                                     _ => unreachable!(),
                                 }
@@ -1216,3 +1227,628 @@ fn decode_idp() {
         err.kind()
     );
 }
+
+#[test]
+fn typed_extension_accessors() {
+    let cert = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+    let tbs = &cert.tbs_certificate;
+
+    let (critical, bc) = tbs.get::<BasicConstraints>().unwrap().unwrap();
+    assert!(critical);
+    assert!(bc.ca);
+
+    let (critical, ku) = tbs.get::<KeyUsage>().unwrap().unwrap();
+    assert!(critical);
+    assert!(ku.key_cert_sign());
+
+    assert!(tbs.get::<SubjectAltName>().unwrap().is_none());
+    assert_eq!(tbs.unrecognized_extensions().count(), 0);
+
+    // This cert includes a Netscape certificate type extension
+    let cert = Certificate::from_der(include_bytes!(
+        "examples/0954e2343dd5efe0a7f0967d69caf33e5f893720.der"
+    ))
+    .unwrap();
+    let unrecognized = cert
+        .tbs_certificate
+        .unrecognized_extensions()
+        .map(|ext| ext.extn_id.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(unrecognized, ["2.16.840.1.113730.1.1"]);
+}
+
+#[test]
+fn qc_statements() {
+    use der::{
+        asn1::{Ia5String, PrintableString},
+        Any,
+    };
+    use x509_cert::ext::pkix::qcstatements::*;
+
+    let retention = QcRetentionPeriod(15);
+    let pds = PdsLocations(vec![PdsLocation {
+        url: Ia5String::new("https://example.com/pds").unwrap(),
+        language: PrintableString::new("en").unwrap(),
+    }]);
+    let limit = MonetaryValue {
+        currency: Iso4217CurrencyCode::Alphabetic(PrintableString::new("EUR").unwrap()),
+        amount: 100,
+        exponent: 3,
+    };
+
+    let statements = QcStatements(vec![
+        QcStatement {
+            statement_id: ID_ETSI_QCS_QC_COMPLIANCE,
+            statement_info: None,
+        },
+        QcStatement {
+            statement_id: ID_ETSI_QCS_QC_TYPE,
+            statement_info: Some(Any::encode_from(&QcType(vec![ID_ETSI_QCT_ESIGN])).unwrap()),
+        },
+        QcStatement {
+            statement_id: ID_ETSI_QCS_QC_RETENTION_PERIOD,
+            statement_info: Some(Any::encode_from(&retention).unwrap()),
+        },
+        QcStatement {
+            statement_id: ID_ETSI_QCS_QC_PDS,
+            statement_info: Some(Any::encode_from(&pds).unwrap()),
+        },
+        QcStatement {
+            statement_id: ID_ETSI_QCS_QC_LIMIT_VALUE,
+            statement_info: Some(Any::encode_from(&limit).unwrap()),
+        },
+    ]);
+
+    let der = statements.to_der().unwrap();
+    let statements = QcStatements::from_der(&der).unwrap();
+
+    assert!(statements.is_qc_compliant());
+    assert!(!statements.contains(ID_ETSI_QCS_QC_SSCD));
+    assert_eq!(
+        statements.get::<QcType>().unwrap(),
+        Some(QcType(vec![ID_ETSI_QCT_ESIGN]))
+    );
+    assert_eq!(
+        statements.get::<QcRetentionPeriod>().unwrap(),
+        Some(retention)
+    );
+    assert_eq!(statements.get::<PdsLocations>().unwrap(), Some(pds));
+    assert_eq!(statements.get::<MonetaryValue>().unwrap(), Some(limit));
+    assert_eq!(statements.get::<SemanticsInformation>().unwrap(), None);
+
+    // A statement lacking the statementInfo of its type is invalid
+    let statements = QcStatements(vec![QcStatement {
+        statement_id: ID_ETSI_QCS_QC_TYPE,
+        statement_info: None,
+    }]);
+    assert_eq!(
+        statements.get::<QcType>().unwrap_err().kind(),
+        ErrorKind::Value { tag: Tag::Sequence }
+    );
+}
+
+#[cfg(feature = "sct")]
+#[test]
+fn precertificate_tbs() {
+    use const_oid::db::rfc6962::{CT_PRECERT_POISON, CT_PRECERT_SCTS};
+
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let extension_ids = |tbs: &x509_cert::TbsCertificate| {
+        tbs.extensions
+            .iter()
+            .flatten()
+            .map(|e| e.extn_id)
+            .collect::<Vec<_>>()
+    };
+
+    // The SCT list is removed from the final certificate, everything else is
+    // kept in order
+    let tbs = cert.precert_tbs_certificate(None).unwrap();
+    let mut expected = extension_ids(&cert.tbs_certificate);
+    expected.retain(|oid| *oid != CT_PRECERT_SCTS);
+    assert_eq!(extension_ids(&tbs), expected);
+    assert_eq!(tbs.issuer, cert.tbs_certificate.issuer);
+    assert!(!tbs.is_precertificate());
+
+    // A precertificate reconstructs to the same TBS
+    let mut precert = cert.clone();
+    precert.tbs_certificate = tbs.clone();
+    precert.tbs_certificate.add_precert_poison().unwrap();
+    precert.tbs_certificate.add_precert_poison().unwrap();
+    assert!(precert.tbs_certificate.is_precertificate());
+
+    let poison = precert
+        .tbs_certificate
+        .extensions
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter(|e| e.extn_id == CT_PRECERT_POISON)
+        .collect::<Vec<_>>();
+    assert_eq!(poison.len(), 1);
+    assert!(poison[0].critical);
+    assert_eq!(poison[0].extn_value.as_bytes(), &hex!("0500"));
+    assert_eq!(
+        precert
+            .tbs_certificate
+            .get::<PrecertificatePoison>()
+            .unwrap(),
+        Some((true, PrecertificatePoison))
+    );
+
+    assert_eq!(precert.precert_tbs_certificate(None).unwrap(), tbs);
+
+    // A CA certificate without the precertificate signing EKU is rejected
+    let ca = Certificate::from_der(include_bytes!("examples/GoodCACert.crt")).unwrap();
+    assert_eq!(
+        precert.precert_tbs_certificate(Some(&ca)),
+        Err(Error::InvalidPrecertSigningCertificate)
+    );
+}
+
+#[test]
+fn name_constraints_evaluation() {
+    use der::asn1::Ia5String;
+    use x509_cert::ext::pkix::constraints::name::GeneralSubtree;
+
+    fn subtree(base: GeneralName) -> GeneralSubtree {
+        GeneralSubtree {
+            base,
+            minimum: 0,
+            maximum: None,
+        }
+    }
+    fn dns(name: &str) -> GeneralName {
+        GeneralName::DnsName(Ia5String::new(name).unwrap())
+    }
+    fn email(name: &str) -> GeneralName {
+        GeneralName::Rfc822Name(Ia5String::new(name).unwrap())
+    }
+    fn uri(name: &str) -> GeneralName {
+        GeneralName::UniformResourceIdentifier(Ia5String::new(name).unwrap())
+    }
+    fn ip(octets: &[u8]) -> GeneralName {
+        GeneralName::IpAddress(OctetString::new(octets).unwrap())
+    }
+    fn dn(name: &str) -> GeneralName {
+        GeneralName::DirectoryName(name.parse().unwrap())
+    }
+
+    // dNSName
+    let base = subtree(dns("example.com"));
+    assert!(base.contains(&dns("example.com")));
+    assert!(base.contains(&dns("WWW.Example.COM")));
+    assert!(!base.contains(&dns("badexample.com")));
+    assert!(!base.contains(&dns("example.org")));
+    assert!(!subtree(dns(".example.com")).contains(&dns("example.com")));
+    assert!(subtree(dns(".example.com")).contains(&dns("a.b.example.com")));
+    assert!(subtree(dns("")).contains(&dns("anything.test")));
+
+    // rfc822Name
+    assert!(subtree(email("user@example.com")).contains(&email("user@EXAMPLE.com")));
+    assert!(!subtree(email("user@example.com")).contains(&email("other@example.com")));
+    assert!(subtree(email("example.com")).contains(&email("user@example.com")));
+    assert!(!subtree(email("example.com")).contains(&email("user@mail.example.com")));
+    assert!(subtree(email(".example.com")).contains(&email("user@mail.example.com")));
+
+    // uniformResourceIdentifier
+    let base = subtree(uri("example.com"));
+    assert!(base.contains(&uri("https://user@example.com:8443/path?query")));
+    assert!(!base.contains(&uri("https://www.example.com/")));
+    assert!(!base.contains(&uri("urn:example.com")));
+    assert!(subtree(uri(".example.com")).contains(&uri("http://www.example.com")));
+
+    // iPAddress
+    let base = subtree(ip(&[192, 168, 0, 0, 255, 255, 0, 0]));
+    assert!(base.contains(&ip(&[192, 168, 10, 1])));
+    assert!(!base.contains(&ip(&[192, 169, 0, 1])));
+    assert!(!base.contains(&ip(&[0; 16])));
+
+    // directoryName
+    let base = subtree(dn("O=Acme,C=US"));
+    assert!(base.contains(&dn("CN=Alice,O=Acme,C=US")));
+    assert!(base.contains(&dn("O=Acme,C=US")));
+    assert!(base.contains(&dn("CN=Bob,O=ACME,C=US")));
+    assert!(!base.contains(&dn("CN=Mallory,O=Evil,C=US")));
+    assert!(!base.contains(&dns("example.com")));
+
+    let constraints = NameConstraints {
+        permitted_subtrees: Some(vec![subtree(dns("example.com")), subtree(dn("C=US"))]),
+        excluded_subtrees: Some(vec![subtree(dns("secret.example.com"))]),
+    };
+    assert!(constraints.permits(&dns("www.example.com")));
+    assert!(!constraints.permits(&dns("www.example.org")));
+    assert!(!constraints.permits(&dns("db.secret.example.com")));
+    assert!(!constraints.permits(&dn("C=CA")));
+    // No permitted subtrees of this name type
+    assert!(constraints.permits(&ip(&[10, 0, 0, 1])));
+
+    // The subject and SANs of the certificate are checked
+    let mut cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let constraints = NameConstraints {
+        permitted_subtrees: Some(vec![subtree(dns("amazon.com"))]),
+        excluded_subtrees: None,
+    };
+    assert!(!constraints
+        .permits_certificate(&cert.tbs_certificate)
+        .unwrap());
+
+    let constraints = NameConstraints {
+        permitted_subtrees: None,
+        excluded_subtrees: Some(vec![subtree(dns("example.com"))]),
+    };
+    assert!(constraints
+        .permits_certificate(&cert.tbs_certificate)
+        .unwrap());
+
+    cert.tbs_certificate.subject = "CN=www.amazon.com,O=Amazon.com\\, Inc.,C=CA"
+        .parse()
+        .unwrap();
+    let constraints = NameConstraints {
+        permitted_subtrees: Some(vec![subtree(dn("C=US"))]),
+        excluded_subtrees: None,
+    };
+    assert!(!constraints
+        .permits_certificate(&cert.tbs_certificate)
+        .unwrap());
+}
+
+#[test]
+fn certificate_policies_qualifiers() {
+    use der::asn1::{BmpString, Ia5String, ObjectIdentifier};
+    use x509_cert::ext::{
+        pkix::certpolicy::{
+            DisplayText, PolicyInformation, PolicyQualifier, PolicyQualifierInfo, UserNotice,
+            ANY_POLICY,
+        },
+        AsExtension,
+    };
+
+    let cps = PolicyQualifier::CpsUri(Ia5String::new("https://example.com/cps").unwrap());
+    let notice = PolicyQualifier::UserNotice(UserNotice::new("Do not rely").unwrap());
+    let policies = CertificatePolicies(vec![
+        PolicyInformation::new(ObjectIdentifier::new_unwrap("2.23.140.1.2.1")),
+        PolicyInformation::new(ANY_POLICY)
+            .with_qualifier(&cps)
+            .unwrap()
+            .with_qualifier(&notice)
+            .unwrap(),
+    ]);
+
+    let extension = policies.to_extension(&Name::default(), &[]).unwrap();
+    assert_eq!(extension.extn_id, ID_CE_CERTIFICATE_POLICIES);
+    assert!(!extension.critical);
+
+    let decoded = CertificatePolicies::from_der(extension.extn_value.as_bytes()).unwrap();
+    assert_eq!(decoded, policies);
+    assert!(decoded.0[0].policy_qualifiers.is_none());
+    let qualifiers = decoded.0[1].policy_qualifiers.as_ref().unwrap();
+    assert_eq!(qualifiers[0].policy_qualifier_id, ID_QT_CPS);
+    assert_eq!(qualifiers[0].decode_qualifier().unwrap(), Some(cps));
+    assert_eq!(qualifiers[1].policy_qualifier_id, ID_QT_UNOTICE);
+    assert_eq!(qualifiers[1].decode_qualifier().unwrap(), Some(notice));
+
+    // Unknown qualifiers are left undecoded
+    let other = PolicyQualifierInfo {
+        policy_qualifier_id: ObjectIdentifier::new_unwrap("1.2.3"),
+        qualifier: qualifiers[0].qualifier.clone(),
+    };
+    assert_eq!(other.decode_qualifier().unwrap(), None);
+
+    // Every DisplayText choice is supported
+    for (der, text) in [
+        (&hex!("1603486921")[..], "Hi!"),
+        (&hex!("1A03486921")[..], "Hi!"),
+        (&hex!("1E06004800690021")[..], "Hi!"),
+        (&hex!("0C03486921")[..], "Hi!"),
+    ] {
+        let decoded = DisplayText::from_der(der).unwrap();
+        assert_eq!(decoded.to_string(), text);
+        assert_eq!(decoded.to_der().unwrap(), der);
+    }
+    assert_eq!(
+        DisplayText::from_der(&hex!("1E06004800690021")).unwrap(),
+        DisplayText::BmpString(BmpString::from_utf8("Hi!").unwrap())
+    );
+    assert!(DisplayText::from_der(&hex!("1A020A21")).is_err());
+    assert!(DisplayText::from_der(&hex!("1303486921")).is_err());
+
+    assert!(DisplayText::new("").is_err());
+    assert!(DisplayText::new(&"a".repeat(200)).is_ok());
+    assert!(DisplayText::new(&"a".repeat(201)).is_err());
+}
+
+#[test]
+fn information_access_uris() {
+    let der_encoded_cert =
+        include_bytes!("examples/026EDA6FA1EDFA8C253936C75B5EEBD954BFF452.fake.der");
+    let cert = Certificate::from_der(der_encoded_cert).unwrap();
+
+    let (_, aia) = cert
+        .tbs_certificate
+        .get::<AuthorityInfoAccessSyntax>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        aia.ocsp_responders().collect::<Vec<_>>(),
+        ["http://igcrootpte.ocsp.identrust.com.test:8125"]
+    );
+    assert_eq!(
+        aia.ca_issuers().collect::<Vec<_>>(),
+        ["http://apps-stg.identrust.com.test/roots/IGCRootca1.p7c"]
+    );
+    assert_eq!(aia.filter(ID_AD_OCSP).count(), 1);
+    assert_eq!(aia.filter(ID_AD_TIME_STAMPING).count(), 0);
+
+    let (_, sia) = cert
+        .tbs_certificate
+        .get::<SubjectInfoAccessSyntax>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        sia.ca_repositories().collect::<Vec<_>>(),
+        ["http://http.cite.fpki-lab.gov.test/bridge/caCertsIssuedBytestFBCA.p7c"]
+    );
+    assert_eq!(sia.time_stamping().count(), 0);
+
+    // Access locations which are not URIs are skipped
+    let sia = SubjectInfoAccessSyntax(vec![AccessDescription {
+        access_method: ID_AD_TIME_STAMPING,
+        access_location: GeneralName::DirectoryName(Name::default()),
+    }]);
+    assert_eq!(sia.filter(ID_AD_TIME_STAMPING).count(), 1);
+    assert_eq!(sia.time_stamping().count(), 0);
+}
+
+#[test]
+fn rfc3779_ip_addr_blocks() {
+    use x509_cert::ext::pkix::resources::*;
+
+    let cert = Certificate::from_der(include_bytes!("examples/rpki-ca.der")).unwrap();
+    let tbs = &cert.tbs_certificate;
+    assert_eq!(tbs.unrecognized_extensions().count(), 0);
+
+    let (critical, blocks) = tbs.get::<IpAddrBlocks>().unwrap().unwrap();
+    assert!(critical);
+    assert_eq!(blocks.0.len(), 3);
+
+    let ext = tbs
+        .extensions
+        .iter()
+        .flatten()
+        .find(|ext| ext.extn_id == ID_PE_IP_ADDR_BLOCKS)
+        .unwrap();
+    assert_eq!(blocks.to_der().unwrap(), ext.extn_value.as_bytes());
+
+    let ipv4 = blocks.get(AFI_IPV4, None).unwrap();
+    assert_eq!(ipv4.address_len(), Some(4));
+    let entries = match &ipv4.ip_address_choice {
+        IpAddressChoice::AddressesOrRanges(entries) => entries,
+        IpAddressChoice::Inherit => panic!("expected addresses"),
+    };
+    assert_eq!(
+        entries,
+        &[
+            IpAddressOrRange::prefix(&[10, 0, 0, 0], 8).unwrap(),
+            IpAddressOrRange::range(&[172, 16, 0, 1], &[172, 16, 0, 200]).unwrap(),
+            IpAddressOrRange::prefix(&[192, 168, 0, 0], 23).unwrap(),
+        ]
+    );
+    assert!(matches!(entries[1], IpAddressOrRange::AddressRange(_)));
+    assert_eq!(entries[2].min_address(4).unwrap(), [192, 168, 0, 0]);
+    assert_eq!(entries[2].max_address(4).unwrap(), [192, 168, 1, 255]);
+    assert_eq!(entries[2].min_address(2), None);
+
+    let ipv6 = blocks.get(AFI_IPV6, None).unwrap();
+    assert_eq!(
+        ipv6.ip_address_choice,
+        IpAddressChoice::AddressesOrRanges(vec![IpAddressOrRange::prefix(
+            &hex!("20010db8000000000000000000000000"),
+            32
+        )
+        .unwrap()])
+    );
+    let ipv6_unicast = blocks.get(AFI_IPV6, Some(1)).unwrap();
+    assert_eq!(ipv6_unicast.ip_address_choice, IpAddressChoice::Inherit);
+
+    // The extension is already in its canonical form
+    let mut canonical = blocks.clone();
+    canonical.canonicalize().unwrap();
+    assert_eq!(canonical, blocks);
+
+    // Families are sorted, and overlapping and adjacent blocks merged
+    let mut blocks = IpAddrBlocks(vec![
+        IpAddressFamily::new(AFI_IPV6, None, IpAddressChoice::Inherit).unwrap(),
+        IpAddressFamily::new(
+            AFI_IPV4,
+            None,
+            IpAddressChoice::AddressesOrRanges(vec![
+                IpAddressOrRange::prefix(&[192, 168, 1, 0], 24).unwrap(),
+                IpAddressOrRange::prefix(&[10, 0, 0, 0], 8).unwrap(),
+                IpAddressOrRange::range(&[172, 16, 0, 128], &[172, 16, 0, 200]).unwrap(),
+                IpAddressOrRange::prefix(&[10, 1, 0, 0], 16).unwrap(),
+                IpAddressOrRange::range(&[172, 16, 0, 1], &[172, 16, 0, 127]).unwrap(),
+                IpAddressOrRange::prefix(&[192, 168, 0, 0], 24).unwrap(),
+            ]),
+        )
+        .unwrap(),
+    ]);
+    blocks.canonicalize().unwrap();
+    assert_eq!(blocks.0[0].afi(), Some(AFI_IPV4));
+    assert_eq!(blocks.0[1].afi(), Some(AFI_IPV6));
+    assert_eq!(blocks.0[0].ip_address_choice, ipv4.ip_address_choice);
+
+    // Malformed blocks
+    assert!(IpAddressOrRange::prefix(&[10, 0, 0, 0], 33).is_err());
+    assert!(IpAddressOrRange::range(&[10, 0, 0, 1], &[10, 0, 0, 0]).is_err());
+    assert!(IpAddressOrRange::range(&[10, 0, 0, 1], &[10, 0, 1]).is_err());
+    let mut unknown =
+        IpAddressFamily::new(3, None, IpAddressChoice::AddressesOrRanges(Vec::new())).unwrap();
+    assert!(unknown.canonicalize().is_err());
+}
+
+#[test]
+fn rfc3779_as_identifiers() {
+    use x509_cert::ext::pkix::resources::*;
+
+    let cert = Certificate::from_der(include_bytes!("examples/rpki-ca.der")).unwrap();
+    let (critical, ids) = cert
+        .tbs_certificate
+        .get::<AsIdentifiers>()
+        .unwrap()
+        .unwrap();
+    assert!(critical);
+    assert_eq!(
+        ids.asnum,
+        Some(AsIdentifierChoice::AsIdsOrRanges(vec![
+            AsIdOrRange::Id(64496),
+            AsIdOrRange::Range(AsRange {
+                min: 64500,
+                max: 64510
+            }),
+        ]))
+    );
+    assert_eq!(ids.rdi, Some(AsIdentifierChoice::Inherit));
+    assert_eq!(
+        ids.to_der().unwrap(),
+        hex!("3019A0133011020300FBF0300A020300FBF4020300FBFEA1020500")
+    );
+
+    let mut canonical = ids.clone();
+    canonical.canonicalize().unwrap();
+    assert_eq!(canonical, ids);
+
+    let mut ids = AsIdentifiers {
+        asnum: Some(AsIdentifierChoice::AsIdsOrRanges(vec![
+            AsIdOrRange::range(10, 20).unwrap(),
+            AsIdOrRange::Id(u32::MAX),
+            AsIdOrRange::Id(5),
+            AsIdOrRange::Id(21),
+            AsIdOrRange::range(15, 30).unwrap(),
+            AsIdOrRange::range(4294967290, 4294967294).unwrap(),
+        ])),
+        rdi: None,
+    };
+    ids.canonicalize().unwrap();
+    assert_eq!(
+        ids.asnum,
+        Some(AsIdentifierChoice::AsIdsOrRanges(vec![
+            AsIdOrRange::Id(5),
+            AsIdOrRange::range(10, 30).unwrap(),
+            AsIdOrRange::range(4294967290, u32::MAX).unwrap(),
+        ]))
+    );
+
+    assert_eq!(AsIdOrRange::range(7, 7).unwrap(), AsIdOrRange::Id(7));
+    assert!(AsIdOrRange::range(8, 7).is_err());
+
+    let mut malformed =
+        AsIdentifierChoice::AsIdsOrRanges(vec![AsIdOrRange::Range(AsRange { min: 2, max: 1 })]);
+    assert!(malformed.canonicalize().is_err());
+}
+
+#[test]
+fn additional_extensions() {
+    use core::time::Duration;
+    use der::asn1::{GeneralizedTime, Ia5String, SetOfVec};
+    use spki::AlgorithmIdentifierOwned;
+    use x509_cert::attr::Attribute;
+    use x509_cert::ext::pkix::qcstatements::{
+        BiometricData, PredefinedBiometricType, TypeOfBiometricData,
+    };
+    use x509_cert::ext::AsExtension;
+
+    assert_eq!(OcspNoCheck.to_der().unwrap(), hex!("0500"));
+    assert_eq!(OcspNoCheck::from_der(&hex!("0500")).unwrap(), OcspNoCheck);
+    assert!(OcspNoCheck::from_der(&hex!("050100")).is_err());
+
+    let biometric = BiometricSyntax(vec![
+        BiometricData {
+            type_of_biometric_data: TypeOfBiometricData::PredefinedBiometricType(
+                PredefinedBiometricType::Picture,
+            ),
+            hash_algorithm: AlgorithmIdentifierOwned {
+                oid: const_oid::db::rfc5912::ID_SHA_256,
+                parameters: None,
+            },
+            biometric_data_hash: OctetString::new(hex!("01020304")).unwrap(),
+            source_data_uri: Some(Ia5String::new("http://x").unwrap()),
+        },
+        BiometricData {
+            type_of_biometric_data: TypeOfBiometricData::BiometricDataOid(
+                "1.2.3.4".parse().unwrap(),
+            ),
+            hash_algorithm: AlgorithmIdentifierOwned {
+                oid: const_oid::db::rfc5912::ID_SHA_256,
+                parameters: None,
+            },
+            biometric_data_hash: OctetString::new(hex!("05060708")).unwrap(),
+            source_data_uri: None,
+        },
+    ]);
+    let der = biometric.to_der().unwrap();
+    assert_eq!(
+        der[..36],
+        hex!(
+            "303C"
+            "3020020100300B0609608648016503040201040401020304"
+            "1608687474703A2F2F78"
+        )
+    );
+    assert_eq!(BiometricSyntax::from_der(&der).unwrap(), biometric);
+    assert!(BiometricData::from_der(&hex!("3013020102300B0609608648016503040201040100")).is_err());
+
+    let not_before = GeneralizedTime::from_unix_duration(Duration::from_secs(0)).unwrap();
+    let period = PrivateKeyUsagePeriod {
+        not_before: Some(not_before),
+        not_after: None,
+    };
+    assert_eq!(
+        period.to_der().unwrap(),
+        hex!("3011800F31393730303130313030303030305A")
+    );
+
+    let mut values = SetOfVec::new();
+    values
+        .insert(Utf8StringRef::new("US").unwrap().into())
+        .unwrap();
+    let attributes = SubjectDirectoryAttributes(vec![Attribute {
+        oid: const_oid::db::rfc2985::PKCS_9_AT_COUNTRY_OF_CITIZENSHIP,
+        values,
+    }]);
+
+    // All of these are recognized once present in a certificate
+    let mut cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    // The SCT list is only recognized with the `sct` feature enabled
+    let unrecognized = cert.tbs_certificate.unrecognized_extensions().count();
+    let subject = cert.tbs_certificate.subject.clone();
+    let extensions = cert.tbs_certificate.extensions.get_or_insert_with(Vec::new);
+    for ext in [
+        OcspNoCheck.to_extension(&subject, &[]).unwrap(),
+        biometric.to_extension(&subject, &[]).unwrap(),
+        period.to_extension(&subject, &[]).unwrap(),
+        attributes.to_extension(&subject, &[]).unwrap(),
+    ] {
+        assert!(!ext.critical);
+        extensions.push(ext);
+    }
+
+    let tbs = &cert.tbs_certificate;
+    assert_eq!(tbs.unrecognized_extensions().count(), unrecognized);
+    assert_eq!(
+        tbs.get::<OcspNoCheck>().unwrap(),
+        Some((false, OcspNoCheck))
+    );
+    assert_eq!(
+        tbs.get::<BiometricSyntax>().unwrap(),
+        Some((false, biometric))
+    );
+    assert_eq!(
+        tbs.get::<PrivateKeyUsagePeriod>().unwrap(),
+        Some((false, period))
+    );
+    assert_eq!(
+        tbs.get::<SubjectDirectoryAttributes>().unwrap(),
+        Some((false, attributes))
+    );
+}
@@ -0,0 +1,139 @@
+//! serde tests
+
+#![cfg(feature = "serde")]
+
+use der::{Decode, Encode};
+use hex_literal::hex;
+use x509_cert::{
+    ext::{pkix::BasicConstraints, AsExtension, Extension},
+    name::Name,
+    Certificate,
+};
+
+#[test]
+fn certificate_json_round_trip() {
+    let der = include_bytes!("examples/amazon.der");
+    let cert = Certificate::from_der(der).unwrap();
+
+    let json = serde_json::to_value(&cert).unwrap();
+    let tbs = &json["tbs_certificate"];
+
+    assert_eq!(tbs["version"], "V3");
+    assert_eq!(tbs["serial_number"], "0E4239AB85E2E6A27C52C6DE9B9078D9");
+    assert_eq!(tbs["signature"]["oid"], "1.2.840.113549.1.1.11");
+    assert_eq!(
+        tbs["validity"]["not_before"]["UtcTime"],
+        "2021-10-06T00:00:00Z"
+    );
+    assert_eq!(
+        tbs["validity"]["not_after"]["UtcTime"],
+        "2022-09-19T23:59:59Z"
+    );
+
+    // C=US, O=DigiCert Inc, CN=DigiCert Global CA G2
+    let issuer = tbs["issuer"].as_array().unwrap();
+    assert_eq!(issuer.len(), 3);
+    assert_eq!(issuer[0][0]["oid"], "2.5.4.6");
+    assert_eq!(issuer[0][0]["value"]["PrintableString"], "US");
+    assert_eq!(
+        issuer[2][0]["value"]["PrintableString"],
+        "DigiCert Global CA G2"
+    );
+
+    let extensions = tbs["extensions"].as_array().unwrap();
+    assert_eq!(
+        extensions.len(),
+        cert.tbs_certificate.extensions.as_ref().unwrap().len()
+    );
+    let extension = |oid: &str| extensions.iter().find(|ext| ext["extn_id"] == oid).unwrap();
+
+    let san = extension("2.5.29.17");
+    assert_eq!(san["critical"], false);
+    assert_eq!(
+        san["extn_value"]["SubjectAltName"][0]["DnsName"],
+        "amazon.co.uk"
+    );
+
+    let key_usage = extension("2.5.29.15");
+    assert_eq!(key_usage["critical"], true);
+    assert_eq!(
+        key_usage["extn_value"]["KeyUsage"],
+        serde_json::json!(["DigitalSignature", "KeyEncipherment"])
+    );
+    assert_eq!(
+        extension("2.5.29.37")["extn_value"]["ExtendedKeyUsage"],
+        serde_json::json!(["1.3.6.1.5.5.7.3.1", "1.3.6.1.5.5.7.3.2"])
+    );
+    assert_eq!(
+        extension("2.5.29.35")["extn_value"]["AuthorityKeyIdentifier"]["key_identifier"],
+        "246E2B2DD06A925151256901AA9A47A689E74020"
+    );
+
+    // Other extensions are left as DER
+    assert!(extension("2.5.29.31")["extn_value"]["Der"]
+        .as_str()
+        .unwrap()
+        .starts_with("306E"));
+
+    assert_eq!(json["signature"]["unused_bits"], 0);
+
+    let decoded: Certificate = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, cert);
+    assert_eq!(decoded.to_der().unwrap(), der);
+}
+
+#[test]
+fn name_json() {
+    let name: Name = "CN=example,O=Acme".parse().unwrap();
+
+    let json = serde_json::to_string(&name).unwrap();
+    assert_eq!(
+        json,
+        r#"[[{"oid":"2.5.4.10","value":{"Utf8String":"Acme"}}],[{"oid":"2.5.4.3","value":{"Utf8String":"example"}}]]"#
+    );
+    assert_eq!(serde_json::from_str::<Name>(&json).unwrap(), name);
+
+    // Values of other types are kept as DER
+    let name: Name =
+        serde_json::from_str(r#"[[{"oid":"2.5.4.3","value":{"Der":"1E020041"}}]]"#).unwrap();
+    assert_eq!(
+        name.to_der().unwrap(),
+        hex!("300D310B300906035504031E020041").to_vec()
+    );
+
+    // Invalid object identifiers, strings and DER are rejected
+    assert!(serde_json::from_str::<Name>(r#"[[{"oid":"x","value":{"Utf8String":"a"}}]]"#).is_err());
+    assert!(serde_json::from_str::<Name>(
+        r#"[[{"oid":"2.5.4.3","value":{"PrintableString":"a@b"}}]]"#
+    )
+    .is_err());
+    assert!(
+        serde_json::from_str::<Name>(r#"[[{"oid":"2.5.4.3","value":{"Der":"0C01"}}]]"#).is_err()
+    );
+}
+
+#[test]
+fn extension_json() {
+    let bc = BasicConstraints {
+        ca: true,
+        path_len_constraint: Some(0),
+    };
+    let extension = bc.to_extension(&Name::default(), &[]).unwrap();
+
+    let json = serde_json::to_string(&extension).unwrap();
+    assert_eq!(
+        json,
+        r#"{"extn_id":"2.5.29.19","critical":true,"extn_value":{"BasicConstraints":{"ca":true,"path_len_constraint":0}}}"#
+    );
+    assert_eq!(serde_json::from_str::<Extension>(&json).unwrap(), extension);
+
+    // The value must be of the extension identified by `extn_id`
+    assert!(serde_json::from_str::<Extension>(
+        r#"{"extn_id":"2.5.29.15","critical":true,"extn_value":{"BasicConstraints":{"ca":true,"path_len_constraint":0}}}"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<Extension>(
+        r#"{"extn_id":"2.5.29.15","critical":true,"extn_value":{"KeyUsage":["Unknown"]}}"#
+    )
+    .is_err());
+}
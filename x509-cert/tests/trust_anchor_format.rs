@@ -3,8 +3,9 @@ use der::{
     Decode, Encode, SliceReader,
 };
 use hex_literal::hex;
-use x509_cert::anchor::{CertPolicies, TrustAnchorChoice};
+use x509_cert::anchor::{CertPolicies, TrustAnchorChoice, TrustAnchorList};
 use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::Certificate;
 
 #[test]
 fn decode_ta1() {
@@ -459,3 +460,46 @@ fn decode_ta4() {
         _ => panic!("Unexpected TrustAnchorChoice contents"),
     }
 }
+
+#[test]
+fn trust_anchor_list() {
+    let ta_info = TrustAnchorChoice::from_der(include_bytes!("examples/eca_policies.ta")).unwrap();
+    let cert = Certificate::from_der(include_bytes!("examples/entrust.der")).unwrap();
+    let tbs = Certificate::from_der(include_bytes!("examples/exostar.der"))
+        .unwrap()
+        .tbs_certificate;
+
+    let list = TrustAnchorList(vec![
+        ta_info.clone(),
+        TrustAnchorChoice::Certificate(cert.clone()),
+        TrustAnchorChoice::TbsCertificate(tbs.clone()),
+    ]);
+    let encoded = list.to_der().unwrap();
+    assert_eq!(TrustAnchorList::from_der(&encoded).unwrap(), list);
+
+    // TrustAnchorList ::= SEQUENCE SIZE (1..MAX) OF TrustAnchorChoice
+    assert!(TrustAnchorList::from_der(&[0x30, 0x00]).is_err());
+
+    let cert_path = match &ta_info {
+        TrustAnchorChoice::TaInfo(info) => info.cert_path.as_ref().unwrap(),
+        _ => panic!("Unexpected TrustAnchorChoice contents"),
+    };
+    assert_eq!(ta_info.name(), Some(&cert_path.ta_name));
+    assert_eq!(
+        ta_info.public_key(),
+        &cert_path
+            .certificate
+            .as_ref()
+            .unwrap()
+            .tbs_certificate
+            .subject_public_key_info
+    );
+
+    assert_eq!(list.0[1].name(), Some(&cert.tbs_certificate.subject));
+    assert_eq!(
+        list.0[1].public_key(),
+        &cert.tbs_certificate.subject_public_key_info
+    );
+    assert_eq!(list.0[2].name(), Some(&tbs.subject));
+    assert_eq!(list.0[2].public_key(), &tbs.subject_public_key_info);
+}
@@ -2,7 +2,8 @@
 
 use der::{Decode, Encode};
 use hex_literal::hex;
-use x509_cert::time::Validity;
+use std::time::Duration;
+use x509_cert::time::{Time, Validity};
 
 #[test]
 fn decode_validity() {
@@ -134,3 +135,43 @@ fn encode_validity() {
         &hex!("3020180F32303032303130313132303130305A170D3330313233313038333030305A")[..]
     );
 }
+
+#[test]
+fn validity_from_duration() {
+    // 2049-12-31T23:59:59Z is the last time which is encoded as UTCTime
+    let last_utc_time = Duration::from_secs(2_524_607_999);
+
+    let validity = Validity::from_unix_duration(last_utc_time, Duration::from_secs(1)).unwrap();
+    assert!(matches!(validity.not_before, Time::UtcTime(_)));
+    assert!(matches!(validity.not_after, Time::GeneralTime(_)));
+    assert_eq!(validity.not_after.to_string(), "2050-01-01T00:00:00Z");
+
+    let validity = Validity::from_unix_duration(Duration::ZERO, Duration::MAX);
+    assert!(validity.is_err());
+}
+
+#[test]
+fn validity_is_valid_at() {
+    // 01/01/2010 08:30:00 GMT - 31/12/2030 08:30:00 GMT
+    let validity = Validity::from_der(
+        &hex!("301E170D3130303130313038333030305A170D3330313233313038333030305A")[..],
+    )
+    .unwrap();
+
+    assert!(validity.is_valid_at(validity.not_before));
+    assert!(validity.is_valid_at(validity.not_after));
+    assert!(
+        validity.is_valid_at(Time::from_unix_duration(Duration::from_secs(1_700_000_000)).unwrap())
+    );
+    assert!(!validity
+        .is_valid_at(Time::from_unix_duration(Duration::from_secs(1_262_334_599)).unwrap()));
+    assert!(!validity.is_valid_at(Time::INFINITY));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn validity_from_now() {
+    let validity = Validity::from_now(Duration::from_secs(60)).unwrap();
+    assert!(validity.is_currently_valid());
+    assert!(!validity.is_valid_at(Time::INFINITY));
+}
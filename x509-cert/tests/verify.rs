@@ -0,0 +1,334 @@
+//! Signature verification tests
+
+#![cfg(feature = "rsa")]
+
+use der::{asn1::BitString, Decode, Encode, Header, Tag, TagNumber};
+use sha2::Sha256;
+use spki::SubjectPublicKeyInfoOwned;
+use x509_cert::{certificate::SpannedCertificate, verify::Error, Certificate};
+
+const RSA_2048_CRT_DER: &[u8] = include_bytes!("examples/rsa2048-crt.der");
+const RSA_2048_PRIV_DER: &[u8] = include_bytes!("examples/rsa2048-priv.der");
+const RSA_2048_PUB_DER: &[u8] = include_bytes!("examples/rsa2048-pub.der");
+
+/// Encode a TLV production with the given `tag` and `value`.
+fn tlv(tag: Tag, value: &[u8]) -> Vec<u8> {
+    let mut tlv = Header::new(tag, value.len()).unwrap().to_der().unwrap();
+    tlv.extend_from_slice(value);
+    tlv
+}
+
+#[test]
+fn verify_rsa_pkcs1v15() {
+    let cert = Certificate::from_der(RSA_2048_CRT_DER).unwrap();
+    let issuer = cert.tbs_certificate.subject_public_key_info.clone();
+
+    cert.verify_signature(&issuer).unwrap();
+    cert.verify_signature_with::<rsa::pkcs1v15::VerifyingKey<Sha256>, rsa::pkcs1v15::Signature>(
+        &issuer,
+    )
+    .unwrap();
+
+    // Any change to the TBSCertificate invalidates the signature
+    let mut tampered = cert.clone();
+    tampered.tbs_certificate.subject = "CN=example.org".parse().unwrap();
+    assert!(matches!(
+        tampered.verify_signature(&issuer),
+        Err(Error::Signature(_))
+    ));
+
+    let mut tampered = cert.clone();
+    let mut signature = cert.signature.raw_bytes().to_vec();
+    signature[0] ^= 1;
+    tampered.signature = BitString::from_bytes(&signature).unwrap();
+    assert!(matches!(
+        tampered.verify_signature(&issuer),
+        Err(Error::Signature(_))
+    ));
+
+    // The signature algorithms of the certificate and the TBSCertificate must match
+    let mut mismatched = cert.clone();
+    mismatched.tbs_certificate.signature.oid = const_oid::db::rfc5912::SHA_384_WITH_RSA_ENCRYPTION;
+    assert!(matches!(
+        mismatched.verify_signature(&issuer),
+        Err(Error::AlgorithmMismatch)
+    ));
+
+    // Signatures over other algorithms aren't supported
+    let mut unsupported = cert;
+    let oid = const_oid::db::rfc5912::SHA_1_WITH_RSA_ENCRYPTION;
+    unsupported.signature_algorithm.oid = oid;
+    unsupported.tbs_certificate.signature.oid = oid;
+    assert!(matches!(
+        unsupported.verify_signature(&issuer),
+        Err(Error::UnsupportedAlgorithm(got)) if got == oid
+    ));
+}
+
+#[test]
+fn verify_non_canonical_tbs_certificate() {
+    use rsa::pkcs1::DecodeRsaPrivateKey;
+    use signature::{SignatureEncoding, Signer};
+
+    let cert = Certificate::from_der(RSA_2048_CRT_DER).unwrap();
+    let mut tbs = cert.tbs_certificate.clone();
+    let extensions = tbs.extensions.take().unwrap();
+
+    // Encode the extensions with an explicit, non-canonical `critical FALSE`
+    let mut encoded_extensions = Vec::new();
+    for ext in &extensions {
+        assert!(!ext.critical);
+        let mut value = ext.extn_id.to_der().unwrap();
+        value.extend_from_slice(&[0x01, 0x01, 0x00]);
+        value.extend_from_slice(&ext.extn_value.to_der().unwrap());
+        encoded_extensions.extend_from_slice(&tlv(Tag::Sequence, &value));
+    }
+
+    let tbs_der = tbs.to_der().unwrap();
+    let mut tbs_value = der::AnyRef::from_der(&tbs_der).unwrap().value().to_vec();
+    tbs_value.extend_from_slice(&tlv(
+        Tag::ContextSpecific {
+            constructed: true,
+            number: TagNumber::N3,
+        },
+        &tlv(Tag::Sequence, &encoded_extensions),
+    ));
+    let tbs_der = tlv(Tag::Sequence, &tbs_value);
+
+    let private_key = rsa::RsaPrivateKey::from_pkcs1_der(RSA_2048_PRIV_DER).unwrap();
+    let signer = rsa::pkcs1v15::SigningKey::<Sha256>::new(private_key);
+    let signature = signer.sign(&tbs_der).to_vec();
+
+    let mut cert_value = tbs_der.clone();
+    cert_value.extend_from_slice(&cert.signature_algorithm.to_der().unwrap());
+    cert_value.extend_from_slice(&BitString::from_bytes(&signature).unwrap().to_der().unwrap());
+    let cert_der = tlv(Tag::Sequence, &cert_value);
+
+    let issuer = SubjectPublicKeyInfoOwned::try_from(RSA_2048_PUB_DER).unwrap();
+
+    // The original encoding of the TBSCertificate is retained and verified
+    let spanned = SpannedCertificate::<'_>::from_der(&cert_der).unwrap();
+    assert_eq!(spanned.tbs_certificate_der(), tbs_der);
+    assert_eq!(spanned.tbs_certificate.value().extensions, Some(extensions));
+    spanned.verify_signature(&issuer).unwrap();
+    spanned
+        .verify_signature_with::<rsa::pkcs1v15::VerifyingKey<Sha256>, rsa::pkcs1v15::Signature>(
+            &issuer,
+        )
+        .unwrap();
+    assert_eq!(spanned.to_der().unwrap(), cert_der);
+
+    // Re-encoding the decoded TBSCertificate drops the explicit defaults
+    let decoded = Certificate::from(spanned);
+    assert_eq!(decoded, Certificate::from_der(&cert_der).unwrap());
+    assert_ne!(decoded.tbs_certificate.to_der().unwrap(), tbs_der);
+    assert!(matches!(
+        decoded.verify_signature(&issuer),
+        Err(Error::Signature(_))
+    ));
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn verify_ed25519() {
+    use const_oid::db::rfc8410::ID_ED_25519;
+    use ed25519_compact::{KeyPair, Seed};
+    use spki::AlgorithmIdentifierOwned;
+
+    let key_pair = KeyPair::from_seed(Seed::new([7; 32]));
+    let algorithm = AlgorithmIdentifierOwned {
+        oid: ID_ED_25519,
+        parameters: None,
+    };
+    let issuer = SubjectPublicKeyInfoOwned {
+        algorithm: algorithm.clone(),
+        subject_public_key: BitString::from_bytes(key_pair.pk.as_ref()).unwrap(),
+    };
+
+    let mut cert = Certificate::from_der(RSA_2048_CRT_DER).unwrap();
+    cert.tbs_certificate.signature = algorithm.clone();
+    cert.signature_algorithm = algorithm;
+    let signature = key_pair
+        .sk
+        .sign(cert.tbs_certificate.to_der().unwrap(), None);
+    cert.signature = BitString::from_bytes(signature.as_ref()).unwrap();
+
+    cert.verify_signature(&issuer).unwrap();
+
+    // Any change to the TBSCertificate invalidates the signature
+    let mut tampered = cert.clone();
+    tampered.tbs_certificate.subject = "CN=example.org".parse().unwrap();
+    assert!(matches!(
+        tampered.verify_signature(&issuer),
+        Err(Error::Signature(_))
+    ));
+
+    // The issuer key must be an Ed25519 key
+    let rsa_key = SubjectPublicKeyInfoOwned::try_from(RSA_2048_PUB_DER).unwrap();
+    assert!(matches!(
+        cert.verify_signature(&rsa_key),
+        Err(Error::PublicKey(_))
+    ));
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn verify_certificate_request() {
+    use rsa::pkcs1::DecodeRsaPrivateKey;
+    use x509_cert::{
+        builder::{Builder, RequestBuilder},
+        request::{CertReq, SpannedCertReq},
+    };
+
+    type VerifyingKey = rsa::pkcs1v15::VerifyingKey<Sha256>;
+    type Signature = rsa::pkcs1v15::Signature;
+
+    let private_key = rsa::RsaPrivateKey::from_pkcs1_der(RSA_2048_PRIV_DER).unwrap();
+    let signer = rsa::pkcs1v15::SigningKey::<Sha256>::new(private_key);
+    let builder = RequestBuilder::new("CN=service.domination.world".parse().unwrap()).unwrap();
+    let cert_req = builder.build::<_, Signature>(&signer).unwrap();
+
+    cert_req.verify::<VerifyingKey, Signature>().unwrap();
+
+    let der = cert_req.to_der().unwrap();
+    let spanned = SpannedCertReq::from_der(&der).unwrap();
+    assert_eq!(spanned.info_der(), cert_req.info.to_der().unwrap());
+    spanned.verify::<VerifyingKey, Signature>().unwrap();
+    assert_eq!(CertReq::from(spanned), cert_req);
+
+    // Tampering with the request invalidates the signature
+    let mut tampered = cert_req.clone();
+    tampered.info.subject = "CN=evil.domination.world".parse().unwrap();
+    assert!(matches!(
+        tampered.verify::<VerifyingKey, Signature>(),
+        Err(Error::Signature(_))
+    ));
+
+    // The signature algorithm must be one used with RSA keys
+    let mut mismatched = cert_req.clone();
+    mismatched.algorithm.oid = const_oid::db::rfc5912::ECDSA_WITH_SHA_256;
+    assert!(matches!(
+        mismatched.verify::<VerifyingKey, Signature>(),
+        Err(Error::KeyAlgorithmMismatch)
+    ));
+
+    // Signatures with unused bits are rejected
+    let mut unused_bits = cert_req;
+    unused_bits.signature = BitString::new(1, unused_bits.signature.raw_bytes()).unwrap();
+    assert!(matches!(
+        unused_bits.verify::<VerifyingKey, Signature>(),
+        Err(Error::Asn1(_))
+    ));
+}
+
+#[cfg(all(feature = "builder", feature = "p256"))]
+mod builder {
+    use super::*;
+    use p256::{ecdsa::DerSignature, pkcs8::DecodePrivateKey};
+    use rsa::pkcs1::DecodeRsaPrivateKey;
+    use std::{str::FromStr, time::Duration};
+    use x509_cert::{
+        builder::{Builder, CertificateBuilder, Profile, RequestBuilder},
+        name::Name,
+        serial_number::SerialNumber,
+        time::Validity,
+    };
+
+    const P256_PRIV_DER: &[u8] = include_bytes!("examples/p256-priv.der");
+    const P256_PUB_DER: &[u8] = include_bytes!("examples/p256-pub.der");
+    #[cfg(feature = "p384")]
+    const P384_PRIV_DER: &[u8] = include_bytes!("examples/p384-priv.der");
+    #[cfg(feature = "p384")]
+    const P384_PUB_DER: &[u8] = include_bytes!("examples/p384-pub.der");
+
+    fn builder(pub_key: SubjectPublicKeyInfoOwned) -> CertificateBuilder {
+        let serial_number = SerialNumber::from(42u32);
+        let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+        let subject = Name::from_str("CN=World domination corporation,C=US").unwrap();
+
+        CertificateBuilder::new(Profile::Root, serial_number, validity, subject, pub_key).unwrap()
+    }
+
+    #[test]
+    fn verify_rsa_pss() {
+        let pub_key = SubjectPublicKeyInfoOwned::try_from(RSA_2048_PUB_DER).unwrap();
+        let private_key = rsa::RsaPrivateKey::from_pkcs1_der(RSA_2048_PRIV_DER).unwrap();
+        let signer = rsa::pss::SigningKey::<Sha256>::new(private_key);
+
+        let cert = builder(pub_key.clone())
+            .build_with_rng::<_, rsa::pss::Signature>(&signer, &mut rand::thread_rng())
+            .unwrap();
+
+        cert.verify_signature(&pub_key).unwrap();
+
+        let other = Certificate::from_der(RSA_2048_CRT_DER).unwrap();
+        assert!(other.verify_signature(&pub_key).is_err());
+    }
+
+    #[test]
+    fn verify_ecdsa_p256() {
+        let pub_key = SubjectPublicKeyInfoOwned::try_from(P256_PUB_DER).unwrap();
+        let secret_key = p256::SecretKey::from_pkcs8_der(P256_PRIV_DER).unwrap();
+        let signer = p256::ecdsa::SigningKey::from(secret_key);
+
+        let cert = builder(pub_key.clone())
+            .build::<_, DerSignature>(&signer)
+            .unwrap();
+
+        cert.verify_signature(&pub_key).unwrap();
+        cert.verify_signature_with::<p256::ecdsa::VerifyingKey, DerSignature>(&pub_key)
+            .unwrap();
+
+        // The issuer key must match the signature algorithm
+        let rsa_key = SubjectPublicKeyInfoOwned::try_from(RSA_2048_PUB_DER).unwrap();
+        assert!(matches!(
+            cert.verify_signature(&rsa_key),
+            Err(Error::PublicKey(_))
+        ));
+    }
+
+    #[cfg(feature = "p384")]
+    #[test]
+    fn verify_ecdsa_p384() {
+        let pub_key = SubjectPublicKeyInfoOwned::try_from(P384_PUB_DER).unwrap();
+        let secret_key = p384::SecretKey::from_pkcs8_der(P384_PRIV_DER).unwrap();
+        let signer = p384::ecdsa::SigningKey::from(secret_key);
+
+        let cert = builder(pub_key.clone())
+            .build::<_, p384::ecdsa::DerSignature>(&signer)
+            .unwrap();
+
+        cert.verify_signature(&pub_key).unwrap();
+        cert.verify_signature_with::<p384::ecdsa::VerifyingKey, p384::ecdsa::DerSignature>(
+            &pub_key,
+        )
+        .unwrap();
+
+        // A P-256 key doesn't verify a P-384 signature
+        let p256_key = SubjectPublicKeyInfoOwned::try_from(P256_PUB_DER).unwrap();
+        assert!(cert.verify_signature(&p256_key).is_err());
+    }
+
+    #[test]
+    fn verify_certificate_request_p256() {
+        let subject = Name::from_str("CN=service.domination.world").unwrap();
+
+        let secret_key = p256::SecretKey::from_pkcs8_der(P256_PRIV_DER).unwrap();
+        let signer = p256::ecdsa::SigningKey::from(secret_key);
+        let builder = RequestBuilder::new(subject).expect("Create certificate request");
+        let mut cert_req = builder.build::<_, DerSignature>(&signer).unwrap();
+
+        // No extensions were requested
+        assert!(cert_req.info.attributes.is_empty());
+
+        cert_req
+            .verify::<p256::ecdsa::VerifyingKey, DerSignature>()
+            .expect("verify certificate request");
+
+        // Tampering with the request invalidates the signature
+        cert_req.info.subject = Name::from_str("CN=evil.domination.world").unwrap();
+        assert!(cert_req
+            .verify::<p256::ecdsa::VerifyingKey, DerSignature>()
+            .is_err());
+    }
+}
@@ -1,17 +1,19 @@
 //! Basic OCSP Response
 
 use crate::{
-    ext::Nonce, AsResponseBytes, CertId, CertStatus, OcspGeneralizedTime, ResponderId, Version,
+    ext::{self, ArchiveCutoff, CrlReferences, ExtendedRevoke, Nonce},
+    AsResponseBytes, CertId, CertStatus, OcspGeneralizedTime, OcspRequest, Request, ResponderId,
+    Status, TbsRequest, Version,
 };
 use alloc::vec::Vec;
 use const_oid::{
     db::rfc6960::{ID_PKIX_OCSP_BASIC, ID_PKIX_OCSP_NONCE},
     AssociatedOid,
 };
-use core::{default::Default, option::Option};
+use core::{default::Default, option::Option, time::Duration};
 use der::{
     asn1::{BitString, ObjectIdentifier},
-    Decode, Sequence,
+    Decode, Sequence, Spanned,
 };
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::{certificate::Certificate, ext::Extensions};
@@ -27,6 +29,7 @@ use x509_cert::{certificate::Certificate, ext::Extensions};
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct BasicOcspResponse {
@@ -44,6 +47,21 @@ impl BasicOcspResponse {
     pub fn nonce(&self) -> Option<Nonce> {
         self.tbs_response_data.nonce()
     }
+
+    /// Does the nonce of this response match that of `request`? See
+    /// [`ResponseData::matches_nonce`].
+    pub fn matches_nonce(&self, request: &OcspRequest) -> bool {
+        self.tbs_response_data.matches_nonce(&request.tbs_request)
+    }
+
+    /// Pairs each [`Request`] of `request` with the [`SingleResponse`] for the same certificate,
+    /// if any. See [`ResponseData::match_requests`].
+    pub fn match_requests<'a>(
+        &'a self,
+        request: &'a OcspRequest,
+    ) -> impl Iterator<Item = (&'a Request, Option<&'a SingleResponse>)> + 'a {
+        self.tbs_response_data.match_requests(&request.tbs_request)
+    }
 }
 
 impl AssociatedOid for BasicOcspResponse {
@@ -52,6 +70,43 @@ impl AssociatedOid for BasicOcspResponse {
 
 impl AsResponseBytes for BasicOcspResponse {}
 
+/// BasicOcspResponse which retains the original encoding of its
+/// `ResponseData`.
+///
+/// Re-encoding a decoded [`ResponseData`] doesn't necessarily reproduce the
+/// bytes which were signed. This type records the exact encoding of the
+/// `ResponseData` as it was decoded, so that the signature of the response
+/// can be verified over those bytes.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SpannedBasicOcspResponse<'a> {
+    pub tbs_response_data: Spanned<'a, ResponseData>,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
+
+    #[asn1(context_specific = "0", optional = "true", tag_mode = "EXPLICIT")]
+    pub certs: Option<Vec<Certificate>>,
+}
+
+impl<'a> SpannedBasicOcspResponse<'a> {
+    /// Get the original encoding of the `ResponseData`, i.e. the bytes over
+    /// which the response was signed.
+    pub fn tbs_response_data_der(&self) -> &'a [u8] {
+        self.tbs_response_data.as_bytes()
+    }
+}
+
+impl From<SpannedBasicOcspResponse<'_>> for BasicOcspResponse {
+    fn from(resp: SpannedBasicOcspResponse<'_>) -> Self {
+        Self {
+            tbs_response_data: resp.tbs_response_data.into_inner(),
+            signature_algorithm: resp.signature_algorithm,
+            signature: resp.signature,
+            certs: resp.certs,
+        }
+    }
+}
+
 /// ResponseData structure as defined in [RFC 6960 Section 4.2.1].
 ///
 /// ```text
@@ -64,6 +119,7 @@ impl AsResponseBytes for BasicOcspResponse {}
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct ResponseData {
@@ -96,6 +152,56 @@ impl ResponseData {
             None => None,
         }
     }
+
+    /// Does the responder support the extended definition of the `revoked` status? This is
+    /// indicated by the [`ExtendedRevoke`] extension, and means that certificates which were never
+    /// issued may be reported as `revoked`, as described in [RFC 6960 Section 2.2].
+    ///
+    /// [RFC 6960 Section 2.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-2.2
+    pub fn extended_revoke(&self) -> bool {
+        ext::find::<ExtendedRevoke>(&self.response_extensions).is_some()
+    }
+
+    /// Sets the response's nonce, replacing any existing `Nonce` extension. Errors when the
+    /// extension encoding fails.
+    ///
+    /// The response must be signed afterwards.
+    pub fn set_nonce(&mut self, nonce: Nonce) -> Result<(), der::Error> {
+        nonce.set(&mut self.response_extensions)
+    }
+
+    /// Does the nonce of this response match that of `request`?
+    ///
+    /// Returns `true` if `request` has no nonce, and otherwise whether this response has the same
+    /// nonce, as described in [RFC 8954 Section 2.1].
+    ///
+    /// [RFC 8954 Section 2.1]: https://datatracker.ietf.org/doc/html/rfc8954#section-2.1
+    pub fn matches_nonce(&self, request: &TbsRequest) -> bool {
+        match request.nonce() {
+            Some(nonce) => self.nonce() == Some(nonce),
+            None => true,
+        }
+    }
+
+    /// Returns the [`SingleResponse`] for the certificate identified by `request`, if any. The
+    /// `CertID`s are compared with [`CertId::matches`].
+    pub fn find_response(&self, request: &Request) -> Option<&SingleResponse> {
+        self.responses
+            .iter()
+            .find(|response| response.cert_id.matches(&request.req_cert))
+    }
+
+    /// Pairs each [`Request`] of `request`, in order, with the [`SingleResponse`] for the same
+    /// certificate, if any. See [`ResponseData::find_response`].
+    pub fn match_requests<'a>(
+        &'a self,
+        request: &'a TbsRequest,
+    ) -> impl Iterator<Item = (&'a Request, Option<&'a SingleResponse>)> + 'a {
+        request
+            .request_list
+            .iter()
+            .map(move |req| (req, self.find_response(req)))
+    }
 }
 
 /// SingleResponse structure as defined in [RFC 6960 Section 4.2.1].
@@ -110,6 +216,7 @@ impl ResponseData {
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct SingleResponse {
@@ -124,9 +231,91 @@ pub struct SingleResponse {
     pub single_extensions: Option<Extensions>,
 }
 
+impl SingleResponse {
+    /// Returns the status of the certificate. See [`CertStatus::status`].
+    pub fn status(&self) -> Status {
+        self.cert_status.status()
+    }
+
+    /// Returns the response's archive cutoff, if any. This method will return `None` if the
+    /// response has no `ArchiveCutoff` extension or decoding of the extension fails.
+    ///
+    /// As described in [RFC 6960 Section 4.4.4], responders retaining revocation information
+    /// beyond the expiration of certificates use this to indicate the earliest
+    /// `notAfter` date for which the status of a certificate is still reported.
+    ///
+    /// [RFC 6960 Section 4.4.4]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.4
+    pub fn archive_cutoff(&self) -> Option<ArchiveCutoff> {
+        ext::find(&self.single_extensions)
+    }
+
+    /// Returns the reference to the CRL on which a revoked or `onHold` certificate is found, if
+    /// any, as described in [RFC 6960 Section 4.4.2]. This method will return `None` if the
+    /// response has no `CrlReferences` extension or decoding of the extension fails.
+    ///
+    /// [RFC 6960 Section 4.4.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.2
+    pub fn crl_references(&self) -> Option<CrlReferences> {
+        ext::find(&self.single_extensions)
+    }
+
+    /// Is this response fresh at `time`?
+    ///
+    /// A response is fresh if, allowing for a clock difference of `allowed_clock_skew` with the
+    /// responder:
+    ///
+    /// - its `thisUpdate` is not after `time`,
+    /// - it was produced no more than `max_age` before `time`, i.e. `time` is not after
+    ///   `thisUpdate` plus `max_age`,
+    /// - `time` is not after its `nextUpdate`, if present.
+    ///
+    /// As described in [RFC 6960 Section 4.2.2.1], the absence of `nextUpdate` indicates that
+    /// newer information is always available, so only `max_age` limits the use of such a
+    /// response.
+    ///
+    /// [RFC 6960 Section 4.2.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.2.1
+    pub fn is_fresh_at(
+        &self,
+        time: OcspGeneralizedTime,
+        max_age: Duration,
+        allowed_clock_skew: Duration,
+    ) -> bool {
+        self.is_fresh(time.0.to_unix_duration(), max_age, allowed_clock_skew)
+    }
+
+    /// Is this response fresh at the current system time? See [`SingleResponse::is_fresh_at`].
+    #[cfg(feature = "std")]
+    pub fn is_currently_fresh(&self, max_age: Duration, allowed_clock_skew: Duration) -> bool {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .is_ok_and(|now| self.is_fresh(now, max_age, allowed_clock_skew))
+    }
+
+    fn is_fresh(
+        &self,
+        unix_duration: Duration,
+        max_age: Duration,
+        allowed_clock_skew: Duration,
+    ) -> bool {
+        let this_update = self.this_update.0.to_unix_duration();
+        let not_after = |time: Duration| {
+            time.checked_add(allowed_clock_skew)
+                .map_or(true, |time| unix_duration <= time)
+        };
+
+        this_update.saturating_sub(allowed_clock_skew) <= unix_duration
+            && this_update.checked_add(max_age).map_or(true, not_after)
+            && self.next_update.map_or(true, |next_update| {
+                not_after(next_update.0.to_unix_duration())
+            })
+    }
+}
+
 #[cfg(feature = "builder")]
 mod builder {
-    use crate::{builder::Error, CertId, CertStatus, OcspGeneralizedTime, SingleResponse};
+    use crate::{
+        builder::Error, BasicOcspResponse, CertId, CertStatus, OcspGeneralizedTime, ResponseData,
+        SingleResponse,
+    };
     use const_oid::AssociatedOid;
     use digest::Digest;
     use x509_cert::{
@@ -227,4 +416,41 @@ mod builder {
             })
         }
     }
+
+    impl ResponseData {
+        /// Returns the [`SingleResponse`] for `cert`, if any. Errors when the `CertID` of `cert`
+        /// can't be computed.
+        ///
+        /// The `CertID` of `cert` is computed with the [`Digest`] `D`, see
+        /// [`CertId::from_cert`], and compared with [`CertId::matches`]. Responses whose `CertID`
+        /// uses another hash algorithm are not found.
+        pub fn find_cert<D>(
+            &self,
+            issuer: &Certificate,
+            cert: &Certificate,
+        ) -> Result<Option<&SingleResponse>, Error>
+        where
+            D: Digest + AssociatedOid,
+        {
+            let cert_id = CertId::from_cert::<D>(issuer, cert)?;
+            Ok(self
+                .responses
+                .iter()
+                .find(|response| response.cert_id.matches(&cert_id)))
+        }
+    }
+
+    impl BasicOcspResponse {
+        /// Returns the [`SingleResponse`] for `cert`, if any. See [`ResponseData::find_cert`].
+        pub fn find_cert<D>(
+            &self,
+            issuer: &Certificate,
+            cert: &Certificate,
+        ) -> Result<Option<&SingleResponse>, Error>
+        where
+            D: Digest + AssociatedOid,
+        {
+            self.tbs_response_data.find_cert::<D>(issuer, cert)
+        }
+    }
 }
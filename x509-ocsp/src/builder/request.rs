@@ -1,8 +1,10 @@
 //! OCSP request builder
 
-use crate::{builder::Error, OcspRequest, Request, Signature, TbsRequest, Version};
+use crate::{builder::Error, ext::Nonce, OcspRequest, Request, Signature, TbsRequest, Version};
 use alloc::vec::Vec;
+use const_oid::AssociatedOid;
 use der::Encode;
+use digest::Digest;
 use rand_core::CryptoRngCore;
 use signature::{RandomizedSigner, Signer};
 use spki::{DynSignatureAlgorithmIdentifier, SignatureBitStringEncoding};
@@ -41,6 +43,14 @@ use x509_cert::{
 /// let mut rng = rand::thread_rng();
 ///
 /// let req = OcspRequestBuilder::default()
+///     .with_cert::<Sha1>(&issuer, &cert)
+///     .unwrap()
+///     .with_nonce(&mut rng, 32)
+///     .unwrap()
+///     .build();
+/// assert_eq!(req.tbs_request.nonce().unwrap().0.as_bytes().len(), 32);
+///
+/// let req = OcspRequestBuilder::default()
 ///     .with_request(Request::from_issuer::<Sha1>(&issuer, SerialNumber::from(2usize)).unwrap())
 ///     .with_request(Request::from_issuer::<Sha1>(&issuer, SerialNumber::from(3usize)).unwrap())
 ///     .with_request(Request::from_issuer::<Sha1>(&issuer, SerialNumber::from(4usize)).unwrap())
@@ -91,6 +101,29 @@ impl OcspRequestBuilder {
         self
     }
 
+    /// Adds a [`Request`] for `cert` to the builder, with a `CertID` computed using the
+    /// [`Digest`] `D` as defined in [RFC 6960 Section 4.1.1]. This does not ensure that `cert`
+    /// is actually issued by `issuer`.
+    ///
+    /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+    pub fn with_cert<D>(self, issuer: &Certificate, cert: &Certificate) -> Result<Self, Error>
+    where
+        D: Digest + AssociatedOid,
+    {
+        Ok(self.with_request(Request::from_cert::<D>(issuer, cert)?))
+    }
+
+    /// Adds a random [`Nonce`] extension of `length` bytes as specified in
+    /// [RFC 6960 Section 4.4.1]. Errors when the extension encoding fails.
+    ///
+    /// [RFC 8954] recommends a length of 32 bytes.
+    ///
+    /// [RFC 6960 Section 4.4.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.1
+    /// [RFC 8954]: https://datatracker.ietf.org/doc/html/rfc8954
+    pub fn with_nonce(self, rng: &mut impl CryptoRngCore, length: usize) -> Result<Self, Error> {
+        self.with_extension(Nonce::generate(rng, length)?)
+    }
+
     /// Adds a request extension as specified in [RFC 6960 Section 4.4]. Errors when the
     /// extension encoding fails.
     ///
@@ -78,8 +78,8 @@ pub struct OcspResponseBuilder {
 }
 
 impl OcspResponseBuilder {
-    /// Returns a `OcspResponseBuilder` given the [`Version`], [`ResponderId`], and `Produced
-    /// At` values.
+    /// Returns a `OcspResponseBuilder` given the [`ResponderId`], see
+    /// [`ResponderId::by_name`] and [`ResponderId::by_key`].
     pub fn new(responder_id: impl Into<ResponderId>) -> Self {
         let responder_id = responder_id.into();
         Self {
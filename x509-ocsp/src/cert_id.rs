@@ -1,6 +1,9 @@
 //! X.509 OCSP CertID
 
-use der::{asn1::OctetString, Sequence};
+use der::{
+    asn1::{Any, OctetString},
+    Sequence,
+};
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::serial_number::SerialNumber;
 
@@ -15,6 +18,7 @@ use x509_cert::serial_number::SerialNumber;
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct CertId {
@@ -24,6 +28,24 @@ pub struct CertId {
     pub serial_number: SerialNumber,
 }
 
+impl CertId {
+    /// Does this `CertID` identify the same certificate as `other`?
+    ///
+    /// Unlike `==`, this treats absent and NULL parameters of the hash algorithm as equivalent,
+    /// as both are used in practice for the common hash algorithms.
+    pub fn matches(&self, other: &Self) -> bool {
+        fn params(alg: &AlgorithmIdentifierOwned) -> Option<&Any> {
+            alg.parameters.as_ref().filter(|params| !params.is_null())
+        }
+
+        self.hash_algorithm.oid == other.hash_algorithm.oid
+            && params(&self.hash_algorithm) == params(&other.hash_algorithm)
+            && self.issuer_name_hash == other.issuer_name_hash
+            && self.issuer_key_hash == other.issuer_key_hash
+            && self.serial_number == other.serial_number
+    }
+}
+
 impl From<&CertId> for CertId {
     /// Clones the referenced `CertID`
     fn from(other: &CertId) -> Self {
@@ -40,8 +62,8 @@ mod builder {
         Encode,
     };
     use digest::Digest;
-    use spki::AlgorithmIdentifierOwned;
-    use x509_cert::{serial_number::SerialNumber, Certificate};
+    use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+    use x509_cert::{name::Name, serial_number::SerialNumber, Certificate};
 
     impl CertId {
         /// Generates a `CertID` by running the issuer's subject and key through the specified
@@ -57,40 +79,50 @@ mod builder {
         where
             D: Digest + AssociatedOid,
         {
-            Ok(Self {
-                hash_algorithm: AlgorithmIdentifierOwned {
-                    oid: D::OID,
-                    parameters: Some(Null.into()),
-                },
-                issuer_name_hash: OctetString::new(
-                    D::digest(issuer.tbs_certificate.subject.to_der()?).to_vec(),
-                )?,
-                issuer_key_hash: OctetString::new(
-                    D::digest(
-                        issuer
-                            .tbs_certificate
-                            .subject_public_key_info
-                            .subject_public_key
-                            .raw_bytes(),
-                    )
-                    .to_vec(),
-                )?,
-                serial_number,
-            })
+            let tbs = &issuer.tbs_certificate;
+            Self::new::<D>(&tbs.subject, &tbs.subject_public_key_info, serial_number)
         }
 
-        /// Generates a `CertID` by running the issuer's subject and key through the specified
-        /// [`Digest`] and pulls the serial from `cert`. This does not ensure that `cert` is actually
-        /// issued by `issuer`.
+        /// Generates a `CertID` for `cert` by running the issuer's name and key through the
+        /// specified [`Digest`].
         ///
-        /// [RFC 6960 Section 4.1.1]
+        /// As required by [RFC 6960 Section 4.1.1], the name hash is computed over the DER
+        /// encoding of the `issuer` field of `cert`, rather than the `subject` of `issuer`, which
+        /// may be encoded differently. The key hash is computed over the value of the
+        /// `subjectPublicKey` BIT STRING of `issuer`, excluding its tag, length and number of
+        /// unused bits. This does not ensure that `cert` is actually issued by `issuer`.
         ///
         /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
         pub fn from_cert<D>(issuer: &Certificate, cert: &Certificate) -> Result<Self, Error>
         where
             D: Digest + AssociatedOid,
         {
-            Self::from_issuer::<D>(issuer, cert.tbs_certificate.serial_number.clone())
+            Self::new::<D>(
+                &cert.tbs_certificate.issuer,
+                &issuer.tbs_certificate.subject_public_key_info,
+                cert.tbs_certificate.serial_number.clone(),
+            )
+        }
+
+        fn new<D>(
+            issuer_name: &Name,
+            issuer_key: &SubjectPublicKeyInfoOwned,
+            serial_number: SerialNumber,
+        ) -> Result<Self, Error>
+        where
+            D: Digest + AssociatedOid,
+        {
+            Ok(Self {
+                hash_algorithm: AlgorithmIdentifierOwned {
+                    oid: D::OID,
+                    parameters: Some(Null.into()),
+                },
+                issuer_name_hash: OctetString::new(D::digest(issuer_name.to_der()?).to_vec())?,
+                issuer_key_hash: OctetString::new(
+                    D::digest(issuer_key.subject_public_key.raw_bytes()).to_vec(),
+                )?,
+                serial_number,
+            })
         }
     }
 }
@@ -3,7 +3,7 @@
 use crate::OcspGeneralizedTime;
 use const_oid::AssociatedOid;
 use core::option::Option;
-use der::{asn1::Null, Choice, Decode, Sequence};
+use der::{asn1::Null, Choice, DateTime, Decode, Sequence};
 use x509_cert::{crl::RevokedCert, ext::pkix::CrlReason};
 
 /// CertStatus structure as defined in [RFC 6960 Section 4.2.1].
@@ -16,6 +16,7 @@ use x509_cert::{crl::RevokedCert, ext::pkix::CrlReason};
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum CertStatus {
@@ -50,6 +51,63 @@ impl CertStatus {
     pub fn unknown() -> Self {
         Self::Unknown(Null)
     }
+
+    /// Returns the [`Status`] of the certificate.
+    pub fn status(&self) -> Status {
+        match self {
+            Self::Good(_) => Status::Good,
+            Self::Revoked(info) => Status::Revoked {
+                revocation_time: info.revocation_time.0.to_date_time(),
+                revocation_reason: info.revocation_reason,
+            },
+            Self::Unknown(_) => Status::Unknown,
+        }
+    }
+}
+
+/// Status of a certificate as reported in a [`CertStatus`].
+///
+/// This carries the same information as [`CertStatus`], without the details of its encoding.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The certificate is not revoked
+    Good,
+
+    /// The certificate is revoked, or on hold
+    Revoked {
+        /// Time at which the certificate was revoked or placed on hold
+        revocation_time: DateTime,
+
+        /// Reason of the revocation, if provided by the responder
+        revocation_reason: Option<CrlReason>,
+    },
+
+    /// The responder doesn't know about the certificate
+    Unknown,
+}
+
+impl Status {
+    /// Is the certificate not revoked?
+    pub fn is_good(&self) -> bool {
+        matches!(self, Self::Good)
+    }
+
+    /// Is the certificate revoked, or on hold?
+    pub fn is_revoked(&self) -> bool {
+        matches!(self, Self::Revoked { .. })
+    }
+}
+
+impl From<&CertStatus> for Status {
+    fn from(status: &CertStatus) -> Self {
+        status.status()
+    }
+}
+
+impl From<CertStatus> for Status {
+    fn from(status: CertStatus) -> Self {
+        status.status()
+    }
 }
 
 /// RevokedInfo structure as defined in [RFC 6960 Section 4.2.1].
@@ -61,6 +119,7 @@ impl CertStatus {
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct RevokedInfo {
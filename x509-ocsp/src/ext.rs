@@ -4,18 +4,19 @@ use crate::OcspGeneralizedTime;
 use alloc::vec::Vec;
 use const_oid::{
     db::rfc6960::{
-        ID_PKIX_OCSP_ARCHIVE_CUTOFF, ID_PKIX_OCSP_CRL, ID_PKIX_OCSP_NONCE,
-        ID_PKIX_OCSP_PREF_SIG_ALGS, ID_PKIX_OCSP_RESPONSE, ID_PKIX_OCSP_SERVICE_LOCATOR,
+        ID_PKIX_OCSP_ARCHIVE_CUTOFF, ID_PKIX_OCSP_CRL, ID_PKIX_OCSP_EXTENDED_REVOKE,
+        ID_PKIX_OCSP_NONCE, ID_PKIX_OCSP_PREF_SIG_ALGS, ID_PKIX_OCSP_RESPONSE,
+        ID_PKIX_OCSP_SERVICE_LOCATOR,
     },
     AssociatedOid,
 };
 use der::{
-    asn1::{Ia5String, ObjectIdentifier, OctetString, Uint},
-    Sequence, ValueOrd,
+    asn1::{Ia5String, Null, ObjectIdentifier, OctetString, Uint},
+    Decode, Sequence, ValueOrd,
 };
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::{
-    ext::{pkix::AuthorityInfoAccessSyntax, AsExtension, Extension},
+    ext::{pkix::AuthorityInfoAccessSyntax, AsExtension, Extension, Extensions},
     impl_newtype,
     name::Name,
 };
@@ -34,6 +35,19 @@ macro_rules! impl_extension {
     };
 }
 
+/// Returns the first extension of type `T` in `extensions`, if any. Returns `None` if decoding the
+/// extension fails.
+pub(crate) fn find<'a, T>(extensions: &'a Option<Extensions>) -> Option<T>
+where
+    T: AssociatedOid + Decode<'a>,
+{
+    extensions
+        .as_ref()?
+        .iter()
+        .find(|e| e.extn_id == T::OID)
+        .and_then(|e| T::from_der(e.extn_value.as_bytes()).ok())
+}
+
 /// Nonce extension as defined in [RFC 6960 Section 4.4.1].
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Nonce(pub OctetString);
@@ -48,6 +62,15 @@ impl AssociatedOid for Nonce {
 }
 
 impl Nonce {
+    /// Replaces any `Nonce` in `extensions` with `nonce`.
+    pub(crate) fn set(self, extensions: &mut Option<Extensions>) -> Result<(), der::Error> {
+        let ext = self.to_extension(&Name::default(), &[])?;
+        let extensions = extensions.get_or_insert_with(Vec::new);
+        extensions.retain(|e| e.extn_id != ID_PKIX_OCSP_NONCE);
+        extensions.push(ext);
+        Ok(())
+    }
+
     /// Creates a Nonce object given the bytes
     pub fn new(bytes: impl Into<Vec<u8>>) -> Result<Self, der::Error> {
         Ok(Self(OctetString::new(bytes)?))
@@ -145,6 +168,7 @@ impl AssociatedOid for AcceptableResponses {
 /// ```
 ///
 /// [RFC 6960 Section 4.4.4]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.4
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct ArchiveCutoff(pub OcspGeneralizedTime);
 
 impl_newtype!(ArchiveCutoff, OcspGeneralizedTime);
@@ -211,3 +235,49 @@ pub struct PreferredSignatureAlgorithm {
     pub sig_identifier: AlgorithmIdentifierOwned,
     pub cert_identifier: Option<AlgorithmIdentifierOwned>,
 }
+
+impl PreferredSignatureAlgorithms {
+    /// Returns the first of the preferred signature algorithms which is in `supported`, if any.
+    ///
+    /// As described in [RFC 6960 Section 4.4.7.2], the algorithms are listed in order of
+    /// preference. Only the `sigIdentifier` OIDs are compared.
+    ///
+    /// [RFC 6960 Section 4.4.7.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.7.2
+    pub fn select(&self, supported: &[ObjectIdentifier]) -> Option<&PreferredSignatureAlgorithm> {
+        self.0
+            .iter()
+            .find(|alg| supported.contains(&alg.sig_identifier.oid))
+    }
+}
+
+/// ExtendedRevoke extension as defined in [RFC 6960 Section 4.4.8].
+///
+/// A responder includes this extension in its `responseExtensions` to indicate that it supports
+/// the extended definition of the `revoked` status of [RFC 6960 Section 2.2], i.e. that it may
+/// respond `revoked` for certificates which were never issued.
+///
+/// ```text
+/// id-pkix-ocsp-extended-revoke OBJECT IDENTIFIER ::= {id-pkix-ocsp 9}
+/// ```
+///
+/// The value of the extension is `NULL`.
+///
+/// [RFC 6960 Section 2.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-2.2
+/// [RFC 6960 Section 4.4.8]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.4.8
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedRevoke(pub Null);
+
+impl_newtype!(ExtendedRevoke, Null);
+
+// This extension MUST NOT be marked critical
+impl_extension!(ExtendedRevoke, critical = false);
+
+impl AssociatedOid for ExtendedRevoke {
+    const OID: ObjectIdentifier = ID_PKIX_OCSP_EXTENDED_REVOKE;
+}
+
+impl Default for ExtendedRevoke {
+    fn default() -> Self {
+        Self(Null)
+    }
+}
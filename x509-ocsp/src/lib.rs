@@ -23,9 +23,9 @@ mod time;
 
 pub mod ext;
 
-pub use basic::{BasicOcspResponse, ResponseData, SingleResponse};
+pub use basic::{BasicOcspResponse, ResponseData, SingleResponse, SpannedBasicOcspResponse};
 pub use cert_id::CertId;
-pub use cert_status::{CertStatus, RevokedInfo, UnknownInfo};
+pub use cert_status::{CertStatus, RevokedInfo, Status, UnknownInfo};
 pub use request::{OcspRequest, Request, Signature, TbsRequest};
 pub use responder_id::ResponderId;
 pub use response::{AsResponseBytes, OcspNoCheck, OcspResponse, OcspResponseStatus, ResponseBytes};
@@ -37,6 +37,9 @@ extern crate std;
 #[cfg(feature = "builder")]
 pub mod builder;
 
+#[cfg(feature = "verify")]
+pub mod verify;
+
 use der::Enumerated;
 
 /// OCSP `Version` as defined in [RFC 6960 Section 4.1.1].
@@ -46,6 +49,7 @@ use der::Enumerated;
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Default, Copy, PartialEq, Eq, Enumerated)]
 #[asn1(type = "INTEGER")]
 #[repr(u8)]
@@ -1,6 +1,9 @@
 //! OCSP Request
 
-use crate::{ext::Nonce, CertId, Version};
+use crate::{
+    ext::{self, Nonce, PreferredSignatureAlgorithms},
+    CertId, Version,
+};
 use alloc::vec::Vec;
 use const_oid::db::rfc6960::ID_PKIX_OCSP_NONCE;
 use core::{default::Default, option::Option};
@@ -20,6 +23,7 @@ use x509_cert::{
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct OcspRequest {
@@ -48,6 +52,7 @@ impl OcspRequest {
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct TbsRequest {
@@ -82,6 +87,21 @@ impl TbsRequest {
             None => None,
         }
     }
+
+    /// Returns the request's preferred signature algorithms, if any. This method will return
+    /// `None` if the request has no `PreferredSignatureAlgorithms` extension or decoding of the
+    /// extension fails.
+    pub fn preferred_signature_algorithms(&self) -> Option<PreferredSignatureAlgorithms> {
+        ext::find(&self.request_extensions)
+    }
+
+    /// Sets the request's nonce, replacing any existing `Nonce` extension. Errors when the
+    /// extension encoding fails.
+    ///
+    /// Any signature over the request must be computed afterwards.
+    pub fn set_nonce(&mut self, nonce: Nonce) -> Result<(), der::Error> {
+        nonce.set(&mut self.request_extensions)
+    }
 }
 
 /// Signature structure as defined in [RFC 6960 Section 4.1.1].
@@ -94,6 +114,7 @@ impl TbsRequest {
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct Signature {
@@ -113,6 +134,7 @@ pub struct Signature {
 /// ```
 ///
 /// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct Request {
@@ -182,3 +204,78 @@ mod builder {
         }
     }
 }
+
+#[cfg(feature = "base64")]
+mod base64 {
+    use crate::OcspRequest;
+    use alloc::{string::String, vec::Vec};
+    use base64ct::{Base64, Encoding};
+    use der::{Decode, Encode, Tag};
+
+    impl OcspRequest {
+        /// Returns the URL of an HTTP GET request for this `OCSPRequest` to the responder at
+        /// `responder_url`, as defined in [RFC 6960 Appendix A.1]. Errors when the request
+        /// encoding fails.
+        ///
+        /// The URL is `responder_url`, followed by a `/` if it doesn't end with one, and the
+        /// percent-encoded base64 encoding of the DER encoded request.
+        ///
+        /// [RFC 6960 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc6960#appendix-A.1
+        pub fn to_get_url(&self, responder_url: &str) -> der::Result<String> {
+            let encoded = Base64::encode_string(&self.to_der()?);
+
+            let mut url = String::with_capacity(responder_url.len() + 1 + encoded.len() * 3 / 2);
+            url.push_str(responder_url);
+            if !url.ends_with('/') {
+                url.push('/');
+            }
+
+            for c in encoded.chars() {
+                match c {
+                    '+' => url.push_str("%2B"),
+                    '/' => url.push_str("%2F"),
+                    '=' => url.push_str("%3D"),
+                    c => url.push(c),
+                }
+            }
+
+            Ok(url)
+        }
+
+        /// Decodes an `OCSPRequest` from the path of an HTTP GET request, as defined in
+        /// [RFC 6960 Appendix A.1]. Errors when the path isn't a valid encoding.
+        ///
+        /// `path` is the part of the URL following the responder's base path and its `/`
+        /// separator. Percent-encoded characters are decoded before the base64 encoding, so
+        /// requests from clients which don't percent-encode the `+`, `/` and `=` characters are
+        /// accepted as well.
+        ///
+        /// [RFC 6960 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc6960#appendix-A.1
+        pub fn from_get_path(path: &str) -> der::Result<Self> {
+            let mut encoded = Vec::with_capacity(path.len());
+            let mut rest = path.as_bytes();
+
+            while let Some((&b, tail)) = rest.split_first() {
+                rest = match (b, tail) {
+                    (b'%', [hi, lo, tail @ ..]) => {
+                        let digit = |c: &u8| char::from(*c).to_digit(16);
+                        match (digit(hi), digit(lo)) {
+                            (Some(hi), Some(lo)) => encoded.push(((hi << 4) | lo) as u8),
+                            _ => return Err(Tag::Sequence.value_error()),
+                        }
+                        tail
+                    }
+                    (b'%', _) => return Err(Tag::Sequence.value_error()),
+                    _ => {
+                        encoded.push(b);
+                        tail
+                    }
+                };
+            }
+
+            let der =
+                Base64::decode_in_place(&mut encoded).map_err(|_| Tag::Sequence.value_error())?;
+            Self::from_der(der)
+        }
+    }
+}
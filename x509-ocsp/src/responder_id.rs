@@ -12,6 +12,7 @@ use x509_cert::name::Name;
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum ResponderId {
@@ -34,6 +35,38 @@ impl From<KeyHash> for ResponderId {
     }
 }
 
+#[cfg(feature = "builder")]
+mod builder {
+    use crate::{builder::Error, ResponderId};
+    use der::asn1::OctetString;
+    use digest::Digest;
+    use x509_cert::Certificate;
+
+    impl ResponderId {
+        /// Identifies the responder by the subject of its certificate as defined in
+        /// [RFC 6960 Section 4.2.1].
+        ///
+        /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+        pub fn by_name(responder: &Certificate) -> Self {
+            Self::ByName(responder.tbs_certificate.subject.clone())
+        }
+
+        /// Identifies the responder by the hash of the public key of its certificate, computed
+        /// with the [`Digest`] `D`, as defined in [RFC 6960 Section 4.2.1]. Errors when the hash
+        /// can't be encoded.
+        ///
+        /// RFC 6960 requires `D` to be SHA-1.
+        ///
+        /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+        pub fn by_key<D: Digest>(responder: &Certificate) -> Result<Self, Error> {
+            let key = &responder.tbs_certificate.subject_public_key_info;
+            Ok(Self::ByKey(OctetString::new(
+                D::digest(key.subject_public_key.raw_bytes()).to_vec(),
+            )?))
+        }
+    }
+}
+
 /// KeyHash structure as defined in [RFC 6960 Section 4.2.1].
 ///
 /// ```text
@@ -27,6 +27,7 @@ pub type OcspNoCheck = Null;
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct OcspResponse {
@@ -125,6 +126,7 @@ impl OcspResponse {
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Enumerated, Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 #[allow(missing_docs)]
@@ -146,6 +148,7 @@ pub enum OcspResponseStatus {
 /// ```
 ///
 /// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct ResponseBytes {
@@ -9,6 +9,7 @@ use x509_cert::{impl_newtype, time::Time};
 /// [`GeneralizedTime`] wrapper for easy conversion from legacy `UTCTime`
 ///
 /// OCSP does not support `UTCTime` while many other X.509 structures do.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct OcspGeneralizedTime(pub GeneralizedTime);
 
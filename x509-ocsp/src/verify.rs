@@ -0,0 +1,298 @@
+//! OCSP response verification
+//!
+//! [`BasicOcspResponse::verify_with`] checks a response as described in
+//! [RFC 6960 Section 3.2]:
+//!
+//! - the `ResponderID` identifies the certificate of the responder,
+//! - the signature of the response verifies with the key of that certificate,
+//! - the responder is either the CA which issued the certificates in question,
+//!   or a delegated responder, i.e. a certificate issued by that CA with the
+//!   `id-kp-OCSPSigning` extended key usage, see [RFC 6960 Section 4.2.2.2].
+//!   The signature of a delegated responder certificate is verified with the
+//!   public key of the CA.
+//!
+//! The `CertID`s of the single responses aren't compared to the CA.
+//!
+//! [RFC 6960 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-3.2
+//! [RFC 6960 Section 4.2.2.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.2.2
+
+use crate::{BasicOcspResponse, ResponderId, ResponseData, SpannedBasicOcspResponse};
+use alloc::fmt;
+use const_oid::db::rfc5280::ID_KP_OCSP_SIGNING;
+use der::{asn1::BitString, Encode, Tag};
+use digest::Digest;
+use signature::{SignatureEncoding, Verifier};
+use spki::DecodePublicKey;
+use x509_cert::{ext::pkix::ExtendedKeyUsage, Certificate};
+
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors
+    Asn1(der::Error),
+
+    /// Public key errors
+    PublicKey(spki::Error),
+
+    /// Errors verifying the signature of a delegated responder certificate
+    Certificate(x509_cert::verify::Error),
+
+    /// Verification errors
+    Signature(signature::Error),
+
+    /// The `ResponderID` doesn't identify the responder certificate
+    ResponderIdMismatch,
+
+    /// The responder isn't authorized to sign responses on behalf of the CA
+    UnauthorizedResponder,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            Error::Certificate(err) => write!(f, "responder certificate error: {}", err),
+            Error::Signature(err) => write!(f, "signature error: {}", err),
+            Error::ResponderIdMismatch => {
+                f.write_str("responder ID doesn't match the responder certificate")
+            }
+            Error::UnauthorizedResponder => f.write_str("responder is not authorized by the CA"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(other: der::Error) -> Self {
+        Self::Asn1(other)
+    }
+}
+
+impl From<spki::Error> for Error {
+    fn from(other: spki::Error) -> Self {
+        Self::PublicKey(other)
+    }
+}
+
+impl From<x509_cert::verify::Error> for Error {
+    fn from(other: x509_cert::verify::Error) -> Self {
+        Self::Certificate(other)
+    }
+}
+
+impl From<signature::Error> for Error {
+    fn from(other: signature::Error) -> Self {
+        Self::Signature(other)
+    }
+}
+
+impl ResponderId {
+    /// Does this `ResponderID` identify `responder`?
+    ///
+    /// A `byName` identifier is compared to the subject of the certificate,
+    /// see [`Name::matches`], and a `byKey` identifier to the hash of its
+    /// public key computed with the [`Digest`] `D`. RFC 6960 requires `D` to
+    /// be SHA-1.
+    ///
+    /// [`Name::matches`]: x509_cert::name::Name::matches
+    pub fn matches<D: Digest>(&self, responder: &Certificate) -> bool {
+        let tbs = &responder.tbs_certificate;
+        match self {
+            Self::ByName(name) => tbs.subject == *name || tbs.subject.matches(name),
+            Self::ByKey(hash) => {
+                let key = &tbs.subject_public_key_info.subject_public_key;
+                D::digest(key.raw_bytes()).as_slice() == hash.as_bytes()
+            }
+        }
+    }
+}
+
+impl BasicOcspResponse {
+    /// Verifies the signature of this response with the public key of
+    /// `responder`, decoded as `VK`.
+    ///
+    /// This doesn't check whether `responder` is authorized to sign the
+    /// response, see [`BasicOcspResponse::verify_with`].
+    ///
+    /// The signature is verified over the DER encoding of the
+    /// `ResponseData`. If the response was decoded from an encoding which
+    /// isn't canonical DER, this differs from the signed bytes: use
+    /// [`SpannedBasicOcspResponse::verify_signature_with`] to verify those
+    /// instead.
+    pub fn verify_signature_with<VK, S>(&self, responder: &Certificate) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        verify_signature::<VK, S>(
+            &self.tbs_response_data.to_der()?,
+            &self.signature,
+            responder,
+        )
+    }
+
+    /// Verifies that this response was signed by `responder` on behalf of the
+    /// CA `issuer`.
+    ///
+    /// The `ResponderID` is compared to `responder` with the [`Digest`] `D`,
+    /// see [`ResponderId::matches`], the signature is verified with
+    /// [`BasicOcspResponse::verify_signature_with`], and `responder` must be
+    /// authorized by `issuer`, see [`is_authorized_responder`]. The signature
+    /// of a delegated responder certificate is verified with the public key
+    /// of `issuer`, decoded as `IVK`.
+    pub fn verify_with<VK, S, IVK, IS, D>(
+        &self,
+        responder: &Certificate,
+        issuer: &Certificate,
+    ) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+        IVK: DecodePublicKey + Verifier<IS>,
+        IS: SignatureEncoding,
+        D: Digest,
+    {
+        verify_response::<VK, S, IVK, IS, D>(
+            &self.tbs_response_data,
+            &self.tbs_response_data.to_der()?,
+            &self.signature,
+            responder,
+            issuer,
+        )
+    }
+}
+
+impl SpannedBasicOcspResponse<'_> {
+    /// Verifies the signature of this response with the public key of
+    /// `responder`, decoded as `VK`.
+    ///
+    /// This is the same as [`BasicOcspResponse::verify_signature_with`],
+    /// except that the signature is verified over the original encoding of
+    /// the `ResponseData`.
+    pub fn verify_signature_with<VK, S>(&self, responder: &Certificate) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+    {
+        verify_signature::<VK, S>(self.tbs_response_data_der(), &self.signature, responder)
+    }
+
+    /// Verifies that this response was signed by `responder` on behalf of the
+    /// CA `issuer`.
+    ///
+    /// This is the same as [`BasicOcspResponse::verify_with`], except that
+    /// the signature is verified over the original encoding of the
+    /// `ResponseData`.
+    pub fn verify_with<VK, S, IVK, IS, D>(
+        &self,
+        responder: &Certificate,
+        issuer: &Certificate,
+    ) -> Result<()>
+    where
+        VK: DecodePublicKey + Verifier<S>,
+        S: SignatureEncoding,
+        IVK: DecodePublicKey + Verifier<IS>,
+        IS: SignatureEncoding,
+        D: Digest,
+    {
+        verify_response::<VK, S, IVK, IS, D>(
+            self.tbs_response_data.value(),
+            self.tbs_response_data_der(),
+            &self.signature,
+            responder,
+            issuer,
+        )
+    }
+}
+
+/// Verifies `signature` over `tbs` with the public key of `responder`.
+fn verify_signature<VK, S>(tbs: &[u8], signature: &BitString, responder: &Certificate) -> Result<()>
+where
+    VK: DecodePublicKey + Verifier<S>,
+    S: SignatureEncoding,
+{
+    let key = &responder.tbs_certificate.subject_public_key_info;
+    let verifying_key = VK::from_public_key_der(&key.to_der()?)?;
+
+    // Signatures are a whole number of octets
+    let signature = signature
+        .as_bytes()
+        .ok_or_else(|| Tag::BitString.value_error())?;
+    let signature = S::try_from(signature).map_err(|_| signature::Error::new())?;
+
+    Ok(verifying_key.verify(tbs, &signature)?)
+}
+
+/// Verifies that the `tbs_response_data` encoded as `tbs` was signed by
+/// `responder` on behalf of `issuer`.
+fn verify_response<VK, S, IVK, IS, D>(
+    tbs_response_data: &ResponseData,
+    tbs: &[u8],
+    signature: &BitString,
+    responder: &Certificate,
+    issuer: &Certificate,
+) -> Result<()>
+where
+    VK: DecodePublicKey + Verifier<S>,
+    S: SignatureEncoding,
+    IVK: DecodePublicKey + Verifier<IS>,
+    IS: SignatureEncoding,
+    D: Digest,
+{
+    if !tbs_response_data.responder_id.matches::<D>(responder) {
+        return Err(Error::ResponderIdMismatch);
+    }
+
+    verify_signature::<VK, S>(tbs, signature, responder)?;
+
+    if !is_authorized_responder::<IVK, IS>(responder, issuer)? {
+        return Err(Error::UnauthorizedResponder);
+    }
+
+    Ok(())
+}
+
+/// May `responder` sign responses for the certificates issued by `issuer`?
+///
+/// This is the case if `responder` is `issuer` itself, or if it's a delegated
+/// responder as defined in [RFC 6960 Section 4.2.2.2]: it has the
+/// `id-kp-OCSPSigning` extended key usage, its issuer is the subject of
+/// `issuer`, its `AuthorityKeyIdentifier`, if any, identifies `issuer`, and
+/// its signature verifies with the public key of `issuer`, decoded as `VK`.
+///
+/// [RFC 6960 Section 4.2.2.2]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.2.2
+pub fn is_authorized_responder<VK, S>(responder: &Certificate, issuer: &Certificate) -> Result<bool>
+where
+    VK: DecodePublicKey + Verifier<S>,
+    S: SignatureEncoding,
+{
+    if responder == issuer {
+        return Ok(true);
+    }
+
+    let ocsp_signing = responder
+        .tbs_certificate
+        .get::<ExtendedKeyUsage>()?
+        .is_some_and(|(_, eku)| eku.0.contains(&ID_KP_OCSP_SIGNING));
+
+    if !ocsp_signing
+        || !issuer.is_issuer_name_of(responder)
+        || issuer.is_authority_key_of(responder)? == Some(false)
+    {
+        return Ok(false);
+    }
+
+    let issuer_key = &issuer.tbs_certificate.subject_public_key_info;
+    match responder.verify_signature_with::<VK, S>(issuer_key) {
+        Ok(()) => Ok(true),
+        Err(x509_cert::verify::Error::Signature(_)) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
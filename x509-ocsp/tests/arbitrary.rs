@@ -0,0 +1,31 @@
+//! Tests for the `Arbitrary` implementations
+
+#![cfg(feature = "arbitrary")]
+
+use x509_cert_test_support::arbitrary::round_trip;
+use x509_ocsp::{BasicOcspResponse, CertId, OcspRequest, OcspResponse, ResponderId};
+
+#[test]
+fn arbitrary_cert_id() {
+    round_trip::<CertId>(64);
+}
+
+#[test]
+fn arbitrary_responder_id() {
+    round_trip::<ResponderId>(64);
+}
+
+#[test]
+fn arbitrary_ocsp_request() {
+    round_trip::<OcspRequest>(64);
+}
+
+#[test]
+fn arbitrary_ocsp_response() {
+    round_trip::<OcspResponse>(64);
+}
+
+#[test]
+fn arbitrary_basic_ocsp_response() {
+    round_trip::<BasicOcspResponse>(64);
+}
@@ -106,6 +106,69 @@ fn encode_ocsp_req_sha512_certid() {
     assert_eq!(&req.to_der().unwrap(), &req_der);
 }
 
+#[test]
+fn encode_cert_id_from_cert() {
+    use sha1::Digest;
+
+    let cert_id = CertId::from_cert::<Sha1>(&ISSUER, &CERT).unwrap();
+    assert_eq!(
+        cert_id,
+        CertId::from_issuer::<Sha1>(&ISSUER, CERT.tbs_certificate.serial_number.clone()).unwrap()
+    );
+    assert_eq!(
+        cert_id.issuer_name_hash.as_bytes(),
+        &Sha1::digest(CERT.tbs_certificate.issuer.to_der().unwrap())[..]
+    );
+    assert_eq!(
+        cert_id.issuer_key_hash.as_bytes(),
+        &hex!("5DD72C171C018B2FFA92C3133913689EBD82115C")
+    );
+    assert_eq!(cert_id.serial_number, CERT.tbs_certificate.serial_number);
+
+    // The name hash covers the issuer field of the certificate, which here is a
+    // PrintableString, and not the subject of the issuer re-encoded as a UTF8String
+    let mut issuer = ISSUER.clone();
+    issuer.tbs_certificate.subject = "CN=rsa-2048-sha256-ca".parse().unwrap();
+    assert!(issuer.is_issuer_name_of(&CERT));
+    assert_eq!(CertId::from_cert::<Sha1>(&issuer, &CERT).unwrap(), cert_id);
+    assert_ne!(
+        CertId::from_issuer::<Sha1>(&issuer, CERT.tbs_certificate.serial_number.clone()).unwrap(),
+        cert_id
+    );
+}
+
+#[test]
+fn encode_ocsp_req_cert_with_nonce() {
+    let mut rng = rand::thread_rng();
+    let req = OcspRequestBuilder::default()
+        .with_cert::<Sha256>(&ISSUER, &CERT)
+        .unwrap()
+        .with_nonce(&mut rng, 32)
+        .unwrap()
+        .build();
+
+    let tbs = &req.tbs_request;
+    assert_eq!(
+        tbs.request_list,
+        [Request::from_cert::<Sha256>(&ISSUER, &CERT).unwrap()]
+    );
+    assert_eq!(
+        tbs.request_list[0].req_cert.serial_number,
+        CERT.tbs_certificate.serial_number
+    );
+    let nonce = tbs.nonce().unwrap();
+    assert_eq!(nonce.0.as_bytes().len(), 32);
+
+    let req = OcspRequest::from_der(&req.to_der().unwrap()).unwrap();
+    assert_eq!(req.tbs_request.nonce(), Some(nonce.clone()));
+
+    let other = OcspRequestBuilder::default()
+        .with_nonce(&mut rng, 32)
+        .unwrap()
+        .build();
+    assert_ne!(other.tbs_request.nonce(), Some(nonce));
+}
+
 #[test]
 fn encode_ocsp_req_multiple_extensions() {
     let req_der = std::fs::read("tests/examples/ocsp-multiple-exts-clean-req.der").unwrap();
@@ -406,6 +469,36 @@ fn encode_ocsp_resp_multiple_responses() {
     assert_eq!(&resp.to_der().unwrap(), &resp_der);
 }
 
+#[test]
+fn encode_ocsp_resp_responder_id() {
+    assert_eq!(ResponderId::by_name(&OCSP), *RESPONDER_ID);
+
+    let responder_id = ResponderId::by_key::<Sha1>(&OCSP).unwrap();
+    assert_eq!(
+        responder_id,
+        ResponderId::ByKey(
+            der::asn1::OctetString::new(&hex!("1EAF313B8AAEC86C88A6EF3230ED1D87AF454BF9")[..])
+                .unwrap()
+        )
+    );
+
+    let mut signer = SigningKey::<Sha256>::new(OCSP_KEY.clone());
+    let this_update = OcspGeneralizedTime::from(DateTime::new(2023, 11, 5, 1, 9, 46).unwrap());
+    let resp = OcspResponseBuilder::new(responder_id.clone())
+        .with_single_response(SingleResponse::new(
+            CertId::from_cert::<Sha1>(&ISSUER, &CERT).unwrap(),
+            CertStatus::good(),
+            this_update,
+        ))
+        .sign(&mut signer, Some(vec![OCSP.clone()]), this_update)
+        .unwrap();
+
+    let resp = OcspResponse::from_der(&resp.to_der().unwrap()).unwrap();
+    let basic =
+        BasicOcspResponse::from_der(resp.response_bytes.unwrap().response.as_bytes()).unwrap();
+    assert_eq!(basic.tbs_response_data.responder_id, responder_id);
+}
+
 #[test]
 fn encode_ocsp_resp_revoked_delegated() {
     let resp_der = std::fs::read("tests/examples/rsa-2048-sha256-revoked-ocsp-res.der").unwrap();
@@ -434,3 +527,83 @@ fn encode_ocsp_resp_revoked_delegated() {
         .unwrap();
     assert_eq!(&resp.to_der().unwrap(), &resp_der);
 }
+
+#[test]
+fn decode_ocsp_resp_find_cert() {
+    let resp_der = std::fs::read("tests/examples/rsa-2048-sha256-revoked-ocsp-res.der").unwrap();
+    let resp = OcspResponse::from_der(&resp_der).unwrap();
+    let basic =
+        BasicOcspResponse::from_der(resp.response_bytes.unwrap().response.as_bytes()).unwrap();
+
+    let mut revoked = CERT.clone();
+    revoked.tbs_certificate.serial_number = SerialNumber::from(3usize);
+    let single = basic.find_cert::<Sha1>(&ISSUER, &revoked).unwrap().unwrap();
+    let status = single.status();
+    assert!(status.is_revoked());
+    assert!(!status.is_good());
+    assert_eq!(
+        status,
+        Status::Revoked {
+            revocation_time: DateTime::new(2023, 11, 5, 1, 9, 45).unwrap(),
+            revocation_reason: None,
+        }
+    );
+    assert_eq!(Status::from(&single.cert_status), status);
+
+    // The response doesn't cover other certificates, nor CertIDs using other hash algorithms
+    assert!(basic.find_cert::<Sha1>(&ISSUER, &CERT).unwrap().is_none());
+    assert!(basic
+        .find_cert::<Sha256>(&ISSUER, &revoked)
+        .unwrap()
+        .is_none());
+
+    assert_eq!(CertStatus::good().status(), Status::Good);
+    assert_eq!(CertStatus::unknown().status(), Status::Unknown);
+}
+
+#[test]
+fn encode_ocsp_negotiation_extensions() {
+    let pref_sig_algs = PreferredSignatureAlgorithms::from(vec![PreferredSignatureAlgorithm {
+        sig_identifier: spki::AlgorithmIdentifierOwned {
+            oid: const_oid::db::rfc5912::SHA_256_WITH_RSA_ENCRYPTION,
+            parameters: None,
+        },
+        cert_identifier: None,
+    }]);
+    let req = OcspRequestBuilder::default()
+        .with_cert::<Sha1>(&ISSUER, &CERT)
+        .unwrap()
+        .with_extension(pref_sig_algs.clone())
+        .unwrap()
+        .build();
+    let req = OcspRequest::from_der(&req.to_der().unwrap()).unwrap();
+    assert_eq!(
+        req.tbs_request.preferred_signature_algorithms(),
+        Some(pref_sig_algs)
+    );
+
+    let this_update = OcspGeneralizedTime::from(DateTime::new(2023, 11, 5, 1, 9, 46).unwrap());
+    let builder =
+        OcspResponseBuilder::new(RESPONDER_ID.clone()).with_single_response(SingleResponse::new(
+            CertId::from_cert::<Sha1>(&ISSUER, &CERT).unwrap(),
+            CertStatus::good(),
+            this_update,
+        ));
+    let mut signer = SigningKey::<Sha256>::new(OCSP_KEY.clone());
+    let resp = builder
+        .clone()
+        .sign(&mut signer, None, this_update)
+        .unwrap();
+    let basic =
+        BasicOcspResponse::from_der(resp.response_bytes.unwrap().response.as_bytes()).unwrap();
+    assert!(!basic.tbs_response_data.extended_revoke());
+
+    let resp = builder
+        .with_extension(ExtendedRevoke::default())
+        .unwrap()
+        .sign(&mut signer, None, this_update)
+        .unwrap();
+    let basic =
+        BasicOcspResponse::from_der(resp.response_bytes.unwrap().response.as_bytes()).unwrap();
+    assert!(basic.tbs_response_data.extended_revoke());
+}
@@ -218,3 +218,50 @@ fn as_extension_pref_sig_algs() {
         )[..]
     );
 }
+
+#[test]
+fn pref_sig_algs_select() {
+    const SHA_256_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+        ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+    const ECDSA_WITH_SHA_256: ObjectIdentifier =
+        ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+    const ID_ED_25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+    let alg = |oid| PreferredSignatureAlgorithm {
+        sig_identifier: AlgorithmIdentifierOwned {
+            oid,
+            parameters: None,
+        },
+        cert_identifier: None,
+    };
+    let ext = PreferredSignatureAlgorithms::from(vec![
+        alg(ID_ED_25519),
+        alg(ECDSA_WITH_SHA_256),
+        alg(SHA_256_WITH_RSA_ENCRYPTION),
+    ]);
+
+    assert_eq!(
+        ext.select(&[SHA_256_WITH_RSA_ENCRYPTION, ECDSA_WITH_SHA_256]),
+        Some(&alg(ECDSA_WITH_SHA_256))
+    );
+    assert_eq!(
+        ext.select(&[SHA_256_WITH_RSA_ENCRYPTION]),
+        Some(&alg(SHA_256_WITH_RSA_ENCRYPTION))
+    );
+    assert_eq!(ext.select(&[]), None);
+}
+
+//  0:d=0  hl=2 l=  15 cons: SEQUENCE
+//  2:d=1  hl=2 l=   9 prim: OBJECT            :1.3.6.1.5.5.7.48.1.9
+// 13:d=1  hl=2 l=   2 prim: OCTET STRING      [HEX DUMP]:0500
+#[test]
+fn as_extension_extended_revoke() {
+    let ext = ExtendedRevoke::default();
+    assert_eq!(ext, ExtendedRevoke::from(Null));
+    let ext = ext.to_extension(&Name::default(), &[]).unwrap();
+    assert!(!ext.critical);
+    assert_eq!(
+        &ext.to_der().unwrap(),
+        &hex!("300f06092b060105050730010904020500")[..]
+    );
+}
@@ -314,3 +314,140 @@ fn decode_ocsp_resp_delegated() {
     let reenc = ocsp_resp.to_der().unwrap();
     assert_eq!(reenc, &hex!("30820FA10A0100A0820F9A30820F9606092B060105050730010104820F8730820F83308209F1A2160414ADB0A9B2DDE9D444B4DF80F599598E84AC5EC687180F32303232303230333135303233365A308209C4307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017456A116180F32303231303532303138303635305AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017457A116180F32303231303730373139353830355AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017459A116180F32303231303532303138303634395AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101745AA116180F32303231303132323136303832375AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101745BA116180F32303231303731393138353734355AA0030A0101180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101745CA116180F32303231303132323136303832365AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101745EA116180F32303231303432323132323332335AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017460A116180F32303231303731393138353734335AA0030A0101180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017462A116180F32303231303932393136353734375AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017464A116180F32303231303432323132323332325AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017467A116180F32303231303932393136353734375AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017477A116180F32303231303730313138313934365AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017479A116180F32303232303130353138313532325AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101747AA116180F32303231313130343131353932395AA0030A0101180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101747BA116180F32303231303730313138313934325AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101747CA116180F32303232303130353138313532325AA0030A0103180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101747DA116180F32303231303231303136333032315AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B9202040101747EA116180F32303231313130343131353932325AA0030A0101180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017480A116180F32303231303231303136333032305AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A307B303D300906052B0E03021A050004140F0D5890F551D42ACF5431B7F42A321F7B74A4730414771441A65D9526D01DFF953B628CEAB7B55D3B92020401017481A116180F32303231303132323133353031395AA0030A0104180F32303232303230333030333030305AA011180F32303232303231303031333030305A300D06092A864886F70D01010B050003820101008946FF8FF3009FC7265CB687D56960190379BEF6537FAC541A2306ED709B51AACD79B313331E297495AC4E26ABD94030CC407FDCE26CFC0B7A2BE1F226906182863FF1F140D628FA02B1D0BE1A1697E2F9C17AEF5FEC32AA4D120FAF0F58474AC2718964033076286DC358F5E96B08A5C83323CA4CA2B881D8FFA8E0EA3DB9A90863D378A3349EBF189030DA11891C6695950C5B93CBD440D998E67C0137655AC9DA40826B51211AD5985918336939131A4D1BF9E9A2A88CFB3C5059E3D1CE0D9B19F487EC018C60A0279E24270C7090006A4EC728E5C4A1F86829AFA236AF9979165DF1E7F0F5544217864F9C348ABF2B17C2FCD7DDE35838A99BC4A7256821A0820476308204723082046E30820356A0030201020203358762300D06092A864886F70D01010B0500305D310B300906035504061302555331183016060355040A130F552E532E20476F7665726E6D656E74310C300A060355040B1303446F44310C300A060355040B1303504B49311830160603550403130F444F4420454D41494C2043412D3539301E170D3232303132353030303035315A170D3232303331313030303035315A3064310B300906035504061302555331183016060355040A130F552E532E20476F7665726E6D656E74310C300A060355040B1303446F44310C300A060355040B1303504B49311F301D06035504031316444F44204E4950524E4554204F43535020313244203230820122300D06092A864886F70D01010105000382010F003082010A0282010100BF8A691DDB447562EFCDCB6C4DED76DDB200D1A188E01A3BA4EEEE1C42A7E5E78C0784C512F5CF64DD1A407A2EBC1F280761C1372351E03AED90C2DA0556066398E555EEE91C92988A62CDA1FD1F4E2E5F6EC6C022821C0D7C8B0E90E7CCF203CE8F102CBF4756D9B8CD09A2F16CF08F1E944311C8CD2120B98F6BCAEA1E60D1841A933815C8936BD20CC984FA1F5BF7AFFFFF2886DE53FDF3AE80E8394E09D441AE615693A78FE0051942B724147595DF049382897E26BBEA4E6902376C494F31141D1693005AA4E8AEBE4FEC342D10BFD90661935FB00D61A1FF7CB3B8FF45E473B3FFDD6D2C692FA8C0DE23373F1A6AD84109E7A9274008C5E94E28CE91850203010001A382012E3082012A301F0603551D23041830168014771441A65D9526D01DFF953B628CEAB7B55D3B92304606082B06010505070101043A3038303606082B06010505073002862A687474703A2F2F63726C2E646973612E6D696C2F7369676E2F444F44454D41494C43415F35392E63657230160603551D250101FF040C300A06082B06010505070309300E0603551D0F0101FF040403020780300F06092B060105050730010504020500301D0603551D0E04160414ADB0A9B2DDE9D444B4DF80F599598E84AC5EC68730670603551D200460305E300B0609608648016502010B24300B0609608648016502010B27300B0609608648016502010B2A300B0609608648016502010B3B300C060A6086480165030201030D300C060A60864801650302010311300C060A60864801650302010327300D06092A864886F70D01010B0500038201010047FE1DF6D439EF205C931F34B8C802B6E6D9C4EFEAFA7228FD07E56DFF17EEC18C250941EB46D5F416A02F2706A951A2A16435C589EE1059BB86EC38C7F07C2C6660C4B25C8918115FFD21A0D9798B860F878122B3781DCA004AFE869B4B88E0B8ABB4C3A255BC034B36ED82E4692AB765667E3036FC4BB2A53EF9B4654D5C8FDA62E1F6B9DFC7382FA9A3BEAC5EB35A105CB6FFD7B33D561DA77B886D17F58F9D6EDC722C9786E7C664598116F9ACB41282FB0C75EF6BA87BAE97249A42DF45FEC6AEB724C03D8EE5FF4FD7753AC0FE50CB6B746544D4A4DB977946D29183295B160538338FA2FB27F6BC84B5CBD658A431A8B87A9E20A57753BC8AB964B486")[..])
 }
+
+/// Decodes a response from a buffer which doesn't outlive this function.
+fn decode_owned(path: &str) -> OcspResponse {
+    let data = std::fs::read(path).unwrap();
+    OcspResponse::from_der(&data).unwrap()
+}
+
+#[test]
+fn owned_response_cache() {
+    fn assert_owned<T: Clone + Send + Sync + 'static>() {}
+    assert_owned::<OcspRequest>();
+    assert_owned::<OcspResponse>();
+    assert_owned::<BasicOcspResponse>();
+    assert_owned::<SingleResponse>();
+    assert_owned::<CertId>();
+
+    // Parsed responses can be cached and re-encoded without the original buffer
+    let path = "tests/examples/rsa-2048-sha256-revoked-ocsp-res.der";
+    let cache: std::collections::BTreeMap<&str, OcspResponse> =
+        [(path, decode_owned(path))].into_iter().collect();
+    let basic = BasicOcspResponse::from_der(
+        cache[path]
+            .response_bytes
+            .as_ref()
+            .unwrap()
+            .response
+            .as_bytes(),
+    )
+    .unwrap();
+    drop(cache);
+    assert!(!basic.tbs_response_data.responses.is_empty());
+
+    let data = std::fs::read(path).unwrap();
+    let resp = decode_owned(path);
+    assert_eq!(resp.to_der().unwrap(), data);
+}
+
+#[test]
+fn encoding_invariants() {
+    use der::asn1::{BitString, GeneralizedTime, OctetString};
+    use spki::AlgorithmIdentifierOwned;
+    use x509_cert::serial_number::SerialNumber;
+
+    let cert_id = CertId {
+        hash_algorithm: AlgorithmIdentifierOwned {
+            oid: ObjectIdentifier::new_unwrap("1.3.14.3.2.26"),
+            parameters: Some(Null.into()),
+        },
+        issuer_name_hash: OctetString::new(&[1u8; 20][..]).unwrap(),
+        issuer_key_hash: OctetString::new(&[2u8; 20][..]).unwrap(),
+        serial_number: SerialNumber::from(1u32),
+    };
+    let time =
+        OcspGeneralizedTime(GeneralizedTime::from_unix_duration(Default::default()).unwrap());
+    let signature_algorithm = AlgorithmIdentifierOwned {
+        oid: ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11"),
+        parameters: None,
+    };
+
+    // The default version is omitted, and the optional fields are absent
+    let req = OcspRequest {
+        tbs_request: TbsRequest {
+            version: V1,
+            requestor_name: None,
+            request_list: vec![Request {
+                req_cert: cert_id.clone(),
+                single_request_extensions: None,
+            }],
+            request_extensions: None,
+        },
+        optional_signature: None,
+    };
+    let der = req.to_der().unwrap();
+    let seq = |content: Vec<u8>| {
+        let mut seq = vec![0x30, content.len() as u8];
+        seq.extend_from_slice(&content);
+        seq
+    };
+    let expected = seq(seq(seq(seq(cert_id.to_der().unwrap()))));
+    assert_eq!(der, expected);
+    assert_eq!(OcspRequest::from_der(&der).unwrap(), req);
+
+    // The signature and certificates of a request are [0] EXPLICIT
+    let mut signed = req.clone();
+    signed.optional_signature = Some(Signature {
+        signature_algorithm: signature_algorithm.clone(),
+        signature: BitString::from_bytes(&[3]).unwrap(),
+        certs: Some(vec![]),
+    });
+    let der = signed.to_der().unwrap();
+    let signature_der = signed
+        .optional_signature
+        .as_ref()
+        .unwrap()
+        .to_der()
+        .unwrap();
+    assert!(signature_der.ends_with(&[0xA0, 0x02, 0x30, 0x00]));
+    let mut tagged = vec![0xA0, signature_der.len() as u8];
+    tagged.extend_from_slice(&signature_der);
+    assert!(der.ends_with(&tagged));
+    assert_eq!(OcspRequest::from_der(&der).unwrap(), signed);
+
+    // A response omits the default version, and its certificates are [0] EXPLICIT
+    let data = ResponseData {
+        version: V1,
+        responder_id: ResponderId::ByKey(OctetString::new(&[4u8; 20][..]).unwrap()),
+        produced_at: time,
+        responses: vec![SingleResponse {
+            cert_id,
+            cert_status: CertStatus::good(),
+            this_update: time,
+            next_update: None,
+            single_extensions: None,
+        }],
+        response_extensions: None,
+    };
+    let der = data.to_der().unwrap();
+    assert_eq!(
+        der[2], 0xA2,
+        "responderID byKey follows the omitted version"
+    );
+    assert_eq!(ResponseData::from_der(&der).unwrap(), data);
+
+    let basic = BasicOcspResponse {
+        tbs_response_data: data,
+        signature_algorithm,
+        signature: BitString::from_bytes(&[5]).unwrap(),
+        certs: Some(vec![]),
+    };
+    let der = basic.to_der().unwrap();
+    assert!(der.ends_with(&[0xA0, 0x02, 0x30, 0x00]));
+    assert_eq!(BasicOcspResponse::from_der(&der).unwrap(), basic);
+
+    // The status of a certificate is IMPLICIT tagged
+    assert_eq!(CertStatus::good().to_der().unwrap(), [0x80, 0x00]);
+    assert_eq!(CertStatus::unknown().to_der().unwrap(), [0x82, 0x00]);
+}
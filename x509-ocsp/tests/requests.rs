@@ -442,3 +442,32 @@ fn decode_ocsp_req_signed() {
         None => panic!("no signature"),
     }
 }
+
+#[cfg(feature = "base64")]
+#[test]
+fn ocsp_req_get_url() {
+    const ENCODED: &str = "MEQwQjBAMD4wPDAJBgUrDgMCGgUABBSU1BjIXYAK8xJm8T09jNjNaqW7dAQUXdcsFxwBi\
+                           y%2F6ksMTORNonr2CEVwCAwEAAQ%3D%3D";
+
+    let data = std::fs::read("tests/examples/sha1-certid-ocsp-req.der").unwrap();
+    let ocsp_req = OcspRequest::from_der(&data[..]).unwrap();
+
+    let url = ocsp_req.to_get_url("http://ocsp.example.com").unwrap();
+    assert_eq!(url, format!("http://ocsp.example.com/{}", ENCODED));
+    assert_eq!(
+        ocsp_req.to_get_url("http://ocsp.example.com/").unwrap(),
+        url
+    );
+
+    assert_eq!(OcspRequest::from_get_path(ENCODED).unwrap(), ocsp_req);
+
+    // Clients may use lowercase escapes, or not percent-encode at all
+    let lowercase = ENCODED.replace("%2F", "%2f").replace("%3D", "%3d");
+    assert_eq!(OcspRequest::from_get_path(&lowercase).unwrap(), ocsp_req);
+    let unencoded = ENCODED.replace("%2F", "/").replace("%3D", "=");
+    assert_eq!(OcspRequest::from_get_path(&unencoded).unwrap(), ocsp_req);
+
+    assert!(OcspRequest::from_get_path(&ENCODED[..ENCODED.len() - 1]).is_err());
+    assert!(OcspRequest::from_get_path(&ENCODED.replace("%2F", "%2G")).is_err());
+    assert!(OcspRequest::from_get_path(&ENCODED[4..]).is_err());
+}
@@ -1,7 +1,7 @@
 //! ocsp response decode tests
 
 use der::{
-    asn1::{Null, ObjectIdentifier, OctetString},
+    asn1::{Ia5String, Null, ObjectIdentifier, OctetString, Uint},
     DateTime, Decode, Encode,
 };
 use hex_literal::hex;
@@ -346,6 +346,18 @@ fn decode_ocsp_resp_multiple_extensions() {
         false,
         &crl_refs_ext[..],
     );
+    assert_eq!(
+        single.archive_cutoff(),
+        Some(ext::ArchiveCutoff::from(*TIME))
+    );
+    assert_eq!(
+        single.crl_references(),
+        Some(ext::CrlReferences {
+            crl_url: Some(Ia5String::new("http://127.0.0.1/crl").unwrap()),
+            crl_num: Some(Uint::new(&[1]).unwrap()),
+            crl_time: Some(*TIME),
+        })
+    );
 }
 
 #[test]
@@ -566,3 +578,113 @@ fn decode_ocsp_resp_revoked_response() {
         },
     }
 }
+
+#[test]
+fn decode_ocsp_resp_match_requests() {
+    let data = std::fs::read("tests/examples/ocsp-multiple-requests-nonce-req.der").unwrap();
+    let req = OcspRequest::from_der(&data[..]).unwrap();
+    let data = std::fs::read("tests/examples/ocsp-multiple-responses-res.der").unwrap();
+    let mut res = assert_ocsp_response(&OcspResponse::from_der(&data[..]).unwrap());
+
+    assert_eq!(req.tbs_request.request_list.len(), 8);
+    for (request, response) in res.match_requests(&req) {
+        assert_eq!(response.unwrap().cert_id, request.req_cert);
+    }
+
+    // Absent and NULL hash algorithm parameters are equivalent
+    let mut cert_id = req.tbs_request.request_list[0].req_cert.clone();
+    cert_id.hash_algorithm.parameters = None;
+    assert_ne!(cert_id, req.tbs_request.request_list[0].req_cert);
+    assert!(cert_id.matches(&req.tbs_request.request_list[0].req_cert));
+
+    let removed = res.tbs_response_data.responses.remove(1);
+    {
+        let mut pairs = res.match_requests(&req);
+        assert!(pairs.next().unwrap().1.is_some());
+        let (request, response) = pairs.next().unwrap();
+        assert!(request.req_cert.matches(&removed.cert_id));
+        assert!(response.is_none());
+    }
+
+    // The request has a nonce, which the response lacks
+    let nonce = req.nonce().unwrap();
+    assert!(res.nonce().is_none());
+    assert!(!res.matches_nonce(&req));
+
+    res.tbs_response_data
+        .set_nonce(Nonce::new(&b"other"[..]).unwrap())
+        .unwrap();
+    assert!(!res.matches_nonce(&req));
+
+    res.tbs_response_data.set_nonce(nonce.clone()).unwrap();
+    assert_eq!(res.nonce(), Some(nonce.clone()));
+    assert!(res.matches_nonce(&req));
+    assert_eq!(
+        res.tbs_response_data
+            .response_extensions
+            .as_ref()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Requests without a nonce are matched by any response
+    let mut req = req;
+    req.tbs_request.request_extensions = None;
+    assert!(res.matches_nonce(&req));
+    req.tbs_request.set_nonce(nonce).unwrap();
+    assert_eq!(req.tbs_request.request_extensions.unwrap().len(), 1);
+}
+
+#[test]
+fn single_response_freshness() {
+    use core::time::Duration;
+
+    let at = |hour, minute| {
+        OcspGeneralizedTime::from(DateTime::new(2020, 1, 1, hour, minute, 0).unwrap())
+    };
+    let mut single = SingleResponse {
+        cert_id: CertId {
+            hash_algorithm: AlgorithmIdentifierOwned {
+                oid: ID_SHA1,
+                parameters: Some(Null.into()),
+            },
+            issuer_name_hash: OctetString::new(&[0u8; 20][..]).unwrap(),
+            issuer_key_hash: OctetString::new(&[0u8; 20][..]).unwrap(),
+            serial_number: SerialNumber::from(1u32),
+        },
+        cert_status: CertStatus::good(),
+        this_update: at(12, 0),
+        next_update: Some(at(13, 0)),
+        single_extensions: None,
+    };
+    let hour = Duration::from_secs(3600);
+    let skew = Duration::from_secs(300);
+
+    assert!(single.is_fresh_at(at(12, 0), hour, Duration::ZERO));
+    assert!(single.is_fresh_at(at(13, 0), hour, Duration::ZERO));
+    assert!(single.is_fresh_at(at(12, 30), 2 * hour, skew));
+
+    // thisUpdate in the future
+    assert!(!single.is_fresh_at(at(11, 59), hour, Duration::ZERO));
+    assert!(single.is_fresh_at(at(11, 59), hour, skew));
+    assert!(!single.is_fresh_at(at(11, 54), hour, skew));
+
+    // Older than the maximum age
+    assert!(!single.is_fresh_at(at(12, 31), Duration::from_secs(1800), Duration::ZERO));
+    assert!(single.is_fresh_at(at(12, 31), Duration::from_secs(1800), skew));
+
+    // After nextUpdate
+    assert!(!single.is_fresh_at(at(13, 1), 2 * hour, Duration::ZERO));
+    assert!(single.is_fresh_at(at(13, 1), 2 * hour, skew));
+    assert!(!single.is_fresh_at(at(13, 6), 2 * hour, skew));
+
+    // Without nextUpdate, only the maximum age applies
+    single.next_update = None;
+    assert!(single.is_fresh_at(at(13, 59), 2 * hour, Duration::ZERO));
+    assert!(!single.is_fresh_at(at(14, 1), 2 * hour, Duration::ZERO));
+    assert!(!single.is_fresh_at(at(11, 59), 2 * hour, Duration::ZERO));
+
+    #[cfg(feature = "std")]
+    assert!(!single.is_currently_fresh(2 * hour, skew));
+}
@@ -0,0 +1,191 @@
+//! OCSP response verification tests
+
+#![cfg(all(feature = "builder", feature = "verify"))]
+
+use der::{asn1::BitString, DateTime, Decode, Encode};
+use lazy_static::lazy_static;
+use rsa::{
+    pkcs1v15::{Signature, SigningKey, VerifyingKey},
+    pkcs8::DecodePrivateKey,
+    signature::{SignatureEncoding, Signer},
+    RsaPrivateKey,
+};
+use sha1::Sha1;
+use sha2::Sha256;
+use x509_cert::Certificate;
+use x509_ocsp::builder::OcspResponseBuilder;
+use x509_ocsp::verify::{is_authorized_responder, Error};
+use x509_ocsp::*;
+
+lazy_static! {
+    static ref ISSUER: Certificate =
+        Certificate::from_der(&std::fs::read("tests/examples/rsa-2048-sha256-ca.der").unwrap())
+            .unwrap();
+    static ref ISSUER_KEY: RsaPrivateKey = RsaPrivateKey::from_pkcs8_der(
+        &std::fs::read("tests/examples/rsa-2048-sha256-ca-key.der").unwrap()
+    )
+    .unwrap();
+    static ref CERT: Certificate =
+        Certificate::from_der(&std::fs::read("tests/examples/rsa-2048-sha256-crt.der").unwrap())
+            .unwrap();
+    static ref CERT_KEY: RsaPrivateKey = RsaPrivateKey::from_pkcs8_der(
+        &std::fs::read("tests/examples/rsa-2048-sha256-crt-key.der").unwrap()
+    )
+    .unwrap();
+    static ref OCSP: Certificate = Certificate::from_der(
+        &std::fs::read("tests/examples/rsa-2048-sha256-ocsp-crt.der").unwrap()
+    )
+    .unwrap();
+}
+
+type Verifier = VerifyingKey<Sha256>;
+
+fn decode_basic(der: &[u8]) -> BasicOcspResponse {
+    let resp = OcspResponse::from_der(der).unwrap();
+    BasicOcspResponse::from_der(resp.response_bytes.unwrap().response.as_bytes()).unwrap()
+}
+
+#[test]
+fn verify_delegated_responder() {
+    let basic = decode_basic(
+        &std::fs::read("tests/examples/rsa-2048-sha256-revoked-ocsp-res.der").unwrap(),
+    );
+
+    assert!(basic.tbs_response_data.responder_id.matches::<Sha1>(&OCSP));
+    assert!(!basic
+        .tbs_response_data
+        .responder_id
+        .matches::<Sha1>(&ISSUER));
+    assert!(ResponderId::by_key::<Sha1>(&OCSP)
+        .unwrap()
+        .matches::<Sha1>(&OCSP));
+    assert!(!ResponderId::by_key::<Sha1>(&OCSP)
+        .unwrap()
+        .matches::<Sha1>(&CERT));
+
+    assert!(is_authorized_responder::<Verifier, Signature>(&OCSP, &ISSUER).unwrap());
+    assert!(is_authorized_responder::<Verifier, Signature>(&ISSUER, &ISSUER).unwrap());
+    // The end-entity certificate lacks the id-kp-OCSPSigning extended key usage
+    assert!(!is_authorized_responder::<Verifier, Signature>(&CERT, &ISSUER).unwrap());
+    // The delegated responder wasn't issued by the end-entity certificate
+    assert!(!is_authorized_responder::<Verifier, Signature>(&OCSP, &CERT).unwrap());
+
+    basic
+        .verify_signature_with::<Verifier, Signature>(&OCSP)
+        .unwrap();
+    basic
+        .verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&OCSP, &ISSUER)
+        .unwrap();
+
+    assert!(matches!(
+        basic.verify_signature_with::<Verifier, Signature>(&ISSUER),
+        Err(Error::Signature(_))
+    ));
+    assert!(matches!(
+        basic.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&ISSUER, &ISSUER),
+        Err(Error::ResponderIdMismatch)
+    ));
+    assert!(matches!(
+        basic.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&OCSP, &CERT),
+        Err(Error::UnauthorizedResponder)
+    ));
+
+    let mut tampered = basic.clone();
+    let mut signature = basic.signature.raw_bytes().to_vec();
+    signature[0] ^= 1;
+    tampered.signature = BitString::from_bytes(&signature).unwrap();
+    assert!(matches!(
+        tampered.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&OCSP, &ISSUER),
+        Err(Error::Signature(_))
+    ));
+
+    // Signatures with unused bits are rejected
+    let mut tampered = basic.clone();
+    tampered.signature = BitString::new(1, basic.signature.raw_bytes()).unwrap();
+    assert!(matches!(
+        tampered.verify_signature_with::<Verifier, Signature>(&OCSP),
+        Err(Error::Asn1(_))
+    ));
+}
+
+#[test]
+fn verify_spanned_response() {
+    let der = std::fs::read("tests/examples/rsa-2048-sha256-revoked-ocsp-res.der").unwrap();
+    let resp = OcspResponse::from_der(&der).unwrap();
+    let der = resp.response_bytes.unwrap().response;
+    let spanned = SpannedBasicOcspResponse::from_der(der.as_bytes()).unwrap();
+
+    spanned
+        .verify_signature_with::<Verifier, Signature>(&OCSP)
+        .unwrap();
+    spanned
+        .verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&OCSP, &ISSUER)
+        .unwrap();
+    assert!(matches!(
+        spanned.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&ISSUER, &ISSUER),
+        Err(Error::ResponderIdMismatch)
+    ));
+
+    let basic = BasicOcspResponse::from(spanned.clone());
+    assert_eq!(
+        basic.tbs_response_data.to_der().unwrap(),
+        spanned.tbs_response_data_der()
+    );
+    assert_eq!(basic.to_der().unwrap(), der.as_bytes());
+}
+
+#[test]
+fn verify_issuer_responder() {
+    let mut signer = SigningKey::<Sha256>::new(ISSUER_KEY.clone());
+    let this_update = OcspGeneralizedTime::from(DateTime::new(2023, 11, 5, 1, 9, 46).unwrap());
+    let resp = OcspResponseBuilder::new(ResponderId::by_key::<Sha1>(&ISSUER).unwrap())
+        .with_single_response(SingleResponse::new(
+            CertId::from_cert::<Sha1>(&ISSUER, &CERT).unwrap(),
+            CertStatus::good(),
+            this_update,
+        ))
+        .sign(&mut signer, None, this_update)
+        .unwrap();
+    let basic = decode_basic(&resp.to_der().unwrap());
+
+    basic
+        .verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&ISSUER, &ISSUER)
+        .unwrap();
+    assert!(matches!(
+        basic.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&OCSP, &ISSUER),
+        Err(Error::ResponderIdMismatch)
+    ));
+}
+
+#[test]
+fn verify_forged_responder() {
+    // A copy of the delegated responder certificate with the name of the CA
+    // and another key, self-signed with that key
+    let mut forged = OCSP.clone();
+    forged.tbs_certificate.subject = ISSUER.tbs_certificate.subject.clone();
+    forged.tbs_certificate.subject_public_key_info =
+        CERT.tbs_certificate.subject_public_key_info.clone();
+    let mut signer = SigningKey::<Sha256>::new(CERT_KEY.clone());
+    let signature: Signature = signer.sign(&forged.tbs_certificate.to_der().unwrap());
+    forged.signature = BitString::from_bytes(&signature.to_bytes()).unwrap();
+    assert!(!is_authorized_responder::<Verifier, Signature>(&forged, &ISSUER).unwrap());
+
+    let this_update = OcspGeneralizedTime::from(DateTime::new(2023, 11, 5, 1, 9, 46).unwrap());
+    let resp = OcspResponseBuilder::new(ResponderId::by_key::<Sha1>(&forged).unwrap())
+        .with_single_response(SingleResponse::new(
+            CertId::from_cert::<Sha1>(&ISSUER, &CERT).unwrap(),
+            CertStatus::good(),
+            this_update,
+        ))
+        .sign(&mut signer, None, this_update)
+        .unwrap();
+    let basic = decode_basic(&resp.to_der().unwrap());
+
+    basic
+        .verify_signature_with::<Verifier, Signature>(&forged)
+        .unwrap();
+    assert!(matches!(
+        basic.verify_with::<Verifier, Signature, Verifier, Signature, Sha1>(&forged, &ISSUER),
+        Err(Error::UnauthorizedResponder)
+    ));
+}